@@ -53,12 +53,23 @@ pub struct ActionContext {
     /// Additional arguments passed after `--` to passthrough.
     pub passthrough_args: Vec<String>,
 
+    /// Group to register spawned child processes under, so that they can be
+    /// terminated separately from other pipelines (via `moon watch`).
+    pub process_group: Option<String>,
+
     /// Targets to run after the initial locators have been resolved.
     pub primary_targets: FxHashSet<Target>,
 
     /// The type of profiler to run tasks with.
     pub profile: Option<ProfileType>,
 
+    /// Known flaky targets, whose failures will be reported as warnings
+    /// instead of failing the pipeline (via `pipeline.quarantineFlaky`).
+    pub quarantined_targets: FxHashSet<Target>,
+
+    /// Trace file access of all tasks, regardless of their `sandbox` option.
+    pub sandbox: bool,
+
     /// The current state of running tasks (via their target).
     /// @mutable
     pub target_states: scc::HashMap<Target, TargetState>,
@@ -2,6 +2,7 @@ use crate::action_graph_error::ActionGraphError;
 use graph_cycles::Cycles;
 use moon_action::ActionNode;
 use moon_common::is_test_env;
+use moon_graph_utils::{format_graphml, format_mermaid, format_svg};
 use petgraph::dot::{Config, Dot};
 use petgraph::prelude::*;
 use petgraph::visit::{IntoEdgeReferences, IntoNodeReferences};
@@ -130,6 +131,18 @@ impl ActionGraph {
         }
     }
 
+    pub fn to_graphml(&self) -> String {
+        format_graphml(&self.labeled_graph())
+    }
+
+    pub fn to_mermaid(&self) -> String {
+        format_mermaid(&self.labeled_graph())
+    }
+
+    pub fn to_svg(&self) -> String {
+        format_svg(&self.labeled_graph())
+    }
+
     pub fn to_dot(&self) -> String {
         type DotGraph = DiGraph<String, ()>;
 
@@ -0,0 +1,138 @@
+use crate::action_graph::ActionGraph;
+use moon_action::{ActionNode, RunTaskNode};
+use moon_task::Target;
+use petgraph::prelude::*;
+use petgraph::unionfind::UnionFind;
+use rustc_hash::FxHashMap;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use tracing::debug;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionGraphShard {
+    /// Index of the shard, starting from 1.
+    pub index: usize,
+
+    /// Tasks to run within the shard.
+    pub targets: Vec<Target>,
+
+    /// Cumulative weight of all tasks within the shard.
+    pub weight: u64,
+}
+
+struct ShardItem {
+    targets: BTreeSet<Target>,
+    weight: u64,
+}
+
+impl ActionGraph {
+    /// Partition all tasks in the graph into the provided number of shards,
+    /// balanced by the weight of each task. Tasks that depend on each other
+    /// are kept within the same shard, unless doing so would exceed
+    /// the weight of an evenly balanced shard.
+    pub fn partition<W>(&self, total: usize, weigh: W) -> Vec<ActionGraphShard>
+    where
+        W: Fn(&RunTaskNode) -> u64,
+    {
+        let graph = self.get_inner_graph();
+        let total = total.max(1);
+
+        // Group tasks that depend on each other
+        let mut groups = UnionFind::<usize>::new(graph.node_count());
+
+        for edge in graph.edge_references() {
+            if is_task(graph.node_weight(edge.source()))
+                && is_task(graph.node_weight(edge.target()))
+            {
+                groups.union(edge.source().index(), edge.target().index());
+            }
+        }
+
+        let mut components: FxHashMap<usize, Vec<(&RunTaskNode, u64)>> = FxHashMap::default();
+
+        for index in graph.node_indices() {
+            if let Some(ActionNode::RunTask(inner)) = graph.node_weight(index) {
+                components
+                    .entry(groups.find(index.index()))
+                    .or_default()
+                    .push((inner, weigh(inner).max(1)));
+            }
+        }
+
+        let total_weight = components
+            .values()
+            .flatten()
+            .map(|(_, weight)| weight)
+            .sum::<u64>();
+        let max_weight = total_weight.div_ceil(total as u64);
+        let mut items = vec![];
+
+        for tasks in components.into_values() {
+            let weight = tasks.iter().map(|(_, weight)| weight).sum::<u64>();
+
+            // Too large for a single shard, so split the tasks apart,
+            // and rely on the remote cache to share their outputs
+            if weight > max_weight {
+                for (task, weight) in tasks {
+                    items.push(ShardItem {
+                        targets: BTreeSet::from_iter([task.target.clone()]),
+                        weight,
+                    });
+                }
+            } else {
+                items.push(ShardItem {
+                    targets: tasks
+                        .into_iter()
+                        .map(|(task, _)| task.target.clone())
+                        .collect(),
+                    weight,
+                });
+            }
+        }
+
+        // Assign the heaviest items first to the lightest shard
+        items.sort_by(|a, d| {
+            d.weight
+                .cmp(&a.weight)
+                .then_with(|| a.targets.first().cmp(&d.targets.first()))
+        });
+
+        let mut shards = (1..=total)
+            .map(|index| ActionGraphShard {
+                index,
+                ..ActionGraphShard::default()
+            })
+            .collect::<Vec<_>>();
+
+        for item in items {
+            let shard = shards
+                .iter_mut()
+                .min_by_key(|shard| shard.weight)
+                .expect("At least 1 shard!");
+
+            shard.weight += item.weight;
+            shard.targets.extend(item.targets);
+        }
+
+        for shard in &mut shards {
+            shard.targets.sort();
+            shard.targets.dedup();
+        }
+
+        debug!(
+            shards = ?shards
+                .iter()
+                .map(|shard| (shard.targets.len(), shard.weight))
+                .collect::<Vec<_>>(),
+            "Partitioned action graph into {} shards",
+            total
+        );
+
+        shards
+    }
+}
+
+fn is_task(node: Option<&ActionNode>) -> bool {
+    matches!(node, Some(ActionNode::RunTask(_)))
+}
@@ -1,7 +1,9 @@
 mod action_graph;
 mod action_graph_builder;
 mod action_graph_error;
+mod action_graph_partition;
 
 pub use action_graph::*;
 pub use action_graph_builder::*;
 pub use action_graph_error::*;
+pub use action_graph_partition::*;
@@ -0,0 +1,174 @@
+use moon_action::{ActionNode, RunTaskNode};
+use moon_action_graph::{ActionGraph, ActionGraphShard, ActionGraphType};
+use moon_task::Target;
+use moon_toolchain::Runtime;
+use rustc_hash::FxHashMap;
+
+fn create_task(target: &str) -> ActionNode {
+    ActionNode::run_task(RunTaskNode::new(
+        Target::parse(target).unwrap(),
+        Runtime::system(),
+    ))
+}
+
+fn create_graph<const N: usize>(targets: [&str; N], deps: &[(usize, usize)]) -> ActionGraph {
+    let mut graph = ActionGraphType::default();
+    let sync = graph.add_node(ActionNode::sync_workspace());
+
+    let indices = targets
+        .iter()
+        .map(|target| {
+            let index = graph.add_node(create_task(target));
+            graph.add_edge(index, sync, ());
+            index
+        })
+        .collect::<Vec<_>>();
+
+    for (from, to) in deps {
+        graph.add_edge(indices[*from], indices[*to], ());
+    }
+
+    ActionGraph::new(graph)
+}
+
+fn create_weigher(weights: &[(&str, u64)]) -> impl Fn(&RunTaskNode) -> u64 {
+    let weights = weights
+        .iter()
+        .map(|(target, weight)| (target.to_string(), *weight))
+        .collect::<FxHashMap<_, _>>();
+
+    move |node| weights.get(node.target.as_str()).copied().unwrap_or(1)
+}
+
+fn get_targets(shard: &ActionGraphShard) -> Vec<&str> {
+    shard.targets.iter().map(|target| target.as_str()).collect()
+}
+
+mod action_graph_partition {
+    use super::*;
+
+    #[test]
+    fn creates_empty_shards() {
+        let graph = create_graph([], &[]);
+
+        let shards = graph.partition(3, |_| 1);
+
+        assert_eq!(shards.len(), 3);
+        assert_eq!(shards[0].index, 1);
+        assert_eq!(shards[2].index, 3);
+        assert!(shards.iter().all(|shard| shard.targets.is_empty()));
+    }
+
+    #[test]
+    fn treats_zero_total_as_one() {
+        let graph = create_graph(["a:build", "b:build"], &[]);
+
+        let shards = graph.partition(0, |_| 1);
+
+        assert_eq!(shards.len(), 1);
+        assert_eq!(get_targets(&shards[0]), vec!["a:build", "b:build"]);
+    }
+
+    #[test]
+    fn balances_by_weight() {
+        let graph = create_graph(["a:build", "b:build", "c:build", "d:build"], &[]);
+
+        let shards = graph.partition(
+            2,
+            create_weigher(&[
+                ("a:build", 10),
+                ("b:build", 6),
+                ("c:build", 3),
+                ("d:build", 2),
+            ]),
+        );
+
+        assert_eq!(get_targets(&shards[0]), vec!["a:build"]);
+        assert_eq!(shards[0].weight, 10);
+        assert_eq!(
+            get_targets(&shards[1]),
+            vec!["b:build", "c:build", "d:build"]
+        );
+        assert_eq!(shards[1].weight, 11);
+    }
+
+    #[test]
+    fn keeps_dependencies_with_dependents() {
+        let graph = create_graph(
+            ["a:build", "a:test", "b:build", "b:test"],
+            &[(1, 0), (3, 2)],
+        );
+
+        let shards = graph.partition(2, |_| 1);
+
+        assert_eq!(get_targets(&shards[0]), vec!["a:build", "a:test"]);
+        assert_eq!(get_targets(&shards[1]), vec!["b:build", "b:test"]);
+    }
+
+    #[test]
+    fn doesnt_group_through_non_task_nodes() {
+        // All tasks depend on the sync workspace node
+        let graph = create_graph(["a:build", "b:build"], &[]);
+
+        let shards = graph.partition(2, |_| 1);
+
+        assert_eq!(get_targets(&shards[0]), vec!["a:build"]);
+        assert_eq!(get_targets(&shards[1]), vec!["b:build"]);
+    }
+
+    #[test]
+    fn splits_groups_larger_than_a_shard() {
+        let graph = create_graph(
+            ["a:build", "a:test", "a:lint", "b:build"],
+            &[(1, 0), (2, 0)],
+        );
+
+        let shards = graph.partition(
+            2,
+            create_weigher(&[("a:build", 4), ("a:test", 4), ("a:lint", 4), ("b:build", 4)]),
+        );
+
+        assert_eq!(shards[0].weight, 8);
+        assert_eq!(shards[1].weight, 8);
+        assert_eq!(
+            shards
+                .iter()
+                .map(|shard| shard.targets.len())
+                .sum::<usize>(),
+            4
+        );
+    }
+
+    #[test]
+    fn uses_a_minimum_weight_of_one() {
+        let graph = create_graph(["a:build", "b:build"], &[]);
+
+        let shards = graph.partition(2, |_| 0);
+
+        assert_eq!(shards[0].weight, 1);
+        assert_eq!(shards[1].weight, 1);
+    }
+
+    #[test]
+    fn dedupes_targets() {
+        let mut graph = ActionGraphType::default();
+        graph.add_node(create_task("a:build"));
+        graph.add_node(create_task("a:build"));
+
+        let shards = ActionGraph::new(graph).partition(1, |_| 1);
+
+        assert_eq!(get_targets(&shards[0]), vec!["a:build"]);
+        assert_eq!(shards[0].weight, 2);
+    }
+
+    #[test]
+    fn is_deterministic() {
+        let graph = create_graph(["c:build", "a:build", "b:build", "d:build"], &[]);
+
+        let first = graph.partition(2, |_| 1);
+
+        for _ in 0..5 {
+            assert_eq!(graph.partition(2, |_| 1), first);
+        }
+    }
+}
@@ -1,23 +1,33 @@
+use crate::duration_history::DurationHistoryState;
 use crate::event_emitter::{Event, EventEmitter};
+use crate::flaky_history::FlakyHistoryState;
 use crate::job::Job;
 use crate::job_context::JobContext;
 use crate::job_dispatcher::JobDispatcher;
+use crate::reports::critical_path::{analyze_critical_path, extract_dependencies};
+use crate::resource_pool::ResourcePool;
 use crate::subscribers::cleanup_subscriber::CleanupSubscriber;
 use crate::subscribers::console_subscriber::ConsoleSubscriber;
 use crate::subscribers::remote_subscriber::RemoteSubscriber;
 use crate::subscribers::reports_subscriber::ReportsSubscriber;
+use crate::subscribers::trace_subscriber::TraceSubscriber;
 use crate::subscribers::webhooks_subscriber::WebhooksSubscriber;
 use miette::IntoDiagnostic;
 use moon_action::{Action, ActionNode, ActionPipelineStatus};
 use moon_action_context::{ActionContext, TargetState};
 use moon_action_graph::ActionGraph;
 use moon_app_context::AppContext;
+use moon_cache_item::CacheItem;
 use moon_common::{color, is_ci, is_test_env};
 use moon_process::{ProcessRegistry, SignalType};
 use moon_toolchain_plugin::ToolchainRegistry;
 use moon_workspace_graph::WorkspaceGraph;
 use rustc_hash::{FxHashMap, FxHashSet};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::cmp::Reverse;
 use std::mem;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::{RwLock, Semaphore, mpsc};
@@ -30,10 +40,13 @@ pub struct ActionPipeline {
     pub concurrency: usize,
     pub report_name: String,
     pub summarize: bool,
+    pub trace_endpoint: Option<String>,
+    pub trace_file: Option<PathBuf>,
 
     // State
     actions: Vec<Action>,
     duration: Option<Duration>,
+    duration_history: DurationHistoryState,
     status: ActionPipelineStatus,
 
     // Data
@@ -59,11 +72,14 @@ impl ActionPipeline {
             bail: false,
             concurrency: num_cpus::get(),
             duration: None,
+            duration_history: DurationHistoryState::default(),
             emitter: Arc::new(EventEmitter::default()),
             report_name: "runReport.json".into(),
             status: ActionPipelineStatus::Pending,
             summarize: false,
             toolchain_registry,
+            trace_endpoint: None,
+            trace_file: None,
             workspace_graph,
         }
     }
@@ -77,8 +93,29 @@ impl ActionPipeline {
     pub async fn run_with_context(
         mut self,
         action_graph: ActionGraph,
-        action_context: ActionContext,
+        mut action_context: ActionContext,
     ) -> miette::Result<Vec<Action>> {
+        // Load the flakiness of previous runs, so that known flaky tasks can be quarantined
+        let mut flaky_history =
+            self.load_history::<FlakyHistoryState>("flakyHistory.json", "flaky task history");
+
+        let pipeline_config = &self.app_context.workspace_config.pipeline;
+
+        if pipeline_config.quarantine_flaky {
+            for node in action_graph.get_nodes() {
+                if let ActionNode::RunTask(inner) = node {
+                    if flaky_history.data.is_flaky(
+                        inner.target.as_str(),
+                        f32::from(pipeline_config.quarantine_flaky_threshold) / 100.0,
+                    ) {
+                        action_context
+                            .quarantined_targets
+                            .insert(inner.target.clone());
+                    }
+                }
+            }
+        }
+
         self.action_context = Arc::new(action_context);
         self.setup_subscribers().await;
 
@@ -90,16 +127,44 @@ impl ActionPipeline {
             })
             .await?;
 
+        // Load durations of previous runs, so that long-running tasks are started first
+        let mut duration_history = self.load_history::<DurationHistoryState>(
+            "durationHistory.json",
+            "action duration history",
+        );
+
+        self.duration_history = mem::take(&mut duration_history.data);
+
         // Run the pipeline based on the graph
+        let dependencies = extract_dependencies(&action_graph);
         let result = self.internal_run(action_graph).await;
         let actions = mem::take(&mut self.actions);
 
+        duration_history.data = mem::take(&mut self.duration_history);
+        duration_history.data.record(&actions);
+
+        flaky_history.data.record(&actions);
+
+        // Failing to persist the histories shouldn't mask the pipeline result
+        if let Err(error) = duration_history.save() {
+            warn!("Failed to save action duration history: {error}");
+        }
+
+        if let Err(error) = flaky_history.save() {
+            warn!("Failed to save flaky task history: {error}");
+        }
+
+        let analysis = self.duration.as_ref().map(|duration| {
+            analyze_critical_path(&actions, &dependencies, self.concurrency, duration)
+        });
+
         // Handle the result of the pipeline
         match result {
             Ok(_) => {
                 self.emitter
                     .emit(Event::PipelineCompleted {
                         actions: &actions,
+                        analysis: analysis.as_ref(),
                         context: &self.action_context,
                         duration: self.duration,
                         error: None,
@@ -114,6 +179,7 @@ impl ActionPipeline {
                 self.emitter
                     .emit(Event::PipelineCompleted {
                         actions: &actions,
+                        analysis: analysis.as_ref(),
                         context: &self.action_context,
                         duration: self.duration,
                         error: Some(error.to_string()),
@@ -150,6 +216,10 @@ impl ActionPipeline {
             completed_jobs: Arc::new(RwLock::new(FxHashSet::default())),
             emitter: Arc::clone(&self.emitter),
             result_sender: sender,
+            resources: Arc::new(RwLock::new(ResourcePool::new(
+                self.concurrency,
+                self.app_context.workspace_config.pipeline.resources.clone(),
+            ))),
             semaphore: Arc::new(Semaphore::new(self.concurrency)),
             running_jobs: Arc::new(RwLock::new(FxHashMap::default())),
             toolchain_registry: Arc::clone(&self.toolchain_registry),
@@ -241,7 +311,22 @@ impl ActionPipeline {
     ) -> miette::Result<JoinHandle<JoinSet<()>>> {
         let node_indices = action_graph.sort_topological()?;
         let node_count = node_indices.len();
-        let priority_groups = action_graph.group_priorities(node_indices);
+        let mut priority_groups = action_graph.group_priorities(node_indices);
+
+        // Within each priority, start the longest running tasks first (based on
+        // previous runs), so that they don't become the long pole of the pipeline.
+        // Indices without a history retain their topological order.
+        for indices in priority_groups.values_mut() {
+            indices.sort_by_cached_key(|index| {
+                Reverse(
+                    action_graph
+                        .get_node_from_index(index)
+                        .and_then(|node| self.duration_history.estimate(node))
+                        .unwrap_or_default(),
+                )
+            });
+        }
+
         let app_context = Arc::clone(&self.app_context);
         let action_context = Arc::clone(&self.action_context);
 
@@ -361,6 +446,28 @@ impl ActionPipeline {
         }))
     }
 
+    // Histories only optimize the pipeline, so failing to load them (when corrupted,
+    // for example) shouldn't fail the pipeline. Instead fall back to an empty history,
+    // which will overwrite the corrupted file when saved.
+    fn load_history<T>(&self, file: &str, label: &str) -> CacheItem<T>
+    where
+        T: Default + DeserializeOwned + Serialize,
+    {
+        let state = &self.app_context.cache_engine.state;
+
+        state.load_state::<T>(file).unwrap_or_else(|error| {
+            warn!("Failed to load {label}, starting with an empty history: {error}");
+
+            let mut path = state.resolve_path(file);
+            path.set_extension("json");
+
+            CacheItem {
+                data: T::default(),
+                path,
+            }
+        })
+    }
+
     fn monitor_signals(&self, cancel_token: CancellationToken) -> JoinHandle<SignalType> {
         tokio::spawn(async move {
             let mut receiver = ProcessRegistry::instance().receive_signal();
@@ -399,6 +506,17 @@ impl ActionPipeline {
             ))
             .await;
 
+        if self.trace_file.is_some() || self.trace_endpoint.is_some() {
+            debug!("Subscribing trace exports");
+
+            self.emitter
+                .subscribe(TraceSubscriber::new(
+                    self.trace_file.clone(),
+                    self.trace_endpoint.clone(),
+                ))
+                .await;
+        }
+
         // For security and privacy purposes, only send webhooks from a CI environment
         if is_ci() || is_test_env() {
             if let Some(webhook_url) = &self.app_context.workspace_config.notifier.webhook_url {
@@ -416,9 +534,16 @@ impl ActionPipeline {
 
         if self.app_context.workspace_config.pipeline.auto_clean_cache {
             let lifetime = &self.app_context.workspace_config.pipeline.cache_lifetime;
+            let max_size = self
+                .app_context
+                .workspace_config
+                .pipeline
+                .cache_max_size
+                .as_deref();
 
             debug!(
                 lifetime = lifetime,
+                max_size = max_size,
                 "Subscribing cache cleanup ({} enabled)",
                 color::property("runner.autoCleanCache"),
             );
@@ -427,6 +552,7 @@ impl ActionPipeline {
                 .subscribe(CleanupSubscriber::new(
                     Arc::clone(&self.app_context.cache_engine),
                     lifetime,
+                    max_size,
                 ))
                 .await;
         }
@@ -0,0 +1,71 @@
+use moon_action::{Action, ActionNode, ActionStatus};
+use moon_cache_item::cache_item;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Maximum number of durations to keep for each target.
+pub const MAX_SAMPLES: usize = 10;
+
+cache_item!(
+    pub struct DurationHistoryState {
+        /// Most recent durations (in milliseconds) of each target, oldest first.
+        pub targets: BTreeMap<String, Vec<u64>>,
+    }
+);
+
+impl DurationHistoryState {
+    /// Estimate how long the action will take to run, based on the median
+    /// of its previous durations. Only tasks are tracked.
+    pub fn estimate(&self, node: &ActionNode) -> Option<Duration> {
+        let ActionNode::RunTask(inner) = node else {
+            return None;
+        };
+
+        self.estimate_target(inner.target.as_str())
+    }
+
+    /// Estimate how long the target will take to run.
+    pub fn estimate_target(&self, target: &str) -> Option<Duration> {
+        let samples = self.targets.get(target)?;
+
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_owned();
+        sorted.sort_unstable();
+
+        Some(Duration::from_millis(sorted[sorted.len() / 2]))
+    }
+
+    /// Record the durations of all tasks that were executed, while
+    /// dropping the oldest durations once the limit has been reached.
+    pub fn record(&mut self, actions: &[Action]) {
+        for action in actions {
+            let ActionNode::RunTask(inner) = &*action.node else {
+                continue;
+            };
+
+            // Cached tasks didn't execute, so their duration isn't
+            // representative, while persistent tasks never complete
+            if action.status != ActionStatus::Passed || inner.persistent {
+                continue;
+            }
+
+            let Some(duration) = action.duration else {
+                continue;
+            };
+
+            let samples = self
+                .targets
+                .entry(inner.target.as_str().to_owned())
+                .or_default();
+
+            samples.push(duration.as_millis() as u64);
+
+            if samples.len() > MAX_SAMPLES {
+                samples.drain(0..samples.len() - MAX_SAMPLES);
+            }
+        }
+    }
+}
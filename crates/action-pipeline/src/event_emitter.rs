@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use moon_action::{Action, ActionNode, ActionPipelineStatus, RunTaskNode};
+use moon_action::{Action, ActionNode, ActionPipelineStatus, PipelineAnalysis, RunTaskNode};
 use moon_action_context::ActionContext;
 use moon_project::Project;
 use moon_task::Target;
@@ -46,6 +46,7 @@ pub enum Event<'data> {
     #[serde(rename_all = "camelCase")]
     PipelineCompleted {
         actions: &'data [Action],
+        analysis: Option<&'data PipelineAnalysis>,
         context: &'data ActionContext,
         duration: Option<Duration>,
         error: Option<String>,
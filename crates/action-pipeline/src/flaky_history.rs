@@ -0,0 +1,94 @@
+use moon_action::{Action, ActionNode, ActionStatus};
+use moon_cache_item::cache_item;
+use std::collections::BTreeMap;
+
+/// Maximum number of runs to keep for each target.
+pub const MAX_RUNS: usize = 20;
+
+cache_item!(
+    pub struct FlakyTargetState {
+        /// Hash of the last run if it failed, so that a subsequent
+        /// pass with the same hash can be detected as flaky.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub last_failed_hash: Option<String>,
+
+        /// Whether the most recent runs of the target were flaky, oldest first.
+        pub runs: Vec<bool>,
+    }
+);
+
+impl FlakyTargetState {
+    /// Number of recorded runs that were flaky.
+    pub fn flaky_count(&self) -> usize {
+        self.runs.iter().filter(|flaky| **flaky).count()
+    }
+
+    /// Ratio of flaky runs to all recorded runs, between 0 and 1.
+    pub fn score(&self) -> f32 {
+        if self.runs.is_empty() {
+            return 0.0;
+        }
+
+        self.flaky_count() as f32 / self.runs.len() as f32
+    }
+}
+
+cache_item!(
+    pub struct FlakyHistoryState {
+        pub targets: BTreeMap<String, FlakyTargetState>,
+    }
+);
+
+impl FlakyHistoryState {
+    /// Return the flakiness score of the target, if it has been ran before.
+    pub fn get_score(&self, target: &str) -> Option<f32> {
+        self.targets.get(target).map(|state| state.score())
+    }
+
+    /// Return true if the target has flaked at least once, and its
+    /// score has reached the provided threshold.
+    pub fn is_flaky(&self, target: &str, threshold: f32) -> bool {
+        self.targets
+            .get(target)
+            .is_some_and(|state| state.flaky_count() > 0 && state.score() >= threshold)
+    }
+
+    /// Record the outcome of all tasks that were executed. A run is flaky if it
+    /// failed and then passed on retry, or if it passed with the same hash as
+    /// the previous failed run.
+    pub fn record(&mut self, actions: &[Action]) {
+        for action in actions {
+            let ActionNode::RunTask(inner) = &*action.node else {
+                continue;
+            };
+
+            // Cached and skipped tasks didn't execute, while
+            // persistent tasks never complete
+            if inner.persistent || !(action.status == ActionStatus::Passed || action.has_failed())
+            {
+                continue;
+            }
+
+            let hash = action.operations.get_hash();
+            let state = self
+                .targets
+                .entry(inner.target.as_str().to_owned())
+                .or_default();
+
+            if action.has_failed() {
+                state.last_failed_hash = hash.map(|hash| hash.to_owned());
+                state.runs.push(false);
+            } else {
+                let flaky = action.flaky
+                    || hash.is_some() && state.last_failed_hash.as_deref() == hash;
+
+                state.last_failed_hash = None;
+                state.runs.push(flaky);
+            }
+
+            if state.runs.len() > MAX_RUNS {
+                state.runs.drain(0..state.runs.len() - MAX_RUNS);
+            }
+        }
+    }
+}
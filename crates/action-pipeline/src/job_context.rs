@@ -1,4 +1,5 @@
 use crate::event_emitter::EventEmitter;
+use crate::resource_pool::ResourcePool;
 use moon_action::Action;
 use moon_toolchain_plugin::ToolchainRegistry;
 use moon_workspace_graph::WorkspaceGraph;
@@ -28,6 +29,9 @@ pub struct JobContext {
     /// Currently running jobs (used by the dispatcher)
     pub running_jobs: Arc<RwLock<FxHashMap<NodeIndex, u64>>>,
 
+    /// Resources reserved by running jobs (used by the dispatcher)
+    pub resources: Arc<RwLock<ResourcePool>>,
+
     /// Acquires a permit for concurrency
     pub semaphore: Arc<Semaphore>,
 
@@ -45,6 +49,7 @@ impl JobContext {
 
     pub async fn mark_completed(&self, index: NodeIndex) {
         self.running_jobs.write().await.remove(&index);
+        self.resources.write().await.release(index.index());
         self.completed_jobs.write().await.insert(index);
     }
 
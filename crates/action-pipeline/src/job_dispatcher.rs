@@ -1,27 +1,62 @@
 use crate::job_context::JobContext;
+use moon_action::ActionNode;
 use moon_action_graph::{ActionGraph, ActionGraphType};
+use moon_common::Id;
 use petgraph::prelude::*;
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 use std::collections::BTreeMap;
 use tracing::trace;
 
+static EMPTY_REQUIREMENTS: BTreeMap<Id, u32> = BTreeMap::new();
+
+// How many jobs can be dispatched ahead of the oldest job that is waiting
+// for resources, before resources are held back for it, so that heavy
+// jobs are not starved by a continuous stream of lighter jobs
+const MAX_DISPATCHED_AHEAD: usize = 5;
+
 pub struct JobDispatcher<'graph> {
+    blocked: Option<BlockedJob>,
     context: JobContext,
     graph: &'graph ActionGraphType,
-    groups: BTreeMap<u8, Vec<NodeIndex>>, // topo
+    groups: BTreeMap<u8, Vec<NodeIndex>>, // longest first
+    requirements: FxHashMap<NodeIndex, BTreeMap<Id, u32>>,
     visited: FxHashSet<NodeIndex>,
 }
 
+struct BlockedJob {
+    dispatched_ahead: usize,
+    index: NodeIndex,
+}
+
 impl<'graph> JobDispatcher<'graph> {
     pub fn new(
         action_graph: &'graph ActionGraph,
         context: JobContext,
         groups: BTreeMap<u8, Vec<NodeIndex>>,
     ) -> Self {
+        let graph = action_graph.get_inner_graph();
+
+        // Load the resources required by each task (`options.resources`)
+        // up front, so that they can be reserved when dispatching
+        let requirements = graph
+            .node_indices()
+            .filter_map(|index| {
+                let ActionNode::RunTask(inner) = graph.node_weight(index)? else {
+                    return None;
+                };
+
+                let task = context.workspace_graph.get_task(&inner.target).ok()?;
+
+                Some((index, task.options.resources.clone()?))
+            })
+            .collect();
+
         Self {
+            blocked: None,
             context,
-            graph: action_graph.get_inner_graph(),
+            graph,
             groups,
+            requirements,
             visited: FxHashSet::default(),
         }
     }
@@ -49,6 +84,50 @@ impl<'graph> JobDispatcher<'graph> {
     }
 }
 
+// Once too many jobs have been dispatched ahead of the oldest
+// blocked job, hold back resources until it can be dispatched
+async fn try_reserve(
+    context: &JobContext,
+    blocked: &mut Option<BlockedJob>,
+    index: NodeIndex,
+    requirements: &BTreeMap<Id, u32>,
+) -> bool {
+    if let Some(job) = blocked {
+        if job.index != index && job.dispatched_ahead >= MAX_DISPATCHED_AHEAD {
+            trace!(
+                index = index.index(),
+                blocked_index = job.index.index(),
+                "Holding back resources for a blocked job, deferring dispatch",
+            );
+
+            return false;
+        }
+    }
+
+    let reserved = context
+        .resources
+        .write()
+        .await
+        .try_reserve(index.index(), requirements);
+
+    if blocked.as_ref().is_some_and(|job| job.index == index) {
+        if reserved {
+            *blocked = None;
+        }
+    } else if let Some(job) = blocked {
+        if reserved {
+            job.dispatched_ahead += 1;
+        }
+    } else if !reserved {
+        *blocked = Some(BlockedJob {
+            dispatched_ahead: 0,
+            index,
+        });
+    }
+
+    reserved
+}
+
 // This is based on the `Topo` struct from petgraph!
 impl JobDispatcher<'_> {
     pub async fn next(&mut self) -> Option<NodeIndex> {
@@ -57,8 +136,9 @@ impl JobDispatcher<'_> {
         // Loop based on priority groups, from critical to low
         {
             for (group, indices) in &self.groups {
-                // Then loop through the indices within the group,
-                // which are topologically sorted
+                // Then loop through the indices within the group, which are
+                // sorted by their estimated duration (longest first), and
+                // otherwise topologically
                 for index in indices {
                     if self.visited.contains(index) || completed.contains(index) {
                         continue;
@@ -109,7 +189,26 @@ impl JobDispatcher<'_> {
 
                                     continue;
                                 }
+                            }
+
+                            // Avoid oversubscribing the machine by only dispatching
+                            // tasks when their required resources are available,
+                            // otherwise lighter tasks that fit will be dispatched.
+                            // Persistent tasks are dispatched at the end, so skip them.
+                            if matches!(node, ActionNode::RunTask(_))
+                                && !node.is_persistent()
+                                && !try_reserve(
+                                    &self.context,
+                                    &mut self.blocked,
+                                    index,
+                                    self.requirements.get(&index).unwrap_or(&EMPTY_REQUIREMENTS),
+                                )
+                                .await
+                            {
+                                continue;
+                            }
 
+                            if id > 0 && node.is_standard() {
                                 self.context.running_jobs.write().await.insert(index, id);
                             }
                         }
@@ -136,3 +235,124 @@ impl JobDispatcher<'_> {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resource_pool::{CPU_RESOURCE, ResourcePool};
+    use moon_action::RunTaskNode;
+    use moon_toolchain::Runtime;
+    use std::collections::VecDeque;
+    use std::sync::Arc;
+    use tokio::sync::{RwLock, Semaphore, mpsc};
+    use tokio_util::sync::CancellationToken;
+
+    fn create_context(concurrency: usize) -> JobContext {
+        JobContext {
+            abort_token: CancellationToken::new(),
+            cancel_token: CancellationToken::new(),
+            completed_jobs: Arc::new(RwLock::new(FxHashSet::default())),
+            emitter: Arc::new(Default::default()),
+            result_sender: mpsc::channel(1).0,
+            running_jobs: Arc::new(RwLock::new(FxHashMap::default())),
+            resources: Arc::new(RwLock::new(ResourcePool::new(
+                concurrency,
+                BTreeMap::default(),
+            ))),
+            semaphore: Arc::new(Semaphore::new(concurrency)),
+            toolchain_registry: Arc::new(Default::default()),
+            workspace_graph: Arc::new(Default::default()),
+        }
+    }
+
+    fn create_graph(labels: &[&str]) -> ActionGraph {
+        let mut graph = ActionGraphType::default();
+
+        for label in labels {
+            graph.add_node(ActionNode::run_task(RunTaskNode::new(
+                (*label).into(),
+                Runtime::system(),
+            )));
+        }
+
+        ActionGraph::new(graph)
+    }
+
+    // Dispatch all jobs, and when none can be dispatched,
+    // complete the oldest running job to free up resources
+    async fn dispatch_all(dispatcher: &mut JobDispatcher<'_>, context: &JobContext) -> Vec<usize> {
+        let mut order = vec![];
+        let mut running = VecDeque::new();
+
+        while dispatcher.has_queued_jobs() {
+            match dispatcher.next().await {
+                Some(index) => {
+                    order.push(index.index());
+                    running.push_back(index);
+                }
+                None => {
+                    let index = running.pop_front().expect("no jobs could be dispatched");
+
+                    context.mark_completed(index).await;
+                }
+            };
+        }
+
+        order
+    }
+
+    #[tokio::test]
+    async fn dispatches_heavy_job_before_it_starves() {
+        let labels = [
+            "app:a",
+            "app:heavy",
+            "app:b",
+            "app:c",
+            "app:d",
+            "app:e",
+            "app:f",
+            "app:g",
+            "app:h",
+            "app:i",
+            "app:j",
+            "app:k",
+        ];
+        let action_graph = create_graph(&labels);
+        let context = create_context(2);
+        let groups =
+            BTreeMap::from_iter([(2, action_graph.get_inner_graph().node_indices().collect())]);
+
+        let mut dispatcher = JobDispatcher::new(&action_graph, context.clone(), groups);
+        dispatcher.requirements.insert(
+            NodeIndex::new(1),
+            BTreeMap::from_iter([(Id::raw(CPU_RESOURCE), 2)]),
+        );
+
+        let order = dispatch_all(&mut dispatcher, &context).await;
+
+        // Lighter jobs are dispatched ahead of the heavy job, but only up
+        // to a limit, after which resources are held back for it
+        assert_eq!(order.len(), labels.len());
+        assert_eq!(
+            order.iter().position(|index| *index == 1),
+            Some(MAX_DISPATCHED_AHEAD + 1)
+        );
+    }
+
+    #[tokio::test]
+    async fn dispatches_jobs_in_order_when_resources_are_available() {
+        let labels = ["app:a", "app:heavy", "app:b"];
+        let action_graph = create_graph(&labels);
+        let context = create_context(4);
+        let groups =
+            BTreeMap::from_iter([(2, action_graph.get_inner_graph().node_indices().collect())]);
+
+        let mut dispatcher = JobDispatcher::new(&action_graph, context.clone(), groups);
+        dispatcher.requirements.insert(
+            NodeIndex::new(1),
+            BTreeMap::from_iter([(Id::raw(CPU_RESOURCE), 2)]),
+        );
+
+        assert_eq!(dispatch_all(&mut dispatcher, &context).await, vec![0, 1, 2]);
+    }
+}
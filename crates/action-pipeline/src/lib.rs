@@ -1,10 +1,13 @@
 mod action_pipeline;
 mod action_runner;
+pub mod duration_history;
 mod event_emitter;
+pub mod flaky_history;
 mod job;
 mod job_context;
 mod job_dispatcher;
 pub mod reports;
+pub mod resource_pool;
 mod subscribers;
 
 pub use action_pipeline::*;
@@ -0,0 +1,201 @@
+use moon_action::{Action, ActionSpeedup, CriticalPathItem, PipelineAnalysis};
+use moon_action_graph::ActionGraph;
+use petgraph::Direction;
+use rustc_hash::FxHashMap;
+use std::time::Duration;
+use tracing::{debug, instrument};
+
+const MAX_SPEEDUPS: usize = 5;
+
+/// Dependencies of each action, keyed by node index.
+pub type ActionDependencies = FxHashMap<usize, Vec<usize>>;
+
+pub fn extract_dependencies(action_graph: &ActionGraph) -> ActionDependencies {
+    let graph = action_graph.get_inner_graph();
+
+    graph
+        .node_indices()
+        .map(|index| {
+            (
+                index.index(),
+                graph
+                    .neighbors_directed(index, Direction::Outgoing)
+                    .map(|dep_index| dep_index.index())
+                    .collect(),
+            )
+        })
+        .collect()
+}
+
+struct PathNode {
+    finish: Duration,
+    previous: Option<usize>,
+}
+
+struct CriticalPathSolver<'data> {
+    dependencies: &'data ActionDependencies,
+    durations: FxHashMap<usize, Duration>,
+}
+
+impl CriticalPathSolver<'_> {
+    // Calculate the longest path ending at each action, optionally
+    // treating an action as if it took no time at all
+    fn solve(&self, zeroed: Option<usize>) -> FxHashMap<usize, PathNode> {
+        let mut paths = FxHashMap::default();
+
+        for index in self.durations.keys() {
+            self.visit(*index, zeroed, &mut paths);
+        }
+
+        paths
+    }
+
+    fn visit(
+        &self,
+        index: usize,
+        zeroed: Option<usize>,
+        paths: &mut FxHashMap<usize, PathNode>,
+    ) -> Duration {
+        if let Some(node) = paths.get(&index) {
+            return node.finish;
+        }
+
+        let mut start = Duration::ZERO;
+        let mut previous = None;
+
+        if let Some(deps) = self.dependencies.get(&index) {
+            for dep_index in deps {
+                if !self.durations.contains_key(dep_index) {
+                    continue;
+                }
+
+                let dep_finish = self.visit(*dep_index, zeroed, paths);
+
+                if dep_finish > start || previous.is_none() {
+                    start = dep_finish;
+                    previous = Some(*dep_index);
+                }
+            }
+        }
+
+        let duration = if zeroed.is_some_and(|zeroed| zeroed == index) {
+            Duration::ZERO
+        } else {
+            self.durations[&index]
+        };
+
+        let finish = start + duration;
+
+        paths.insert(index, PathNode { finish, previous });
+
+        finish
+    }
+}
+
+fn find_longest(paths: &FxHashMap<usize, PathNode>) -> Option<(usize, Duration)> {
+    paths
+        .iter()
+        .map(|(index, node)| (*index, node.finish))
+        // Prefer the lowest index on ties, so that results are deterministic
+        .max_by(|a, d| a.1.cmp(&d.1).then(d.0.cmp(&a.0)))
+}
+
+#[instrument(name = "analyze_critical_path", skip_all)]
+pub fn analyze_critical_path(
+    actions: &[Action],
+    dependencies: &ActionDependencies,
+    concurrency: usize,
+    pipeline_duration: &Duration,
+) -> PipelineAnalysis {
+    debug!("Analyzing the critical path of the pipeline");
+
+    let mut durations = FxHashMap::default();
+    let mut labels = FxHashMap::default();
+
+    for action in actions {
+        // Persistent tasks never complete, so they would always
+        // be the critical path, which isn't useful
+        if action.node.is_persistent() {
+            continue;
+        }
+
+        durations.insert(action.node_index, action.duration.unwrap_or_default());
+        labels.insert(action.node_index, action.label.as_str());
+    }
+
+    let concurrency = concurrency.max(1);
+    let total_duration = durations.values().sum::<Duration>();
+
+    let solver = CriticalPathSolver {
+        dependencies,
+        durations,
+    };
+    let paths = solver.solve(None);
+
+    let Some((last_index, critical_path_duration)) = find_longest(&paths) else {
+        return PipelineAnalysis {
+            concurrency,
+            ..PipelineAnalysis::default()
+        };
+    };
+
+    // Walk the path backwards from the last action
+    let mut critical_path = vec![];
+    let mut current = Some(last_index);
+
+    while let Some(index) = current {
+        critical_path.push(index);
+        current = paths[&index].previous;
+    }
+
+    critical_path.reverse();
+
+    // Only actions on the critical path can shorten the pipeline,
+    // but another path may become critical once they're faster
+    let mut speedups = critical_path
+        .iter()
+        .filter_map(|index| {
+            let duration = solver.durations[index];
+
+            if duration.is_zero() {
+                return None;
+            }
+
+            let (_, finish) = find_longest(&solver.solve(Some(*index)))?;
+            let savings = critical_path_duration.saturating_sub(finish);
+
+            if savings.is_zero() {
+                return None;
+            }
+
+            Some(ActionSpeedup {
+                duration,
+                label: labels[index].to_owned(),
+                savings,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    speedups.sort_by(|a, d| d.savings.cmp(&a.savings).then(a.label.cmp(&d.label)));
+    speedups.truncate(MAX_SPEEDUPS);
+
+    PipelineAnalysis {
+        concurrency,
+        critical_path: critical_path
+            .into_iter()
+            .map(|index| CriticalPathItem {
+                duration: solver.durations[&index],
+                label: labels[&index].to_owned(),
+            })
+            .collect(),
+        critical_path_duration,
+        minimum_duration: critical_path_duration.max(total_duration / concurrency as u32),
+        parallelism: if pipeline_duration.is_zero() {
+            0.0
+        } else {
+            total_duration.as_secs_f32() / pipeline_duration.as_secs_f32()
+        },
+        speedups,
+        total_duration,
+    }
+}
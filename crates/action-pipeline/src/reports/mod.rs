@@ -1 +1,3 @@
+pub mod critical_path;
 pub mod estimate;
+pub mod trace;
@@ -0,0 +1,418 @@
+use moon_action::{Action, ActionPipelineStatus, ActionStatus, Operation, OperationMeta};
+use moon_time::chrono::NaiveDateTime;
+use serde::Serialize;
+use starbase_utils::json::{JsonMap, JsonValue, json};
+use std::time::Duration;
+use tracing::{debug, instrument};
+use uuid::Uuid;
+
+const PROCESS_ID: u32 = 1;
+
+/// An event in the Chrome Trace Event format. Only "complete" (X)
+/// and "metadata" (M) phases are used.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct TraceEvent {
+    pub name: String,
+
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub cat: String,
+
+    pub ph: String,
+
+    /// Timestamp in microseconds, relative to the start of the pipeline.
+    pub ts: u64,
+
+    /// Duration in microseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dur: Option<u64>,
+
+    pub pid: u32,
+
+    pub tid: usize,
+
+    #[serde(skip_serializing_if = "JsonMap::is_empty")]
+    pub args: JsonMap<String, JsonValue>,
+}
+
+/// A trace in the Chrome Trace Event JSON format, that can be
+/// viewed in Perfetto, or `chrome://tracing`.
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChromeTrace {
+    pub trace_events: Vec<TraceEvent>,
+
+    pub display_time_unit: String,
+}
+
+impl ChromeTrace {
+    #[instrument(name = "create_chrome_trace", skip_all)]
+    pub fn from_actions(actions: &[Action]) -> Self {
+        debug!("Creating a Chrome trace from pipeline actions");
+
+        let mut trace_events = vec![TraceEvent {
+            name: "process_name".into(),
+            cat: String::new(),
+            ph: "M".into(),
+            ts: 0,
+            dur: None,
+            pid: PROCESS_ID,
+            tid: 0,
+            args: create_args([("name", json!("moon"))]),
+        }];
+
+        let tracks = assign_tracks(actions);
+
+        let Some(base) = tracks.iter().map(|(action, _)| action.started_at).min() else {
+            return Self::new(trace_events);
+        };
+
+        let track_count = tracks.iter().map(|(_, track)| *track).max().unwrap_or(0);
+
+        for track in 1..=track_count {
+            trace_events.push(TraceEvent {
+                name: "thread_name".into(),
+                cat: String::new(),
+                ph: "M".into(),
+                ts: 0,
+                dur: None,
+                pid: PROCESS_ID,
+                tid: track,
+                args: create_args([("name", json!(format!("Slot {track}")))]),
+            });
+        }
+
+        for (action, track) in tracks {
+            let mut args = create_args([("status", json!(action.status))]);
+
+            if let Some(error) = &action.action.error {
+                args.insert("error".into(), json!(error));
+            }
+
+            trace_events.push(TraceEvent {
+                name: action.action.label.clone(),
+                cat: "action".into(),
+                ph: "X".into(),
+                ts: to_micros(base, action.started_at),
+                dur: Some(action.duration.as_micros() as u64),
+                pid: PROCESS_ID,
+                tid: track,
+                args,
+            });
+
+            for operation in action.action.operations.iter() {
+                push_operation_events(&mut trace_events, operation, base, track);
+            }
+        }
+
+        Self::new(trace_events)
+    }
+
+    fn new(trace_events: Vec<TraceEvent>) -> Self {
+        Self {
+            trace_events,
+            display_time_unit: "ms".into(),
+        }
+    }
+}
+
+fn push_operation_events(
+    events: &mut Vec<TraceEvent>,
+    operation: &Operation,
+    base: NaiveDateTime,
+    track: usize,
+) {
+    let mut args = create_args([("status", json!(operation.status))]);
+
+    match &operation.meta {
+        OperationMeta::HashGeneration(inner) => {
+            if let Some(hash) = &inner.hash {
+                args.insert("hash".into(), json!(hash));
+            }
+        }
+        OperationMeta::OutputHydration(inner)
+        | OperationMeta::ProcessExecution(inner)
+        | OperationMeta::TaskExecution(inner) => {
+            if let Some(command) = &inner.command {
+                args.insert("command".into(), json!(command));
+            }
+
+            if let Some(exit_code) = &inner.exit_code {
+                args.insert("exitCode".into(), json!(exit_code));
+            }
+        }
+        _ => {}
+    };
+
+    events.push(TraceEvent {
+        name: get_operation_label(&operation.meta),
+        cat: "operation".into(),
+        ph: "X".into(),
+        ts: to_micros(base, operation.started_at),
+        dur: Some(get_operation_duration(operation).as_micros() as u64),
+        pid: PROCESS_ID,
+        tid: track,
+        args,
+    });
+
+    for child in &operation.operations {
+        push_operation_events(events, child, base, track);
+    }
+}
+
+/// A trace in the OpenTelemetry protocol (OTLP) JSON format, that can
+/// be sent to a collector over HTTP.
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OtlpTrace {
+    pub resource_spans: Vec<JsonValue>,
+}
+
+impl OtlpTrace {
+    #[instrument(name = "create_otlp_trace", skip_all)]
+    pub fn from_actions(actions: &[Action], status: &ActionPipelineStatus) -> Self {
+        debug!("Creating an OTLP trace from pipeline actions");
+
+        let trace_id = Uuid::new_v4().simple().to_string();
+        let root_span_id = create_span_id();
+        let tracks = assign_tracks(actions);
+        let mut spans = vec![];
+        let mut pipeline_start = None;
+        let mut pipeline_end = None;
+
+        for (action, track) in tracks {
+            let span_id = create_span_id();
+            let end = action.started_at + action.duration;
+
+            if pipeline_start.is_none_or(|start| action.started_at < start) {
+                pipeline_start = Some(action.started_at);
+            }
+
+            if pipeline_end.is_none_or(|prev| end > prev) {
+                pipeline_end = Some(end);
+            }
+
+            let mut attributes = vec![
+                create_attribute("moon.action.status", json!(action.status)),
+                create_attribute("moon.action.slot", json!(track)),
+            ];
+
+            if let Some(error) = &action.action.error {
+                attributes.push(create_attribute("moon.action.error", json!(error)));
+            }
+
+            spans.push(create_span(
+                &trace_id,
+                &span_id,
+                Some(&root_span_id),
+                &action.action.label,
+                action.started_at,
+                end,
+                attributes,
+                action.action.has_failed(),
+            ));
+
+            for operation in action.action.operations.iter() {
+                push_operation_spans(&mut spans, operation, &trace_id, &span_id);
+            }
+        }
+
+        if let (Some(start), Some(end)) = (pipeline_start, pipeline_end) {
+            spans.insert(
+                0,
+                create_span(
+                    &trace_id,
+                    &root_span_id,
+                    None,
+                    "pipeline",
+                    start,
+                    end,
+                    vec![create_attribute("moon.pipeline.status", json!(status))],
+                    *status != ActionPipelineStatus::Completed,
+                ),
+            );
+        }
+
+        Self {
+            resource_spans: vec![json!({
+                "resource": {
+                    "attributes": [
+                        create_attribute("service.name", json!("moon")),
+                        create_attribute("service.version", json!(env!("CARGO_PKG_VERSION"))),
+                    ],
+                },
+                "scopeSpans": [{
+                    "scope": { "name": "moon_action_pipeline" },
+                    "spans": spans,
+                }],
+            })],
+        }
+    }
+}
+
+fn push_operation_spans(
+    spans: &mut Vec<JsonValue>,
+    operation: &Operation,
+    trace_id: &str,
+    parent_span_id: &str,
+) {
+    let span_id = create_span_id();
+
+    spans.push(create_span(
+        trace_id,
+        &span_id,
+        Some(parent_span_id),
+        &get_operation_label(&operation.meta),
+        operation.started_at,
+        operation.started_at + get_operation_duration(operation),
+        vec![create_attribute(
+            "moon.operation.status",
+            json!(operation.status),
+        )],
+        operation.has_failed(),
+    ));
+
+    for child in &operation.operations {
+        push_operation_spans(spans, child, trace_id, &span_id);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn create_span(
+    trace_id: &str,
+    span_id: &str,
+    parent_span_id: Option<&str>,
+    name: &str,
+    start: NaiveDateTime,
+    end: NaiveDateTime,
+    attributes: Vec<JsonValue>,
+    failed: bool,
+) -> JsonValue {
+    json!({
+        "traceId": trace_id,
+        "spanId": span_id,
+        "parentSpanId": parent_span_id.unwrap_or_default(),
+        "name": name,
+        // SPAN_KIND_INTERNAL
+        "kind": 1,
+        // 64-bit integers are encoded as strings in OTLP JSON
+        "startTimeUnixNano": to_unix_nanos(start).to_string(),
+        "endTimeUnixNano": to_unix_nanos(end).to_string(),
+        "attributes": attributes,
+        // STATUS_CODE_OK or STATUS_CODE_ERROR
+        "status": { "code": if failed { 2 } else { 1 } },
+    })
+}
+
+fn create_attribute(key: &str, value: JsonValue) -> JsonValue {
+    let value = match value {
+        JsonValue::Number(number) => json!({ "intValue": number.to_string() }),
+        JsonValue::Bool(bool) => json!({ "boolValue": bool }),
+        JsonValue::String(string) => json!({ "stringValue": string }),
+        other => json!({ "stringValue": other.to_string() }),
+    };
+
+    json!({ "key": key, "value": value })
+}
+
+fn create_span_id() -> String {
+    Uuid::new_v4().simple().to_string()[0..16].to_owned()
+}
+
+fn create_args<const N: usize>(items: [(&str, JsonValue); N]) -> JsonMap<String, JsonValue> {
+    items
+        .into_iter()
+        .map(|(key, value)| (key.to_owned(), value))
+        .collect()
+}
+
+/// An action that was started, with its resolved timings.
+pub struct TimedAction<'action> {
+    pub action: &'action Action,
+    pub duration: Duration,
+    pub started_at: NaiveDateTime,
+    pub status: ActionStatus,
+}
+
+/// Assign each started action to a track (starting from 1), where each track
+/// represents a concurrency slot, and actions within a track never overlap.
+pub fn assign_tracks(actions: &[Action]) -> Vec<(TimedAction<'_>, usize)> {
+    let mut timed = actions
+        .iter()
+        .filter_map(|action| {
+            let started_at = action.started_at?;
+
+            Some(TimedAction {
+                action,
+                duration: action.duration.unwrap_or_else(|| {
+                    action
+                        .finished_at
+                        .and_then(|finished_at| (finished_at - started_at).to_std().ok())
+                        .unwrap_or_default()
+                }),
+                started_at,
+                status: action.status,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    timed.sort_by(|a, d| {
+        a.started_at
+            .cmp(&d.started_at)
+            .then(a.action.node_index.cmp(&d.action.node_index))
+    });
+
+    let mut track_ends: Vec<NaiveDateTime> = vec![];
+    let mut result = vec![];
+
+    for item in timed {
+        let end = item.started_at + item.duration;
+
+        let track = match track_ends
+            .iter()
+            .position(|track_end| *track_end <= item.started_at)
+        {
+            Some(index) => {
+                track_ends[index] = end;
+                index
+            }
+            None => {
+                track_ends.push(end);
+                track_ends.len() - 1
+            }
+        };
+
+        result.push((item, track + 1));
+    }
+
+    result
+}
+
+fn get_operation_label(meta: &OperationMeta) -> String {
+    match meta {
+        OperationMeta::NoOperation => "No operation".into(),
+        OperationMeta::OutputHydration(_) => "Output hydration".into(),
+        OperationMeta::ProcessExecution(_) => "Process execution".into(),
+        OperationMeta::SyncOperation(inner) => inner.label.clone(),
+        OperationMeta::TaskExecution(_) => "Task execution".into(),
+        OperationMeta::ArchiveCreation => "Archive creation".into(),
+        OperationMeta::HashGeneration(_) => "Hash generation".into(),
+        OperationMeta::MutexAcquisition => "Mutex acquisition".into(),
+    }
+}
+
+fn get_operation_duration(operation: &Operation) -> Duration {
+    operation.duration.unwrap_or_else(|| {
+        operation
+            .finished_at
+            .and_then(|finished_at| (finished_at - operation.started_at).to_std().ok())
+            .unwrap_or_default()
+    })
+}
+
+fn to_micros(base: NaiveDateTime, time: NaiveDateTime) -> u64 {
+    (time - base).num_microseconds().unwrap_or_default().max(0) as u64
+}
+
+fn to_unix_nanos(time: NaiveDateTime) -> i64 {
+    time.and_utc().timestamp_nanos_opt().unwrap_or_default()
+}
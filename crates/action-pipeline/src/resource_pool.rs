@@ -0,0 +1,124 @@
+use moon_common::{Id, color};
+use rustc_hash::FxHashMap;
+use std::collections::{BTreeMap, BTreeSet};
+use tracing::{trace, warn};
+
+/// Resource for the number of CPU cores, bounded by the pipeline concurrency.
+pub const CPU_RESOURCE: &str = "cpu";
+
+/// Resource for the amount of memory, in megabytes.
+pub const MEMORY_RESOURCE: &str = "memory";
+
+/// Tracks the capacity of resources (CPU, memory, custom pools), and the
+/// amounts that have been reserved by running jobs, so that the dispatcher
+/// can weight concurrency instead of relying on a job count alone.
+#[derive(Debug, Default)]
+pub struct ResourcePool {
+    /// Total capacity of each resource. Resources without a
+    /// capacity are unbounded.
+    capacity: BTreeMap<Id, u32>,
+
+    /// Amounts currently in use, across all reservations.
+    in_use: BTreeMap<Id, u32>,
+
+    /// Amounts reserved by each job (node index).
+    reservations: FxHashMap<usize, BTreeMap<Id, u32>>,
+
+    /// Required resources without a capacity, that have been warned about.
+    unbounded: BTreeSet<Id>,
+}
+
+impl ResourcePool {
+    pub fn new(concurrency: usize, mut capacity: BTreeMap<Id, u32>) -> Self {
+        capacity
+            .entry(Id::raw(CPU_RESOURCE))
+            .or_insert(concurrency.max(1) as u32);
+
+        Self {
+            capacity,
+            ..Default::default()
+        }
+    }
+
+    /// Return the capacity of a resource, or `None` if unbounded.
+    pub fn get_capacity(&self, resource: &str) -> Option<u32> {
+        self.capacity.get(resource).copied()
+    }
+
+    /// Return the amount of a resource currently in use.
+    pub fn get_in_use(&self, resource: &str) -> u32 {
+        self.in_use.get(resource).copied().unwrap_or_default()
+    }
+
+    /// Resolve the amounts to reserve for the requirements. Every job requires
+    /// at least 1 CPU, and amounts are clamped to the capacity, so that a job
+    /// requiring more than is available will still run, but in isolation.
+    pub fn resolve_requirements(&self, requirements: &BTreeMap<Id, u32>) -> BTreeMap<Id, u32> {
+        let mut resolved = requirements.clone();
+
+        resolved.entry(Id::raw(CPU_RESOURCE)).or_insert(1);
+
+        for (resource, amount) in resolved.iter_mut() {
+            if let Some(capacity) = self.capacity.get(resource) {
+                *amount = (*amount).min(*capacity);
+            }
+        }
+
+        resolved.retain(|_, amount| *amount > 0);
+        resolved
+    }
+
+    /// Attempt to reserve the required resources for a job. Returns false
+    /// if any resource does not have enough remaining capacity.
+    pub fn try_reserve(&mut self, index: usize, requirements: &BTreeMap<Id, u32>) -> bool {
+        let resolved = self.resolve_requirements(requirements);
+
+        for (resource, amount) in &resolved {
+            if let Some(capacity) = self.capacity.get(resource) {
+                if self.get_in_use(resource) + amount > *capacity {
+                    trace!(
+                        index,
+                        resource = resource.as_str(),
+                        required = amount,
+                        in_use = self.get_in_use(resource),
+                        capacity,
+                        "Not enough resources available to dispatch job",
+                    );
+
+                    return false;
+                }
+            }
+        }
+
+        for (resource, amount) in &resolved {
+            *self.in_use.entry(resource.to_owned()).or_default() += amount;
+
+            // Memory and custom pools have no capacity by default, so requiring
+            // them won't limit concurrency, which is easy to miss
+            if !self.capacity.contains_key(resource) && self.unbounded.insert(resource.to_owned()) {
+                warn!(
+                    "Tasks require the {} resource, but no capacity has been configured for it in {}, so it won't limit concurrency",
+                    color::symbol(resource),
+                    color::property("pipeline.resources"),
+                );
+            }
+        }
+
+        self.reservations.insert(index, resolved);
+
+        true
+    }
+
+    /// Release the resources reserved by a job, if any.
+    pub fn release(&mut self, index: usize) {
+        let Some(reserved) = self.reservations.remove(&index) else {
+            return;
+        };
+
+        for (resource, amount) in reserved {
+            if let Some(in_use) = self.in_use.get_mut(&resource) {
+                *in_use = in_use.saturating_sub(amount);
+            }
+        }
+    }
+}
@@ -8,13 +8,15 @@ use tracing::debug;
 pub struct CleanupSubscriber {
     cache_engine: Arc<CacheEngine>,
     lifetime: String,
+    max_size: Option<String>,
 }
 
 impl CleanupSubscriber {
-    pub fn new(cache_engine: Arc<CacheEngine>, lifetime: &str) -> Self {
+    pub fn new(cache_engine: Arc<CacheEngine>, lifetime: &str, max_size: Option<&str>) -> Self {
         CleanupSubscriber {
             cache_engine,
             lifetime: lifetime.to_owned(),
+            max_size: max_size.map(|size| size.to_owned()),
         }
     }
 }
@@ -32,6 +34,12 @@ impl Subscriber for CleanupSubscriber {
             debug!("Cleaning stale cache");
 
             self.cache_engine.clean_stale_cache(&self.lifetime, false)?;
+
+            if let Some(max_size) = &self.max_size {
+                debug!("Evicting cache to maximum size");
+
+                self.cache_engine.clean_cache_to_size(max_size)?;
+            }
         }
 
         Ok(())
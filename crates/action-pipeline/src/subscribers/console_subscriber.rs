@@ -24,12 +24,21 @@ impl Subscriber for ConsoleSubscriber {
             }
             Event::PipelineCompleted {
                 actions,
+                analysis,
                 duration,
                 error_report,
                 status,
                 ..
             } => {
+                // The dashboard owns the terminal, so wait for it to exit
+                // (automatically or by the user) before printing the results
+                if let Some(dashboard) = self.console.get_dashboard() {
+                    dashboard.complete();
+                    dashboard.wait_for_close().await;
+                }
+
                 let item = PipelineReportItem {
+                    analysis: analysis.cloned(),
                     duration: *duration,
                     summarize: self.summarize,
                     status: **status,
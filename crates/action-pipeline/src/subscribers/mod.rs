@@ -2,4 +2,5 @@ pub mod cleanup_subscriber;
 pub mod console_subscriber;
 pub mod remote_subscriber;
 pub mod reports_subscriber;
+pub mod trace_subscriber;
 pub mod webhooks_subscriber;
@@ -1,7 +1,7 @@
 use crate::event_emitter::{Event, Subscriber};
 use crate::reports::estimate::Estimate;
 use async_trait::async_trait;
-use moon_action::{Action, ActionPipelineStatus};
+use moon_action::{Action, ActionPipelineStatus, PipelineAnalysis};
 use moon_action_context::ActionContext;
 use moon_cache::CacheEngine;
 use serde::Serialize;
@@ -14,6 +14,9 @@ use tracing::debug;
 pub struct RunReport<'data> {
     pub actions: &'data [Action],
 
+    /// Critical path and parallelism of the pipeline.
+    pub analysis: Option<&'data PipelineAnalysis>,
+
     pub context: &'data ActionContext,
 
     /// How long the pipeline took to execute all actions.
@@ -51,6 +54,7 @@ impl Subscriber for ReportsSubscriber {
     async fn on_emit<'data>(&mut self, event: &Event<'data>) -> miette::Result<()> {
         if let Event::PipelineCompleted {
             actions,
+            analysis,
             duration: Some(duration),
             status,
             ..
@@ -62,6 +66,7 @@ impl Subscriber for ReportsSubscriber {
 
             let report = RunReport {
                 actions,
+                analysis: *analysis,
                 context: &self.action_context,
                 duration,
                 comparison_estimate: estimate,
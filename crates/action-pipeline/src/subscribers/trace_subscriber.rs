@@ -0,0 +1,62 @@
+use crate::event_emitter::{Event, Subscriber};
+use crate::reports::trace::{ChromeTrace, OtlpTrace};
+use async_trait::async_trait;
+use moon_common::color;
+use moon_notifier::notify_webhook;
+use starbase_utils::json;
+use std::path::PathBuf;
+use tracing::{debug, warn};
+
+pub struct TraceSubscriber {
+    endpoint: Option<String>,
+    file: Option<PathBuf>,
+}
+
+impl TraceSubscriber {
+    pub fn new(file: Option<PathBuf>, endpoint: Option<String>) -> Self {
+        TraceSubscriber { endpoint, file }
+    }
+}
+
+#[async_trait]
+impl Subscriber for TraceSubscriber {
+    async fn on_emit<'data>(&mut self, event: &Event<'data>) -> miette::Result<()> {
+        if let Event::PipelineCompleted {
+            actions, status, ..
+        } = event
+        {
+            if let Some(file) = &self.file {
+                debug!(file = ?file, "Writing Chrome trace");
+
+                json::write_file(file, &ChromeTrace::from_actions(actions), false)?;
+            }
+
+            if let Some(endpoint) = &self.endpoint {
+                let url = format!("{}/v1/traces", endpoint.trim_end_matches('/'));
+
+                debug!(url = &url, "Sending OTLP trace to collector");
+
+                let body = json::format(&OtlpTrace::from_actions(actions, status), false)?;
+
+                match notify_webhook(url, body).await {
+                    Ok(response) if !response.status().is_success() => {
+                        warn!(
+                            "Failed to send trace to OTLP collector {}: {}",
+                            color::url(endpoint),
+                            response.status()
+                        );
+                    }
+                    Err(error) => {
+                        warn!(
+                            "Failed to send trace to OTLP collector {}: {error}",
+                            color::url(endpoint),
+                        );
+                    }
+                    _ => {}
+                };
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,112 @@
+use moon_action::*;
+use moon_action_pipeline::duration_history::{DurationHistoryState, MAX_SAMPLES};
+use moon_toolchain::Runtime;
+use std::sync::Arc;
+use std::time::Duration;
+
+fn create_node(target: &str) -> ActionNode {
+    ActionNode::run_task(RunTaskNode::new(target.into(), Runtime::system()))
+}
+
+fn create_action(target: &str, status: ActionStatus, millis: u64) -> Action {
+    Action {
+        duration: Some(Duration::from_millis(millis)),
+        node: Arc::new(create_node(target)),
+        status,
+        ..Action::default()
+    }
+}
+
+mod duration_history {
+    use super::*;
+
+    #[test]
+    fn records_passed_tasks() {
+        let mut history = DurationHistoryState::default();
+
+        history.record(&[
+            create_action("a:build", ActionStatus::Passed, 100),
+            create_action("a:test", ActionStatus::Passed, 200),
+        ]);
+
+        assert_eq!(history.targets["a:build"], vec![100]);
+        assert_eq!(history.targets["a:test"], vec![200]);
+    }
+
+    #[test]
+    fn doesnt_record_cached_or_failed_tasks() {
+        let mut history = DurationHistoryState::default();
+
+        history.record(&[
+            create_action("a:build", ActionStatus::Cached, 100),
+            create_action("a:lint", ActionStatus::CachedFromRemote, 100),
+            create_action("a:test", ActionStatus::Failed, 200),
+        ]);
+
+        assert!(history.targets.is_empty());
+    }
+
+    #[test]
+    fn doesnt_record_non_tasks() {
+        let mut history = DurationHistoryState::default();
+
+        history.record(&[Action {
+            duration: Some(Duration::from_millis(100)),
+            node: Arc::new(ActionNode::sync_workspace()),
+            status: ActionStatus::Passed,
+            ..Action::default()
+        }]);
+
+        assert!(history.targets.is_empty());
+    }
+
+    #[test]
+    fn doesnt_record_persistent_tasks() {
+        let mut node = RunTaskNode::new("a:dev".into(), Runtime::system());
+        node.persistent = true;
+
+        let mut history = DurationHistoryState::default();
+
+        history.record(&[Action {
+            duration: Some(Duration::from_millis(100)),
+            node: Arc::new(ActionNode::run_task(node)),
+            status: ActionStatus::Passed,
+            ..Action::default()
+        }]);
+
+        assert!(history.targets.is_empty());
+    }
+
+    #[test]
+    fn drops_oldest_samples() {
+        let mut history = DurationHistoryState::default();
+
+        for i in 0..(MAX_SAMPLES + 3) {
+            history.record(&[create_action("a:build", ActionStatus::Passed, i as u64)]);
+        }
+
+        assert_eq!(history.targets["a:build"].len(), MAX_SAMPLES);
+        assert_eq!(history.targets["a:build"][0], 3);
+    }
+
+    #[test]
+    fn estimates_using_median() {
+        let mut history = DurationHistoryState::default();
+        history
+            .targets
+            .insert("a:build".into(), vec![100, 5000, 200, 150, 120]);
+
+        assert_eq!(
+            history.estimate(&create_node("a:build")),
+            Some(Duration::from_millis(150))
+        );
+    }
+
+    #[test]
+    fn doesnt_estimate_unknown_targets() {
+        let history = DurationHistoryState::default();
+
+        assert_eq!(history.estimate(&create_node("a:build")), None);
+        assert_eq!(history.estimate(&ActionNode::sync_workspace()), None);
+    }
+}
@@ -0,0 +1,134 @@
+use moon_action::*;
+use moon_action_pipeline::flaky_history::{FlakyHistoryState, MAX_RUNS};
+use moon_toolchain::Runtime;
+use std::sync::Arc;
+
+fn create_action(target: &str, status: ActionStatus, hash: Option<&str>) -> Action {
+    let mut operations = OperationList::default();
+
+    if let Some(hash) = hash {
+        let mut operation = Operation::hash_generation();
+
+        if let OperationMeta::HashGeneration(inner) = &mut operation.meta {
+            inner.hash = Some(hash.into());
+        }
+
+        operations.push(operation);
+    }
+
+    Action {
+        node: Arc::new(ActionNode::run_task(RunTaskNode::new(
+            target.into(),
+            Runtime::system(),
+        ))),
+        operations,
+        status,
+        ..Action::default()
+    }
+}
+
+mod flaky_history {
+    use super::*;
+
+    #[test]
+    fn records_passed_and_failed_tasks() {
+        let mut history = FlakyHistoryState::default();
+
+        history.record(&[
+            create_action("a:build", ActionStatus::Passed, None),
+            create_action("a:test", ActionStatus::Failed, Some("abc")),
+        ]);
+
+        assert_eq!(history.targets["a:build"].runs, vec![false]);
+        assert_eq!(history.targets["a:test"].runs, vec![false]);
+        assert_eq!(
+            history.targets["a:test"].last_failed_hash.as_deref(),
+            Some("abc")
+        );
+    }
+
+    #[test]
+    fn doesnt_record_cached_or_skipped_tasks() {
+        let mut history = FlakyHistoryState::default();
+
+        history.record(&[
+            create_action("a:build", ActionStatus::Cached, None),
+            create_action("a:lint", ActionStatus::CachedFromRemote, None),
+            create_action("a:test", ActionStatus::Skipped, None),
+        ]);
+
+        assert!(history.targets.is_empty());
+    }
+
+    #[test]
+    fn marks_passed_on_retry_as_flaky() {
+        let mut history = FlakyHistoryState::default();
+        let mut action = create_action("a:test", ActionStatus::Passed, None);
+        action.flaky = true;
+
+        history.record(&[action]);
+
+        assert_eq!(history.targets["a:test"].runs, vec![true]);
+    }
+
+    #[test]
+    fn marks_passed_with_same_hash_as_flaky() {
+        let mut history = FlakyHistoryState::default();
+
+        history.record(&[create_action("a:test", ActionStatus::Failed, Some("abc"))]);
+        history.record(&[create_action("a:test", ActionStatus::Passed, Some("abc"))]);
+
+        assert_eq!(history.targets["a:test"].runs, vec![false, true]);
+        assert_eq!(history.targets["a:test"].last_failed_hash, None);
+    }
+
+    #[test]
+    fn doesnt_mark_passed_with_different_hash_as_flaky() {
+        let mut history = FlakyHistoryState::default();
+
+        history.record(&[create_action("a:test", ActionStatus::Failed, Some("abc"))]);
+        history.record(&[create_action("a:test", ActionStatus::Passed, Some("xyz"))]);
+
+        assert_eq!(history.targets["a:test"].runs, vec![false, false]);
+    }
+
+    #[test]
+    fn drops_oldest_runs() {
+        let mut history = FlakyHistoryState::default();
+        let mut action = create_action("a:test", ActionStatus::Passed, None);
+        action.flaky = true;
+
+        history.record(&[action]);
+
+        for _ in 0..MAX_RUNS {
+            history.record(&[create_action("a:test", ActionStatus::Passed, None)]);
+        }
+
+        assert_eq!(history.targets["a:test"].runs.len(), MAX_RUNS);
+        assert_eq!(history.targets["a:test"].flaky_count(), 0);
+    }
+
+    #[test]
+    fn calculates_score() {
+        let mut history = FlakyHistoryState::default();
+        history.targets.entry("a:test".into()).or_default().runs =
+            vec![true, false, false, false];
+
+        assert_eq!(history.get_score("a:test"), Some(0.25));
+        assert_eq!(history.get_score("a:build"), None);
+    }
+
+    #[test]
+    fn is_flaky_when_threshold_reached() {
+        let mut history = FlakyHistoryState::default();
+        history.targets.entry("a:test".into()).or_default().runs =
+            vec![true, false, false, false];
+        history.targets.entry("a:build".into()).or_default().runs = vec![false, false];
+
+        assert!(history.is_flaky("a:test", 0.1));
+        assert!(history.is_flaky("a:test", 0.25));
+        assert!(!history.is_flaky("a:test", 0.5));
+        assert!(!history.is_flaky("a:build", 0.0));
+        assert!(!history.is_flaky("a:unknown", 0.0));
+    }
+}
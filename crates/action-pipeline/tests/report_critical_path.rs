@@ -0,0 +1,192 @@
+use moon_action::*;
+use moon_action_pipeline::reports::critical_path::{ActionDependencies, analyze_critical_path};
+use moon_toolchain::Runtime;
+use std::sync::Arc;
+use std::time::Duration;
+
+fn create_action(index: usize, label: &str, secs: u64) -> Action {
+    Action {
+        duration: Some(Duration::from_secs(secs)),
+        label: label.into(),
+        node: Arc::new(ActionNode::run_task(RunTaskNode::new(
+            label.into(),
+            Runtime::system(),
+        ))),
+        node_index: index,
+        ..Action::default()
+    }
+}
+
+fn create_deps<const N: usize>(items: [(usize, Vec<usize>); N]) -> ActionDependencies {
+    ActionDependencies::from_iter(items)
+}
+
+fn get_labels(items: &[CriticalPathItem]) -> Vec<&str> {
+    items.iter().map(|item| item.label.as_str()).collect()
+}
+
+mod critical_path {
+    use super::*;
+
+    #[test]
+    fn returns_empty_when_no_actions() {
+        let analysis = analyze_critical_path(
+            &[],
+            &ActionDependencies::default(),
+            4,
+            &Duration::from_secs(1),
+        );
+
+        assert_eq!(
+            analysis,
+            PipelineAnalysis {
+                concurrency: 4,
+                ..PipelineAnalysis::default()
+            }
+        );
+    }
+
+    #[test]
+    fn follows_longest_chain() {
+        // a -> b -> d
+        // a -> c -> d
+        let analysis = analyze_critical_path(
+            &[
+                create_action(0, "p:a", 1),
+                create_action(1, "p:b", 5),
+                create_action(2, "p:c", 2),
+                create_action(3, "p:d", 1),
+            ],
+            &create_deps([(1, vec![0]), (2, vec![0]), (3, vec![1, 2])]),
+            4,
+            &Duration::from_secs(8),
+        );
+
+        assert_eq!(
+            get_labels(&analysis.critical_path),
+            vec!["p:a", "p:b", "p:d"]
+        );
+        assert_eq!(analysis.critical_path_duration, Duration::from_secs(7));
+        assert_eq!(analysis.total_duration, Duration::from_secs(9));
+    }
+
+    #[test]
+    fn calculates_minimum_from_concurrency() {
+        let actions = [
+            create_action(0, "p:a", 4),
+            create_action(1, "p:b", 4),
+            create_action(2, "p:c", 4),
+            create_action(3, "p:d", 4),
+        ];
+        let deps = ActionDependencies::default();
+
+        // Bound by the critical path
+        let analysis = analyze_critical_path(&actions, &deps, 4, &Duration::from_secs(4));
+
+        assert_eq!(analysis.minimum_duration, Duration::from_secs(4));
+        assert_eq!(analysis.parallelism, 4.0);
+
+        // Bound by the total work
+        let analysis = analyze_critical_path(&actions, &deps, 2, &Duration::from_secs(8));
+
+        assert_eq!(analysis.minimum_duration, Duration::from_secs(8));
+        assert_eq!(analysis.parallelism, 2.0);
+    }
+
+    #[test]
+    fn treats_zero_concurrency_as_one() {
+        let analysis = analyze_critical_path(
+            &[create_action(0, "p:a", 2), create_action(1, "p:b", 2)],
+            &ActionDependencies::default(),
+            0,
+            &Duration::from_secs(4),
+        );
+
+        assert_eq!(analysis.concurrency, 1);
+        assert_eq!(analysis.minimum_duration, Duration::from_secs(4));
+    }
+
+    #[test]
+    fn ignores_dependencies_that_didnt_run() {
+        let analysis = analyze_critical_path(
+            &[create_action(1, "p:b", 3)],
+            &create_deps([(1, vec![0])]),
+            1,
+            &Duration::from_secs(3),
+        );
+
+        assert_eq!(get_labels(&analysis.critical_path), vec!["p:b"]);
+    }
+
+    #[test]
+    fn ignores_persistent_tasks() {
+        let mut persistent = RunTaskNode::new("p:dev".into(), Runtime::system());
+        persistent.persistent = true;
+
+        let analysis = analyze_critical_path(
+            &[
+                create_action(0, "p:a", 2),
+                Action {
+                    duration: Some(Duration::from_secs(100)),
+                    label: "p:dev".into(),
+                    node: Arc::new(ActionNode::run_task(persistent)),
+                    node_index: 1,
+                    ..Action::default()
+                },
+            ],
+            &create_deps([(1, vec![0])]),
+            2,
+            &Duration::from_secs(100),
+        );
+
+        assert_eq!(get_labels(&analysis.critical_path), vec!["p:a"]);
+        assert_eq!(analysis.total_duration, Duration::from_secs(2));
+    }
+
+    #[test]
+    fn ranks_speedups_by_savings() {
+        // a -> b
+        // c (parallel)
+        let analysis = analyze_critical_path(
+            &[
+                create_action(0, "p:a", 2),
+                create_action(1, "p:b", 6),
+                create_action(2, "p:c", 5),
+            ],
+            &create_deps([(1, vec![0])]),
+            4,
+            &Duration::from_secs(8),
+        );
+
+        assert_eq!(
+            analysis.speedups,
+            vec![
+                // Without b, c becomes the critical path (8s -> 5s)
+                ActionSpeedup {
+                    duration: Duration::from_secs(6),
+                    label: "p:b".into(),
+                    savings: Duration::from_secs(3),
+                },
+                ActionSpeedup {
+                    duration: Duration::from_secs(2),
+                    label: "p:a".into(),
+                    savings: Duration::from_secs(2),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn excludes_speedups_without_savings() {
+        // Two chains of equal length, so speeding up one doesn't help
+        let analysis = analyze_critical_path(
+            &[create_action(0, "p:a", 3), create_action(1, "p:b", 3)],
+            &ActionDependencies::default(),
+            2,
+            &Duration::from_secs(3),
+        );
+
+        assert_eq!(analysis.critical_path_duration, Duration::from_secs(3));
+        assert!(analysis.speedups.is_empty());
+    }
+}
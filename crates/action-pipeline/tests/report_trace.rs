@@ -0,0 +1,229 @@
+use moon_action::*;
+use moon_action_pipeline::reports::trace::{ChromeTrace, OtlpTrace, assign_tracks};
+use moon_time::chrono::{NaiveDate, NaiveDateTime, TimeDelta};
+use std::time::Duration;
+
+fn create_start() -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2025, 1, 1)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap()
+}
+
+fn create_action(label: &str, offset_ms: i64, duration_ms: u64) -> Action {
+    Action {
+        duration: Some(Duration::from_millis(duration_ms)),
+        label: label.into(),
+        started_at: Some(create_start() + TimeDelta::milliseconds(offset_ms)),
+        status: ActionStatus::Passed,
+        ..Action::default()
+    }
+}
+
+fn create_operation(offset_ms: i64, duration_ms: u64) -> Operation {
+    let mut op = Operation::task_execution("echo");
+    op.started_at = create_start() + TimeDelta::milliseconds(offset_ms);
+    op.duration = Some(Duration::from_millis(duration_ms));
+    op.status = ActionStatus::Passed;
+    op
+}
+
+mod trace {
+    use super::*;
+
+    mod tracks {
+        use super::*;
+
+        #[test]
+        fn ignores_unstarted_actions() {
+            let actions = vec![Action::default(), create_action("a", 0, 10)];
+
+            let tracks = assign_tracks(&actions);
+
+            assert_eq!(tracks.len(), 1);
+            assert_eq!(tracks[0].0.action.label, "a");
+        }
+
+        #[test]
+        fn reuses_tracks_when_not_overlapping() {
+            let actions = vec![
+                create_action("a", 0, 1000),
+                create_action("b", 0, 2000),
+                create_action("c", 1000, 1000),
+                create_action("d", 1500, 100),
+            ];
+
+            let tracks = assign_tracks(&actions)
+                .into_iter()
+                .map(|(item, track)| (item.action.label.as_str(), track))
+                .collect::<Vec<_>>();
+
+            assert_eq!(tracks, vec![("a", 1), ("b", 2), ("c", 1), ("d", 3)]);
+        }
+
+        #[test]
+        fn falls_back_to_finished_at() {
+            let mut action = create_action("a", 0, 0);
+            action.duration = None;
+            action.finished_at = Some(create_start() + TimeDelta::milliseconds(500));
+
+            let actions = vec![action];
+            let tracks = assign_tracks(&actions);
+
+            assert_eq!(tracks[0].0.duration, Duration::from_millis(500));
+        }
+    }
+
+    mod chrome {
+        use super::*;
+
+        #[test]
+        fn only_includes_process_name_when_empty() {
+            let trace = ChromeTrace::from_actions(&[]);
+
+            assert_eq!(trace.trace_events.len(), 1);
+            assert_eq!(trace.trace_events[0].name, "process_name");
+            assert_eq!(trace.display_time_unit, "ms");
+        }
+
+        #[test]
+        fn names_each_slot() {
+            let trace = ChromeTrace::from_actions(&[
+                create_action("a", 0, 1000),
+                create_action("b", 500, 1000),
+            ]);
+
+            let names = trace
+                .trace_events
+                .iter()
+                .filter(|event| event.name == "thread_name")
+                .map(|event| (event.tid, event.args["name"].as_str().unwrap()))
+                .collect::<Vec<_>>();
+
+            assert_eq!(names, vec![(1, "Slot 1"), (2, "Slot 2")]);
+        }
+
+        #[test]
+        fn uses_relative_microseconds() {
+            let mut action = create_action("a", 250, 1000);
+            action.operations.push(create_operation(300, 500));
+
+            let trace = ChromeTrace::from_actions(&[create_action("first", 0, 100), action]);
+
+            let events = trace
+                .trace_events
+                .iter()
+                .filter(|event| event.ph == "X")
+                .map(|event| (event.name.as_str(), event.cat.as_str(), event.ts, event.dur))
+                .collect::<Vec<_>>();
+
+            assert_eq!(
+                events,
+                vec![
+                    ("first", "action", 0, Some(100_000)),
+                    ("a", "action", 250_000, Some(1_000_000)),
+                    ("Task execution", "operation", 300_000, Some(500_000)),
+                ]
+            );
+        }
+
+        #[test]
+        fn places_operations_on_action_track() {
+            let mut action = create_action("b", 500, 1000);
+            let mut op = create_operation(500, 1000);
+            op.operations.push(create_operation(600, 100));
+            action.operations.push(op);
+
+            let trace = ChromeTrace::from_actions(&[create_action("a", 0, 1000), action]);
+
+            let tids = trace
+                .trace_events
+                .iter()
+                .filter(|event| event.cat == "operation")
+                .map(|event| event.tid)
+                .collect::<Vec<_>>();
+
+            assert_eq!(tids, vec![2, 2]);
+        }
+
+        #[test]
+        fn includes_command_in_args() {
+            let mut action = create_action("a", 0, 1000);
+            action.operations.push(create_operation(0, 1000));
+
+            let trace = ChromeTrace::from_actions(&[action]);
+            let event = trace.trace_events.last().unwrap();
+
+            assert_eq!(event.args["command"], "echo");
+            assert_eq!(event.args["status"], "passed");
+        }
+    }
+
+    mod otlp {
+        use super::*;
+
+        fn get_spans(trace: &OtlpTrace) -> &Vec<starbase_utils::json::JsonValue> {
+            trace.resource_spans[0]["scopeSpans"][0]["spans"]
+                .as_array()
+                .unwrap()
+        }
+
+        #[test]
+        fn has_no_spans_when_empty() {
+            let trace = OtlpTrace::from_actions(&[], &ActionPipelineStatus::Completed);
+
+            assert!(get_spans(&trace).is_empty());
+        }
+
+        #[test]
+        fn nests_spans_under_parents() {
+            let mut action = create_action("a", 0, 1000);
+            action.operations.push(create_operation(100, 500));
+
+            let trace = OtlpTrace::from_actions(&[action], &ActionPipelineStatus::Completed);
+            let spans = get_spans(&trace);
+
+            assert_eq!(spans.len(), 3);
+            assert_eq!(spans[0]["name"], "pipeline");
+            assert_eq!(spans[0]["parentSpanId"], "");
+            assert_eq!(spans[1]["name"], "a");
+            assert_eq!(spans[1]["parentSpanId"], spans[0]["spanId"]);
+            assert_eq!(spans[2]["name"], "Task execution");
+            assert_eq!(spans[2]["parentSpanId"], spans[1]["spanId"]);
+
+            for span in spans {
+                assert_eq!(span["traceId"], spans[0]["traceId"]);
+                assert_eq!(span["traceId"].as_str().unwrap().len(), 32);
+                assert_eq!(span["spanId"].as_str().unwrap().len(), 16);
+            }
+        }
+
+        #[test]
+        fn spans_pipeline_across_all_actions() {
+            let trace = OtlpTrace::from_actions(
+                &[create_action("a", 0, 1000), create_action("b", 500, 2000)],
+                &ActionPipelineStatus::Completed,
+            );
+            let pipeline = &get_spans(&trace)[0];
+            let start = create_start().and_utc().timestamp_nanos_opt().unwrap();
+
+            assert_eq!(pipeline["startTimeUnixNano"], start.to_string());
+            assert_eq!(
+                pipeline["endTimeUnixNano"],
+                (start + 2_500_000_000).to_string()
+            );
+        }
+
+        #[test]
+        fn marks_failures_as_errors() {
+            let mut action = create_action("a", 0, 1000);
+            action.status = ActionStatus::Failed;
+
+            let trace = OtlpTrace::from_actions(&[action], &ActionPipelineStatus::Aborted);
+            let spans = get_spans(&trace);
+
+            assert_eq!(spans[0]["status"]["code"], 2);
+            assert_eq!(spans[1]["status"]["code"], 2);
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use moon_action_pipeline::resource_pool::*;
+use moon_common::Id;
+use std::collections::BTreeMap;
+
+fn create_map(list: &[(&str, u32)]) -> BTreeMap<Id, u32> {
+    list.iter()
+        .map(|(id, amount)| (Id::raw(id), *amount))
+        .collect()
+}
+
+mod resource_pool {
+    use super::*;
+
+    #[test]
+    fn defaults_cpu_to_concurrency() {
+        let pool = ResourcePool::new(8, BTreeMap::new());
+
+        assert_eq!(pool.get_capacity(CPU_RESOURCE), Some(8));
+        assert_eq!(pool.get_capacity(MEMORY_RESOURCE), None);
+    }
+
+    #[test]
+    fn can_override_cpu_capacity() {
+        let pool = ResourcePool::new(8, create_map(&[("cpu", 4), ("memory", 1024)]));
+
+        assert_eq!(pool.get_capacity(CPU_RESOURCE), Some(4));
+        assert_eq!(pool.get_capacity(MEMORY_RESOURCE), Some(1024));
+    }
+
+    #[test]
+    fn requires_1_cpu_by_default() {
+        let pool = ResourcePool::new(8, BTreeMap::new());
+
+        assert_eq!(
+            pool.resolve_requirements(&BTreeMap::new()),
+            create_map(&[("cpu", 1)])
+        );
+        assert_eq!(
+            pool.resolve_requirements(&create_map(&[("memory", 512)])),
+            create_map(&[("cpu", 1), ("memory", 512)])
+        );
+    }
+
+    #[test]
+    fn clamps_requirements_to_capacity() {
+        let pool = ResourcePool::new(4, create_map(&[("memory", 1024)]));
+
+        assert_eq!(
+            pool.resolve_requirements(&create_map(&[("cpu", 16), ("memory", 4096)])),
+            create_map(&[("cpu", 4), ("memory", 1024)])
+        );
+    }
+
+    #[test]
+    fn packs_light_jobs_around_heavy_jobs() {
+        let mut pool = ResourcePool::new(8, BTreeMap::new());
+
+        assert!(pool.try_reserve(0, &create_map(&[("cpu", 6)])));
+        assert!(pool.try_reserve(1, &BTreeMap::new()));
+        assert!(pool.try_reserve(2, &BTreeMap::new()));
+        assert!(!pool.try_reserve(3, &BTreeMap::new()));
+        assert!(!pool.try_reserve(4, &create_map(&[("cpu", 4)])));
+
+        assert_eq!(pool.get_in_use(CPU_RESOURCE), 8);
+    }
+
+    #[test]
+    fn limits_by_memory() {
+        let mut pool = ResourcePool::new(8, create_map(&[("memory", 4096)]));
+
+        assert!(pool.try_reserve(0, &create_map(&[("memory", 3000)])));
+        assert!(!pool.try_reserve(1, &create_map(&[("memory", 2000)])));
+        assert!(pool.try_reserve(2, &create_map(&[("memory", 1000)])));
+
+        assert_eq!(pool.get_in_use(MEMORY_RESOURCE), 4000);
+    }
+
+    #[test]
+    fn limits_by_custom_pools() {
+        let mut pool = ResourcePool::new(8, create_map(&[("gpu-license", 1)]));
+
+        assert!(pool.try_reserve(0, &create_map(&[("gpu-license", 1)])));
+        assert!(!pool.try_reserve(1, &create_map(&[("gpu-license", 1)])));
+        assert!(pool.try_reserve(2, &BTreeMap::new()));
+    }
+
+    #[test]
+    fn doesnt_limit_undefined_pools() {
+        let mut pool = ResourcePool::new(8, BTreeMap::new());
+
+        assert!(pool.try_reserve(0, &create_map(&[("memory", 100_000)])));
+        assert!(pool.try_reserve(1, &create_map(&[("gpu-license", 50)])));
+        assert!(pool.try_reserve(2, &create_map(&[("gpu-license", 50)])));
+    }
+
+    #[test]
+    fn releases_reservations() {
+        let mut pool = ResourcePool::new(2, create_map(&[("gpu-license", 1)]));
+
+        assert!(pool.try_reserve(0, &create_map(&[("cpu", 2), ("gpu-license", 1)])));
+        assert!(!pool.try_reserve(1, &BTreeMap::new()));
+
+        pool.release(0);
+
+        assert_eq!(pool.get_in_use(CPU_RESOURCE), 0);
+        assert_eq!(pool.get_in_use("gpu-license"), 0);
+        assert!(pool.try_reserve(1, &BTreeMap::new()));
+
+        // Unknown indices are ignored
+        pool.release(100);
+
+        assert_eq!(pool.get_in_use(CPU_RESOURCE), 1);
+    }
+
+    #[test]
+    fn doesnt_reserve_partially() {
+        let mut pool = ResourcePool::new(8, create_map(&[("memory", 1024)]));
+
+        assert!(pool.try_reserve(0, &create_map(&[("memory", 1024)])));
+        assert!(!pool.try_reserve(1, &create_map(&[("cpu", 2), ("memory", 1)])));
+
+        assert_eq!(pool.get_in_use(CPU_RESOURCE), 1);
+    }
+}
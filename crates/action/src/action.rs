@@ -1,5 +1,6 @@
 use crate::action_node::ActionNode;
 use crate::operation_list::OperationList;
+use crate::test_report::TestReport;
 use moon_time::chrono::NaiveDateTime;
 use moon_time::now_timestamp;
 use serde::{Deserialize, Serialize};
@@ -60,12 +61,17 @@ pub struct Action {
 
     pub operations: OperationList,
 
+    pub quarantined: bool,
+
     pub started_at: Option<NaiveDateTime>,
 
     #[serde(skip)]
     pub start_time: Option<Instant>,
 
     pub status: ActionStatus,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub test_report: Option<TestReport>,
 }
 
 impl Action {
@@ -84,7 +90,9 @@ impl Action {
             operations: OperationList::default(),
             started_at: None,
             start_time: None,
+            quarantined: false,
             status: ActionStatus::Running,
+            test_report: None,
         }
     }
 
@@ -3,9 +3,13 @@ mod action_node;
 mod operation;
 mod operation_list;
 mod operation_meta;
+mod pipeline_analysis;
+mod test_report;
 
 pub use action::*;
 pub use action_node::*;
 pub use operation::*;
 pub use operation_list::*;
 pub use operation_meta::*;
+pub use pipeline_analysis::*;
+pub use test_report::*;
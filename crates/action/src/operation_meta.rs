@@ -1,3 +1,4 @@
+use moon_common::path::WorkspaceRelativePathBuf;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::process::ExitStatus;
@@ -19,6 +20,22 @@ pub struct OperationMetaSync {
     pub label: String,
 }
 
+#[derive(Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct OperationMetaSandbox {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub undeclared_inputs: Vec<WorkspaceRelativePathBuf>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub undeclared_outputs: Vec<WorkspaceRelativePathBuf>,
+}
+
+impl OperationMetaSandbox {
+    pub fn has_violations(&self) -> bool {
+        !self.undeclared_inputs.is_empty() || !self.undeclared_outputs.is_empty()
+    }
+}
+
 #[derive(Debug, Default, Deserialize, PartialEq, Serialize)]
 #[serde(default, rename_all = "camelCase")]
 pub struct OperationMetaOutput {
@@ -31,6 +48,9 @@ pub struct OperationMetaOutput {
     #[serde(skip)]
     pub exit_status: Option<ExitStatus>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sandbox: Option<OperationMetaSandbox>,
+
     #[serde(skip)]
     pub stderr: Option<Arc<String>>,
 
@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CriticalPathItem {
+    pub duration: Duration,
+    pub label: String,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionSpeedup {
+    pub duration: Duration,
+    pub label: String,
+
+    /// How much the pipeline would be shortened if this action took no time.
+    pub savings: Duration,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PipelineAnalysis {
+    /// Maximum number of actions that may run in parallel.
+    pub concurrency: usize,
+
+    /// Chain of dependent actions with the longest cumulative duration,
+    /// ordered from the first action ran to the last.
+    pub critical_path: Vec<CriticalPathItem>,
+
+    /// Cumulative duration of all actions in the critical path.
+    pub critical_path_duration: Duration,
+
+    /// Theoretical minimum wall time at the configured concurrency,
+    /// assuming actions were scheduled perfectly.
+    pub minimum_duration: Duration,
+
+    /// Average number of actions that were running at the same time.
+    pub parallelism: f32,
+
+    /// Actions on the critical path whose speedup would
+    /// shorten the pipeline the most.
+    pub speedups: Vec<ActionSpeedup>,
+
+    /// Cumulative duration of all actions.
+    pub total_duration: Duration,
+}
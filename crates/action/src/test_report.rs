@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Copy, Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TestCaseStatus {
+    Failed,
+    #[default]
+    Passed,
+    Skipped,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TestCase {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<Duration>,
+
+    /// Failure or skip message, if provided by the report.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+
+    pub name: String,
+
+    pub status: TestCaseStatus,
+
+    /// Suite or class name the test belongs to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suite: Option<String>,
+}
+
+impl TestCase {
+    pub fn get_label(&self) -> String {
+        match &self.suite {
+            Some(suite) => format!("{suite} › {}", self.name),
+            None => self.name.clone(),
+        }
+    }
+}
+
+/// Results of tests ran by a task, as parsed from a JUnit or TAP report.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TestReport {
+    pub failed: usize,
+
+    pub passed: usize,
+
+    pub skipped: usize,
+
+    pub tests: Vec<TestCase>,
+}
+
+impl TestReport {
+    pub fn add_test(&mut self, test: TestCase) {
+        match test.status {
+            TestCaseStatus::Failed => self.failed += 1,
+            TestCaseStatus::Passed => self.passed += 1,
+            TestCaseStatus::Skipped => self.skipped += 1,
+        };
+
+        self.tests.push(test);
+    }
+
+    pub fn extend(&mut self, report: TestReport) {
+        for test in report.tests {
+            self.add_test(test);
+        }
+    }
+
+    pub fn get_failed_tests(&self) -> impl Iterator<Item = &TestCase> {
+        self.tests
+            .iter()
+            .filter(|test| test.status == TestCaseStatus::Failed)
+    }
+
+    pub fn has_failed(&self) -> bool {
+        self.failed > 0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tests.is_empty()
+    }
+
+    pub fn total(&self) -> usize {
+        self.tests.len()
+    }
+}
@@ -24,17 +24,34 @@ pub async fn run_task(
 
     // Must be set before running the task in case it fails and
     // and error is bubbled up the stack
-    action.allow_failure = task.options.allow_failure;
+    let quarantined = action_context.quarantined_targets.contains(&task.target);
 
-    let result = TaskRunner::new(&app_context, &project, &task)?
-        .run(&action_context, &action.node)
-        .await?;
+    action.allow_failure = task.options.allow_failure || quarantined;
+
+    let mut runner = TaskRunner::new(&app_context, &project, &task)?;
+
+    runner.set_dependencies(
+        task.deps
+            .iter()
+            .map(|dep| workspace_graph.get_task(&dep.target))
+            .collect::<miette::Result<Vec<_>>>()?,
+    );
+
+    let result = runner.run(&action_context, &action.node).await?;
 
     action.flaky = result.operations.is_flaky();
     action.status = result.operations.get_final_status();
     action.operations = result.operations;
+    action.test_report = result.test_report;
+
+    if action.has_failed() && quarantined && !task.options.allow_failure {
+        action.quarantined = true;
 
-    if action.has_failed() && action.allow_failure {
+        warn!(
+            "Task {} has failed, but is known to be flaky and has been quarantined, continuing pipeline",
+            color::label(&task.target),
+        );
+    } else if action.has_failed() && action.allow_failure {
         warn!(
             "Task {} has failed, but is marked to allow failures, continuing pipeline",
             color::label(&task.target),
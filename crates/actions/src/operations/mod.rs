@@ -1,9 +1,11 @@
 mod plugin_compat;
+mod resolve_codeowners;
 mod sync_codeowners;
 mod sync_config_schemas;
 mod sync_vcs_hooks;
 
 pub use plugin_compat::*;
+pub use resolve_codeowners::*;
 pub use sync_codeowners::*;
 pub use sync_config_schemas::*;
 pub use sync_vcs_hooks::*;
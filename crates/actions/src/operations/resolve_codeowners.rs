@@ -0,0 +1,31 @@
+use super::sync_codeowners::get_sorted_projects;
+use moon_app_context::AppContext;
+use moon_codeowners::CodeownersResolver;
+use moon_workspace_graph::WorkspaceGraph;
+use tracing::instrument;
+
+#[instrument(skip_all)]
+pub fn resolve_codeowners(
+    app_context: &AppContext,
+    workspace_graph: &WorkspaceGraph,
+) -> miette::Result<CodeownersResolver> {
+    let codeowners_config = &app_context.workspace_config.codeowners;
+    let mut resolver = CodeownersResolver::new(
+        &app_context.workspace_root,
+        app_context.workspace_config.vcs.provider,
+    );
+
+    // Rules must be added in the same order as the generated file
+    resolver.add_workspace_entries(codeowners_config)?;
+
+    for project in get_sorted_projects(app_context, workspace_graph) {
+        resolver.add_project_entry(
+            &project.id,
+            project.source.as_str(),
+            &project.config.owners,
+            codeowners_config,
+        )?;
+    }
+
+    Ok(resolver)
+}
@@ -1,10 +1,27 @@
 use moon_app_context::AppContext;
 use moon_codeowners::{CodeownersGenerator, CodeownersHash};
 use moon_config::CodeownersOrderBy;
+use moon_project::Project;
 use moon_workspace_graph::WorkspaceGraph;
 use std::path::PathBuf;
 use tracing::instrument;
 
+// Sort the projects based on config
+pub(crate) fn get_sorted_projects<'graph>(
+    app_context: &AppContext,
+    workspace_graph: &'graph WorkspaceGraph,
+) -> Vec<&'graph Project> {
+    let mut projects = workspace_graph.projects.get_all_unexpanded();
+    let order_by = app_context.workspace_config.codeowners.order_by;
+
+    projects.sort_by(|a, d| match order_by {
+        CodeownersOrderBy::FileSource => a.source.cmp(&d.source),
+        CodeownersOrderBy::ProjectName => a.id.cmp(&d.id),
+    });
+
+    projects
+}
+
 #[instrument(skip_all)]
 pub async fn sync_codeowners(
     app_context: &AppContext,
@@ -16,14 +33,7 @@ pub async fn sync_codeowners(
         app_context.workspace_config.vcs.provider,
     )?;
 
-    // Sort the projects based on config
-    let mut projects = workspace_graph.projects.get_all_unexpanded();
-    let order_by = app_context.workspace_config.codeowners.order_by;
-
-    projects.sort_by(|a, d| match order_by {
-        CodeownersOrderBy::FileSource => a.source.cmp(&d.source),
-        CodeownersOrderBy::ProjectName => a.id.cmp(&d.id),
-    });
+    let projects = get_sorted_projects(app_context, workspace_graph);
 
     // Generate a hash for the codeowners file
     let mut codeowners_hash = CodeownersHash::new(&app_context.workspace_config.codeowners);
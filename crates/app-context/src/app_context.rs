@@ -1,6 +1,7 @@
 use moon_cache::CacheEngine;
 use moon_config::{ToolchainConfig, Version, WorkspaceConfig};
 use moon_console::Console;
+use moon_extension_plugin::ExtensionRegistry;
 use moon_toolchain_plugin::ToolchainRegistry;
 use moon_vcs::BoxedVcs;
 use std::path::PathBuf;
@@ -20,6 +21,7 @@ pub struct AppContext {
     pub workspace_config: Arc<WorkspaceConfig>,
 
     // Plugins
+    pub extension_registry: Arc<ExtensionRegistry>,
     pub toolchain_registry: Arc<ToolchainRegistry>,
 
     // Paths
@@ -1,5 +1,6 @@
 use crate::app_options::*;
 use crate::commands::bin::BinArgs;
+use crate::commands::cache::CacheCommands;
 use crate::commands::check::CheckArgs;
 use crate::commands::ci::CiArgs;
 use crate::commands::clean::CleanArgs;
@@ -8,18 +9,23 @@ use crate::commands::debug::DebugCommands;
 use crate::commands::docker::DockerCommands;
 use crate::commands::ext::ExtArgs;
 use crate::commands::generate::GenerateArgs;
+use crate::commands::graph::GraphCommands;
 use crate::commands::graph::action::ActionGraphArgs;
 use crate::commands::graph::project::ProjectGraphArgs;
 use crate::commands::graph::task::TaskGraphArgs;
 use crate::commands::init::InitArgs;
+use crate::commands::lsp::LspArgs;
 use crate::commands::migrate::MigrateCommands;
 use crate::commands::node::NodeCommands;
+use crate::commands::owners::OwnersArgs;
 use crate::commands::project::ProjectArgs;
 use crate::commands::query::QueryCommands;
 use crate::commands::run::RunArgs;
 use crate::commands::sync::SyncCommands;
 use crate::commands::task::TaskArgs;
 use crate::commands::templates::TemplatesArgs;
+use crate::commands::watch::WatchArgs;
+use crate::commands::why::WhyArgs;
 use crate::systems::bootstrap;
 use clap::builder::styling::{Color, Style, Styles};
 use clap::{Parser, Subcommand};
@@ -94,6 +100,16 @@ pub enum Commands {
     )]
     ActionGraph(ActionGraphArgs),
 
+    // moon graph <operation>
+    #[command(
+        name = "graph",
+        about = "Operations for inspecting and comparing graphs."
+    )]
+    Graph {
+        #[command(subcommand)]
+        command: GraphCommands,
+    },
+
     // moon project <id>
     #[command(
         name = "project",
@@ -174,6 +190,21 @@ pub enum Commands {
     )]
     Run(RunArgs),
 
+    // moon watch [...targets]
+    #[command(
+        name = "watch",
+        about = "Run one or many project tasks, and re-run affected tasks when files change.",
+        alias = "w"
+    )]
+    Watch(WatchArgs),
+
+    // moon why <target>
+    #[command(
+        name = "why",
+        about = "Explain why a task will run, be skipped, or miss the cache."
+    )]
+    Why(WhyArgs),
+
     // PLUGINS
 
     // moon ext
@@ -182,6 +213,13 @@ pub enum Commands {
 
     // OTHER
 
+    // moon cache <operation>
+    #[command(name = "cache", about = "Operations for managing the cache.")]
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommands,
+    },
+
     // moon clean
     #[command(
         name = "clean",
@@ -199,6 +237,13 @@ pub enum Commands {
         command: DockerCommands,
     },
 
+    // moon lsp
+    #[command(
+        name = "lsp",
+        about = "Start a language server for moon configuration files."
+    )]
+    Lsp(LspArgs),
+
     // moon migrate <operation>
     #[command(
         name = "migrate",
@@ -217,6 +262,14 @@ pub enum Commands {
         skip_touched_files_check: bool,
     },
 
+    // moon owners [...paths]
+    #[command(
+        name = "owners",
+        about = "Resolve the code owners of files, and verify required approvals.",
+        long_about = "Resolve the code owners of files, and verify required approvals. When no paths are provided, the touched files between revisions are used."
+    )]
+    Owners(OwnersArgs),
+
     // moon query <operation>
     #[command(
         name = "query",
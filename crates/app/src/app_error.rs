@@ -57,10 +57,23 @@ pub enum AppError {
     )]
     UnknownExtension { id: Id },
 
+    #[diagnostic(code(app::owners::path_outside_workspace))]
+    #[error(
+        "Unable to resolve owners for {}, as it's outside of the workspace.",
+        .0.style(Style::Path),
+    )]
+    OwnersPathOutsideWorkspace(String),
+
     #[diagnostic(code(app::upgrade::requires_internet))]
     #[error("Upgrading moon requires an internet connection!")]
     UpgradeRequiresInternet,
 
+    #[diagnostic(code(app::graph::diff_requires_git))]
+    #[error(
+        "Diffing graphs requires a Git repository, as the base revision is checked out into a temporary worktree."
+    )]
+    GraphDiffRequiresGit,
+
     #[diagnostic(code(app::id_required))]
     #[error("A project ID is required.")]
     ProjectIdRequired,
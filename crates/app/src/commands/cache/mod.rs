@@ -0,0 +1,14 @@
+mod serve;
+
+pub use serve::*;
+
+use clap::Subcommand;
+
+#[derive(Clone, Debug, Subcommand)]
+pub enum CacheCommands {
+    #[command(
+        name = "serve",
+        about = "Start a local remote cache server, backed by the file system."
+    )]
+    Serve(CacheServeArgs),
+}
@@ -0,0 +1,88 @@
+use crate::session::MoonSession;
+use clap::Args;
+use iocraft::prelude::element;
+use miette::IntoDiagnostic;
+use moon_common::size::parse_max_size;
+use moon_console::ui::{Container, Notice, StyledText, Variant};
+use moon_process::ProcessRegistry;
+use moon_remote_server::{DiskStorage, RemoteServer, RemoteServerError};
+use starbase::AppResult;
+use starbase_utils::fs;
+use std::path::PathBuf;
+use tokio::net::TcpListener;
+use tracing::{debug, instrument};
+
+#[derive(Args, Clone, Debug)]
+pub struct CacheServeArgs {
+    #[arg(
+        long,
+        help = "Directory to store cached artifacts in",
+        help_heading = "Storage"
+    )]
+    pub dir: Option<PathBuf>,
+
+    #[arg(
+        long,
+        default_value = "10GB",
+        help = "Maximum size of the cache before evicting least recently used artifacts",
+        help_heading = "Storage"
+    )]
+    pub max_size: String,
+
+    #[arg(
+        long,
+        help = "Execute actions on this machine through the Execution service"
+    )]
+    pub execute: bool,
+
+    #[arg(long, default_value = "127.0.0.1", help = "Host to bind the server to")]
+    pub host: String,
+
+    #[arg(long, default_value_t = 9092, help = "Port to bind the server to")]
+    pub port: u16,
+}
+
+#[instrument(skip_all)]
+pub async fn serve(session: MoonSession, args: CacheServeArgs) -> AppResult {
+    let dir = args
+        .dir
+        .clone()
+        .unwrap_or_else(|| session.moon_env.store_root.join("remote-cache"));
+    let max_size = parse_max_size(&args.max_size)
+        .map_err(|_| RemoteServerError::InvalidSize(args.max_size.clone()))?;
+
+    fs::create_dir_all(&dir)?;
+
+    let mut server = RemoteServer::new(DiskStorage::new(&dir, max_size)?);
+    server.execute = args.execute;
+
+    let listener = TcpListener::bind((args.host.as_str(), args.port))
+        .await
+        .into_diagnostic()?;
+    let address = listener.local_addr().into_diagnostic()?;
+
+    session.console.render(element! {
+        Container {
+            Notice(variant: Variant::Info) {
+                StyledText(
+                    content: format!(
+                        "Remote cache listening on <url>grpc://{address}</url> and <url>http://{address}</url>, storing artifacts in <path>{}</path>",
+                        dir.display()
+                    )
+                )
+            }
+        }
+    })?;
+
+    let mut signal_receiver = ProcessRegistry::instance().receive_signal();
+
+    server
+        .serve(listener, async move {
+            let _ = signal_receiver.recv().await;
+
+            debug!("Received signal, shutting down remote cache server");
+        })
+        .await?;
+
+    Ok(None)
+}
@@ -3,25 +3,73 @@ use crate::components::run_action_pipeline;
 use crate::queries::touched_files::{QueryTouchedFilesOptions, query_touched_files};
 use crate::session::MoonSession;
 use ci_env::CiOutput;
-use clap::Args;
+use clap::builder::RangedU64ValueParser;
+use clap::{ArgGroup, Args, ValueEnum};
+use moon_action::{ActionNode, RunTaskNode};
 use moon_action_context::ActionContext;
-use moon_action_graph::{ActionGraph, RunRequirements};
+use moon_action_graph::{ActionGraph, ActionGraphShard, RunRequirements};
+use moon_action_pipeline::duration_history::DurationHistoryState;
 use moon_affected::{DownstreamScope, UpstreamScope};
 use moon_common::path::WorkspaceRelativePathBuf;
 use moon_console::Console;
-use moon_task::TargetLocator;
+use moon_feature_flags::glob_walk_with_options;
+use moon_task::{Target, TargetLocator};
 use moon_workspace_graph::WorkspaceGraph;
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::Serialize;
 use starbase::AppResult;
 use starbase_styles::color;
+use starbase_utils::glob::GlobWalkOptions;
+use starbase_utils::json;
+use std::str::FromStr;
 use std::sync::Arc;
-use tracing::instrument;
+use tracing::{instrument, warn};
 
 type TargetList = Vec<TargetLocator>;
 
 const HEADING_PARALLELISM: &str = "Parallelism and distribution";
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ShardLocator {
+    pub index: usize,
+    pub total: usize,
+}
+
+impl FromStr for ShardLocator {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let error = || format!("Invalid shard \"{value}\", expected the format N/M.");
+
+        let (index, total) = value.split_once('/').ok_or_else(error)?;
+        let index = index.trim().parse::<usize>().map_err(|_| error())?;
+        let total = total.trim().parse::<usize>().map_err(|_| error())?;
+
+        if total == 0 || index == 0 || index > total {
+            return Err(format!(
+                "Invalid shard \"{value}\", the index must be between 1 and the total."
+            ));
+        }
+
+        Ok(Self { index, total })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub enum CiPlanFormat {
+    #[default]
+    Json,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CiPlan {
+    shards: Vec<ActionGraphShard>,
+    total: usize,
+}
+
 #[derive(Args, Clone, Debug)]
+#[command(group(ArgGroup::new("shards").args(["shard", "shard_count"])))]
 pub struct CiArgs {
     #[arg(help = "List of targets to run")]
     targets: Vec<TargetLocator>,
@@ -37,17 +85,49 @@ pub struct CiArgs {
 
     #[arg(long = "jobTotal", help = "Total amount of jobs to run", help_heading = HEADING_PARALLELISM)]
     job_total: Option<usize>,
+
+    #[arg(
+        long,
+        help = "Run a shard of the affected tasks, in the format of N/M",
+        help_heading = HEADING_PARALLELISM,
+        conflicts_with_all = ["job", "job_total"],
+    )]
+    shard: Option<ShardLocator>,
+
+    #[arg(
+        long = "shardCount",
+        alias = "shard-count",
+        help = "Total amount of shards to plan, when not running a shard",
+        help_heading = HEADING_PARALLELISM,
+        value_parser = RangedU64ValueParser::<usize>::new().range(1..),
+        requires = "plan",
+    )]
+    shard_count: Option<usize>,
+
+    #[arg(
+        long,
+        value_enum,
+        help = "Print the plan of all shards instead of running tasks",
+        help_heading = HEADING_PARALLELISM,
+        requires = "shards",
+    )]
+    plan: Option<CiPlanFormat>,
 }
 
 struct CiConsole {
     inner: Arc<Console>,
     output: CiOutput,
     last_title: String,
+    // When printing a plan, stdout must only contain the plan
+    silent: bool,
 }
 
 impl CiConsole {
     pub fn write_line<T: AsRef<[u8]>>(&self, data: T) -> miette::Result<()> {
-        self.inner.out.write_line(data)?;
+        if !self.silent {
+            self.inner.out.write_line(data)?;
+        }
+
         Ok(())
     }
 
@@ -198,15 +278,162 @@ fn distribute_targets_across_jobs(
     Ok(batched_targets)
 }
 
-/// Generate a dependency graph with the runnable targets.
-async fn generate_action_graph(
+/// Weigh a task for sharding, based on its durations from previous runs,
+/// or the amount of input files when it has never ran.
+struct TaskWeigher {
+    file_counts: FxHashMap<Target, u64>,
+    history: DurationHistoryState,
+    millis_per_file: u64,
+}
+
+impl TaskWeigher {
+    fn new(
+        session: &MoonSession,
+        workspace_graph: &WorkspaceGraph,
+        action_graph: &ActionGraph,
+        history: DurationHistoryState,
+    ) -> Self {
+        let mut file_counts = FxHashMap::default();
+
+        // Count the input files of each task only once, as globbing is expensive
+        for node in action_graph.get_nodes() {
+            if let ActionNode::RunTask(inner) = node {
+                file_counts
+                    .entry(inner.target.clone())
+                    .or_insert_with(|| count_input_files(session, workspace_graph, &inner.target));
+            }
+        }
+
+        // Convert file counts into durations, so that tasks with and
+        // without a history can be compared against each other
+        let mut known_millis = 0;
+        let mut known_files = 0;
+
+        for (target, count) in &file_counts {
+            if let Some(duration) = history.estimate_target(target.as_str()) {
+                known_millis += duration.as_millis() as u64;
+                known_files += count;
+            }
+        }
+
+        Self {
+            file_counts,
+            history,
+            millis_per_file: if known_millis > 0 && known_files > 0 {
+                (known_millis / known_files).max(1)
+            } else {
+                1
+            },
+        }
+    }
+
+    fn weigh(&self, node: &RunTaskNode) -> u64 {
+        if let Some(duration) = self.history.estimate_target(node.target.as_str()) {
+            return duration.as_millis() as u64;
+        }
+
+        self.file_counts
+            .get(&node.target)
+            .copied()
+            .unwrap_or_default()
+            * self.millis_per_file
+    }
+}
+
+fn count_input_files(
+    session: &MoonSession,
+    workspace_graph: &WorkspaceGraph,
+    target: &Target,
+) -> u64 {
+    let Ok(task) = workspace_graph.get_task(target) else {
+        return 0;
+    };
+
+    let mut count = task.input_files.len();
+
+    if !task.input_globs.is_empty() {
+        count += glob_walk_with_options(
+            &session.workspace_root,
+            &task.input_globs,
+            GlobWalkOptions::default().files(),
+        )
+        .map(|files| files.len())
+        .unwrap_or_default();
+    }
+
+    count as u64
+}
+
+/// Partition the targets into balanced shards, and return the targets of the current shard.
+async fn distribute_targets_across_shards(
     console: &mut CiConsole,
+    session: &MoonSession,
+    args: &CiArgs,
+    shard: ShardLocator,
+    targets: TargetList,
+    touched_files: &FxHashSet<WorkspaceRelativePathBuf>,
+) -> miette::Result<Option<TargetList>> {
+    console.print_header("Distributing targets across shards")?;
+
+    let (action_graph, _) =
+        build_action_graph(session, &targets, touched_files.clone(), true).await?;
+
+    let workspace_graph = session.get_workspace_graph().await?;
+    let history = session
+        .get_cache_engine()?
+        .state
+        .load_state::<DurationHistoryState>("durationHistory.json")?
+        .data;
+
+    let weigher = TaskWeigher::new(session, &workspace_graph, &action_graph, history);
+    let shards = action_graph.partition(shard.total, |node| weigher.weigh(node));
+
+    if args.plan.is_some() {
+        console.inner.out.write_line(json::format(
+            &CiPlan {
+                shards,
+                total: shard.total,
+            },
+            true,
+        )?)?;
+
+        return Ok(None);
+    }
+
+    if session.workspace_config.remote.is_none() {
+        warn!(
+            "No remote cache has been configured, so dependencies that were assigned to other shards will also run in this shard"
+        );
+    }
+
+    let current = shards
+        .into_iter()
+        .find(|item| item.index == shard.index)
+        .unwrap_or_default();
+
+    console.write_line(format!("Shard index: {}", shard.index))?;
+    console.write_line(format!("Shard total: {}", shard.total))?;
+    console.write_line(format!("Shard weight: {}", current.weight))?;
+    console.write_line("Sharded targets:")?;
+
+    let sharded_targets = current
+        .targets
+        .into_iter()
+        .map(TargetLocator::Qualified)
+        .collect::<Vec<_>>();
+
+    console.print_targets(&sharded_targets)?;
+    console.print_footer()?;
+
+    Ok(Some(sharded_targets))
+}
+
+async fn build_action_graph(
     session: &MoonSession,
     targets: &TargetList,
     touched_files: FxHashSet<WorkspaceRelativePathBuf>,
+    dependents: bool,
 ) -> miette::Result<(ActionGraph, ActionContext)> {
-    console.print_header("Generating action graph")?;
-
     let mut action_graph_builder = session.build_action_graph().await?;
     action_graph_builder.set_touched_files(touched_files)?;
     action_graph_builder.track_affected(UpstreamScope::Deep, DownstreamScope::Deep, true)?;
@@ -215,7 +442,7 @@ async fn generate_action_graph(
     let reqs = RunRequirements {
         ci: true,
         ci_check: true,
-        dependents: true,
+        dependents,
         interactive: false,
     };
 
@@ -228,6 +455,22 @@ async fn generate_action_graph(
     let (mut action_context, action_graph) = action_graph_builder.build();
     action_context.initial_targets.extend(targets.clone());
 
+    Ok((action_graph, action_context))
+}
+
+/// Generate a dependency graph with the runnable targets.
+async fn generate_action_graph(
+    console: &mut CiConsole,
+    session: &MoonSession,
+    targets: &TargetList,
+    touched_files: FxHashSet<WorkspaceRelativePathBuf>,
+    dependents: bool,
+) -> miette::Result<(ActionGraph, ActionContext)> {
+    console.print_header("Generating action graph")?;
+
+    let (action_graph, action_context) =
+        build_action_graph(session, targets, touched_files, dependents).await?;
+
     console.write_line(format!("Target count: {}", targets.len()))?;
     console.write_line(format!("Action count: {}", action_graph.get_node_count()))?;
     console.print_footer()?;
@@ -244,6 +487,7 @@ pub async fn ci(session: MoonSession, args: CiArgs) -> AppResult {
             open_log_group: "▪▪▪▪ {name}",
         }),
         last_title: String::new(),
+        silent: args.plan.is_some(),
     };
 
     let workspace_graph = session.get_workspace_graph().await?;
@@ -256,9 +500,42 @@ pub async fn ci(session: MoonSession, args: CiArgs) -> AppResult {
         return Ok(None);
     }
 
-    let targets = distribute_targets_across_jobs(&mut console, &args, targets)?;
+    // When only planning, the shard to run is irrelevant, so only the total is required
+    let shard = args.shard.or_else(|| {
+        args.shard_count
+            .map(|total| ShardLocator { index: 1, total })
+    });
+
+    // Shards already include dependents, so avoid running them in multiple shards
+    let (targets, dependents) = if let Some(shard) = shard {
+        match distribute_targets_across_shards(
+            &mut console,
+            &session,
+            &args,
+            shard,
+            targets,
+            &touched_files,
+        )
+        .await?
+        {
+            Some(targets) => (targets, false),
+            None => return Ok(None),
+        }
+    } else {
+        (
+            distribute_targets_across_jobs(&mut console, &args, targets)?,
+            true,
+        )
+    };
+
+    if targets.is_empty() {
+        console.write_line(color::invalid("No tasks assigned to this shard"))?;
+
+        return Ok(None);
+    }
+
     let (action_graph, action_context) =
-        generate_action_graph(&mut console, &session, &targets, touched_files).await?;
+        generate_action_graph(&mut console, &session, &targets, touched_files, dependents).await?;
 
     if action_graph.is_empty() {
         console.write_line(color::invalid("No tasks affected based on touched files"))?;
@@ -1,18 +1,54 @@
 use crate::session::MoonSession;
 use clap::Args;
-use iocraft::prelude::element;
-use moon_console::ui::{Container, Notice, StyledText, Variant};
+use iocraft::prelude::{Size, element};
+use moon_console::ui::{
+    Container, Entry, Notice, Section, Style, StyledText, Table, TableCol, TableHeader, TableRow,
+    Variant,
+};
 use starbase::AppResult;
+use std::collections::BTreeMap;
 use tracing::instrument;
 
 #[derive(Args, Clone, Debug)]
 pub struct CleanArgs {
     #[arg(long, default_value = "7 days", help = "Lifetime of cached artifacts")]
     lifetime: String,
+
+    #[arg(
+        long,
+        help = "Print a breakdown of cache usage by project and task, instead of cleaning"
+    )]
+    stats: bool,
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut size = bytes as f64;
+    let mut unit = "B";
+
+    for next_unit in UNITS {
+        if size < 1024.0 {
+            break;
+        }
+
+        size /= 1024.0;
+        unit = next_unit;
+    }
+
+    format!("{size:.1} {unit}")
 }
 
 #[instrument(skip_all)]
 pub async fn clean(session: MoonSession, args: CleanArgs) -> AppResult {
+    if args.stats {
+        return stats(session).await;
+    }
+
     let (files_deleted, bytes_saved) = session
         .get_cache_engine()?
         .clean_stale_cache(&args.lifetime, true)?;
@@ -30,3 +66,127 @@ pub async fn clean(session: MoonSession, args: CleanArgs) -> AppResult {
 
     Ok(None)
 }
+
+async fn stats(session: MoonSession) -> AppResult {
+    let cache_engine = session.get_cache_engine()?;
+    let usage = cache_engine.hash.get_usage(true)?;
+    let targets = usage.group_by_target();
+
+    // Shared blobs are counted for each target that references them,
+    // so the sum of projects may be larger than the total size
+    let mut projects = BTreeMap::<String, (usize, u64)>::default();
+
+    for (target, stats) in &targets {
+        let project = target
+            .as_deref()
+            .and_then(|target| target.split_once(':'))
+            .map(|(project, _)| project.to_owned())
+            .unwrap_or_else(|| "(unknown)".into());
+
+        let entry = projects.entry(project).or_default();
+        entry.0 += stats.hashes;
+        entry.1 += stats.size;
+    }
+
+    let target_width = targets.keys().fold(10, |acc, target| {
+        acc.max(target.as_deref().map(|target| target.len()).unwrap_or(9))
+    });
+
+    session.console.render(element! {
+        Container {
+            Section(title: "Cache") {
+                Entry(
+                    name: "Location",
+                    value: element! {
+                        StyledText(
+                            content: cache_engine.cache_dir.to_string_lossy(),
+                            style: Style::Path
+                        )
+                    }.into_any()
+                )
+                Entry(
+                    name: "Hashes",
+                    content: usage.entries.len().to_string(),
+                )
+                Entry(
+                    name: "Blobs",
+                    content: usage.blobs.len().to_string(),
+                )
+                Entry(
+                    name: "Total size",
+                    content: format_size(usage.get_total_size()),
+                )
+                #(session.workspace_config.pipeline.cache_max_size.as_ref().map(|max_size| {
+                    element! {
+                        Entry(
+                            name: "Maximum size",
+                            content: max_size,
+                        )
+                    }
+                }))
+            }
+            Table(
+                headers: vec![
+                    TableHeader::new("Project", Size::Length((target_width + 5) as u32)),
+                    TableHeader::new("Hashes", Size::Length(10)),
+                    TableHeader::new("Size", Size::Auto),
+                ]
+            ) {
+                #(projects.iter().enumerate().map(|(i, (project, (hashes, size)))| {
+                    element! {
+                        TableRow(row: i as i32) {
+                            TableCol(col: 0) {
+                                StyledText(
+                                    content: project,
+                                    style: Style::Id
+                                )
+                            }
+                            TableCol(col: 1) {
+                                StyledText(
+                                    content: hashes.to_string(),
+                                )
+                            }
+                            TableCol(col: 2) {
+                                StyledText(
+                                    content: format_size(*size),
+                                )
+                            }
+                        }
+                    }
+                }))
+            }
+            Table(
+                headers: vec![
+                    TableHeader::new("Task", Size::Length((target_width + 5) as u32)),
+                    TableHeader::new("Hashes", Size::Length(10)),
+                    TableHeader::new("Size", Size::Auto),
+                ]
+            ) {
+                #(targets.iter().enumerate().map(|(i, (target, stats))| {
+                    element! {
+                        TableRow(row: i as i32) {
+                            TableCol(col: 0) {
+                                StyledText(
+                                    content: target.as_deref().unwrap_or("(unknown)"),
+                                    style: Style::Id
+                                )
+                            }
+                            TableCol(col: 1) {
+                                StyledText(
+                                    content: stats.hashes.to_string(),
+                                )
+                            }
+                            TableCol(col: 2) {
+                                StyledText(
+                                    content: format_size(stats.size),
+                                )
+                            }
+                        }
+                    }
+                }))
+            }
+        }
+    })?;
+
+    Ok(None)
+}
@@ -6,7 +6,10 @@ use clap::builder::{
 use clap::parser::ValueSource;
 use clap::{Arg, ArgAction, Command};
 use iocraft::prelude::{View, Weight, element};
-use moon_codegen::{CodeGenerator, CodegenError, FileState, Template};
+use moon_codegen::{
+    CodeGenerator, CodegenError, FileState, Template, TemplateLock, TemplateLockEntry,
+};
+use moon_common::path::{RelativePathBuf, to_virtual_string};
 use moon_config::{TemplateVariable, TemplateVariableEnumDefault};
 use moon_console::{
     Console,
@@ -17,7 +20,8 @@ use moon_console::{
 };
 use rustc_hash::FxHashMap;
 use starbase::AppResult;
-use std::path::PathBuf;
+use starbase_styles::color;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tera::Context as TemplateContext;
 use tracing::{debug, instrument};
@@ -38,7 +42,8 @@ pub struct GenerateArgs {
 
     #[arg(
         long = "dryRun",
-        help = "Run entire generator process without writing files"
+        alias = "dry-run",
+        help = "Run entire generator process without writing files, and print a diff of changes"
     )]
     dry_run: bool,
 
@@ -48,6 +53,14 @@ pub struct GenerateArgs {
     #[arg(long, help = "Create a new template")]
     template: bool,
 
+    #[arg(
+        long,
+        help = "Update a previously generated template at the destination, merging template changes with local changes",
+        requires = "dest",
+        conflicts_with_all = ["force", "template"]
+    )]
+    update: bool,
+
     // Variable args (after --)
     #[arg(last = true, help = "Arguments to define as variable values")]
     vars: Vec<String>,
@@ -222,13 +235,26 @@ pub fn parse_args_into_variables(
     Ok(vars)
 }
 
-#[instrument(skip(template, console))]
+#[instrument(skip(template, console, locked))]
 pub async fn gather_variables(
     args: &GenerateArgs,
     template: &Template,
     console: &Console,
+    locked: Option<&TemplateLockEntry>,
 ) -> miette::Result<TemplateContext> {
-    let mut context = parse_args_into_variables(&args.vars, &template.config.variables)?;
+    let mut context = TemplateContext::default();
+
+    // Variables used when previously generated, that can be overridden by arguments
+    if let Some(locked) = locked {
+        debug!("Inheriting variable values from the template lock");
+
+        context.extend(locked.to_context());
+    }
+
+    context.extend(parse_args_into_variables(
+        &args.vars,
+        &template.config.variables,
+    )?);
 
     debug!("Gathering variable values from defaults and user prompts");
 
@@ -488,8 +514,36 @@ pub async fn generate(session: MoonSession, args: GenerateArgs) -> AppResult {
         }
     })?;
 
+    // Load the previous state of the template when updating
+    let locked = if args.update {
+        // The lock lives in the destination, which may contain variables, so interpolate
+        // it first. Previous values aren't known yet, so only arguments can be used.
+        let mut dest_context = parse_args_into_variables(&args.vars, &template.config.variables)?;
+        dest_context.insert("working_dir", &session.working_dir);
+        dest_context.insert("workspace_root", &session.workspace_root);
+
+        let relative_dest = template.interpolate_path(
+            &PathBuf::from(args.dest.as_deref().unwrap_or_default()),
+            &dest_context,
+        )?;
+        let dest = resolve_dest(&session, relative_dest.as_str());
+        let mut lock = TemplateLock::load(&dest)?;
+
+        let Some(entry) = lock.templates.remove(&template.id) else {
+            return Err(CodegenError::MissingTemplateLock {
+                id: template.id.clone(),
+                dest,
+            }
+            .into());
+        };
+
+        Some(entry)
+    } else {
+        None
+    };
+
     // Gather variables
-    let mut context = gather_variables(&args, &template, &session.console).await?;
+    let mut context = gather_variables(&args, &template, &session.console, locked.as_ref()).await?;
     context.insert("working_dir", &session.working_dir);
     context.insert("workspace_root", &session.workspace_root);
 
@@ -527,13 +581,8 @@ pub async fn generate(session: MoonSession, args: GenerateArgs) -> AppResult {
             }
         }
     };
-    let relative_from_root = relative_dest.starts_with('/');
-    let relative_dest = template.interpolate_path(&PathBuf::from(relative_dest), &context)?;
-    let dest = relative_dest.to_logical_path(if relative_from_root {
-        &session.workspace_root
-    } else {
-        &session.working_dir
-    });
+    let relative_dest = template.interpolate_path(&PathBuf::from(&relative_dest), &context)?;
+    let dest = resolve_dest(&session, relative_dest.as_str());
 
     debug!(dest = ?dest, "Destination path set");
 
@@ -544,12 +593,42 @@ pub async fn generate(session: MoonSession, args: GenerateArgs) -> AppResult {
     // Load template files and determine when to overwrite
     template.load_files(&dest, &context)?;
 
+    // Record the state before merging, so that it can be used as the base of future updates
+    let lock_entry = TemplateLockEntry::new(&template, &dest, &context)?;
+
+    // Files that were previously generated, but no longer exist in the template,
+    // are not deleted, as they may have local changes
+    let removed_files = locked
+        .as_ref()
+        .map(|locked| {
+            locked
+                .files
+                .keys()
+                .filter(|rel_path| !lock_entry.files.contains_key(*rel_path))
+                .map(|rel_path| RelativePathBuf::from(rel_path).to_logical_path(&dest))
+                .filter(|path| path.exists())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
     for file in template.files.values_mut() {
         if file.is_skipped() {
             file.state = FileState::Skip;
             continue;
         }
 
+        if let Some(locked) = &locked {
+            let base = file
+                .dest_path
+                .strip_prefix(&dest)
+                .ok()
+                .and_then(|rel_path| to_virtual_string(rel_path).ok())
+                .and_then(|rel_path| locked.files.get(&rel_path));
+
+            file.resolve_update(base.map(|base| base.as_str()))?;
+            continue;
+        }
+
         if file.dest_path.exists() {
             if args.force || file.is_forced() {
                 file.state = FileState::Replace;
@@ -613,8 +692,18 @@ pub async fn generate(session: MoonSession, args: GenerateArgs) -> AppResult {
     // Generate the files in the destination and print the results
     if !args.dry_run {
         generator.generate(&template)?;
+
+        let mut lock = TemplateLock::load(&dest)?;
+        lock.templates.insert(template.id.clone(), lock_entry);
+        lock.save()?;
     }
 
+    let conflicts = template
+        .files
+        .values()
+        .filter(|file| matches!(file.state, FileState::Conflict))
+        .count();
+
     console.render(element! {
         View(
             margin_top: if has_prompts {
@@ -627,10 +716,12 @@ pub async fn generate(session: MoonSession, args: GenerateArgs) -> AppResult {
             List {
                 #(template.files.values().map(|file| {
                     let (label, arrow, style) = match &file.state {
+                        FileState::Conflict => ("conflict", "-➤", "failure"),
                         FileState::Create => ("created", "--➤", "success"),
                         FileState::Merge => ("merged", "---➤", "success"),
                         FileState::Replace => ("replaced", "-➤", "failure"),
                         FileState::Skip => ("skipped", "--➤", "invalid"),
+                        FileState::Update => ("updated", "--➤", "success"),
                     };
 
                     element! {
@@ -667,5 +758,84 @@ pub async fn generate(session: MoonSession, args: GenerateArgs) -> AppResult {
         }
     })?;
 
+    if conflicts > 0 {
+        console.render(element! {
+            Container {
+                Notice(variant: Variant::Caution) {
+                    StyledText(content: format!(
+                        "Unable to merge template changes with local changes in {conflicts} file(s). Conflicts have been marked in the files above, and must be resolved manually."
+                    ))
+                }
+            }
+        })?;
+    }
+
+    if !removed_files.is_empty() {
+        console.render(element! {
+            Container {
+                Notice(variant: Variant::Caution) {
+                    StyledText(content: format!(
+                        "{} file(s) were removed from the template, but still exist in the destination, and must be deleted manually: {}",
+                        removed_files.len(),
+                        removed_files
+                            .iter()
+                            .map(|path| format!(
+                                "<path>{}</path>",
+                                path.strip_prefix(&session.working_dir)
+                                    .unwrap_or(path)
+                                    .display()
+                            ))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ))
+                }
+            }
+        })?;
+    }
+
+    if args.dry_run {
+        for file in template.files.values() {
+            let label = file
+                .dest_path
+                .strip_prefix(&session.working_dir)
+                .unwrap_or(&file.dest_path)
+                .display()
+                .to_string();
+
+            if let Some(diff) = template.diff_file(file, &label)? {
+                console.out.write_line(colorize_diff(&diff))?;
+            }
+        }
+    }
+
     Ok(None)
 }
+
+fn resolve_dest(session: &MoonSession, relative_dest: &str) -> PathBuf {
+    let base: &Path = if relative_dest.starts_with('/') {
+        &session.workspace_root
+    } else {
+        &session.working_dir
+    };
+
+    RelativePathBuf::from(relative_dest).to_logical_path(base)
+}
+
+fn colorize_diff(diff: &str) -> String {
+    diff.lines()
+        .map(|line| {
+            if line.starts_with("+++") || line.starts_with("---") {
+                color::muted_light(line)
+            } else if line.starts_with('+') {
+                color::success(line)
+            } else if line.starts_with('-') {
+                color::failure(line)
+            } else if line.starts_with("@@") {
+                color::muted(line)
+            } else {
+                line.to_owned()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -1,3 +1,4 @@
+use crate::commands::graph::GraphFormat;
 use crate::commands::graph::utils::{action_graph_repr, run_server};
 use crate::session::MoonSession;
 use clap::Args;
@@ -15,10 +16,25 @@ pub struct ActionGraphArgs {
     #[arg(long, help = "Include dependents of the focused target(s)")]
     dependents: bool,
 
-    #[arg(long, help = "Print the graph in DOT format")]
+    #[arg(
+        long,
+        help = "Print the graph in DOT format",
+        conflicts_with = "format"
+    )]
     dot: bool,
 
-    #[arg(long, help = "Print the graph in JSON format")]
+    #[arg(
+        value_enum,
+        long,
+        help = "Print the graph in the provided format, instead of starting the visualizer"
+    )]
+    format: Option<GraphFormat>,
+
+    #[arg(
+        long,
+        help = "Print the graph in JSON format",
+        conflicts_with = "format"
+    )]
     json: bool,
 }
 
@@ -49,19 +65,16 @@ pub async fn action_graph(session: MoonSession, args: ActionGraphArgs) -> AppRes
 
     let (_, action_graph) = action_graph_builder.build();
 
-    if args.dot {
-        session.console.out.write_line(action_graph.to_dot())?;
-
-        return Ok(None);
-    }
-
     let graph_info = action_graph_repr(&action_graph).await;
 
-    if args.json {
-        session
-            .console
-            .out
-            .write_line(json::format(&graph_info, true)?)?;
+    if let Some(format) = GraphFormat::resolve(args.format, args.dot, args.json) {
+        session.console.out.write_line(match format {
+            GraphFormat::Dot => action_graph.to_dot(),
+            GraphFormat::Graphml => action_graph.to_graphml(),
+            GraphFormat::Json => json::format(&graph_info, true)?,
+            GraphFormat::Mermaid => action_graph.to_mermaid(),
+            GraphFormat::Svg => action_graph.to_svg(),
+        })?;
 
         return Ok(None);
     }
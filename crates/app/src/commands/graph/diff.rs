@@ -0,0 +1,218 @@
+use crate::app_error::AppError;
+use crate::session::MoonSession;
+use clap::Args;
+use iocraft::prelude::element;
+use miette::IntoDiagnostic;
+use moon_config::VcsManager;
+use moon_console::ui::{Container, Notice, Section, StyledText, Variant};
+use moon_process::Command;
+use moon_project_graph::{GraphDiff, GraphSnapshot};
+use serde::Serialize;
+use starbase::AppResult;
+use starbase_utils::{fs, json};
+use std::env;
+use std::path::Path;
+use tracing::{debug, instrument};
+
+#[derive(Args, Clone, Debug)]
+pub struct GraphDiffArgs {
+    #[arg(
+        long,
+        required = true,
+        help = "Base revision to compare the current workspace against"
+    )]
+    base: String,
+
+    #[arg(long, help = "Print the diff in JSON format")]
+    json: bool,
+}
+
+#[derive(Serialize)]
+struct GraphDiffResult {
+    projects: GraphDiff,
+    tasks: GraphDiff,
+}
+
+async fn load_graph_snapshot(
+    exe: &Path,
+    workspace_root: &Path,
+    command: &str,
+    key_field: &str,
+) -> miette::Result<GraphSnapshot> {
+    let output = Command::new(exe)
+        .args([command, "--format", "json"])
+        .cwd(workspace_root)
+        .env("MOON_CACHE", "off")
+        .env_remove("MOON_WORKSPACE_ROOT")
+        .without_shell()
+        .exec_capture_output()
+        .await?;
+
+    GraphSnapshot::from_json(&String::from_utf8_lossy(&output.stdout), key_field)
+}
+
+// Load the graphs of the workspace at the base revision, by running
+// the current binary within a temporary worktree of that revision.
+async fn load_base_snapshots(
+    session: &MoonSession,
+    worktree_dir: &Path,
+    revision: &str,
+) -> miette::Result<(GraphSnapshot, GraphSnapshot)> {
+    let vcs = session.get_vcs_adapter()?;
+    let repo_root = vcs.get_repository_root().await?;
+
+    debug!(
+        revision,
+        worktree_dir = ?worktree_dir,
+        "Checking out base revision into a temporary worktree"
+    );
+
+    Command::new("git")
+        .args(["worktree", "add", "--detach"])
+        .arg(worktree_dir)
+        .arg(revision)
+        .cwd(&repo_root)
+        .without_shell()
+        .exec_capture_output()
+        .await?;
+
+    // The workspace may be nested within the repository
+    let base_root = match session.workspace_root.strip_prefix(&repo_root) {
+        Ok(rel_root) => worktree_dir.join(rel_root),
+        Err(_) => worktree_dir.to_path_buf(),
+    };
+
+    let exe = env::current_exe().into_diagnostic()?;
+    let projects = load_graph_snapshot(&exe, &base_root, "project-graph", "id").await?;
+    let tasks = load_graph_snapshot(&exe, &base_root, "task-graph", "target").await?;
+
+    Ok((projects, tasks))
+}
+
+async fn remove_worktree(session: &MoonSession, worktree_dir: &Path) -> miette::Result<()> {
+    let repo_root = session.get_vcs_adapter()?.get_repository_root().await?;
+
+    Command::new("git")
+        .args(["worktree", "remove", "--force"])
+        .arg(worktree_dir)
+        .cwd(&repo_root)
+        .without_shell()
+        .set_error_on_nonzero(false)
+        .exec_capture_output()
+        .await?;
+
+    fs::remove_dir_all(worktree_dir)?;
+
+    Ok(())
+}
+
+fn format_node_changes(diff: &GraphDiff) -> Vec<String> {
+    diff.added_nodes
+        .iter()
+        .map(|node| format!("<success>+</success> <id>{node}</id>"))
+        .chain(
+            diff.removed_nodes
+                .iter()
+                .map(|node| format!("<failure>-</failure> <id>{node}</id>")),
+        )
+        .collect()
+}
+
+fn format_edge_changes(diff: &GraphDiff) -> Vec<String> {
+    diff.added_edges
+        .iter()
+        .map(|(source, target)| {
+            format!("<success>+</success> <id>{source}</id> <muted>→</muted> <id>{target}</id>")
+        })
+        .chain(diff.removed_edges.iter().map(|(source, target)| {
+            format!("<failure>-</failure> <id>{source}</id> <muted>→</muted> <id>{target}</id>")
+        }))
+        .collect()
+}
+
+#[instrument(skip_all)]
+pub async fn diff(session: MoonSession, args: GraphDiffArgs) -> AppResult {
+    let vcs = session.get_vcs_adapter()?;
+
+    if !vcs.is_enabled() || session.workspace_config.vcs.manager != VcsManager::Git {
+        return Err(AppError::GraphDiffRequiresGit.into());
+    }
+
+    // Force expand all projects and tasks of the current workspace
+    let project_graph = session.get_project_graph().await?;
+    let task_graph = session.get_task_graph().await?;
+
+    project_graph.get_all()?;
+    task_graph.get_all()?;
+
+    let head_projects = GraphSnapshot::from_graph(&*project_graph);
+    let head_tasks = GraphSnapshot::from_graph(&*task_graph);
+
+    let worktree_dir = session
+        .get_cache_engine()?
+        .temp_dir
+        .join(format!("graph-diff-{}", std::process::id()));
+
+    let base_result = load_base_snapshots(&session, &worktree_dir, &args.base).await;
+
+    remove_worktree(&session, &worktree_dir).await?;
+
+    let (base_projects, base_tasks) = base_result?;
+
+    let result = GraphDiffResult {
+        projects: GraphDiff::new(&base_projects, &head_projects),
+        tasks: GraphDiff::new(&base_tasks, &head_tasks),
+    };
+
+    if args.json {
+        session
+            .console
+            .out
+            .write_line(json::format(&result, true)?)?;
+
+        return Ok(None);
+    }
+
+    if result.projects.is_empty() && result.tasks.is_empty() {
+        session.console.render(element! {
+            Container {
+                Notice(variant: Variant::Success) {
+                    StyledText(content: format!(
+                        "No projects, tasks, or dependencies have changed since <hash>{}</hash>",
+                        args.base
+                    ))
+                }
+            }
+        })?;
+
+        return Ok(None);
+    }
+
+    let sections = [
+        ("Projects", format_node_changes(&result.projects)),
+        (
+            "Project dependencies",
+            format_edge_changes(&result.projects),
+        ),
+        ("Tasks", format_node_changes(&result.tasks)),
+        ("Task dependencies", format_edge_changes(&result.tasks)),
+    ];
+
+    session.console.render(element! {
+        Container {
+            #(sections.into_iter().filter(|(_, lines)| !lines.is_empty()).map(|(title, lines)| {
+                element! {
+                    Section(title: title) {
+                        #(lines.into_iter().map(|line| {
+                            element! {
+                                StyledText(content: line)
+                            }
+                        }))
+                    }
+                }
+            }))
+        }
+    })?;
+
+    Ok(None)
+}
@@ -1,5 +1,41 @@
 pub mod action;
+mod diff;
 mod dto;
 pub mod project;
 pub mod task;
 mod utils;
+
+pub use diff::*;
+
+use clap::{Subcommand, ValueEnum};
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum GraphFormat {
+    Dot,
+    Graphml,
+    Json,
+    Mermaid,
+    Svg,
+}
+
+impl GraphFormat {
+    /// Resolve the format from the `--format` option, or the legacy `--dot` and `--json` flags.
+    pub fn resolve(format: Option<GraphFormat>, dot: bool, json: bool) -> Option<GraphFormat> {
+        format.or(if dot {
+            Some(GraphFormat::Dot)
+        } else if json {
+            Some(GraphFormat::Json)
+        } else {
+            None
+        })
+    }
+}
+
+#[derive(Clone, Debug, Subcommand)]
+pub enum GraphCommands {
+    #[command(
+        name = "diff",
+        about = "Display projects, tasks, and dependencies that were added or removed since a base revision."
+    )]
+    Diff(GraphDiffArgs),
+}
@@ -1,8 +1,9 @@
+use super::GraphFormat;
 use super::utils::{project_graph_repr, run_server};
 use crate::session::MoonSession;
 use clap::Args;
 use moon_common::Id;
-use moon_project_graph::{GraphToDot, GraphToJson};
+use moon_project_graph::{GraphToDot, GraphToGraphml, GraphToJson, GraphToMermaid, GraphToSvg};
 use starbase::AppResult;
 use std::sync::Arc;
 use tracing::instrument;
@@ -15,10 +16,25 @@ pub struct ProjectGraphArgs {
     #[arg(long, help = "Include direct dependents of the focused project")]
     dependents: bool,
 
-    #[arg(long, help = "Print the graph in DOT format")]
+    #[arg(
+        long,
+        help = "Print the graph in DOT format",
+        conflicts_with = "format"
+    )]
     dot: bool,
 
-    #[arg(long, help = "Print the graph in JSON format")]
+    #[arg(
+        value_enum,
+        long,
+        help = "Print the graph in the provided format, instead of starting the visualizer"
+    )]
+    format: Option<GraphFormat>,
+
+    #[arg(
+        long,
+        help = "Print the graph in JSON format",
+        conflicts_with = "format"
+    )]
     json: bool,
 }
 
@@ -33,14 +49,14 @@ pub async fn project_graph(session: MoonSession, args: ProjectGraphArgs) -> AppR
     // Force expand all projects
     project_graph.get_all()?;
 
-    if args.dot {
-        session.console.out.write_line(project_graph.to_dot())?;
-
-        return Ok(None);
-    }
-
-    if args.json {
-        session.console.out.write_line(project_graph.to_json()?)?;
+    if let Some(format) = GraphFormat::resolve(args.format, args.dot, args.json) {
+        session.console.out.write_line(match format {
+            GraphFormat::Dot => project_graph.to_dot(),
+            GraphFormat::Graphml => project_graph.to_graphml(),
+            GraphFormat::Json => project_graph.to_json()?,
+            GraphFormat::Mermaid => project_graph.to_mermaid(),
+            GraphFormat::Svg => project_graph.to_svg(),
+        })?;
 
         return Ok(None);
     }
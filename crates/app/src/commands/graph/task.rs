@@ -1,8 +1,9 @@
+use super::GraphFormat;
 use super::utils::{run_server, task_graph_repr};
 use crate::session::MoonSession;
 use clap::Args;
 use moon_task::Target;
-use moon_task_graph::{GraphToDot, GraphToJson};
+use moon_task_graph::{GraphToDot, GraphToGraphml, GraphToJson, GraphToMermaid, GraphToSvg};
 use starbase::AppResult;
 use std::sync::Arc;
 use tracing::instrument;
@@ -15,10 +16,25 @@ pub struct TaskGraphArgs {
     #[arg(long, help = "Include direct dependents of the focused target")]
     dependents: bool,
 
-    #[arg(long, help = "Print the graph in DOT format")]
+    #[arg(
+        long,
+        help = "Print the graph in DOT format",
+        conflicts_with = "format"
+    )]
     dot: bool,
 
-    #[arg(long, help = "Print the graph in JSON format")]
+    #[arg(
+        value_enum,
+        long,
+        help = "Print the graph in the provided format, instead of starting the visualizer"
+    )]
+    format: Option<GraphFormat>,
+
+    #[arg(
+        long,
+        help = "Print the graph in JSON format",
+        conflicts_with = "format"
+    )]
     json: bool,
 }
 
@@ -33,14 +49,14 @@ pub async fn task_graph(session: MoonSession, args: TaskGraphArgs) -> AppResult
     // Force expand all tasks
     task_graph.get_all()?;
 
-    if args.dot {
-        session.console.out.write_line(task_graph.to_dot())?;
-
-        return Ok(None);
-    }
-
-    if args.json {
-        session.console.out.write_line(task_graph.to_json()?)?;
+    if let Some(format) = GraphFormat::resolve(args.format, args.dot, args.json) {
+        session.console.out.write_line(match format {
+            GraphFormat::Dot => task_graph.to_dot(),
+            GraphFormat::Graphml => task_graph.to_graphml(),
+            GraphFormat::Json => task_graph.to_json()?,
+            GraphFormat::Mermaid => task_graph.to_mermaid(),
+            GraphFormat::Svg => task_graph.to_svg(),
+        })?;
 
         return Ok(None);
     }
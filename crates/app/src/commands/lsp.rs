@@ -0,0 +1,61 @@
+use crate::components::create_workspace_graph_context;
+use crate::session::MoonSession;
+use crate::systems::startup;
+use async_trait::async_trait;
+use clap::Args;
+use moon_lsp::{WorkspaceGraphLoader, run_language_server};
+use moon_workspace::WorkspaceBuilder;
+use moon_workspace_graph::WorkspaceGraph;
+use starbase::AppResult;
+use std::sync::Arc;
+use tracing::{debug, instrument};
+
+#[derive(Args, Clone, Debug)]
+pub struct LspArgs {
+    #[arg(
+        long,
+        help = "Communicate over stdin and stdout (the default and only transport)"
+    )]
+    pub stdio: bool,
+}
+
+/// Loads a fresh workspace graph for every request, so that changes
+/// to configuration files are picked up while the server is running.
+struct SessionGraphLoader {
+    session: MoonSession,
+}
+
+#[async_trait]
+impl WorkspaceGraphLoader for SessionGraphLoader {
+    async fn load_workspace_graph(&self) -> miette::Result<Arc<WorkspaceGraph>> {
+        let mut session = self.session.clone();
+
+        session.workspace_config =
+            startup::load_workspace_config(session.config_loader.clone(), &session.workspace_root)
+                .await?;
+
+        session.tasks_config =
+            startup::load_tasks_configs(session.config_loader.clone(), &session.workspace_root)
+                .await?;
+
+        let context = create_workspace_graph_context(&session).await?;
+        let workspace_graph = WorkspaceBuilder::new(context).await?.build().await?;
+
+        Ok(Arc::new(workspace_graph))
+    }
+}
+
+#[instrument(skip_all)]
+pub async fn lsp(session: MoonSession, _args: LspArgs) -> AppResult {
+    debug!(
+        workspace_root = ?session.workspace_root,
+        "Starting language server over stdio"
+    );
+
+    let workspace_root = session.workspace_root.clone();
+    let loader = Arc::new(SessionGraphLoader { session });
+
+    run_language_server(workspace_root, loader).await;
+
+    Ok(None)
+}
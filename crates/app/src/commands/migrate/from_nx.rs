@@ -0,0 +1,68 @@
+use super::{check_dirty_repo, create_migration, write_migration};
+use crate::session::MoonSession;
+use clap::Args;
+use moon_migrate::{NxJson, NxProjectJson, migrate_nx};
+use starbase::AppResult;
+use starbase_styles::color;
+use starbase_utils::json;
+use std::collections::BTreeMap;
+use tracing::{debug, info, instrument};
+
+#[derive(Args, Clone, Debug)]
+pub struct FromNxArgs {
+    #[arg(long, help = "Run package scripts with Bun instead of Node.js")]
+    bun: bool,
+
+    #[arg(long, hide = true)]
+    pub skip_touched_files_check: bool,
+}
+
+#[instrument(skip_all)]
+pub async fn from_nx(session: MoonSession, args: FromNxArgs) -> AppResult {
+    if args.skip_touched_files_check {
+        info!("Skipping touched files check.");
+    } else {
+        check_dirty_repo(&session).await?;
+    };
+
+    let nx_path = session.workspace_root.join("nx.json");
+    let root: NxJson = if nx_path.exists() {
+        json::read_file(&nx_path)?
+    } else {
+        NxJson::default()
+    };
+
+    let mut migration = create_migration(&session, args.bun).await?;
+    let mut projects = BTreeMap::new();
+
+    for project in migration.projects.values_mut() {
+        let project_path = session
+            .workspace_root
+            .join(&project.source)
+            .join("project.json");
+
+        if !project_path.exists() {
+            continue;
+        }
+
+        debug!(
+            project_id = project.id.as_str(),
+            "Found a {}",
+            color::file("project.json")
+        );
+
+        let project_json: NxProjectJson = json::read_file(&project_path)?;
+
+        // Nx projects are referenced by the name in `project.json`,
+        // falling back to the name in `package.json`
+        if let Some(name) = &project_json.name {
+            project.name = Some(name.to_owned());
+        }
+
+        projects.insert(project.id.clone(), project_json);
+    }
+
+    migrate_nx(&mut migration, root, projects)?;
+
+    write_migration(&session, migration, args.bun)
+}
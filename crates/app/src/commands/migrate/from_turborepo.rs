@@ -1,12 +1,67 @@
+use super::{check_dirty_repo, create_migration, write_migration};
+use crate::session::MoonSession;
+use clap::Args;
+use miette::miette;
+use moon_migrate::{TurboJson, migrate_turborepo};
 use starbase::AppResult;
 use starbase_styles::color;
-use tracing::warn;
+use starbase_utils::json;
+use std::collections::BTreeMap;
+use tracing::{debug, info, instrument};
 
-pub async fn from_turborepo() -> AppResult {
-    warn!(
-        "This command is deprecated. Use {} instead.",
-        color::shell("moon ext migrate-turborepo")
-    );
+#[derive(Args, Clone, Debug)]
+pub struct FromTurborepoArgs {
+    #[arg(long, help = "Run package scripts with Bun instead of Node.js")]
+    bun: bool,
 
-    Ok(None)
+    #[arg(long, hide = true)]
+    pub skip_touched_files_check: bool,
+}
+
+#[instrument(skip_all)]
+pub async fn from_turborepo(session: MoonSession, args: FromTurborepoArgs) -> AppResult {
+    if args.skip_touched_files_check {
+        info!("Skipping touched files check.");
+    } else {
+        check_dirty_repo(&session).await?;
+    };
+
+    let root_turbo_path = session.workspace_root.join("turbo.json");
+
+    if !root_turbo_path.exists() {
+        return Err(miette!(
+            code = "moon::migrate",
+            "No {} found in the workspace root.",
+            color::file("turbo.json"),
+        ));
+    }
+
+    let root: TurboJson = json::read_file(&root_turbo_path)?;
+    let mut migration = create_migration(&session, args.bun).await?;
+    let mut packages = BTreeMap::new();
+
+    for project in migration.projects.values() {
+        if project.is_root_level() {
+            continue;
+        }
+
+        let turbo_path = session
+            .workspace_root
+            .join(&project.source)
+            .join("turbo.json");
+
+        if turbo_path.exists() {
+            debug!(
+                project_id = project.id.as_str(),
+                "Found a package level {}",
+                color::file("turbo.json")
+            );
+
+            packages.insert(project.id.clone(), json::read_file(&turbo_path)?);
+        }
+    }
+
+    migrate_turborepo(&mut migration, root, packages)?;
+
+    write_migration(&session, migration, args.bun)
 }
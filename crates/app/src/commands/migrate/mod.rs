@@ -1,17 +1,33 @@
+mod from_nx;
 mod from_package_json;
 mod from_turborepo;
 
+pub use from_nx::*;
 pub use from_package_json::{FromPackageJsonArgs, from_package_json};
 pub use from_turborepo::*;
 
 use crate::session::MoonSession;
 use clap::Subcommand;
+use iocraft::prelude::element;
 use miette::miette;
+use moon_common::consts::CONFIG_DIRNAME;
+use moon_config::NodePackageManager;
+use moon_console::ui::{Container, List, ListItem, Notice, Section, Style, StyledText, Variant};
+use moon_migrate::{MigrateProject, Migration};
+use moon_node_lang::PackageJsonCache;
 use starbase::AppResult;
 use starbase_styles::color;
+use starbase_utils::yaml;
+use std::path::PathBuf;
 
 #[derive(Clone, Debug, Subcommand)]
 pub enum MigrateCommands {
+    #[command(
+        name = "from-nx",
+        about = "Migrate `nx.json` and `project.json` files to moon configuration files."
+    )]
+    FromNx(FromNxArgs),
+
     #[command(
         name = "from-package-json",
         about = "Migrate `package.json` scripts and dependencies to `moon.*`."
@@ -22,7 +38,7 @@ pub enum MigrateCommands {
         name = "from-turborepo",
         about = "Migrate `turbo.json` to moon configuration files."
     )]
-    FromTurborepo,
+    FromTurborepo(FromTurborepoArgs),
 }
 
 pub async fn check_dirty_repo(session: &MoonSession) -> AppResult {
@@ -42,3 +58,158 @@ pub async fn check_dirty_repo(session: &MoonSession) -> AppResult {
 
     Ok(None)
 }
+
+fn get_tasks_config_path(session: &MoonSession, bun: bool) -> PathBuf {
+    session
+        .workspace_root
+        .join(CONFIG_DIRNAME)
+        .join("tasks")
+        .join(if bun { "bun.yml" } else { "node.yml" })
+}
+
+/// Create a migration with the current state of all projects,
+/// and the global tasks configuration file.
+pub async fn create_migration(session: &MoonSession, bun: bool) -> miette::Result<Migration> {
+    let package_manager = if bun {
+        NodePackageManager::Bun
+    } else {
+        session
+            .toolchain_config
+            .node
+            .as_ref()
+            .map(|cfg| cfg.package_manager)
+            .unwrap_or(NodePackageManager::Npm)
+    };
+
+    let mut migration = Migration::new(
+        package_manager,
+        session.config_loader.load_tasks_partial_config_from_path(
+            &session.workspace_root,
+            get_tasks_config_path(session, bun),
+        )?,
+    );
+
+    let project_graph = session.get_project_graph().await?;
+
+    for project in project_graph.get_all_unexpanded() {
+        let mut migrate_project = MigrateProject {
+            config: session
+                .config_loader
+                .load_project_partial_config(&project.root)?,
+            id: project.id.clone(),
+            source: project.source.to_string(),
+            ..Default::default()
+        };
+
+        if let Some(package_json) = PackageJsonCache::read(&project.root)? {
+            migrate_project.name = package_json.data.name;
+
+            if let Some(scripts) = package_json.data.scripts {
+                migrate_project.scripts.extend(scripts.into_keys());
+            }
+        }
+
+        migration.add_project(migrate_project);
+    }
+
+    Ok(migration)
+}
+
+/// Write all changed configuration files to disk,
+/// and render a report of what could not be migrated.
+pub fn write_migration(session: &MoonSession, migration: Migration, bun: bool) -> AppResult {
+    let mut written_files = vec![];
+
+    if migration.tasks_config_changed {
+        let tasks_config_path = get_tasks_config_path(session, bun);
+
+        yaml::write_file_with_config(&tasks_config_path, &migration.tasks_config)?;
+
+        written_files.push(tasks_config_path);
+    }
+
+    let config_names = session.config_loader.get_project_file_names();
+
+    for (id, project) in &migration.projects {
+        if !migration.touched_projects.contains(id) {
+            continue;
+        }
+
+        let config_path = session
+            .workspace_root
+            .join(&project.source)
+            .join(&config_names[0]);
+
+        yaml::write_file_with_config(&config_path, &project.config)?;
+
+        written_files.push(config_path);
+    }
+
+    let written_files = written_files
+        .into_iter()
+        .map(|path| {
+            path.strip_prefix(&session.workspace_root)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .replace('\\', "/")
+        })
+        .collect::<Vec<_>>();
+
+    session.console.render(element! {
+        Container {
+            #(if written_files.is_empty() {
+                Some(element! {
+                    Notice(variant: Variant::Info) {
+                        StyledText(content: "No configuration was migrated.")
+                    }
+                })
+            } else {
+                None
+            })
+            #(if written_files.is_empty() {
+                None
+            } else {
+                Some(element! {
+                    Section(title: "Migrated files") {
+                        List {
+                            #(written_files.into_iter().map(|file| {
+                                element! {
+                                    ListItem {
+                                        StyledText(
+                                            content: file,
+                                            style: Style::File
+                                        )
+                                    }
+                                }
+                            }))
+                        }
+                    }
+                })
+            })
+            #(if migration.warnings.is_empty() {
+                None
+            } else {
+                Some(element! {
+                    Section(title: "Unable to migrate") {
+                        List {
+                            #(migration.warnings.iter().map(|warning| {
+                                element! {
+                                    ListItem {
+                                        StyledText(content: warning)
+                                    }
+                                }
+                            }))
+                        }
+                        Notice(variant: Variant::Caution) {
+                            StyledText(
+                                content: "The settings above must be migrated manually."
+                            )
+                        }
+                    }
+                })
+            })
+        }
+    })?;
+
+    Ok(None)
+}
@@ -1,4 +1,5 @@
 pub mod bin;
+pub mod cache;
 pub mod check;
 pub mod ci;
 pub mod clean;
@@ -9,8 +10,10 @@ pub mod ext;
 pub mod generate;
 pub mod graph;
 pub mod init;
+pub mod lsp;
 pub mod migrate;
 pub mod node;
+pub mod owners;
 pub mod project;
 pub mod query;
 pub mod run;
@@ -21,3 +24,5 @@ pub mod task;
 pub mod teardown;
 pub mod templates;
 pub mod upgrade;
+pub mod watch;
+pub mod why;
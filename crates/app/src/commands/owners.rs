@@ -0,0 +1,286 @@
+use crate::app_error::AppError;
+use crate::queries::touched_files::{QueryTouchedFilesOptions, query_touched_files};
+use crate::session::MoonSession;
+use clap::Args;
+use iocraft::prelude::{Size, element};
+use moon_actions::operations::resolve_codeowners;
+use moon_codeowners::{ApprovalRequirement, PathOwnership};
+use moon_common::is_ci;
+use moon_common::path::{RelativePathBuf, standardize_separators};
+use moon_console::ui::{
+    Container, Notice, Style, StyledText, Table, TableCol, TableHeader, TableRow, Variant,
+};
+use serde::Serialize;
+use starbase::AppResult;
+use starbase_utils::json;
+use std::path::Component;
+use tracing::instrument;
+
+#[derive(Args, Clone, Debug)]
+pub struct OwnersArgs {
+    #[arg(help = "List of file paths to resolve owners for. Defaults to touched files")]
+    paths: Vec<String>,
+
+    #[arg(
+        long,
+        help = "Owner that has approved the changes. Can be passed multiple times"
+    )]
+    approver: Vec<String>,
+
+    #[arg(long, help = "Base branch, commit, or revision to compare against")]
+    base: Option<String>,
+
+    #[arg(
+        long,
+        help = "Exit with a non-zero code if files are unowned, or approvals are not satisfied"
+    )]
+    check: bool,
+
+    #[arg(long, help = "Current branch, commit, or revision to compare with")]
+    head: Option<String>,
+
+    #[arg(long, help = "Print the owners in JSON format")]
+    json: bool,
+
+    #[arg(
+        long,
+        help = "Gather touched files from you local state instead of the remote"
+    )]
+    local: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnersResult {
+    pub approvals: Vec<ApprovalRequirement>,
+    pub files: Vec<PathOwnership>,
+    pub unowned: Vec<String>,
+}
+
+fn to_workspace_relative(session: &MoonSession, path: &str) -> miette::Result<String> {
+    let abs_path = session.working_dir.join(path);
+    let mut rel_path = RelativePathBuf::new();
+
+    // Normalize the path without touching the file system,
+    // as deleted files should still resolve
+    for component in abs_path
+        .strip_prefix(&session.workspace_root)
+        .map_err(|_| AppError::OwnersPathOutsideWorkspace(path.to_owned()))?
+        .components()
+    {
+        match component {
+            Component::Normal(part) => rel_path.push(part.to_string_lossy().as_ref()),
+            Component::ParentDir => {
+                if !rel_path.pop() {
+                    return Err(AppError::OwnersPathOutsideWorkspace(path.to_owned()).into());
+                }
+            }
+            _ => {}
+        };
+    }
+
+    Ok(standardize_separators(rel_path.as_str()))
+}
+
+#[instrument(skip_all)]
+pub async fn owners(session: MoonSession, args: OwnersArgs) -> AppResult {
+    let mut paths = vec![];
+
+    if args.paths.is_empty() {
+        let vcs = session.get_vcs_adapter()?;
+        let result = query_touched_files(
+            &vcs,
+            &QueryTouchedFilesOptions {
+                base: args.base.clone(),
+                default_branch: is_ci(),
+                head: args.head.clone(),
+                local: args.local,
+                ..QueryTouchedFilesOptions::default()
+            },
+        )
+        .await?;
+
+        paths.extend(result.files.into_iter().map(|file| file.to_string()));
+    } else {
+        for path in &args.paths {
+            paths.push(to_workspace_relative(&session, path)?);
+        }
+    }
+
+    paths.sort();
+    paths.dedup();
+
+    let app_context = session.get_app_context().await?;
+    let workspace_graph = session.get_workspace_graph().await?;
+    let resolver = resolve_codeowners(&app_context, &workspace_graph)?;
+
+    let files = paths
+        .iter()
+        .map(|path| resolver.resolve(path))
+        .collect::<Vec<_>>();
+    let unowned = files
+        .iter()
+        .filter(|file| !file.is_owned())
+        .map(|file| file.path.clone())
+        .collect::<Vec<_>>();
+    let approvals = resolver.check_approvals(&files, &args.approver);
+    let unapproved = approvals.iter().filter(|req| !req.is_satisfied()).count();
+
+    // When no approvers are provided, required approvals are always missing
+    let failed = args.check && (!unowned.is_empty() || unapproved > 0);
+
+    if args.json {
+        session.console.out.write_line(json::format(
+            &OwnersResult {
+                approvals,
+                files,
+                unowned,
+            },
+            true,
+        )?)?;
+
+        return Ok(if failed { Some(1) } else { None });
+    }
+
+    if files.is_empty() {
+        session.console.render(element! {
+            Container {
+                Notice(variant: Variant::Info) {
+                    StyledText(content: "No files to resolve owners for.")
+                }
+            }
+        })?;
+
+        return Ok(None);
+    }
+
+    let path_width = files.iter().fold(0, |acc, file| acc.max(file.path.len()));
+
+    session.console.render(element! {
+        Container {
+            Table(
+                headers: vec![
+                    TableHeader::new("File", Size::Length((path_width + 5).max(10) as u32)),
+                    TableHeader::new("Owners", Size::Auto),
+                    TableHeader::new("Source", Size::Length(25)).hide_below(100),
+                ]
+            ) {
+                #(files.iter().enumerate().map(|(i, file)| {
+                    element! {
+                        TableRow(row: i as i32) {
+                            TableCol(col: 0) {
+                                StyledText(
+                                    content: &file.path,
+                                    style: Style::File
+                                )
+                            }
+                            TableCol(col: 1) {
+                                StyledText(
+                                    content: if file.is_owned() {
+                                        file.owners.join(" ")
+                                    } else {
+                                        "(unowned)".into()
+                                    },
+                                    style: if file.is_owned() {
+                                        Style::Label
+                                    } else {
+                                        Style::Caution
+                                    }
+                                )
+                            }
+                            TableCol(col: 2) {
+                                StyledText(
+                                    content: file
+                                        .rules
+                                        .iter()
+                                        .map(|rule| rule.section.as_str())
+                                        .collect::<Vec<_>>()
+                                        .join(", "),
+                                    style: Style::Id
+                                )
+                            }
+                        }
+                    }
+                }))
+            }
+            #(if approvals.is_empty() {
+                None
+            } else {
+                Some(element! {
+                    Table(
+                        headers: vec![
+                            TableHeader::new("Approvals", Size::Length(25)),
+                            TableHeader::new("Required", Size::Length(10)),
+                            TableHeader::new("Approved by", Size::Auto),
+                        ]
+                    ) {
+                        #(approvals.iter().enumerate().map(|(i, req)| {
+                            element! {
+                                TableRow(row: i as i32) {
+                                    TableCol(col: 0) {
+                                        StyledText(
+                                            content: &req.section,
+                                            style: Style::Id
+                                        )
+                                    }
+                                    TableCol(col: 1) {
+                                        StyledText(
+                                            content: req.required_approvals.to_string(),
+                                        )
+                                    }
+                                    TableCol(col: 2) {
+                                        StyledText(
+                                            content: if req.approved_by.is_empty() {
+                                                "(none)".into()
+                                            } else {
+                                                req.approved_by.join(" ")
+                                            },
+                                            style: if req.is_satisfied() {
+                                                Style::Success
+                                            } else {
+                                                Style::Caution
+                                            }
+                                        )
+                                    }
+                                }
+                            }
+                        }))
+                    }
+                })
+            })
+            #(if unapproved == 0 {
+                None
+            } else {
+                Some(element! {
+                    Notice(variant: if args.check { Variant::Failure } else { Variant::Caution }) {
+                        StyledText(
+                            content: if args.approver.is_empty() {
+                                format!(
+                                    "{unapproved} section(s) require approvals, but no approvers were provided. Pass each approver with <shell>--approver</shell>."
+                                )
+                            } else {
+                                format!("{unapproved} section(s) are missing required approvals.")
+                            }
+                        )
+                    }
+                })
+            })
+            #(if unowned.is_empty() {
+                None
+            } else {
+                Some(element! {
+                    Notice(variant: if args.check { Variant::Failure } else { Variant::Caution }) {
+                        StyledText(
+                            content: format!(
+                                "{} file(s) are not owned by anyone. Configure <property>owners</property> in the project, or <property>codeowners.globalPaths</property> in the workspace.",
+                                unowned.len()
+                            )
+                        )
+                    }
+                })
+            })
+        }
+    })?;
+
+    Ok(if failed { Some(1) } else { None })
+}
@@ -6,6 +6,7 @@ pub use crate::queries::touched_files::*;
 use crate::session::MoonSession;
 use clap::{Args, Subcommand};
 use iocraft::prelude::{Size, element};
+use moon_action_pipeline::flaky_history::FlakyHistoryState;
 use moon_affected::{AffectedTracker, DownstreamScope, UpstreamScope};
 use moon_common::is_ci;
 use moon_console::ui::{Container, Style, StyledText, Table, TableCol, TableHeader, TableRow};
@@ -468,6 +469,12 @@ pub async fn tasks(session: MoonSession, args: QueryTasksArgs) -> AppResult {
         return Ok(None);
     }
 
+    let flaky_history = session
+        .get_cache_engine()?
+        .state
+        .load_state::<FlakyHistoryState>("flakyHistory.json")?
+        .data;
+
     let id_width = tasks
         .iter()
         .fold(0, |acc, task| acc.max(task.target.as_str().len()));
@@ -484,10 +491,17 @@ pub async fn tasks(session: MoonSession, args: QueryTasksArgs) -> AppResult {
                     TableHeader::new("Type", Size::Length(10)).hide_below(130),
                     TableHeader::new("Preset", Size::Length(10)).hide_below(160),
                     TableHeader::new("Toolchains", Size::Length(25)),
+                    TableHeader::new("Flakiness", Size::Length(11)).hide_below(120),
                     TableHeader::new("Description", Size::Auto).hide_below(100),
                 ]
             ) {
                 #(tasks.into_iter().enumerate().map(|(i, task)| {
+                    let flakiness = flaky_history
+                        .get_score(task.target.as_str())
+                        .filter(|score| *score > 0.0)
+                        .map(|score| format!("{:.0}%", score * 100.0))
+                        .unwrap_or_default();
+
                     element! {
                         TableRow(row: i as i32) {
                             TableCol(col: 0) {
@@ -522,6 +536,12 @@ pub async fn tasks(session: MoonSession, args: QueryTasksArgs) -> AppResult {
                                 )
                             }
                             TableCol(col: 5) {
+                                StyledText(
+                                    content: flakiness,
+                                    style: Style::Caution
+                                )
+                            }
+                            TableCol(col: 6) {
                                 StyledText(
                                     content: task.description.as_deref().unwrap_or(""),
                                 )
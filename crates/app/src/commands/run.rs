@@ -1,23 +1,35 @@
-use crate::components::run_action_pipeline;
+use crate::components::{create_task_dashboard, run_action_pipeline};
 use crate::queries::touched_files::{QueryTouchedFilesOptions, query_touched_files};
 use crate::session::MoonSession;
-use clap::Args;
+use clap::{Args, ValueEnum};
 use iocraft::prelude::element;
 use moon_action_context::{ActionContext, ProfileType};
-use moon_action_graph::{ActionGraphBuilderOptions, RunRequirements};
+use moon_action_graph::{ActionGraph, ActionGraphBuilderOptions, RunRequirements};
 use moon_affected::{DownstreamScope, UpstreamScope};
 use moon_cache::CacheMode;
 use moon_common::{is_ci, is_test_env};
 use moon_console::ui::{Container, Notice, StyledText, Variant};
+use moon_process::ProcessRegistry;
 use moon_task::TargetLocator;
 use moon_vcs::TouchedStatus;
 use rustc_hash::FxHashSet;
 use starbase::AppResult;
-use tracing::instrument;
+use std::path::PathBuf;
+use tracing::{debug, instrument};
 
 const HEADING_AFFECTED: &str = "Affected by";
 const HEADING_DEBUGGING: &str = "Debugging";
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub enum RunUi {
+    /// Print checkpoints and output as a linear stream
+    #[default]
+    Default,
+
+    /// Render a full-screen dashboard with a pane per task
+    Tui,
+}
+
 #[derive(Args, Clone, Debug, Default)]
 pub struct RunArgs {
     #[arg(required = true, help = "List of targets to run")]
@@ -46,6 +58,20 @@ pub struct RunArgs {
     )]
     pub summary: bool,
 
+    #[arg(
+        value_enum,
+        long,
+        default_value_t,
+        help = "Interface to render running tasks with"
+    )]
+    pub ui: RunUi,
+
+    #[arg(
+        long,
+        help = "Keep the dashboard open once the pipeline has completed (requires --ui tui)"
+    )]
+    pub keep_open: bool,
+
     #[arg(
         short = 'u',
         long = "updateCache",
@@ -75,6 +101,30 @@ pub struct RunArgs {
     )]
     pub profile: Option<ProfileType>,
 
+    #[arg(
+        long,
+        help = "Trace file access and report undeclared inputs and outputs (Linux only)",
+        help_heading = HEADING_DEBUGGING,
+    )]
+    pub sandbox: bool,
+
+    #[arg(
+        long,
+        help = "Write a Chrome trace of the pipeline to the provided file",
+        help_heading = HEADING_DEBUGGING,
+    )]
+    pub trace: Option<PathBuf>,
+
+    #[arg(
+        long,
+        num_args = 0..=1,
+        default_missing_value = "http://localhost:4318",
+        value_name = "ENDPOINT",
+        help = "Send the pipeline as OpenTelemetry spans to an OTLP/HTTP collector",
+        help_heading = HEADING_DEBUGGING,
+    )]
+    pub trace_otlp: Option<String>,
+
     // Affected
     #[arg(
         long,
@@ -235,16 +285,54 @@ pub async fn run_target(
     // Process all tasks in the graph
     let (action_context, action_graph) = action_graph_builder.build();
 
-    let results = run_action_pipeline(
+    let dashboard = if args.ui == RunUi::Tui {
+        if should_render_dashboard(session, args, &action_graph) {
+            Some(create_task_dashboard(
+                &session.console,
+                &action_graph,
+                args.keep_open,
+            ))
+        } else {
+            debug!("Unable to render the dashboard, falling back to the default reporter");
+
+            None
+        }
+    } else {
+        None
+    };
+
+    let pipeline = run_action_pipeline(
         session,
         ActionContext {
             passthrough_args: args.passthrough.to_owned(),
             profile: args.profile.to_owned(),
+            sandbox: args.sandbox,
             ..action_context
         },
         action_graph,
-    )
-    .await?;
+    );
+
+    let results = if let Some(instance) = dashboard {
+        tokio::pin!(pipeline);
+
+        let results = tokio::select! {
+            results = &mut pipeline => results,
+
+            // Exiting the dashboard while tasks are still running
+            // (typically persistent tasks) will terminate them
+            _ = instance.dashboard.wait_for_exit() => {
+                ProcessRegistry::instance().terminate_running();
+
+                pipeline.await
+            }
+        };
+
+        instance.stop().await?;
+
+        results?
+    } else {
+        pipeline.await?
+    };
 
     if args.no_bail {
         let failed = results.iter().any(|result| {
@@ -263,6 +351,22 @@ pub async fn run_target(
     Ok(None)
 }
 
+fn should_render_dashboard(
+    session: &MoonSession,
+    args: &RunArgs,
+    action_graph: &ActionGraph,
+) -> bool {
+    // The dashboard requires a terminal, and can't pass stdin to interactive tasks
+    session.console.out.is_terminal()
+        && !session.console.out.is_quiet()
+        && !is_ci()
+        && !args.interactive
+        && !action_graph
+            .get_nodes()
+            .into_iter()
+            .any(|node| node.is_interactive())
+}
+
 #[instrument(skip_all)]
 pub async fn run(session: MoonSession, args: RunArgs) -> AppResult {
     return run_target(&session, &args, &args.targets).await;
@@ -2,6 +2,7 @@ use crate::app_error::AppError;
 use crate::session::MoonSession;
 use clap::Args;
 use iocraft::prelude::{View, element};
+use moon_action_pipeline::flaky_history::FlakyHistoryState;
 use moon_common::is_test_env;
 use moon_console::ui::{
     Container, Entry, List, ListItem, Map, MapItem, Section, Style, StyledText,
@@ -63,6 +64,16 @@ pub async fn task(session: MoonSession, args: TaskArgs) -> AppResult {
     outputs.extend(&task.output_files);
     outputs.sort();
 
+    let flaky_history = session
+        .get_cache_engine()?
+        .state
+        .load_state::<FlakyHistoryState>("flakyHistory.json")?
+        .data;
+    let flakiness = flaky_history
+        .targets
+        .get(task.target.as_str())
+        .filter(|state| !state.runs.is_empty());
+
     let show_in_prod = !is_test_env();
 
     session.console.render(element! {
@@ -199,6 +210,41 @@ pub async fn task(session: MoonSession, args: TaskArgs) -> AppResult {
                         }))
                     }
                 }
+                Entry(
+                    name: "Secrets",
+                    no_children: task.secrets.is_empty()
+                ) {
+                    Map {
+                        #(task.secrets.iter().map(|(key, secret)| {
+                            let source = if let Some(env) = &secret.env {
+                                format!("env ${env}")
+                            } else if let Some(file) = &secret.file {
+                                format!("file {file}")
+                            } else if let Some(provider) = &secret.provider {
+                                format!("provider {provider}")
+                            } else {
+                                "unknown".into()
+                            };
+
+                            element! {
+                                MapItem(
+                                    name: element! {
+                                        StyledText(
+                                            content: key,
+                                            style: Style::Property
+                                        )
+                                    }.into_any(),
+                                    value: element! {
+                                        StyledText(
+                                            content: source,
+                                            style: Style::MutedLight
+                                        )
+                                    }.into_any(),
+                                )
+                            }
+                        }))
+                    }
+                }
                 Entry(
                     name: "Depends on",
                     no_children: task.deps.is_empty()
@@ -249,6 +295,19 @@ pub async fn task(session: MoonSession, args: TaskArgs) -> AppResult {
                         "No"
                     }.to_string(),
                 )
+                #(flakiness.map(|state| {
+                    element! {
+                        Entry(
+                            name: "Flakiness",
+                            content: format!(
+                                "{:.0}% ({} of {} runs)",
+                                state.score() * 100.0,
+                                state.flaky_count(),
+                                state.runs.len()
+                            ),
+                        )
+                    }
+                }))
             }
 
             Section(title: "Configuration") {
@@ -1,5 +1,5 @@
 use crate::session::MoonSession;
-use clap::Args;
+use clap::{Args, Subcommand};
 use iocraft::prelude::element;
 use miette::IntoDiagnostic;
 use moon_codegen::CodeGenerator;
@@ -12,8 +12,21 @@ use std::collections::BTreeMap;
 use std::sync::Arc;
 use tracing::instrument;
 
+#[derive(Clone, Debug, Subcommand)]
+pub enum TemplatesCommands {
+    #[command(
+        name = "update",
+        about = "Refresh remote templates (Git repositories, npm packages, archives) in the cache."
+    )]
+    Update,
+}
+
 #[derive(Args, Clone, Debug)]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct TemplatesArgs {
+    #[command(subcommand)]
+    command: Option<TemplatesCommands>,
+
     #[arg(long, help = "Filter the templates based on this pattern")]
     filter: Option<String>,
 
@@ -21,8 +34,64 @@ pub struct TemplatesArgs {
     json: bool,
 }
 
+#[instrument(skip_all)]
+pub async fn update(session: MoonSession) -> AppResult {
+    let mut generator = CodeGenerator::new(
+        &session.workspace_root,
+        &session.workspace_config.generator,
+        Arc::clone(&session.moon_env),
+    );
+
+    generator.update_templates().await?;
+
+    let remotes = session
+        .workspace_config
+        .generator
+        .templates
+        .iter()
+        .filter(|locator| locator.is_remote())
+        .collect::<Vec<_>>();
+
+    if remotes.is_empty() {
+        session.console.render(element! {
+            Container {
+                Notice(variant: Variant::Info) {
+                    StyledText(content: "No remote templates configured, nothing to update.")
+                }
+            }
+        })?;
+
+        return Ok(None);
+    }
+
+    session.console.render(element! {
+        Container {
+            Section(title: "Updated templates") {
+                List {
+                    #(remotes.into_iter().map(|locator| {
+                        element! {
+                            ListItem {
+                                StyledText(
+                                    content: locator.to_string(),
+                                    style: Style::File
+                                )
+                            }
+                        }
+                    }))
+                }
+            }
+        }
+    })?;
+
+    Ok(None)
+}
+
 #[instrument(skip_all)]
 pub async fn templates(session: MoonSession, args: TemplatesArgs) -> AppResult {
+    if let Some(TemplatesCommands::Update) = args.command {
+        return update(session).await;
+    }
+
     let mut generator = CodeGenerator::new(
         &session.workspace_root,
         &session.workspace_config.generator,
@@ -107,6 +176,32 @@ pub async fn templates(session: MoonSession, args: TemplatesArgs) -> AppResult {
                                 )
                             }.into_any()
                         )
+                        #(template.source.as_ref().map(|source| {
+                            element! {
+                                Entry(
+                                    name: "Source",
+                                    value: element! {
+                                        StyledText(
+                                            content: source.to_string(),
+                                            style: Style::File
+                                        )
+                                    }.into_any()
+                                )
+                            }
+                        }))
+                        #(template.source.as_ref().and_then(|source| source.get_version()).map(|version| {
+                            element! {
+                                Entry(
+                                    name: "Version",
+                                    value: element! {
+                                        StyledText(
+                                            content: version,
+                                            style: Style::Hash
+                                        )
+                                    }.into_any()
+                                )
+                            }
+                        }))
                         #(template.config.destination.as_ref().map(|dest| {
                             element! {
                                 Entry(
@@ -0,0 +1,732 @@
+use crate::components::run_action_pipeline;
+use crate::session::MoonSession;
+use clap::Args;
+use iocraft::prelude::element;
+use miette::IntoDiagnostic;
+use moon_action::ActionNode;
+use moon_action_context::ActionContext;
+use moon_action_graph::{ActionGraph, RunRequirements};
+use moon_affected::{DownstreamScope, UpstreamScope};
+use moon_common::consts::CONFIG_DIRNAME;
+use moon_common::path::{PathExt, WorkspaceRelativePathBuf};
+use moon_config::ConfigFinder;
+use moon_console::ui::{Container, Notice, StyledText, Variant};
+use moon_process::ProcessRegistry;
+use moon_task::{Target, TargetLocator};
+use moon_vcs::BoxedVcs;
+use moon_workspace_graph::WorkspaceGraph;
+use notify::{EventKind, RecursiveMode, Watcher};
+use rustc_hash::{FxHashMap, FxHashSet};
+use starbase::AppResult;
+use starbase_utils::glob::GlobSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+use tokio::task::JoinHandle;
+use tracing::{debug, instrument, trace};
+
+#[derive(Args, Clone, Debug)]
+pub struct WatchArgs {
+    #[arg(required = true, help = "List of targets to run")]
+    pub targets: Vec<TargetLocator>,
+
+    #[arg(
+        long,
+        default_value_t = 100,
+        help = "Amount of milliseconds to wait for file system changes to settle"
+    )]
+    pub debounce: u64,
+
+    #[arg(long, help = "Focus target(s) based on the result of a query")]
+    pub query: Option<String>,
+
+    #[arg(
+        long,
+        short = 's',
+        help = "Include a summary of all actions that were processed in the pipeline"
+    )]
+    pub summary: bool,
+
+    // Passthrough args (after --)
+    #[arg(
+        last = true,
+        help = "Arguments to pass through to the underlying command"
+    )]
+    pub passthrough: Vec<String>,
+}
+
+/// Prefix of the groups that child processes of persistent tasks are registered
+/// under, so that they can be restarted without affecting other processes.
+const PERSISTENT_PROCESS_GROUP: &str = "watch-persistent";
+
+fn get_persistent_process_group(target: &Target) -> String {
+    format!("{PERSISTENT_PROCESS_GROUP}:{target}")
+}
+
+/// Filters file system events down to files that should trigger a re-run.
+struct ChangeFilter {
+    ignored_dirs: Vec<PathBuf>,
+    output_files: FxHashSet<WorkspaceRelativePathBuf>,
+    output_globs: GlobSet<'static>,
+    project_file_names: Vec<String>,
+    tasks_file_names: Vec<String>,
+    vcs: Option<Arc<BoxedVcs>>,
+    workspace_file_names: Vec<String>,
+    workspace_root: PathBuf,
+}
+
+impl ChangeFilter {
+    pub fn new(workspace_root: &Path, config_finder: &ConfigFinder) -> miette::Result<Self> {
+        let mut workspace_file_names = config_finder.get_workspace_file_names();
+        workspace_file_names.extend(config_finder.get_toolchain_file_names());
+
+        Ok(Self {
+            ignored_dirs: vec![
+                workspace_root.join(".git"),
+                workspace_root.join(".hg"),
+                workspace_root.join(".jj"),
+                workspace_root.join(".sl"),
+                workspace_root.join(CONFIG_DIRNAME).join("cache"),
+            ],
+            output_files: FxHashSet::default(),
+            output_globs: GlobSet::new_owned(&Vec::<String>::new())?,
+            project_file_names: config_finder.get_project_file_names(),
+            tasks_file_names: config_finder.get_tasks_file_names(),
+            vcs: None,
+            workspace_file_names,
+            workspace_root: workspace_root.to_path_buf(),
+        })
+    }
+
+    pub fn set_vcs(&mut self, vcs: Arc<BoxedVcs>) {
+        if vcs.is_enabled() {
+            self.vcs = Some(vcs);
+        }
+    }
+
+    /// Outputs are written by the tasks we run, so they must be ignored,
+    /// otherwise we'll trigger an infinite loop of runs.
+    pub fn set_outputs(&mut self, workspace_graph: &WorkspaceGraph) -> miette::Result<()> {
+        let mut output_files = FxHashSet::default();
+        let mut output_globs = vec![];
+
+        for task in workspace_graph.get_tasks_with_internal()? {
+            output_files.extend(task.output_files.iter().cloned());
+            output_globs.extend(task.output_globs.iter().map(|glob| glob.to_string()));
+        }
+
+        self.output_files = output_files;
+        self.output_globs = GlobSet::new_owned(&output_globs)?;
+
+        Ok(())
+    }
+
+    pub fn filter(&self, path: &Path) -> Option<WorkspaceRelativePathBuf> {
+        if self.ignored_dirs.iter().any(|dir| path.starts_with(dir))
+            || self.vcs.as_ref().is_some_and(|vcs| vcs.is_ignored(path))
+        {
+            return None;
+        }
+
+        let file = path.relative_to(&self.workspace_root).ok()?;
+
+        if self
+            .output_files
+            .iter()
+            .any(|output| file.starts_with(output))
+            || self.output_globs.matches(file.as_str())
+        {
+            return None;
+        }
+
+        Some(file)
+    }
+
+    /// Project and task configs, which require the workspace graph to be rebuilt.
+    pub fn is_graph_config(&self, file: &WorkspaceRelativePathBuf) -> bool {
+        let Some(name) = file.file_name() else {
+            return false;
+        };
+
+        if file.starts_with(CONFIG_DIRNAME) {
+            return file.starts_with(format!("{CONFIG_DIRNAME}/tasks"))
+                || (is_in_config_dir(file) && self.tasks_file_names.iter().any(|n| n == name));
+        }
+
+        self.project_file_names.iter().any(|n| n == name)
+    }
+
+    /// Workspace and toolchain configs, which require the process to be restarted.
+    pub fn is_workspace_config(&self, file: &WorkspaceRelativePathBuf) -> bool {
+        file.file_name().is_some_and(|name| {
+            is_in_config_dir(file) && self.workspace_file_names.iter().any(|n| n == name)
+        })
+    }
+}
+
+fn is_in_config_dir(file: &WorkspaceRelativePathBuf) -> bool {
+    file.parent()
+        .is_some_and(|parent| parent.as_str() == CONFIG_DIRNAME)
+}
+
+/// Wait for a batch of changed files, debounced by the provided duration.
+async fn wait_for_changes(
+    receiver: &mut UnboundedReceiver<PathBuf>,
+    filter: &ChangeFilter,
+    debounce: Duration,
+) -> FxHashSet<WorkspaceRelativePathBuf> {
+    let mut files = FxHashSet::default();
+
+    // Block until we receive the first relevant change
+    while let Some(path) = receiver.recv().await {
+        if let Some(file) = filter.filter(&path) {
+            files.insert(file);
+            break;
+        }
+    }
+
+    // Then collect all changes that occur shortly after
+    while let Ok(Some(path)) = tokio::time::timeout(debounce, receiver.recv()).await {
+        if let Some(file) = filter.filter(&path) {
+            files.insert(file);
+        }
+    }
+
+    files
+}
+
+/// Build an action graph for the targets. When touched files are provided,
+/// only the tasks affected by those files will be included.
+async fn build_action_graph(
+    session: &MoonSession,
+    args: &WatchArgs,
+    locators: &[TargetLocator],
+    touched_files: Option<FxHashSet<WorkspaceRelativePathBuf>>,
+) -> miette::Result<Option<(ActionContext, ActionGraph)>> {
+    let mut action_graph_builder = session.build_action_graph().await?;
+
+    if let Some(query_input) = &args.query {
+        action_graph_builder.set_query(query_input)?;
+    }
+
+    if let Some(touched_files) = touched_files {
+        action_graph_builder.set_touched_files(touched_files)?;
+        action_graph_builder.track_affected(UpstreamScope::Deep, DownstreamScope::Deep, false)?;
+    }
+
+    let reqs = RunRequirements::default();
+    let mut inserted_nodes = FxHashSet::default();
+
+    for locator in locators {
+        inserted_nodes.extend(
+            action_graph_builder
+                .run_task_by_target_locator(locator, &reqs)
+                .await?,
+        );
+    }
+
+    if inserted_nodes.is_empty() {
+        return Ok(None);
+    }
+
+    let (action_context, action_graph) = action_graph_builder.build();
+
+    Ok(Some((
+        ActionContext {
+            passthrough_args: args.passthrough.to_owned(),
+            ..action_context
+        },
+        action_graph,
+    )))
+}
+
+/// Return the primary targets of persistent tasks within the action graph.
+fn get_persistent_targets(
+    action_context: &ActionContext,
+    action_graph: &ActionGraph,
+) -> Vec<Target> {
+    let mut targets = action_graph
+        .get_nodes()
+        .into_iter()
+        .filter_map(|node| match node {
+            ActionNode::RunTask(inner)
+                if inner.persistent && action_context.primary_targets.contains(&inner.target) =>
+            {
+                Some(inner.target.clone())
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    targets.sort();
+    targets.dedup();
+    targets
+}
+
+/// Persistent tasks never complete, so each is ran in its own background pipeline,
+/// with its own process group, so that it can be restarted without affecting others.
+#[derive(Default)]
+struct PersistentPipelines {
+    handles: FxHashMap<Target, JoinHandle<()>>,
+}
+
+impl PersistentPipelines {
+    pub async fn start(
+        &mut self,
+        session: &MoonSession,
+        args: &WatchArgs,
+        target: Target,
+    ) -> miette::Result<()> {
+        self.stop(&target).await;
+
+        let locators = [TargetLocator::Qualified(target.clone())];
+
+        let Some((mut action_context, action_graph)) =
+            build_action_graph(session, args, &locators, None).await?
+        else {
+            return Ok(());
+        };
+
+        action_context.process_group = Some(get_persistent_process_group(&target));
+
+        let session = session.clone();
+
+        self.handles.insert(
+            target,
+            tokio::spawn(async move {
+                if let Err(error) =
+                    run_action_pipeline(&session, action_context, action_graph).await
+                {
+                    let _ = render_notice(&session, Variant::Failure, error.to_string());
+                }
+            }),
+        );
+
+        Ok(())
+    }
+
+    pub async fn stop(&mut self, target: &Target) {
+        if let Some(handle) = self.handles.remove(target) {
+            debug!(target = target.as_str(), "Stopping persistent task");
+
+            // Abort before terminating, so that the pipeline doesn't
+            // report the terminated processes as failures
+            handle.abort();
+            let _ = handle.await;
+
+            ProcessRegistry::instance()
+                .terminate_running_in_group(&get_persistent_process_group(target))
+                .await;
+        }
+    }
+
+    pub async fn stop_all(&mut self) {
+        let targets = self.handles.keys().cloned().collect::<Vec<_>>();
+
+        for target in targets {
+            self.stop(&target).await;
+        }
+    }
+}
+
+fn render_notice(session: &MoonSession, variant: Variant, message: String) -> miette::Result<()> {
+    session.console.render(element! {
+        Container {
+            Notice(variant: variant) {
+                StyledText(content: message)
+            }
+        }
+    })?;
+
+    Ok(())
+}
+
+#[instrument(skip_all)]
+pub async fn watch(mut session: MoonSession, args: WatchArgs) -> AppResult {
+    let vcs = session.get_vcs_adapter()?;
+    let debounce = Duration::from_millis(args.debounce);
+
+    let mut filter = ChangeFilter::new(&session.workspace_root, &session.config_loader)?;
+    filter.set_vcs(Arc::clone(&vcs));
+    filter.set_outputs(&session.get_workspace_graph().await?)?;
+
+    // Watch the entire workspace and forward paths to the main loop
+    let (sender, mut receiver) = mpsc::unbounded_channel::<PathBuf>();
+
+    let mut watcher = notify::recommended_watcher(move |result: notify::Result<notify::Event>| {
+        if let Ok(event) = result {
+            if matches!(event.kind, EventKind::Access(_)) {
+                return;
+            }
+
+            for path in event.paths {
+                let _ = sender.send(path);
+            }
+        }
+    })
+    .into_diagnostic()?;
+
+    watcher
+        .watch(&session.workspace_root, RecursiveMode::Recursive)
+        .into_diagnostic()?;
+
+    debug!(root = ?session.workspace_root, "Watching workspace for changes");
+
+    let mut signal_receiver = ProcessRegistry::instance().receive_signal();
+    let mut persistent_pipelines = PersistentPipelines::default();
+    let mut touched_files = None;
+
+    loop {
+        match build_action_graph(&session, &args, &args.targets, touched_files.take()).await? {
+            Some((action_context, action_graph)) => {
+                let persistent_targets = get_persistent_targets(&action_context, &action_graph);
+
+                if persistent_targets.is_empty() {
+                    if let Err(error) =
+                        run_action_pipeline(&session, action_context, action_graph).await
+                    {
+                        render_notice(&session, Variant::Failure, error.to_string())?;
+                    }
+                } else {
+                    // Run the other affected tasks first, as persistent tasks
+                    // never complete, and would block them from running
+                    let locators = action_context
+                        .primary_targets
+                        .iter()
+                        .filter(|target| !persistent_targets.contains(target))
+                        .map(|target| TargetLocator::Qualified(target.to_owned()))
+                        .collect::<Vec<_>>();
+
+                    if !locators.is_empty() {
+                        if let Some((action_context, action_graph)) =
+                            build_action_graph(&session, &args, &locators, None).await?
+                        {
+                            if let Err(error) =
+                                run_action_pipeline(&session, action_context, action_graph).await
+                            {
+                                render_notice(&session, Variant::Failure, error.to_string())?;
+                            }
+                        }
+                    }
+
+                    // Then restart (instead of re-queueing) only the affected persistent tasks
+                    for target in persistent_targets {
+                        persistent_pipelines.start(&session, &args, target).await?;
+                    }
+                }
+            }
+            None => {
+                trace!("No tasks affected by changed files");
+            }
+        };
+
+        render_notice(
+            &session,
+            Variant::Info,
+            "Waiting for file changes...".into(),
+        )?;
+
+        tokio::select! {
+            files = wait_for_changes(&mut receiver, &filter, debounce) => {
+                debug!(
+                    files = ?files.iter().collect::<Vec<_>>(),
+                    "Detected {} changed files",
+                    files.len(),
+                );
+
+                // Clear command caches, as the repository state has changed
+                vcs.clear_cache();
+
+                if files.iter().any(|file| filter.is_workspace_config(file)) {
+                    render_notice(
+                        &session,
+                        Variant::Caution,
+                        "Workspace or toolchain configuration has changed, restart <shell>moon watch</shell> for it to take effect".into(),
+                    )?;
+                }
+
+                if files.iter().any(|file| filter.is_graph_config(file)) {
+                    debug!("Project or task configuration has changed, rebuilding workspace graph");
+
+                    match session.reload_workspace_graph().await {
+                        Ok(_) => {
+                            filter.set_outputs(&session.get_workspace_graph().await?)?;
+                        }
+                        Err(error) => {
+                            render_notice(&session, Variant::Failure, error.to_string())?;
+                        }
+                    };
+                }
+
+                touched_files = Some(files);
+            }
+            _ = signal_receiver.recv() => {
+                debug!("Received signal, stopping watcher");
+
+                break;
+            }
+        };
+    }
+
+    persistent_pipelines.stop_all().await;
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_filter() -> ChangeFilter {
+        ChangeFilter::new(Path::new("/workspace"), &ConfigFinder::default()).unwrap()
+    }
+
+    fn file(path: &str) -> WorkspaceRelativePathBuf {
+        WorkspaceRelativePathBuf::from(path)
+    }
+
+    mod change_filter {
+        use super::*;
+
+        #[test]
+        fn returns_relative_files() {
+            let filter = create_filter();
+
+            assert_eq!(
+                filter.filter(Path::new("/workspace/app/src/index.ts")),
+                Some(file("app/src/index.ts"))
+            );
+        }
+
+        #[test]
+        fn ignores_files_outside_workspace() {
+            let filter = create_filter();
+
+            assert_eq!(filter.filter(Path::new("/other/index.ts")), None);
+        }
+
+        #[test]
+        fn ignores_vcs_and_cache_dirs() {
+            let filter = create_filter();
+
+            for path in [
+                "/workspace/.git/index",
+                "/workspace/.hg/dirstate",
+                "/workspace/.jj/repo/store",
+                "/workspace/.sl/dirstate",
+                "/workspace/.moon/cache/states/app/build/lastRun.json",
+            ] {
+                assert_eq!(filter.filter(Path::new(path)), None);
+            }
+        }
+
+        #[test]
+        fn ignores_output_files_and_dirs() {
+            let mut filter = create_filter();
+            filter.output_files.insert(file("app/dist"));
+            filter.output_files.insert(file("app/types.d.ts"));
+
+            assert_eq!(
+                filter.filter(Path::new("/workspace/app/dist/index.js")),
+                None
+            );
+            assert_eq!(filter.filter(Path::new("/workspace/app/types.d.ts")), None);
+            assert_eq!(
+                filter.filter(Path::new("/workspace/app/distribution.ts")),
+                Some(file("app/distribution.ts"))
+            );
+        }
+
+        #[test]
+        fn ignores_output_globs() {
+            let mut filter = create_filter();
+            filter.output_globs = GlobSet::new_owned(&vec!["app/build/**/*".to_owned()]).unwrap();
+
+            assert_eq!(
+                filter.filter(Path::new("/workspace/app/build/nested/index.js")),
+                None
+            );
+            assert_eq!(
+                filter.filter(Path::new("/workspace/app/src/index.js")),
+                Some(file("app/src/index.js"))
+            );
+        }
+
+        #[test]
+        fn detects_graph_configs() {
+            let filter = create_filter();
+
+            assert!(filter.is_graph_config(&file("app/moon.yml")));
+            assert!(filter.is_graph_config(&file("packages/lib/moon.pkl")));
+            assert!(filter.is_graph_config(&file(".moon/tasks.yml")));
+            assert!(filter.is_graph_config(&file(".moon/tasks/node.yml")));
+            assert!(filter.is_graph_config(&file(".moon/tasks/tag-ui.yml")));
+
+            assert!(!filter.is_graph_config(&file("app/moon.json")));
+            assert!(!filter.is_graph_config(&file("app/src/index.ts")));
+            assert!(!filter.is_graph_config(&file(".moon/workspace.yml")));
+            assert!(!filter.is_graph_config(&file(".moon/moon.yml")));
+        }
+
+        #[test]
+        fn detects_workspace_configs() {
+            let filter = create_filter();
+
+            assert!(filter.is_workspace_config(&file(".moon/workspace.yml")));
+            assert!(filter.is_workspace_config(&file(".moon/toolchain.yml")));
+            assert!(filter.is_workspace_config(&file(".moon/toolchain.pkl")));
+
+            assert!(!filter.is_workspace_config(&file("app/workspace.yml")));
+            assert!(!filter.is_workspace_config(&file(".moon/tasks.yml")));
+            assert!(!filter.is_workspace_config(&file("app/moon.yml")));
+        }
+    }
+
+    #[cfg(unix)]
+    mod persistent_pipelines {
+        use super::*;
+        use moon_action::RunTaskNode;
+        use moon_action_graph::ActionGraphType;
+        use moon_toolchain::Runtime;
+
+        fn create_graph(nodes: &[(&str, bool)]) -> ActionGraph {
+            let mut graph = ActionGraphType::default();
+
+            for (target, persistent) in nodes {
+                let mut node = RunTaskNode::new(Target::parse(target).unwrap(), Runtime::system());
+                node.persistent = *persistent;
+
+                graph.add_node(ActionNode::run_task(node));
+            }
+
+            ActionGraph::new(graph)
+        }
+
+        fn spawn_sleep() -> tokio::process::Child {
+            tokio::process::Command::new("sleep")
+                .arg("30")
+                .spawn()
+                .unwrap()
+        }
+
+        #[test]
+        fn only_returns_primary_persistent_targets() {
+            let graph = create_graph(&[("web:dev", true), ("web:build", false), ("db:dev", true)]);
+            let context = ActionContext {
+                primary_targets: FxHashSet::from_iter([
+                    Target::parse("web:dev").unwrap(),
+                    Target::parse("web:build").unwrap(),
+                ]),
+                ..Default::default()
+            };
+
+            assert_eq!(
+                get_persistent_targets(&context, &graph),
+                [Target::parse("web:dev").unwrap()]
+            );
+        }
+
+        #[tokio::test]
+        async fn only_stops_affected_targets() {
+            let api = Target::parse("api:dev").unwrap();
+            let web = Target::parse("web:dev").unwrap();
+            let registry = ProcessRegistry::instance();
+
+            let api_child = registry
+                .add_running_to_group(spawn_sleep(), &get_persistent_process_group(&api))
+                .await;
+            let web_child = registry
+                .add_running_to_group(spawn_sleep(), &get_persistent_process_group(&web))
+                .await;
+
+            let mut pipelines = PersistentPipelines::default();
+            pipelines
+                .handles
+                .insert(api.clone(), tokio::spawn(std::future::pending()));
+            pipelines
+                .handles
+                .insert(web.clone(), tokio::spawn(std::future::pending()));
+
+            // Only `web` has been affected by the changed files
+            let graph = create_graph(&[("web:dev", true)]);
+            let context = ActionContext {
+                primary_targets: FxHashSet::from_iter([web.clone()]),
+                ..Default::default()
+            };
+
+            for target in get_persistent_targets(&context, &graph) {
+                pipelines.stop(&target).await;
+            }
+
+            assert!(!pipelines.handles.contains_key(&web));
+            assert!(!pipelines.handles[&api].is_finished());
+            assert!(registry.get_running_by_pid(web_child.id()).await.is_none());
+            assert!(registry.get_running_by_pid(api_child.id()).await.is_some());
+
+            pipelines.stop_all().await;
+
+            assert!(pipelines.handles.is_empty());
+            assert!(registry.get_running_by_pid(api_child.id()).await.is_none());
+        }
+    }
+
+    mod wait_for_changes {
+        use super::*;
+
+        #[tokio::test]
+        async fn collects_changes_within_debounce() {
+            let filter = create_filter();
+            let (sender, mut receiver) = mpsc::unbounded_channel();
+
+            sender.send(PathBuf::from("/workspace/a.ts")).unwrap();
+            sender.send(PathBuf::from("/workspace/b.ts")).unwrap();
+            sender.send(PathBuf::from("/workspace/a.ts")).unwrap();
+
+            let files = wait_for_changes(&mut receiver, &filter, Duration::from_millis(50)).await;
+
+            assert_eq!(files, FxHashSet::from_iter([file("a.ts"), file("b.ts")]));
+        }
+
+        #[tokio::test]
+        async fn skips_filtered_changes() {
+            let filter = create_filter();
+            let (sender, mut receiver) = mpsc::unbounded_channel();
+
+            sender.send(PathBuf::from("/workspace/.git/index")).unwrap();
+            sender
+                .send(PathBuf::from("/workspace/.moon/cache/hashes/abc.json"))
+                .unwrap();
+            sender.send(PathBuf::from("/workspace/a.ts")).unwrap();
+
+            let files = wait_for_changes(&mut receiver, &filter, Duration::from_millis(50)).await;
+
+            assert_eq!(files, FxHashSet::from_iter([file("a.ts")]));
+        }
+
+        #[tokio::test]
+        async fn leaves_changes_after_debounce_for_next_batch() {
+            let filter = create_filter();
+            let (sender, mut receiver) = mpsc::unbounded_channel();
+
+            sender.send(PathBuf::from("/workspace/a.ts")).unwrap();
+
+            let delayed_sender = sender.clone();
+
+            tokio::spawn(async move {
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                delayed_sender
+                    .send(PathBuf::from("/workspace/b.ts"))
+                    .unwrap();
+            });
+
+            let files = wait_for_changes(&mut receiver, &filter, Duration::from_millis(20)).await;
+
+            assert_eq!(files, FxHashSet::from_iter([file("a.ts")]));
+
+            let files = wait_for_changes(&mut receiver, &filter, Duration::from_millis(20)).await;
+
+            assert_eq!(files, FxHashSet::from_iter([file("b.ts")]));
+        }
+    }
+}
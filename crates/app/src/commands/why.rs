@@ -0,0 +1,380 @@
+use crate::app_error::AppError;
+use crate::queries::touched_files::load_touched_files;
+use crate::session::MoonSession;
+use clap::Args;
+use iocraft::prelude::element;
+use moon_action::{ActionNode, RunTaskNode};
+use moon_action_context::{ActionContext, TargetState};
+use moon_affected::{AffectedBy, AffectedTracker};
+use moon_common::is_ci;
+use moon_console::ui::{Container, Section, StyledText};
+use moon_project::Project;
+use moon_task::{Target, TargetScope, Task, TaskOptionRunInCI};
+use moon_task_hasher::{TaskHashChange, TaskHashChangeKind, diff_task_hash_manifests};
+use moon_task_runner::{TaskRunCacheState, TaskRunner};
+use moon_toolchain::Runtime;
+use moon_workspace_graph::WorkspaceGraph;
+use serde::Serialize;
+use starbase::AppResult;
+use starbase_utils::json::{self, JsonValue};
+use std::sync::Arc;
+use tracing::instrument;
+
+#[derive(Args, Clone, Debug)]
+pub struct WhyArgs {
+    #[arg(help = "Target of task to explain")]
+    target: Target,
+
+    #[arg(long, help = "Print the explanation in JSON format")]
+    json: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WhyResult {
+    affected: Option<String>,
+    cache: Vec<String>,
+    changes: Vec<TaskHashChange>,
+    current_hash: Option<String>,
+    previous_hash: Option<String>,
+    skipped: Vec<String>,
+    target: Target,
+}
+
+fn describe_affected_by(by: &AffectedBy) -> String {
+    match by {
+        AffectedBy::AlreadyMarked => "another affected task".into(),
+        AffectedBy::AlwaysAffected => "runInCI being set to always".into(),
+        AffectedBy::DownstreamProject(id) => format!("downstream project {id}"),
+        AffectedBy::DownstreamTask(target) => format!("downstream task {target}"),
+        AffectedBy::EnvironmentVariable(name) => format!("environment variable ${name}"),
+        AffectedBy::Task(target) => format!("task {target}"),
+        AffectedBy::TouchedFile(file) => format!("touched file {file}"),
+        AffectedBy::UpstreamProject(id) => format!("upstream project {id}"),
+        AffectedBy::UpstreamTask(target) => format!("upstream task {target}"),
+    }
+}
+
+fn explain_skipped(task: &Task, dep_states: &[(&Target, TaskRunCacheState)]) -> Vec<String> {
+    let mut reasons = vec![];
+
+    if let Some(os_list) = &task.options.os {
+        if !os_list.iter().any(|os| os.is_current_system()) {
+            reasons.push(format!(
+                "Only runs on {}, so its command is a no-op on this system",
+                os_list
+                    .iter()
+                    .map(|os| os.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+    } else if task.is_no_op() {
+        reasons.push("Command is a no-op, so nothing will be executed".into());
+    }
+
+    if !task.options.run_in_ci.is_enabled() {
+        reasons.push("Option runInCI is disabled, so it will be skipped by moon ci".into());
+    } else if !task.should_run_in_ci() {
+        reasons.push(format!(
+            "Only build and test tasks run in CI, and this is a {} task, so it will be skipped by moon ci",
+            task.type_of
+        ));
+    }
+
+    for (target, state) in dep_states {
+        if state.exit_code != 0 {
+            reasons.push(format!(
+                "Dependency {target} failed on its last run (exit code {}), and tasks are skipped when a dependency fails",
+                state.exit_code
+            ));
+        } else if state.sandbox_violation {
+            reasons.push(format!(
+                "Dependency {target} failed on its last run (accessed undeclared files), and tasks are skipped when a dependency fails"
+            ));
+        }
+    }
+
+    reasons
+}
+
+async fn explain_affected(
+    session: &MoonSession,
+    workspace_graph: Arc<WorkspaceGraph>,
+    task: &Task,
+) -> miette::Result<Option<String>> {
+    let vcs = session.get_vcs_adapter()?;
+
+    if !vcs.is_enabled() {
+        return Ok(None);
+    }
+
+    let touched_files = load_touched_files(&vcs).await?;
+    let ci = is_ci();
+
+    let mut affected_tracker = AffectedTracker::new(workspace_graph, touched_files);
+    affected_tracker.set_ci_check(ci);
+
+    let message = match affected_tracker.is_task_affected(task)? {
+        Some(by) => format!("Affected by {}", describe_affected_by(&by)),
+        None if ci && matches!(task.options.run_in_ci, TaskOptionRunInCI::Enabled(false)) => {
+            "Not affected, as runInCI is disabled".into()
+        }
+        None if task.state.empty_inputs => "Not affected, as it has no inputs".into(),
+        None => {
+            "Not affected, as no touched files or environment variables match its inputs".into()
+        }
+    };
+
+    Ok(Some(message))
+}
+
+async fn explain_cache(
+    session: &MoonSession,
+    project: &Project,
+    task: &Task,
+    dep_states: &[(&Target, TaskRunCacheState)],
+    result: &mut WhyResult,
+) -> miette::Result<()> {
+    if !task.options.cache {
+        result
+            .cache
+            .push("Caching is disabled, so it will always run".into());
+
+        return Ok(());
+    }
+
+    let app_context = session.get_app_context().await?;
+    let workspace_graph = session.get_workspace_graph().await?;
+    let context = ActionContext::default();
+
+    // Dependencies are hashed with the results of the current pipeline, which
+    // is not available here, so use the hashes of their last successful runs
+    for (target, state) in dep_states {
+        if !state.last_success_hash.is_empty() {
+            context.set_target_state(target, TargetState::Passed(state.last_success_hash.clone()));
+        } else if !workspace_graph.get_task(target)?.options.cache {
+            context.set_target_state(target, TargetState::Passthrough);
+        }
+    }
+
+    let action_graph_builder = session.build_action_graph().await?;
+    let runtime = task
+        .toolchains
+        .first()
+        .and_then(|toolchain_id| action_graph_builder.get_runtime(project, toolchain_id, true))
+        .unwrap_or_else(Runtime::system);
+    let node = ActionNode::run_task(RunTaskNode::new(task.target.clone(), runtime));
+
+    let mut runner = TaskRunner::new(&app_context, project, task)?;
+    let exit_code = runner.cache.data.exit_code;
+    let is_successful = runner.cache.data.is_successful();
+    let sandbox_violation = runner.cache.data.sandbox_violation;
+    let last_hash = runner.cache.data.hash.clone();
+    let last_success_hash = runner.cache.data.last_success_hash.clone();
+
+    let current_hash = runner.generate_hash(&context, &node).await?;
+
+    // States from before successful hashes were tracked only have the last hash
+    let previous_hash = if !last_success_hash.is_empty() {
+        Some(last_success_hash)
+    } else if is_successful && !last_hash.is_empty() {
+        Some(last_hash.clone())
+    } else {
+        None
+    };
+
+    if exit_code != 0 && !last_hash.is_empty() {
+        result.cache.push(format!(
+            "The last run failed (exit code {exit_code}), so it will not be hydrated from the cache"
+        ));
+    } else if sandbox_violation && !last_hash.is_empty() {
+        result.cache.push(
+            "The last run failed (accessed undeclared files), so it will not be hydrated from the cache"
+                .into(),
+        );
+    }
+
+    match &previous_hash {
+        None => {
+            result.cache.push(
+                "Has not ran successfully before, so there is no previous hash to compare against"
+                    .into(),
+            );
+        }
+        Some(previous_hash) if previous_hash == &current_hash => {
+            result.cache.push(if is_successful {
+                "Hash matches the last successful run, so it will be a cache hit, unless its outputs are missing or its cacheLifetime has expired"
+                    .into()
+            } else {
+                "Hash matches the last successful run".into()
+            });
+        }
+        Some(previous_hash) => {
+            result.cache.push(
+                "Hash has changed since the last successful run, so it will be a cache miss".into(),
+            );
+
+            let hash_engine = &app_context.cache_engine.hash;
+            let previous_manifest = hash_engine.get_manifest_path(previous_hash);
+
+            if previous_manifest.exists() {
+                let previous: JsonValue = json::read_file(previous_manifest)?;
+                let current: JsonValue =
+                    json::read_file(hash_engine.get_manifest_path(&current_hash))?;
+
+                result.changes = diff_task_hash_manifests(&previous, &current);
+            } else {
+                result.cache.push(
+                    "The hash manifest of the last successful run no longer exists (the cache may have been cleaned), so changes can't be determined"
+                        .into(),
+                );
+            }
+        }
+    };
+
+    result.current_hash = Some(current_hash);
+    result.previous_hash = previous_hash;
+
+    Ok(())
+}
+
+fn get_change_label(kind: TaskHashChangeKind) -> &'static str {
+    match kind {
+        TaskHashChangeKind::Args => "Arguments",
+        TaskHashChangeKind::Command => "Command",
+        TaskHashChangeKind::Dep => "Dependencies",
+        TaskHashChangeKind::Env => "Environment variables",
+        TaskHashChangeKind::Input => "Inputs",
+        TaskHashChangeKind::InputEnv => "Input environment variables",
+        TaskHashChangeKind::Output => "Outputs",
+        TaskHashChangeKind::ProjectDep => "Project dependencies",
+        TaskHashChangeKind::Setting => "Settings",
+        TaskHashChangeKind::Toolchain => "Toolchains and plugins",
+    }
+}
+
+fn format_change(change: &TaskHashChange) -> String {
+    let (key_tag, value_tag) = match change.kind {
+        TaskHashChangeKind::Input => ("file", Some("hash")),
+        TaskHashChangeKind::Dep => ("id", Some("hash")),
+        TaskHashChangeKind::ProjectDep | TaskHashChangeKind::Toolchain => ("id", None),
+        TaskHashChangeKind::Output => ("file", None),
+        _ => ("property", None),
+    };
+
+    let format_value = |value: &str| match value_tag {
+        _ if value.is_empty() => "<muted>(empty)</muted>".to_owned(),
+        Some(tag) => format!("<{tag}>{value}</{tag}>"),
+        None => value.to_owned(),
+    };
+
+    // Items of a list are the key itself, so only the status is relevant
+    let status = match (&change.previous, &change.current) {
+        (Some(previous), Some(current)) => {
+            format!("{} → {}", format_value(previous), format_value(current))
+        }
+        (None, Some(current)) if current == &change.key => "added".into(),
+        (None, Some(current)) => format!("added {}", format_value(current)),
+        (Some(previous), None) if previous == &change.key => "removed".into(),
+        (Some(previous), None) => format!("removed {}", format_value(previous)),
+        (None, None) => String::new(),
+    };
+
+    format!("<{key_tag}>{}</{key_tag}> {status}", change.key)
+}
+
+#[instrument(skip_all)]
+pub async fn why(session: MoonSession, args: WhyArgs) -> AppResult {
+    let TargetScope::Project(project_locator) = &args.target.scope else {
+        return Err(AppError::ProjectIdRequired.into());
+    };
+
+    let workspace_graph = session.get_workspace_graph().await?;
+    let project = workspace_graph.get_project(project_locator)?;
+    let task = workspace_graph.get_task(&args.target)?;
+    let cache_engine = session.get_cache_engine()?;
+
+    let mut dep_states = vec![];
+
+    for dep in &task.deps {
+        dep_states.push((
+            &dep.target,
+            cache_engine
+                .state
+                .load_target_state::<TaskRunCacheState>(&dep.target)?
+                .data,
+        ));
+    }
+
+    let mut result = WhyResult {
+        affected: explain_affected(&session, workspace_graph.clone(), &task).await?,
+        cache: vec![],
+        changes: vec![],
+        current_hash: None,
+        previous_hash: None,
+        skipped: explain_skipped(&task, &dep_states),
+        target: task.target.clone(),
+    };
+
+    explain_cache(&session, &project, &task, &dep_states, &mut result).await?;
+
+    if args.json {
+        session
+            .console
+            .out
+            .write_line(json::format(&result, true)?)?;
+
+        return Ok(None);
+    }
+
+    let mut sections = vec![];
+
+    if !result.skipped.is_empty() {
+        sections.push(("Skipped", result.skipped.clone()));
+    }
+
+    if let Some(affected) = &result.affected {
+        sections.push(("Affected", vec![affected.to_owned()]));
+    }
+
+    let mut cache = result.cache.clone();
+
+    if let Some(hash) = &result.previous_hash {
+        cache.push(format!("Previous hash: <hash>{hash}</hash>"));
+    }
+
+    if let Some(hash) = &result.current_hash {
+        cache.push(format!("Current hash: <hash>{hash}</hash>"));
+    }
+
+    sections.push(("Cache", cache));
+
+    for change in &result.changes {
+        let label = get_change_label(change.kind);
+        let line = format_change(change);
+
+        match sections.iter_mut().find(|(title, _)| *title == label) {
+            Some((_, lines)) => lines.push(line),
+            None => sections.push((label, vec![line])),
+        };
+    }
+
+    session.console.render(element! {
+        Container {
+            #(sections.into_iter().map(|(title, lines)| {
+                element! {
+                    Section(title: title) {
+                        #(lines.into_iter().map(|line| {
+                            element! {
+                                StyledText(content: line)
+                            }
+                        }))
+                    }
+                }
+            }))
+        }
+    })?;
+
+    Ok(None)
+}
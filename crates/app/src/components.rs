@@ -1,13 +1,13 @@
 use crate::app::Commands;
 use crate::session::MoonSession;
-use iocraft::prelude::element;
+use iocraft::prelude::{ElementExt, element};
 use miette::IntoDiagnostic;
-use moon_action::Action;
+use moon_action::{Action, ActionNode};
 use moon_action_context::ActionContext;
 use moon_action_graph::ActionGraph;
 use moon_action_pipeline::ActionPipeline;
 use moon_console::ui::{OwnedOrShared, Progress, ProgressDisplay, ProgressReporter};
-use moon_console::{Console, ConsoleError};
+use moon_console::{Console, ConsoleError, TaskDashboard, TaskDashboardView};
 use moon_platform::PlatformManager;
 use moon_workspace::{
     ExtendProjectData, ExtendProjectEvent, ExtendProjectGraphData, ExtendProjectGraphEvent,
@@ -46,10 +46,18 @@ pub async fn run_action_pipeline(
         Commands::Run(cmd) => {
             pipeline.bail = !cmd.no_bail;
             pipeline.summarize = cmd.summary;
+            pipeline.trace_endpoint = cmd.trace_otlp.clone();
+            pipeline.trace_file = cmd
+                .trace
+                .as_ref()
+                .map(|file| session.working_dir.join(file));
         }
         Commands::Sync { .. } => {
             pipeline.summarize = true;
         }
+        Commands::Watch(cmd) => {
+            pipeline.summarize = cmd.summary;
+        }
         _ => {}
     };
 
@@ -151,3 +159,60 @@ impl ProgressInstance {
         Ok(())
     }
 }
+
+pub fn create_task_dashboard(
+    console: &Console,
+    action_graph: &ActionGraph,
+    keep_open: bool,
+) -> DashboardInstance {
+    let nodes = action_graph.get_nodes();
+
+    // Persistent tasks are expected to run until exited by the user,
+    // so keep the dashboard open to review why they have stopped
+    let dashboard = Arc::new(TaskDashboard::new(
+        keep_open
+            || nodes
+                .iter()
+                .any(|node| matches!(node, ActionNode::RunTask(inner) if inner.persistent)),
+    ));
+
+    // Add all tasks upfront so that pending tasks are listed
+    for node in nodes {
+        if let ActionNode::RunTask(inner) = node {
+            dashboard.add_task(&inner.target, inner.persistent);
+        }
+    }
+
+    console.enable_dashboard(Arc::clone(&dashboard));
+
+    let dashboard_clone = Arc::clone(&dashboard);
+    let handle = tokio::task::spawn(async move {
+        let result = element! {
+            TaskDashboardView(dashboard: Arc::clone(&dashboard_clone))
+        }
+        .fullscreen()
+        .await;
+
+        // The terminal has been restored, so results can be printed
+        dashboard_clone.close();
+
+        result
+    });
+
+    DashboardInstance { handle, dashboard }
+}
+
+pub struct DashboardInstance {
+    pub handle: tokio::task::JoinHandle<std::io::Result<()>>,
+    pub dashboard: Arc<TaskDashboard>,
+}
+
+impl DashboardInstance {
+    /// Wait for the dashboard to exit.
+    pub async fn stop(self) -> miette::Result<()> {
+        self.dashboard.complete();
+        self.handle.await.into_diagnostic()?.into_diagnostic()?;
+
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 use crate::app::{Cli, Commands};
 use crate::app_error::AppError;
+use crate::commands::cache::CacheCommands;
 use crate::commands::docker::DockerCommands;
 use crate::components::*;
 use crate::systems::*;
@@ -8,7 +9,9 @@ use moon_action_graph::{ActionGraphBuilder, ActionGraphBuilderOptions};
 use moon_app_context::AppContext;
 use moon_cache::CacheEngine;
 use moon_common::is_formatted_output;
-use moon_config::{ConfigLoader, InheritedTasksManager, ToolchainConfig, WorkspaceConfig};
+use moon_config::{
+    ConfigLoader, InheritedTasksManager, ToolchainConfig, VcsManager, WorkspaceConfig,
+};
 use moon_console::{Console, MoonReporter, create_console_theme};
 use moon_env::MoonEnvironment;
 use moon_extension_plugin::*;
@@ -19,7 +22,7 @@ use moon_project_graph::ProjectGraph;
 use moon_task_graph::TaskGraph;
 use moon_toolchain_plugin::*;
 use moon_vcs::gitx::Gitx;
-use moon_vcs::{BoxedVcs, Git};
+use moon_vcs::{BoxedVcs, Git, Hg, HgFlavor, Jujutsu};
 use moon_workspace::WorkspaceBuilder;
 use moon_workspace_graph::WorkspaceGraph;
 use proto_core::ProtoEnvironment;
@@ -70,7 +73,10 @@ impl MoonSession {
             cache_engine: OnceLock::new(),
             cli_version: Version::parse(&cli_version).unwrap(),
             config_loader: ConfigLoader::default(),
-            console: Console::new(cli.quiet || is_formatted_output()),
+            // The language server communicates over stdout, so it must not be written to
+            console: Console::new(
+                cli.quiet || is_formatted_output() || matches!(cli.command, Commands::Lsp(_)),
+            ),
             extension_registry: OnceLock::new(),
             moon_env: Arc::new(MoonEnvironment::default()),
             project_graph: OnceLock::new(),
@@ -118,6 +124,7 @@ impl MoonSession {
             cache_engine: self.get_cache_engine()?,
             console: self.get_console()?,
             vcs: self.get_vcs_adapter()?,
+            extension_registry: self.get_extension_registry().await?,
             toolchain_config: Arc::clone(&self.toolchain_config),
             toolchain_registry: self.get_toolchain_registry().await?,
             workspace_config: Arc::clone(&self.workspace_config),
@@ -192,21 +199,39 @@ impl MoonSession {
         if self.vcs_adapter.get().is_none() {
             let config = &self.workspace_config.vcs;
 
-            let git: BoxedVcs = if FeatureFlags::instance().is_enabled(Flag::GitV2) {
-                Box::new(Gitx::load(
+            let vcs: BoxedVcs = match config.manager {
+                VcsManager::Git if FeatureFlags::instance().is_enabled(Flag::GitV2) => {
+                    Box::new(Gitx::load(
+                        &self.workspace_root,
+                        &config.default_branch,
+                        &config.remote_candidates,
+                    )?)
+                }
+                VcsManager::Git => Box::new(Git::load(
+                    &self.workspace_root,
+                    &config.default_branch,
+                    &config.remote_candidates,
+                )?),
+                VcsManager::Jujutsu => Box::new(Jujutsu::load(
                     &self.workspace_root,
                     &config.default_branch,
                     &config.remote_candidates,
-                )?)
-            } else {
-                Box::new(Git::load(
+                )?),
+                VcsManager::Mercurial => Box::new(Hg::load(
                     &self.workspace_root,
                     &config.default_branch,
                     &config.remote_candidates,
-                )?)
+                    HgFlavor::Mercurial,
+                )?),
+                VcsManager::Sapling => Box::new(Hg::load(
+                    &self.workspace_root,
+                    &config.default_branch,
+                    &config.remote_candidates,
+                    HgFlavor::Sapling,
+                )?),
             };
 
-            let _ = self.vcs_adapter.set(Arc::new(git));
+            let _ = self.vcs_adapter.set(Arc::new(vcs));
         }
 
         Ok(self.vcs_adapter.get().map(Arc::clone).unwrap())
@@ -220,6 +245,28 @@ impl MoonSession {
         Ok(self.workspace_graph.get().map(Arc::clone).unwrap())
     }
 
+    /// Reload inherited tasks and rebuild the workspace graph, so that configuration
+    /// changes are picked up by long-running commands, like `moon watch`. If loading
+    /// fails, the current graph is kept.
+    pub async fn reload_workspace_graph(&mut self) -> miette::Result<()> {
+        let mut session = self.clone();
+
+        session.tasks_config =
+            startup::load_tasks_configs(self.config_loader.clone(), &self.workspace_root).await?;
+
+        // Plugin registries can only be provided the graph once
+        session.extension_registry = OnceLock::new();
+        session.toolchain_registry = OnceLock::new();
+        session.project_graph = OnceLock::new();
+        session.task_graph = OnceLock::new();
+        session.workspace_graph = OnceLock::new();
+        session.load_workspace_graph().await?;
+
+        *self = session;
+
+        Ok(())
+    }
+
     pub fn is_telemetry_enabled(&self) -> bool {
         self.workspace_config.telemetry
     }
@@ -227,7 +274,11 @@ impl MoonSession {
     pub fn requires_workspace_configured(&self) -> bool {
         !matches!(
             self.cli.command,
-            Commands::Completions(_) | Commands::Init(_)
+            Commands::Completions(_)
+                | Commands::Init(_)
+                | Commands::Cache {
+                    command: CacheCommands::Serve(_)
+                }
         )
     }
 
@@ -366,7 +417,11 @@ impl AppSession for MoonSession {
         if self.is_telemetry_enabled()
             && matches!(
                 self.cli.command,
-                Commands::Ci(_) | Commands::Check(_) | Commands::Run(_) | Commands::Sync { .. }
+                Commands::Ci(_)
+                    | Commands::Check(_)
+                    | Commands::Run(_)
+                    | Commands::Sync { .. }
+                    | Commands::Watch(_)
             )
         {
             let cache_engine = self.get_cache_engine()?;
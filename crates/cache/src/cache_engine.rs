@@ -2,6 +2,7 @@ use crate::{HashEngine, StateEngine, merge_clean_results, resolve_path};
 use moon_cache_item::*;
 use moon_common::consts;
 use moon_common::path::encode_component;
+use moon_common::size::parse_max_size;
 use moon_env_var::GlobalEnvBag;
 use moon_time::parse_duration;
 use serde::Serialize;
@@ -104,6 +105,29 @@ impl CacheEngine {
             result = merge_clean_results(result, fs::remove_dir_stale_contents(dir, duration)?);
         }
 
+        // Blobs are shared between manifests, so they can only be
+        // removed once they are no longer referenced
+        result = merge_clean_results(result, self.hash.clean_unreferenced_blobs()?);
+
+        debug!(
+            "Deleted {} artifacts and saved {} bytes",
+            result.files_deleted, result.bytes_saved
+        );
+
+        Ok((result.files_deleted, result.bytes_saved))
+    }
+
+    #[instrument(skip(self))]
+    pub fn clean_cache_to_size(&self, max_size: &str) -> miette::Result<(usize, u64)> {
+        let bytes = self.parse_max_size(max_size)?;
+
+        debug!(
+            "Evicting least recently used cached artifacts until the cache is smaller than \"{}\"",
+            max_size
+        );
+
+        let result = self.hash.evict_to_size(bytes)?;
+
         debug!(
             "Deleted {} artifacts and saved {} bytes",
             result.files_deleted, result.bytes_saved
@@ -173,6 +197,10 @@ impl CacheEngine {
         parse_duration(lifetime).map_err(|error| miette::miette!("Invalid lifetime: {error}"))
     }
 
+    pub fn parse_max_size(&self, max_size: &str) -> miette::Result<u64> {
+        Ok(parse_max_size(max_size)?)
+    }
+
     pub fn resolve_path(&self, path: impl AsRef<OsStr>) -> PathBuf {
         resolve_path(&self.cache_dir, path)
     }
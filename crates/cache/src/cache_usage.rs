@@ -0,0 +1,156 @@
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::collections::BTreeMap;
+use std::time::SystemTime;
+
+/// A hash in the cache, and the artifacts that belong to it.
+#[derive(Debug)]
+pub struct CacheUsageEntry {
+    /// Blobs referenced by the outputs manifest.
+    pub blobs: Vec<String>,
+
+    pub hash: String,
+
+    /// When the hash was last generated, or its outputs hydrated.
+    pub last_used: SystemTime,
+
+    /// Combined size of the hash and outputs manifests.
+    pub manifest_size: u64,
+
+    /// Target of the task that generated the hash, if resolved.
+    pub target: Option<String>,
+}
+
+/// Aggregated usage of a group of entries.
+#[derive(Debug, PartialEq)]
+pub struct CacheUsageStats {
+    pub hashes: usize,
+    pub last_used: SystemTime,
+    pub size: u64,
+}
+
+/// Usage of the hashes, outputs, and blobs directories.
+#[derive(Debug, Default)]
+pub struct CacheUsage {
+    /// Size of each blob in the store, keyed by hash.
+    pub blobs: FxHashMap<String, u64>,
+
+    pub entries: Vec<CacheUsageEntry>,
+}
+
+impl CacheUsage {
+    /// Return the size of the entry, including all of its blobs,
+    /// even if they are shared with other entries.
+    pub fn get_entry_size(&self, entry: &CacheUsageEntry) -> u64 {
+        let blobs = entry.blobs.iter().collect::<FxHashSet<_>>();
+
+        entry.manifest_size
+            + blobs
+                .into_iter()
+                .map(|blob| self.blobs.get(blob).copied().unwrap_or_default())
+                .sum::<u64>()
+    }
+
+    /// Aggregate the usage of entries by their target. Entries without a
+    /// resolved target are grouped under `None`. Blobs that are shared
+    /// between entries of the same target are only counted once.
+    pub fn group_by_target(&self) -> BTreeMap<Option<String>, CacheUsageStats> {
+        let mut groups = BTreeMap::<Option<String>, Vec<&CacheUsageEntry>>::default();
+
+        for entry in &self.entries {
+            groups.entry(entry.target.clone()).or_default().push(entry);
+        }
+
+        groups
+            .into_iter()
+            .map(|(target, entries)| {
+                let blobs = entries
+                    .iter()
+                    .flat_map(|entry| entry.blobs.iter())
+                    .collect::<FxHashSet<_>>();
+
+                let stats = CacheUsageStats {
+                    hashes: entries.len(),
+                    last_used: entries
+                        .iter()
+                        .map(|entry| entry.last_used)
+                        .max()
+                        .unwrap_or(SystemTime::UNIX_EPOCH),
+                    size: entries.iter().map(|entry| entry.manifest_size).sum::<u64>()
+                        + blobs
+                            .into_iter()
+                            .map(|blob| self.blobs.get(blob).copied().unwrap_or_default())
+                            .sum::<u64>(),
+                };
+
+                (target, stats)
+            })
+            .collect()
+    }
+
+    /// Return the size of all manifests, and the blobs they reference.
+    /// Unreferenced blobs are not included, as they will be removed.
+    pub fn get_total_size(&self) -> u64 {
+        let blobs = self
+            .entries
+            .iter()
+            .flat_map(|entry| entry.blobs.iter())
+            .collect::<FxHashSet<_>>();
+
+        self.entries
+            .iter()
+            .map(|entry| entry.manifest_size)
+            .sum::<u64>()
+            + blobs
+                .into_iter()
+                .map(|blob| self.blobs.get(blob).copied().unwrap_or_default())
+                .sum::<u64>()
+    }
+
+    /// Determine which entries should be evicted, least recently used first,
+    /// so that the total size is within the provided maximum. Blobs that are
+    /// shared with retained entries do not count towards the savings.
+    pub fn plan_eviction(&self, max_size: u64) -> Vec<&CacheUsageEntry> {
+        let mut total_size = self.get_total_size();
+        let mut evicted = vec![];
+
+        if total_size <= max_size {
+            return evicted;
+        }
+
+        let mut references = FxHashMap::<&str, usize>::default();
+
+        for entry in &self.entries {
+            for blob in entry.blobs.iter().collect::<FxHashSet<_>>() {
+                *references.entry(blob).or_default() += 1;
+            }
+        }
+
+        let mut entries = self.entries.iter().collect::<Vec<_>>();
+        entries.sort_by(|a, d| a.last_used.cmp(&d.last_used).then(a.hash.cmp(&d.hash)));
+
+        for entry in entries {
+            if total_size <= max_size {
+                break;
+            }
+
+            total_size = total_size.saturating_sub(entry.manifest_size);
+
+            for blob in entry.blobs.iter().collect::<FxHashSet<_>>() {
+                let Some(count) = references.get_mut(blob.as_str()) else {
+                    continue;
+                };
+
+                *count -= 1;
+
+                if *count == 0 {
+                    total_size =
+                        total_size.saturating_sub(self.blobs.get(blob).copied().unwrap_or_default());
+                }
+            }
+
+            evicted.push(entry);
+        }
+
+        evicted
+    }
+}
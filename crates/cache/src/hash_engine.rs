@@ -1,50 +1,347 @@
+use crate::cache_usage::{CacheUsage, CacheUsageEntry};
+use crate::output_manifest::OutputManifest;
 use moon_hash::ContentHasher;
-use serde::Serialize;
-use starbase_utils::fs;
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::{Deserialize, Serialize};
+use starbase_utils::fs::RemoveDirContentsResult;
+use starbase_utils::{fs, json};
 use std::path::{Path, PathBuf};
-use tracing::debug;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, SystemTime};
+use tracing::{debug, instrument, warn};
+
+/// Blobs are written before the outputs manifest that references them,
+/// so recently written blobs are never cleaned, as they may still be
+/// referenced by a manifest that is about to be written.
+pub const BLOB_GRACE_PERIOD: Duration = Duration::from_secs(60 * 60);
+
+static TEMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+// Only the target is required from a hash manifest, so avoid
+// deserializing the rest of its content
+#[derive(Deserialize)]
+struct HashManifestItem {
+    target: Option<String>,
+}
 
 #[derive(Debug)]
 pub struct HashEngine {
+    /// The `.moon/cache/blobs` directory. Stores the contents of task
+    /// outputs, keyed by their SHA256 digest.
+    pub blobs_dir: PathBuf,
+
     /// The `.moon/cache/hashes` directory. Stores hash manifests.
     pub hashes_dir: PathBuf,
 
-    /// The `.moon/cache/outputs` directory. Stores task outputs as hashed archives.
+    /// The `.moon/cache/outputs` directory. Stores manifests of task
+    /// outputs, that reference blobs, keyed by hash.
     pub outputs_dir: PathBuf,
 }
 
 impl HashEngine {
     pub fn new(cache_dir: &Path) -> miette::Result<HashEngine> {
+        let blobs_dir = cache_dir.join("blobs");
         let hashes_dir = cache_dir.join("hashes");
         let outputs_dir = cache_dir.join("outputs");
 
         debug!(
+            blobs_dir = ?blobs_dir,
             hashes_dir = ?hashes_dir,
             outputs_dir = ?outputs_dir,
             "Creating hash engine",
         );
 
+        fs::create_dir_all(&blobs_dir)?;
         fs::create_dir_all(&hashes_dir)?;
         fs::create_dir_all(&outputs_dir)?;
 
         Ok(HashEngine {
+            blobs_dir,
             hashes_dir,
             outputs_dir,
         })
     }
 
+    /// Delete all blobs that are no longer referenced by an outputs manifest,
+    /// typically after stale manifests have been removed. Blobs written
+    /// within the [`BLOB_GRACE_PERIOD`] are kept.
+    #[instrument(skip(self))]
+    pub fn clean_unreferenced_blobs(&self) -> miette::Result<RemoveDirContentsResult> {
+        let mut referenced = FxHashSet::default();
+
+        for entry in fs::read_dir(&self.outputs_dir)? {
+            let path = entry.path();
+
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+
+            match json::read_file::<_, OutputManifest>(&path) {
+                Ok(manifest) => {
+                    referenced.extend(manifest.get_blob_hashes().into_iter().map(String::from));
+                }
+                Err(error) => {
+                    // If we can't read a manifest, we can't determine which blobs
+                    // are referenced, so abort instead of deleting too much!
+                    warn!(
+                        manifest = ?path,
+                        "Failed to read outputs manifest, skipping blob cleanup: {error}"
+                    );
+
+                    return Ok(RemoveDirContentsResult {
+                        files_deleted: 0,
+                        bytes_saved: 0,
+                    });
+                }
+            }
+        }
+
+        let mut result = RemoveDirContentsResult {
+            files_deleted: 0,
+            bytes_saved: 0,
+        };
+
+        for entry in fs::read_dir(&self.blobs_dir)? {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+
+            // Skip blobs that are currently being written
+            if name.ends_with(".tmp") || referenced.contains(name.as_ref()) {
+                continue;
+            }
+
+            let Ok(meta) = entry.metadata() else {
+                continue;
+            };
+
+            // Skip blobs that may be referenced by a manifest that is being written
+            if meta
+                .modified()
+                .ok()
+                .and_then(|modified| modified.elapsed().ok())
+                .is_none_or(|elapsed| elapsed < BLOB_GRACE_PERIOD)
+            {
+                continue;
+            }
+
+            let size = meta.len();
+
+            fs::remove_file(entry.path())?;
+
+            result.files_deleted += 1;
+            result.bytes_saved += size;
+        }
+
+        debug!(
+            "Deleted {} unreferenced blobs and saved {} bytes",
+            result.files_deleted, result.bytes_saved
+        );
+
+        Ok(result)
+    }
+
+    /// Evict the least recently used hashes (their hash and outputs manifests),
+    /// until the total size of the cache is within the provided maximum.
+    /// Blobs that are no longer referenced are removed afterwards.
+    #[instrument(skip(self))]
+    pub fn evict_to_size(&self, max_size: u64) -> miette::Result<RemoveDirContentsResult> {
+        let usage = self.get_usage(false)?;
+        let evicted = usage.plan_eviction(max_size);
+
+        let mut result = RemoveDirContentsResult {
+            files_deleted: 0,
+            bytes_saved: 0,
+        };
+
+        if evicted.is_empty() {
+            return Ok(result);
+        }
+
+        debug!(
+            total_size = usage.get_total_size(),
+            max_size,
+            "Cache has exceeded its maximum size, evicting {} least recently used hashes",
+            evicted.len()
+        );
+
+        for entry in evicted {
+            for path in [
+                self.get_manifest_path(&entry.hash),
+                self.get_outputs_manifest_path(&entry.hash),
+            ] {
+                if path.exists() {
+                    fs::remove_file(&path)?;
+
+                    result.files_deleted += 1;
+                }
+            }
+
+            result.bytes_saved += entry.manifest_size;
+        }
+
+        let blobs_result = self.clean_unreferenced_blobs()?;
+
+        result.files_deleted += blobs_result.files_deleted;
+        result.bytes_saved += blobs_result.bytes_saved;
+
+        Ok(result)
+    }
+
+    /// Gather the size and last used time of all hashes in the cache, and the
+    /// blobs they reference. When `resolve_targets` is true, the target of each
+    /// hash will be read from its manifest, which is slower.
+    #[instrument(skip(self))]
+    pub fn get_usage(&self, resolve_targets: bool) -> miette::Result<CacheUsage> {
+        let mut usage = CacheUsage::default();
+        let mut entries = FxHashMap::<String, CacheUsageEntry>::default();
+
+        for dir in [&self.hashes_dir, &self.outputs_dir] {
+            for dir_entry in fs::read_dir(dir)? {
+                let path = dir_entry.path();
+
+                if path.extension().is_none_or(|ext| ext != "json") {
+                    continue;
+                }
+
+                let Some(hash) = path.file_stem().map(|stem| stem.to_string_lossy()) else {
+                    continue;
+                };
+
+                let (size, modified) = dir_entry
+                    .metadata()
+                    .map(|meta| {
+                        (
+                            meta.len(),
+                            meta.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                        )
+                    })
+                    .unwrap_or((0, SystemTime::UNIX_EPOCH));
+
+                let entry = entries
+                    .entry(hash.to_string())
+                    .or_insert_with(|| CacheUsageEntry {
+                        blobs: vec![],
+                        hash: hash.to_string(),
+                        last_used: SystemTime::UNIX_EPOCH,
+                        manifest_size: 0,
+                        target: None,
+                    });
+
+                entry.manifest_size += size;
+                entry.last_used = entry.last_used.max(modified);
+
+                if dir == &self.outputs_dir {
+                    if let Ok(manifest) = json::read_file::<_, OutputManifest>(&path) {
+                        entry.blobs = manifest
+                            .get_blob_hashes()
+                            .into_iter()
+                            .map(String::from)
+                            .collect();
+                    }
+                } else if resolve_targets {
+                    if let Ok(items) = json::read_file::<_, Vec<HashManifestItem>>(&path) {
+                        entry.target = items.into_iter().find_map(|item| item.target);
+                    }
+                }
+            }
+        }
+
+        for dir_entry in fs::read_dir(&self.blobs_dir)? {
+            let name = dir_entry.file_name().to_string_lossy().to_string();
+
+            if name.ends_with(".tmp") {
+                continue;
+            }
+
+            usage.blobs.insert(
+                name,
+                dir_entry
+                    .metadata()
+                    .map(|meta| meta.len())
+                    .unwrap_or_default(),
+            );
+        }
+
+        usage.entries = entries.into_values().collect();
+
+        Ok(usage)
+    }
+
     pub fn create_hasher<T: AsRef<str>>(&self, label: T) -> ContentHasher {
         ContentHasher::new(label.as_ref())
     }
 
-    pub fn get_archive_path(&self, hash: &str) -> PathBuf {
-        self.outputs_dir.join(format!("{hash}.tar.gz"))
+    pub fn get_blob_path(&self, hash: &str) -> PathBuf {
+        self.blobs_dir.join(hash)
     }
 
     pub fn get_manifest_path(&self, hash: &str) -> PathBuf {
         self.hashes_dir.join(format!("{hash}.json"))
     }
 
+    pub fn get_outputs_manifest_path(&self, hash: &str) -> PathBuf {
+        self.outputs_dir.join(format!("{hash}.json"))
+    }
+
+    /// Load the outputs manifest for the hash, and mark it as recently used,
+    /// so that it is not evicted when the cache exceeds its maximum size.
+    pub fn load_outputs_manifest(&self, hash: &str) -> miette::Result<Option<OutputManifest>> {
+        let path = self.get_outputs_manifest_path(hash);
+
+        if path.exists() {
+            let manifest = json::read_file(&path)?;
+
+            if let Ok(file) = std::fs::File::options().write(true).open(&path) {
+                let _ = file.set_modified(SystemTime::now());
+            }
+
+            return Ok(Some(manifest));
+        }
+
+        Ok(None)
+    }
+
+    pub fn save_outputs_manifest(
+        &self,
+        hash: &str,
+        manifest: &OutputManifest,
+    ) -> miette::Result<()> {
+        let path = self.get_outputs_manifest_path(hash);
+
+        debug!(manifest = ?path, "Saving outputs manifest");
+
+        json::write_file(&path, manifest, false)?;
+
+        Ok(())
+    }
+
+    /// Write the blob to the store, if it does not already exist. Blobs are
+    /// written to a temporary file first, so that a partial blob is never read.
+    pub fn save_blob(&self, hash: &str, bytes: &[u8]) -> miette::Result<bool> {
+        let path = self.get_blob_path(hash);
+
+        if path.exists() {
+            // Mark as recently written, so that it's not cleaned
+            // before the manifest that references it is written
+            if let Ok(file) = std::fs::File::options().write(true).open(&path) {
+                let _ = file.set_modified(SystemTime::now());
+            }
+
+            return Ok(false);
+        }
+
+        let temp_path = self.blobs_dir.join(format!(
+            "{hash}.{}.{}.tmp",
+            std::process::id(),
+            TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        fs::write_file(&temp_path, bytes)?;
+        fs::rename(&temp_path, &path)?;
+
+        Ok(true)
+    }
+
     pub fn save_manifest(&self, hasher: &mut ContentHasher) -> miette::Result<String> {
         let hash = hasher.generate_hash()?;
         let path = self.get_manifest_path(&hash);
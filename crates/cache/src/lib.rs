@@ -1,10 +1,14 @@
 mod cache_engine;
+mod cache_usage;
 mod hash_engine;
+mod output_manifest;
 mod state_engine;
 
 pub use cache_engine::*;
+pub use cache_usage::*;
 pub use hash_engine::*;
 pub use moon_cache_item::*;
+pub use output_manifest::*;
 pub use state_engine::*;
 
 use starbase_utils::fs::RemoveDirContentsResult;
@@ -0,0 +1,55 @@
+use moon_common::path::WorkspaceRelativePathBuf;
+use serde::{Deserialize, Serialize};
+
+/// An output file that references a blob in the local content-addressable
+/// store, by its SHA256 digest.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct OutputManifestFile {
+    pub executable: bool,
+    pub hash: String,
+    pub path: WorkspaceRelativePathBuf,
+    pub size: u64,
+}
+
+/// An output symlink, that will be re-linked when hydrating.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct OutputManifestSymlink {
+    pub path: WorkspaceRelativePathBuf,
+    pub target: WorkspaceRelativePathBuf,
+}
+
+/// A manifest of all outputs (and logs) that were created by a task
+/// for a specific hash. File contents are not stored in the manifest,
+/// and are instead stored as blobs, so that identical files across
+/// hashes are only stored once.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct OutputManifest {
+    pub files: Vec<OutputManifestFile>,
+    pub stderr_hash: Option<String>,
+    pub stdout_hash: Option<String>,
+    pub symlinks: Vec<OutputManifestSymlink>,
+}
+
+impl OutputManifest {
+    /// Return the hashes of all blobs that are referenced by this manifest.
+    pub fn get_blob_hashes(&self) -> Vec<&str> {
+        let mut hashes = self
+            .files
+            .iter()
+            .map(|file| file.hash.as_str())
+            .collect::<Vec<_>>();
+
+        if let Some(hash) = &self.stderr_hash {
+            hashes.push(hash);
+        }
+
+        if let Some(hash) = &self.stdout_hash {
+            hashes.push(hash);
+        }
+
+        hashes
+    }
+}
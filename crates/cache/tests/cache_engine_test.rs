@@ -91,4 +91,24 @@ mod cache_engine {
 
         bag.remove("MOON_CACHE");
     }
+
+    #[test]
+    fn parses_max_size() {
+        let sandbox = create_empty_sandbox();
+        let engine = CacheEngine::new(sandbox.path()).unwrap();
+
+        assert_eq!(engine.parse_max_size("512").unwrap(), 512);
+        assert_eq!(engine.parse_max_size("10KB").unwrap(), 10 * 1024);
+        assert_eq!(engine.parse_max_size("1.5 MB").unwrap(), 1572864);
+        assert_eq!(engine.parse_max_size("20GB").unwrap(), 20 * 1024 * 1024 * 1024);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid max size")]
+    fn errors_for_invalid_max_size() {
+        let sandbox = create_empty_sandbox();
+        let engine = CacheEngine::new(sandbox.path()).unwrap();
+
+        engine.parse_max_size("20 bananas").unwrap();
+    }
 }
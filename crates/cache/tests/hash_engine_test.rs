@@ -2,6 +2,27 @@ use moon_cache::*;
 use moon_hash::*;
 use starbase_sandbox::create_empty_sandbox;
 use std::fs;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+fn set_modified(path: &Path, secs_ago: u64) {
+    fs::File::options()
+        .write(true)
+        .open(path)
+        .unwrap()
+        .set_modified(SystemTime::now() - Duration::from_secs(secs_ago))
+        .unwrap();
+}
+
+// Blobs within the grace period are never cleaned
+fn save_old_blob(engine: &HashEngine, hash: &str, bytes: &[u8], secs_ago: u64) {
+    engine.save_blob(hash, bytes).unwrap();
+
+    set_modified(
+        &engine.get_blob_path(hash),
+        BLOB_GRACE_PERIOD.as_secs() + secs_ago,
+    );
+}
 
 hash_content!(
     struct Content<'l> {
@@ -75,3 +96,232 @@ fn saves_manifest_without_hasher() {
         r#"[{"one":"abc","two":123}]"#
     )
 }
+
+#[test]
+fn saves_blob_once() {
+    let sandbox = create_empty_sandbox();
+    let engine = HashEngine::new(sandbox.path()).unwrap();
+
+    assert!(engine.save_blob("abc", b"content").unwrap());
+    assert!(!engine.save_blob("abc", b"content").unwrap());
+
+    assert_eq!(
+        fs::read_to_string(sandbox.path().join("blobs/abc")).unwrap(),
+        "content"
+    );
+}
+
+#[test]
+fn saves_and_loads_outputs_manifest() {
+    let sandbox = create_empty_sandbox();
+    let engine = HashEngine::new(sandbox.path()).unwrap();
+    let manifest = OutputManifest {
+        files: vec![OutputManifestFile {
+            hash: "abc".into(),
+            path: "project/file.txt".into(),
+            size: 7,
+            ..Default::default()
+        }],
+        stdout_hash: Some("def".into()),
+        ..Default::default()
+    };
+
+    assert_eq!(engine.load_outputs_manifest("hash123").unwrap(), None);
+
+    engine.save_outputs_manifest("hash123", &manifest).unwrap();
+
+    assert!(sandbox.path().join("outputs/hash123.json").exists());
+    assert_eq!(
+        engine.load_outputs_manifest("hash123").unwrap(),
+        Some(manifest)
+    );
+}
+
+#[test]
+fn cleans_unreferenced_blobs() {
+    let sandbox = create_empty_sandbox();
+    let engine = HashEngine::new(sandbox.path()).unwrap();
+
+    save_old_blob(&engine, "used", b"used", 0);
+    save_old_blob(&engine, "log", b"log", 0);
+    save_old_blob(&engine, "unused", b"unused", 0);
+    engine
+        .save_outputs_manifest(
+            "hash123",
+            &OutputManifest {
+                files: vec![OutputManifestFile {
+                    hash: "used".into(),
+                    ..Default::default()
+                }],
+                stderr_hash: Some("log".into()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    let result = engine.clean_unreferenced_blobs().unwrap();
+
+    assert_eq!(result.files_deleted, 1);
+    assert_eq!(result.bytes_saved, 6);
+    assert!(sandbox.path().join("blobs/used").exists());
+    assert!(sandbox.path().join("blobs/log").exists());
+    assert!(!sandbox.path().join("blobs/unused").exists());
+}
+
+#[test]
+fn doesnt_clean_recently_written_blobs() {
+    let sandbox = create_empty_sandbox();
+    let engine = HashEngine::new(sandbox.path()).unwrap();
+
+    engine.save_blob("new", b"new").unwrap();
+    save_old_blob(&engine, "reused", b"reused", 0);
+
+    // Saving an existing blob marks it as recently written
+    engine.save_blob("reused", b"reused").unwrap();
+
+    let result = engine.clean_unreferenced_blobs().unwrap();
+
+    assert_eq!(result.files_deleted, 0);
+    assert!(sandbox.path().join("blobs/new").exists());
+    assert!(sandbox.path().join("blobs/reused").exists());
+}
+
+#[test]
+fn doesnt_clean_blobs_if_a_manifest_is_invalid() {
+    let sandbox = create_empty_sandbox();
+    let engine = HashEngine::new(sandbox.path()).unwrap();
+
+    save_old_blob(&engine, "unused", b"unused", 0);
+    sandbox.create_file("outputs/hash123.json", "{invalid");
+
+    let result = engine.clean_unreferenced_blobs().unwrap();
+
+    assert_eq!(result.files_deleted, 0);
+    assert!(sandbox.path().join("blobs/unused").exists());
+}
+
+mod usage {
+    use super::*;
+
+    fn create_entry(engine: &HashEngine, hash: &str, target: &str, blob: &str, secs_ago: u64) {
+        fs::write(
+            engine.get_manifest_path(hash),
+            format!(r#"[{{"command":"build","target":"{target}"}}]"#),
+        )
+        .unwrap();
+
+        save_old_blob(engine, blob, &[0; 1000], secs_ago);
+        engine
+            .save_outputs_manifest(
+                hash,
+                &OutputManifest {
+                    files: vec![OutputManifestFile {
+                        hash: blob.into(),
+                        path: "project/file.txt".into(),
+                        size: 1000,
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+        set_modified(&engine.get_manifest_path(hash), secs_ago);
+        set_modified(&engine.get_outputs_manifest_path(hash), secs_ago);
+    }
+
+    #[test]
+    fn gathers_entries_and_blobs() {
+        let sandbox = create_empty_sandbox();
+        let engine = HashEngine::new(sandbox.path()).unwrap();
+
+        create_entry(&engine, "a", "app:build", "blob-a", 10);
+        create_entry(&engine, "b", "app:test", "blob-b", 20);
+
+        let usage = engine.get_usage(false).unwrap();
+
+        assert_eq!(usage.entries.len(), 2);
+        assert_eq!(usage.blobs.len(), 2);
+        assert!(usage.entries.iter().all(|entry| entry.target.is_none()));
+        assert!(usage.get_total_size() > 2000);
+    }
+
+    #[test]
+    fn resolves_targets() {
+        let sandbox = create_empty_sandbox();
+        let engine = HashEngine::new(sandbox.path()).unwrap();
+
+        create_entry(&engine, "a", "app:build", "blob-a", 10);
+        create_entry(&engine, "b", "app:build", "blob-a", 20);
+        create_entry(&engine, "c", "app:test", "blob-c", 30);
+
+        let usage = engine.get_usage(true).unwrap();
+        let targets = usage.group_by_target();
+
+        assert_eq!(targets[&Some("app:build".to_owned())].hashes, 2);
+        assert_eq!(targets[&Some("app:test".to_owned())].hashes, 1);
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let sandbox = create_empty_sandbox();
+        let engine = HashEngine::new(sandbox.path()).unwrap();
+
+        create_entry(&engine, "old", "app:build", "blob-old", 300);
+        create_entry(&engine, "mid", "app:build", "blob-mid", 200);
+        create_entry(&engine, "new", "app:build", "blob-new", 100);
+
+        let result = engine.evict_to_size(2500).unwrap();
+
+        assert!(result.files_deleted > 0);
+        assert!(!engine.get_manifest_path("old").exists());
+        assert!(!engine.get_outputs_manifest_path("old").exists());
+        assert!(!engine.get_blob_path("blob-old").exists());
+        assert!(engine.get_outputs_manifest_path("mid").exists());
+        assert!(engine.get_outputs_manifest_path("new").exists());
+        assert!(engine.get_usage(false).unwrap().get_total_size() <= 2500);
+    }
+
+    #[test]
+    fn doesnt_evict_when_within_size() {
+        let sandbox = create_empty_sandbox();
+        let engine = HashEngine::new(sandbox.path()).unwrap();
+
+        create_entry(&engine, "a", "app:build", "blob-a", 10);
+
+        let result = engine.evict_to_size(1024 * 1024).unwrap();
+
+        assert_eq!(result.files_deleted, 0);
+        assert!(engine.get_outputs_manifest_path("a").exists());
+    }
+
+    #[test]
+    fn keeps_shared_blobs() {
+        let sandbox = create_empty_sandbox();
+        let engine = HashEngine::new(sandbox.path()).unwrap();
+
+        create_entry(&engine, "old", "app:build", "shared", 300);
+        create_entry(&engine, "new", "app:build", "shared", 100);
+
+        engine.evict_to_size(1250).unwrap();
+
+        assert!(!engine.get_outputs_manifest_path("old").exists());
+        assert!(engine.get_outputs_manifest_path("new").exists());
+        assert!(engine.get_blob_path("shared").exists());
+    }
+
+    #[test]
+    fn loading_outputs_marks_as_recently_used() {
+        let sandbox = create_empty_sandbox();
+        let engine = HashEngine::new(sandbox.path()).unwrap();
+
+        create_entry(&engine, "old", "app:build", "blob-old", 300);
+        create_entry(&engine, "new", "app:build", "blob-new", 100);
+
+        engine.load_outputs_manifest("old").unwrap();
+        engine.evict_to_size(1500).unwrap();
+
+        assert!(engine.get_outputs_manifest_path("old").exists());
+        assert!(!engine.get_outputs_manifest_path("new").exists());
+    }
+}
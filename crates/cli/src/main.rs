@@ -3,8 +3,10 @@ mod lookup;
 use clap::Parser;
 use lookup::*;
 use mimalloc::MiMalloc;
+use moon_app::commands::cache::CacheCommands;
 use moon_app::commands::debug::DebugCommands;
 use moon_app::commands::docker::DockerCommands;
+use moon_app::commands::graph::GraphCommands;
 use moon_app::commands::migrate::MigrateCommands;
 use moon_app::commands::node::NodeCommands;
 use moon_app::commands::query::QueryCommands;
@@ -142,6 +144,9 @@ async fn main() -> MainResult {
                     commands::graph::action::action_graph(session, args).await
                 }
                 Commands::Bin(args) => commands::bin::bin(session, args).await,
+                Commands::Cache { command } => match command {
+                    CacheCommands::Serve(args) => commands::cache::serve(session, args).await,
+                },
                 Commands::Ci(args) => commands::ci::ci(session, args).await,
                 Commands::Check(args) => commands::check::check(session, args).await,
                 Commands::Clean(args) => commands::clean::clean(session, args).await,
@@ -161,7 +166,11 @@ async fn main() -> MainResult {
                 },
                 Commands::Ext(args) => commands::ext::ext(session, args).await,
                 Commands::Generate(args) => commands::generate::generate(session, args).await,
+                Commands::Graph { command } => match command {
+                    GraphCommands::Diff(args) => commands::graph::diff(session, args).await,
+                },
                 Commands::Init(args) => commands::init::init(session, args).await,
+                Commands::Lsp(args) => commands::lsp::lsp(session, args).await,
                 Commands::Migrate {
                     command,
                     skip_touched_files_check,
@@ -170,13 +179,21 @@ async fn main() -> MainResult {
                         args.skip_touched_files_check = skip_touched_files_check;
                         commands::migrate::from_package_json(session, args).await
                     }
-                    MigrateCommands::FromTurborepo => commands::migrate::from_turborepo().await,
+                    MigrateCommands::FromNx(mut args) => {
+                        args.skip_touched_files_check = skip_touched_files_check;
+                        commands::migrate::from_nx(session, args).await
+                    }
+                    MigrateCommands::FromTurborepo(mut args) => {
+                        args.skip_touched_files_check = skip_touched_files_check;
+                        commands::migrate::from_turborepo(session, args).await
+                    }
                 },
                 Commands::Node { command } => match command {
                     NodeCommands::RunScript(args) => {
                         commands::node::run_script(session, args).await
                     }
                 },
+                Commands::Owners(args) => commands::owners::owners(session, args).await,
                 Commands::Project(args) => commands::project::project(session, args).await,
                 Commands::ProjectGraph(args) => {
                     commands::graph::project::project_graph(session, args).await
@@ -212,6 +229,8 @@ async fn main() -> MainResult {
                 Commands::Teardown => commands::teardown::teardown(session).await,
                 Commands::Templates(args) => commands::templates::templates(session, args).await,
                 Commands::Upgrade => commands::upgrade::upgrade(session).await,
+                Commands::Watch(args) => commands::watch::watch(session, args).await,
+                Commands::Why(args) => commands::why::why(session, args).await,
             }
         })
         .await?;
@@ -6,7 +6,7 @@ use moon_config::{
     TemplateVariableEnumValue, TemplateVariableNumberSetting, TemplateVariableStringSetting,
 };
 use moon_test_utils::{
-    Sandbox, assert_snapshot, create_sandbox_with_config, predicates::prelude::*,
+    Sandbox, SandboxAssert, assert_snapshot, create_sandbox_with_config, predicates::prelude::*,
 };
 use rustc_hash::FxHashMap;
 use std::fs;
@@ -455,6 +455,204 @@ mod frontmatter {
     }
 }
 
+mod update {
+    use super::*;
+
+    fn update_sandbox() -> Sandbox {
+        let sandbox = generate_sandbox();
+
+        sandbox.create_file(
+            "templates/update/template.yml",
+            "title: 'Update'\ndescription: 'Template for updates.'\nvariables:\n  name:\n    type: string\n    default: 'unknown'\n",
+        );
+        sandbox.create_file(
+            "templates/update/file.txt",
+            "name = {{ name }}\none\ntwo\nthree\nfour\nfive\n",
+        );
+
+        sandbox
+    }
+
+    fn generate<'s>(sandbox: &'s Sandbox, args: &[&str]) -> SandboxAssert<'s> {
+        sandbox.run_moon(|cmd| {
+            cmd.arg("generate")
+                .arg("update")
+                .arg("./test")
+                .arg("--defaults")
+                .args(args);
+        })
+    }
+
+    #[test]
+    fn writes_a_lock_file() {
+        let sandbox = update_sandbox();
+
+        generate(&sandbox, &["--", "--name", "moon"]).success();
+
+        let lock = fs::read_to_string(sandbox.path().join("test/.moon-template.lock")).unwrap();
+
+        assert!(lock.contains("\"name\": \"moon\""));
+        assert!(lock.contains("file.txt"));
+    }
+
+    #[test]
+    fn errors_if_not_generated() {
+        let sandbox = update_sandbox();
+
+        generate(&sandbox, &["--update"])
+            .failure()
+            .stderr(predicate::str::contains("has not been generated into"));
+    }
+
+    #[test]
+    fn updates_unchanged_files_with_locked_variables() {
+        let sandbox = update_sandbox();
+
+        generate(&sandbox, &["--", "--name", "moon"]).success();
+
+        sandbox.create_file(
+            "templates/update/file.txt",
+            "name = {{ name }}\none\ntwo\nTHREE\nfour\nfive\n",
+        );
+
+        generate(&sandbox, &["--update"])
+            .success()
+            .stdout(predicate::str::contains("updated"));
+
+        assert_eq!(
+            fs::read_to_string(sandbox.path().join("test/file.txt")).unwrap(),
+            "name = moon\none\ntwo\nTHREE\nfour\nfive\n"
+        );
+    }
+
+    #[test]
+    fn merges_template_changes_with_local_changes() {
+        let sandbox = update_sandbox();
+
+        generate(&sandbox, &[]).success();
+
+        sandbox.create_file(
+            "test/file.txt",
+            "name = unknown\nONE\ntwo\nthree\nfour\nfive\n",
+        );
+        sandbox.create_file(
+            "templates/update/file.txt",
+            "name = {{ name }}\none\ntwo\nthree\nfour\nFIVE\n",
+        );
+
+        generate(&sandbox, &["--update"]).success();
+
+        assert_eq!(
+            fs::read_to_string(sandbox.path().join("test/file.txt")).unwrap(),
+            "name = unknown\nONE\ntwo\nthree\nfour\nFIVE\n"
+        );
+    }
+
+    #[test]
+    fn marks_conflicts() {
+        let sandbox = update_sandbox();
+
+        generate(&sandbox, &[]).success();
+
+        sandbox.create_file(
+            "test/file.txt",
+            "name = unknown\none\nlocal\nthree\nfour\nfive\n",
+        );
+        sandbox.create_file(
+            "templates/update/file.txt",
+            "name = {{ name }}\none\ntemplate\nthree\nfour\nfive\n",
+        );
+
+        generate(&sandbox, &["--update"])
+            .success()
+            .stdout(predicate::str::contains("conflict"));
+
+        assert_eq!(
+            fs::read_to_string(sandbox.path().join("test/file.txt")).unwrap(),
+            "name = unknown\none\n<<<<<<< current\nlocal\n=======\ntemplate\n>>>>>>> template\nthree\nfour\nfive\n"
+        );
+    }
+
+    #[test]
+    fn loads_lock_from_interpolated_dest() {
+        let sandbox = update_sandbox();
+
+        sandbox
+            .run_moon(|cmd| {
+                cmd.args([
+                    "generate",
+                    "update",
+                    "./packages/{{ name }}",
+                    "--defaults",
+                    "--",
+                    "--name",
+                    "moon",
+                ]);
+            })
+            .success();
+
+        assert!(
+            sandbox
+                .path()
+                .join("packages/moon/.moon-template.lock")
+                .exists()
+        );
+
+        sandbox
+            .run_moon(|cmd| {
+                cmd.args([
+                    "generate",
+                    "update",
+                    "./packages/{{ name }}",
+                    "--defaults",
+                    "--update",
+                    "--",
+                    "--name",
+                    "moon",
+                ]);
+            })
+            .success();
+    }
+
+    #[test]
+    fn reports_files_removed_from_template() {
+        let sandbox = update_sandbox();
+        sandbox.create_file("templates/update/old.txt", "old");
+
+        generate(&sandbox, &[]).success();
+
+        fs::remove_file(sandbox.path().join("templates/update/old.txt")).unwrap();
+
+        generate(&sandbox, &["--update"])
+            .success()
+            .stdout(predicate::str::contains("removed from the template"));
+
+        assert!(sandbox.path().join("test/old.txt").exists());
+    }
+
+    #[test]
+    fn prints_diff_when_dry_run() {
+        let sandbox = update_sandbox();
+
+        generate(&sandbox, &[]).success();
+
+        sandbox.create_file(
+            "templates/update/file.txt",
+            "name = {{ name }}\none\ntwo\nthree\nfour\nsix\n",
+        );
+
+        generate(&sandbox, &["--update", "--dry-run"])
+            .success()
+            .stdout(predicate::str::contains("-five").and(predicate::str::contains("+six")));
+
+        assert!(
+            fs::read_to_string(sandbox.path().join("test/file.txt"))
+                .unwrap()
+                .contains("five")
+        );
+    }
+}
+
 mod cli_args {
     use super::*;
 
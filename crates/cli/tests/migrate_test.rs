@@ -7,10 +7,12 @@ use starbase_utils::string_vec;
 use std::fs;
 
 fn migrate_sandbox() -> Sandbox {
+    migrate_sandbox_with_projects("package-json/*")
+}
+
+fn migrate_sandbox_with_projects(glob: &str) -> Sandbox {
     let workspace_config = PartialWorkspaceConfig {
-        projects: Some(PartialWorkspaceProjects::Globs(string_vec![
-            "package-json/*",
-        ])),
+        projects: Some(PartialWorkspaceProjects::Globs(string_vec![glob])),
         ..PartialWorkspaceConfig::default()
     };
 
@@ -94,3 +96,60 @@ mod from_package_json {
         assert.success();
     }
 }
+
+mod from_turborepo {
+    use super::*;
+
+    #[test]
+    fn errors_if_no_turbo_json() {
+        let sandbox = migrate_sandbox();
+
+        fs::remove_file(sandbox.path().join("turbo.json")).unwrap();
+
+        let assert = sandbox.run_moon(|cmd| {
+            cmd.args(["migrate", "--skipTouchedFilesCheck", "from-turborepo"]);
+        });
+
+        assert.failure().stderr(contains("No turbo.json found"));
+    }
+
+    #[test]
+    fn converts_tasks() {
+        let sandbox = migrate_sandbox_with_projects("turborepo/*");
+
+        let assert = sandbox.run_moon(|cmd| {
+            cmd.args(["migrate", "--skipTouchedFilesCheck", "from-turborepo"]);
+        });
+
+        assert_snapshot!(fs::read_to_string(sandbox.path().join(".moon/tasks/node.yml")).unwrap());
+
+        assert_snapshot!(
+            fs::read_to_string(sandbox.path().join("turborepo/web/moon.yml")).unwrap()
+        );
+
+        assert_snapshot!(fs::read_to_string(sandbox.path().join("turborepo/ui/moon.yml")).unwrap());
+
+        assert.success().stdout(contains("passThroughEnv"));
+    }
+}
+
+mod from_nx {
+    use super::*;
+
+    #[test]
+    fn converts_projects() {
+        let sandbox = migrate_sandbox_with_projects("nx/*");
+
+        let assert = sandbox.run_moon(|cmd| {
+            cmd.args(["migrate", "--skipTouchedFilesCheck", "from-nx"]);
+        });
+
+        assert_snapshot!(fs::read_to_string(sandbox.path().join(".moon/tasks/node.yml")).unwrap());
+
+        assert_snapshot!(fs::read_to_string(sandbox.path().join("nx/web/moon.yml")).unwrap());
+
+        assert_snapshot!(fs::read_to_string(sandbox.path().join("nx/ui/moon.yml")).unwrap());
+
+        assert.success().stdout(contains("@nx/vite:dev-server"));
+    }
+}
@@ -0,0 +1,141 @@
+use moon_test_utils2::{MoonSandbox, create_empty_moon_sandbox};
+use starbase_sandbox::predicates::prelude::*;
+
+fn create_owners_sandbox(provider: &str) -> MoonSandbox {
+    let sandbox = create_empty_moon_sandbox();
+
+    sandbox.create_file(
+        ".moon/workspace.yml",
+        format!(
+            r"
+projects: ['*']
+vcs:
+  provider: '{provider}'
+codeowners:
+  globalPaths:
+    '/.moon/': ['@infra']
+"
+        ),
+    );
+
+    sandbox.create_file(
+        "app/moon.yml",
+        r"
+owners:
+  defaultOwner: '@frontend'
+  requiredApprovals: 2
+  paths: ['src/']
+",
+    );
+
+    sandbox.create_file("app/src/index.ts", "");
+    sandbox.create_file("app/README.md", "");
+    sandbox
+}
+
+mod owners {
+    use super::*;
+
+    #[test]
+    fn resolves_owners_of_paths() {
+        let sandbox = create_owners_sandbox("github");
+
+        let assert = sandbox.run_bin(|cmd| {
+            cmd.args([
+                "owners",
+                "app/src/index.ts",
+                ".moon/workspace.yml",
+                "--json",
+            ]);
+        });
+
+        let output = assert.output();
+
+        assert!(output.contains("\"@frontend\""));
+        assert!(output.contains("\"@infra\""));
+        assert!(output.contains("\"unowned\": []"));
+    }
+
+    #[test]
+    fn resolves_relative_to_working_dir() {
+        let sandbox = create_owners_sandbox("github");
+
+        let assert = sandbox.run_bin(|cmd| {
+            cmd.args(["owners", "src/index.ts", "--json"])
+                .current_dir(sandbox.path().join("app"));
+        });
+
+        assert
+            .inner
+            .stdout(predicate::str::contains("\"path\": \"app/src/index.ts\""));
+    }
+
+    #[test]
+    fn errors_for_paths_outside_workspace() {
+        let sandbox = create_owners_sandbox("github");
+
+        let assert = sandbox.run_bin(|cmd| {
+            cmd.args(["owners", "../outside.txt"]);
+        });
+
+        assert.inner.failure().stderr(predicate::str::contains(
+            "Unable to resolve owners for ../outside.txt, as it's outside of the workspace.",
+        ));
+    }
+
+    #[test]
+    fn fails_check_for_unowned_files() {
+        let sandbox = create_owners_sandbox("github");
+
+        let assert = sandbox.run_bin(|cmd| {
+            cmd.args(["owners", "app/README.md", "--check"]);
+        });
+
+        assert.inner.failure().stdout(predicate::str::contains(
+            "1 file(s) are not owned by anyone.",
+        ));
+    }
+
+    #[test]
+    fn passes_check_when_owned() {
+        let sandbox = create_owners_sandbox("github");
+
+        let assert = sandbox.run_bin(|cmd| {
+            cmd.args(["owners", "app/src/index.ts", "--check"]);
+        });
+
+        assert.inner.success();
+    }
+
+    #[test]
+    fn fails_check_for_unsatisfied_approvals() {
+        let sandbox = create_owners_sandbox("gitlab");
+
+        let assert = sandbox.run_bin(|cmd| {
+            cmd.args([
+                "owners",
+                "app/src/index.ts",
+                "--check",
+                "--approver",
+                "@frontend",
+            ]);
+        });
+
+        assert.inner.failure();
+
+        let assert = sandbox.run_bin(|cmd| {
+            cmd.args([
+                "owners",
+                "app/src/index.ts",
+                "--check",
+                "--approver",
+                "@frontend",
+                "--approver",
+                "@frontend",
+            ]);
+        });
+
+        // Approvers are unique
+        assert.inner.failure();
+    }
+}
@@ -319,7 +319,7 @@ mod unix {
                 sandbox
                     .path()
                     .join(".moon/cache/outputs")
-                    .join(format!("{}.tar.gz", state.hash))
+                    .join(format!("{}.json", state.hash))
                     .exists()
             );
         }
@@ -627,7 +627,7 @@ mod windows {
                 sandbox
                     .path()
                     .join(".moon/cache/outputs")
-                    .join(format!("{}.tar.gz", state.hash))
+                    .join(format!("{}.json", state.hash))
                     .exists()
             );
         }
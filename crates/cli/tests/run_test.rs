@@ -1,4 +1,4 @@
-use moon_cache::CacheEngine;
+use moon_cache::{CacheEngine, OutputManifest};
 use moon_config::{
     HasherWalkStrategy, PartialCodeownersConfig, PartialHasherConfig, PartialVcsConfig,
     PartialWorkspaceConfig, VcsProvider,
@@ -707,10 +707,21 @@ mod hashing {
 mod outputs {
     use super::*;
 
-    fn untar(tarball: &Path, root: &Path) {
-        starbase_archive::Archiver::new(root, tarball)
-            .unpack(starbase_archive::tar::TarUnpacker::new_gz)
-            .unwrap();
+    fn unpack(manifest_file: &Path, root: &Path) {
+        let blobs_dir = manifest_file
+            .parent()
+            .unwrap()
+            .parent()
+            .unwrap()
+            .join("blobs");
+        let manifest: OutputManifest = json::read_file(manifest_file).unwrap();
+
+        for file in manifest.files {
+            let path = file.path.to_logical_path(root);
+
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::copy(blobs_dir.join(file.hash), path).unwrap();
+        }
     }
 
     #[test]
@@ -787,7 +798,7 @@ mod outputs {
             sandbox
                 .path()
                 .join(".moon/cache/outputs")
-                .join(format!("{hash}.tar.gz"))
+                .join(format!("{hash}.json"))
                 .exists()
         );
     }
@@ -817,7 +828,7 @@ mod outputs {
             sandbox
                 .path()
                 .join(".moon/cache/outputs")
-                .join(format!("{hash}.tar.gz"))
+                .join(format!("{hash}.json"))
                 .exists()
         );
     }
@@ -847,7 +858,7 @@ mod outputs {
             sandbox
                 .path()
                 .join(".moon/cache/outputs")
-                .join(format!("{hash}.tar.gz"))
+                .join(format!("{hash}.json"))
                 .exists()
         );
     }
@@ -877,7 +888,7 @@ mod outputs {
             sandbox
                 .path()
                 .join(".moon/cache/outputs")
-                .join(format!("{hash}.tar.gz"))
+                .join(format!("{hash}.json"))
                 .exists()
         );
     }
@@ -907,7 +918,7 @@ mod outputs {
             sandbox
                 .path()
                 .join(".moon/cache/outputs")
-                .join(format!("{hash}.tar.gz"))
+                .join(format!("{hash}.json"))
                 .exists()
         );
     }
@@ -922,13 +933,13 @@ mod outputs {
         });
 
         let hash = extract_hash_from_run(sandbox.path(), "outputs:generateFileTypes");
-        let tarball = sandbox
+        let manifest_file = sandbox
             .path()
             .join(".moon/cache/outputs")
-            .join(format!("{hash}.tar.gz"));
+            .join(format!("{hash}.json"));
         let dir = sandbox.path().join(".moon/cache/outputs").join(hash);
 
-        untar(&tarball, &dir);
+        unpack(&manifest_file, &dir);
 
         assert!(dir.join("outputs/multiple-types/one.js").exists());
         assert!(dir.join("outputs/multiple-types/two.js").exists());
@@ -946,13 +957,13 @@ mod outputs {
         });
 
         let hash = extract_hash_from_run(sandbox.path(), "outputs:generateFileAndFolder");
-        let tarball = sandbox
+        let manifest_file = sandbox
             .path()
             .join(".moon/cache/outputs")
-            .join(format!("{hash}.tar.gz"));
+            .join(format!("{hash}.json"));
         let dir = sandbox.path().join(".moon/cache/outputs").join(hash);
 
-        untar(&tarball, &dir);
+        unpack(&manifest_file, &dir);
 
         assert!(dir.join("outputs/both/a/one.js").exists());
         assert!(dir.join("outputs/both/b/two.js").exists());
@@ -968,13 +979,13 @@ mod outputs {
         });
 
         let hash = extract_hash_from_run(sandbox.path(), "outputs:generateFileAndFolderWorkspace");
-        let tarball = sandbox
+        let manifest_file = sandbox
             .path()
             .join(".moon/cache/outputs")
-            .join(format!("{hash}.tar.gz"));
+            .join(format!("{hash}.json"));
         let dir = sandbox.path().join(".moon/cache/outputs").join(hash);
 
-        untar(&tarball, &dir);
+        unpack(&manifest_file, &dir);
 
         assert!(dir.join("both/a/one.js").exists());
         assert!(dir.join("both/b/two.js").exists());
@@ -990,13 +1001,13 @@ mod outputs {
         });
 
         let hash = extract_hash_from_run(sandbox.path(), "outputs:negatedOutputGlob");
-        let tarball = sandbox
+        let manifest_file = sandbox
             .path()
             .join(".moon/cache/outputs")
-            .join(format!("{hash}.tar.gz"));
+            .join(format!("{hash}.json"));
         let dir = sandbox.path().join(".moon/cache/outputs").join(hash);
 
-        untar(&tarball, &dir);
+        unpack(&manifest_file, &dir);
 
         assert!(dir.join("outputs/both/a/one.js").exists());
         assert!(!dir.join("outputs/both/b/two.js").exists());
@@ -1184,7 +1195,7 @@ mod outputs {
             assert!(
                 sandbox
                     .path()
-                    .join(format!(".moon/cache/outputs/{hash}.tar.gz"))
+                    .join(format!(".moon/cache/outputs/{hash}.json"))
                     .exists()
             );
         }
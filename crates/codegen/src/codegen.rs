@@ -2,12 +2,17 @@ use crate::codegen_error::CodegenError;
 use crate::template::Template;
 use miette::IntoDiagnostic;
 use moon_common::Id;
+use moon_common::consts::CONFIG_DIRNAME;
 use moon_common::path::{PathExt, RelativePathBuf};
-use moon_config::{ConfigFinder, GeneratorConfig, TemplateLocator, load_template_config_template};
+use moon_config::{
+    ConfigFinder, GeneratorConfig, TEMPLATE_ARCHIVE_EXTENSIONS, TemplateLocator,
+    load_template_config_template,
+};
 use moon_env::MoonEnvironment;
 use moon_process::Command;
 use moon_time::now_millis;
 use rustc_hash::FxHashMap;
+use sha2::{Digest, Sha256};
 use starbase_archive::Archiver;
 use starbase_utils::{fs, glob, net};
 use std::path::{Path, PathBuf};
@@ -20,7 +25,9 @@ pub struct CodeGenerator<'app> {
     pub config: &'app GeneratorConfig,
     pub templates: FxHashMap<Id, Template>,
     pub template_locations: Vec<PathBuf>,
+    pub template_sources: Vec<TemplateLocator>,
 
+    cache_dir: PathBuf,
     moon_env: Arc<MoonEnvironment>,
     workspace_root: &'app Path,
 }
@@ -37,23 +44,39 @@ impl<'app> CodeGenerator<'app> {
         );
 
         CodeGenerator {
+            cache_dir: workspace_root
+                .join(CONFIG_DIRNAME)
+                .join("cache")
+                .join("templates"),
             config,
             moon_env,
             templates: FxHashMap::default(),
             template_locations: vec![],
+            template_sources: vec![],
             workspace_root,
         }
     }
 
     #[instrument(skip_all)]
     pub async fn load_templates(&mut self) -> miette::Result<()> {
-        self.resolve_template_locations().await?;
+        self.resolve_template_locations(false).await?;
+        self.load_templates_from_locations()
+    }
+
+    /// Refresh all remote template locations (Git, npm, archives) in the cache,
+    /// and then load templates from all locations.
+    #[instrument(skip_all)]
+    pub async fn update_templates(&mut self) -> miette::Result<()> {
+        self.resolve_template_locations(true).await?;
+        self.load_templates_from_locations()
+    }
 
+    fn load_templates_from_locations(&mut self) -> miette::Result<()> {
         debug!("Loading all available templates from locations");
 
         let finder = ConfigFinder::default();
 
-        for location in &self.template_locations {
+        for (location, source) in self.template_locations.iter().zip(&self.template_sources) {
             debug!(location = ?location, "Scanning location");
 
             for template_root in fs::read_dir(location)? {
@@ -66,8 +89,9 @@ impl<'app> CodeGenerator<'app> {
 
                 debug!(root = ?template_root, "Found a template, attempting to load");
 
-                let template =
+                let mut template =
                     Template::new(Id::clean(fs::file_name(&template_root))?, template_root)?;
+                template.source = Some(source.clone());
 
                 if let Some(existing_template) = self.templates.get(&template.id) {
                     return Err(CodegenError::DuplicateTemplate {
@@ -187,8 +211,9 @@ impl<'app> CodeGenerator<'app> {
     }
 
     #[instrument(skip_all)]
-    async fn resolve_template_locations(&mut self) -> miette::Result<()> {
+    async fn resolve_template_locations(&mut self, refresh: bool) -> miette::Result<()> {
         let mut locations = vec![];
+        let mut sources = vec![];
         let mut futures = vec![];
         let config_file_names = ConfigFinder::default().get_template_file_names();
 
@@ -202,11 +227,13 @@ impl<'app> CodeGenerator<'app> {
                             .normalize()
                             .to_logical_path(self.workspace_root),
                     );
+                    sources.push(locator.clone());
                 }
                 TemplateLocator::Glob { glob: pattern } => {
                     for path in glob::walk(self.workspace_root, [pattern])? {
                         if path.is_dir() {
                             locations.push(path);
+                            sources.push(locator.clone());
                         } else if path.is_file()
                             && path.file_name().is_some_and(|name| {
                                 config_file_names
@@ -215,6 +242,7 @@ impl<'app> CodeGenerator<'app> {
                             })
                         {
                             locations.push(path.parent().unwrap().to_path_buf());
+                            sources.push(locator.clone());
                         }
                     }
                 }
@@ -222,24 +250,37 @@ impl<'app> CodeGenerator<'app> {
                     remote_url,
                     revision,
                 } => {
-                    let base_url = remote_url.trim_start_matches('/');
-                    let url = format!("https://{base_url}");
-                    let template_location = self.moon_env.templates_dir.join(base_url);
+                    let url = if let Some(path) = remote_url.strip_prefix("file://") {
+                        // Local repositories are relative from the workspace root
+                        resolve_local_path(path, self.workspace_root)
+                            .to_string_lossy()
+                            .to_string()
+                    } else if remote_url.contains("://") {
+                        remote_url.to_owned()
+                    } else {
+                        format!("https://{}", remote_url.trim_start_matches('/'))
+                    };
+                    let template_location = self
+                        .cache_dir
+                        .join("git")
+                        .join(create_cache_key(&url))
+                        .join(revision.replace(['/', '\\'], "-"));
 
                     futures.push(spawn(clone_and_checkout_git_repository(
                         url,
                         revision.to_owned(),
                         template_location.clone(),
+                        refresh,
                     )));
 
                     locations.push(template_location);
+                    sources.push(locator.clone());
                 }
                 TemplateLocator::Npm { package, version } => {
                     let package_slug = package.replace('@', "").replace('/', "_").to_lowercase();
                     let version_string = version.to_string();
                     let template_location = self
-                        .moon_env
-                        .templates_dir
+                        .cache_dir
                         .join("npm")
                         .join(&package_slug)
                         .join(&version_string);
@@ -253,9 +294,35 @@ impl<'app> CodeGenerator<'app> {
                         version_string,
                         template_location.clone(),
                         temp_file,
+                        refresh,
+                    )));
+
+                    locations.push(template_location);
+                    sources.push(locator.clone());
+                }
+                TemplateLocator::Archive { url, checksum } => {
+                    let cache_key = create_cache_key(&locator.to_string());
+                    let template_location = self.cache_dir.join("archive").join(&cache_key);
+                    let extension = TEMPLATE_ARCHIVE_EXTENSIONS
+                        .iter()
+                        .find(|ext| url.to_lowercase().ends_with(*ext))
+                        .unwrap_or(&".tar.gz");
+
+                    futures.push(spawn(download_and_unpack_archive(
+                        ArchiveSource {
+                            url: url.to_owned(),
+                            checksum: checksum.to_owned(),
+                            workspace_root: self.workspace_root.to_path_buf(),
+                        },
+                        template_location.clone(),
+                        self.moon_env
+                            .temp_dir
+                            .join(format!("{cache_key}{extension}")),
+                        refresh,
                     )));
 
                     locations.push(template_location);
+                    sources.push(locator.clone());
                 }
             }
         }
@@ -265,16 +332,60 @@ impl<'app> CodeGenerator<'app> {
         }
 
         self.template_locations = locations;
+        self.template_sources = sources;
 
         Ok(())
     }
 }
 
+fn resolve_local_path(path: &str, workspace_root: &Path) -> PathBuf {
+    let file = PathBuf::from(path);
+
+    if file.is_absolute() {
+        file
+    } else {
+        RelativePathBuf::from(path)
+            .normalize()
+            .to_logical_path(workspace_root)
+    }
+}
+
+/// Create a readable and unique directory name for a URL.
+fn create_cache_key(url: &str) -> String {
+    let name = url
+        .trim_end_matches('/')
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default()
+        .split(['#', '?'])
+        .next()
+        .unwrap_or_default()
+        .trim_end_matches(".git")
+        .trim_end_matches(".tar.gz")
+        .trim_end_matches(".tgz")
+        .trim_end_matches(".zip")
+        .replace(
+            |c: char| !c.is_ascii_alphanumeric() && c != '-' && c != '_',
+            "-",
+        );
+
+    let mut hasher = Sha256::new();
+    hasher.update(url.as_bytes());
+    let hash = format!("{:x}", hasher.finalize());
+
+    if name.is_empty() {
+        hash[0..16].to_owned()
+    } else {
+        format!("{name}-{}", &hash[0..8])
+    }
+}
+
 #[instrument]
 async fn clone_and_checkout_git_repository(
     url: String,
     revision: String,
     template_location: PathBuf,
+    refresh: bool,
 ) -> miette::Result<()> {
     debug!(
         url,
@@ -292,15 +403,41 @@ async fn clone_and_checkout_git_repository(
         Ok(())
     }
 
+    let remote_branch = format!("origin/{revision}");
+
     // Clone or fetch the repository
     if template_location.join(".git").exists() {
+        // Tags and commits are pinned, so they can be reused from the cache,
+        // while branches must always pull the latest changes
+        if !refresh
+            && run_git(
+                &["rev-parse", "--verify", "--quiet", &remote_branch],
+                &template_location,
+            )
+            .await
+            .is_err()
+        {
+            debug!(
+                location = ?template_location,
+                "Repository already exists, using cached checkout",
+            );
+
+            return Ok(());
+        }
+
         debug!(
             location = ?template_location,
             "Repository already exists, fetching latest",
         );
 
         run_git(
-            &["fetch", "--prune", "--no-recurse-submodules"],
+            &[
+                "fetch",
+                "--prune",
+                "--tags",
+                "--force",
+                "--no-recurse-submodules",
+            ],
             &template_location,
         )
         .await?;
@@ -311,27 +448,39 @@ async fn clone_and_checkout_git_repository(
         );
 
         fs::create_dir_all(&template_location)?;
-        run_git(&["clone", &url, "."], &template_location).await?;
-    }
 
-    // Checkout the revision
-    debug!(revision, "Checking out the configured revision");
+        if let Err(error) = run_git(&["clone", &url, "."], &template_location).await {
+            fs::remove_dir_all(&template_location)?;
 
-    run_git(
-        &["checkout", "-B", &revision, "--track"],
+            return Err(error);
+        }
+    }
+
+    // Checkout the revision. Branches are reset to the latest remote commit,
+    // while tags and commits are pinned by detaching
+    if run_git(
+        &["rev-parse", "--verify", "--quiet", &remote_branch],
         &template_location,
     )
-    .await?;
-
-    // Checkout the revision
-    debug!("Pulling latest changes");
+    .await
+    .is_ok()
+    {
+        debug!(revision, "Checking out the configured branch");
 
-    run_git(&["pull", "--rebase", "--prune"], &template_location).await?;
+        run_git(
+            &["checkout", "--force", "-B", &revision, &remote_branch],
+            &template_location,
+        )
+        .await?;
+    } else {
+        debug!(revision, "Checking out the configured tag or commit");
 
-    fs::write_file(
-        template_location.parent().unwrap().join(".installed-at"),
-        now_millis().to_string(),
-    )?;
+        run_git(
+            &["checkout", "--force", "--detach", &revision],
+            &template_location,
+        )
+        .await?;
+    }
 
     Ok(())
 }
@@ -342,13 +491,16 @@ async fn download_and_unpack_npm_archive(
     version: String,
     template_location: PathBuf,
     temp_file: PathBuf,
+    refresh: bool,
 ) -> miette::Result<()> {
     debug!(
         package,
         version, "Resolving template location for npm package"
     );
 
-    if template_location.exists() {
+    // When refreshing, the existing template is only replaced once
+    // the new template has been downloaded and unpacked
+    if template_location.exists() && !refresh {
         debug!(location = ?template_location, "Template location already exists locally");
 
         return Ok(());
@@ -370,23 +522,151 @@ async fn download_and_unpack_npm_archive(
 
     net::download_from_url(&tarball_url, &temp_file).await?;
 
-    // Unpack tarball
+    // Unpack tarball into a temporary directory, and then move into place
+    let unpack_dir = template_location.with_extension("unpacking");
+
     debug!(
         temp_file = ?temp_file,
         location = ?template_location,
         "Unpacking npm tarball into template location",
     );
 
-    Archiver::new(&template_location, &temp_file)
+    if unpack_dir.exists() {
+        fs::remove_dir_all(&unpack_dir)?;
+    }
+
+    Archiver::new(&unpack_dir, &temp_file)
         .set_prefix("package")
         .unpack_from_ext()?;
 
     fs::remove_file(temp_file)?;
 
-    fs::write_file(
-        template_location.join(".installed-at"),
-        now_millis().to_string(),
-    )?;
+    fs::write_file(unpack_dir.join(".installed-at"), now_millis().to_string())?;
+
+    replace_template_location(&unpack_dir, &template_location)?;
+
+    Ok(())
+}
+
+#[derive(Debug)]
+struct ArchiveSource {
+    url: String,
+    checksum: Option<String>,
+    workspace_root: PathBuf,
+}
+
+#[instrument]
+async fn download_and_unpack_archive(
+    source: ArchiveSource,
+    template_location: PathBuf,
+    temp_file: PathBuf,
+    refresh: bool,
+) -> miette::Result<()> {
+    let url = &source.url;
+
+    debug!(url, "Resolving template location for archive");
+
+    // When refreshing, the existing template is only replaced once
+    // the new template has been downloaded and unpacked
+    if template_location.exists() && !refresh {
+        debug!(location = ?template_location, "Template location already exists locally");
+
+        return Ok(());
+    }
+
+    // Local archives are relative from the workspace root
+    let archive_file = if let Some(path) = url.strip_prefix("file://") {
+        resolve_local_path(path, &source.workspace_root)
+    } else {
+        debug!(url, temp_file = ?temp_file, "Downloading archive");
+
+        net::download_from_url(url, &temp_file).await?;
+
+        temp_file.clone()
+    };
+
+    // Verify the checksum before unpacking
+    if let Some(checksum) = &source.checksum {
+        let mut hasher = Sha256::new();
+        hasher.update(fs::read_file_bytes(&archive_file)?);
+        let actual = format!("{:x}", hasher.finalize());
+
+        if &actual != checksum {
+            if archive_file == temp_file {
+                fs::remove_file(&temp_file)?;
+            }
+
+            return Err(CodegenError::ArchiveChecksumMismatch {
+                url: url.to_owned(),
+                expected: checksum.to_owned(),
+                actual,
+            }
+            .into());
+        }
+    }
+
+    // Unpack into a temporary directory, and then move into place,
+    // so that an archive with a single root directory is flattened
+    let unpack_dir = template_location.with_extension("unpacking");
+
+    debug!(
+        archive = ?archive_file,
+        location = ?template_location,
+        "Unpacking archive into template location",
+    );
+
+    if unpack_dir.exists() {
+        fs::remove_dir_all(&unpack_dir)?;
+    }
+
+    Archiver::new(&unpack_dir, &archive_file).unpack_from_ext()?;
+
+    if archive_file == temp_file {
+        fs::remove_file(&temp_file)?;
+    }
+
+    let entries = fs::read_dir(&unpack_dir)?;
+    let root_dir = if entries.len() == 1 && entries[0].path().is_dir() {
+        entries[0].path()
+    } else {
+        unpack_dir.clone()
+    };
+
+    fs::write_file(root_dir.join(".installed-at"), now_millis().to_string())?;
+
+    replace_template_location(&root_dir, &template_location)?;
+
+    if unpack_dir.exists() {
+        fs::remove_dir_all(&unpack_dir)?;
+    }
+
+    Ok(())
+}
+
+// Move the unpacked template into place. An existing template (when refreshing)
+// is moved aside first, and is restored if the new template can't be moved.
+fn replace_template_location(source_dir: &Path, template_location: &Path) -> miette::Result<()> {
+    let previous_dir = template_location.with_extension("previous");
+
+    if previous_dir.exists() {
+        fs::remove_dir_all(&previous_dir)?;
+    }
+
+    if template_location.exists() {
+        std::fs::rename(template_location, &previous_dir).into_diagnostic()?;
+    }
+
+    if let Err(error) = std::fs::rename(source_dir, template_location) {
+        if previous_dir.exists() {
+            let _ = std::fs::rename(&previous_dir, template_location);
+        }
+
+        return Err(error).into_diagnostic();
+    }
+
+    if previous_dir.exists() {
+        fs::remove_dir_all(&previous_dir)?;
+    }
 
     Ok(())
 }
@@ -34,6 +34,30 @@ pub enum CodegenError {
     )]
     MissingTemplate(Id),
 
+    #[diagnostic(
+        code(codegen::template::missing_lock),
+        help = "Only templates that have been generated with a lock can be updated."
+    )]
+    #[error(
+        "Unable to update template {}, as it has not been generated into {}.",
+        .id.style(Style::Id),
+        .dest.style(Style::Path),
+    )]
+    MissingTemplateLock { id: Id, dest: PathBuf },
+
+    #[diagnostic(code(codegen::template::checksum_mismatch))]
+    #[error(
+        "Checksum mismatch for template archive {}. Expected {}, received {}.",
+        .url.style(Style::Url),
+        .expected.style(Style::Hash),
+        .actual.style(Style::Hash),
+    )]
+    ArchiveChecksumMismatch {
+        url: String,
+        expected: String,
+        actual: String,
+    },
+
     #[diagnostic(code(codegen::template::duplicate))]
     #[error(
         "Found multiple templates with the same name {}.\nOriginal template at {}.\nCurrent template at {}.",
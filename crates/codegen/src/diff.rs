@@ -0,0 +1,161 @@
+use similar::{Algorithm, DiffTag, TextDiff, capture_diff_slices};
+use std::ops::Range;
+
+pub const CONFLICT_MARKER_START: &str = "<<<<<<< current";
+pub const CONFLICT_MARKER_SEPARATOR: &str = "=======";
+pub const CONFLICT_MARKER_END: &str = ">>>>>>> template";
+
+/// Result of a three-way merge.
+#[derive(Debug, Default, PartialEq)]
+pub struct MergeResult {
+    /// Merged content, with conflict markers when applicable.
+    pub content: String,
+
+    /// Number of conflicting regions within the content.
+    pub conflicts: usize,
+}
+
+impl MergeResult {
+    pub fn has_conflicts(&self) -> bool {
+        self.conflicts > 0
+    }
+}
+
+#[derive(Debug)]
+struct Hunk<'text> {
+    base: Range<usize>,
+    lines: &'text [&'text str],
+    ours: bool,
+}
+
+fn split_lines(content: &str) -> Vec<&str> {
+    content.split_inclusive('\n').collect()
+}
+
+fn collect_hunks<'text>(base: &[&str], other: &'text [&'text str], ours: bool) -> Vec<Hunk<'text>> {
+    capture_diff_slices(Algorithm::Myers, base, other)
+        .into_iter()
+        .filter_map(|op| {
+            let (tag, base_range, other_range) = op.as_tag_tuple();
+
+            if tag == DiffTag::Equal {
+                None
+            } else {
+                Some(Hunk {
+                    base: base_range,
+                    lines: &other[other_range],
+                    ours,
+                })
+            }
+        })
+        .collect()
+}
+
+/// Apply the hunks of one side to the base lines within the range.
+fn apply_hunks(base: &[&str], range: &Range<usize>, hunks: &[&Hunk], ours: bool) -> String {
+    let mut content = String::new();
+    let mut index = range.start;
+
+    for hunk in hunks.iter().filter(|hunk| hunk.ours == ours) {
+        content.extend(base[index..hunk.base.start].iter().copied());
+        content.extend(hunk.lines.iter().copied());
+        index = hunk.base.end;
+    }
+
+    content.extend(base[index..range.end].iter().copied());
+    content
+}
+
+fn push_block(content: &mut String, block: &str) {
+    content.push_str(block);
+
+    if !block.is_empty() && !block.ends_with('\n') {
+        content.push('\n');
+    }
+}
+
+/// Merge the changes between the base and current content (ours), and the base
+/// and new content (theirs), line by line. Changes to the same or adjacent lines
+/// that differ are wrapped in Git-style conflict markers.
+pub fn merge_three_way(base: &str, current: &str, new: &str) -> MergeResult {
+    let base_lines = split_lines(base);
+    let current_lines = split_lines(current);
+    let new_lines = split_lines(new);
+
+    let mut hunks = collect_hunks(&base_lines, &current_lines, true);
+    hunks.extend(collect_hunks(&base_lines, &new_lines, false));
+    hunks.sort_by_key(|hunk| (hunk.base.start, hunk.base.end));
+
+    // Group overlapping or touching hunks into regions
+    let mut groups: Vec<(Range<usize>, Vec<&Hunk>)> = vec![];
+
+    for hunk in &hunks {
+        if let Some((range, group)) = groups.last_mut() {
+            if hunk.base.start <= range.end {
+                range.end = range.end.max(hunk.base.end);
+                group.push(hunk);
+                continue;
+            }
+        }
+
+        groups.push((hunk.base.clone(), vec![hunk]));
+    }
+
+    let mut result = MergeResult::default();
+    let mut index = 0;
+
+    for (range, group) in groups {
+        result
+            .content
+            .extend(base_lines[index..range.start].iter().copied());
+
+        let has_ours = group.iter().any(|hunk| hunk.ours);
+        let has_theirs = group.iter().any(|hunk| !hunk.ours);
+        let ours = apply_hunks(&base_lines, &range, &group, true);
+
+        if has_ours && has_theirs {
+            let theirs = apply_hunks(&base_lines, &range, &group, false);
+
+            if ours == theirs {
+                result.content.push_str(&ours);
+            } else {
+                result.conflicts += 1;
+                result.content.push_str(CONFLICT_MARKER_START);
+                result.content.push('\n');
+                push_block(&mut result.content, &ours);
+                result.content.push_str(CONFLICT_MARKER_SEPARATOR);
+                result.content.push('\n');
+                push_block(&mut result.content, &theirs);
+                result.content.push_str(CONFLICT_MARKER_END);
+                result.content.push('\n');
+            }
+        } else if has_ours {
+            result.content.push_str(&ours);
+        } else {
+            result
+                .content
+                .push_str(&apply_hunks(&base_lines, &range, &group, false));
+        }
+
+        index = range.end;
+    }
+
+    result.content.extend(base_lines[index..].iter().copied());
+    result
+}
+
+/// Create a unified diff between the old and new content,
+/// or `None` if the content is the same.
+pub fn create_unified_diff(old: &str, new: &str, path: &str) -> Option<String> {
+    if old == new {
+        return None;
+    }
+
+    Some(
+        TextDiff::from_lines(old, new)
+            .unified_diff()
+            .context_radius(3)
+            .header(&format!("a/{path}"), &format!("b/{path}"))
+            .to_string(),
+    )
+}
@@ -1,15 +1,19 @@
 mod asset_file;
 mod codegen;
 mod codegen_error;
+mod diff;
 mod filters;
 mod funcs;
 mod template;
 mod template_file;
+mod template_lock;
 
 pub use asset_file::*;
 pub use codegen::*;
 pub use codegen_error::*;
+pub use diff::*;
 pub use template::*;
 pub use template_file::*;
+pub use template_lock::*;
 pub use tera;
 pub use tera::Context as TemplateContext;
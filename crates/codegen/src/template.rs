@@ -1,10 +1,11 @@
 use crate::asset_file::AssetFile;
+use crate::diff::create_unified_diff;
 use crate::template_file::{FileState, MergeType, TemplateFile};
 use crate::{CodegenError, filters, funcs};
 use miette::IntoDiagnostic;
 use moon_common::Id;
 use moon_common::path::{RelativePathBuf, to_virtual_string};
-use moon_config::{ConfigLoader, TemplateConfig, schematic::strip_bom};
+use moon_config::{ConfigLoader, TemplateConfig, TemplateLocator, schematic::strip_bom};
 use regex::Regex;
 use serde::Serialize;
 use starbase_utils::{fs, json, yaml};
@@ -27,6 +28,8 @@ pub struct Template {
     pub files: BTreeMap<RelativePathBuf, TemplateFile>,
     pub id: Id,
     pub root: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<TemplateLocator>,
     pub templates: Vec<Template>, // Extending
 }
 
@@ -56,6 +59,7 @@ impl Template {
             engine,
             files: BTreeMap::new(),
             root,
+            source: None,
             templates: vec![],
         })
     }
@@ -282,6 +286,36 @@ impl Template {
         Ok(())
     }
 
+    /// Create a unified diff between the destination file, and the content
+    /// that would be written, or `None` if nothing would change.
+    pub fn diff_file(&self, file: &TemplateFile, label: &str) -> miette::Result<Option<String>> {
+        let prev = if file.dest_path.exists() {
+            fs::read_file(&file.dest_path)?
+        } else {
+            String::new()
+        };
+
+        let next = match file.state {
+            FileState::Skip => return Ok(None),
+            FileState::Merge => match file.is_mergeable() {
+                Some(MergeType::Json) => {
+                    let next: json::JsonValue = json::parse(&file.content)?;
+
+                    json::format(&json::merge(&json::parse(&prev)?, &next), true)?
+                }
+                Some(MergeType::Yaml) => {
+                    let next: yaml::YamlValue = yaml::parse(&file.content)?;
+
+                    yaml::format(&yaml::merge(&yaml::parse(&prev)?, &next))?
+                }
+                None => return Ok(None),
+            },
+            _ => file.content.clone(),
+        };
+
+        Ok(create_unified_diff(&prev, &next, label))
+    }
+
     /// Write the template file to the defined destination path.
     pub fn write_file(&self, file: &TemplateFile) -> miette::Result<()> {
         match file.state {
@@ -321,6 +355,16 @@ impl Template {
 
                 fs::write_file(&file.dest_path, &file.content)?;
             }
+            FileState::Conflict | FileState::Update => {
+                debug!(
+                    file = file.name.as_str(),
+                    to = ?file.dest_path,
+                    conflict = matches!(file.state, FileState::Conflict),
+                    "Updating with template file",
+                );
+
+                fs::write_file(&file.dest_path, &file.content)?;
+            }
             _ => {
                 debug!(
                     file = file.name.as_str(),
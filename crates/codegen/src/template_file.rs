@@ -1,6 +1,8 @@
+use crate::diff::merge_three_way;
 use moon_common::path::RelativePathBuf;
 use moon_config::TemplateFrontmatterConfig;
 use serde::Serialize;
+use starbase_utils::fs;
 use std::path::{Path, PathBuf};
 use tracing::debug;
 
@@ -14,10 +16,12 @@ pub enum MergeType {
 #[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum FileState {
+    Conflict,
     Create,
     Merge,
     Replace,
     Skip,
+    Update,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize)]
@@ -125,6 +129,55 @@ impl TemplateFile {
         self.set_content(content, dest)
     }
 
+    /// Determine the state of the file when updating a previously generated template,
+    /// by comparing the previous render (base), the current file, and the new render.
+    /// If both the current file and the template have changed, a three-way merge
+    /// will be applied, and conflicts will be marked within the content.
+    pub fn resolve_update(&mut self, base: Option<&str>) -> miette::Result<()> {
+        if !self.dest_path.exists() {
+            // File was removed after generating, so don't bring it back
+            self.state = if base.is_some() {
+                FileState::Skip
+            } else {
+                FileState::Create
+            };
+
+            return Ok(());
+        }
+
+        let current = fs::read_file(&self.dest_path)?;
+
+        // Already up to date, or the template has not changed
+        if current == self.content || base == Some(self.content.as_str()) {
+            self.state = FileState::Skip;
+
+            return Ok(());
+        }
+
+        // File has not changed since generating, so use the new render
+        if base == Some(current.as_str()) {
+            self.state = FileState::Update;
+
+            return Ok(());
+        }
+
+        debug!(
+            file = %self.name,
+            "File has changed since generating, applying a three-way merge",
+        );
+
+        let result = merge_three_way(base.unwrap_or_default(), &current, &self.content);
+
+        self.state = if result.has_conflicts() {
+            FileState::Conflict
+        } else {
+            FileState::Update
+        };
+        self.content = result.content;
+
+        Ok(())
+    }
+
     pub fn should_write(&self) -> bool {
         !matches!(self.state, FileState::Skip)
     }
@@ -0,0 +1,101 @@
+use crate::template::Template;
+use moon_common::Id;
+use moon_common::path::to_virtual_string;
+use serde::{Deserialize, Serialize};
+use starbase_utils::json::{self, JsonMap, JsonValue};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tera::Context;
+use tracing::debug;
+
+pub const TEMPLATE_LOCK_FILE: &str = ".moon-template.lock";
+
+/// Variables that are injected by moon and should not be locked.
+const BUILT_IN_VARIABLES: [&str; 4] = ["dest_dir", "dest_rel_dir", "working_dir", "workspace_root"];
+
+/// The state of a template at the time it was generated.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct TemplateLockEntry {
+    /// Rendered content of each file, relative from the destination.
+    /// Used as the base for three-way merges when updating.
+    pub files: BTreeMap<String, String>,
+
+    /// Variable values that were used to render the template.
+    pub variables: JsonMap<String, JsonValue>,
+}
+
+impl TemplateLockEntry {
+    pub fn new(template: &Template, dest: &Path, context: &Context) -> miette::Result<Self> {
+        let mut entry = TemplateLockEntry::default();
+
+        for file in template.files.values() {
+            if file.is_skipped() {
+                continue;
+            }
+
+            if let Ok(rel_path) = file.dest_path.strip_prefix(dest) {
+                entry
+                    .files
+                    .insert(to_virtual_string(rel_path)?, file.content.clone());
+            }
+        }
+
+        if let JsonValue::Object(variables) = context.clone().into_json() {
+            entry.variables = variables
+                .into_iter()
+                .filter(|(key, _)| !BUILT_IN_VARIABLES.contains(&key.as_str()))
+                .collect();
+        }
+
+        Ok(entry)
+    }
+
+    /// Convert the locked variables into a context for rendering.
+    pub fn to_context(&self) -> Context {
+        let mut context = Context::new();
+
+        for (key, value) in &self.variables {
+            context.insert(key, value);
+        }
+
+        context
+    }
+}
+
+/// A lock file that is written to the destination of generated templates,
+/// and records each template's variables and rendered files.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct TemplateLock {
+    pub templates: BTreeMap<Id, TemplateLockEntry>,
+
+    #[serde(skip)]
+    pub path: PathBuf,
+}
+
+impl TemplateLock {
+    pub fn load(dest: &Path) -> miette::Result<Self> {
+        let path = dest.join(TEMPLATE_LOCK_FILE);
+
+        let mut lock: TemplateLock = if path.exists() {
+            debug!(lock = ?path, "Loading template lock");
+
+            json::read_file(&path)?
+        } else {
+            TemplateLock::default()
+        };
+
+        lock.path = path;
+
+        Ok(lock)
+    }
+
+    pub fn save(&self) -> miette::Result<()> {
+        debug!(lock = ?self.path, "Saving template lock");
+
+        json::write_file(&self.path, self, true)?;
+
+        Ok(())
+    }
+}
@@ -4,9 +4,76 @@ use moon_config::{
     FilePath, GeneratorConfig, GlobPath, TemplateLocator, TemplateVariable, Version,
 };
 use moon_env::MoonEnvironment;
-use starbase_sandbox::{create_empty_sandbox, create_sandbox};
+use sha2::{Digest, Sha256};
+use starbase_archive::Archiver;
+use starbase_sandbox::{Sandbox, create_empty_sandbox, create_sandbox};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
 use std::sync::Arc;
 
+fn git(cwd: &Path, args: &[&str]) {
+    let output = Command::new("git")
+        .args([
+            "-c",
+            "user.name=moon",
+            "-c",
+            "user.email=fake@moonrepo.dev",
+            "-c",
+            "commit.gpgsign=false",
+        ])
+        .args(args)
+        .current_dir(cwd)
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+fn create_template_files(sandbox: &Sandbox, dir: &str, content: &str) {
+    sandbox.create_file(
+        format!("{dir}/one/template.yml"),
+        "title: 'One'\ndescription: 'One template'",
+    );
+    sandbox.create_file(format!("{dir}/one/file.txt"), content);
+}
+
+fn create_git_repo(sandbox: &Sandbox) {
+    create_template_files(sandbox, "repo", "v1");
+
+    let repo = sandbox.path().join("repo");
+
+    git(&repo, &["init", "--initial-branch", "main"]);
+    git(&repo, &["add", "--all"]);
+    git(&repo, &["commit", "--message", "v1"]);
+    git(&repo, &["tag", "v1"]);
+
+    sandbox.create_file("repo/one/file.txt", "v2");
+
+    git(&repo, &["commit", "--all", "--message", "v2"]);
+}
+
+fn create_archive(sandbox: &Sandbox, file: &str) -> String {
+    create_template_files(sandbox, "archive/root", "archived");
+
+    let archive_file = sandbox.path().join(file);
+
+    Archiver::new(&sandbox.path().join("archive"), &archive_file)
+        .add_source_glob("**/*")
+        .pack_from_ext()
+        .unwrap();
+
+    format!("{:x}", Sha256::digest(fs::read(archive_file).unwrap()))
+}
+
+fn read_template_file(codegen: &CodeGenerator) -> String {
+    fs::read_to_string(codegen.template_locations[0].join("one/file.txt")).unwrap()
+}
+
 mod codegen {
     use super::*;
 
@@ -135,14 +202,11 @@ mod codegen {
             let mut codegen = CodeGenerator::new(sandbox.path(), &config, Arc::clone(&env));
             codegen.load_templates().await.unwrap();
 
-            assert!(codegen.template_locations[0].starts_with(&env.templates_dir));
             assert!(
-                env.templates_dir
-                    .join("github.com")
-                    .join("moonrepo")
-                    .join("moon-configs.git")
-                    .exists()
+                codegen.template_locations[0]
+                    .starts_with(sandbox.path().join(".moon/cache/templates/git"))
             );
+            assert!(codegen.template_locations[0].join(".git").exists());
         }
 
         #[tokio::test]
@@ -159,16 +223,273 @@ mod codegen {
             let mut codegen = CodeGenerator::new(sandbox.path(), &config, Arc::clone(&env));
             codegen.load_templates().await.unwrap();
 
-            assert!(codegen.template_locations[0].starts_with(&env.templates_dir));
             assert!(
-                env.templates_dir
-                    .join("npm")
+                sandbox
+                    .path()
+                    .join(".moon/cache/templates/npm")
                     .join("moonrepo_cli")
                     .join("1.0.0")
                     .exists()
             );
         }
 
+        #[tokio::test]
+        async fn clones_a_local_git_repo_at_a_tag() {
+            let sandbox = create_empty_sandbox();
+            create_git_repo(&sandbox);
+
+            let config = GeneratorConfig {
+                templates: vec![TemplateLocator::Git {
+                    remote_url: "file://./repo".into(),
+                    revision: "v1".into(),
+                }],
+            };
+
+            let mut codegen = CodeGenerator::new(
+                sandbox.path(),
+                &config,
+                MoonEnvironment::new_testing(sandbox.path()).into(),
+            );
+            codegen.load_templates().await.unwrap();
+
+            assert!(
+                codegen.template_locations[0]
+                    .starts_with(sandbox.path().join(".moon/cache/templates/git"))
+            );
+            assert!(codegen.template_locations[0].ends_with("v1"));
+            assert_eq!(read_template_file(&codegen), "v1");
+            assert_eq!(
+                codegen.get_template("one").unwrap().source,
+                Some(config.templates[0].clone())
+            );
+        }
+
+        #[tokio::test]
+        async fn clones_a_local_git_repo_at_a_branch() {
+            let sandbox = create_empty_sandbox();
+            create_git_repo(&sandbox);
+
+            let config = GeneratorConfig {
+                templates: vec![TemplateLocator::Git {
+                    remote_url: "file://./repo".into(),
+                    revision: "main".into(),
+                }],
+            };
+
+            let mut codegen = CodeGenerator::new(
+                sandbox.path(),
+                &config,
+                MoonEnvironment::new_testing(sandbox.path()).into(),
+            );
+            codegen.load_templates().await.unwrap();
+
+            assert_eq!(read_template_file(&codegen), "v2");
+        }
+
+        #[tokio::test]
+        async fn pulls_latest_changes_for_git_branches() {
+            let sandbox = create_empty_sandbox();
+            create_git_repo(&sandbox);
+
+            let config = GeneratorConfig {
+                templates: vec![TemplateLocator::Git {
+                    remote_url: "file://./repo".into(),
+                    revision: "main".into(),
+                }],
+            };
+            let env = Arc::new(MoonEnvironment::new_testing(sandbox.path()));
+
+            let mut codegen = CodeGenerator::new(sandbox.path(), &config, Arc::clone(&env));
+            codegen.load_templates().await.unwrap();
+
+            assert_eq!(read_template_file(&codegen), "v2");
+
+            sandbox.create_file("repo/one/file.txt", "v3");
+            git(
+                &sandbox.path().join("repo"),
+                &["commit", "--all", "--message", "v3"],
+            );
+
+            let mut codegen = CodeGenerator::new(sandbox.path(), &config, Arc::clone(&env));
+            codegen.load_templates().await.unwrap();
+
+            assert_eq!(read_template_file(&codegen), "v3");
+        }
+
+        #[tokio::test]
+        async fn reuses_cached_git_repo_for_tags_until_updated() {
+            let sandbox = create_empty_sandbox();
+            create_git_repo(&sandbox);
+
+            let config = GeneratorConfig {
+                templates: vec![TemplateLocator::Git {
+                    remote_url: "file://./repo".into(),
+                    revision: "v1".into(),
+                }],
+            };
+            let env = Arc::new(MoonEnvironment::new_testing(sandbox.path()));
+
+            let mut codegen = CodeGenerator::new(sandbox.path(), &config, Arc::clone(&env));
+            codegen.load_templates().await.unwrap();
+
+            // Move the tag to the latest commit
+            git(&sandbox.path().join("repo"), &["tag", "--force", "v1"]);
+
+            let mut codegen = CodeGenerator::new(sandbox.path(), &config, Arc::clone(&env));
+            codegen.load_templates().await.unwrap();
+
+            assert_eq!(read_template_file(&codegen), "v1");
+
+            let mut codegen = CodeGenerator::new(sandbox.path(), &config, Arc::clone(&env));
+            codegen.update_templates().await.unwrap();
+
+            assert_eq!(read_template_file(&codegen), "v2");
+        }
+
+        #[tokio::test]
+        async fn unpacks_a_local_tar_archive() {
+            let sandbox = create_empty_sandbox();
+            let checksum = create_archive(&sandbox, "templates.tar.gz");
+
+            let config = GeneratorConfig {
+                templates: vec![TemplateLocator::Archive {
+                    url: "file://./templates.tar.gz".into(),
+                    checksum: Some(checksum),
+                }],
+            };
+
+            let mut codegen = CodeGenerator::new(
+                sandbox.path(),
+                &config,
+                MoonEnvironment::new_testing(sandbox.path()).into(),
+            );
+            codegen.load_templates().await.unwrap();
+
+            assert!(
+                codegen.template_locations[0]
+                    .starts_with(sandbox.path().join(".moon/cache/templates/archive"))
+            );
+            assert_eq!(read_template_file(&codegen), "archived");
+            assert!(codegen.get_template("one").is_ok());
+        }
+
+        #[tokio::test]
+        async fn unpacks_a_local_zip_archive() {
+            let sandbox = create_empty_sandbox();
+            create_archive(&sandbox, "templates.zip");
+
+            let config = GeneratorConfig {
+                templates: vec![TemplateLocator::Archive {
+                    url: "file://./templates.zip".into(),
+                    checksum: None,
+                }],
+            };
+
+            let mut codegen = CodeGenerator::new(
+                sandbox.path(),
+                &config,
+                MoonEnvironment::new_testing(sandbox.path()).into(),
+            );
+            codegen.load_templates().await.unwrap();
+
+            assert_eq!(read_template_file(&codegen), "archived");
+        }
+
+        #[tokio::test]
+        async fn refreshes_a_local_archive() {
+            let sandbox = create_empty_sandbox();
+            create_archive(&sandbox, "templates.zip");
+
+            let config = GeneratorConfig {
+                templates: vec![TemplateLocator::Archive {
+                    url: "file://./templates.zip".into(),
+                    checksum: None,
+                }],
+            };
+            let env = Arc::new(MoonEnvironment::new_testing(sandbox.path()));
+
+            let mut codegen = CodeGenerator::new(sandbox.path(), &config, Arc::clone(&env));
+            codegen.load_templates().await.unwrap();
+
+            assert_eq!(read_template_file(&codegen), "archived");
+
+            sandbox.create_file("archive/root/one/file.txt", "refreshed");
+            fs::remove_file(sandbox.path().join("templates.zip")).unwrap();
+
+            Archiver::new(
+                &sandbox.path().join("archive"),
+                &sandbox.path().join("templates.zip"),
+            )
+            .add_source_glob("**/*")
+            .pack_from_ext()
+            .unwrap();
+
+            let mut codegen = CodeGenerator::new(sandbox.path(), &config, Arc::clone(&env));
+            codegen.update_templates().await.unwrap();
+
+            assert_eq!(read_template_file(&codegen), "refreshed");
+            assert!(
+                !codegen.template_locations[0]
+                    .with_extension("previous")
+                    .exists()
+            );
+            assert!(
+                !codegen.template_locations[0]
+                    .with_extension("unpacking")
+                    .exists()
+            );
+        }
+
+        #[tokio::test]
+        async fn keeps_existing_archive_when_refresh_fails() {
+            let sandbox = create_empty_sandbox();
+            create_archive(&sandbox, "templates.zip");
+
+            let config = GeneratorConfig {
+                templates: vec![TemplateLocator::Archive {
+                    url: "file://./templates.zip".into(),
+                    checksum: None,
+                }],
+            };
+            let env = Arc::new(MoonEnvironment::new_testing(sandbox.path()));
+
+            let mut codegen = CodeGenerator::new(sandbox.path(), &config, Arc::clone(&env));
+            codegen.load_templates().await.unwrap();
+
+            let template_location = codegen.template_locations[0].clone();
+
+            fs::remove_file(sandbox.path().join("templates.zip")).unwrap();
+
+            let mut codegen = CodeGenerator::new(sandbox.path(), &config, Arc::clone(&env));
+
+            assert!(codegen.update_templates().await.is_err());
+            assert_eq!(
+                fs::read_to_string(template_location.join("one/file.txt")).unwrap(),
+                "archived"
+            );
+        }
+
+        #[tokio::test]
+        #[should_panic(expected = "Checksum mismatch for template archive")]
+        async fn errors_for_archive_checksum_mismatch() {
+            let sandbox = create_empty_sandbox();
+            create_archive(&sandbox, "templates.tar.gz");
+
+            let config = GeneratorConfig {
+                templates: vec![TemplateLocator::Archive {
+                    url: "file://./templates.tar.gz".into(),
+                    checksum: Some("0".repeat(64)),
+                }],
+            };
+
+            let mut codegen = CodeGenerator::new(
+                sandbox.path(),
+                &config,
+                MoonEnvironment::new_testing(sandbox.path()).into(),
+            );
+            codegen.load_templates().await.unwrap();
+        }
+
         #[tokio::test]
         async fn walks_with_globs() {
             let sandbox = create_sandbox("include");
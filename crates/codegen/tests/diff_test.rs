@@ -0,0 +1,92 @@
+use moon_codegen::{MergeResult, create_unified_diff, merge_three_way};
+
+mod merge_three_way {
+    use super::*;
+
+    const BASE: &str = "a\nb\nc\nd\ne\n";
+
+    #[test]
+    fn returns_base_if_nothing_changed() {
+        assert_eq!(
+            merge_three_way(BASE, BASE, BASE),
+            MergeResult {
+                content: BASE.into(),
+                conflicts: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn applies_one_side() {
+        assert_eq!(
+            merge_three_way(BASE, "a\nB\nc\nd\ne\n", BASE).content,
+            "a\nB\nc\nd\ne\n"
+        );
+        assert_eq!(
+            merge_three_way(BASE, BASE, "a\nb\nc\nD\ne\n").content,
+            "a\nb\nc\nD\ne\n"
+        );
+    }
+
+    #[test]
+    fn applies_both_sides_when_not_overlapping() {
+        let result = merge_three_way(BASE, "A\nb\nc\nd\ne\n", "a\nb\nc\nd\nE\nf\n");
+
+        assert_eq!(result.content, "A\nb\nc\nd\nE\nf\n");
+        assert!(!result.has_conflicts());
+    }
+
+    #[test]
+    fn applies_identical_changes_once() {
+        let result = merge_three_way(BASE, "a\nb\nX\nd\ne\n", "a\nb\nX\nd\ne\n");
+
+        assert_eq!(result.content, "a\nb\nX\nd\ne\n");
+        assert!(!result.has_conflicts());
+    }
+
+    #[test]
+    fn marks_conflicts() {
+        let result = merge_three_way(BASE, "a\nb\nours\nd\ne\n", "a\nb\ntheirs\nd\ne\n");
+
+        assert_eq!(
+            result.content,
+            "a\nb\n<<<<<<< current\nours\n=======\ntheirs\n>>>>>>> template\nd\ne\n"
+        );
+        assert_eq!(result.conflicts, 1);
+    }
+
+    #[test]
+    fn marks_conflicts_without_trailing_newline() {
+        let result = merge_three_way("a", "b", "c");
+
+        assert_eq!(
+            result.content,
+            "<<<<<<< current\nb\n=======\nc\n>>>>>>> template\n"
+        );
+    }
+
+    #[test]
+    fn merges_without_base() {
+        let result = merge_three_way("", "a\n", "a\n");
+
+        assert_eq!(result.content, "a\n");
+        assert!(!result.has_conflicts());
+    }
+}
+
+mod create_unified_diff {
+    use super::*;
+
+    #[test]
+    fn returns_none_if_same() {
+        assert_eq!(create_unified_diff("a\n", "a\n", "file.txt"), None);
+    }
+
+    #[test]
+    fn creates_diff() {
+        assert_eq!(
+            create_unified_diff("a\nb\n", "a\nc\n", "file.txt").unwrap(),
+            "--- a/file.txt\n+++ b/file.txt\n@@ -1,2 +1,2 @@\n a\n-b\n+c\n"
+        );
+    }
+}
@@ -0,0 +1,365 @@
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use miette::IntoDiagnostic;
+use moon_config::{CodeownersConfig, OwnersConfig, OwnersPaths, VcsProvider};
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tracing::{debug, trace};
+
+pub const WORKSPACE_SECTION: &str = "(workspace)";
+
+/// A single ownership rule, equivalent to a line in a `CODEOWNERS` file.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeownersRule {
+    /// Owners of files matching the pattern.
+    pub owners: Vec<String>,
+
+    /// Whether approvals from this section are optional (GitLab only).
+    pub optional: bool,
+
+    /// The pattern as written to the `CODEOWNERS` file.
+    pub pattern: String,
+
+    /// The number of approvals required from the owners.
+    pub required_approvals: u8,
+
+    /// The project ID that defined the rule, or `(workspace)`.
+    pub section: String,
+
+    #[serde(skip)]
+    matcher: Gitignore,
+}
+
+impl CodeownersRule {
+    pub fn matches(&self, file: &Path) -> bool {
+        self.matcher
+            .matched_path_or_any_parents(file, false)
+            .is_ignore()
+    }
+}
+
+/// The resolved ownership of a single file.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PathOwnership {
+    /// Workspace relative path to the file.
+    pub path: String,
+
+    /// Unique owners across all matching rules.
+    pub owners: Vec<String>,
+
+    /// Rules that apply to the file. For GitHub and Bitbucket, this will
+    /// be the last matching rule, while for GitLab, it will be the last
+    /// matching rule of each section.
+    pub rules: Vec<CodeownersRule>,
+}
+
+impl PathOwnership {
+    pub fn is_owned(&self) -> bool {
+        !self.owners.is_empty()
+    }
+}
+
+/// The approval state of a section that requires approvals.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalRequirement {
+    /// Approvers that are owners of the section.
+    pub approved_by: Vec<String>,
+
+    /// Owners that can approve the section.
+    pub owners: Vec<String>,
+
+    /// Files within the section that require approval.
+    pub paths: Vec<String>,
+
+    /// The number of approvals required.
+    pub required_approvals: u8,
+
+    /// The project ID that requires approvals, or `(workspace)`.
+    pub section: String,
+}
+
+impl ApprovalRequirement {
+    pub fn is_satisfied(&self) -> bool {
+        self.approved_by.len() >= self.required_approvals as usize
+    }
+}
+
+/// Resolves the owners of files by evaluating the same rules that are
+/// written by the `CodeownersGenerator`, while following the precedence
+/// rules of the configured provider.
+pub struct CodeownersResolver {
+    /// Bitbucket custom groups (`@@@name`) mapped to their members.
+    custom_groups: FxHashMap<String, Vec<String>>,
+
+    provider: VcsProvider,
+
+    rules: Vec<CodeownersRule>,
+
+    workspace_root: PathBuf,
+}
+
+impl CodeownersResolver {
+    pub fn new(workspace_root: &Path, provider: VcsProvider) -> CodeownersResolver {
+        debug!("Aggregating code owners for resolution");
+
+        CodeownersResolver {
+            custom_groups: FxHashMap::default(),
+            provider,
+            rules: vec![],
+            workspace_root: workspace_root.to_path_buf(),
+        }
+    }
+
+    pub fn add_project_entry(
+        &mut self,
+        id: &str,
+        source: &str,
+        config: &OwnersConfig,
+        root_config: &CodeownersConfig,
+    ) -> miette::Result<()> {
+        if config.paths.is_empty() {
+            return Ok(());
+        }
+
+        trace!(project_id = id, source, "Adding project rules");
+
+        let required_approvals = match self.provider {
+            VcsProvider::GitLab => config
+                .required_approvals
+                .or(root_config.required_approvals)
+                .unwrap_or(0),
+            // Only applies to the default owner through a `Check()` condition
+            VcsProvider::Bitbucket if config.default_owner.is_some() => config
+                .required_approvals
+                .or(root_config.required_approvals)
+                .unwrap_or(0),
+            _ => 0,
+        };
+        let optional = matches!(self.provider, VcsProvider::GitLab) && config.optional;
+        let root = PathBuf::from("/").join(source);
+
+        if matches!(self.provider, VcsProvider::Bitbucket) {
+            self.custom_groups.extend(config.custom_groups.clone());
+        }
+
+        let entries = match &config.paths {
+            OwnersPaths::List(paths) => paths
+                .iter()
+                .map(|path| (path.to_owned(), vec![]))
+                .collect::<Vec<_>>(),
+            OwnersPaths::Map(map) => map
+                .iter()
+                .map(|(path, owners)| (path.to_owned(), owners.to_owned()))
+                .collect(),
+        };
+
+        for (path, owners) in entries {
+            let owners = if owners.is_empty() {
+                config.default_owner.clone().into_iter().collect()
+            } else {
+                owners
+            };
+
+            self.add_rule(
+                format_path(root.join(path)),
+                owners,
+                id,
+                required_approvals,
+                optional,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn add_workspace_entries(&mut self, config: &CodeownersConfig) -> miette::Result<()> {
+        if config.global_paths.is_empty() {
+            return Ok(());
+        }
+
+        trace!("Adding workspace rules");
+
+        for (path, owners) in &config.global_paths {
+            if !owners.is_empty() {
+                self.add_rule(
+                    format_path(PathBuf::from(path)),
+                    owners.to_owned(),
+                    WORKSPACE_SECTION,
+                    0,
+                    false,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn get_rules(&self) -> &[CodeownersRule] {
+        &self.rules
+    }
+
+    /// Resolve the owners of the provided workspace relative file.
+    pub fn resolve(&self, path: &str) -> PathOwnership {
+        let file = self.workspace_root.join(path);
+        let mut rules: Vec<CodeownersRule> = vec![];
+
+        // Rules are evaluated in order and the last match wins,
+        // but GitLab evaluates each section independently
+        for rule in self.rules.iter().rev() {
+            if !rule.matches(&file) {
+                continue;
+            }
+
+            if matches!(self.provider, VcsProvider::GitLab) {
+                if rules.iter().all(|other| other.section != rule.section) {
+                    rules.push(rule.clone());
+                }
+            } else {
+                rules.push(rule.clone());
+                break;
+            }
+        }
+
+        rules.reverse();
+
+        let mut owners = vec![];
+
+        for rule in &rules {
+            for owner in &rule.owners {
+                if !owners.contains(owner) {
+                    owners.push(owner.to_owned());
+                }
+            }
+        }
+
+        PathOwnership {
+            path: path.to_owned(),
+            owners,
+            rules,
+        }
+    }
+
+    /// Determine whether the required approvals of each section that
+    /// applies to the provided files, have been satisfied by the approvers.
+    /// Approvers are matched against owners by name, while Bitbucket custom
+    /// groups are expanded to their members. Each approver counts as a single
+    /// approval, and groups or teams can't approve on behalf of their members.
+    pub fn check_approvals(
+        &self,
+        ownerships: &[PathOwnership],
+        approvers: &[String],
+    ) -> Vec<ApprovalRequirement> {
+        let mut requirements: Vec<ApprovalRequirement> = vec![];
+
+        for ownership in ownerships {
+            for rule in &ownership.rules {
+                if rule.optional || rule.required_approvals == 0 {
+                    continue;
+                }
+
+                let index = match requirements
+                    .iter()
+                    .position(|req| req.section == rule.section)
+                {
+                    Some(index) => index,
+                    None => {
+                        requirements.push(ApprovalRequirement {
+                            approved_by: vec![],
+                            owners: vec![],
+                            paths: vec![],
+                            required_approvals: rule.required_approvals,
+                            section: rule.section.clone(),
+                        });
+
+                        requirements.len() - 1
+                    }
+                };
+
+                let requirement = &mut requirements[index];
+
+                for owner in &rule.owners {
+                    if !requirement.owners.contains(owner) {
+                        requirement.owners.push(owner.to_owned());
+                    }
+                }
+
+                if !requirement.paths.contains(&ownership.path) {
+                    requirement.paths.push(ownership.path.clone());
+                }
+            }
+        }
+
+        for requirement in &mut requirements {
+            let eligible = self.expand_owners(&requirement.owners);
+
+            requirement.approved_by = approvers
+                .iter()
+                .filter(|approver| !self.is_group(approver) && eligible.contains(approver.as_str()))
+                .fold(vec![], |mut acc, approver| {
+                    if !acc.contains(approver) {
+                        acc.push(approver.to_owned());
+                    }
+                    acc
+                });
+        }
+
+        requirements
+    }
+
+    fn add_rule(
+        &mut self,
+        pattern: String,
+        owners: Vec<String>,
+        section: &str,
+        required_approvals: u8,
+        optional: bool,
+    ) -> miette::Result<()> {
+        let mut builder = GitignoreBuilder::new(&self.workspace_root);
+
+        // Patterns are escaped when written, so unescape for matching
+        builder
+            .add_line(None, &pattern.replace("\\ ", " "))
+            .into_diagnostic()?;
+
+        self.rules.push(CodeownersRule {
+            owners,
+            optional,
+            pattern,
+            required_approvals,
+            section: section.to_owned(),
+            matcher: builder.build().into_diagnostic()?,
+        });
+
+        Ok(())
+    }
+
+    // Custom groups, and GitHub or GitLab teams (`@org/team`)
+    fn is_group(&self, owner: &str) -> bool {
+        self.custom_groups.contains_key(owner) || (owner.starts_with('@') && owner.contains('/'))
+    }
+
+    fn expand_owners<'a>(&'a self, owners: &'a [String]) -> FxHashSet<&'a str> {
+        let mut set = FxHashSet::default();
+
+        for owner in owners {
+            set.insert(owner.as_str());
+
+            if let Some(members) = self.custom_groups.get(owner) {
+                set.extend(members.iter().map(|member| member.as_str()));
+            }
+        }
+
+        set
+    }
+}
+
+fn format_path(path: PathBuf) -> String {
+    path.to_string_lossy()
+        // Always use forward slashes
+        .replace('\\', "/")
+        // Escape spaces
+        .replace(' ', "\\ ")
+}
@@ -0,0 +1,279 @@
+use moon_codeowners::{CodeownersResolver, PathOwnership};
+use moon_config::{ConfigLoader, VcsProvider};
+use starbase_sandbox::{Sandbox, create_empty_sandbox, locate_fixture};
+use std::fs;
+
+fn load_resolver(provider: VcsProvider) -> (Sandbox, CodeownersResolver) {
+    let sandbox = create_empty_sandbox();
+    let config_loader = ConfigLoader::default();
+
+    sandbox.create_file(
+        ".moon/workspace.yml",
+        fs::read_to_string(locate_fixture("workspace").join("workspace.yml")).unwrap(),
+    );
+
+    let mut resolver = CodeownersResolver::new(sandbox.path(), provider);
+    let workspace_config = config_loader.load_workspace_config(sandbox.path()).unwrap();
+
+    resolver
+        .add_workspace_entries(&workspace_config.codeowners)
+        .unwrap();
+
+    for project_fixture in ["custom-groups", "list-paths", "map-paths", "no-paths"] {
+        sandbox.create_file(
+            format!("{}/moon.yml", project_fixture),
+            fs::read_to_string(locate_fixture(project_fixture).join("moon.yml")).unwrap(),
+        );
+
+        let project_config = config_loader
+            .load_project_config_from_source(sandbox.path(), project_fixture)
+            .unwrap();
+
+        resolver
+            .add_project_entry(
+                project_fixture,
+                project_fixture,
+                &project_config.owners,
+                &workspace_config.codeowners,
+            )
+            .unwrap();
+    }
+
+    (sandbox, resolver)
+}
+
+fn get_sections(ownership: &PathOwnership) -> Vec<&str> {
+    ownership
+        .rules
+        .iter()
+        .map(|rule| rule.section.as_str())
+        .collect()
+}
+
+mod codeowners_resolver {
+    use super::*;
+
+    #[test]
+    fn last_match_wins_for_github() {
+        let (_sandbox, resolver) = load_resolver(VcsProvider::GitHub);
+
+        let ownership = resolver.resolve("map-paths/lib.rs");
+
+        assert_eq!(ownership.owners, ["@rust-dev", "@api-team"]);
+        assert_eq!(get_sections(&ownership), ["map-paths"]);
+
+        let ownership = resolver.resolve("list-paths/index.ts");
+
+        assert_eq!(ownership.owners, ["@frontend"]);
+    }
+
+    #[test]
+    fn falls_back_to_workspace_rules() {
+        let (_sandbox, resolver) = load_resolver(VcsProvider::GitHub);
+
+        assert_eq!(resolver.resolve("README.md").owners, ["@admins"]);
+        assert_eq!(resolver.resolve("config/file.yml").owners, ["@infra"]);
+        assert_eq!(resolver.resolve("root.js").owners, ["@infra", "@frontend"]);
+        assert_eq!(resolver.resolve("no-paths/file.txt").owners, ["@admins"]);
+    }
+
+    #[test]
+    fn matches_directories_and_escaped_paths() {
+        let (_sandbox, resolver) = load_resolver(VcsProvider::GitHub);
+
+        assert_eq!(
+            resolver.resolve("map-paths/config/nested/file.json").owners,
+            ["@backend"]
+        );
+        assert_eq!(
+            resolver
+                .resolve("custom-groups/path/with space/file.txt")
+                .owners,
+            ["@@@group"]
+        );
+    }
+
+    #[test]
+    fn evaluates_each_section_for_gitlab() {
+        let (_sandbox, resolver) = load_resolver(VcsProvider::GitLab);
+
+        let ownership = resolver.resolve("map-paths/lib.rs");
+
+        assert_eq!(ownership.owners, ["@admins", "@rust-dev", "@api-team"]);
+        assert_eq!(get_sections(&ownership), ["(workspace)", "map-paths"]);
+    }
+
+    #[test]
+    fn reports_unowned_files() {
+        let sandbox = create_empty_sandbox();
+        let resolver = CodeownersResolver::new(sandbox.path(), VcsProvider::GitHub);
+
+        let ownership = resolver.resolve("file.txt");
+
+        assert!(!ownership.is_owned());
+        assert!(ownership.rules.is_empty());
+    }
+}
+
+mod approvals {
+    use super::*;
+
+    #[test]
+    fn ignored_for_github() {
+        let (_sandbox, resolver) = load_resolver(VcsProvider::GitHub);
+
+        let ownerships = vec![resolver.resolve("map-paths/lib.rs")];
+
+        assert!(resolver.check_approvals(&ownerships, &[]).is_empty());
+    }
+
+    #[test]
+    fn counts_approvers_for_gitlab() {
+        let (_sandbox, resolver) = load_resolver(VcsProvider::GitLab);
+
+        let ownerships = vec![
+            resolver.resolve("map-paths/lib.rs"),
+            resolver.resolve("map-paths/config/file.json"),
+        ];
+
+        let requirements = resolver.check_approvals(
+            &ownerships,
+            &["@rust-dev".into(), "@backend".into(), "@unknown".into()],
+        );
+
+        assert_eq!(requirements.len(), 1);
+        assert_eq!(requirements[0].section, "map-paths");
+        assert_eq!(requirements[0].required_approvals, 3);
+        assert_eq!(requirements[0].approved_by, ["@rust-dev", "@backend"]);
+        assert_eq!(requirements[0].paths.len(), 2);
+        assert!(!requirements[0].is_satisfied());
+
+        let requirements = resolver.check_approvals(
+            &ownerships,
+            &["@rust-dev".into(), "@backend".into(), "@api-team".into()],
+        );
+
+        assert!(requirements[0].is_satisfied());
+    }
+
+    #[test]
+    fn skips_optional_sections_for_gitlab() {
+        let sandbox = create_empty_sandbox();
+        let config_loader = ConfigLoader::default();
+
+        sandbox.create_file(
+            "project/moon.yml",
+            "owners:\n  defaultOwner: '@team'\n  optional: true\n  requiredApprovals: 2\n  paths: ['**/*']",
+        );
+
+        let project_config = config_loader
+            .load_project_config_from_source(sandbox.path(), "project")
+            .unwrap();
+
+        let mut resolver = CodeownersResolver::new(sandbox.path(), VcsProvider::GitLab);
+        resolver
+            .add_project_entry(
+                "project",
+                "project",
+                &project_config.owners,
+                &Default::default(),
+            )
+            .unwrap();
+
+        let ownerships = vec![resolver.resolve("project/file.txt")];
+
+        assert_eq!(ownerships[0].owners, ["@team"]);
+        assert!(resolver.check_approvals(&ownerships, &[]).is_empty());
+    }
+
+    #[test]
+    fn expands_custom_groups_for_bitbucket() {
+        let sandbox = create_empty_sandbox();
+        let config_loader = ConfigLoader::default();
+
+        sandbox.create_file(
+            "project/moon.yml",
+            r"
+owners:
+  customGroups:
+    '@@@devs': ['@alice', '@bob']
+  defaultOwner: '@@@devs'
+  requiredApprovals: 2
+  paths: ['src/']
+",
+        );
+
+        let project_config = config_loader
+            .load_project_config_from_source(sandbox.path(), "project")
+            .unwrap();
+
+        let mut resolver = CodeownersResolver::new(sandbox.path(), VcsProvider::Bitbucket);
+        resolver
+            .add_project_entry(
+                "project",
+                "project",
+                &project_config.owners,
+                &Default::default(),
+            )
+            .unwrap();
+
+        let ownerships = vec![resolver.resolve("project/src/index.ts")];
+
+        let requirements = resolver.check_approvals(&ownerships, &["@alice".into()]);
+
+        assert_eq!(requirements[0].approved_by, ["@alice"]);
+        assert!(!requirements[0].is_satisfied());
+
+        let requirements = resolver.check_approvals(&ownerships, &["@alice".into(), "@bob".into()]);
+
+        assert!(requirements[0].is_satisfied());
+    }
+
+    #[test]
+    fn doesnt_count_groups_as_approvers() {
+        let sandbox = create_empty_sandbox();
+        let config_loader = ConfigLoader::default();
+
+        sandbox.create_file(
+            "project/moon.yml",
+            r"
+owners:
+  customGroups:
+    '@@@devs': ['@alice', '@bob']
+  defaultOwner: '@@@devs'
+  requiredApprovals: 2
+  paths:
+    'src/': ['@@@devs', '@org/team', '@carol']
+",
+        );
+
+        let project_config = config_loader
+            .load_project_config_from_source(sandbox.path(), "project")
+            .unwrap();
+
+        let mut resolver = CodeownersResolver::new(sandbox.path(), VcsProvider::Bitbucket);
+        resolver
+            .add_project_entry(
+                "project",
+                "project",
+                &project_config.owners,
+                &Default::default(),
+            )
+            .unwrap();
+
+        let ownerships = vec![resolver.resolve("project/src/index.ts")];
+
+        let requirements = resolver.check_approvals(
+            &ownerships,
+            &["@@@devs".into(), "@org/team".into(), "@carol".into()],
+        );
+
+        assert_eq!(requirements[0].approved_by, ["@carol"]);
+        assert!(!requirements[0].is_satisfied());
+
+        let requirements = resolver.check_approvals(&ownerships, &[]);
+
+        assert!(requirements[0].approved_by.is_empty());
+        assert!(!requirements[0].is_satisfied());
+    }
+}
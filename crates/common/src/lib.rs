@@ -4,6 +4,7 @@ mod env;
 mod id;
 mod macros;
 pub mod path;
+pub mod size;
 
 #[cfg(not(target_arch = "wasm32"))]
 pub use env::*;
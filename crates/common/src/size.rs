@@ -0,0 +1,36 @@
+use miette::Diagnostic;
+use std::num::ParseFloatError;
+use thiserror::Error;
+
+#[derive(Error, Debug, Diagnostic)]
+pub enum SizeError {
+    #[diagnostic(code(size::invalid_number))]
+    #[error("Invalid max size: {0}")]
+    InvalidNumber(#[from] ParseFloatError),
+
+    #[diagnostic(code(size::unknown_unit))]
+    #[error("Invalid max size: unknown unit {0}")]
+    UnknownUnit(String),
+}
+
+/// Parse a human readable size (like `512`, `10KB`, or `1.5 GiB`) into bytes.
+pub fn parse_max_size(max_size: &str) -> Result<u64, SizeError> {
+    let value = max_size.trim();
+    let index = value
+        .find(|ch: char| !ch.is_ascii_digit() && ch != '.')
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(index);
+
+    let multiplier: u64 = match unit.trim().to_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" | "kib" => 1024,
+        "m" | "mb" | "mib" => 1024 * 1024,
+        "g" | "gb" | "gib" => 1024 * 1024 * 1024,
+        "t" | "tb" | "tib" => 1024 * 1024 * 1024 * 1024,
+        _ => return Err(SizeError::UnknownUnit(unit.trim().to_owned())),
+    };
+
+    let number = number.parse::<f64>()?;
+
+    Ok((number * multiplier as f64) as u64)
+}
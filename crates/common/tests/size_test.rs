@@ -0,0 +1,30 @@
+use moon_common::size::{SizeError, parse_max_size};
+
+mod max_size {
+    use super::*;
+
+    #[test]
+    fn parses_units() {
+        assert_eq!(parse_max_size("100").unwrap(), 100);
+        assert_eq!(parse_max_size("100b").unwrap(), 100);
+        assert_eq!(parse_max_size("2KB").unwrap(), 2048);
+        assert_eq!(parse_max_size("1.5 MB").unwrap(), 1024 * 1024 * 3 / 2);
+        assert_eq!(parse_max_size("10GB").unwrap(), 10 * 1024 * 1024 * 1024);
+    }
+
+    #[test]
+    fn errors_for_invalid() {
+        assert!(matches!(
+            parse_max_size("abc"),
+            Err(SizeError::UnknownUnit(unit)) if unit == "abc"
+        ));
+        assert!(matches!(
+            parse_max_size("10 PB"),
+            Err(SizeError::UnknownUnit(unit)) if unit == "PB"
+        ));
+        assert!(matches!(
+            parse_max_size("1.2.3MB"),
+            Err(SizeError::InvalidNumber(_))
+        ));
+    }
+}
@@ -21,6 +21,7 @@ fn generate_project(out_dir: &Path) -> miette::Result<()> {
                 "PartialTaskDependency".into(),
                 "PartialTaskDependencyConfig".into(),
                 "PartialTaskOptionsConfig".into(),
+                "PartialTaskOptionTestReportConfig".into(),
                 "PartialToolchainPluginConfig".into(),
                 "PlatformType".into(),
                 "PluginLocator".into(),
@@ -32,10 +33,13 @@ fn generate_project(out_dir: &Path) -> miette::Result<()> {
                 "TaskOperatingSystem".into(),
                 "TaskOptionAffectedFiles".into(),
                 "TaskOptionEnvFile".into(),
+                "TaskOptionSandbox".into(),
+                "TaskOptionTestReportConfig".into(),
                 "TaskOptionsConfig".into(),
                 "TaskOutputStyle".into(),
                 "TaskPreset".into(),
                 "TaskPriority".into(),
+                "TaskTestReportFormat".into(),
                 "TaskUnixShell".into(),
                 "TaskWindowsShell".into(),
                 "TaskType".into(),
@@ -112,9 +116,14 @@ fn generate_workspace(out_dir: &Path) -> miette::Result<()> {
     generator.generate(
         out_dir.join("workspace-config.ts"),
         TypeScriptRenderer::new(TypeScriptOptions {
-            exclude_references: vec!["ExtendsFrom".into(), "PluginLocator".into()],
+            exclude_references: vec![
+                "ExtendsFrom".into(),
+                "PluginLocator".into(),
+                "ProjectType".into(),
+            ],
             external_types: HashMap::from_iter([
                 ("./common".into(), vec!["ExtendsFrom".into()]),
+                ("./project-config".into(), vec!["ProjectType".into()]),
                 ("./toolchain-config".into(), vec!["PluginLocator".into()]),
             ]),
             ..Default::default()
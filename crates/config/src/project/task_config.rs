@@ -1,4 +1,5 @@
 use crate::language_platform::PlatformType;
+use crate::portable_path::FilePath;
 use crate::project::{PartialTaskOptionsConfig, TaskOptionsConfig};
 use crate::shapes::{InputPath, OneOrMany, OutputPath};
 use crate::{config_enum, config_struct, config_unit_enum};
@@ -68,6 +69,40 @@ pub(crate) fn validate_deps<D, C>(
     Ok(())
 }
 
+pub(crate) fn validate_secrets<D, C>(
+    secrets: &FxHashMap<String, PartialTaskSecretConfig>,
+    _task: &D,
+    _context: &C,
+    _finalize: bool,
+) -> Result<(), ValidateError> {
+    for (name, secret) in secrets {
+        let sources = [
+            secret.env.is_some(),
+            secret.file.is_some(),
+            secret.provider.is_some(),
+        ]
+        .into_iter()
+        .filter(|source| *source)
+        .count();
+
+        if sources != 1 {
+            return Err(ValidateError::with_segment(
+                "exactly one of `env`, `file`, or `provider` is required",
+                schematic::PathSegment::Key(name.to_owned()),
+            ));
+        }
+
+        if secret.key.is_some() && secret.provider.is_none() {
+            return Err(ValidateError::with_segment(
+                "a `key` can only be used with a `provider`",
+                schematic::PathSegment::Key(name.to_owned()),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
 config_enum!(
     /// Preset options to inherit.
     #[derive(ConfigEnum, Copy)]
@@ -167,6 +202,54 @@ impl TaskDependency {
     }
 }
 
+config_struct!(
+    /// Configures where the value of a secret is sourced from. Secrets are
+    /// injected as environment variables when the task is ran, but are never
+    /// hashed, and are redacted from logs, captured output, and reports.
+    #[derive(Config)]
+    pub struct TaskSecretConfig {
+        /// Name of an environment variable on the host machine to read the value from.
+        pub env: Option<String>,
+
+        /// Path to a file to read the value from, relative from the project root,
+        /// or the workspace root when prefixed with `/`.
+        pub file: Option<FilePath>,
+
+        /// The key to request from the provider. When not provided,
+        /// will use the name of the secret.
+        pub key: Option<String>,
+
+        /// Don't fail the task when the value could not be resolved.
+        pub optional: bool,
+
+        /// ID of an extension that will resolve the value.
+        pub provider: Option<Id>,
+    }
+);
+
+impl TaskSecretConfig {
+    pub fn from_env(name: impl AsRef<str>) -> Self {
+        Self {
+            env: Some(name.as_ref().to_owned()),
+            ..Default::default()
+        }
+    }
+
+    pub fn from_file(path: FilePath) -> Self {
+        Self {
+            file: Some(path),
+            ..Default::default()
+        }
+    }
+
+    pub fn from_provider(id: Id) -> Self {
+        Self {
+            provider: Some(id),
+            ..Default::default()
+        }
+    }
+}
+
 config_struct!(
     /// Configures a task to be ran within the action pipeline.
     #[derive(Config)]
@@ -235,6 +318,12 @@ config_struct!(
         /// arguments, merging, or inheritance.
         pub script: Option<String>,
 
+        /// A mapping of secrets that will be set as environment variables when
+        /// the task is ran. Values are resolved at runtime, and are excluded
+        /// from the task hash and redacted from all output.
+        #[setting(nested, validate = validate_secrets)]
+        pub secrets: Option<FxHashMap<String, TaskSecretConfig>>,
+
         /// The toolchain(s) in which the task will be ran in. The toolchain determines
         /// available binaries, lookup paths, and more. When not provided, will
         /// be automatically detected.
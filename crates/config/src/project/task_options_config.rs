@@ -1,8 +1,10 @@
-use crate::portable_path::FilePath;
+use crate::portable_path::{FilePath, GlobPath};
 use crate::shapes::{InputPath, OneOrMany};
 use crate::{config_enum, config_struct, config_unit_enum, generate_switch};
+use moon_common::Id;
 use schematic::schema::{StringType, UnionType};
 use schematic::{Config, ConfigEnum, Schema, SchemaBuilder, Schematic, ValidateError};
+use std::collections::BTreeMap;
 use std::env::consts;
 use std::str::FromStr;
 
@@ -101,6 +103,52 @@ config_enum!(
 
 generate_switch!(TaskOptionRunInCI, ["always", "affected"]);
 
+config_enum!(
+    /// The mode in which undeclared file access will be reported when sandboxed.
+    #[serde(expecting = "expected `warn`, `error`, or a boolean")]
+    pub enum TaskOptionSandbox {
+        /// Report undeclared file access as warnings.
+        Warn,
+        /// Report undeclared file access as errors, and fail the task.
+        Error,
+        /// Report undeclared file access as warnings.
+        #[serde(untagged)]
+        Enabled(bool),
+    }
+);
+
+generate_switch!(TaskOptionSandbox, ["warn", "error"]);
+
+impl TaskOptionSandbox {
+    pub fn is_error(&self) -> bool {
+        matches!(self, Self::Error)
+    }
+}
+
+config_unit_enum!(
+    /// The format of a test report generated by a task.
+    #[derive(ConfigEnum)]
+    pub enum TaskTestReportFormat {
+        #[default]
+        Junit,
+        Tap,
+    }
+);
+
+config_struct!(
+    /// Configures a test report that is generated by the task, and parsed
+    /// after the task has ran to record the results of each test.
+    #[derive(Config)]
+    pub struct TaskOptionTestReportConfig {
+        /// The format of the report.
+        pub format: TaskTestReportFormat,
+
+        /// Path (or glob) to the report file(s), relative from the project root,
+        /// or the workspace root when prefixed with `/`.
+        pub path: GlobPath,
+    }
+);
+
 config_unit_enum!(
     /// The strategy in which to merge a specific task option.
     #[derive(ConfigEnum)]
@@ -286,6 +334,11 @@ config_struct!(
         /// in which it is ran within the pipeline.
         pub priority: Option<TaskPriority>,
 
+        /// Resources required to run the task, used by the pipeline to weight
+        /// concurrency. Supports `cpu` (number of cores), `memory` (in megabytes),
+        /// and custom pools defined in `pipeline.resources`.
+        pub resources: Option<BTreeMap<Id, u32>>,
+
         /// The number of times a failing task will be retried to succeed.
         #[setting(env = "MOON_RETRY_COUNT")]
         pub retry_count: Option<u8>,
@@ -301,6 +354,11 @@ config_struct!(
         /// Runs the task from the workspace root, instead of the project root.
         pub run_from_workspace_root: Option<bool>,
 
+        /// Traces file access while the task is running (Linux only), and
+        /// reports files that were read but not declared as `inputs`, or
+        /// written but not declared as `outputs`.
+        pub sandbox: Option<TaskOptionSandbox>,
+
         /// Runs the task within a shell. When not defined, runs the task
         /// directly while relying on `PATH` resolution.
         pub shell: Option<bool>,
@@ -308,6 +366,11 @@ config_struct!(
         /// The maximum time in seconds that a task can run before being cancelled.
         pub timeout: Option<u64>,
 
+        /// A test report (JUnit or TAP) generated by the task, that will be
+        /// parsed after the task has ran and attached to the run report.
+        #[setting(nested)]
+        pub test_report: Option<TaskOptionTestReportConfig>,
+
         /// The shell to run the task in when on a Unix-based machine.
         pub unix_shell: Option<TaskUnixShell>,
 
@@ -35,7 +35,7 @@ config_unit_enum!(
 
 config_unit_enum!(
     /// The type of project, for categorizing.
-    #[derive(ConfigEnum)]
+    #[derive(ConfigEnum, Hash)]
     pub enum ProjectType {
         Application,
         Automation,
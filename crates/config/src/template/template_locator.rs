@@ -11,6 +11,9 @@ static GIT: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new("^(?<url>[a-zA-Z@0-9.-]+/[a-zA-Z0-9-_./]+)#(?<revision>[a-z0-9-_.@]+)$").unwrap()
 });
 
+static CHECKSUM: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new("^sha256:(?<hash>[a-fA-F0-9]{64})$").unwrap());
+
 static NPM: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new("^(?<package>(@[a-z][a-z0-9-_.]*/)?[a-z][a-z0-9-_.]*)#(?<version>[a-z0-9-.+]+)$")
         .unwrap()
@@ -33,9 +36,70 @@ derive_enum!(
             package: String,
             version: Version,
         },
+        Archive {
+            url: String,
+            checksum: Option<String>,
+        },
     }
 );
 
+/// Archive formats that can be downloaded and unpacked.
+pub const TEMPLATE_ARCHIVE_EXTENSIONS: [&str; 3] = [".tar.gz", ".tgz", ".zip"];
+
+fn is_archive_url(url: &str) -> bool {
+    TEMPLATE_ARCHIVE_EXTENSIONS
+        .iter()
+        .any(|ext| url.to_lowercase().ends_with(ext))
+}
+
+fn parse_archive(url: &str) -> Result<TemplateLocator, ParseError> {
+    let (url, checksum) = match url.split_once('#') {
+        Some((url, fragment)) => {
+            let Some(result) = CHECKSUM.captures(fragment) else {
+                return Err(ParseError::new(
+                    "Invalid archive template locator checksum, must be in the format of `#sha256:hash`",
+                ));
+            };
+
+            (
+                url,
+                Some(result.name("hash").unwrap().as_str().to_lowercase()),
+            )
+        }
+        None => (url, None),
+    };
+
+    Ok(TemplateLocator::Archive {
+        url: url.to_owned(),
+        checksum,
+    })
+}
+
+impl TemplateLocator {
+    /// Return true if the templates are located outside of the workspace,
+    /// and must be downloaded or cloned.
+    pub fn is_remote(&self) -> bool {
+        matches!(
+            self,
+            TemplateLocator::Archive { .. }
+                | TemplateLocator::Git { .. }
+                | TemplateLocator::Npm { .. }
+        )
+    }
+
+    /// Return the pinned version of remote templates, if applicable.
+    pub fn get_version(&self) -> Option<String> {
+        match self {
+            TemplateLocator::Archive { checksum, .. } => checksum
+                .as_ref()
+                .map(|checksum| format!("sha256:{}", &checksum[0..12])),
+            TemplateLocator::Git { revision, .. } => Some(revision.to_owned()),
+            TemplateLocator::Npm { version, .. } => Some(version.to_string()),
+            _ => None,
+        }
+    }
+}
+
 impl fmt::Display for TemplateLocator {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -44,8 +108,18 @@ impl fmt::Display for TemplateLocator {
             TemplateLocator::Git {
                 remote_url,
                 revision,
-            } => write!(f, "git://{remote_url}#{revision}"),
+            } => {
+                if remote_url.starts_with("file://") {
+                    write!(f, "git+{remote_url}#{revision}")
+                } else {
+                    write!(f, "git://{remote_url}#{revision}")
+                }
+            }
             TemplateLocator::Npm { package, version } => write!(f, "npm://{package}#{version}"),
+            TemplateLocator::Archive { url, checksum } => match checksum {
+                Some(checksum) => write!(f, "{url}#sha256:{checksum}"),
+                None => write!(f, "{url}"),
+            },
         }
     }
 }
@@ -69,6 +143,20 @@ impl FromStr for TemplateLocator {
             }
 
             match protocol {
+                "git+file" => {
+                    if let Some((path, revision)) = inner_value.rsplit_once('#') {
+                        if !path.is_empty() && !revision.is_empty() {
+                            return Ok(TemplateLocator::Git {
+                                remote_url: format!("file://{path}"),
+                                revision: revision.to_owned(),
+                            });
+                        }
+                    }
+
+                    return Err(ParseError::new(
+                        "Invalid Git template locator, must be in the format of `git+file://path#revision`",
+                    ));
+                }
                 "git" | "git+http" | "git+https" => {
                     if let Some(result) = GIT.captures(inner_value) {
                         return Ok(TemplateLocator::Git {
@@ -94,6 +182,19 @@ impl FromStr for TemplateLocator {
                         "Invalid npm template locator, must be in the format of `{protocol}://package#version`"
                     )));
                 }
+                "http" | "https" => {
+                    if is_archive_url(inner_value.split('#').next().unwrap_or_default()) {
+                        return parse_archive(value);
+                    }
+
+                    return Err(ParseError::new(format!(
+                        "Invalid archive template locator, must be a URL to a {} file",
+                        TEMPLATE_ARCHIVE_EXTENSIONS.join(", ")
+                    )));
+                }
+                "file" if is_archive_url(inner_value.split('#').next().unwrap_or_default()) => {
+                    return parse_archive(value);
+                }
                 "file" => {
                     return Ok(TemplateLocator::File {
                         path: FilePath::from_str(inner_value)?,
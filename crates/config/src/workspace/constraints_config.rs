@@ -1,17 +1,66 @@
 use crate::config_struct;
+use crate::project_config::ProjectType;
 use moon_common::Id;
 use rustc_hash::FxHashMap;
-use schematic::Config;
+use schematic::{Config, validate};
+
+config_struct!(
+    /// Forbids dependencies between projects, using query language (MQL)
+    /// statements to match the dependent and dependency projects.
+    #[derive(Config)]
+    pub struct ForbiddenDependencyConfig {
+        /// A query that matches the projects that are not allowed
+        /// to depend on the `to` projects.
+        #[setting(validate = validate::not_empty)]
+        pub from: String,
+
+        /// A custom reason that is displayed when the rule is violated.
+        pub reason: Option<String>,
+
+        /// A query that matches the projects that can not be depended on.
+        #[setting(validate = validate::not_empty)]
+        pub to: String,
+
+        /// Also match dependencies of dependencies, instead of only
+        /// direct dependencies.
+        pub transitive: bool,
+    }
+);
 
 config_struct!(
     /// Configures boundaries and constraints between projects.
     #[derive(Config)]
     pub struct ConstraintsConfig {
+        /// Disallows dependency cycles between projects that belong
+        /// to different stacks, based on each project's `stack` setting.
+        pub ban_cross_stack_cycles: bool,
+
+        /// Calls the `check_project_constraints` function of each toolchain
+        /// plugin that a project belongs to, to enforce custom constraints.
+        pub enforce_plugin_constraints: bool,
+
         /// Enforces relationships between projects based on each project's
         /// `type` setting.
         #[setting(default = true)]
         pub enforce_project_type_relationships: bool,
 
+        /// A list of dependency rules that are forbidden between projects.
+        #[setting(nested)]
+        pub forbidden_dependencies: Vec<ForbiddenDependencyConfig>,
+
+        /// The maximum depth of a project's dependency chain. A direct
+        /// dependency has a depth of 1.
+        pub max_dependency_depth: Option<usize>,
+
+        /// Restricts which projects can depend on a project with a specific
+        /// tag. Requires a mapping of tags, to tags that a dependent project
+        /// must have one of.
+        pub restricted_tags: FxHashMap<Id, Vec<Id>>,
+
+        /// Requires projects of a specific `type` to have at least one of
+        /// the configured tags.
+        pub required_tags: FxHashMap<ProjectType, Vec<Id>>,
+
         /// Enforces relationships between projects based on each project's
         /// `tags` setting. Requires a mapping of tags, to acceptable tags.
         pub tag_relationships: FxHashMap<Id, Vec<Id>>,
@@ -1,7 +1,33 @@
 use crate::{config_enum, config_struct};
 use moon_common::Id;
+use moon_common::size::parse_max_size;
 use moon_target::Target;
-use schematic::Config;
+use schematic::{Config, ValidateError};
+use std::collections::BTreeMap;
+
+fn validate_max_size<D, C>(
+    value: &str,
+    _data: &D,
+    _ctx: &C,
+    _finalize: bool,
+) -> Result<(), ValidateError> {
+    parse_max_size(value).map_err(|error| ValidateError::new(error.to_string()))?;
+
+    Ok(())
+}
+
+fn validate_percentage<D, C>(
+    value: &u8,
+    _data: &D,
+    _ctx: &C,
+    _finalize: bool,
+) -> Result<(), ValidateError> {
+    if *value > 100 {
+        return Err(ValidateError::new("must be a percentage between 0 and 100"));
+    }
+
+    Ok(())
+}
 
 config_enum!(
     /// Toggles the state of actions within the pipeline.
@@ -55,6 +81,11 @@ config_struct!(
         #[setting(default = "7 days")]
         pub cache_lifetime: String,
 
+        /// The maximum size of the cache (like `20GB`). When exceeded, the
+        /// least recently used task hashes and outputs will be evicted.
+        #[setting(validate = validate_max_size)]
+        pub cache_max_size: Option<String>,
+
         /// Automatically inherit color settings for all tasks being ran.
         #[setting(default = true)]
         pub inherit_colors_for_piped_tasks: bool,
@@ -73,6 +104,22 @@ config_struct!(
         /// Logs the task's command and arguments when running the task.
         pub log_running_command: bool,
 
+        /// Report failures of known flaky tasks as warnings, instead of
+        /// failing the pipeline. A task is flaky when it has failed and then
+        /// passed on retry, or passed with an unchanged hash.
+        pub quarantine_flaky: bool,
+
+        /// The flakiness score (percentage of recent runs that were flaky)
+        /// in which a task is considered flaky and will be quarantined.
+        #[setting(default = 10, validate = validate_percentage)]
+        pub quarantine_flaky_threshold: u8,
+
+        /// The capacity of resources available to tasks, as required by their
+        /// `options.resources`. The `cpu` resource defaults to the concurrency
+        /// of the pipeline, while `memory` (in megabytes) and custom pools are
+        /// unbounded unless defined.
+        pub resources: BTreeMap<Id, u32>,
+
         /// Run the `SyncProject` actions in the pipeline for each owning project
         /// of a running task.
         #[setting(nested)]
@@ -139,6 +139,11 @@ config_struct!(
         #[setting(nested)]
         pub cache: RemoteCacheConfig,
 
+        /// Execute tasks on the remote host through the Execution service,
+        /// instead of only caching their outputs. Requires the gRPC API.
+        #[setting(env = "MOON_REMOTE_EXECUTE", parse_env = env::parse_bool)]
+        pub execute: bool,
+
         /// The remote host to connect and send requests to.
         /// Supports gRPC protocols.
         #[setting(env = "MOON_REMOTE_HOST", validate = validate::not_empty)]
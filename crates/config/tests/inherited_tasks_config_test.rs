@@ -1196,10 +1196,13 @@ mod task_manager {
                         persistent: Some(true),
                         priority: None,
                         retry_count: Some(3),
+                        resources: None,
                         run_deps_in_parallel: Some(false),
                         run_in_ci: Some(TaskOptionRunInCI::Enabled(true)),
                         run_from_workspace_root: Some(false),
+                        sandbox: None,
                         shell: Some(false),
+                        test_report: None,
                         timeout: Some(60),
                         unix_shell: Some(TaskUnixShell::Zsh),
                         windows_shell: Some(TaskWindowsShell::Pwsh)
@@ -29,7 +29,7 @@ mod task_config {
 
     #[test]
     #[should_panic(
-        expected = "unknown field `unknown`, expected one of `extends`, `description`, `command`, `args`, `deps`, `env`, `inputs`, `local`, `outputs`, `options`, `platform`, `preset`, `script`, `toolchain`, `type`"
+        expected = "unknown field `unknown`, expected one of `extends`, `description`, `command`, `args`, `deps`, `env`, `inputs`, `local`, `outputs`, `options`, `platform`, `preset`, `script`, `secrets`, `toolchain`, `type`"
     )]
     fn error_unknown_field() {
         test_parse_config("unknown: 123", load_config_from_code);
@@ -357,6 +357,89 @@ outputs:
         }
     }
 
+    mod secrets {
+        use super::*;
+        use moon_config::TaskSecretConfig;
+
+        #[test]
+        fn can_set_sources() {
+            let config = test_parse_config(
+                r"
+secrets:
+  FROM_ENV:
+    env: HOST_VAR
+  FROM_FILE:
+    file: /.secrets/token
+  FROM_PROVIDER:
+    provider: vault
+    key: path/to/key
+    optional: true
+",
+                load_config_from_code,
+            );
+
+            let secrets = config.secrets.unwrap();
+
+            assert_eq!(
+                secrets.get("FROM_ENV").unwrap(),
+                &TaskSecretConfig::from_env("HOST_VAR")
+            );
+            assert_eq!(
+                secrets.get("FROM_FILE").unwrap(),
+                &TaskSecretConfig::from_file(FilePath("/.secrets/token".into()))
+            );
+            assert_eq!(
+                secrets.get("FROM_PROVIDER").unwrap(),
+                &TaskSecretConfig {
+                    key: Some("path/to/key".into()),
+                    optional: true,
+                    ..TaskSecretConfig::from_provider(Id::raw("vault"))
+                }
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "exactly one of `env`, `file`, or `provider` is required")]
+        fn errors_if_no_source() {
+            test_parse_config(
+                r"
+secrets:
+  TOKEN:
+    optional: true
+",
+                load_config_from_code,
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "exactly one of `env`, `file`, or `provider` is required")]
+        fn errors_if_multiple_sources() {
+            test_parse_config(
+                r"
+secrets:
+  TOKEN:
+    env: TOKEN
+    file: .token
+",
+                load_config_from_code,
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "a `key` can only be used with a `provider`")]
+        fn errors_if_key_without_provider() {
+            test_parse_config(
+                r"
+secrets:
+  TOKEN:
+    env: TOKEN
+    key: token
+",
+                load_config_from_code,
+            );
+        }
+    }
+
     mod type_of {
         use super::*;
 
@@ -664,6 +747,160 @@ options:
                     r"
 options:
   os: unknown
+",
+                    load_config_from_code,
+                );
+            }
+        }
+
+        mod sandbox {
+            use super::*;
+            use moon_config::TaskOptionSandbox;
+
+            #[test]
+            fn can_use_true() {
+                let config = test_parse_config(
+                    r"
+options:
+  sandbox: true
+",
+                    load_config_from_code,
+                );
+
+                assert_eq!(
+                    config.options.sandbox,
+                    Some(TaskOptionSandbox::Enabled(true))
+                );
+            }
+
+            #[test]
+            fn can_set_warn() {
+                let config = test_parse_config(
+                    r"
+options:
+  sandbox: warn
+",
+                    load_config_from_code,
+                );
+
+                assert_eq!(config.options.sandbox, Some(TaskOptionSandbox::Warn));
+            }
+
+            #[test]
+            fn can_set_error() {
+                let config = test_parse_config(
+                    r"
+options:
+  sandbox: error
+",
+                    load_config_from_code,
+                );
+
+                assert_eq!(config.options.sandbox, Some(TaskOptionSandbox::Error));
+            }
+
+            #[test]
+            #[should_panic(expected = "expected `warn`, `error`, or a boolean")]
+            fn errors_on_invalid_variant() {
+                test_parse_config(
+                    r"
+options:
+  sandbox: other
+",
+                    load_config_from_code,
+                );
+            }
+        }
+
+        mod resources {
+            use super::*;
+            use std::collections::BTreeMap;
+
+            #[test]
+            fn can_set_resources() {
+                let config = test_parse_config(
+                    r"
+options:
+  resources:
+    cpu: 4
+    memory: 2048
+    gpu-license: 1
+",
+                    load_config_from_code,
+                );
+
+                assert_eq!(
+                    config.options.resources,
+                    Some(BTreeMap::from_iter([
+                        (Id::raw("cpu"), 4),
+                        (Id::raw("gpu-license"), 1),
+                        (Id::raw("memory"), 2048),
+                    ]))
+                );
+            }
+
+            #[test]
+            #[should_panic(expected = "invalid value: integer `-1`")]
+            fn errors_on_negative_amount() {
+                test_parse_config(
+                    r"
+options:
+  resources:
+    cpu: -1
+",
+                    load_config_from_code,
+                );
+            }
+        }
+
+        mod test_report {
+            use super::*;
+            use moon_config::{GlobPath, TaskTestReportFormat};
+
+            #[test]
+            fn defaults_to_junit() {
+                let config = test_parse_config(
+                    r"
+options:
+  testReport:
+    path: junit.xml
+",
+                    load_config_from_code,
+                );
+
+                let report = config.options.test_report.unwrap();
+
+                assert_eq!(report.format, TaskTestReportFormat::Junit);
+                assert_eq!(report.path, GlobPath("junit.xml".into()));
+            }
+
+            #[test]
+            fn can_set_format() {
+                let config = test_parse_config(
+                    r"
+options:
+  testReport:
+    format: tap
+    path: /reports/*.tap
+",
+                    load_config_from_code,
+                );
+
+                let report = config.options.test_report.unwrap();
+
+                assert_eq!(report.format, TaskTestReportFormat::Tap);
+                assert_eq!(report.path, GlobPath("/reports/*.tap".into()));
+            }
+
+            #[test]
+            #[should_panic(expected = "unknown variant `xml`, expected `junit` or `tap`")]
+            fn errors_on_invalid_format() {
+                test_parse_config(
+                    r"
+options:
+  testReport:
+    format: xml
+    path: report.xml
 ",
                     load_config_from_code,
                 );
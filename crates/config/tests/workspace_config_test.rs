@@ -3,8 +3,8 @@ mod utils;
 use httpmock::prelude::*;
 use moon_common::Id;
 use moon_config::{
-    ConfigLoader, ExtensionConfig, FilePath, GlobPath, TemplateLocator, VcsProvider,
-    WorkspaceConfig, WorkspaceProjects,
+    ConfigLoader, ExtensionConfig, FilePath, ForbiddenDependencyConfig, GlobPath, ProjectType,
+    TemplateLocator, VcsProvider, WorkspaceConfig, WorkspaceProjects,
 };
 use proto_core::warpgate::UrlLocator;
 use rustc_hash::FxHashMap;
@@ -307,6 +307,10 @@ projects:
 
             assert!(config.constraints.enforce_project_type_relationships);
             assert!(config.constraints.tag_relationships.is_empty());
+            assert!(!config.constraints.ban_cross_stack_cycles);
+            assert!(!config.constraints.enforce_plugin_constraints);
+            assert!(config.constraints.forbidden_dependencies.is_empty());
+            assert!(config.constraints.max_dependency_depth.is_none());
         }
 
         #[test]
@@ -328,6 +332,63 @@ constraints:
             );
         }
 
+        #[test]
+        fn can_set_boundaries() {
+            let config = test_load_config(
+                FILENAME,
+                r"
+constraints:
+  banCrossStackCycles: true
+  forbiddenDependencies:
+    - from: 'projectType=library'
+      to: 'projectType=application'
+      reason: 'Libraries must not depend on applications.'
+      transitive: true
+  maxDependencyDepth: 5
+  requiredTags:
+    library: ['domain']
+  restrictedTags:
+    internal: ['admin']
+",
+                load_config_from_root,
+            );
+
+            assert!(config.constraints.ban_cross_stack_cycles);
+            assert_eq!(
+                config.constraints.forbidden_dependencies,
+                vec![ForbiddenDependencyConfig {
+                    from: "projectType=library".into(),
+                    reason: Some("Libraries must not depend on applications.".into()),
+                    to: "projectType=application".into(),
+                    transitive: true,
+                }]
+            );
+            assert_eq!(config.constraints.max_dependency_depth, Some(5));
+            assert_eq!(
+                config.constraints.required_tags,
+                FxHashMap::from_iter([(ProjectType::Library, vec![Id::raw("domain")])])
+            );
+            assert_eq!(
+                config.constraints.restricted_tags,
+                FxHashMap::from_iter([(Id::raw("internal"), vec![Id::raw("admin")])])
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "must not be empty")]
+        fn errors_on_empty_forbidden_query() {
+            test_load_config(
+                FILENAME,
+                r"
+constraints:
+  forbiddenDependencies:
+    - from: ''
+      to: 'projectType=application'
+",
+                load_config_from_root,
+            );
+        }
+
         #[test]
         #[should_panic(
             expected = "invalid type: integer `123`, expected struct PartialConstraintsConfig"
@@ -478,6 +539,70 @@ generator:
             );
         }
 
+        #[test]
+        fn can_set_local_git_locations() {
+            let config = test_load_config(
+                FILENAME,
+                r"
+generator:
+  templates:
+    - git+file://./repos/templates#main
+    - git+file:///abs/repos/templates#v1.2.3
+",
+                load_config_from_root,
+            );
+
+            assert_eq!(
+                config.generator.templates,
+                vec![
+                    TemplateLocator::Git {
+                        remote_url: "file://./repos/templates".into(),
+                        revision: "main".into()
+                    },
+                    TemplateLocator::Git {
+                        remote_url: "file:///abs/repos/templates".into(),
+                        revision: "v1.2.3".into()
+                    },
+                ]
+            );
+        }
+
+        #[test]
+        fn can_set_archive_locations() {
+            let config = test_load_config(
+                FILENAME,
+                r"
+generator:
+  templates:
+    - https://example.com/templates.tar.gz
+    - https://example.com/templates.zip#sha256:9F86D081884C7D659A2FEAA0C55AD015A3BF4F1B2B0B822CD15D6C15B0F00A08
+    - file://./archives/templates.tgz
+",
+                load_config_from_root,
+            );
+
+            assert_eq!(
+                config.generator.templates,
+                vec![
+                    TemplateLocator::Archive {
+                        url: "https://example.com/templates.tar.gz".into(),
+                        checksum: None,
+                    },
+                    TemplateLocator::Archive {
+                        url: "https://example.com/templates.zip".into(),
+                        checksum: Some(
+                            "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+                                .into()
+                        ),
+                    },
+                    TemplateLocator::Archive {
+                        url: "file://./archives/templates.tgz".into(),
+                        checksum: None,
+                    },
+                ]
+            );
+        }
+
         #[test]
         fn can_set_glob_locations() {
             let config = test_load_config(
@@ -529,6 +654,32 @@ generator:
                 r"
 generator:
   templates: ['npm://@scope/package']
+",
+                load_config_from_root,
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "Invalid archive template locator, must be a URL to a")]
+        fn errors_for_non_archive_url() {
+            test_load_config(
+                FILENAME,
+                r"
+generator:
+  templates: ['https://example.com/templates']
+",
+                load_config_from_root,
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "Invalid archive template locator checksum")]
+        fn errors_for_invalid_archive_checksum() {
+            test_load_config(
+                FILENAME,
+                r"
+generator:
+  templates: ['https://example.com/templates.zip#md5:abc']
 ",
                 load_config_from_root,
             );
@@ -654,6 +805,94 @@ runner:
             assert_eq!(config.pipeline.cache_lifetime, "10 hours");
             assert!(!config.pipeline.inherit_colors_for_piped_tasks);
         }
+
+        #[test]
+        fn can_set_resources() {
+            let config = test_load_config(
+                FILENAME,
+                r"
+pipeline:
+  resources:
+    cpu: 6
+    memory: 16384
+    gpu-license: 2
+",
+                load_config_from_root,
+            );
+
+            assert_eq!(
+                config.pipeline.resources,
+                std::collections::BTreeMap::from_iter([
+                    (Id::raw("cpu"), 6),
+                    (Id::raw("gpu-license"), 2),
+                    (Id::raw("memory"), 16384),
+                ])
+            );
+        }
+
+        #[test]
+        fn can_set_cache_max_size() {
+            let config = test_load_config(
+                FILENAME,
+                r"
+pipeline:
+  cacheMaxSize: 20GB
+",
+                load_config_from_root,
+            );
+
+            assert_eq!(config.pipeline.cache_max_size, Some("20GB".into()));
+        }
+
+        #[test]
+        #[should_panic(expected = "Invalid max size: unknown unit bananas")]
+        fn errors_for_invalid_cache_max_size() {
+            test_load_config(
+                FILENAME,
+                r"
+pipeline:
+  cacheMaxSize: 20 bananas
+",
+                load_config_from_root,
+            );
+        }
+
+        #[test]
+        fn can_set_quarantine_flaky() {
+            let config = test_load_config(
+                FILENAME,
+                r"
+pipeline:
+  quarantineFlaky: true
+  quarantineFlakyThreshold: 25
+",
+                load_config_from_root,
+            );
+
+            assert!(config.pipeline.quarantine_flaky);
+            assert_eq!(config.pipeline.quarantine_flaky_threshold, 25);
+        }
+
+        #[test]
+        fn defaults_quarantine_flaky_threshold() {
+            let config = test_load_config(FILENAME, "pipeline: {}", load_config_from_root);
+
+            assert!(!config.pipeline.quarantine_flaky);
+            assert_eq!(config.pipeline.quarantine_flaky_threshold, 10);
+        }
+
+        #[test]
+        #[should_panic(expected = "must be a percentage between 0 and 100")]
+        fn errors_for_quarantine_flaky_threshold_over_100() {
+            test_load_config(
+                FILENAME,
+                r"
+pipeline:
+  quarantineFlakyThreshold: 150
+",
+                load_config_from_root,
+            );
+        }
     }
 
     mod vcs {
@@ -687,13 +926,15 @@ vcs:
         }
 
         #[test]
-        #[should_panic(expected = "unknown variant `mercurial`, expected `git`")]
+        #[should_panic(
+            expected = "unknown variant `svn`, expected one of `git`, `jujutsu`, `mercurial`, `sapling`"
+        )]
         fn errors_on_invalid_manager() {
             test_load_config(
                 FILENAME,
                 r"
 vcs:
-  manager: mercurial
+  manager: svn
 ",
                 load_config_from_root,
             );
@@ -845,6 +1086,7 @@ extensions:
                         Id::raw("a"),
                         vec![Id::raw("b"), Id::raw("c")]
                     )]),
+                    ..Default::default()
                 }
             );
             assert_eq!(
@@ -0,0 +1,384 @@
+use moon_action::{ActionStatus, Operation, OperationList, TestReport};
+use moon_target::Target;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+
+/// Maximum number of lines to keep in each task's output pane.
+pub const MAX_PANE_LINES: usize = 5000;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum DashboardTaskState {
+    Cached,
+    Failed,
+    Passed,
+    #[default]
+    Pending,
+    Running,
+    Skipped,
+}
+
+impl DashboardTaskState {
+    pub fn from_status(status: ActionStatus) -> Self {
+        match status {
+            ActionStatus::Cached | ActionStatus::CachedFromRemote => Self::Cached,
+            ActionStatus::Passed => Self::Passed,
+            ActionStatus::Aborted | ActionStatus::Failed | ActionStatus::TimedOut => Self::Failed,
+            ActionStatus::Invalid | ActionStatus::Skipped => Self::Skipped,
+            ActionStatus::Running => Self::Running,
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        !matches!(self, Self::Pending | Self::Running)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DashboardLine {
+    pub content: String,
+    pub stderr: bool,
+}
+
+#[derive(Debug)]
+pub struct DashboardTask {
+    pub attempt: u8,
+    pub duration: Option<Duration>,
+    pub lines: Vec<DashboardLine>,
+    pub persistent: bool,
+    pub started_at: Option<Instant>,
+    pub state: DashboardTaskState,
+    pub target: Target,
+
+    restart: Arc<Notify>,
+    restarting: bool,
+}
+
+impl DashboardTask {
+    pub fn new(target: Target, persistent: bool) -> Self {
+        Self {
+            attempt: 0,
+            duration: None,
+            lines: vec![],
+            persistent,
+            started_at: None,
+            state: DashboardTaskState::Pending,
+            target,
+            restart: Arc::new(Notify::new()),
+            restarting: false,
+        }
+    }
+
+    /// Return the final duration if complete, otherwise the
+    /// time elapsed since the task started running.
+    pub fn get_elapsed(&self) -> Option<Duration> {
+        self.duration
+            .or_else(|| self.started_at.map(|started| started.elapsed()))
+    }
+
+    fn push_line(&mut self, content: &str, stderr: bool) {
+        for line in content.lines() {
+            self.lines.push(DashboardLine {
+                content: strip_ansi(line),
+                stderr,
+            });
+        }
+
+        if self.lines.len() > MAX_PANE_LINES {
+            self.lines.drain(0..self.lines.len() - MAX_PANE_LINES);
+        }
+    }
+}
+
+/// A lightweight copy of a task, without its output, for rendering the task list.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DashboardTaskSummary {
+    pub attempt: u8,
+    pub elapsed: Option<Duration>,
+    pub line_count: usize,
+    pub persistent: bool,
+    pub state: DashboardTaskState,
+    pub target: Target,
+}
+
+/// Live state of all tasks in the pipeline, that is updated by the
+/// reporter and rendered by the full-screen dashboard.
+#[derive(Debug, Default)]
+pub struct TaskDashboard {
+    closed: AtomicBool,
+    close_signal: Notify,
+    completed: AtomicBool,
+    exit_signal: Notify,
+    exited: AtomicBool,
+    keep_open: bool,
+    tasks: RwLock<Vec<DashboardTask>>,
+}
+
+impl TaskDashboard {
+    /// Create a dashboard that exits automatically once the pipeline
+    /// has completed, unless it should be kept open for review.
+    pub fn new(keep_open: bool) -> Self {
+        Self {
+            keep_open,
+            ..Default::default()
+        }
+    }
+
+    pub fn add_task(&self, target: &Target, persistent: bool) {
+        let mut tasks = self.tasks.write().unwrap();
+
+        if !tasks.iter().any(|task| &task.target == target) {
+            tasks.push(DashboardTask::new(target.to_owned(), persistent));
+        }
+    }
+
+    pub fn start_task(&self, target: &Target, attempt: u8) {
+        self.update_task(target, |task| {
+            if task.restarting {
+                task.push_line("── restarted ──", false);
+                task.restarting = false;
+                task.started_at = None;
+            } else if attempt > 1 {
+                task.push_line(&format!("── attempt {attempt} ──"), false);
+            }
+
+            task.attempt = attempt;
+            task.duration = None;
+            task.state = DashboardTaskState::Running;
+
+            if task.started_at.is_none() {
+                task.started_at = Some(Instant::now());
+            }
+        });
+    }
+
+    pub fn complete_task(&self, target: &Target, operations: &OperationList) {
+        self.update_task(target, |task| {
+            task.state = DashboardTaskState::from_status(operations.get_final_status());
+            task.duration = Some(
+                task.started_at
+                    .map(|started| started.elapsed())
+                    .unwrap_or_default(),
+            );
+        });
+    }
+
+    pub fn write_line(&self, target: &Target, line: impl AsRef<str>, stderr: bool) {
+        self.update_task(target, |task| {
+            task.push_line(line.as_ref(), stderr);
+        });
+    }
+
+    pub fn write_operation_output(&self, target: &Target, operation: &Operation) {
+        let Some(output) = operation.get_exec_output() else {
+            return;
+        };
+
+        self.update_task(target, |task| {
+            if let Some(out) = &output.stdout {
+                task.push_line(out.trim(), false);
+            }
+
+            if let Some(err) = &output.stderr {
+                task.push_line(err.trim(), true);
+            }
+        });
+    }
+
+    pub fn write_test_failures(&self, target: &Target, report: &TestReport) {
+        self.update_task(target, |task| {
+            for test in report.get_failed_tests() {
+                task.push_line(&format!("✖ {}", test.get_label()), true);
+            }
+        });
+    }
+
+    /// Return a pane for writing the streamed output of a task.
+    pub fn get_pane(self: &Arc<Self>, target: &Target) -> TaskPane {
+        TaskPane {
+            dashboard: Arc::clone(self),
+            target: target.to_owned(),
+        }
+    }
+
+    /// Return the lines of a task's output, ending `offset` lines before
+    /// the last line, and containing at most `limit` lines.
+    pub fn get_lines(&self, target: &Target, offset: usize, limit: usize) -> Vec<DashboardLine> {
+        let tasks = self.tasks.read().unwrap();
+
+        let Some(task) = tasks.iter().find(|task| &task.target == target) else {
+            return vec![];
+        };
+
+        let end = task.lines.len().saturating_sub(offset);
+        let start = end.saturating_sub(limit);
+
+        task.lines[start..end].to_vec()
+    }
+
+    /// Return a summary of all tasks, in the order they were added,
+    /// optionally filtered to only those that have failed.
+    pub fn list_tasks(&self, only_failed: bool) -> Vec<DashboardTaskSummary> {
+        self.tasks
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|task| !only_failed || task.state == DashboardTaskState::Failed)
+            .map(|task| DashboardTaskSummary {
+                attempt: task.attempt,
+                elapsed: task.get_elapsed(),
+                line_count: task.lines.len(),
+                persistent: task.persistent,
+                state: task.state,
+                target: task.target.clone(),
+            })
+            .collect()
+    }
+
+    /// Return the signal that interrupts the running process of a
+    /// persistent task, so that it can be restarted.
+    pub fn get_restart_signal(&self, target: &Target) -> Option<Arc<Notify>> {
+        self.tasks
+            .read()
+            .unwrap()
+            .iter()
+            .find(|task| &task.target == target && task.persistent)
+            .map(|task| Arc::clone(&task.restart))
+    }
+
+    /// Request a running persistent task to be restarted.
+    /// Returns false if the task cannot be restarted.
+    pub fn request_restart(&self, target: &Target) -> bool {
+        let mut requested = false;
+
+        self.update_task(target, |task| {
+            if task.persistent && task.state == DashboardTaskState::Running && !task.restarting {
+                task.restarting = true;
+                task.restart.notify_one();
+                requested = true;
+            }
+        });
+
+        requested
+    }
+
+    /// Return true if a restart was requested for the task. The request
+    /// is reset once the task has started again.
+    pub fn is_restarting(&self, target: &Target) -> bool {
+        self.tasks
+            .read()
+            .unwrap()
+            .iter()
+            .any(|task| &task.target == target && task.restarting)
+    }
+
+    /// Mark the pipeline as completed.
+    pub fn complete(&self) {
+        self.completed.store(true, Ordering::Release);
+    }
+
+    pub fn is_completed(&self) -> bool {
+        self.completed.load(Ordering::Acquire)
+    }
+
+    /// Return true if the dashboard should exit on its own,
+    /// as the pipeline has completed and it isn't kept open.
+    pub fn should_auto_exit(&self) -> bool {
+        self.is_completed() && !self.keep_open
+    }
+
+    /// Mark the dashboard as exited, either by the user or automatically.
+    pub fn exit(&self) {
+        self.exited.store(true, Ordering::Release);
+        self.exit_signal.notify_waiters();
+    }
+
+    pub fn is_exited(&self) -> bool {
+        self.exited.load(Ordering::Acquire)
+    }
+
+    /// Wait until the dashboard has exited.
+    pub async fn wait_for_exit(&self) {
+        wait_for_signal(&self.exit_signal, || self.is_exited()).await;
+    }
+
+    /// Mark the dashboard as closed, once the view has stopped
+    /// rendering and the terminal has been restored.
+    pub fn close(&self) {
+        self.closed.store(true, Ordering::Release);
+        self.close_signal.notify_waiters();
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Acquire)
+    }
+
+    /// Wait until the dashboard has closed, so that the terminal can be written to.
+    pub async fn wait_for_close(&self) {
+        wait_for_signal(&self.close_signal, || self.is_closed()).await;
+    }
+
+    fn update_task(&self, target: &Target, op: impl FnOnce(&mut DashboardTask)) {
+        let mut tasks = self.tasks.write().unwrap();
+
+        if let Some(task) = tasks.iter_mut().find(|task| &task.target == target) {
+            op(task);
+        } else {
+            let mut task = DashboardTask::new(target.to_owned(), false);
+            op(&mut task);
+            tasks.push(task);
+        }
+    }
+}
+
+// Register interest in the signal before checking the flag,
+// so that a notification in between the two isn't missed
+async fn wait_for_signal(signal: &Notify, is_set: impl Fn() -> bool) {
+    let mut notified = std::pin::pin!(signal.notified());
+    notified.as_mut().enable();
+
+    if !is_set() {
+        notified.await;
+    }
+}
+
+/// Writes streamed output of a process into a task's pane.
+#[derive(Clone, Debug)]
+pub struct TaskPane {
+    dashboard: Arc<TaskDashboard>,
+    target: Target,
+}
+
+impl TaskPane {
+    pub fn write_line(&self, line: impl AsRef<str>, stderr: bool) {
+        self.dashboard.write_line(&self.target, line, stderr);
+    }
+}
+
+/// Remove ANSI escape sequences, as they can't be rendered within a pane.
+pub fn strip_ansi(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if ch != '\u{1b}' {
+            result.push(ch);
+            continue;
+        }
+
+        // CSI sequences end with a letter, while other
+        // sequences only consume the following character
+        if chars.next_if_eq(&'[').is_some() {
+            for next in chars.by_ref() {
+                if next.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            chars.next();
+        }
+    }
+
+    result
+}
@@ -0,0 +1,264 @@
+use crate::dashboard::{DashboardTaskState, DashboardTaskSummary, TaskDashboard};
+use iocraft::prelude::*;
+use moon_time as time;
+use std::sync::Arc;
+use std::time::Duration;
+
+const PAGE_SIZE: usize = 10;
+const TICK_INTERVAL: Duration = Duration::from_millis(100);
+
+fn get_state_icon(state: DashboardTaskState) -> (&'static str, Color) {
+    match state {
+        DashboardTaskState::Cached => ("✔", Color::Blue),
+        DashboardTaskState::Failed => ("✖", Color::Red),
+        DashboardTaskState::Passed => ("✔", Color::Green),
+        DashboardTaskState::Pending => ("○", Color::DarkGrey),
+        DashboardTaskState::Running => ("●", Color::Yellow),
+        DashboardTaskState::Skipped => ("-", Color::DarkGrey),
+    }
+}
+
+fn format_task_row(task: &DashboardTaskSummary, width: usize) -> String {
+    let (icon, _) = get_state_icon(task.state);
+    let mut comment = task.elapsed.and_then(time::elapsed_opt).unwrap_or_default();
+
+    if task.attempt > 1 && task.state == DashboardTaskState::Running {
+        comment = format!("#{} {comment}", task.attempt);
+    }
+
+    let label_width = width.saturating_sub(comment.chars().count() + 3);
+    let label = task.target.as_str();
+    let label = if label.chars().count() > label_width {
+        let mut truncated = label
+            .chars()
+            .take(label_width.saturating_sub(1))
+            .collect::<String>();
+        truncated.push('…');
+        truncated
+    } else {
+        label.to_owned()
+    };
+
+    format!("{icon} {label:<label_width$} {comment}")
+}
+
+fn format_footer(tasks: &[DashboardTaskSummary], completed: bool, only_failed: bool) -> String {
+    let count = |state: DashboardTaskState| tasks.iter().filter(|task| task.state == state).count();
+
+    let mut parts = vec![
+        format!("{} running", count(DashboardTaskState::Running)),
+        format!(
+            "{} passed",
+            count(DashboardTaskState::Passed) + count(DashboardTaskState::Cached)
+        ),
+        format!("{} failed", count(DashboardTaskState::Failed)),
+        format!("{} pending", count(DashboardTaskState::Pending)),
+    ];
+
+    if completed {
+        parts.push("completed".into());
+    }
+
+    format!(
+        "{} │ ↑↓ select · pgup/pgdn scroll · f {} · r restart · q quit",
+        parts.join(", "),
+        if only_failed {
+            "show all"
+        } else {
+            "only failed"
+        }
+    )
+}
+
+#[derive(Default, Props)]
+pub struct TaskDashboardViewProps {
+    pub dashboard: Option<Arc<TaskDashboard>>,
+}
+
+/// A full-screen view of all tasks in the pipeline, with a task list on the
+/// left, and a scrollable output pane for the selected task on the right.
+#[component]
+pub fn TaskDashboardView(
+    props: &TaskDashboardViewProps,
+    mut hooks: Hooks,
+) -> impl Into<AnyElement<'static>> {
+    let (width, height) = hooks.use_terminal_size();
+    let mut system = hooks.use_context_mut::<SystemContext>();
+    let mut tick = hooks.use_state(|| 0_u64);
+    let mut selected = hooks.use_state(|| 0_usize);
+    let mut scroll = hooks.use_state(|| 0_usize);
+    let mut only_failed = hooks.use_state(|| false);
+    let mut should_exit = hooks.use_state(|| false);
+    let dashboard = props.dashboard.clone().unwrap_or_default();
+
+    // Output is written from other threads, so re-render on an interval
+    hooks.use_future(async move {
+        loop {
+            tokio::time::sleep(TICK_INTERVAL).await;
+            tick += 1;
+        }
+    });
+
+    hooks.use_terminal_events({
+        let dashboard = Arc::clone(&dashboard);
+
+        move |event| {
+            let TerminalEvent::Key(KeyEvent {
+                code,
+                modifiers,
+                kind,
+                ..
+            }) = event
+            else {
+                return;
+            };
+
+            if kind == KeyEventKind::Release {
+                return;
+            }
+
+            match code {
+                KeyCode::Char('q') | KeyCode::Esc => {
+                    should_exit.set(true);
+                }
+                KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => {
+                    should_exit.set(true);
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    selected.set(selected.get().saturating_sub(1));
+                    scroll.set(0);
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    selected.set(selected.get() + 1);
+                    scroll.set(0);
+                }
+                KeyCode::PageUp => {
+                    scroll.set(scroll.get() + PAGE_SIZE);
+                }
+                KeyCode::PageDown => {
+                    scroll.set(scroll.get().saturating_sub(PAGE_SIZE));
+                }
+                KeyCode::End => {
+                    scroll.set(0);
+                }
+                KeyCode::Char('f') => {
+                    only_failed.set(!only_failed.get());
+                    selected.set(0);
+                    scroll.set(0);
+                }
+                KeyCode::Char('r') => {
+                    let tasks = dashboard.list_tasks(only_failed.get());
+
+                    if let Some(task) = tasks.get(selected.get()) {
+                        dashboard.request_restart(&task.target);
+                    }
+                }
+                _ => {}
+            };
+        }
+    });
+
+    if should_exit.get() || dashboard.should_auto_exit() {
+        dashboard.exit();
+        system.exit();
+    }
+
+    let tasks = dashboard.list_tasks(only_failed.get());
+
+    if !tasks.is_empty() && selected.get() >= tasks.len() {
+        selected.set(tasks.len() - 1);
+    }
+
+    let list_width = (width as usize / 3).clamp(30, 60);
+    let body_height = (height as usize).saturating_sub(3);
+    let visible_rows = body_height.saturating_sub(1);
+    let pane_rows = body_height.saturating_sub(1);
+    let current = tasks.get(selected.get().min(tasks.len().saturating_sub(1)));
+
+    // Keep the selected task within the visible rows
+    let first_row = selected
+        .get()
+        .saturating_sub(visible_rows.saturating_sub(1));
+
+    let max_scroll = current
+        .map(|task| task.line_count.saturating_sub(pane_rows))
+        .unwrap_or_default();
+
+    if scroll.get() > max_scroll {
+        scroll.set(max_scroll);
+    }
+
+    let lines = current
+        .map(|task| dashboard.get_lines(&task.target, scroll.get().min(max_scroll), pane_rows))
+        .unwrap_or_default();
+
+    element! {
+        View(
+            flex_direction: FlexDirection::Column,
+            width: width,
+            height: height,
+        ) {
+            View(flex_direction: FlexDirection::Row, flex_grow: 1.0) {
+                View(
+                    flex_direction: FlexDirection::Column,
+                    width: list_width as u32 + 2,
+                    border_style: BorderStyle::Round,
+                    border_color: Color::DarkGrey,
+                ) {
+                    Text(
+                        content: if only_failed.get() { "Failed tasks" } else { "Tasks" },
+                        weight: Weight::Bold,
+                    )
+                    #(tasks.iter().enumerate().skip(first_row).take(visible_rows).map(|(index, task)| {
+                        let (_, color) = get_state_icon(task.state);
+                        let is_selected = index == selected.get();
+
+                        element! {
+                            View(
+                                background_color: if is_selected { Some(Color::DarkGrey) } else { None },
+                            ) {
+                                Text(
+                                    content: format_task_row(task, list_width),
+                                    color: color,
+                                    weight: if is_selected { Weight::Bold } else { Weight::Normal },
+                                    wrap: TextWrap::NoWrap,
+                                )
+                            }
+                        }
+                    }))
+                }
+                View(
+                    flex_direction: FlexDirection::Column,
+                    flex_grow: 1.0,
+                    border_style: BorderStyle::Round,
+                    border_color: Color::DarkGrey,
+                ) {
+                    Text(
+                        content: current.map(|task| {
+                            if task.persistent {
+                                format!("{} (persistent)", task.target)
+                            } else {
+                                task.target.to_string()
+                            }
+                        }).unwrap_or_default(),
+                        weight: Weight::Bold,
+                    )
+                    #(lines.into_iter().map(|line| {
+                        element! {
+                            Text(
+                                content: line.content,
+                                color: if line.stderr { Some(Color::Red) } else { None },
+                                wrap: TextWrap::NoWrap,
+                            )
+                        }
+                    }))
+                }
+            }
+            Text(
+                content: format_footer(&tasks, dashboard.is_completed(), only_failed.get()),
+                color: Color::DarkGrey,
+                wrap: TextWrap::NoWrap,
+            )
+        }
+    }
+}
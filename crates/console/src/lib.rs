@@ -1,6 +1,10 @@
+mod dashboard;
+mod dashboard_view;
 mod reporter;
 mod theme;
 
+pub use dashboard::*;
+pub use dashboard_view::*;
 pub use reporter::*;
 pub use starbase_console::ConsoleError;
 pub use starbase_console::ui;
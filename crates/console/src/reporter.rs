@@ -1,5 +1,7 @@
+use crate::dashboard::TaskDashboard;
 use moon_action::{
     Action, ActionNode, ActionPipelineStatus, ActionStatus, Operation, OperationList,
+    PipelineAnalysis, TestReport,
 };
 use moon_common::{color, is_test_env};
 use moon_config::TaskOutputStyle;
@@ -8,10 +10,12 @@ use moon_time as time;
 use starbase_console::{ConsoleStream, ConsoleStreamType, Reporter};
 use starbase_styles::color::owo::{OwoColorize, XtermColors};
 use starbase_styles::color::{Color, OwoStyle, no_color};
+use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 
 #[derive(Debug, Default)]
 pub struct PipelineReportItem {
+    pub analysis: Option<PipelineAnalysis>,
     pub duration: Option<Duration>,
     pub summarize: bool,
     pub status: ActionPipelineStatus,
@@ -25,6 +29,7 @@ pub struct TaskReportItem {
     pub output_prefix: Option<String>,
     pub output_streamed: bool,
     pub output_style: Option<TaskOutputStyle>,
+    pub test_report: Option<TestReport>,
 }
 
 const STEP_CHAR: &str = "▪";
@@ -55,6 +60,7 @@ fn bold(message: &str) -> String {
 
 #[derive(Debug)]
 pub struct MoonReporter {
+    dashboard: OnceLock<Arc<TaskDashboard>>,
     err: ConsoleStream,
     out: ConsoleStream,
 }
@@ -62,6 +68,7 @@ pub struct MoonReporter {
 impl Default for MoonReporter {
     fn default() -> Self {
         Self {
+            dashboard: OnceLock::new(),
             err: ConsoleStream::empty(ConsoleStreamType::Stderr),
             out: ConsoleStream::empty(ConsoleStreamType::Stdout),
         }
@@ -76,6 +83,16 @@ impl Reporter for MoonReporter {
 }
 
 impl MoonReporter {
+    /// Route all task events to the dashboard, instead of printing checkpoints.
+    /// The dashboard owns the terminal, so it can only be enabled once.
+    pub fn enable_dashboard(&self, dashboard: Arc<TaskDashboard>) {
+        let _ = self.dashboard.set(dashboard);
+    }
+
+    pub fn get_dashboard(&self) -> Option<&Arc<TaskDashboard>> {
+        self.dashboard.get()
+    }
+
     fn get_status_meta_comment(
         &self,
         status: ActionStatus,
@@ -93,6 +110,14 @@ impl MoonReporter {
         hash[0..8].to_owned()
     }
 
+    fn get_test_report_comment(&self, report: &TestReport) -> String {
+        if report.has_failed() {
+            format!("{}/{} tests failed", report.failed, report.total())
+        } else {
+            format!("{} tests passed", report.passed)
+        }
+    }
+
     pub fn format_checkpoint<M: AsRef<str>, C: AsRef<[String]>>(
         &self,
         checkpoint: Checkpoint,
@@ -155,7 +180,7 @@ impl MoonReporter {
         message: M,
         comments: C,
     ) -> miette::Result<()> {
-        if !self.out.is_quiet() {
+        if !self.out.is_quiet() && self.dashboard.get().is_none() {
             self.out
                 .write_line(self.format_checkpoint(checkpoint, message, comments))?;
         }
@@ -226,6 +251,12 @@ impl MoonReporter {
             }
         }
 
+        if let Some(report) = &item.test_report {
+            if !report.is_empty() {
+                comments.push(self.get_test_report_comment(report));
+            }
+        }
+
         // Do not include the hash while testing, as the hash
         // constantly changes and breaks our local snapshots
         if !is_test_env() {
@@ -307,6 +338,32 @@ impl MoonReporter {
         Ok(())
     }
 
+    pub fn print_test_failures(&self, report: &TestReport) -> miette::Result<()> {
+        for test in report.get_failed_tests() {
+            let mut line = format!("{} {}", color::failure("✖"), test.get_label());
+
+            if let Some(duration) = test.duration.and_then(time::elapsed_opt) {
+                line.push(' ');
+                line.push_str(&self.format_comments([duration]));
+            }
+
+            self.out.write_line(line)?;
+
+            // Only show the first line, as the full message and
+            // stack trace is available in the task's output
+            if let Some(message) = test
+                .message
+                .as_ref()
+                .and_then(|message| message.lines().next())
+            {
+                self.out
+                    .write_line(format!("  {}", color::muted_light(message)))?;
+            }
+        }
+
+        Ok(())
+    }
+
     fn print_pipeline_failures(&self, actions: &[Action]) -> miette::Result<()> {
         for action in actions {
             if !action.has_failed() {
@@ -327,6 +384,12 @@ impl MoonReporter {
                 }
             }
 
+            if let Some(report) = &action.test_report {
+                if report.has_failed() {
+                    self.print_test_failures(report)?;
+                }
+            }
+
             // Force flush so the output is rendered in the correct order
             self.out.flush()?;
             self.err.flush()?;
@@ -346,6 +409,7 @@ impl MoonReporter {
         let mut cached_count = 0;
         let mut failed_count = 0;
         let mut invalid_count = 0;
+        let mut quarantined_count = 0;
         let mut skipped_count = 0;
 
         for action in actions {
@@ -353,6 +417,11 @@ impl MoonReporter {
                 continue;
             }
 
+            if action.quarantined {
+                quarantined_count += 1;
+                continue;
+            }
+
             match action.status {
                 ActionStatus::Cached | ActionStatus::CachedFromRemote => {
                     cached_count += 1;
@@ -396,6 +465,10 @@ impl MoonReporter {
             counts_message.push(color::invalid(format!("{invalid_count} invalid")));
         }
 
+        if quarantined_count > 0 {
+            counts_message.push(color::invalid(format!("{quarantined_count} quarantined")));
+        }
+
         if skipped_count > 0 {
             counts_message.push(color::muted_light(format!("{skipped_count} skipped")));
         }
@@ -431,9 +504,56 @@ impl MoonReporter {
         Ok(())
     }
 
+    fn print_pipeline_analysis(&self, analysis: &PipelineAnalysis) -> miette::Result<()> {
+        for item in &analysis.critical_path {
+            self.out.write_line(format!(
+                "{} {}",
+                item.label,
+                self.format_comments([time::elapsed(item.duration)]),
+            ))?;
+        }
+
+        self.out.write_newline()?;
+
+        self.print_entry(
+            "     Length",
+            time::elapsed(analysis.critical_path_duration),
+        )?;
+        self.print_entry(
+            "    Minimum",
+            format!(
+                "{} {}",
+                time::elapsed(analysis.minimum_duration),
+                color::muted(format!("(at concurrency of {})", analysis.concurrency)),
+            ),
+        )?;
+        self.print_entry("Parallelism", format!("{:.1}x", analysis.parallelism))?;
+
+        if !analysis.speedups.is_empty() {
+            self.out.write_newline()?;
+            self.out.write_line(color::muted_light(
+                "Speeding up these actions would shorten the pipeline the most:",
+            ))?;
+
+            for speedup in &analysis.speedups {
+                self.out.write_line(format!(
+                    "{} {}",
+                    speedup.label,
+                    self.format_comments([format!(
+                        "saves up to {}",
+                        time::elapsed(speedup.savings)
+                    )]),
+                ))?;
+            }
+        }
+
+        Ok(())
+    }
+
     fn print_pipeline_summary(&self, actions: &[Action]) -> miette::Result<()> {
         for action in actions {
             let status = match action.status {
+                _ if action.quarantined => color::invalid("warn"),
                 ActionStatus::Passed => color::success("pass"),
                 ActionStatus::Cached | ActionStatus::CachedFromRemote => color::label("pass"),
                 ActionStatus::Aborted | ActionStatus::Failed | ActionStatus::TimedOut => {
@@ -446,7 +566,9 @@ impl MoonReporter {
 
             let mut comments: Vec<String> = vec![];
 
-            if let Some(status_comment) = self.get_status_meta_comment(action.status, || None) {
+            if let Some(status_comment) = self.get_status_meta_comment(action.status, || {
+                action.quarantined.then(|| "quarantined".into())
+            }) {
                 comments.push(status_comment);
             }
 
@@ -501,9 +623,20 @@ impl MoonReporter {
             return Ok(());
         }
 
+        let aborted = matches!(item.status, ActionPipelineStatus::Aborted);
+
+        // Output was only rendered within the dashboard (which has closed by now),
+        // so always review failures, even if the pipeline was aborted
+        let review = self.dashboard.get().is_some() || (item.summarize && !aborted);
+
+        if review && actions.iter().any(|action| action.has_failed()) {
+            self.print_header("Review")?;
+            self.print_pipeline_failures(actions)?;
+        }
+
         // A task failed, so instead of showing the stats,
         // we'll render the error that was bubbled up
-        if matches!(item.status, ActionPipelineStatus::Aborted) {
+        if aborted {
             return Ok(());
         }
 
@@ -516,15 +649,16 @@ impl MoonReporter {
             return Ok(());
         }
 
-        // Otherwise, show all the information we can.
-        if actions.iter().any(|action| action.has_failed()) {
-            self.print_header("Review")?;
-            self.print_pipeline_failures(actions)?;
-        }
-
         self.print_header("Summary")?;
         self.print_pipeline_summary(actions)?;
 
+        if let Some(analysis) = &item.analysis {
+            if !analysis.critical_path.is_empty() {
+                self.print_header("Critical path")?;
+                self.print_pipeline_analysis(analysis)?;
+            }
+        }
+
         self.print_header("Stats")?;
         self.print_pipeline_stats(actions, item)?;
 
@@ -540,6 +674,12 @@ impl MoonReporter {
         attempt: &Operation,
         item: &TaskReportItem,
     ) -> miette::Result<()> {
+        if let Some(dashboard) = self.dashboard.get() {
+            dashboard.start_task(target, item.attempt_current);
+
+            return Ok(());
+        }
+
         self.print_task_checkpoint(target, attempt, item)?;
 
         Ok(())
@@ -559,7 +699,7 @@ impl MoonReporter {
     // When an attempt has finished, print the output if captured
     pub fn on_task_finished(
         &self,
-        _target: &Target,
+        target: &Target,
         attempt: &Operation,
         item: &TaskReportItem,
         _error: Option<&miette::Report>,
@@ -567,7 +707,11 @@ impl MoonReporter {
         // Task output was captured, so there was no output
         // sent to the console, so manually print the logs we have!
         if !item.output_streamed && attempt.has_output() {
-            self.print_operation_output(attempt, item)?;
+            if let Some(dashboard) = self.dashboard.get() {
+                dashboard.write_operation_output(target, attempt);
+            } else {
+                self.print_operation_output(attempt, item)?;
+            }
         }
 
         Ok(())
@@ -581,6 +725,24 @@ impl MoonReporter {
         item: &TaskReportItem,
         _error: Option<&miette::Report>,
     ) -> miette::Result<()> {
+        if let Some(dashboard) = self.dashboard.get() {
+            if let Some(operation) = operations.get_last_process() {
+                if operation.is_cached() && operation.has_output() {
+                    dashboard.write_operation_output(target, operation);
+                }
+            }
+
+            if let Some(report) = &item.test_report {
+                if report.has_failed() {
+                    dashboard.write_test_failures(target, report);
+                }
+            }
+
+            dashboard.complete_task(target, operations);
+
+            return Ok(());
+        }
+
         if let Some(operation) = operations.get_last_process() {
             // If cached, the finished event above is not fired,
             // so handle printing the captured logs here!
@@ -588,6 +750,15 @@ impl MoonReporter {
                 self.print_operation_output(operation, item)?;
             }
 
+            // Summarize failed tests so they don't need to be
+            // searched for within the output
+            if let Some(report) = &item.test_report {
+                if report.has_failed() && !matches!(item.output_style, Some(TaskOutputStyle::None))
+                {
+                    self.print_test_failures(report)?;
+                }
+            }
+
             // Then print the success checkpoint. The success
             // checkpoint should always appear after the output,
             // and "contain" it within the start checkpoint!
@@ -0,0 +1,202 @@
+use moon_action::{ActionStatus, Operation, OperationList};
+use moon_console::*;
+use moon_target::Target;
+use std::sync::Arc;
+
+fn create_target(id: &str) -> Target {
+    Target::parse(id).unwrap()
+}
+
+mod task_dashboard {
+    use super::*;
+
+    #[test]
+    fn adds_tasks_once_in_order() {
+        let dashboard = TaskDashboard::default();
+        dashboard.add_task(&create_target("b:build"), false);
+        dashboard.add_task(&create_target("a:build"), true);
+        dashboard.add_task(&create_target("b:build"), false);
+
+        let tasks = dashboard.list_tasks(false);
+
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].target.as_str(), "b:build");
+        assert_eq!(tasks[0].state, DashboardTaskState::Pending);
+        assert!(tasks[1].persistent);
+    }
+
+    #[test]
+    fn tracks_state_through_lifecycle() {
+        let dashboard = TaskDashboard::default();
+        let target = create_target("app:build");
+
+        dashboard.add_task(&target, false);
+        dashboard.start_task(&target, 1);
+
+        assert_eq!(
+            dashboard.list_tasks(false)[0].state,
+            DashboardTaskState::Running
+        );
+
+        let mut operations = OperationList::default();
+        operations.push(Operation::new_finished(
+            Operation::task_execution("build").meta,
+            ActionStatus::Failed,
+        ));
+
+        dashboard.complete_task(&target, &operations);
+
+        let tasks = dashboard.list_tasks(false);
+
+        assert_eq!(tasks[0].state, DashboardTaskState::Failed);
+        assert!(tasks[0].elapsed.is_some());
+    }
+
+    #[test]
+    fn filters_to_failed_tasks() {
+        let dashboard = TaskDashboard::default();
+        let passed = create_target("app:build");
+        let failed = create_target("app:test");
+
+        for (target, status) in [
+            (&passed, ActionStatus::Passed),
+            (&failed, ActionStatus::Failed),
+        ] {
+            let mut operations = OperationList::default();
+            operations.push(Operation::new_finished(
+                Operation::task_execution("cmd").meta,
+                status,
+            ));
+
+            dashboard.start_task(target, 1);
+            dashboard.complete_task(target, &operations);
+        }
+
+        let tasks = dashboard.list_tasks(true);
+
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].target, failed);
+    }
+
+    #[test]
+    fn writes_lines_to_panes() {
+        let dashboard = Arc::new(TaskDashboard::default());
+        let target = create_target("app:build");
+        let pane = dashboard.get_pane(&target);
+
+        pane.write_line("one", false);
+        pane.write_line("\u{1b}[31mtwo\u{1b}[0m\nthree", true);
+
+        assert_eq!(
+            dashboard.get_lines(&target, 0, 10),
+            vec![
+                DashboardLine {
+                    content: "one".into(),
+                    stderr: false
+                },
+                DashboardLine {
+                    content: "two".into(),
+                    stderr: true
+                },
+                DashboardLine {
+                    content: "three".into(),
+                    stderr: true
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn scrolls_lines_from_the_end() {
+        let dashboard = TaskDashboard::default();
+        let target = create_target("app:build");
+
+        for i in 0..10 {
+            dashboard.write_line(&target, i.to_string(), false);
+        }
+
+        let lines = dashboard
+            .get_lines(&target, 2, 3)
+            .into_iter()
+            .map(|line| line.content)
+            .collect::<Vec<_>>();
+
+        assert_eq!(lines, vec!["5", "6", "7"]);
+        assert!(dashboard.get_lines(&target, 20, 3).is_empty());
+    }
+
+    #[test]
+    fn caps_pane_lines() {
+        let dashboard = TaskDashboard::default();
+        let target = create_target("app:build");
+
+        for i in 0..(MAX_PANE_LINES + 10) {
+            dashboard.write_line(&target, i.to_string(), false);
+        }
+
+        let tasks = dashboard.list_tasks(false);
+
+        assert_eq!(tasks[0].line_count, MAX_PANE_LINES);
+        assert_eq!(dashboard.get_lines(&target, 0, 1)[0].content, "5009");
+    }
+
+    #[test]
+    fn only_restarts_running_persistent_tasks() {
+        let dashboard = TaskDashboard::default();
+        let server = create_target("app:dev");
+        let build = create_target("app:build");
+
+        dashboard.add_task(&server, true);
+        dashboard.add_task(&build, false);
+
+        assert!(!dashboard.request_restart(&server));
+
+        dashboard.start_task(&server, 1);
+        dashboard.start_task(&build, 1);
+
+        assert!(dashboard.get_restart_signal(&server).is_some());
+        assert!(dashboard.get_restart_signal(&build).is_none());
+        assert!(!dashboard.request_restart(&build));
+        assert!(dashboard.request_restart(&server));
+        assert!(dashboard.is_restarting(&server));
+
+        // Starting again resets the request
+        dashboard.start_task(&server, 1);
+
+        assert!(!dashboard.is_restarting(&server));
+        assert_eq!(
+            dashboard.get_lines(&server, 0, 1)[0].content,
+            "── restarted ──"
+        );
+    }
+
+    #[test]
+    fn auto_exits_once_completed() {
+        let dashboard = TaskDashboard::new(false);
+
+        assert!(!dashboard.should_auto_exit());
+
+        dashboard.complete();
+
+        assert!(dashboard.should_auto_exit());
+    }
+
+    #[test]
+    fn doesnt_auto_exit_when_kept_open() {
+        let dashboard = TaskDashboard::new(true);
+        dashboard.complete();
+
+        assert!(!dashboard.should_auto_exit());
+    }
+}
+
+mod strip_ansi {
+    use super::*;
+
+    #[test]
+    fn removes_escape_sequences() {
+        assert_eq!(strip_ansi("plain"), "plain");
+        assert_eq!(strip_ansi("\u{1b}[1;32mbold green\u{1b}[0m"), "bold green");
+        assert_eq!(strip_ansi("a\u{1b}[2Kb"), "ab");
+    }
+}
@@ -14,6 +14,7 @@ pub struct EnvSubstitutor<'bag> {
 
     global_vars: Option<&'bag GlobalEnvBag>,
     local_vars: Option<&'bag FxHashMap<String, String>>,
+    secret_vars: Option<&'bag FxHashSet<String>>,
 }
 
 impl<'bag> EnvSubstitutor<'bag> {
@@ -36,6 +37,13 @@ impl<'bag> EnvSubstitutor<'bag> {
         self
     }
 
+    /// Variables that contain secrets, and should never be substituted,
+    /// so that their values can be resolved at runtime instead.
+    pub fn with_secret_vars(mut self, vars: &'bag FxHashSet<String>) -> Self {
+        self.secret_vars = Some(vars);
+        self
+    }
+
     pub fn substitute(&mut self, value: impl AsRef<str>) -> String {
         self.do_substitute(value, None)
     }
@@ -68,6 +76,7 @@ impl<'bag> EnvSubstitutor<'bag> {
 
         let global_vars = self.global_vars;
         let local_vars = self.local_vars;
+        let secret_vars = self.secret_vars;
         let mut substituted = FxHashSet::default();
 
         let result = ENV_VAR_SUBSTITUTE.replace_all(value, |caps: &regex::Captures| {
@@ -84,6 +93,11 @@ impl<'bag> EnvSubstitutor<'bag> {
                 .or_else(|| caps.name("flag2"))
                 .map(|cap| cap.as_str());
 
+            // Secrets are kept as-is, without the flag
+            if secret_vars.is_some_and(|bag| bag.contains(name)) {
+                return format!("${name}");
+            }
+
             // If the variable is referencing itself, don't pull
             // from the local map, and instead only pull from the
             // system environment. Otherwise we hit recursion!
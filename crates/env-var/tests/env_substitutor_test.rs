@@ -1,5 +1,5 @@
 use moon_env_var::*;
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 
 mod env_substitutor {
     use super::*;
@@ -68,4 +68,25 @@ mod env_substitutor {
 
         assert_eq!(sub.substitute("$SOURCE"), "$SOURCE");
     }
+
+    #[test]
+    fn doesnt_substitute_secrets() {
+        let global = GlobalEnvBag::default();
+        global.set("TOKEN", "abc123");
+
+        let mut local = FxHashMap::default();
+        local.insert("KEY".to_owned(), "value".to_owned());
+
+        let secrets = FxHashSet::from_iter(["TOKEN".to_owned()]);
+
+        let mut sub = EnvSubstitutor::default()
+            .with_local_vars(&local)
+            .with_global_vars(&global)
+            .with_secret_vars(&secrets);
+
+        assert_eq!(sub.substitute("$KEY $TOKEN"), "value $TOKEN");
+        assert_eq!(sub.substitute("${TOKEN}"), "$TOKEN");
+        assert_eq!(sub.substitute("${TOKEN?}"), "$TOKEN");
+        assert!(!sub.replaced.contains("TOKEN"));
+    }
 }
@@ -23,6 +23,32 @@ impl ExtensionPlugin {
 
         Ok(())
     }
+
+    pub async fn supports_secrets(&self) -> bool {
+        self.plugin.has_func("resolve_secret").await
+    }
+
+    #[instrument(skip(self, context))]
+    pub async fn resolve_secret(
+        &self,
+        key: &str,
+        target: &str,
+        context: MoonContext,
+    ) -> miette::Result<Option<String>> {
+        let output: ResolveSecretOutput = self
+            .plugin
+            .call_func_with(
+                "resolve_secret",
+                ResolveSecretInput {
+                    context,
+                    key: key.to_owned(),
+                    target: target.to_owned(),
+                },
+            )
+            .await?;
+
+        Ok(output.value)
+    }
 }
 
 #[async_trait]
@@ -0,0 +1,93 @@
+use crate::graph_traits::GraphData;
+use petgraph::graph::NodeIndex;
+use serde::{Deserialize, Serialize};
+use starbase_utils::json::{self, JsonValue};
+use std::collections::BTreeSet;
+use std::fmt::Display;
+
+/// The structure of a graph at a point in time, keyed by node.
+#[derive(Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct GraphSnapshot {
+    pub edges: BTreeSet<(String, String)>,
+    pub nodes: BTreeSet<String>,
+}
+
+impl GraphSnapshot {
+    pub fn from_graph<N, E, K: Display>(graph: &impl GraphData<N, E, K>) -> Self {
+        let inner = graph.get_graph();
+        let key = |index: NodeIndex| graph.get_node_key(&inner[index]).to_string();
+
+        Self {
+            edges: inner
+                .raw_edges()
+                .iter()
+                .map(|edge| (key(edge.source()), key(edge.target())))
+                .collect(),
+            nodes: inner.node_indices().map(key).collect(),
+        }
+    }
+
+    /// Create a snapshot from a graph that was serialized with `GraphToJson`,
+    /// using the provided field of each node as its key.
+    pub fn from_json(content: &str, key_field: &str) -> miette::Result<Self> {
+        let data: JsonValue = json::parse(content)?;
+        let graph = &data["graph"];
+
+        let keys = graph["nodes"]
+            .as_array()
+            .map(|nodes| {
+                nodes
+                    .iter()
+                    .map(|node| node[key_field].as_str().unwrap_or_default().to_owned())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        let edges = graph["edges"]
+            .as_array()
+            .map(|edges| {
+                edges
+                    .iter()
+                    .filter_map(|edge| {
+                        let source = keys.get(edge[0].as_u64()? as usize)?;
+                        let target = keys.get(edge[1].as_u64()? as usize)?;
+
+                        Some((source.to_owned(), target.to_owned()))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(Self {
+            edges,
+            nodes: keys.into_iter().filter(|key| !key.is_empty()).collect(),
+        })
+    }
+}
+
+/// Nodes and edges that were added or removed between two snapshots of a graph.
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct GraphDiff {
+    pub added_edges: Vec<(String, String)>,
+    pub added_nodes: Vec<String>,
+    pub removed_edges: Vec<(String, String)>,
+    pub removed_nodes: Vec<String>,
+}
+
+impl GraphDiff {
+    pub fn new(base: &GraphSnapshot, head: &GraphSnapshot) -> Self {
+        Self {
+            added_edges: head.edges.difference(&base.edges).cloned().collect(),
+            added_nodes: head.nodes.difference(&base.nodes).cloned().collect(),
+            removed_edges: base.edges.difference(&head.edges).cloned().collect(),
+            removed_nodes: base.nodes.difference(&head.nodes).cloned().collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added_edges.is_empty()
+            && self.added_nodes.is_empty()
+            && self.removed_edges.is_empty()
+            && self.removed_nodes.is_empty()
+    }
+}
@@ -1,10 +1,11 @@
+use crate::graph_layout::GraphLayout;
 use crate::graph_traits::*;
 use petgraph::dot::{Config, Dot};
 use petgraph::graph::DiGraph;
 use petgraph::visit::{EdgeRef, NodeRef};
 use serde::Serialize;
 use starbase_utils::json;
-use std::fmt::{Debug, Display};
+use std::fmt::{Debug, Display, Write};
 
 #[derive(Serialize)]
 pub struct GraphCache<'graph, N, E> {
@@ -54,3 +55,182 @@ pub trait GraphToJson<N: Serialize, E: Serialize, K>: GraphData<N, E, K> {
         )?)
     }
 }
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn escape_mermaid(value: &str) -> String {
+    value.replace('"', "#quot;")
+}
+
+/// Format a graph as a Mermaid flowchart.
+pub fn format_mermaid<N: Display, E: Display>(graph: &DiGraph<N, E>) -> String {
+    let mut out = String::from("flowchart TB\n");
+
+    for index in graph.node_indices() {
+        let _ = writeln!(
+            out,
+            "  n{}[\"{}\"]",
+            index.index(),
+            escape_mermaid(&graph[index].to_string())
+        );
+    }
+
+    for edge in graph.raw_edges() {
+        let label = edge.weight.to_string();
+
+        if label.is_empty() {
+            let _ = writeln!(
+                out,
+                "  n{} --> n{}",
+                edge.source().index(),
+                edge.target().index()
+            );
+        } else {
+            let _ = writeln!(
+                out,
+                "  n{} -->|\"{}\"| n{}",
+                edge.source().index(),
+                escape_mermaid(&label),
+                edge.target().index()
+            );
+        }
+    }
+
+    out
+}
+
+/// Format a graph as a GraphML document.
+pub fn format_graphml<N: Display, E: Display>(graph: &DiGraph<N, E>) -> String {
+    let mut out = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+  <key id="label" for="node" attr.name="label" attr.type="string"/>
+  <key id="edge_label" for="edge" attr.name="label" attr.type="string"/>
+  <graph id="G" edgedefault="directed">
+"#,
+    );
+
+    for index in graph.node_indices() {
+        let _ = writeln!(
+            out,
+            r#"    <node id="n{}"><data key="label">{}</data></node>"#,
+            index.index(),
+            escape_xml(&graph[index].to_string())
+        );
+    }
+
+    for (index, edge) in graph.raw_edges().iter().enumerate() {
+        let label = edge.weight.to_string();
+
+        if label.is_empty() {
+            let _ = writeln!(
+                out,
+                r#"    <edge id="e{index}" source="n{}" target="n{}"/>"#,
+                edge.source().index(),
+                edge.target().index()
+            );
+        } else {
+            let _ = writeln!(
+                out,
+                r#"    <edge id="e{index}" source="n{}" target="n{}"><data key="edge_label">{}</data></edge>"#,
+                edge.source().index(),
+                edge.target().index(),
+                escape_xml(&label)
+            );
+        }
+    }
+
+    out.push_str("  </graph>\n</graphml>\n");
+    out
+}
+
+/// Format a graph as a static SVG image, using a layered layout.
+pub fn format_svg<N: Display, E: Display>(graph: &DiGraph<N, E>) -> String {
+    let layout = GraphLayout::new(graph);
+    let mut out = String::new();
+
+    let _ = writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}" font-family="sans-serif" font-size="12">"#,
+        layout.width, layout.height
+    );
+    out.push_str(
+        r##"  <defs>
+    <marker id="arrow" viewBox="0 0 10 10" refX="10" refY="5" markerWidth="8" markerHeight="8" orient="auto-start-reverse">
+      <path d="M 0 0 L 10 5 L 0 10 z" fill="#6b7280"/>
+    </marker>
+  </defs>
+  <rect width="100%" height="100%" fill="#ffffff"/>
+"##,
+    );
+
+    for edge in &layout.edges {
+        let source = &layout.nodes[edge.source];
+        let target = &layout.nodes[edge.target];
+
+        // Edges flow downwards, unless they are within a cycle
+        let (start_y, end_y) = if target.layer > source.layer {
+            (source.y + source.height, target.y)
+        } else {
+            (source.y, target.y + target.height)
+        };
+        let middle_y = (start_y + end_y) / 2;
+
+        let _ = write!(
+            out,
+            r##"  <path d="M {x1} {start_y} C {x1} {middle_y}, {x2} {middle_y}, {x2} {end_y}" fill="none" stroke="#6b7280" marker-end="url(#arrow)">"##,
+            x1 = source.center_x(),
+            x2 = target.center_x(),
+        );
+
+        if !edge.label.is_empty() {
+            let _ = write!(out, "<title>{}</title>", escape_xml(&edge.label));
+        }
+
+        out.push_str("</path>\n");
+    }
+
+    for node in &layout.nodes {
+        let _ = writeln!(
+            out,
+            r##"  <g><rect x="{}" y="{}" width="{}" height="{}" rx="16" fill="#e5e7eb" stroke="#9ca3af"/><text x="{}" y="{}" text-anchor="middle" dominant-baseline="central" fill="#111827">{}</text></g>"##,
+            node.x,
+            node.y,
+            node.width,
+            node.height,
+            node.center_x(),
+            node.y + node.height / 2,
+            escape_xml(&node.label)
+        );
+    }
+
+    out.push_str("</svg>\n");
+    out
+}
+
+pub trait GraphToMermaid<N: Display, E: Display, K>: GraphData<N, E, K> {
+    /// Format graph as a Mermaid flowchart.
+    fn to_mermaid(&self) -> String {
+        format_mermaid(self.get_graph())
+    }
+}
+
+pub trait GraphToGraphml<N: Display, E: Display, K>: GraphData<N, E, K> {
+    /// Format graph as a GraphML document.
+    fn to_graphml(&self) -> String {
+        format_graphml(self.get_graph())
+    }
+}
+
+pub trait GraphToSvg<N: Display, E: Display, K>: GraphData<N, E, K> {
+    /// Format graph as a static SVG image.
+    fn to_svg(&self) -> String {
+        format_svg(self.get_graph())
+    }
+}
@@ -0,0 +1,208 @@
+use petgraph::Direction;
+use petgraph::graph::{DiGraph, NodeIndex};
+use std::collections::VecDeque;
+use std::fmt::Display;
+
+const CHAR_WIDTH: u32 = 7;
+const NODE_HEIGHT: u32 = 32;
+const NODE_PADDING: u32 = 16;
+const LAYER_GAP: u32 = 64;
+const NODE_GAP: u32 = 24;
+const MARGIN: u32 = 24;
+const ORDERING_PASSES: usize = 4;
+
+#[derive(Debug, PartialEq)]
+pub struct GraphLayoutNode {
+    pub height: u32,
+    pub label: String,
+    pub layer: usize,
+    pub width: u32,
+    pub x: u32,
+    pub y: u32,
+}
+
+impl GraphLayoutNode {
+    pub fn center_x(&self) -> u32 {
+        self.x + self.width / 2
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct GraphLayoutEdge {
+    pub label: String,
+    pub source: usize,
+    pub target: usize,
+}
+
+/// A layered layout of a graph, where nodes are placed in rows based on
+/// the longest path from a root node, so that edges generally flow downwards.
+#[derive(Debug, PartialEq)]
+pub struct GraphLayout {
+    pub edges: Vec<GraphLayoutEdge>,
+    pub height: u32,
+    pub nodes: Vec<GraphLayoutNode>,
+    pub width: u32,
+}
+
+impl GraphLayout {
+    pub fn new<N: Display, E: Display>(graph: &DiGraph<N, E>) -> Self {
+        let layers = assign_layers(graph);
+        let rows = order_layers(graph, &layers);
+
+        let mut nodes = graph
+            .node_indices()
+            .map(|index| {
+                let label = graph.node_weight(index).unwrap().to_string();
+
+                GraphLayoutNode {
+                    height: NODE_HEIGHT,
+                    layer: layers[index.index()],
+                    width: label.chars().count() as u32 * CHAR_WIDTH + NODE_PADDING * 2,
+                    label,
+                    x: 0,
+                    y: 0,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let row_widths = rows
+            .iter()
+            .map(|row| {
+                row.iter().map(|index| nodes[*index].width).sum::<u32>()
+                    + NODE_GAP * (row.len().saturating_sub(1) as u32)
+            })
+            .collect::<Vec<_>>();
+        let max_width = row_widths.iter().copied().max().unwrap_or_default();
+
+        // Center each row horizontally
+        for (layer, row) in rows.iter().enumerate() {
+            let mut x = MARGIN + (max_width - row_widths[layer]) / 2;
+            let y = MARGIN + layer as u32 * (NODE_HEIGHT + LAYER_GAP);
+
+            for index in row {
+                let node = &mut nodes[*index];
+                node.x = x;
+                node.y = y;
+
+                x += node.width + NODE_GAP;
+            }
+        }
+
+        let edges = graph
+            .raw_edges()
+            .iter()
+            .map(|edge| GraphLayoutEdge {
+                label: edge.weight.to_string(),
+                source: edge.source().index(),
+                target: edge.target().index(),
+            })
+            .collect();
+
+        let height = if rows.is_empty() {
+            MARGIN * 2
+        } else {
+            MARGIN * 2 + rows.len() as u32 * NODE_HEIGHT + (rows.len() as u32 - 1) * LAYER_GAP
+        };
+
+        Self {
+            edges,
+            height,
+            nodes,
+            width: MARGIN * 2 + max_width,
+        }
+    }
+}
+
+// Assign each node to a layer based on the longest path from a node without
+// incoming edges. Nodes within a cycle are placed below their resolved parents.
+fn assign_layers<N, E>(graph: &DiGraph<N, E>) -> Vec<usize> {
+    let mut layers = vec![0; graph.node_count()];
+    let mut in_degrees = graph
+        .node_indices()
+        .map(|index| graph.neighbors_directed(index, Direction::Incoming).count())
+        .collect::<Vec<_>>();
+    let mut queue = graph
+        .node_indices()
+        .filter(|index| in_degrees[index.index()] == 0)
+        .collect::<VecDeque<_>>();
+
+    while let Some(index) = queue.pop_front() {
+        for next in graph.neighbors_directed(index, Direction::Outgoing) {
+            let next_index = next.index();
+
+            layers[next_index] = layers[next_index].max(layers[index.index()] + 1);
+            in_degrees[next_index] = in_degrees[next_index].saturating_sub(1);
+
+            if in_degrees[next_index] == 0 {
+                queue.push_back(next);
+            }
+        }
+    }
+
+    layers
+}
+
+// Order the nodes within each layer by the average position of their
+// neighbors in the adjacent layer, to reduce the amount of edge crossings.
+fn order_layers<N, E>(graph: &DiGraph<N, E>, layers: &[usize]) -> Vec<Vec<usize>> {
+    let layer_count = layers.iter().max().map(|max| max + 1).unwrap_or_default();
+    let mut rows = vec![vec![]; layer_count];
+
+    for (index, layer) in layers.iter().enumerate() {
+        rows[*layer].push(index);
+    }
+
+    let mut positions = vec![0.0_f64; graph.node_count()];
+
+    let update_positions = |rows: &[Vec<usize>], positions: &mut [f64]| {
+        for row in rows {
+            for (position, index) in row.iter().enumerate() {
+                positions[*index] = position as f64;
+            }
+        }
+    };
+
+    update_positions(&rows, &mut positions);
+
+    for pass in 0..ORDERING_PASSES {
+        let downwards = pass % 2 == 0;
+        let direction = if downwards {
+            Direction::Incoming
+        } else {
+            Direction::Outgoing
+        };
+        let order = if downwards {
+            (1..rows.len()).collect::<Vec<_>>()
+        } else {
+            (0..rows.len().saturating_sub(1)).rev().collect::<Vec<_>>()
+        };
+
+        for layer in order {
+            let mut weighted = rows[layer]
+                .iter()
+                .map(|index| {
+                    let neighbors = graph
+                        .neighbors_directed(NodeIndex::new(*index), direction)
+                        .map(|neighbor| positions[neighbor.index()])
+                        .collect::<Vec<_>>();
+
+                    let weight = if neighbors.is_empty() {
+                        positions[*index]
+                    } else {
+                        neighbors.iter().sum::<f64>() / neighbors.len() as f64
+                    };
+
+                    (*index, weight)
+                })
+                .collect::<Vec<_>>();
+
+            weighted.sort_by(|a, d| a.1.total_cmp(&d.1).then(a.0.cmp(&d.0)));
+
+            rows[layer] = weighted.into_iter().map(|(index, _)| index).collect();
+
+            update_positions(&rows, &mut positions);
+        }
+    }
+
+    rows
+}
@@ -1,9 +1,13 @@
 mod graph_context;
+mod graph_diff;
 mod graph_formats;
+mod graph_layout;
 mod graph_traits;
 
 pub use graph_context::*;
+pub use graph_diff::*;
 pub use graph_formats::*;
+pub use graph_layout::*;
 pub use graph_traits::*;
 
 #[derive(serde::Deserialize, serde::Serialize)]
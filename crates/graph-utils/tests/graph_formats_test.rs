@@ -0,0 +1,134 @@
+use moon_graph_utils::*;
+use petgraph::graph::DiGraph;
+
+fn create_graph() -> DiGraph<String, String> {
+    let mut graph = DiGraph::new();
+    let app = graph.add_node("app".to_owned());
+    let lib = graph.add_node("lib".to_owned());
+    let types = graph.add_node("<types>".to_owned());
+
+    graph.add_edge(app, lib, "required".into());
+    graph.add_edge(app, types, String::new());
+    graph.add_edge(lib, types, "required".into());
+    graph
+}
+
+mod mermaid {
+    use super::*;
+
+    #[test]
+    fn formats_nodes_and_edges() {
+        assert_eq!(
+            format_mermaid(&create_graph()),
+            r#"flowchart TB
+  n0["app"]
+  n1["lib"]
+  n2["<types>"]
+  n0 -->|"required"| n1
+  n0 --> n2
+  n1 -->|"required"| n2
+"#
+        );
+    }
+}
+
+mod graphml {
+    use super::*;
+
+    #[test]
+    fn formats_nodes_and_edges() {
+        let out = format_graphml(&create_graph());
+
+        assert!(out.contains(r#"<node id="n2"><data key="label">&lt;types&gt;</data></node>"#));
+        assert!(out.contains(r#"<edge id="e1" source="n0" target="n2"/>"#));
+        assert!(out.contains(
+            r#"<edge id="e2" source="n1" target="n2"><data key="edge_label">required</data></edge>"#
+        ));
+        assert!(out.ends_with("</graphml>\n"));
+    }
+}
+
+mod svg {
+    use super::*;
+
+    #[test]
+    fn renders_a_node_and_path_for_each() {
+        let out = format_svg(&create_graph());
+
+        assert!(out.starts_with("<svg "));
+        assert_eq!(out.matches("<rect x=").count(), 3);
+        assert_eq!(out.matches("<path d=\"M ").count(), 3);
+        assert!(out.contains("&lt;types&gt;</text>"));
+    }
+
+    #[test]
+    fn layers_nodes_by_longest_path() {
+        let layout = GraphLayout::new(&create_graph());
+
+        assert_eq!(
+            layout
+                .nodes
+                .iter()
+                .map(|node| node.layer)
+                .collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+        assert!(layout.nodes[0].y < layout.nodes[1].y);
+        assert!(layout.nodes[1].y < layout.nodes[2].y);
+    }
+
+    #[test]
+    fn supports_cycles() {
+        let mut graph = create_graph();
+        graph.add_edge(2.into(), 0.into(), String::new());
+
+        let layout = GraphLayout::new(&graph);
+
+        assert_eq!(layout.nodes.len(), 3);
+        assert_eq!(layout.edges.len(), 4);
+    }
+
+    #[test]
+    fn supports_empty_graphs() {
+        let layout = GraphLayout::new(&DiGraph::<String, String>::new());
+
+        assert!(layout.nodes.is_empty());
+        assert_eq!(layout.width, layout.height);
+    }
+}
+
+mod diff {
+    use super::*;
+
+    #[test]
+    fn creates_snapshot_from_json() {
+        let snapshot = GraphSnapshot::from_json(
+            r#"{"graph":{"nodes":[{"id":"a"},{"id":"b"}],"node_holes":[],"edge_property":"directed","edges":[[0,1,null]]}}"#,
+            "id",
+        )
+        .unwrap();
+
+        assert_eq!(snapshot.nodes.len(), 2);
+        assert!(snapshot.edges.contains(&("a".into(), "b".into())));
+    }
+
+    #[test]
+    fn detects_added_and_removed() {
+        let base = GraphSnapshot {
+            edges: [("a".to_owned(), "b".to_owned())].into(),
+            nodes: ["a".to_owned(), "b".to_owned()].into(),
+        };
+        let head = GraphSnapshot {
+            edges: [("a".to_owned(), "c".to_owned())].into(),
+            nodes: ["a".to_owned(), "c".to_owned()].into(),
+        };
+
+        let diff = GraphDiff::new(&base, &head);
+
+        assert_eq!(diff.added_nodes, vec!["c"]);
+        assert_eq!(diff.removed_nodes, vec!["b"]);
+        assert_eq!(diff.added_edges, vec![("a".into(), "c".into())]);
+        assert_eq!(diff.removed_edges, vec![("a".into(), "b".into())]);
+        assert!(GraphDiff::new(&head, &head).is_empty());
+    }
+}
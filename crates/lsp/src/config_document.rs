@@ -0,0 +1,328 @@
+use moon_common::consts::CONFIG_DIRNAME;
+use std::ops::Range as ByteRange;
+use std::path::{Path, PathBuf};
+use tower_lsp::lsp_types::{Position, Range};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConfigDocumentKind {
+    Project,
+    Tasks,
+    Template,
+    Toolchain,
+    Workspace,
+}
+
+impl ConfigDocumentKind {
+    /// Detect the kind of configuration based on the file path. Returns `None`
+    /// if the file is not a moon configuration file. Only YAML is supported.
+    pub fn detect(workspace_root: &Path, path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?;
+
+        if ext != "yml" && ext != "yaml" {
+            return None;
+        }
+
+        let name = path.file_stem()?.to_str()?;
+        let moon_dir = workspace_root.join(CONFIG_DIRNAME);
+
+        if path.parent().is_some_and(|parent| parent == moon_dir) {
+            return match name {
+                "tasks" => Some(Self::Tasks),
+                "toolchain" => Some(Self::Toolchain),
+                "workspace" => Some(Self::Workspace),
+                _ => None,
+            };
+        }
+
+        if path.starts_with(moon_dir.join("tasks")) {
+            return Some(Self::Tasks);
+        }
+
+        match name {
+            "moon" => Some(Self::Project),
+            "template" => Some(Self::Template),
+            _ => None,
+        }
+    }
+}
+
+/// An open configuration file, with helpers for inspecting its YAML
+/// structure. The structure is inferred from indentation, so that it
+/// can be inspected while the document is being edited and is invalid.
+#[derive(Clone, Debug)]
+pub struct ConfigDocument {
+    pub content: String,
+    pub kind: ConfigDocumentKind,
+    pub path: PathBuf,
+}
+
+impl ConfigDocument {
+    pub fn new(kind: ConfigDocumentKind, path: PathBuf, content: String) -> Self {
+        Self {
+            content,
+            kind,
+            path,
+        }
+    }
+
+    /// Return the "word" at the provided position, and its range. A word may contain
+    /// target and token characters, like `^:build` or `@group(sources)`.
+    pub fn get_word_at(&self, position: Position) -> (String, Range) {
+        let Some(line) = self.content.lines().nth(position.line as usize) else {
+            return (String::new(), Range::new(position, position));
+        };
+
+        let offset = to_byte_offset(line, position.character);
+
+        let start = line[..offset]
+            .char_indices()
+            .rev()
+            .take_while(|(_, ch)| is_word_char(*ch))
+            .last()
+            .map(|(index, _)| index)
+            .unwrap_or(offset);
+
+        let mut end = line[offset..]
+            .char_indices()
+            .find(|(_, ch)| !is_word_char(*ch))
+            .map(|(index, _)| offset + index)
+            .unwrap_or(line.len());
+
+        // Keys are followed by a colon, which is never part of a value
+        if line[start..end].ends_with(':') {
+            end -= 1;
+        }
+
+        (
+            line[start..end].to_owned(),
+            to_range(position.line, line, start..end),
+        )
+    }
+
+    /// Return the path of mapping keys that contain the provided position.
+    /// If the position is within the value of a key, that key is included.
+    pub fn get_key_path_at(&self, position: Position) -> Vec<String> {
+        let lines = self.content.lines().collect::<Vec<_>>();
+
+        let Some(current) = lines.get(position.line as usize) else {
+            return vec![];
+        };
+
+        let mut path = vec![];
+
+        if let Some((key, range)) = parse_key(current) {
+            if to_byte_offset(current, position.character) > range.end {
+                path.push(key);
+            }
+        }
+
+        let mut indent = get_indent(current);
+        let mut in_sequence = current.trim_start().starts_with('-');
+
+        for line in lines[..position.line as usize].iter().rev() {
+            if indent == 0 && !in_sequence {
+                break;
+            }
+
+            let trimmed = line.trim_start();
+
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+
+            let line_indent = get_indent(line);
+
+            // Items in a sequence may be indented at the same level as their parent key
+            let is_parent = line_indent < indent
+                || (line_indent == indent && in_sequence && !trimmed.starts_with('-'));
+
+            if !is_parent {
+                continue;
+            }
+
+            if let Some((key, _)) = parse_key(line) {
+                path.push(key);
+            }
+
+            indent = line_indent;
+            in_sequence = trimmed.starts_with('-');
+        }
+
+        path.reverse();
+        path
+    }
+
+    /// Find the range of the key at the provided path of mapping keys.
+    pub fn find_key(&self, path: &[&str]) -> Option<Range> {
+        self.find_key_line(path)
+            .map(|(line_index, line, range)| to_range(line_index as u32, line, range))
+    }
+
+    /// Find the range of a value that is nested within the key at the provided path,
+    /// for example, an item in a sequence.
+    pub fn find_value(&self, path: &[&str], value: &str) -> Option<Range> {
+        let (key_index, _, key_range) = self.find_key_line(path)?;
+
+        for (line_index, line) in self.content.lines().enumerate().skip(key_index) {
+            let trimmed = line.trim_start();
+            let indent = get_indent(line);
+
+            // Stop at the next sibling key, but not at sequence items
+            // that are indented at the same level as the key
+            if line_index > key_index
+                && !trimmed.is_empty()
+                && (indent < key_range.start
+                    || (indent == key_range.start && !trimmed.starts_with('-')))
+            {
+                break;
+            }
+
+            let search_start = if line_index == key_index {
+                key_range.end
+            } else {
+                0
+            };
+
+            for (start, _) in line
+                .match_indices(value)
+                .filter(|(start, _)| *start >= search_start)
+            {
+                let end = start + value.len();
+                let is_bounded = !line[..start].chars().last().is_some_and(is_word_char)
+                    && !line[end..].chars().next().is_some_and(is_word_char);
+
+                if is_bounded {
+                    return Some(to_range(line_index as u32, line, start..end));
+                }
+            }
+        }
+
+        None
+    }
+
+    fn find_key_line(&self, path: &[&str]) -> Option<(usize, &str, ByteRange<usize>)> {
+        let mut stack: Vec<(usize, String)> = vec![];
+
+        for (line_index, line) in self.content.lines().enumerate() {
+            let Some((key, range)) = parse_key(line) else {
+                continue;
+            };
+
+            while stack
+                .last()
+                .is_some_and(|(indent, _)| *indent >= range.start)
+            {
+                stack.pop();
+            }
+
+            stack.push((range.start, key));
+
+            if stack.len() == path.len()
+                && stack
+                    .iter()
+                    .zip(path)
+                    .all(|((_, key), expected)| key == expected)
+            {
+                return Some((line_index, line, range));
+            }
+        }
+
+        None
+    }
+}
+
+/// Return true if the key path is within the value of the provided key. Values may
+/// also be declared as an object, in which case the item key is checked instead.
+pub fn is_within_key(key_path: &[String], key: &str, item_key: &str) -> bool {
+    match key_path {
+        [.., last] if last == key => true,
+        [.., parent, last] => parent == key && last == item_key,
+        _ => false,
+    }
+}
+
+fn is_word_char(ch: char) -> bool {
+    ch.is_alphanumeric()
+        || matches!(
+            ch,
+            '-' | '_' | ':' | '/' | '.' | '@' | '$' | '^' | '~' | '#' | '(' | ')' | '*' | '!'
+        )
+}
+
+fn get_indent(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+// Parse the mapping key of a line, and return it with its byte range.
+// Sequence items that are mappings (`- key: value`) are supported.
+fn parse_key(line: &str) -> Option<(String, ByteRange<usize>)> {
+    let mut start = get_indent(line);
+    let mut rest = &line[start..];
+
+    if let Some(item) = rest.strip_prefix('-') {
+        let item_start = item.len() - item.trim_start().len();
+
+        start += 1 + item_start;
+        rest = &item[item_start..];
+    }
+
+    if rest.starts_with('#') {
+        return None;
+    }
+
+    let colon = rest
+        .find(": ")
+        .or_else(|| rest.ends_with(':').then(|| rest.len() - 1))?;
+    let raw_key = &rest[..colon];
+    let key = raw_key.trim_matches(['\'', '"']);
+
+    if key.is_empty() || key.contains([' ', '[', ']', '{', '}', ',']) {
+        return None;
+    }
+
+    let key_start = start + (raw_key.len() - raw_key.trim_start_matches(['\'', '"']).len());
+
+    Some((key.to_owned(), key_start..key_start + key.len()))
+}
+
+fn to_byte_offset(line: &str, character: u32) -> usize {
+    let mut units = 0;
+
+    for (index, ch) in line.char_indices() {
+        if units >= character as usize {
+            return index;
+        }
+
+        units += ch.len_utf16();
+    }
+
+    line.len()
+}
+
+fn to_character(line: &str, offset: usize) -> u32 {
+    line[..offset].encode_utf16().count() as u32
+}
+
+fn to_range(line_index: u32, line: &str, range: ByteRange<usize>) -> Range {
+    Range::new(
+        Position::new(line_index, to_character(line, range.start)),
+        Position::new(line_index, to_character(line, range.end)),
+    )
+}
+
+/// Convert a byte offset within the content into a position.
+pub fn offset_to_position(content: &str, offset: usize) -> Position {
+    let mut offset = offset.min(content.len());
+
+    while !content.is_char_boundary(offset) {
+        offset -= 1;
+    }
+
+    let before = &content[..offset];
+    let line_start = before.rfind('\n').map(|index| index + 1).unwrap_or(0);
+
+    Position::new(
+        before.matches('\n').count() as u32,
+        to_character(&content[line_start..], offset - line_start),
+    )
+}
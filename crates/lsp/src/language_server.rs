@@ -0,0 +1,270 @@
+use crate::config_document::{ConfigDocument, ConfigDocumentKind};
+use crate::lsp_completion::get_completions;
+use crate::lsp_definition::get_definition;
+use crate::lsp_diagnostics::{create_graph_diagnostic, validate_document};
+use crate::lsp_hover::get_hover;
+use crate::lsp_workspace::{LspWorkspace, WorkspaceGraphLoader};
+use moon_common::consts::CONFIG_DIRNAME;
+use rustc_hash::FxHashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tower_lsp::jsonrpc::Result;
+use tower_lsp::lsp_types::*;
+use tower_lsp::{Client, LanguageServer, LspService, Server};
+use tracing::{debug, instrument};
+
+pub struct MoonLanguageServer {
+    client: Client,
+
+    /// Open configuration documents, mapped by file path.
+    documents: RwLock<FxHashMap<PathBuf, ConfigDocument>>,
+
+    /// Diagnostics from the last workspace graph build, mapped by file path.
+    graph_diagnostics: RwLock<FxHashMap<PathBuf, Vec<Diagnostic>>>,
+
+    loader: Arc<dyn WorkspaceGraphLoader>,
+
+    /// The last successfully loaded workspace.
+    workspace: RwLock<Option<Arc<LspWorkspace>>>,
+
+    workspace_root: PathBuf,
+}
+
+impl MoonLanguageServer {
+    pub fn new(
+        client: Client,
+        workspace_root: PathBuf,
+        loader: Arc<dyn WorkspaceGraphLoader>,
+    ) -> Self {
+        Self {
+            client,
+            documents: RwLock::new(FxHashMap::default()),
+            graph_diagnostics: RwLock::new(FxHashMap::default()),
+            loader,
+            workspace: RwLock::new(None),
+            workspace_root,
+        }
+    }
+
+    async fn get_document(&self, url: &Url) -> Option<ConfigDocument> {
+        let path = url.to_file_path().ok()?;
+
+        self.documents.read().await.get(&path).cloned()
+    }
+
+    async fn get_workspace(&self) -> Option<Arc<LspWorkspace>> {
+        self.workspace.read().await.clone()
+    }
+
+    async fn update_document(&self, url: &Url, content: String) {
+        let Ok(path) = url.to_file_path() else {
+            return;
+        };
+
+        let Some(kind) = ConfigDocumentKind::detect(&self.workspace_root, &path) else {
+            return;
+        };
+
+        self.documents.write().await.insert(
+            path.clone(),
+            ConfigDocument::new(kind, path.clone(), content),
+        );
+
+        self.publish_diagnostics(&path).await;
+    }
+
+    async fn publish_diagnostics(&self, path: &Path) {
+        let mut diagnostics = match self.documents.read().await.get(path) {
+            Some(document) => validate_document(document),
+            None => vec![],
+        };
+
+        if let Some(graph_diagnostics) = self.graph_diagnostics.read().await.get(path) {
+            diagnostics.extend(graph_diagnostics.iter().cloned());
+        }
+
+        if let Ok(url) = Url::from_file_path(path) {
+            self.client
+                .publish_diagnostics(url, diagnostics, None)
+                .await;
+        }
+    }
+
+    /// Reload the workspace graph, and publish diagnostics for any errors, like
+    /// constraint violations. If the graph fails to load, the previous graph is kept.
+    #[instrument(skip(self))]
+    async fn reload_workspace(&self, saved_path: Option<&Path>) {
+        debug!("Reloading workspace graph for language server");
+
+        let mut paths = self
+            .graph_diagnostics
+            .write()
+            .await
+            .drain()
+            .map(|(path, _)| path)
+            .collect::<Vec<_>>();
+
+        match self.loader.load_workspace_graph().await {
+            Ok(graph) => {
+                *self.workspace.write().await =
+                    Some(Arc::new(LspWorkspace::new(graph, &self.workspace_root)));
+            }
+            Err(error) => {
+                let workspace = self.get_workspace().await;
+                let fallback = saved_path
+                    .map(|path| path.to_path_buf())
+                    .unwrap_or_else(|| {
+                        self.workspace_root
+                            .join(CONFIG_DIRNAME)
+                            .join("workspace.yml")
+                    });
+
+                let (path, diagnostic) =
+                    create_graph_diagnostic(&error, workspace.as_deref(), &fallback);
+
+                self.client
+                    .log_message(
+                        MessageType::ERROR,
+                        format!("Failed to load workspace graph: {}", diagnostic.message),
+                    )
+                    .await;
+
+                self.graph_diagnostics
+                    .write()
+                    .await
+                    .insert(path.clone(), vec![diagnostic]);
+
+                paths.push(path);
+            }
+        };
+
+        for path in paths {
+            self.publish_diagnostics(&path).await;
+        }
+    }
+}
+
+#[tower_lsp::async_trait]
+impl LanguageServer for MoonLanguageServer {
+    async fn initialize(&self, _params: InitializeParams) -> Result<InitializeResult> {
+        Ok(InitializeResult {
+            capabilities: ServerCapabilities {
+                completion_provider: Some(CompletionOptions {
+                    trigger_characters: Some(
+                        [":", "@", "$", "("].into_iter().map(String::from).collect(),
+                    ),
+                    ..Default::default()
+                }),
+                definition_provider: Some(OneOf::Left(true)),
+                hover_provider: Some(HoverProviderCapability::Simple(true)),
+                text_document_sync: Some(TextDocumentSyncCapability::Options(
+                    TextDocumentSyncOptions {
+                        change: Some(TextDocumentSyncKind::FULL),
+                        open_close: Some(true),
+                        save: Some(TextDocumentSyncSaveOptions::Supported(true)),
+                        ..Default::default()
+                    },
+                )),
+                ..Default::default()
+            },
+            server_info: Some(ServerInfo {
+                name: "moon".into(),
+                version: None,
+            }),
+        })
+    }
+
+    async fn initialized(&self, _params: InitializedParams) {
+        self.reload_workspace(None).await;
+    }
+
+    async fn shutdown(&self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn did_open(&self, params: DidOpenTextDocumentParams) {
+        self.update_document(&params.text_document.uri, params.text_document.text)
+            .await;
+    }
+
+    async fn did_change(&self, mut params: DidChangeTextDocumentParams) {
+        // We only support full syncs, so the last change is the entire document
+        if let Some(change) = params.content_changes.pop() {
+            self.update_document(&params.text_document.uri, change.text)
+                .await;
+        }
+    }
+
+    async fn did_save(&self, params: DidSaveTextDocumentParams) {
+        let Ok(path) = params.text_document.uri.to_file_path() else {
+            return;
+        };
+
+        if self.documents.read().await.contains_key(&path) {
+            self.reload_workspace(Some(&path)).await;
+        }
+    }
+
+    async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        let Ok(path) = params.text_document.uri.to_file_path() else {
+            return;
+        };
+
+        if self.documents.write().await.remove(&path).is_some() {
+            self.publish_diagnostics(&path).await;
+        }
+    }
+
+    async fn completion(&self, params: CompletionParams) -> Result<Option<CompletionResponse>> {
+        let params = params.text_document_position;
+
+        let Some(document) = self.get_document(&params.text_document.uri).await else {
+            return Ok(None);
+        };
+
+        let workspace = self.get_workspace().await;
+        let items = get_completions(&document, workspace.as_deref(), params.position);
+
+        Ok((!items.is_empty()).then_some(CompletionResponse::Array(items)))
+    }
+
+    async fn hover(&self, params: HoverParams) -> Result<Option<Hover>> {
+        let params = params.text_document_position_params;
+
+        let (Some(document), Some(workspace)) = (
+            self.get_document(&params.text_document.uri).await,
+            self.get_workspace().await,
+        ) else {
+            return Ok(None);
+        };
+
+        Ok(get_hover(&document, &workspace, params.position))
+    }
+
+    async fn goto_definition(
+        &self,
+        params: GotoDefinitionParams,
+    ) -> Result<Option<GotoDefinitionResponse>> {
+        let params = params.text_document_position_params;
+
+        let (Some(document), Some(workspace)) = (
+            self.get_document(&params.text_document.uri).await,
+            self.get_workspace().await,
+        ) else {
+            return Ok(None);
+        };
+
+        Ok(get_definition(&document, &workspace, params.position))
+    }
+}
+
+/// Run the language server over stdin and stdout, until the client exits.
+pub async fn run_language_server(workspace_root: PathBuf, loader: Arc<dyn WorkspaceGraphLoader>) {
+    let (service, socket) =
+        LspService::new(|client| MoonLanguageServer::new(client, workspace_root, loader));
+
+    Server::new(tokio::io::stdin(), tokio::io::stdout(), socket)
+        .serve(service)
+        .await;
+}
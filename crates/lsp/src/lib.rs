@@ -0,0 +1,15 @@
+mod config_document;
+mod language_server;
+mod lsp_completion;
+mod lsp_definition;
+mod lsp_diagnostics;
+mod lsp_hover;
+mod lsp_workspace;
+
+pub use config_document::*;
+pub use language_server::*;
+pub use lsp_completion::*;
+pub use lsp_definition::*;
+pub use lsp_diagnostics::*;
+pub use lsp_hover::*;
+pub use lsp_workspace::*;
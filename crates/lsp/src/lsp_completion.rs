@@ -0,0 +1,307 @@
+use crate::config_document::{ConfigDocument, is_within_key};
+use crate::lsp_workspace::LspWorkspace;
+use moon_project::Project;
+use tower_lsp::lsp_types::{
+    CompletionItem, CompletionItemKind, CompletionTextEdit, InsertTextFormat, Position, Range,
+    TextEdit,
+};
+
+/// Token functions, and whether their argument is a file group.
+pub const TOKEN_FUNCTIONS: [(&str, &str, bool); 9] = [
+    ("dirs", "Directories matched by a file group", true),
+    ("envs", "Environment variables from a file group", true),
+    ("files", "Files matched by a file group", true),
+    ("globs", "Globs of a file group", true),
+    ("group", "All paths and globs of a file group", true),
+    ("in", "An input by index", false),
+    ("meta", "A project metadata field", false),
+    ("out", "An output by index", false),
+    ("root", "Lowest common directory of a file group", true),
+];
+
+pub const TOKEN_VARIABLES: [(&str, &str); 28] = [
+    ("arch", "Architecture of the host"),
+    ("date", "Current date"),
+    ("datetime", "Current date and time"),
+    ("language", "Language of the project"),
+    ("os", "Operating system of the host"),
+    ("osFamily", "Operating system family of the host"),
+    ("project", "ID of the project"),
+    ("projectAlias", "Alias of the project"),
+    ("projectChannel", "Channel of the project"),
+    ("projectName", "Name of the project"),
+    ("projectOwner", "Owner of the project"),
+    ("projectRoot", "Absolute path to the project root"),
+    ("projectSource", "Relative path to the project root"),
+    ("projectStack", "Stack of the project"),
+    ("projectType", "Type of the project"),
+    ("target", "Target of the task"),
+    ("task", "ID of the task"),
+    ("taskToolchain", "First toolchain of the task"),
+    ("taskToolchains", "All toolchains of the task"),
+    ("taskType", "Type of the task"),
+    ("time", "Current time"),
+    ("timestamp", "Current UNIX timestamp"),
+    ("vcsBranch", "Current branch"),
+    ("vcsRepository", "Repository slug"),
+    ("vcsRevision", "Current revision"),
+    ("workingDir", "Current working directory"),
+    ("workspaceRoot", "Absolute path to the workspace root"),
+    ("taskPlatform", "Deprecated, use $taskToolchain"),
+];
+
+pub const META_FIELDS: [&str; 5] = ["channel", "description", "maintainers", "name", "owner"];
+
+pub const DEP_KEYS: [&str; 2] = ["deps", "implicitDeps"];
+
+fn create_item(
+    label: impl Into<String>,
+    detail: impl Into<String>,
+    kind: CompletionItemKind,
+    range: Range,
+) -> CompletionItem {
+    let label = label.into();
+
+    CompletionItem {
+        detail: Some(detail.into()),
+        kind: Some(kind),
+        text_edit: Some(CompletionTextEdit::Edit(TextEdit::new(
+            range,
+            label.clone(),
+        ))),
+        label,
+        ..Default::default()
+    }
+}
+
+// Replace from the provided character within the word, through to the end of the word.
+fn get_partial_range(word: &str, range: Range, from: usize) -> Range {
+    Range::new(
+        Position::new(
+            range.start.line,
+            range.start.character + word[..from].encode_utf16().count() as u32,
+        ),
+        range.end,
+    )
+}
+
+fn complete_tokens(
+    document: &ConfigDocument,
+    workspace: Option<&LspWorkspace>,
+    word: &str,
+    range: Range,
+) -> Vec<CompletionItem> {
+    let func_index = word.rfind('@');
+    let var_index = word.rfind('$');
+
+    if let Some(index) = func_index.filter(|index| var_index.is_none_or(|var| var < *index)) {
+        let token = &word[index + 1..];
+
+        // Within the function arguments
+        if let Some(paren) = token.find('(') {
+            let func = &token[..paren];
+            let arg_range = get_partial_range(word, range, index + paren + 2);
+
+            if func == "meta" {
+                return META_FIELDS
+                    .iter()
+                    .map(|field| {
+                        create_item(
+                            format!("{field})"),
+                            "Metadata field",
+                            CompletionItemKind::FIELD,
+                            arg_range,
+                        )
+                    })
+                    .collect();
+            }
+
+            let is_group_func = TOKEN_FUNCTIONS
+                .iter()
+                .any(|(name, _, is_group)| *name == func && *is_group);
+
+            return match workspace {
+                Some(workspace) if is_group_func => workspace
+                    .get_file_group_names(document)
+                    .into_iter()
+                    .map(|name| {
+                        create_item(
+                            format!("{name})"),
+                            "File group",
+                            CompletionItemKind::FOLDER,
+                            arg_range,
+                        )
+                    })
+                    .collect(),
+                _ => vec![],
+            };
+        }
+
+        let func_range = get_partial_range(word, range, index);
+
+        return TOKEN_FUNCTIONS
+            .iter()
+            .map(|(name, description, _)| CompletionItem {
+                insert_text_format: Some(InsertTextFormat::SNIPPET),
+                text_edit: Some(CompletionTextEdit::Edit(TextEdit::new(
+                    func_range,
+                    format!("@{name}($1)"),
+                ))),
+                ..create_item(
+                    format!("@{name}"),
+                    *description,
+                    CompletionItemKind::FUNCTION,
+                    func_range,
+                )
+            })
+            .collect();
+    }
+
+    if let Some(index) = var_index {
+        let var_range = get_partial_range(word, range, index);
+
+        return TOKEN_VARIABLES
+            .iter()
+            .map(|(name, description)| {
+                create_item(
+                    format!("${name}"),
+                    *description,
+                    CompletionItemKind::VARIABLE,
+                    var_range,
+                )
+            })
+            .collect();
+    }
+
+    vec![]
+}
+
+fn complete_projects(workspace: &LspWorkspace, range: Range) -> Vec<CompletionItem> {
+    workspace
+        .get_projects()
+        .into_iter()
+        .map(|project| {
+            create_item(
+                project.id.as_str(),
+                project.source.as_str(),
+                CompletionItemKind::MODULE,
+                range,
+            )
+        })
+        .collect()
+}
+
+fn complete_targets(
+    document: &ConfigDocument,
+    workspace: &LspWorkspace,
+    word: &str,
+    range: Range,
+) -> Vec<CompletionItem> {
+    let project = workspace.get_document_project(document);
+    let mut items = vec![];
+
+    // Complete the task of a scope, for example, `app:` or `^:`
+    if let Some((scope, _)) = word.split_once(':') {
+        let projects: Vec<&Project> = match scope {
+            "" | "~" => project.into_iter().collect(),
+            "^" => project
+                .map(|project| {
+                    project
+                        .get_dependency_ids()
+                        .into_iter()
+                        .filter_map(|id| workspace.get_project(id.as_str()))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            _ => match scope.strip_prefix('#') {
+                Some(tag) => workspace
+                    .get_projects()
+                    .into_iter()
+                    .filter(|project| project.config.tags.iter().any(|t| t.as_str() == tag))
+                    .collect(),
+                None => workspace.get_project(scope).into_iter().collect(),
+            },
+        };
+
+        let mut task_ids = projects
+            .into_iter()
+            .flat_map(|project| workspace.get_task_ids(project))
+            .collect::<Vec<_>>();
+
+        task_ids.sort();
+        task_ids.dedup();
+
+        for task_id in task_ids {
+            items.push(create_item(
+                format!("{scope}:{task_id}"),
+                "Task",
+                CompletionItemKind::FUNCTION,
+                range,
+            ));
+        }
+
+        return items;
+    }
+
+    // Otherwise complete scopes, and tasks within the current project
+    if let Some(project) = project {
+        for task_id in workspace.get_task_ids(project) {
+            items.push(create_item(
+                task_id.as_str(),
+                "Task in this project",
+                CompletionItemKind::FUNCTION,
+                range,
+            ));
+        }
+
+        items.push(create_item(
+            "^:",
+            "Tasks in dependencies",
+            CompletionItemKind::KEYWORD,
+            range,
+        ));
+        items.push(create_item(
+            "~:",
+            "Tasks in this project",
+            CompletionItemKind::KEYWORD,
+            range,
+        ));
+    }
+
+    for project in workspace.get_projects() {
+        items.push(create_item(
+            format!("{}:", project.id),
+            project.source.as_str(),
+            CompletionItemKind::MODULE,
+            range,
+        ));
+    }
+
+    items
+}
+
+/// Return completion items for the provided position, based on the key that
+/// contains the position, and the word being typed.
+pub fn get_completions(
+    document: &ConfigDocument,
+    workspace: Option<&LspWorkspace>,
+    position: Position,
+) -> Vec<CompletionItem> {
+    let (word, range) = document.get_word_at(position);
+    let key_path = document.get_key_path_at(position);
+
+    if let Some(workspace) = workspace {
+        if is_within_key(&key_path, "dependsOn", "id") {
+            return complete_projects(workspace, range);
+        }
+
+        if DEP_KEYS
+            .iter()
+            .any(|key| is_within_key(&key_path, key, "target"))
+        {
+            return complete_targets(document, workspace, &word, range);
+        }
+    }
+
+    complete_tokens(document, workspace, &word, range)
+}
@@ -0,0 +1,65 @@
+use crate::config_document::{ConfigDocument, ConfigDocumentKind, is_within_key};
+use crate::lsp_completion::DEP_KEYS;
+use crate::lsp_workspace::LspWorkspace;
+use starbase_utils::fs;
+use std::path::Path;
+use tower_lsp::lsp_types::{GotoDefinitionResponse, Location, Position, Range, Url};
+
+fn create_location(workspace: &LspWorkspace, path: &Path, key_path: &[&str]) -> Option<Location> {
+    let kind = ConfigDocumentKind::detect(&workspace.workspace_root, path)?;
+    let range = fs::read_file(path)
+        .ok()
+        .and_then(|content| {
+            ConfigDocument::new(kind, path.to_path_buf(), content).find_key(key_path)
+        })
+        .unwrap_or_default();
+
+    Some(Location::new(Url::from_file_path(path).ok()?, range))
+}
+
+/// Return the locations of the projects or tasks referenced at the provided position.
+/// Tasks may be defined in multiple files, when inherited and then overridden.
+pub fn get_definition(
+    document: &ConfigDocument,
+    workspace: &LspWorkspace,
+    position: Position,
+) -> Option<GotoDefinitionResponse> {
+    let (word, _) = document.get_word_at(position);
+
+    if word.is_empty() {
+        return None;
+    }
+
+    let key_path = document.get_key_path_at(position);
+    let mut locations = vec![];
+
+    if is_within_key(&key_path, "dependsOn", "id") {
+        let path = workspace.get_project_config_path(workspace.get_project(&word)?);
+
+        locations.push(Location::new(
+            Url::from_file_path(path).ok()?,
+            Range::default(),
+        ));
+    } else if DEP_KEYS
+        .iter()
+        .any(|key| is_within_key(&key_path, key, "target"))
+    {
+        let project = workspace.get_document_project(document);
+
+        for target in workspace.resolve_targets(&word, project) {
+            for location in workspace.get_task_locations(&target) {
+                locations.extend(create_location(
+                    workspace,
+                    &location.file,
+                    &["tasks", location.task_id.as_str()],
+                ));
+            }
+        }
+    }
+
+    match locations.len() {
+        0 => None,
+        1 => locations.pop().map(GotoDefinitionResponse::Scalar),
+        _ => Some(GotoDefinitionResponse::Array(locations)),
+    }
+}
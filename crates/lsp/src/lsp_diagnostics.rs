@@ -0,0 +1,214 @@
+use crate::config_document::{ConfigDocument, ConfigDocumentKind, offset_to_position};
+use crate::lsp_workspace::LspWorkspace;
+use moon_common::Id;
+use moon_config::schematic::{Config, ConfigLoader, Format};
+use moon_config::{
+    InheritedTasksConfig, ProjectConfig, TemplateConfig, ToolchainConfig, WorkspaceConfig,
+};
+use moon_project_constraints::ProjectConstraintsError;
+use regex::Regex;
+use starbase_utils::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString, Range};
+
+static ANSI_ESCAPE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\x1b\[[0-9;]*[A-Za-z]").unwrap());
+
+// Validation errors list each invalid setting on its own line, for example,
+// `tasks.build.deps[0]: <message>`.
+static SETTING_ERROR: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*([A-Za-z$][\w.\[\]$-]*): (.+)$").unwrap());
+
+fn strip_ansi(value: &str) -> String {
+    ANSI_ESCAPE.replace_all(value, "").into_owned()
+}
+
+fn create_diagnostic(range: Range, message: String, error: &miette::Report) -> Diagnostic {
+    Diagnostic {
+        code: error
+            .code()
+            .map(|code| NumberOrString::String(code.to_string())),
+        message: strip_ansi(&message),
+        range,
+        severity: Some(DiagnosticSeverity::ERROR),
+        source: Some("moon".into()),
+        ..Default::default()
+    }
+}
+
+fn load_config<T: Config>(document: &ConfigDocument) -> miette::Result<()> {
+    let mut content = document.content.as_str();
+
+    if content.trim().is_empty() {
+        content = "{}";
+    }
+
+    let mut loader = ConfigLoader::<T>::new();
+    loader.code(content, Format::Yaml)?;
+
+    if let Some(root) = document.path.parent() {
+        loader.set_root(root);
+    }
+
+    loader.load()?;
+
+    Ok(())
+}
+
+/// Validate the content of a document against its configuration schema,
+/// and return diagnostics for all parse and validation errors.
+pub fn validate_document(document: &ConfigDocument) -> Vec<Diagnostic> {
+    let result = match document.kind {
+        ConfigDocumentKind::Project => load_config::<ProjectConfig>(document),
+        ConfigDocumentKind::Tasks => load_config::<InheritedTasksConfig>(document),
+        ConfigDocumentKind::Template => load_config::<TemplateConfig>(document),
+        ConfigDocumentKind::Toolchain => load_config::<ToolchainConfig>(document),
+        ConfigDocumentKind::Workspace => load_config::<WorkspaceConfig>(document),
+    };
+
+    match result {
+        Ok(_) => vec![],
+        Err(error) => create_config_diagnostics(document, &error),
+    }
+}
+
+/// Convert a configuration error into diagnostics. Parse errors are labeled with
+/// a span of the content, while validation errors reference the path of each setting.
+pub fn create_config_diagnostics(
+    document: &ConfigDocument,
+    error: &miette::Report,
+) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+
+    if let Some(labels) = error.labels() {
+        for label in labels {
+            let range = Range::new(
+                offset_to_position(&document.content, label.offset()),
+                offset_to_position(&document.content, label.offset() + label.len()),
+            );
+
+            diagnostics.push(create_diagnostic(
+                range,
+                label
+                    .label()
+                    .map(|label| label.to_owned())
+                    .unwrap_or_else(|| error.to_string()),
+                error,
+            ));
+        }
+    }
+
+    if diagnostics.is_empty() {
+        let messages = error
+            .chain()
+            .map(|inner| inner.to_string())
+            .chain(
+                error
+                    .related()
+                    .into_iter()
+                    .flatten()
+                    .map(|inner| inner.to_string()),
+            )
+            .collect::<Vec<_>>();
+
+        for message in messages {
+            let message = strip_ansi(&message);
+
+            for line in message.lines() {
+                let Some(captures) = SETTING_ERROR.captures(line) else {
+                    continue;
+                };
+
+                let setting = captures.get(1).unwrap().as_str();
+                let key_path = setting
+                    .split('.')
+                    .map(|key| key.split('[').next().unwrap_or(key))
+                    .filter(|key| !key.is_empty())
+                    .collect::<Vec<_>>();
+
+                // The setting may not exist in the document (when required),
+                // so fallback to the closest parent that does
+                let range = (1..=key_path.len())
+                    .rev()
+                    .find_map(|len| document.find_key(&key_path[..len]))
+                    .unwrap_or_default();
+
+                diagnostics.push(create_diagnostic(range, line.trim().to_owned(), error));
+            }
+        }
+    }
+
+    if diagnostics.is_empty() {
+        diagnostics.push(create_diagnostic(
+            Range::default(),
+            error.to_string(),
+            error,
+        ));
+    }
+
+    diagnostics
+}
+
+// Return the project that violated a constraint, and the dependency it violated it with.
+fn get_constraint_source(error: &ProjectConstraintsError) -> Option<(&Id, Option<&Id>)> {
+    match error {
+        ProjectConstraintsError::InvalidTypeRelationship {
+            source_id, dep_id, ..
+        }
+        | ProjectConstraintsError::InvalidTagRelationship {
+            source_id, dep_id, ..
+        }
+        | ProjectConstraintsError::ForbiddenDependency {
+            source_id, dep_id, ..
+        }
+        | ProjectConstraintsError::RestrictedTag {
+            source_id, dep_id, ..
+        } => Some((source_id, Some(dep_id))),
+        ProjectConstraintsError::MaxDependencyDepth { source_id, .. }
+        | ProjectConstraintsError::MissingRequiredTag { source_id, .. }
+        | ProjectConstraintsError::PluginViolation { source_id, .. } => Some((source_id, None)),
+        ProjectConstraintsError::CrossStackCycle { .. } => None,
+    }
+}
+
+/// Create a diagnostic for an error that occurred while building the workspace graph,
+/// and return it with the file it belongs to. Project constraint violations belong
+/// to the config of the offending project, while other errors belong to the fallback.
+pub fn create_graph_diagnostic(
+    error: &miette::Report,
+    workspace: Option<&LspWorkspace>,
+    fallback: &Path,
+) -> (PathBuf, Diagnostic) {
+    let mut diagnostic = create_diagnostic(Range::default(), error.to_string(), error);
+
+    let Some(constraint) = error.downcast_ref::<ProjectConstraintsError>() else {
+        return (fallback.to_path_buf(), diagnostic);
+    };
+
+    let Some((source_id, dep_id)) = get_constraint_source(constraint) else {
+        return (fallback.to_path_buf(), diagnostic);
+    };
+
+    // Constraints are enforced while building the graph, so locate the
+    // project using the previous graph, as the current graph has failed
+    let Some(path) =
+        workspace.and_then(|workspace| workspace.get_project_config_path_by_id(source_id.as_str()))
+    else {
+        return (fallback.to_path_buf(), diagnostic);
+    };
+
+    if let Ok(content) = fs::read_file(&path) {
+        let document = ConfigDocument::new(ConfigDocumentKind::Project, path.clone(), content);
+
+        diagnostic.range = match constraint {
+            ProjectConstraintsError::MissingRequiredTag { .. } => document.find_key(&["tags"]),
+            _ => dep_id
+                .and_then(|dep_id| document.find_value(&["dependsOn"], dep_id.as_str()))
+                .or_else(|| document.find_key(&["dependsOn"])),
+        }
+        .unwrap_or_default();
+    }
+
+    (path, diagnostic)
+}
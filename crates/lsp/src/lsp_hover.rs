@@ -0,0 +1,182 @@
+use crate::config_document::{ConfigDocument, is_within_key};
+use crate::lsp_completion::DEP_KEYS;
+use crate::lsp_workspace::LspWorkspace;
+use moon_project::Project;
+use moon_task::{Target, Task};
+use std::fmt::Write;
+use tower_lsp::lsp_types::{Hover, HoverContents, MarkupContent, MarkupKind, Position};
+
+fn format_list(items: Vec<String>) -> String {
+    items
+        .into_iter()
+        .map(|item| format!("`{item}`"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn format_task(task: &Task) -> String {
+    let mut out = format!("**{}**", task.target);
+
+    if let Some(description) = &task.description {
+        let _ = write!(out, "\n\n{description}");
+    }
+
+    let _ = write!(out, "\n\n```shell\n{}\n```\n", task.get_command_line());
+
+    let mut modes = vec![];
+
+    if task.is_local() {
+        modes.push("local");
+    }
+    if task.is_internal() {
+        modes.push("internal");
+    }
+    if task.is_interactive() {
+        modes.push("interactive");
+    }
+    if task.is_persistent() {
+        modes.push("persistent");
+    }
+
+    let mut inputs = vec![];
+    inputs.extend(task.input_globs.iter().map(|i| i.to_string()));
+    inputs.extend(task.input_files.iter().map(|i| i.to_string()));
+    inputs.extend(task.input_env.iter().map(|i| format!("${i}")));
+    inputs.sort();
+
+    let mut outputs = vec![];
+    outputs.extend(task.output_globs.iter().map(|o| o.to_string()));
+    outputs.extend(task.output_files.iter().map(|o| o.to_string()));
+    outputs.sort();
+
+    let mut env = task
+        .env
+        .iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<_>>();
+    env.sort();
+
+    let deps = task
+        .deps
+        .iter()
+        .map(|dep| dep.target.to_string())
+        .collect::<Vec<_>>();
+
+    let mut entries = vec![
+        ("Type", task.type_of.to_string()),
+        ("Toolchains", task.toolchains.join(", ")),
+        ("Modes", modes.join(", ")),
+        ("Dependencies", format_list(deps)),
+        ("Inputs", format_list(inputs)),
+        ("Outputs", format_list(outputs)),
+        ("Environment variables", format_list(env)),
+    ];
+
+    if !task.options.cache {
+        entries.push(("Cache", "disabled".into()));
+    }
+
+    for (name, value) in entries {
+        if !value.is_empty() {
+            let _ = write!(out, "\n- **{name}:** {value}");
+        }
+    }
+
+    out
+}
+
+fn format_project(workspace: &LspWorkspace, project: &Project) -> String {
+    let mut out = format!("**{}**", project.id);
+
+    if let Some(description) = project
+        .config
+        .project
+        .as_ref()
+        .map(|metadata| metadata.description.as_str())
+        .filter(|description| !description.is_empty())
+    {
+        let _ = write!(out, "\n\n{description}");
+    }
+
+    let task_ids = workspace
+        .get_task_ids(project)
+        .into_iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<_>>();
+
+    let entries = [
+        ("Source", format!("`{}`", project.source)),
+        ("Alias", project.alias.clone().unwrap_or_default()),
+        ("Type", project.type_of.to_string()),
+        ("Language", project.language.to_string()),
+        ("Stack", project.stack.to_string()),
+        ("Tasks", format_list(task_ids)),
+    ];
+
+    out.push('\n');
+
+    for (name, value) in entries {
+        if !value.is_empty() {
+            let _ = write!(out, "\n- **{name}:** {value}");
+        }
+    }
+
+    out
+}
+
+/// Return hover information for the project or task at the provided position.
+/// Tasks are shown fully expanded, as they would be ran.
+pub fn get_hover(
+    document: &ConfigDocument,
+    workspace: &LspWorkspace,
+    position: Position,
+) -> Option<Hover> {
+    let (word, range) = document.get_word_at(position);
+
+    if word.is_empty() {
+        return None;
+    }
+
+    let key_path = document.get_key_path_at(position);
+    let project = workspace.get_document_project(document);
+
+    let content = if is_within_key(&key_path, "dependsOn", "id") {
+        format_project(workspace, workspace.get_project(&word)?)
+    } else {
+        let targets = match (project, key_path.as_slice()) {
+            // The key of a task within the project
+            (Some(project), [tasks]) if tasks == "tasks" => {
+                vec![Target::new(&project.id, &word).ok()?]
+            }
+            _ if DEP_KEYS
+                .iter()
+                .any(|key| is_within_key(&key_path, key, "target")) =>
+            {
+                workspace.resolve_targets(&word, project)
+            }
+            _ => return None,
+        };
+
+        let tasks = targets
+            .iter()
+            .map(|target| match workspace.graph.get_task(target) {
+                Ok(task) => format_task(&task),
+                Err(error) => format!("**{target}**\n\nFailed to expand task: {error}"),
+            })
+            .collect::<Vec<_>>();
+
+        if tasks.is_empty() {
+            return None;
+        }
+
+        tasks.join("\n\n---\n\n")
+    };
+
+    Some(Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: content,
+        }),
+        range: Some(range),
+    })
+}
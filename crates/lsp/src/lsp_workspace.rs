@@ -0,0 +1,181 @@
+use crate::config_document::{ConfigDocument, ConfigDocumentKind};
+use async_trait::async_trait;
+use moon_common::Id;
+use moon_config::ConfigFinder;
+use moon_project::Project;
+use moon_task::{Target, TargetScope};
+use moon_workspace_graph::WorkspaceGraph;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Loads the workspace graph for the language server. The graph is
+/// reloaded whenever a configuration file is saved.
+#[async_trait]
+pub trait WorkspaceGraphLoader: Send + Sync {
+    async fn load_workspace_graph(&self) -> miette::Result<Arc<WorkspaceGraph>>;
+}
+
+/// The task key within a configuration file that defines a task.
+pub struct TaskLocation {
+    pub file: PathBuf,
+    pub task_id: Id,
+}
+
+/// A loaded workspace, used to resolve projects and tasks
+/// that are referenced within configuration files.
+pub struct LspWorkspace {
+    pub config_finder: ConfigFinder,
+    pub graph: Arc<WorkspaceGraph>,
+    pub workspace_root: PathBuf,
+}
+
+impl LspWorkspace {
+    pub fn new(graph: Arc<WorkspaceGraph>, workspace_root: &Path) -> Self {
+        Self {
+            config_finder: ConfigFinder::default(),
+            graph,
+            workspace_root: workspace_root.to_path_buf(),
+        }
+    }
+
+    /// Return the project that owns the provided document, if it's a project config.
+    pub fn get_document_project(&self, document: &ConfigDocument) -> Option<&Project> {
+        if document.kind != ConfigDocumentKind::Project {
+            return None;
+        }
+
+        let project_root = document.path.parent()?;
+
+        self.get_projects()
+            .into_iter()
+            .find(|project| project.root == project_root)
+    }
+
+    pub fn get_project(&self, id_or_alias: &str) -> Option<&Project> {
+        self.graph.projects.get_unexpanded(id_or_alias).ok()
+    }
+
+    pub fn get_projects(&self) -> Vec<&Project> {
+        let mut projects = self.graph.projects.get_all_unexpanded();
+        projects.sort_by(|a, d| a.id.cmp(&d.id));
+        projects
+    }
+
+    /// Return the config file of the project, or where it would be created.
+    pub fn get_project_config_path(&self, project: &Project) -> PathBuf {
+        let files = self.config_finder.get_project_files(&project.root);
+
+        files
+            .iter()
+            .find(|file| file.exists())
+            .or(files.first())
+            .cloned()
+            .unwrap_or_else(|| project.root.join("moon.yml"))
+    }
+
+    /// Return the config file of a project by ID, if the project exists.
+    pub fn get_project_config_path_by_id(&self, id: &str) -> Option<PathBuf> {
+        self.get_project(id)
+            .map(|project| self.get_project_config_path(project))
+    }
+
+    /// Return the IDs of all tasks that belong to the project, sorted.
+    pub fn get_task_ids(&self, project: &Project) -> Vec<&Id> {
+        let mut ids = project
+            .task_targets
+            .iter()
+            .map(|target| &target.task_id)
+            .collect::<Vec<_>>();
+        ids.sort();
+        ids
+    }
+
+    /// Return the names of all file groups available to the document's project,
+    /// or to all projects when the document is not a project config.
+    pub fn get_file_group_names(&self, document: &ConfigDocument) -> Vec<&Id> {
+        let mut names = match self.get_document_project(document) {
+            Some(project) => project.file_groups.keys().collect::<Vec<_>>(),
+            None => self
+                .get_projects()
+                .into_iter()
+                .flat_map(|project| project.file_groups.keys())
+                .collect(),
+        };
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// Resolve a target locator, as found in `deps`, into all tasks that it references.
+    /// Relative targets are resolved against the provided project.
+    pub fn resolve_targets(&self, locator: &str, project: Option<&Project>) -> Vec<Target> {
+        let target = if locator.contains(':') {
+            Target::parse(locator)
+        } else {
+            Target::parse(&format!("~:{locator}"))
+        };
+
+        let Ok(target) = target else {
+            return vec![];
+        };
+
+        let projects: Vec<&Project> = match &target.scope {
+            TargetScope::All => self.get_projects(),
+            TargetScope::Deps => project
+                .map(|project| {
+                    project
+                        .get_dependency_ids()
+                        .into_iter()
+                        .filter_map(|id| self.get_project(id.as_str()))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            TargetScope::OwnSelf => project.into_iter().collect(),
+            TargetScope::Project(id) => self.get_project(id.as_str()).into_iter().collect(),
+            TargetScope::Tag(tag) => self
+                .get_projects()
+                .into_iter()
+                .filter(|project| project.config.tags.contains(tag))
+                .collect(),
+        };
+
+        projects
+            .into_iter()
+            .filter_map(|project| Target::new(&project.id, &target.task_id).ok())
+            .filter(|target| self.graph.tasks.get_unexpanded(target).is_ok())
+            .collect()
+    }
+
+    /// Return all configuration files that define the task, starting with
+    /// inherited `.moon/tasks` files, followed by the project config.
+    pub fn get_task_locations(&self, target: &Target) -> Vec<TaskLocation> {
+        let mut locations = vec![];
+
+        let Some(project) = target
+            .get_project_id()
+            .and_then(|id| self.get_project(id.as_str()))
+        else {
+            return locations;
+        };
+
+        if let Some(inherited) = &project.inherited {
+            if let Some(layers) = inherited.task_layers.get(target.task_id.as_str()) {
+                for layer in layers {
+                    locations.push(TaskLocation {
+                        file: self.workspace_root.join(layer),
+                        task_id: target.task_id.clone(),
+                    });
+                }
+            }
+        }
+
+        if project.config.tasks.contains_key(&target.task_id) {
+            locations.push(TaskLocation {
+                file: self.get_project_config_path(project),
+                task_id: target.task_id.clone(),
+            });
+        }
+
+        locations
+    }
+}
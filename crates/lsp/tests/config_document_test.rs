@@ -0,0 +1,243 @@
+use moon_lsp::*;
+use std::path::{Path, PathBuf};
+use tower_lsp::lsp_types::{Position, Range};
+
+const PROJECT_CONFIG: &str = r#"language: 'typescript'
+dependsOn:
+  - 'app'
+  - id: 'lib'
+    scope: 'build'
+tasks:
+  build:
+    command: 'vite build'
+    deps:
+    - '^:build'
+    inputs:
+      - '@group(sources)'
+"#;
+
+fn create_document(content: &str) -> ConfigDocument {
+    ConfigDocument::new(
+        ConfigDocumentKind::Project,
+        PathBuf::from("/workspace/apps/app/moon.yml"),
+        content.into(),
+    )
+}
+
+fn range(line: u32, start: u32, end: u32) -> Range {
+    Range::new(Position::new(line, start), Position::new(line, end))
+}
+
+mod detect {
+    use super::*;
+
+    fn detect(path: &str) -> Option<ConfigDocumentKind> {
+        ConfigDocumentKind::detect(Path::new("/workspace"), &Path::new("/workspace").join(path))
+    }
+
+    #[test]
+    fn detects_workspace_configs() {
+        assert_eq!(
+            detect(".moon/workspace.yml"),
+            Some(ConfigDocumentKind::Workspace)
+        );
+        assert_eq!(
+            detect(".moon/toolchain.yml"),
+            Some(ConfigDocumentKind::Toolchain)
+        );
+        assert_eq!(detect(".moon/tasks.yml"), Some(ConfigDocumentKind::Tasks));
+        assert_eq!(
+            detect(".moon/tasks/node.yaml"),
+            Some(ConfigDocumentKind::Tasks)
+        );
+    }
+
+    #[test]
+    fn detects_project_and_template_configs() {
+        assert_eq!(
+            detect("apps/app/moon.yml"),
+            Some(ConfigDocumentKind::Project)
+        );
+        assert_eq!(detect("moon.yaml"), Some(ConfigDocumentKind::Project));
+        assert_eq!(
+            detect("templates/lib/template.yml"),
+            Some(ConfigDocumentKind::Template)
+        );
+    }
+
+    #[test]
+    fn ignores_other_files() {
+        assert_eq!(detect(".moon/other.yml"), None);
+        assert_eq!(detect("apps/app/moon.pkl"), None);
+        assert_eq!(detect("apps/app/package.json"), None);
+        assert_eq!(detect("apps/app/config.yml"), None);
+    }
+}
+
+mod get_word_at {
+    use super::*;
+
+    #[test]
+    fn returns_target() {
+        let document = create_document(PROJECT_CONFIG);
+
+        assert_eq!(
+            document.get_word_at(Position::new(9, 8)),
+            (String::from("^:build"), range(9, 7, 14))
+        );
+    }
+
+    #[test]
+    fn returns_token() {
+        let document = create_document(PROJECT_CONFIG);
+
+        assert_eq!(
+            document.get_word_at(Position::new(11, 12)),
+            (String::from("@group(sources)"), range(11, 9, 24))
+        );
+    }
+
+    #[test]
+    fn excludes_key_colon() {
+        let document = create_document(PROJECT_CONFIG);
+
+        assert_eq!(
+            document.get_word_at(Position::new(6, 3)),
+            (String::from("build"), range(6, 2, 7))
+        );
+    }
+
+    #[test]
+    fn returns_empty_for_whitespace() {
+        let document = create_document(PROJECT_CONFIG);
+
+        assert_eq!(document.get_word_at(Position::new(7, 1)).0, "");
+        assert_eq!(document.get_word_at(Position::new(100, 0)).0, "");
+    }
+}
+
+mod get_key_path_at {
+    use super::*;
+
+    #[test]
+    fn returns_nested_keys() {
+        let document = create_document(PROJECT_CONFIG);
+
+        assert_eq!(
+            document.get_key_path_at(Position::new(7, 16)),
+            ["tasks", "build", "command"]
+        );
+    }
+
+    #[test]
+    fn supports_sequences_at_same_indent() {
+        let document = create_document(PROJECT_CONFIG);
+
+        assert_eq!(
+            document.get_key_path_at(Position::new(9, 8)),
+            ["tasks", "build", "deps"]
+        );
+    }
+
+    #[test]
+    fn supports_sequences_of_mappings() {
+        let document = create_document(PROJECT_CONFIG);
+
+        assert_eq!(
+            document.get_key_path_at(Position::new(3, 10)),
+            ["dependsOn", "id"]
+        );
+        assert_eq!(document.get_key_path_at(Position::new(2, 6)), ["dependsOn"]);
+    }
+
+    #[test]
+    fn excludes_key_when_on_key() {
+        let document = create_document(PROJECT_CONFIG);
+
+        assert_eq!(document.get_key_path_at(Position::new(6, 3)), ["tasks"]);
+        assert!(document.get_key_path_at(Position::new(0, 2)).is_empty());
+    }
+}
+
+mod find_key {
+    use super::*;
+
+    #[test]
+    fn finds_nested_key() {
+        let document = create_document(PROJECT_CONFIG);
+
+        assert_eq!(
+            document.find_key(&["tasks", "build", "deps"]),
+            Some(range(8, 4, 8))
+        );
+        assert_eq!(document.find_key(&["tasks"]), Some(range(5, 0, 5)));
+    }
+
+    #[test]
+    fn returns_none_for_missing_key() {
+        let document = create_document(PROJECT_CONFIG);
+
+        assert_eq!(document.find_key(&["tasks", "test"]), None);
+        assert_eq!(document.find_key(&["build"]), None);
+    }
+}
+
+mod find_value {
+    use super::*;
+
+    #[test]
+    fn finds_sequence_items() {
+        let document = create_document(PROJECT_CONFIG);
+
+        assert_eq!(
+            document.find_value(&["dependsOn"], "app"),
+            Some(range(2, 5, 8))
+        );
+        assert_eq!(
+            document.find_value(&["dependsOn"], "lib"),
+            Some(range(3, 9, 12))
+        );
+    }
+
+    #[test]
+    fn doesnt_search_past_key() {
+        let document = create_document(PROJECT_CONFIG);
+
+        assert_eq!(document.find_value(&["dependsOn"], "vite"), None);
+        assert_eq!(document.find_value(&["dependsOn"], "ap"), None);
+    }
+}
+
+mod is_within_key {
+    use super::*;
+
+    fn path(keys: &[&str]) -> Vec<String> {
+        keys.iter().map(|key| key.to_string()).collect()
+    }
+
+    #[test]
+    fn matches_key_or_item_key() {
+        assert!(is_within_key(&path(&["dependsOn"]), "dependsOn", "id"));
+        assert!(is_within_key(
+            &path(&["dependsOn", "id"]),
+            "dependsOn",
+            "id"
+        ));
+        assert!(is_within_key(
+            &path(&["tasks", "build", "deps"]),
+            "deps",
+            "target"
+        ));
+    }
+
+    #[test]
+    fn doesnt_match_other_keys() {
+        assert!(!is_within_key(&path(&[]), "dependsOn", "id"));
+        assert!(!is_within_key(
+            &path(&["dependsOn", "scope"]),
+            "dependsOn",
+            "id"
+        ));
+        assert!(!is_within_key(&path(&["tasks", "build"]), "deps", "target"));
+    }
+}
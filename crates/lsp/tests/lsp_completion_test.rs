@@ -0,0 +1,127 @@
+use moon_lsp::*;
+use std::path::PathBuf;
+use tower_lsp::lsp_types::{CompletionItem, CompletionTextEdit, InsertTextFormat, Position};
+
+fn complete(content: &str, position: Position) -> Vec<CompletionItem> {
+    let document = ConfigDocument::new(
+        ConfigDocumentKind::Project,
+        PathBuf::from("/workspace/apps/app/moon.yml"),
+        content.into(),
+    );
+
+    get_completions(&document, None, position)
+}
+
+fn get_edit_start(item: &CompletionItem) -> u32 {
+    match item.text_edit.as_ref().unwrap() {
+        CompletionTextEdit::Edit(edit) => edit.range.start.character,
+        CompletionTextEdit::InsertAndReplace(edit) => edit.replace.start.character,
+    }
+}
+
+mod token_functions {
+    use super::*;
+
+    #[test]
+    fn completes_functions() {
+        let items = complete(
+            "tasks:\n  build:\n    inputs:\n      - '@'\n",
+            Position::new(3, 10),
+        );
+
+        assert_eq!(items.len(), TOKEN_FUNCTIONS.len());
+        assert!(items.iter().any(|item| item.label == "@group"));
+        assert!(items.iter().all(|item| {
+            item.insert_text_format == Some(InsertTextFormat::SNIPPET) && get_edit_start(item) == 9
+        }));
+    }
+
+    #[test]
+    fn completes_meta_fields() {
+        let items = complete(
+            "tasks:\n  build:\n    command: 'echo @meta('\n",
+            Position::new(2, 25),
+        );
+
+        assert_eq!(
+            items
+                .iter()
+                .map(|item| item.label.as_str())
+                .collect::<Vec<_>>(),
+            [
+                "channel)",
+                "description)",
+                "maintainers)",
+                "name)",
+                "owner)"
+            ]
+        );
+        assert!(items.iter().all(|item| get_edit_start(item) == 25));
+    }
+
+    #[test]
+    fn doesnt_complete_file_groups_without_workspace() {
+        let items = complete(
+            "tasks:\n  build:\n    command: 'echo @files('\n",
+            Position::new(2, 26),
+        );
+
+        assert!(items.is_empty());
+    }
+}
+
+mod token_variables {
+    use super::*;
+
+    #[test]
+    fn completes_variables() {
+        let items = complete(
+            "tasks:\n  build:\n    command: 'echo $proj'\n",
+            Position::new(2, 24),
+        );
+
+        assert_eq!(items.len(), TOKEN_VARIABLES.len());
+        assert!(items.iter().any(|item| item.label == "$projectRoot"));
+        assert!(items.iter().all(|item| get_edit_start(item) == 19));
+    }
+
+    #[test]
+    fn doesnt_complete_plain_words() {
+        let items = complete(
+            "tasks:\n  build:\n    command: 'echo'\n",
+            Position::new(2, 16),
+        );
+
+        assert!(items.is_empty());
+    }
+}
+
+mod validate {
+    use super::*;
+    use tower_lsp::lsp_types::DiagnosticSeverity;
+
+    fn validate(content: &str) -> Vec<tower_lsp::lsp_types::Diagnostic> {
+        validate_document(&ConfigDocument::new(
+            ConfigDocumentKind::Project,
+            PathBuf::from("/workspace/apps/app/moon.yml"),
+            content.into(),
+        ))
+    }
+
+    #[test]
+    fn passes_for_valid_config() {
+        assert!(validate("").is_empty());
+        assert!(validate("tasks:\n  build:\n    command: 'vite build'\n").is_empty());
+    }
+
+    #[test]
+    fn errors_for_invalid_config() {
+        let diagnostics = validate("tasks:\n  build:\n    command: 'vite build'\n    deps: true\n");
+
+        assert!(!diagnostics.is_empty());
+        assert!(diagnostics.iter().all(|diagnostic| {
+            diagnostic.severity == Some(DiagnosticSeverity::ERROR)
+                && diagnostic.source.as_deref() == Some("moon")
+        }));
+    }
+}
@@ -0,0 +1,385 @@
+use moon_common::Id;
+use moon_lsp::*;
+use moon_project::ProjectType;
+use moon_project_constraints::ProjectConstraintsError;
+use moon_task::Target;
+use moon_test_utils2::WorkspaceMocker;
+use starbase_sandbox::{Sandbox, create_sandbox};
+use std::path::Path;
+use std::sync::Arc;
+use tower_lsp::lsp_types::{GotoDefinitionResponse, HoverContents, Location, Position, Range, Url};
+
+async fn create_workspace(sandbox: &Sandbox) -> LspWorkspace {
+    let graph = WorkspaceMocker::new(sandbox.path())
+        .load_default_configs()
+        .with_default_projects()
+        .with_default_toolchains()
+        .mock_workspace_graph()
+        .await;
+
+    LspWorkspace::new(Arc::new(graph), sandbox.path())
+}
+
+fn load_document(root: &Path, file: &str) -> ConfigDocument {
+    let path = root.join(file);
+
+    ConfigDocument::new(
+        ConfigDocumentKind::detect(root, &path).unwrap(),
+        path.clone(),
+        std::fs::read_to_string(&path).unwrap(),
+    )
+}
+
+fn location(root: &Path, file: &str, range: Range) -> Location {
+    Location::new(Url::from_file_path(root.join(file)).unwrap(), range)
+}
+
+fn range(line: u32, start: u32, end: u32) -> Range {
+    Range::new(Position::new(line, start), Position::new(line, end))
+}
+
+fn map_targets(targets: Vec<Target>) -> Vec<String> {
+    targets
+        .into_iter()
+        .map(|target| target.to_string())
+        .collect()
+}
+
+mod resolve_targets {
+    use super::*;
+
+    #[tokio::test]
+    async fn resolves_deps_scope() {
+        let sandbox = create_sandbox("workspace");
+        let workspace = create_workspace(&sandbox).await;
+        let project = workspace.get_project("app");
+
+        assert_eq!(
+            map_targets(workspace.resolve_targets("^:build", project)),
+            ["lib:build"]
+        );
+    }
+
+    #[tokio::test]
+    async fn resolves_self_scope() {
+        let sandbox = create_sandbox("workspace");
+        let workspace = create_workspace(&sandbox).await;
+        let project = workspace.get_project("app");
+
+        assert_eq!(
+            map_targets(workspace.resolve_targets("~:build", project)),
+            ["app:build"]
+        );
+        assert_eq!(
+            map_targets(workspace.resolve_targets("build", project)),
+            ["app:build"]
+        );
+    }
+
+    #[tokio::test]
+    async fn resolves_tag_scope() {
+        let sandbox = create_sandbox("workspace");
+        let workspace = create_workspace(&sandbox).await;
+
+        assert_eq!(
+            map_targets(workspace.resolve_targets("#frontend:build", None)),
+            ["app:build", "web:build"]
+        );
+    }
+
+    #[tokio::test]
+    async fn resolves_all_scope() {
+        let sandbox = create_sandbox("workspace");
+        let workspace = create_workspace(&sandbox).await;
+
+        assert_eq!(
+            map_targets(workspace.resolve_targets(":build", None)),
+            ["app:build", "lib:build", "web:build"]
+        );
+    }
+
+    #[tokio::test]
+    async fn skips_missing_tasks_and_relative_scopes_without_project() {
+        let sandbox = create_sandbox("workspace");
+        let workspace = create_workspace(&sandbox).await;
+
+        assert!(workspace.resolve_targets("lib:unknown", None).is_empty());
+        assert!(workspace.resolve_targets("^:build", None).is_empty());
+        assert!(workspace.resolve_targets("~:build", None).is_empty());
+    }
+
+    #[tokio::test]
+    async fn locates_inherited_and_project_tasks() {
+        let sandbox = create_sandbox("workspace");
+        let workspace = create_workspace(&sandbox).await;
+
+        let files = workspace
+            .get_task_locations(&Target::parse("app:lint").unwrap())
+            .into_iter()
+            .map(|location| location.file)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            files,
+            [
+                sandbox.path().join(".moon/tasks.yml"),
+                sandbox.path().join("app/moon.yml"),
+            ]
+        );
+    }
+}
+
+mod definition {
+    use super::*;
+
+    #[tokio::test]
+    async fn goes_to_depends_on_project() {
+        let sandbox = create_sandbox("workspace");
+        let workspace = create_workspace(&sandbox).await;
+        let document = load_document(sandbox.path(), "app/moon.yml");
+
+        assert_eq!(
+            get_definition(&document, &workspace, Position::new(2, 6)),
+            Some(GotoDefinitionResponse::Scalar(location(
+                sandbox.path(),
+                "lib/moon.yml",
+                Range::default()
+            )))
+        );
+    }
+
+    #[tokio::test]
+    async fn goes_to_deps_scope_task() {
+        let sandbox = create_sandbox("workspace");
+        let workspace = create_workspace(&sandbox).await;
+        let document = load_document(sandbox.path(), "app/moon.yml");
+
+        assert_eq!(
+            get_definition(&document, &workspace, Position::new(7, 10)),
+            Some(GotoDefinitionResponse::Scalar(location(
+                sandbox.path(),
+                "lib/moon.yml",
+                range(3, 2, 7)
+            )))
+        );
+    }
+
+    #[tokio::test]
+    async fn goes_to_self_scope_task() {
+        let sandbox = create_sandbox("workspace");
+        let workspace = create_workspace(&sandbox).await;
+        let document = load_document(sandbox.path(), "app/moon.yml");
+
+        assert_eq!(
+            get_definition(&document, &workspace, Position::new(11, 10)),
+            Some(GotoDefinitionResponse::Scalar(location(
+                sandbox.path(),
+                "app/moon.yml",
+                range(4, 2, 7)
+            )))
+        );
+    }
+
+    #[tokio::test]
+    async fn goes_to_all_tag_scope_tasks() {
+        let sandbox = create_sandbox("workspace");
+        let workspace = create_workspace(&sandbox).await;
+        let document = load_document(sandbox.path(), "app/moon.yml");
+
+        assert_eq!(
+            get_definition(&document, &workspace, Position::new(15, 12)),
+            Some(GotoDefinitionResponse::Array(vec![
+                location(sandbox.path(), "app/moon.yml", range(4, 2, 7)),
+                location(sandbox.path(), "web/moon.yml", range(2, 2, 7)),
+            ]))
+        );
+    }
+
+    #[tokio::test]
+    async fn doesnt_go_to_plain_values() {
+        let sandbox = create_sandbox("workspace");
+        let workspace = create_workspace(&sandbox).await;
+        let document = load_document(sandbox.path(), "app/moon.yml");
+
+        assert_eq!(
+            get_definition(&document, &workspace, Position::new(5, 16)),
+            None
+        );
+    }
+}
+
+mod hover {
+    use super::*;
+
+    fn get_content(
+        document: &ConfigDocument,
+        workspace: &LspWorkspace,
+        position: Position,
+    ) -> Option<String> {
+        get_hover(document, workspace, position).map(|hover| match hover.contents {
+            HoverContents::Markup(markup) => markup.value,
+            _ => unreachable!(),
+        })
+    }
+
+    #[tokio::test]
+    async fn shows_task_key() {
+        let sandbox = create_sandbox("workspace");
+        let workspace = create_workspace(&sandbox).await;
+        let document = load_document(sandbox.path(), "app/moon.yml");
+        let content = get_content(&document, &workspace, Position::new(4, 3)).unwrap();
+
+        assert!(content.starts_with("**app:build**"));
+        assert!(content.contains("vite build"));
+        assert!(content.contains("- **Dependencies:** `lib:build`"));
+    }
+
+    #[tokio::test]
+    async fn shows_deps_scope_task() {
+        let sandbox = create_sandbox("workspace");
+        let workspace = create_workspace(&sandbox).await;
+        let document = load_document(sandbox.path(), "app/moon.yml");
+        let content = get_content(&document, &workspace, Position::new(7, 10)).unwrap();
+
+        assert!(content.starts_with("**lib:build**"));
+        assert!(content.contains("tsc"));
+    }
+
+    #[tokio::test]
+    async fn shows_all_tag_scope_tasks() {
+        let sandbox = create_sandbox("workspace");
+        let workspace = create_workspace(&sandbox).await;
+        let document = load_document(sandbox.path(), "app/moon.yml");
+        let content = get_content(&document, &workspace, Position::new(15, 12)).unwrap();
+        let tasks = content.split("\n\n---\n\n").collect::<Vec<_>>();
+
+        assert_eq!(tasks.len(), 2);
+        assert!(tasks[0].starts_with("**app:build**"));
+        assert!(tasks[1].starts_with("**web:build**"));
+    }
+
+    #[tokio::test]
+    async fn shows_inherited_task() {
+        let sandbox = create_sandbox("workspace");
+        let workspace = create_workspace(&sandbox).await;
+        let document = load_document(sandbox.path(), "app/moon.yml");
+        let content = get_content(&document, &workspace, Position::new(16, 3)).unwrap();
+
+        assert!(content.starts_with("**app:lint**"));
+        assert!(content.contains("eslint --fix"));
+    }
+
+    #[tokio::test]
+    async fn shows_depends_on_project() {
+        let sandbox = create_sandbox("workspace");
+        let workspace = create_workspace(&sandbox).await;
+        let document = load_document(sandbox.path(), "app/moon.yml");
+        let content = get_content(&document, &workspace, Position::new(2, 6)).unwrap();
+
+        assert!(content.starts_with("**lib**\n\nShared utilities."));
+        assert!(content.contains("- **Source:** `lib`"));
+        assert!(content.contains("- **Tasks:** `build`, `lint`"));
+    }
+
+    #[tokio::test]
+    async fn doesnt_show_unknown_projects() {
+        let sandbox = create_sandbox("workspace");
+        let workspace = create_workspace(&sandbox).await;
+        let document = ConfigDocument::new(
+            ConfigDocumentKind::Project,
+            sandbox.path().join("app/moon.yml"),
+            "dependsOn:\n  - 'unknown'\n".into(),
+        );
+
+        assert!(get_content(&document, &workspace, Position::new(1, 6)).is_none());
+    }
+}
+
+mod diagnostics {
+    use super::*;
+
+    #[tokio::test]
+    async fn passes_for_fixture_configs() {
+        let sandbox = create_sandbox("workspace");
+
+        for file in [
+            ".moon/tasks.yml",
+            "app/moon.yml",
+            "lib/moon.yml",
+            "web/moon.yml",
+        ] {
+            assert!(validate_document(&load_document(sandbox.path(), file)).is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn locates_invalid_setting() {
+        let sandbox = create_sandbox("workspace");
+        let mut document = load_document(sandbox.path(), "lib/moon.yml");
+        document.content.push_str("    deps: true\n");
+
+        let diagnostics = validate_document(&document);
+
+        assert!(!diagnostics.is_empty());
+        assert!(
+            diagnostics
+                .iter()
+                .all(|diagnostic| diagnostic.range.start.line == 5)
+        );
+    }
+
+    #[tokio::test]
+    async fn locates_constraint_dependency() {
+        let sandbox = create_sandbox("workspace");
+        let workspace = create_workspace(&sandbox).await;
+        let error = miette::Report::new(ProjectConstraintsError::InvalidTagRelationship {
+            source_id: Id::raw("app"),
+            source_tag: Id::raw("frontend"),
+            dep_id: Id::raw("lib"),
+            allowed: "#backend".into(),
+        });
+
+        let (path, diagnostic) = create_graph_diagnostic(
+            &error,
+            Some(&workspace),
+            &sandbox.path().join(".moon/workspace.yml"),
+        );
+
+        assert_eq!(path, sandbox.path().join("app/moon.yml"));
+        assert_eq!(diagnostic.range, range(2, 5, 8));
+    }
+
+    #[tokio::test]
+    async fn locates_missing_required_tag() {
+        let sandbox = create_sandbox("workspace");
+        let workspace = create_workspace(&sandbox).await;
+        let error = miette::Report::new(ProjectConstraintsError::MissingRequiredTag {
+            source_id: Id::raw("web"),
+            source_type: ProjectType::Application,
+            allowed: "#ui".into(),
+        });
+
+        let (path, diagnostic) = create_graph_diagnostic(
+            &error,
+            Some(&workspace),
+            &sandbox.path().join(".moon/workspace.yml"),
+        );
+
+        assert_eq!(path, sandbox.path().join("web/moon.yml"));
+        assert_eq!(diagnostic.range, range(0, 0, 4));
+    }
+
+    #[tokio::test]
+    async fn falls_back_for_other_errors() {
+        let sandbox = create_sandbox("workspace");
+        let workspace = create_workspace(&sandbox).await;
+        let fallback = sandbox.path().join(".moon/workspace.yml");
+
+        let (path, diagnostic) =
+            create_graph_diagnostic(&miette::miette!("Failed!"), Some(&workspace), &fallback);
+
+        assert_eq!(path, fallback);
+        assert_eq!(diagnostic.range, Range::default());
+        assert_eq!(diagnostic.message, "Failed!");
+    }
+}
@@ -0,0 +1,7 @@
+mod migration;
+mod nx;
+mod turborepo;
+
+pub use migration::*;
+pub use nx::*;
+pub use turborepo::*;
@@ -0,0 +1,188 @@
+use moon_common::Id;
+use moon_config::{
+    InputPath, NodePackageManager, OutputPath, PartialInheritedTasksConfig, PartialProjectConfig,
+    PartialProjectWorkspaceConfig, PartialProjectWorkspaceInheritedTasksConfig, PartialTaskArgs,
+    PartialTaskConfig,
+};
+use rustc_hash::FxHashSet;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// A moon project that configuration will be migrated into.
+#[derive(Debug, Default)]
+pub struct MigrateProject {
+    /// Current configuration of the project, loaded from `moon.*`.
+    pub config: PartialProjectConfig,
+
+    /// ID of the project in moon.
+    pub id: Id,
+
+    /// Name of the project in the tool being migrated from,
+    /// for example, the `package.json` name.
+    pub name: Option<String>,
+
+    /// Scripts defined in the project's `package.json`.
+    pub scripts: FxHashSet<String>,
+
+    /// Workspace relative source of the project.
+    pub source: String,
+}
+
+impl MigrateProject {
+    pub fn is_root_level(&self) -> bool {
+        self.source.is_empty() || self.source == "."
+    }
+}
+
+/// State of a migration, that is shared between all migrators.
+#[derive(Debug, Default)]
+pub struct Migration {
+    /// Package manager to execute `package.json` scripts with.
+    pub package_manager: NodePackageManager,
+
+    /// Projects that can be migrated into, keyed by moon ID.
+    pub projects: BTreeMap<Id, MigrateProject>,
+
+    /// Current configuration of the global tasks file.
+    pub tasks_config: PartialInheritedTasksConfig,
+
+    /// Whether the global tasks configuration has been changed.
+    pub tasks_config_changed: bool,
+
+    /// IDs of projects that have been changed.
+    pub touched_projects: FxHashSet<Id>,
+
+    /// Settings that could not be migrated.
+    pub warnings: Vec<String>,
+}
+
+impl Migration {
+    pub fn new(
+        package_manager: NodePackageManager,
+        tasks_config: PartialInheritedTasksConfig,
+    ) -> Self {
+        Self {
+            package_manager,
+            tasks_config,
+            ..Default::default()
+        }
+    }
+
+    pub fn add_project(&mut self, project: MigrateProject) {
+        self.projects.insert(project.id.clone(), project);
+    }
+
+    /// Find the moon ID of a project by its name in the tool being migrated from.
+    /// If no name matches, will fallback to matching on the moon ID.
+    pub fn find_project_id(&self, name: &str) -> Option<Id> {
+        self.projects
+            .values()
+            .find(|project| project.name.as_deref() == Some(name))
+            .or_else(|| self.projects.get(name))
+            .map(|project| project.id.clone())
+    }
+
+    pub fn find_root_project_id(&self) -> Option<Id> {
+        self.projects
+            .values()
+            .find(|project| project.is_root_level())
+            .map(|project| project.id.clone())
+    }
+
+    pub fn get_project_config_mut(&mut self, id: &Id) -> &mut PartialProjectConfig {
+        self.touched_projects.insert(id.clone());
+
+        &mut self
+            .projects
+            .get_mut(id)
+            .expect("Unknown project to migrate!")
+            .config
+    }
+
+    pub fn get_tasks_config_mut(&mut self) -> &mut PartialInheritedTasksConfig {
+        self.tasks_config_changed = true;
+
+        &mut self.tasks_config
+    }
+
+    pub fn insert_global_task(&mut self, task_id: Id, config: PartialTaskConfig) {
+        self.get_tasks_config_mut()
+            .tasks
+            .get_or_insert_default()
+            .insert(task_id, config);
+    }
+
+    pub fn insert_project_task(&mut self, project_id: &Id, task_id: Id, config: PartialTaskConfig) {
+        self.get_project_config_mut(project_id)
+            .tasks
+            .get_or_insert_default()
+            .insert(task_id, config);
+    }
+
+    /// Exclude an inherited task from a project, as it's not applicable.
+    pub fn exclude_inherited_task(&mut self, project_id: &Id, task_id: &Id) {
+        let exclude = self
+            .get_project_config_mut(project_id)
+            .workspace
+            .get_or_insert_with(PartialProjectWorkspaceConfig::default)
+            .inherited_tasks
+            .get_or_insert_with(PartialProjectWorkspaceInheritedTasksConfig::default)
+            .exclude
+            .get_or_insert_default();
+
+        if !exclude.contains(task_id) {
+            exclude.push(task_id.to_owned());
+        }
+    }
+
+    pub fn warn(&mut self, message: impl AsRef<str>) {
+        self.warnings.push(message.as_ref().to_owned());
+    }
+
+    /// Create a command that runs a `package.json` script through the package manager.
+    pub fn create_script_command(&self, script: &str) -> PartialTaskArgs {
+        PartialTaskArgs::List(vec![
+            match self.package_manager {
+                NodePackageManager::Bun => "bun",
+                NodePackageManager::Npm => "npm",
+                NodePackageManager::Pnpm => "pnpm",
+                NodePackageManager::Yarn => "yarn",
+            }
+            .into(),
+            "run".into(),
+            script.into(),
+        ])
+    }
+
+    pub fn parse_input(&mut self, label: &str, value: &str) -> Option<InputPath> {
+        match InputPath::from_str(value) {
+            Ok(input) => Some(input),
+            Err(error) => {
+                self.warn(format!(
+                    "{label}: Unable to migrate input <file>{value}</file>, {error}."
+                ));
+
+                None
+            }
+        }
+    }
+
+    pub fn parse_output(&mut self, label: &str, value: &str) -> Option<OutputPath> {
+        match OutputPath::from_str(value) {
+            Ok(output) => Some(output),
+            Err(error) => {
+                self.warn(format!(
+                    "{label}: Unable to migrate output <file>{value}</file>, {error}."
+                ));
+
+                None
+            }
+        }
+    }
+}
+
+/// Convert a task or script name into a valid task ID,
+/// for example, `build:prod` into `build-prod`.
+pub fn to_task_id(name: &str) -> miette::Result<Id> {
+    Ok(Id::clean(name)?)
+}
@@ -0,0 +1,732 @@
+use crate::migration::{Migration, to_task_id};
+use moon_common::Id;
+use moon_config::{
+    InputPath, PartialProjectDependsOn, PartialTaskArgs, PartialTaskConfig, PartialTaskDependency,
+    PartialTaskOptionsConfig, ProjectType,
+};
+use moon_target::Target;
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// An entry in the `dependsOn` setting of a target.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum NxDependsOn {
+    Target(String),
+    Object(NxDependsOnObject),
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct NxDependsOnObject {
+    pub dependencies: Option<bool>,
+    pub params: Option<String>,
+    pub projects: Option<NxProjects>,
+    pub target: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum NxProjects {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// An entry in the `inputs` or `namedInputs` settings.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum NxInput {
+    Path(String),
+    Object(NxInputObject),
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NxInputObject {
+    pub dependencies: Option<bool>,
+    pub dependent_tasks_output_files: Option<String>,
+    pub env: Option<String>,
+    pub external_dependencies: Option<Vec<String>>,
+    pub fileset: Option<String>,
+    pub input: Option<String>,
+    pub projects: Option<NxProjects>,
+    pub runtime: Option<String>,
+}
+
+/// A target in `project.json`, or a target default in `nx.json`.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NxTarget {
+    pub cache: Option<bool>,
+    pub command: Option<String>,
+    pub configurations: Map<String, Value>,
+    pub continuous: Option<bool>,
+    pub depends_on: Option<Vec<NxDependsOn>>,
+    pub executor: Option<String>,
+    pub inputs: Option<Vec<NxInput>>,
+    pub options: Map<String, Value>,
+    pub outputs: Option<Vec<String>>,
+}
+
+/// The root `nx.json` file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NxJson {
+    pub named_inputs: BTreeMap<String, Vec<NxInput>>,
+    pub target_defaults: BTreeMap<String, NxTarget>,
+}
+
+/// A project level `project.json` file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NxProjectJson {
+    pub implicit_dependencies: Vec<String>,
+    pub name: Option<String>,
+    pub named_inputs: BTreeMap<String, Vec<NxInput>>,
+    pub project_type: Option<String>,
+    pub tags: Vec<String>,
+    pub targets: BTreeMap<String, NxTarget>,
+}
+
+// Inputs are derived from named inputs, so we must track both
+// the workspace and project level named inputs for resolution
+struct NxContext<'data> {
+    label: String,
+    named_inputs: FxHashMap<&'data str, &'data [NxInput]>,
+    options: &'data Map<String, Value>,
+}
+
+/// Migrate the root `nx.json`, and project level `project.json` files,
+/// to the global tasks configuration, and project `moon.*` files.
+pub fn migrate_nx(
+    migration: &mut Migration,
+    root: NxJson,
+    projects: BTreeMap<Id, NxProjectJson>,
+) -> miette::Result<()> {
+    let empty_options = Map::new();
+    let workspace_context = NxContext {
+        label: "Setting <property>namedInputs</property>".into(),
+        named_inputs: root
+            .named_inputs
+            .iter()
+            .map(|(name, inputs)| (name.as_str(), inputs.as_slice()))
+            .collect(),
+        options: &empty_options,
+    };
+
+    // Named inputs are file groups, so only migrate file based inputs
+    for (name, inputs) in &root.named_inputs {
+        let group = resolve_file_group(
+            migration,
+            &workspace_context,
+            inputs,
+            &mut FxHashSet::from_iter([name.to_owned()]),
+        );
+
+        if !group.is_empty() {
+            migration
+                .get_tasks_config_mut()
+                .file_groups
+                .get_or_insert_default()
+                .insert(Id::clean(name)?, group);
+        }
+    }
+
+    for (project_id, project) in &projects {
+        migrate_project(migration, &root, project_id, project)?;
+    }
+
+    Ok(())
+}
+
+fn migrate_project(
+    migration: &mut Migration,
+    root: &NxJson,
+    project_id: &Id,
+    project: &NxProjectJson,
+) -> miette::Result<()> {
+    let empty_options = Map::new();
+    let mut context = NxContext {
+        label: format!("Project <id>{project_id}</id>"),
+        named_inputs: root
+            .named_inputs
+            .iter()
+            .map(|(name, inputs)| (name.as_str(), inputs.as_slice()))
+            .collect(),
+        options: &empty_options,
+    };
+
+    for (name, inputs) in &project.named_inputs {
+        context.named_inputs.insert(name, inputs);
+    }
+
+    let mut depends_on = vec![];
+
+    for dep in &project.implicit_dependencies {
+        if dep.starts_with('!') {
+            migration.warn(format!(
+                "{}: Excluding implicit dependencies (<id>{dep}</id>) is not supported.",
+                context.label
+            ));
+        } else if let Some(dep_id) = migration.find_project_id(dep) {
+            depends_on.push(PartialProjectDependsOn::String(dep_id));
+        } else {
+            migration.warn(format!(
+                "{}: Unable to find a project for the implicit dependency <id>{dep}</id>.",
+                context.label
+            ));
+        }
+    }
+
+    let mut file_groups = vec![];
+
+    for (name, inputs) in &project.named_inputs {
+        let group = resolve_file_group(
+            migration,
+            &context,
+            inputs,
+            &mut FxHashSet::from_iter([name.to_owned()]),
+        );
+
+        if !group.is_empty() {
+            file_groups.push((Id::clean(name)?, group));
+        }
+    }
+
+    let mut tasks = vec![];
+
+    for (name, target) in &project.targets {
+        let target = merge_target_defaults(root, name, target);
+        let target_context = NxContext {
+            label: format!("Target <id>{name}</id> in project <id>{project_id}</id>"),
+            named_inputs: context.named_inputs.clone(),
+            options: &target.options,
+        };
+
+        if let Some(config) = create_task(migration, &target_context, &target)? {
+            tasks.push((to_task_id(name)?, config));
+        }
+    }
+
+    let config = migration.get_project_config_mut(project_id);
+
+    if config.type_of.is_none() {
+        config.type_of = match project.project_type.as_deref() {
+            Some("application") => Some(ProjectType::Application),
+            Some("library") => Some(ProjectType::Library),
+            _ => None,
+        };
+    }
+
+    if !project.tags.is_empty() {
+        let tags = config.tags.get_or_insert_default();
+
+        for tag in &project.tags {
+            let tag = Id::clean(tag)?;
+
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+    }
+
+    if !depends_on.is_empty() {
+        config.depends_on.get_or_insert_default().extend(depends_on);
+    }
+
+    if !file_groups.is_empty() {
+        config
+            .file_groups
+            .get_or_insert_default()
+            .extend(file_groups);
+    }
+
+    for (task_id, task) in tasks {
+        migration.insert_project_task(project_id, task_id, task);
+    }
+
+    Ok(())
+}
+
+/// Target defaults can be keyed by executor or target name,
+/// with executor taking precedence.
+fn merge_target_defaults(root: &NxJson, name: &str, target: &NxTarget) -> NxTarget {
+    let Some(defaults) = target
+        .executor
+        .as_deref()
+        .and_then(|executor| root.target_defaults.get(executor))
+        .or_else(|| root.target_defaults.get(name))
+    else {
+        return target.clone();
+    };
+
+    let mut merged = defaults.clone();
+
+    if target.cache.is_some() {
+        merged.cache = target.cache;
+    }
+
+    if target.command.is_some() || target.executor.is_some() {
+        merged.command = target.command.clone();
+        merged.executor = target.executor.clone();
+    }
+
+    if !target.configurations.is_empty() {
+        merged.configurations = target.configurations.clone();
+    }
+
+    if target.continuous.is_some() {
+        merged.continuous = target.continuous;
+    }
+
+    if target.depends_on.is_some() {
+        merged.depends_on = target.depends_on.clone();
+    }
+
+    if target.inputs.is_some() {
+        merged.inputs = target.inputs.clone();
+    }
+
+    if target.outputs.is_some() {
+        merged.outputs = target.outputs.clone();
+    }
+
+    for (key, value) in &target.options {
+        merged.options.insert(key.to_owned(), value.to_owned());
+    }
+
+    merged
+}
+
+fn create_task(
+    migration: &mut Migration,
+    context: &NxContext,
+    target: &NxTarget,
+) -> miette::Result<Option<PartialTaskConfig>> {
+    let label = &context.label;
+    let mut config = PartialTaskConfig::default();
+    let mut options = PartialTaskOptionsConfig::default();
+
+    match (target.executor.as_deref(), &target.command) {
+        (None | Some("nx:run-commands"), command) => {
+            let mut commands = vec![];
+
+            if let Some(command) = command {
+                commands.push(command.to_owned());
+            }
+
+            if let Some(Value::String(command)) = target.options.get("command") {
+                commands.push(command.to_owned());
+            }
+
+            if let Some(Value::Array(list)) = target.options.get("commands") {
+                for item in list {
+                    match item {
+                        Value::String(command) => commands.push(command.to_owned()),
+                        Value::Object(object) => {
+                            if let Some(Value::String(command)) = object.get("command") {
+                                commands.push(command.to_owned());
+                            }
+                        }
+                        _ => {}
+                    };
+                }
+            }
+
+            if commands.is_empty() {
+                migration.warn(format!(
+                    "{label}: Unable to migrate, as no command is defined."
+                ));
+
+                return Ok(None);
+            }
+
+            if commands.len() > 1 && target.options.get("parallel") != Some(&Value::Bool(false)) {
+                migration.warn(format!(
+                    "{label}: Multiple commands will run sequentially instead of in parallel."
+                ));
+            }
+
+            let mut command = commands.join(" && ");
+
+            if let Some(Value::String(args)) = target.options.get("args") {
+                command.push(' ');
+                command.push_str(args);
+            }
+
+            config.command = Some(PartialTaskArgs::String(command));
+
+            // Commands run from the workspace root by default
+            match target.options.get("cwd") {
+                Some(Value::String(cwd)) => {
+                    if cwd != "{projectRoot}" {
+                        migration.warn(format!(
+                            "{label}: The working directory <file>{cwd}</file> is not supported, tasks run from the project or workspace root."
+                        ));
+                    }
+                }
+                _ => {
+                    options.run_from_workspace_root = Some(true);
+                }
+            };
+
+            if let Some(Value::Object(env)) = target.options.get("env") {
+                config.env = Some(
+                    env.iter()
+                        .map(|(key, value)| {
+                            (
+                                key.to_owned(),
+                                match value {
+                                    Value::String(value) => value.to_owned(),
+                                    other => other.to_string(),
+                                },
+                            )
+                        })
+                        .collect(),
+                );
+            }
+
+            let unsupported = target
+                .options
+                .keys()
+                .filter(|key| {
+                    !matches!(
+                        key.as_str(),
+                        "args" | "command" | "commands" | "cwd" | "env" | "parallel"
+                    )
+                })
+                .map(|key| format!("<property>{key}</property>"))
+                .collect::<Vec<_>>();
+
+            if !unsupported.is_empty() {
+                migration.warn(format!(
+                    "{label}: The options {} are not supported.",
+                    unsupported.join(", ")
+                ));
+            }
+        }
+        (Some("nx:run-script"), _) => {
+            let Some(Value::String(script)) = target.options.get("script") else {
+                migration.warn(format!(
+                    "{label}: Unable to migrate, as no <property>script</property> option is defined."
+                ));
+
+                return Ok(None);
+            };
+
+            config.command = Some(migration.create_script_command(script));
+        }
+        (Some("nx:noop"), _) => {
+            config.command = Some(PartialTaskArgs::String("noop".into()));
+        }
+        (Some(executor), _) => {
+            migration.warn(format!(
+                "{label}: Unable to migrate, as the executor <id>{executor}</id> is not supported. Use <property>nx:run-commands</property> or <property>nx:run-script</property> instead."
+            ));
+
+            return Ok(None);
+        }
+    };
+
+    if !target.configurations.is_empty() {
+        migration.warn(format!(
+            "{label}: <property>configurations</property> are not supported, create a task for each configuration instead."
+        ));
+    }
+
+    if let Some(depends_on) = &target.depends_on {
+        let mut deps = vec![];
+
+        for dep in depends_on {
+            deps.extend(create_dependencies(migration, label, dep)?);
+        }
+
+        if !deps.is_empty() {
+            config.deps = Some(deps);
+        }
+    }
+
+    if let Some(inputs) = &target.inputs {
+        let inputs = resolve_inputs(migration, context, inputs, &mut FxHashSet::default());
+
+        if !inputs.is_empty() {
+            config.inputs = Some(inputs);
+        }
+    }
+
+    if let Some(outputs) = &target.outputs {
+        let mut paths = vec![];
+
+        for output in outputs {
+            match interpolate_path(output, context.options) {
+                Ok(path) => paths.extend(migration.parse_output(label, &path)),
+                Err(error) => migration.warn(format!("{label}: {error}")),
+            };
+        }
+
+        if !paths.is_empty() {
+            config.outputs = Some(paths);
+        }
+    }
+
+    // Targets are not cached unless explicitly enabled
+    if target.cache != Some(true) {
+        options.cache = Some(false);
+    }
+
+    if target.continuous == Some(true) {
+        options.persistent = Some(true);
+    }
+
+    if options != PartialTaskOptionsConfig::default() {
+        config.options = Some(options);
+    }
+
+    Ok(Some(config))
+}
+
+fn create_dependencies(
+    migration: &mut Migration,
+    label: &str,
+    dep: &NxDependsOn,
+) -> miette::Result<Vec<PartialTaskDependency>> {
+    let mut deps = vec![];
+
+    match dep {
+        NxDependsOn::Target(value) => {
+            if value.contains('*') {
+                migration.warn(format!(
+                    "{label}: Dependencies with glob patterns (<id>{value}</id>) are not supported."
+                ));
+            } else if let Some(task) = value.strip_prefix('^') {
+                deps.push(Target::parse(&format!("^:{}", to_task_id(task)?))?);
+            } else {
+                deps.push(Target::new_self(to_task_id(value)?)?);
+            }
+        }
+        NxDependsOn::Object(object) => {
+            let task_id = to_task_id(&object.target)?;
+
+            if object.params.is_some() {
+                migration.warn(format!(
+                    "{label}: <property>params</property> for dependency <id>{}</id> are not supported.",
+                    object.target
+                ));
+            }
+
+            match &object.projects {
+                Some(NxProjects::One(value)) if value == "self" => {
+                    deps.push(Target::new_self(task_id)?);
+                }
+                Some(NxProjects::One(value)) if value == "dependencies" => {
+                    deps.push(Target::parse(&format!("^:{task_id}"))?);
+                }
+                Some(NxProjects::One(value)) => {
+                    deps.extend(create_project_target(migration, label, value, &task_id)?);
+                }
+                Some(NxProjects::Many(values)) => {
+                    for value in values {
+                        deps.extend(create_project_target(migration, label, value, &task_id)?);
+                    }
+                }
+                None => {
+                    if object.dependencies == Some(true) {
+                        deps.push(Target::parse(&format!("^:{task_id}"))?);
+                    } else {
+                        deps.push(Target::new_self(task_id)?);
+                    }
+                }
+            };
+        }
+    };
+
+    Ok(deps
+        .into_iter()
+        .map(PartialTaskDependency::Target)
+        .collect())
+}
+
+fn create_project_target(
+    migration: &mut Migration,
+    label: &str,
+    project_name: &str,
+    task_id: &Id,
+) -> miette::Result<Option<Target>> {
+    match migration.find_project_id(project_name) {
+        Some(project_id) => Ok(Some(Target::new(project_id, task_id)?)),
+        None => {
+            migration.warn(format!(
+                "{label}: Unable to find a project for the dependency <id>{project_name}:{task_id}</id>."
+            ));
+
+            Ok(None)
+        }
+    }
+}
+
+/// Resolve a list of inputs into file paths only, as file groups
+/// do not support environment variables or token functions.
+fn resolve_file_group(
+    migration: &mut Migration,
+    context: &NxContext,
+    inputs: &[NxInput],
+    visited: &mut FxHashSet<String>,
+) -> Vec<InputPath> {
+    resolve_inputs(migration, context, inputs, visited)
+        .into_iter()
+        .flat_map(|input| match input {
+            InputPath::TokenFunc(group) => {
+                let name = group
+                    .trim_start_matches("@group(")
+                    .trim_end_matches(')')
+                    .to_owned();
+
+                context
+                    .named_inputs
+                    .get(name.as_str())
+                    .map(|inputs| {
+                        let mut visited = visited.clone();
+                        visited.insert(name);
+
+                        resolve_file_group(migration, context, inputs, &mut visited)
+                    })
+                    .unwrap_or_default()
+            }
+            InputPath::EnvVar(var) | InputPath::EnvVarGlob(var) => {
+                migration.warn(format!(
+                    "{}: Environment variable <symbol>{var}</symbol> cannot be used in a file group.",
+                    context.label
+                ));
+
+                vec![]
+            }
+            other => vec![other],
+        })
+        .collect()
+}
+
+fn resolve_inputs(
+    migration: &mut Migration,
+    context: &NxContext,
+    inputs: &[NxInput],
+    visited: &mut FxHashSet<String>,
+) -> Vec<InputPath> {
+    let label = &context.label;
+    let mut paths = vec![];
+
+    for input in inputs {
+        let value = match input {
+            NxInput::Path(value) => value,
+            NxInput::Object(object) => {
+                if let Some(fileset) = &object.fileset {
+                    fileset
+                } else if let Some(var) = &object.env {
+                    paths.extend(migration.parse_input(label, &format!("${var}")));
+                    continue;
+                } else if let Some(input) = &object.input {
+                    if object.dependencies == Some(true) || object.projects.is_some() {
+                        migration.warn(format!(
+                            "{label}: Inputs from other projects (<id>{input}</id>) are not supported."
+                        ));
+                    } else if context.named_inputs.contains_key(input.as_str()) {
+                        paths.extend(migration.parse_input(label, &format!("@group({input})")));
+                    } else {
+                        migration.warn(format!(
+                            "{label}: Unable to find the named input <id>{input}</id>."
+                        ));
+                    }
+
+                    continue;
+                } else {
+                    migration.warn(format!(
+                        "{label}: Runtime, external dependency, and dependent task output inputs are not supported."
+                    ));
+
+                    continue;
+                }
+            }
+        };
+
+        // Inputs from dependencies
+        if let Some(name) = value.strip_prefix('^') {
+            migration.warn(format!(
+                "{label}: Inputs from dependencies (<id>^{name}</id>) are not supported."
+            ));
+
+            continue;
+        }
+
+        // Named inputs
+        if context.named_inputs.contains_key(value.as_str()) {
+            if visited.contains(value) {
+                continue;
+            }
+
+            paths.extend(migration.parse_input(label, &format!("@group({value})")));
+            continue;
+        }
+
+        match interpolate_path(value, context.options) {
+            Ok(path) => paths.extend(migration.parse_input(label, &path)),
+            Err(error) => migration.warn(format!("{label}: {error}")),
+        };
+    }
+
+    paths
+}
+
+/// Convert an Nx path into a moon path, by replacing `{projectRoot}` and
+/// `{workspaceRoot}` with relative paths, and `{options.*}` with the
+/// value of the option. Paths without a placeholder are workspace relative.
+pub fn interpolate_path(value: &str, options: &Map<String, Value>) -> Result<String, String> {
+    let (negated, value) = match value.strip_prefix('!') {
+        Some(inner) => (true, inner),
+        None => (false, value),
+    };
+
+    let mut path = value.replace("{projectName}", "$project");
+
+    while let Some(start) = path.find("{options.") {
+        let Some(end) = path[start..].find('}') else {
+            break;
+        };
+
+        let key = &path[start + 9..start + end];
+
+        let Some(Value::String(option)) = options.get(key) else {
+            return Err(format!(
+                "Unable to migrate path <file>{value}</file>, as the option <property>{key}</property> is not defined."
+            ));
+        };
+
+        path = format!("{}{}{}", &path[..start], option, &path[start + end + 1..]);
+    }
+
+    let (workspace_relative, path) = if let Some(rest) = path.strip_prefix("{projectRoot}") {
+        let rest = rest.trim_start_matches('/');
+
+        (
+            false,
+            if rest.is_empty() { "**/*" } else { rest }.to_owned(),
+        )
+    } else if let Some(rest) = path.strip_prefix("{workspaceRoot}") {
+        (true, rest.trim_start_matches('/').to_owned())
+    } else {
+        (true, path.trim_start_matches("./").to_owned())
+    };
+
+    if path.contains('{') {
+        return Err(format!(
+            "Unable to migrate path <file>{value}</file>, as it contains an unsupported placeholder."
+        ));
+    }
+
+    Ok(match (negated, workspace_relative) {
+        (true, true) => format!("!/{path}"),
+        (true, false) => format!("!{path}"),
+        (false, true) => format!("/{path}"),
+        (false, false) => path,
+    })
+}
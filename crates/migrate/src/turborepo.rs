@@ -0,0 +1,330 @@
+use crate::migration::{Migration, to_task_id};
+use moon_common::Id;
+use moon_config::{
+    PartialTaskConfig, PartialTaskDependency, PartialTaskOptionsConfig, TaskOutputStyle,
+};
+use moon_target::Target;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// A task (v2) or pipeline (v1) entry in `turbo.json`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TurboTask {
+    pub cache: Option<bool>,
+    pub depends_on: Vec<String>,
+    pub dot_env: Vec<String>,
+    pub env: Vec<String>,
+    pub inputs: Vec<String>,
+    pub interactive: Option<bool>,
+    #[serde(alias = "outputMode")]
+    pub output_logs: Option<String>,
+    pub outputs: Vec<String>,
+    pub pass_through_env: Vec<String>,
+    pub persistent: Option<bool>,
+}
+
+/// A root or package level `turbo.json` file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TurboJson {
+    pub global_dependencies: Vec<String>,
+    pub global_dot_env: Vec<String>,
+    pub global_env: Vec<String>,
+    pub global_pass_through_env: Vec<String>,
+    #[serde(alias = "pipeline")]
+    pub tasks: BTreeMap<String, TurboTask>,
+}
+
+/// Migrate the root `turbo.json`, and package level `turbo.json` files,
+/// to the global tasks configuration, and project `moon.*` files.
+pub fn migrate_turborepo(
+    migration: &mut Migration,
+    root: TurboJson,
+    packages: BTreeMap<Id, TurboJson>,
+) -> miette::Result<()> {
+    migrate_global_settings(migration, &root);
+
+    let mut global_tasks = vec![];
+
+    for (name, task) in &root.tasks {
+        // Root-level task: //#task
+        if let Some(script) = name.strip_prefix("//#") {
+            let label = format!("Task <id>{name}</id>");
+
+            let Some(project_id) = migration.find_root_project_id() else {
+                migration.warn(format!(
+                    "{label}: Root-level tasks require a root-level project, but none has been configured."
+                ));
+
+                continue;
+            };
+
+            let config = create_task(migration, &label, script, task)?;
+
+            migration.insert_project_task(&project_id, to_task_id(script)?, config);
+
+            continue;
+        }
+
+        // Package-level task: package#task
+        if let Some((package_name, script)) = name.split_once('#') {
+            let label = format!("Task <id>{name}</id>");
+
+            let Some(project_id) = migration.find_project_id(package_name) else {
+                migration.warn(format!(
+                    "{label}: Unable to find a project for the package <id>{package_name}</id>."
+                ));
+
+                continue;
+            };
+
+            let config = create_task(migration, &label, script, task)?;
+
+            migration.insert_project_task(&project_id, to_task_id(script)?, config);
+
+            continue;
+        }
+
+        // Global task: task
+        let label = format!("Task <id>{name}</id>");
+        let task_id = to_task_id(name)?;
+        let config = create_task(migration, &label, name, task)?;
+
+        migration.insert_global_task(task_id.clone(), config);
+        global_tasks.push((name, task_id));
+    }
+
+    // Turborepo only runs a task if the package has a matching script,
+    // while moon will inherit the task into every project, so exclude
+    // the tasks from projects that do not have the script
+    let project_ids = migration.projects.keys().cloned().collect::<Vec<_>>();
+
+    for project_id in project_ids {
+        for (script, task_id) in &global_tasks {
+            if !migration.projects[&project_id].scripts.contains(*script) {
+                migration.exclude_inherited_task(&project_id, task_id);
+            }
+        }
+    }
+
+    for (project_id, package) in packages {
+        for (name, task) in &package.tasks {
+            let label = format!("Task <id>{name}</id> in project <id>{project_id}</id>");
+
+            if name.contains('#') {
+                migration.warn(format!(
+                    "{label}: Package configurations cannot reference other packages."
+                ));
+
+                continue;
+            }
+
+            let config = create_task(migration, &label, name, task)?;
+
+            migration.insert_project_task(&project_id, to_task_id(name)?, config);
+        }
+
+        if !package.global_dependencies.is_empty()
+            || !package.global_env.is_empty()
+            || !package.global_pass_through_env.is_empty()
+        {
+            migration.warn(format!(
+                "Project <id>{project_id}</id>: Global settings are only supported in the root <file>turbo.json</file>."
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn migrate_global_settings(migration: &mut Migration, root: &TurboJson) {
+    let mut implicit_inputs = vec![];
+
+    for dep in &root.global_dependencies {
+        let path = match dep.strip_prefix('!') {
+            Some(path) => format!("!/{path}"),
+            None => format!("/{dep}"),
+        };
+
+        if let Some(input) =
+            migration.parse_input("Setting <property>globalDependencies</property>", &path)
+        {
+            implicit_inputs.push(input);
+        }
+    }
+
+    for var in &root.global_env {
+        if var.starts_with('!') {
+            migration.warn(format!(
+                "Setting <property>globalEnv</property>: Excluding environment variables (<symbol>{var}</symbol>) is not supported."
+            ));
+        } else if let Some(input) =
+            migration.parse_input("Setting <property>globalEnv</property>", &format!("${var}"))
+        {
+            implicit_inputs.push(input);
+        }
+    }
+
+    if !implicit_inputs.is_empty() {
+        migration
+            .get_tasks_config_mut()
+            .implicit_inputs
+            .get_or_insert_default()
+            .extend(implicit_inputs);
+    }
+
+    if !root.global_pass_through_env.is_empty() {
+        migration.warn(
+            "Setting <property>globalPassThroughEnv</property>: All environment variables are passed through to tasks, so this setting is not necessary.",
+        );
+    }
+
+    if !root.global_dot_env.is_empty() {
+        migration.warn(
+            "Setting <property>globalDotEnv</property>: Not supported, configure the <property>envFile</property> task option instead.",
+        );
+    }
+}
+
+fn create_task(
+    migration: &mut Migration,
+    label: &str,
+    script: &str,
+    task: &TurboTask,
+) -> miette::Result<PartialTaskConfig> {
+    let mut config = PartialTaskConfig {
+        command: Some(migration.create_script_command(script)),
+        ..Default::default()
+    };
+    let mut options = PartialTaskOptionsConfig::default();
+    let mut deps = vec![];
+    let mut inputs = vec![];
+    let mut outputs = vec![];
+
+    for dep in &task.depends_on {
+        // v1 environment variable dependencies
+        if dep.starts_with('$') {
+            inputs.extend(migration.parse_input(label, dep));
+            continue;
+        }
+
+        let target = if let Some(dep_script) = dep.strip_prefix('^') {
+            Target::parse(&format!("^:{}", to_task_id(dep_script)?))?
+        } else if let Some(dep_script) = dep.strip_prefix("//#") {
+            let Some(project_id) = migration.find_root_project_id() else {
+                migration.warn(format!(
+                    "{label}: Unable to migrate dependency <id>{dep}</id>, as a root-level project has not been configured."
+                ));
+
+                continue;
+            };
+
+            Target::new(project_id, to_task_id(dep_script)?)?
+        } else if let Some((package_name, dep_script)) = dep.split_once('#') {
+            let Some(project_id) = migration.find_project_id(package_name) else {
+                migration.warn(format!(
+                    "{label}: Unable to migrate dependency <id>{dep}</id>, as no project was found for the package <id>{package_name}</id>."
+                ));
+
+                continue;
+            };
+
+            Target::new(project_id, to_task_id(dep_script)?)?
+        } else {
+            Target::new_self(to_task_id(dep)?)?
+        };
+
+        deps.push(PartialTaskDependency::Target(target));
+    }
+
+    // Turborepo includes all files by default, but moon replaces
+    // the defaults when inputs are configured
+    if !task.env.is_empty() && task.inputs.is_empty() {
+        inputs.extend(migration.parse_input(label, "**/*"));
+    }
+
+    for input in &task.inputs {
+        let path = if input == "$TURBO_DEFAULT$" {
+            "**/*".to_owned()
+        } else if let Some(path) = input.strip_prefix("$TURBO_ROOT$/") {
+            format!("/{path}")
+        } else if let Some(path) = input.strip_prefix("!$TURBO_ROOT$/") {
+            format!("!/{path}")
+        } else {
+            input.to_owned()
+        };
+
+        inputs.extend(migration.parse_input(label, &path));
+    }
+
+    for var in &task.env {
+        if var.starts_with('!') {
+            migration.warn(format!(
+                "{label}: Excluding environment variables (<symbol>{var}</symbol>) is not supported."
+            ));
+        } else {
+            inputs.extend(migration.parse_input(label, &format!("${var}")));
+        }
+    }
+
+    for output in &task.outputs {
+        outputs.extend(migration.parse_output(label, output));
+    }
+
+    if task.cache == Some(false) {
+        options.cache = Some(false);
+    }
+
+    if task.interactive == Some(true) {
+        options.interactive = Some(true);
+    }
+
+    if task.persistent == Some(true) {
+        options.persistent = Some(true);
+    }
+
+    if let Some(output_logs) = &task.output_logs {
+        match output_logs.as_str() {
+            "full" => {}
+            "errors-only" => options.output_style = Some(TaskOutputStyle::BufferOnlyFailure),
+            "hash-only" => options.output_style = Some(TaskOutputStyle::Hash),
+            "none" => options.output_style = Some(TaskOutputStyle::None),
+            other => {
+                migration.warn(format!(
+                    "{label}: The <property>outputLogs</property> value <symbol>{other}</symbol> is not supported."
+                ));
+            }
+        };
+    }
+
+    if !task.pass_through_env.is_empty() {
+        migration.warn(format!(
+            "{label}: <property>passThroughEnv</property> is not necessary, as all environment variables are passed through to tasks."
+        ));
+    }
+
+    if !task.dot_env.is_empty() {
+        migration.warn(format!(
+            "{label}: <property>dotEnv</property> is not supported, configure the <property>envFile</property> task option instead."
+        ));
+    }
+
+    if !deps.is_empty() {
+        config.deps = Some(deps);
+    }
+
+    if !inputs.is_empty() {
+        config.inputs = Some(inputs);
+    }
+
+    if !outputs.is_empty() {
+        config.outputs = Some(outputs);
+    }
+
+    if options != PartialTaskOptionsConfig::default() {
+        config.options = Some(options);
+    }
+
+    Ok(config)
+}
@@ -0,0 +1,326 @@
+use moon_common::Id;
+use moon_config::*;
+use moon_migrate::*;
+use moon_target::Target;
+use serde_json::{Map, json};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+fn create_migration() -> Migration {
+    let mut migration = Migration::new(NodePackageManager::Npm, Default::default());
+
+    for (id, source) in [("app", "apps/app"), ("lib", "libs/lib")] {
+        migration.add_project(MigrateProject {
+            id: Id::raw(id),
+            name: Some(format!("nx-{id}")),
+            source: source.into(),
+            ..Default::default()
+        });
+    }
+
+    migration
+}
+
+fn parse_nx(value: serde_json::Value) -> NxJson {
+    serde_json::from_value(value).unwrap()
+}
+
+fn parse_project(value: serde_json::Value) -> BTreeMap<Id, NxProjectJson> {
+    BTreeMap::from_iter([(Id::raw("app"), serde_json::from_value(value).unwrap())])
+}
+
+fn get_task<'a>(migration: &'a Migration, id: &str) -> &'a PartialTaskConfig {
+    migration.projects["app"]
+        .config
+        .tasks
+        .as_ref()
+        .unwrap()
+        .get(id)
+        .unwrap()
+}
+
+mod migrate_nx {
+    use super::*;
+
+    #[test]
+    fn maps_project_settings() {
+        let mut migration = create_migration();
+
+        migrate_nx(
+            &mut migration,
+            NxJson::default(),
+            parse_project(json!({
+                "name": "nx-app",
+                "projectType": "application",
+                "tags": ["scope:app"],
+                "implicitDependencies": ["nx-lib", "unknown"]
+            })),
+        )
+        .unwrap();
+
+        let config = &migration.projects["app"].config;
+
+        assert_eq!(config.type_of, Some(ProjectType::Application));
+        assert_eq!(config.tags, Some(vec![Id::raw("scope-app")]));
+        assert_eq!(
+            config.depends_on,
+            Some(vec![PartialProjectDependsOn::String(Id::raw("lib"))])
+        );
+        assert_eq!(migration.warnings.len(), 1);
+    }
+
+    #[test]
+    fn maps_named_inputs_to_file_groups() {
+        let mut migration = create_migration();
+
+        migrate_nx(
+            &mut migration,
+            parse_nx(json!({
+                "namedInputs": {
+                    "default": ["{projectRoot}/**/*", "sharedGlobals"],
+                    "sharedGlobals": ["{workspaceRoot}/babel.config.json"],
+                    "production": ["default", "!{projectRoot}/**/*.spec.ts", { "env": "NODE_ENV" }]
+                }
+            })),
+            BTreeMap::new(),
+        )
+        .unwrap();
+
+        let groups = migration.tasks_config.file_groups.as_ref().unwrap();
+
+        assert_eq!(
+            groups.get("default").unwrap(),
+            &vec![
+                InputPath::from_str("**/*").unwrap(),
+                InputPath::from_str("/babel.config.json").unwrap(),
+            ]
+        );
+        assert_eq!(
+            groups.get("production").unwrap(),
+            &vec![
+                InputPath::from_str("**/*").unwrap(),
+                InputPath::from_str("/babel.config.json").unwrap(),
+                InputPath::from_str("!**/*.spec.ts").unwrap(),
+            ]
+        );
+        assert_eq!(migration.warnings.len(), 1);
+    }
+
+    #[test]
+    fn maps_run_commands() {
+        let mut migration = create_migration();
+
+        migrate_nx(
+            &mut migration,
+            NxJson::default(),
+            parse_project(json!({
+                "targets": {
+                    "build": {
+                        "executor": "nx:run-commands",
+                        "options": {
+                            "command": "tsc",
+                            "args": "--build",
+                            "cwd": "{projectRoot}",
+                            "env": { "NODE_ENV": "production" }
+                        },
+                        "outputs": ["{projectRoot}/dist", "{workspaceRoot}/coverage"],
+                        "cache": true
+                    },
+                    "lint": {
+                        "command": "eslint ."
+                    }
+                }
+            })),
+        )
+        .unwrap();
+
+        let build = get_task(&migration, "build");
+
+        assert_eq!(
+            build.command,
+            Some(PartialTaskArgs::String("tsc --build".into()))
+        );
+        assert_eq!(
+            build.env.as_ref().unwrap().get("NODE_ENV").unwrap(),
+            "production"
+        );
+        assert_eq!(
+            build.outputs,
+            Some(vec![
+                OutputPath::from_str("dist").unwrap(),
+                OutputPath::from_str("/coverage").unwrap(),
+            ])
+        );
+        assert_eq!(build.options, None);
+
+        let lint = get_task(&migration, "lint");
+        let options = lint.options.as_ref().unwrap();
+
+        assert_eq!(
+            lint.command,
+            Some(PartialTaskArgs::String("eslint .".into()))
+        );
+        assert_eq!(options.cache, Some(false));
+        assert_eq!(options.run_from_workspace_root, Some(true));
+    }
+
+    #[test]
+    fn maps_run_script_and_continuous() {
+        let mut migration = create_migration();
+
+        migrate_nx(
+            &mut migration,
+            NxJson::default(),
+            parse_project(json!({
+                "targets": {
+                    "dev": {
+                        "executor": "nx:run-script",
+                        "options": { "script": "dev" },
+                        "continuous": true
+                    }
+                }
+            })),
+        )
+        .unwrap();
+
+        let dev = get_task(&migration, "dev");
+
+        assert_eq!(
+            dev.command,
+            Some(PartialTaskArgs::List(vec![
+                "npm".into(),
+                "run".into(),
+                "dev".into()
+            ]))
+        );
+        assert_eq!(dev.options.as_ref().unwrap().persistent, Some(true));
+    }
+
+    #[test]
+    fn warns_for_unsupported_executors() {
+        let mut migration = create_migration();
+
+        migrate_nx(
+            &mut migration,
+            NxJson::default(),
+            parse_project(json!({
+                "targets": {
+                    "build": {
+                        "executor": "@nx/webpack:webpack"
+                    }
+                }
+            })),
+        )
+        .unwrap();
+
+        assert!(migration.projects["app"].config.tasks.is_none());
+        assert_eq!(migration.warnings.len(), 1);
+        assert!(migration.warnings[0].contains("@nx/webpack:webpack"));
+    }
+
+    #[test]
+    fn maps_dependencies() {
+        let mut migration = create_migration();
+
+        migrate_nx(
+            &mut migration,
+            NxJson::default(),
+            parse_project(json!({
+                "targets": {
+                    "build": {
+                        "command": "tsc",
+                        "dependsOn": [
+                            "^build",
+                            "codegen",
+                            { "target": "check", "projects": "nx-lib" },
+                            { "target": "test", "dependencies": true }
+                        ]
+                    }
+                }
+            })),
+        )
+        .unwrap();
+
+        assert_eq!(
+            get_task(&migration, "build").deps,
+            Some(vec![
+                PartialTaskDependency::Target(Target::parse("^:build").unwrap()),
+                PartialTaskDependency::Target(Target::parse("~:codegen").unwrap()),
+                PartialTaskDependency::Target(Target::parse("lib:check").unwrap()),
+                PartialTaskDependency::Target(Target::parse("^:test").unwrap()),
+            ])
+        );
+    }
+
+    #[test]
+    fn merges_target_defaults() {
+        let mut migration = create_migration();
+
+        migrate_nx(
+            &mut migration,
+            parse_nx(json!({
+                "namedInputs": {
+                    "production": ["{projectRoot}/src/**/*"]
+                },
+                "targetDefaults": {
+                    "build": {
+                        "cache": true,
+                        "dependsOn": ["^build"],
+                        "inputs": ["production", "^production"]
+                    }
+                }
+            })),
+            parse_project(json!({
+                "targets": {
+                    "build": {
+                        "command": "vite build"
+                    }
+                }
+            })),
+        )
+        .unwrap();
+
+        let build = get_task(&migration, "build");
+
+        assert_eq!(build.options.as_ref().unwrap().cache, None);
+        assert_eq!(
+            build.deps,
+            Some(vec![PartialTaskDependency::Target(
+                Target::parse("^:build").unwrap()
+            )])
+        );
+        assert_eq!(
+            build.inputs,
+            Some(vec![InputPath::from_str("@group(production)").unwrap()])
+        );
+        assert_eq!(migration.warnings.len(), 1);
+    }
+}
+
+mod interpolate_path {
+    use super::*;
+
+    #[test]
+    fn handles_tokens() {
+        let mut options = Map::new();
+        options.insert("outputPath".into(), json!("dist/app"));
+
+        assert_eq!(
+            interpolate_path("{projectRoot}/src", &options).unwrap(),
+            "src"
+        );
+        assert_eq!(
+            interpolate_path("{workspaceRoot}/jest.config.ts", &options).unwrap(),
+            "/jest.config.ts"
+        );
+        assert_eq!(
+            interpolate_path("{options.outputPath}", &options).unwrap(),
+            "/dist/app"
+        );
+        assert_eq!(
+            interpolate_path("!{projectRoot}/**/*.md", &options).unwrap(),
+            "!**/*.md"
+        );
+        assert!(interpolate_path("{options.unknown}", &options).is_err());
+    }
+}
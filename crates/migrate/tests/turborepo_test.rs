@@ -0,0 +1,342 @@
+use moon_common::Id;
+use moon_config::*;
+use moon_migrate::*;
+use moon_target::Target;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+fn create_project(id: &str, source: &str, scripts: &[&str]) -> MigrateProject {
+    MigrateProject {
+        id: Id::raw(id),
+        name: Some(format!("@scope/{id}")),
+        scripts: scripts.iter().map(|script| script.to_string()).collect(),
+        source: source.into(),
+        ..Default::default()
+    }
+}
+
+fn create_migration() -> Migration {
+    let mut migration = Migration::new(NodePackageManager::Pnpm, Default::default());
+    migration.add_project(create_project("root", ".", &["lint"]));
+    migration.add_project(create_project("app", "apps/app", &["build", "dev"]));
+    migration.add_project(create_project("lib", "packages/lib", &["build"]));
+    migration
+}
+
+fn parse_turbo(value: serde_json::Value) -> TurboJson {
+    serde_json::from_value(value).unwrap()
+}
+
+fn get_global_task<'a>(migration: &'a Migration, id: &str) -> &'a PartialTaskConfig {
+    migration
+        .tasks_config
+        .tasks
+        .as_ref()
+        .unwrap()
+        .get(id)
+        .unwrap()
+}
+
+fn get_project_task<'a>(
+    migration: &'a Migration,
+    project: &str,
+    id: &str,
+) -> &'a PartialTaskConfig {
+    migration.projects[project]
+        .config
+        .tasks
+        .as_ref()
+        .unwrap()
+        .get(id)
+        .unwrap()
+}
+
+mod migrate_turborepo {
+    use super::*;
+
+    #[test]
+    fn creates_global_tasks() {
+        let mut migration = create_migration();
+
+        migrate_turborepo(
+            &mut migration,
+            parse_turbo(json!({
+                "tasks": {
+                    "build": {
+                        "dependsOn": ["^build"],
+                        "outputs": ["dist/**"]
+                    }
+                }
+            })),
+            BTreeMap::new(),
+        )
+        .unwrap();
+
+        let task = get_global_task(&migration, "build");
+
+        assert!(migration.tasks_config_changed);
+        assert_eq!(
+            task.command,
+            Some(PartialTaskArgs::List(vec![
+                "pnpm".into(),
+                "run".into(),
+                "build".into()
+            ]))
+        );
+        assert_eq!(
+            task.deps,
+            Some(vec![PartialTaskDependency::Target(
+                Target::parse("^:build").unwrap()
+            )])
+        );
+        assert_eq!(
+            task.outputs,
+            Some(vec![OutputPath::from_str("dist/**").unwrap()])
+        );
+    }
+
+    #[test]
+    fn supports_pipeline_field() {
+        let mut migration = create_migration();
+
+        migrate_turborepo(
+            &mut migration,
+            parse_turbo(json!({
+                "pipeline": {
+                    "build": {}
+                }
+            })),
+            BTreeMap::new(),
+        )
+        .unwrap();
+
+        get_global_task(&migration, "build");
+    }
+
+    #[test]
+    fn excludes_global_tasks_from_projects_without_script() {
+        let mut migration = create_migration();
+
+        migrate_turborepo(
+            &mut migration,
+            parse_turbo(json!({
+                "tasks": {
+                    "dev": {
+                        "persistent": true,
+                        "cache": false
+                    }
+                }
+            })),
+            BTreeMap::new(),
+        )
+        .unwrap();
+
+        let exclude = |id: &str| {
+            migration.projects[id]
+                .config
+                .workspace
+                .as_ref()
+                .and_then(|ws| ws.inherited_tasks.as_ref())
+                .and_then(|it| it.exclude.clone())
+        };
+
+        assert_eq!(exclude("app"), None);
+        assert_eq!(exclude("lib"), Some(vec![Id::raw("dev")]));
+        assert_eq!(exclude("root"), Some(vec![Id::raw("dev")]));
+
+        let options = get_global_task(&migration, "dev").options.as_ref().unwrap();
+
+        assert_eq!(options.cache, Some(false));
+        assert_eq!(options.persistent, Some(true));
+    }
+
+    #[test]
+    fn maps_root_and_package_tasks() {
+        let mut migration = create_migration();
+
+        migrate_turborepo(
+            &mut migration,
+            parse_turbo(json!({
+                "tasks": {
+                    "//#lint": {},
+                    "@scope/app#build": {
+                        "dependsOn": ["@scope/lib#build", "//#lint", "codegen"]
+                    }
+                }
+            })),
+            BTreeMap::new(),
+        )
+        .unwrap();
+
+        get_project_task(&migration, "root", "lint");
+
+        assert_eq!(
+            get_project_task(&migration, "app", "build").deps,
+            Some(vec![
+                PartialTaskDependency::Target(Target::parse("lib:build").unwrap()),
+                PartialTaskDependency::Target(Target::parse("root:lint").unwrap()),
+                PartialTaskDependency::Target(Target::parse("~:codegen").unwrap()),
+            ])
+        );
+        assert!(migration.tasks_config.tasks.is_none());
+    }
+
+    #[test]
+    fn warns_for_unknown_packages() {
+        let mut migration = create_migration();
+
+        migrate_turborepo(
+            &mut migration,
+            parse_turbo(json!({
+                "tasks": {
+                    "unknown#build": {}
+                }
+            })),
+            BTreeMap::new(),
+        )
+        .unwrap();
+
+        assert_eq!(migration.warnings.len(), 1);
+        assert!(migration.warnings[0].contains("Unable to find a project"));
+    }
+
+    #[test]
+    fn maps_inputs_and_env() {
+        let mut migration = create_migration();
+
+        migrate_turborepo(
+            &mut migration,
+            parse_turbo(json!({
+                "globalDependencies": ["tsconfig.json"],
+                "globalEnv": ["CI"],
+                "tasks": {
+                    "build": {
+                        "env": ["NODE_ENV"],
+                        "inputs": ["$TURBO_DEFAULT$", "$TURBO_ROOT$/shared/**", "!README.md"]
+                    },
+                    "test": {
+                        "env": ["API_KEY"]
+                    }
+                }
+            })),
+            BTreeMap::new(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            migration.tasks_config.implicit_inputs,
+            Some(vec![
+                InputPath::from_str("/tsconfig.json").unwrap(),
+                InputPath::from_str("$CI").unwrap(),
+            ])
+        );
+        assert_eq!(
+            get_global_task(&migration, "build").inputs,
+            Some(vec![
+                InputPath::from_str("**/*").unwrap(),
+                InputPath::from_str("/shared/**").unwrap(),
+                InputPath::from_str("!README.md").unwrap(),
+                InputPath::from_str("$NODE_ENV").unwrap(),
+            ])
+        );
+        assert_eq!(
+            get_global_task(&migration, "test").inputs,
+            Some(vec![
+                InputPath::from_str("**/*").unwrap(),
+                InputPath::from_str("$API_KEY").unwrap(),
+            ])
+        );
+    }
+
+    #[test]
+    fn maps_output_logs() {
+        let mut migration = create_migration();
+
+        migrate_turborepo(
+            &mut migration,
+            parse_turbo(json!({
+                "tasks": {
+                    "build": {
+                        "outputLogs": "errors-only"
+                    },
+                    "test": {
+                        "outputMode": "hash-only"
+                    }
+                }
+            })),
+            BTreeMap::new(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            get_global_task(&migration, "build")
+                .options
+                .as_ref()
+                .unwrap()
+                .output_style,
+            Some(TaskOutputStyle::BufferOnlyFailure)
+        );
+        assert_eq!(
+            get_global_task(&migration, "test")
+                .options
+                .as_ref()
+                .unwrap()
+                .output_style,
+            Some(TaskOutputStyle::Hash)
+        );
+    }
+
+    #[test]
+    fn warns_for_unsupported_settings() {
+        let mut migration = create_migration();
+
+        migrate_turborepo(
+            &mut migration,
+            parse_turbo(json!({
+                "globalDotEnv": [".env"],
+                "globalPassThroughEnv": ["AWS_*"],
+                "tasks": {
+                    "build": {
+                        "dotEnv": [".env"],
+                        "passThroughEnv": ["AWS_*"],
+                        "env": ["!SECRET"]
+                    }
+                }
+            })),
+            BTreeMap::new(),
+        )
+        .unwrap();
+
+        assert_eq!(migration.warnings.len(), 5);
+    }
+
+    #[test]
+    fn migrates_package_configs() {
+        let mut migration = create_migration();
+
+        migrate_turborepo(
+            &mut migration,
+            parse_turbo(json!({})),
+            BTreeMap::from_iter([(
+                Id::raw("lib"),
+                parse_turbo(json!({
+                    "tasks": {
+                        "build": {
+                            "outputs": ["lib/**"]
+                        }
+                    }
+                })),
+            )]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            get_project_task(&migration, "lib", "build").outputs,
+            Some(vec![OutputPath::from_str("lib/**").unwrap()])
+        );
+        assert!(migration.touched_projects.contains("lib"));
+        assert!(!migration.touched_projects.contains("app"));
+        assert!(!migration.tasks_config_changed);
+    }
+}
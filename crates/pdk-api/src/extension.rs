@@ -42,3 +42,30 @@ api_struct!(
         pub context: MoonContext,
     }
 );
+
+// SECRETS
+
+api_struct!(
+    /// Input passed to the `resolve_secret` function.
+    pub struct ResolveSecretInput {
+        /// Current moon context.
+        pub context: MoonContext,
+
+        /// The key of the secret to resolve, as configured by the
+        /// task's `secrets.*.key` setting, or the secret's name.
+        pub key: String,
+
+        /// Target of the task that requires the secret.
+        pub target: String,
+    }
+);
+
+api_struct!(
+    /// Output returned from the `resolve_secret` function.
+    #[serde(default)]
+    pub struct ResolveSecretOutput {
+        /// The resolved value, or `None` if the secret does not exist.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub value: Option<String>,
+    }
+);
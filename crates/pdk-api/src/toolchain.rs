@@ -1,5 +1,6 @@
 use crate::context::*;
 use crate::prompts::*;
+use moon_common::Id;
 use moon_config::{DockerPruneConfig, DockerScaffoldConfig, UnresolvedVersionSpec, VersionSpec};
 use moon_project::ProjectFragment;
 use moon_task::TaskFragment;
@@ -150,6 +151,48 @@ api_struct!(
     }
 );
 
+// CONSTRAINTS
+
+api_struct!(
+    /// Input passed to the `check_project_constraints` function.
+    pub struct CheckProjectConstraintsInput {
+        /// Current moon context.
+        pub context: MoonContext,
+
+        /// Other projects that the project being checked depends on.
+        pub project_dependencies: Vec<ProjectFragment>,
+
+        /// Fragment of the project being checked.
+        pub project: ProjectFragment,
+
+        /// Merged toolchain configuration.
+        pub toolchain_config: serde_json::Value,
+    }
+);
+
+api_struct!(
+    /// A constraint that has been violated by a project.
+    pub struct ProjectConstraintViolation {
+        /// ID of the dependency project that caused the violation,
+        /// if the violation is for a relationship.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub dependency_id: Option<Id>,
+
+        /// A message describing the violation.
+        pub message: String,
+    }
+);
+
+api_struct!(
+    /// Output returned from the `check_project_constraints` function.
+    #[serde(default)]
+    pub struct CheckProjectConstraintsOutput {
+        /// List of constraints that have been violated.
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        pub violations: Vec<ProjectConstraintViolation>,
+    }
+);
+
 // SETUP / TEARDOWN
 
 api_struct!(
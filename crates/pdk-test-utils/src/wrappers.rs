@@ -24,6 +24,15 @@ impl ExtensionTestWrapper {
             .await
             .unwrap();
     }
+
+    pub async fn resolve_secret(&self, mut input: ResolveSecretInput) -> ResolveSecretOutput {
+        input.context = self.create_context();
+
+        self.plugin
+            .call_func_with("resolve_secret", input)
+            .await
+            .unwrap()
+    }
 }
 
 pub struct ToolchainTestWrapper {
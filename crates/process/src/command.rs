@@ -1,15 +1,17 @@
 // This implementation is loosely based on Cargo's:
 // https://github.com/rust-lang/cargo/blob/master/crates/cargo-util/src/process_builder.rs
 
+use crate::redact::redact_secrets;
 use crate::shell::Shell;
 use moon_common::{color, is_test_env};
-use moon_console::Console;
+use moon_console::{Console, TaskPane};
 use rustc_hash::{FxHashMap, FxHasher};
 use std::hash::Hasher;
 use std::{
     ffi::{OsStr, OsString},
     sync::Arc,
 };
+use tokio::sync::Notify;
 
 pub struct Command {
     pub args: Vec<OsString>,
@@ -32,12 +34,24 @@ pub struct Command {
     /// Values to pass to stdin
     pub input: Vec<OsString>,
 
+    /// Kill the running child process when notified
+    pub interrupt: Option<Arc<Notify>>,
+
+    /// Dashboard pane to write streamed output to, instead of the console
+    pub pane: Option<TaskPane>,
+
     /// Prefix to prepend to all log lines
     pub prefix: Option<String>,
 
     /// Log the command to the terminal before running
     pub print_command: bool,
 
+    /// Group to register the running child process under
+    pub process_group: Option<String>,
+
+    /// Secret values to redact from logs and captured output
+    pub secrets: Vec<String>,
+
     /// Shell to wrap executing commands in
     pub shell: Option<Shell>,
 
@@ -56,8 +70,12 @@ impl Command {
             error_on_nonzero: true,
             escape_args: true,
             input: vec![],
+            interrupt: None,
+            pane: None,
             prefix: None,
             print_command: false,
+            process_group: None,
+            secrets: vec![],
             shell: Some(Shell::default()),
             console: None,
         }
@@ -179,6 +197,20 @@ impl Command {
         self
     }
 
+    pub fn secret<V: AsRef<str>>(&mut self, value: V) -> &mut Self {
+        let value = value.as_ref();
+
+        if !value.is_empty() && !self.secrets.iter().any(|existing| existing == value) {
+            self.secrets.push(value.to_owned());
+        }
+
+        self
+    }
+
+    pub fn redact<T: AsRef<str>>(&self, value: T) -> String {
+        redact_secrets(value, &self.secrets)
+    }
+
     pub fn get_bin_name(&self) -> String {
         self.bin.to_string_lossy().to_string()
     }
@@ -223,6 +255,11 @@ impl Command {
         self
     }
 
+    pub fn set_process_group<G: AsRef<str>>(&mut self, group: G) -> &mut Self {
+        self.process_group = Some(group.as_ref().to_owned());
+        self
+    }
+
     pub fn set_print_command(&mut self, state: bool) -> &mut Self {
         self.print_command = state;
         self
@@ -258,6 +295,16 @@ impl Command {
         self
     }
 
+    pub fn with_interrupt(&mut self, interrupt: Arc<Notify>) -> &mut Self {
+        self.interrupt = Some(interrupt);
+        self
+    }
+
+    pub fn with_pane(&mut self, pane: TaskPane) -> &mut Self {
+        self.pane = Some(pane);
+        self
+    }
+
     pub fn with_shell(&mut self, shell: Shell) -> &mut Self {
         self.shell = Some(shell);
         self
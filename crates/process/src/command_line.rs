@@ -1,4 +1,5 @@
 use crate::command::Command;
+use crate::redact::redact_secrets;
 use moon_args::join_args_os;
 use moon_common::color;
 use moon_env_var::GlobalEnvBag;
@@ -10,6 +11,7 @@ use std::path::Path;
 pub struct CommandLine {
     pub command: Vec<OsString>,
     pub input: Vec<OsString>,
+    pub secrets: Vec<String>,
     pub shell: bool,
 }
 
@@ -64,6 +66,7 @@ impl CommandLine {
         CommandLine {
             command: command_line,
             input: input_line,
+            secrets: command.secrets.clone(),
             shell: in_shell,
         }
     }
@@ -96,7 +99,10 @@ impl CommandLine {
             }
         }
 
-        command.to_string_lossy().trim().replace('\n', " ")
+        redact_secrets(
+            command.to_string_lossy().trim().replace('\n', " "),
+            &self.secrets,
+        )
     }
 
     pub fn format(command: &str, workspace_root: &Path, working_dir: &Path) -> String {
@@ -4,7 +4,9 @@ use crate::command_line::CommandLine;
 use crate::output_to_error;
 use crate::process_error::ProcessError;
 use crate::process_registry::ProcessRegistry;
+use crate::redact::{LineRedactor, redact_secrets_bytes};
 use crate::shared_child::SharedChild;
+use crate::signal::SignalType;
 use miette::IntoDiagnostic;
 use moon_common::color;
 use moon_env_var::GlobalEnvBag;
@@ -52,7 +54,7 @@ impl Command {
             })?
         };
 
-        let shared_child = registry.add_running(child).await;
+        let shared_child = self.add_running_child(&registry, child).await;
 
         self.pre_log_command(&line, &shared_child);
 
@@ -68,7 +70,10 @@ impl Command {
 
         registry.remove_running(shared_child).await;
 
-        let output = result?;
+        let mut output = result?;
+
+        output.stdout = redact_secrets_bytes(output.stdout, &self.secrets);
+        output.stderr = redact_secrets_bytes(output.stderr, &self.secrets);
 
         self.handle_nonzero_status(&output, true)?;
 
@@ -89,7 +94,7 @@ impl Command {
             error: Box::new(error),
         })?;
 
-        let shared_child = registry.add_running(child).await;
+        let shared_child = self.add_running_child(&registry, child).await;
         let stdin = shared_child.take_stdin().await;
         let stdout = shared_child.take_stdout().await;
         let stderr = shared_child.take_stderr().await;
@@ -158,16 +163,22 @@ impl Command {
 
         let output = Output {
             status,
-            stdout: stdout_handle
-                .await
-                .into_diagnostic()??
-                .join("\n")
-                .into_bytes(),
-            stderr: stderr_handle
-                .await
-                .into_diagnostic()??
-                .join("\n")
-                .into_bytes(),
+            stdout: redact_secrets_bytes(
+                stdout_handle
+                    .await
+                    .into_diagnostic()??
+                    .join("\n")
+                    .into_bytes(),
+                &self.secrets,
+            ),
+            stderr: redact_secrets_bytes(
+                stderr_handle
+                    .await
+                    .into_diagnostic()??
+                    .join("\n")
+                    .into_bytes(),
+                &self.secrets,
+            ),
         };
 
         self.handle_nonzero_status(&output, true)?;
@@ -197,7 +208,7 @@ impl Command {
             })?
         };
 
-        let shared_child = registry.add_running(child).await;
+        let shared_child = self.add_running_child(&registry, child).await;
 
         self.pre_log_command(&line, &shared_child);
 
@@ -249,7 +260,19 @@ impl Command {
             self.write_input_to_child(&mut child, &line).await?;
         }
 
-        let shared_child = registry.add_running(child).await;
+        let shared_child = self.add_running_child(&registry, child).await;
+
+        // Kill the child early when interrupted, for example, when
+        // a persistent task is restarted from the dashboard
+        let interrupt_handle = self.interrupt.clone().map(|interrupt| {
+            let child = shared_child.clone();
+
+            task::spawn(async move {
+                interrupt.notified().await;
+
+                let _ = child.kill_with_signal(SignalType::Terminate).await;
+            })
+        });
 
         // We need to log the child process output to the parent terminal
         // AND capture stdout/stderr so that we can cache it for future runs.
@@ -270,27 +293,44 @@ impl Command {
         let stderr_prefix = Arc::clone(&prefix);
         let stdout_prefix = Arc::clone(&prefix);
 
+        let secrets = Arc::new(self.secrets.clone());
+        let stderr_secrets = Arc::clone(&secrets);
+        let stdout_secrets = Arc::clone(&secrets);
+
         let console = self
             .console
             .as_ref()
             .expect("A console is required when streaming output!");
         let stderr_stream = Arc::new(console.stderr().to_owned());
         let stdout_stream = Arc::new(console.stdout().to_owned());
+        let stderr_pane = self.pane.clone();
+        let stdout_pane = self.pane.clone();
 
         handles.push(task::spawn(async move {
             let mut lines = stderr.lines();
             let mut captured_lines = vec![];
 
-            while let Ok(Some(line)) = lines.next_line().await {
-                let _ = if let Some(prefix) = &*stderr_prefix {
+            let mut redactor = LineRedactor::new(&stderr_secrets);
+
+            let mut write_line = |line: String| {
+                let _ = if let Some(pane) = &stderr_pane {
+                    pane.write_line(&line, true);
+                    Ok(())
+                } else if let Some(prefix) = &*stderr_prefix {
                     stderr_stream.write_line_with_prefix(&line, prefix)
                 } else {
                     stderr_stream.write_line(&line)
                 };
 
                 captured_lines.push(line);
+            };
+
+            while let Ok(Some(line)) = lines.next_line().await {
+                redactor.push(line).into_iter().for_each(&mut write_line);
             }
 
+            redactor.flush().into_iter().for_each(&mut write_line);
+
             captured_stderr_clone
                 .write()
                 .unwrap()
@@ -301,16 +341,27 @@ impl Command {
             let mut lines = stdout.lines();
             let mut captured_lines = vec![];
 
-            while let Ok(Some(line)) = lines.next_line().await {
-                let _ = if let Some(prefix) = &*stdout_prefix {
+            let mut redactor = LineRedactor::new(&stdout_secrets);
+
+            let mut write_line = |line: String| {
+                let _ = if let Some(pane) = &stdout_pane {
+                    pane.write_line(&line, false);
+                    Ok(())
+                } else if let Some(prefix) = &*stdout_prefix {
                     stdout_stream.write_line_with_prefix(&line, prefix)
                 } else {
                     stdout_stream.write_line(&line)
                 };
 
                 captured_lines.push(line);
+            };
+
+            while let Ok(Some(line)) = lines.next_line().await {
+                redactor.push(line).into_iter().for_each(&mut write_line);
             }
 
+            redactor.flush().into_iter().for_each(&mut write_line);
+
             captured_stdout_clone
                 .write()
                 .unwrap()
@@ -334,6 +385,10 @@ impl Command {
 
         self.post_log_command(instant, &shared_child);
 
+        if let Some(handle) = interrupt_handle {
+            handle.abort();
+        }
+
         registry.remove_running(shared_child).await;
 
         let status = result?;
@@ -533,7 +588,7 @@ impl Command {
         }
 
         let debug_env = bag.should_debug_process_env();
-        let env_vars: FxHashMap<&OsString, &OsString> = self
+        let env_vars: FxHashMap<&OsString, String> = self
             .env
             .iter()
             .filter_map(|(key, value)| {
@@ -545,7 +600,9 @@ impl Command {
                         .map(|k| k.starts_with("MOON_"))
                         .unwrap_or_default()
                 {
-                    Some((key, value.as_ref().unwrap()))
+                    let value = value.as_ref().unwrap().to_string_lossy();
+
+                    Some((key, self.redact(value)))
                 } else {
                     None
                 }
@@ -582,6 +639,13 @@ impl Command {
         trace!(pid = child.id(), "Ran command in {:?}", instant.elapsed());
     }
 
+    async fn add_running_child(&self, registry: &ProcessRegistry, child: Child) -> SharedChild {
+        match &self.process_group {
+            Some(group) => registry.add_running_to_group(child, group).await,
+            None => registry.add_running(child).await,
+        }
+    }
+
     async fn write_input_to_child(
         &self,
         child: &mut Child,
@@ -5,6 +5,7 @@ mod output;
 // mod output_stream;
 mod process_error;
 mod process_registry;
+mod redact;
 mod shared_child;
 mod shell;
 mod signal;
@@ -15,6 +16,7 @@ pub use moon_args as args;
 pub use output::*;
 pub use process_error::*;
 pub use process_registry::*;
+pub use redact::*;
 pub use shared_child::*;
 pub use shell::*;
 pub use signal::*;
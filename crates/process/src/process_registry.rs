@@ -7,7 +7,7 @@ use tokio::process::Child;
 use tokio::sync::RwLock;
 use tokio::sync::broadcast::{self, Receiver, Sender};
 use tokio::task::JoinHandle;
-use tokio::time::sleep;
+use tokio::time::{sleep, timeout};
 use tracing::{debug, trace, warn};
 
 static INSTANCE: OnceLock<Arc<ProcessRegistry>> = OnceLock::new();
@@ -15,6 +15,9 @@ static INSTANCE: OnceLock<Arc<ProcessRegistry>> = OnceLock::new();
 pub struct ProcessRegistry {
     pub threshold: u32,
 
+    /// Named groups that running child processes belong to, mapped by pid.
+    groups: Arc<RwLock<FxHashMap<u32, String>>>,
+
     running: Arc<RwLock<FxHashMap<u32, SharedChild>>>,
     signal_sender: Sender<SignalType>,
     signal_wait_handle: JoinHandle<()>,
@@ -44,6 +47,7 @@ impl ProcessRegistry {
         });
 
         Self {
+            groups: Arc::new(RwLock::new(FxHashMap::default())),
             running: processes,
             signal_sender: sender,
             signal_wait_handle,
@@ -71,6 +75,19 @@ impl ProcessRegistry {
         shared
     }
 
+    /// Add a running child process that belongs to the provided group,
+    /// so that it can be terminated alongside other processes in the group.
+    pub async fn add_running_to_group(&self, child: Child, group: &str) -> SharedChild {
+        let shared = self.add_running(child).await;
+
+        self.groups
+            .write()
+            .await
+            .insert(shared.id(), group.to_owned());
+
+        shared
+    }
+
     pub async fn get_running_by_pid(&self, id: u32) -> Option<SharedChild> {
         self.running.read().await.get(&id).cloned()
     }
@@ -81,6 +98,7 @@ impl ProcessRegistry {
 
     pub async fn remove_running_by_pid(&self, id: u32) {
         self.running.write().await.remove(&id);
+        self.groups.write().await.remove(&id);
     }
 
     pub fn receive_signal(&self) -> Receiver<SignalType> {
@@ -91,6 +109,75 @@ impl ProcessRegistry {
         let _ = self.signal_sender.send(SignalType::Terminate);
     }
 
+    /// Terminate running child processes that belong to the provided group.
+    /// Unlike [`terminate_running`], a signal is not broadcast, so that listeners
+    /// (other pipelines) and the registry itself continue to operate afterwards.
+    pub async fn terminate_running_in_group(&self, group: &str) {
+        let pids = {
+            self.groups
+                .read()
+                .await
+                .iter()
+                .filter(|(_, child_group)| *child_group == group)
+                .map(|(pid, _)| *pid)
+                .collect::<Vec<_>>()
+        };
+
+        let children = {
+            let running = self.running.read().await;
+
+            pids.iter()
+                .filter_map(|pid| running.get(pid).map(|child| (*pid, child.clone())))
+                .collect::<Vec<_>>()
+        };
+
+        if children.is_empty() {
+            return;
+        }
+
+        debug!(
+            group,
+            pids = ?pids,
+            "Terminating {} running child processes in group",
+            children.len()
+        );
+
+        let threshold = self.threshold;
+        let mut futures = vec![];
+
+        for (pid, child) in children {
+            futures.push(tokio::spawn(async move {
+                let terminate = child.kill_with_signal(SignalType::Terminate);
+
+                // Force kill the process if it doesn't exit within the threshold
+                let result = if threshold == 0 {
+                    terminate.await
+                } else {
+                    match timeout(Duration::from_millis(threshold as u64), terminate).await {
+                        Ok(result) => result,
+                        Err(_) => child.kill_with_signal(SignalType::Kill).await,
+                    }
+                };
+
+                if let Err(error) = result {
+                    warn!(pid, "Failed to terminate child process: {error}");
+                }
+            }));
+        }
+
+        for future in futures {
+            let _ = future.await;
+        }
+
+        let mut running = self.running.write().await;
+        let mut groups = self.groups.write().await;
+
+        for pid in pids {
+            running.remove(&pid);
+            groups.remove(&pid);
+        }
+    }
+
     pub async fn wait_for_running_to_shutdown(&self) {
         let mut count = 0;
 
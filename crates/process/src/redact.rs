@@ -0,0 +1,172 @@
+/// Placeholder that secret values are replaced with.
+pub const REDACTED: &str = "*****";
+
+/// Replace all occurrences of the provided secret values with a placeholder.
+/// Longer secrets are replaced first, so that a secret that contains
+/// another secret is not partially revealed.
+pub fn redact_secrets<T: AsRef<str>>(value: T, secrets: &[String]) -> String {
+    let mut value = value.as_ref().to_owned();
+
+    if secrets.is_empty() {
+        return value;
+    }
+
+    let mut secrets = secrets
+        .iter()
+        .filter(|secret| !secret.is_empty())
+        .collect::<Vec<_>>();
+
+    secrets.sort_by_key(|secret| std::cmp::Reverse(secret.len()));
+
+    for secret in secrets {
+        if value.contains(secret.as_str()) {
+            value = value.replace(secret.as_str(), REDACTED);
+        }
+    }
+
+    value
+}
+
+/// Redact secret values from the raw bytes of a process output stream.
+pub fn redact_secrets_bytes(data: Vec<u8>, secrets: &[String]) -> Vec<u8> {
+    if secrets.is_empty() {
+        return data;
+    }
+
+    match String::from_utf8(data) {
+        Ok(value) => redact_secrets(value, secrets).into_bytes(),
+        Err(error) => {
+            redact_secrets(String::from_utf8_lossy(error.as_bytes()), secrets).into_bytes()
+        }
+    }
+}
+
+/// Redacts secret values from output that is streamed line by line.
+///
+/// A multi-line secret (like a PEM key) can't be matched against a single
+/// line, so lines that may be the start of a multi-line secret are held back
+/// until the entire secret has been matched (and is redacted as a whole),
+/// or until a line no longer matches (and the held lines are released).
+pub struct LineRedactor {
+    buffer: Vec<String>,
+    multiline_secrets: Vec<Vec<String>>,
+    secrets: Vec<String>,
+}
+
+impl LineRedactor {
+    pub fn new(secrets: &[String]) -> Self {
+        let multiline_secrets = secrets
+            .iter()
+            .filter_map(|secret| {
+                let mut lines = secret
+                    .split('\n')
+                    .map(|line| line.trim_end_matches('\r').to_owned())
+                    .skip_while(|line| line.is_empty())
+                    .collect::<Vec<_>>();
+
+                while lines.last().is_some_and(|line| line.is_empty()) {
+                    lines.pop();
+                }
+
+                (lines.len() > 1).then_some(lines)
+            })
+            .collect();
+
+        Self {
+            buffer: vec![],
+            multiline_secrets,
+            secrets: secrets.to_vec(),
+        }
+    }
+
+    /// Push a line into the redactor, and return the lines that
+    /// are no longer held back, with secrets redacted.
+    pub fn push(&mut self, line: String) -> Vec<String> {
+        if self.multiline_secrets.is_empty() {
+            return vec![redact_secrets(line, &self.secrets)];
+        }
+
+        self.buffer.push(line);
+
+        let mut lines = vec![];
+
+        while !self.buffer.is_empty() {
+            match self.match_buffer() {
+                BufferMatch::Full(line) => {
+                    self.buffer.clear();
+                    lines.push(redact_secrets(line, &self.secrets));
+                }
+                BufferMatch::Partial => {
+                    break;
+                }
+                BufferMatch::None => {
+                    let line = self.buffer.remove(0);
+                    lines.push(redact_secrets(line, &self.secrets));
+                }
+            }
+        }
+
+        lines
+    }
+
+    /// Release all lines that are still held back, with secrets redacted.
+    pub fn flush(&mut self) -> Vec<String> {
+        self.buffer
+            .drain(..)
+            .map(|line| redact_secrets(line, &self.secrets))
+            .collect()
+    }
+
+    fn match_buffer(&self) -> BufferMatch {
+        let mut partial = false;
+
+        for secret in &self.multiline_secrets {
+            if self.buffer.len() > secret.len() {
+                continue;
+            }
+
+            let first = &self.buffer[0];
+            let last_index = secret.len() - 1;
+
+            // The first line may be preceded by other content,
+            // and the last line may be followed by other content
+            let matches = self.buffer.iter().enumerate().all(|(index, line)| {
+                if index == 0 {
+                    line.ends_with(&secret[0])
+                } else if index == last_index {
+                    line.starts_with(&secret[last_index])
+                } else {
+                    line == &secret[index]
+                }
+            });
+
+            if !matches {
+                continue;
+            }
+
+            if self.buffer.len() == secret.len() {
+                let last = &self.buffer[last_index];
+
+                return BufferMatch::Full(format!(
+                    "{}{REDACTED}{}",
+                    &first[..first.len() - secret[0].len()],
+                    &last[secret[last_index].len()..],
+                ));
+            }
+
+            partial = true;
+        }
+
+        if partial {
+            BufferMatch::Partial
+        } else {
+            BufferMatch::None
+        }
+    }
+}
+
+enum BufferMatch {
+    Full(String),
+    Partial,
+    None,
+}
@@ -0,0 +1,55 @@
+#[cfg(unix)]
+mod process_registry {
+    use moon_process::ProcessRegistry;
+    use tokio::process::{Child, Command};
+
+    fn spawn_sleep() -> Child {
+        Command::new("sleep").arg("30").spawn().unwrap()
+    }
+
+    #[tokio::test]
+    async fn terminates_processes_in_group() {
+        let registry = ProcessRegistry::new(1000);
+        let a = registry.add_running_to_group(spawn_sleep(), "watch").await;
+        let b = registry.add_running_to_group(spawn_sleep(), "watch").await;
+
+        registry.terminate_running_in_group("watch").await;
+
+        assert!(registry.get_running_by_pid(a.id()).await.is_none());
+        assert!(registry.get_running_by_pid(b.id()).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn doesnt_terminate_processes_outside_of_group() {
+        let registry = ProcessRegistry::new(1000);
+        let grouped = registry.add_running_to_group(spawn_sleep(), "watch").await;
+        let other_group = registry.add_running_to_group(spawn_sleep(), "other").await;
+        let ungrouped = registry.add_running(spawn_sleep()).await;
+
+        registry.terminate_running_in_group("watch").await;
+
+        assert!(registry.get_running_by_pid(grouped.id()).await.is_none());
+        assert!(
+            registry
+                .get_running_by_pid(other_group.id())
+                .await
+                .is_some()
+        );
+        assert!(registry.get_running_by_pid(ungrouped.id()).await.is_some());
+
+        other_group.kill().await.unwrap();
+        ungrouped.kill().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn does_nothing_for_unknown_group() {
+        let registry = ProcessRegistry::new(1000);
+        let child = registry.add_running_to_group(spawn_sleep(), "watch").await;
+
+        registry.terminate_running_in_group("unknown").await;
+
+        assert!(registry.get_running_by_pid(child.id()).await.is_some());
+
+        child.kill().await.unwrap();
+    }
+}
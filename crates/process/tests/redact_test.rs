@@ -0,0 +1,77 @@
+use moon_process::{LineRedactor, REDACTED};
+
+fn push_all(redactor: &mut LineRedactor, lines: &[&str]) -> Vec<String> {
+    let mut output = vec![];
+
+    for line in lines {
+        output.extend(redactor.push(line.to_string()));
+    }
+
+    output.extend(redactor.flush());
+    output
+}
+
+mod line_redactor {
+    use super::*;
+
+    #[test]
+    fn redacts_single_line_secrets() {
+        let mut redactor = LineRedactor::new(&["abc123".into()]);
+
+        assert_eq!(
+            push_all(&mut redactor, &["token=abc123", "other"]),
+            [format!("token={REDACTED}"), "other".into()]
+        );
+    }
+
+    #[test]
+    fn redacts_multiline_secrets_as_a_whole() {
+        let mut redactor = LineRedactor::new(&["BEGIN\nmiddle\nEND\n".into()]);
+
+        assert_eq!(
+            push_all(
+                &mut redactor,
+                &["before", "key=BEGIN", "middle", "END;", "after"]
+            ),
+            [
+                "before".to_owned(),
+                format!("key={REDACTED};"),
+                "after".into()
+            ]
+        );
+    }
+
+    #[test]
+    fn holds_lines_until_a_multiline_secret_is_matched() {
+        let mut redactor = LineRedactor::new(&["BEGIN\nmiddle\nEND".into()]);
+
+        assert!(redactor.push("BEGIN".into()).is_empty());
+        assert!(redactor.push("middle".into()).is_empty());
+        assert_eq!(redactor.push("END".into()), [REDACTED]);
+    }
+
+    #[test]
+    fn releases_lines_that_dont_match() {
+        let mut redactor = LineRedactor::new(&["{\n  \"key\": \"abc123\"\n}".into()]);
+
+        assert!(redactor.push("{".into()).is_empty());
+        assert_eq!(redactor.push("}".into()), ["{", "}"]);
+    }
+
+    #[test]
+    fn rematches_released_lines() {
+        let mut redactor = LineRedactor::new(&["a\nb\nc".into()]);
+
+        assert_eq!(
+            push_all(&mut redactor, &["a", "a", "b", "c"]),
+            ["a".to_owned(), REDACTED.to_owned()]
+        );
+    }
+
+    #[test]
+    fn flushes_partial_matches() {
+        let mut redactor = LineRedactor::new(&["a\nb\nc".into()]);
+
+        assert_eq!(push_all(&mut redactor, &["a", "b"]), ["a", "b"]);
+    }
+}
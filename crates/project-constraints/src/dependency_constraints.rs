@@ -0,0 +1,299 @@
+use crate::ProjectConstraintsError;
+use moon_common::{Id, Style, Stylize};
+use moon_config::{ForbiddenDependencyConfig, StackType};
+use moon_project::Project;
+use petgraph::algo::tarjan_scc;
+use petgraph::graph::DiGraph;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::collections::VecDeque;
+
+/// Format a list of project IDs as a dependency path, for example `a → b → c`.
+pub fn format_dependency_path(ids: &[&Id]) -> String {
+    ids.iter()
+        .map(|id| id.style(Style::Id))
+        .collect::<Vec<_>>()
+        .join(" → ")
+}
+
+/// A lightweight graph of project dependencies, used to enforce
+/// constraints that span more than a single dependency edge.
+pub struct DependencyConstraints<'proj> {
+    edges: FxHashMap<&'proj Id, Vec<&'proj Id>>,
+    projects: FxHashMap<&'proj Id, &'proj Project>,
+}
+
+impl<'proj> DependencyConstraints<'proj> {
+    pub fn new(projects: impl IntoIterator<Item = &'proj Project>) -> Self {
+        let projects = projects
+            .into_iter()
+            .map(|project| (&project.id, project))
+            .collect::<FxHashMap<_, _>>();
+
+        let aliases = projects
+            .values()
+            .filter_map(|project| project.alias.as_deref().map(|alias| (alias, &project.id)))
+            .collect::<FxHashMap<_, _>>();
+
+        let mut edges = FxHashMap::default();
+
+        for (id, project) in &projects {
+            let mut deps = vec![];
+
+            for dep_config in &project.dependencies {
+                // These are special scopes that are implicitly applied by moon,
+                // so don't take them into account when enforcing constraints.
+                if dep_config.is_build_scope() || dep_config.is_root_scope() {
+                    continue;
+                }
+
+                let dep_id = match projects.get_key_value(&dep_config.id) {
+                    Some((dep_id, _)) => *dep_id,
+                    None => match aliases.get(dep_config.id.as_str()) {
+                        Some(dep_id) => *dep_id,
+                        None => continue,
+                    },
+                };
+
+                if dep_id != *id && !deps.contains(&dep_id) {
+                    deps.push(dep_id);
+                }
+            }
+
+            deps.sort();
+            edges.insert(*id, deps);
+        }
+
+        Self { edges, projects }
+    }
+
+    /// Disallow cycles between projects that belong to different stacks.
+    pub fn enforce_cross_stack_cycles(&self) -> miette::Result<()> {
+        let mut graph = DiGraph::<&Id, ()>::new();
+        let mut indices = FxHashMap::default();
+
+        for id in self.get_sorted_ids() {
+            indices.insert(id, graph.add_node(id));
+        }
+
+        for (id, deps) in &self.edges {
+            for dep_id in deps {
+                graph.add_edge(indices[id], indices[dep_id], ());
+            }
+        }
+
+        for scc in tarjan_scc(&graph) {
+            if scc.len() < 2 {
+                continue;
+            }
+
+            let mut members = scc
+                .into_iter()
+                .map(|index| graph[index])
+                .collect::<Vec<_>>();
+            let mut first: Option<(&Id, StackType)> = None;
+            let mut second: Option<&Id> = None;
+
+            members.sort();
+
+            for id in &members {
+                let stack = self.projects[id].config.stack;
+
+                if stack == StackType::Unknown {
+                    continue;
+                }
+
+                match first {
+                    None => first = Some((id, stack)),
+                    Some((_, first_stack)) if first_stack != stack => {
+                        second = Some(id);
+                        break;
+                    }
+                    _ => {}
+                };
+            }
+
+            let (Some((first, _)), Some(second)) = (first, second) else {
+                continue;
+            };
+
+            // Both projects are in the same strongly connected component,
+            // so a path exists in both directions
+            let members = members.into_iter().collect::<FxHashSet<_>>();
+            let mut path = self.find_path(first, second, Some(&members));
+            let back = self.find_path(second, first, Some(&members));
+
+            path.extend(back.into_iter().skip(1));
+
+            return Err(ProjectConstraintsError::CrossStackCycle {
+                path: format_dependency_path(&path),
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+
+    /// Disallow dependencies between projects that match the `from`
+    /// and `to` sets, which are derived from the rule's queries.
+    pub fn enforce_forbidden_dependency(
+        &self,
+        rule: &ForbiddenDependencyConfig,
+        from_ids: &FxHashSet<Id>,
+        to_ids: &FxHashSet<Id>,
+    ) -> miette::Result<()> {
+        for source_id in self.get_sorted_ids() {
+            if !from_ids.contains(source_id) {
+                continue;
+            }
+
+            let path = if rule.transitive {
+                self.find_path_by(source_id, |id| to_ids.contains(id), None)
+            } else {
+                self.get_deps(source_id)
+                    .iter()
+                    .find(|dep_id| to_ids.contains(**dep_id))
+                    .map(|dep_id| vec![source_id, *dep_id])
+                    .unwrap_or_default()
+            };
+
+            if let Some(dep_id) = path.last() {
+                return Err(ProjectConstraintsError::ForbiddenDependency {
+                    source_id: source_id.to_owned(),
+                    dep_id: (*dep_id).to_owned(),
+                    from: rule.from.clone(),
+                    to: rule.to.clone(),
+                    path: format_dependency_path(&path),
+                    reason: rule.reason.clone(),
+                }
+                .into());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Disallow dependency chains that are deeper than the maximum.
+    /// A direct dependency has a depth of 1.
+    pub fn enforce_max_depth(&self, max: usize) -> miette::Result<()> {
+        let mut chains = FxHashMap::default();
+
+        for id in self.get_sorted_ids() {
+            let chain = self.get_longest_chain(id, &mut chains, &mut FxHashSet::default());
+
+            if chain.len() > max {
+                let mut path = vec![id];
+                path.extend(chain.iter().copied());
+
+                return Err(ProjectConstraintsError::MaxDependencyDepth {
+                    source_id: id.to_owned(),
+                    depth: chain.len(),
+                    max,
+                    path: format_dependency_path(&path),
+                }
+                .into());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get_deps(&self, id: &Id) -> &[&'proj Id] {
+        self.edges
+            .get(id)
+            .map(|deps| deps.as_slice())
+            .unwrap_or_default()
+    }
+
+    fn get_sorted_ids(&self) -> Vec<&'proj Id> {
+        let mut ids = self.projects.keys().copied().collect::<Vec<_>>();
+        ids.sort();
+        ids
+    }
+
+    fn find_path(
+        &self,
+        from: &'proj Id,
+        to: &Id,
+        within: Option<&FxHashSet<&'proj Id>>,
+    ) -> Vec<&'proj Id> {
+        self.find_path_by(from, |id| id == to, within)
+    }
+
+    /// Breadth-first search for the shortest path, starting from (and including)
+    /// the source project, to the first dependency that matches the condition.
+    fn find_path_by(
+        &self,
+        from: &'proj Id,
+        matches: impl Fn(&Id) -> bool,
+        within: Option<&FxHashSet<&'proj Id>>,
+    ) -> Vec<&'proj Id> {
+        let mut parents = FxHashMap::<&Id, &Id>::default();
+        let mut queue = VecDeque::from([from]);
+
+        while let Some(id) = queue.pop_front() {
+            for dep_id in self.get_deps(id).iter().copied() {
+                if dep_id == from
+                    || parents.contains_key(dep_id)
+                    || within.is_some_and(|set| !set.contains(dep_id))
+                {
+                    continue;
+                }
+
+                parents.insert(dep_id, id);
+
+                if matches(dep_id) {
+                    let mut path = vec![dep_id];
+                    let mut current = dep_id;
+
+                    while let Some(parent) = parents.get(current) {
+                        path.push(parent);
+                        current = parent;
+                    }
+
+                    path.reverse();
+
+                    return path;
+                }
+
+                queue.push_back(dep_id);
+            }
+        }
+
+        vec![]
+    }
+
+    /// Return the longest chain of dependencies for a project, excluding
+    /// the project itself. Edges that would create a cycle are ignored.
+    fn get_longest_chain(
+        &self,
+        id: &'proj Id,
+        chains: &mut FxHashMap<&'proj Id, Vec<&'proj Id>>,
+        visiting: &mut FxHashSet<&'proj Id>,
+    ) -> Vec<&'proj Id> {
+        if let Some(chain) = chains.get(id) {
+            return chain.clone();
+        }
+
+        visiting.insert(id);
+
+        let mut longest = vec![];
+
+        for dep_id in self.get_deps(id).iter().copied() {
+            if visiting.contains(dep_id) {
+                continue;
+            }
+
+            let chain = self.get_longest_chain(dep_id, chains, visiting);
+
+            if chain.len() + 1 > longest.len() {
+                longest = vec![dep_id];
+                longest.extend(chain);
+            }
+        }
+
+        visiting.remove(id);
+        chains.insert(id, longest.clone());
+
+        longest
+    }
+}
@@ -1,7 +1,12 @@
+mod dependency_constraints;
+
+pub use dependency_constraints::*;
+
 use miette::Diagnostic;
 use moon_common::{Id, Style, Stylize};
 use moon_config::{DependencyScope, StackType};
 use moon_project::{Project, ProjectType};
+use rustc_hash::FxHashMap;
 use thiserror::Error;
 
 #[derive(Error, Debug, Diagnostic)]
@@ -34,6 +39,88 @@ pub enum ProjectConstraintsError {
         dep_id: Id,
         allowed: String,
     },
+
+    #[diagnostic(code(project_constraints::forbidden_dependency))]
+    #[error(
+        "Forbidden project dependency. Project {} cannot depend on project {}, as it matches the forbidden dependency rule {} → {}.\n\nDependency path: {path}",
+        .source_id.style(Style::Id),
+        .dep_id.style(Style::Id),
+        .from.style(Style::Label),
+        .to.style(Style::Label),
+    )]
+    ForbiddenDependency {
+        source_id: Id,
+        dep_id: Id,
+        from: String,
+        to: String,
+        path: String,
+        #[help]
+        reason: Option<String>,
+    },
+
+    #[diagnostic(code(project_constraints::max_dependency_depth))]
+    #[error(
+        "Project {} has a dependency chain with a depth of {depth}, which exceeds the maximum depth of {max}.\n\nDependency path: {path}\n\nThis can be customized with the {} setting.",
+        .source_id.style(Style::Id),
+        "constraints.maxDependencyDepth".style(Style::Property),
+    )]
+    MaxDependencyDepth {
+        source_id: Id,
+        depth: usize,
+        max: usize,
+        path: String,
+    },
+
+    #[diagnostic(code(project_constraints::cross_stack_cycle))]
+    #[error(
+        "Detected a dependency cycle between projects of different stacks.\n\nDependency path: {path}\n\nThis can be disabled with the {} setting.",
+        "constraints.banCrossStackCycles".style(Style::Property),
+    )]
+    CrossStackCycle { path: String },
+
+    #[diagnostic(code(project_constraints::missing_required_tag))]
+    #[error(
+        "Project {} of type {source_type} is missing a required tag. Must have one of the following tags: {allowed}.",
+        .source_id.style(Style::Id),
+    )]
+    MissingRequiredTag {
+        source_id: Id,
+        source_type: ProjectType,
+        allowed: String,
+    },
+
+    #[diagnostic(code(project_constraints::restricted_tag))]
+    #[error(
+        "Invalid tag relationship. Project {} cannot depend on project {} with tag #{dep_tag}. Only projects with one of the following tags may depend on it: {allowed}.",
+        .source_id.style(Style::Id),
+        .dep_id.style(Style::Id),
+    )]
+    RestrictedTag {
+        source_id: Id,
+        dep_id: Id,
+        dep_tag: Id,
+        allowed: String,
+    },
+
+    #[diagnostic(code(project_constraints::plugin_violation))]
+    #[error(
+        "Project {} failed a constraint provided by the {} toolchain: {message}\n\nDependency path: {path}",
+        .source_id.style(Style::Id),
+        .toolchain.style(Style::Id),
+    )]
+    PluginViolation {
+        toolchain: Id,
+        source_id: Id,
+        message: String,
+        path: String,
+    },
+}
+
+pub(crate) fn format_tags(tags: &[Id]) -> String {
+    tags.iter()
+        .map(|t| format!("#{t}"))
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
 pub fn enforce_project_type_relationships(
@@ -161,11 +248,71 @@ pub fn enforce_tag_relationships(
         source_id: source.id.clone(),
         source_tag: source_tag.clone(),
         dep_id: dependency.id.clone(),
-        allowed: allowed
-            .iter()
-            .map(|t| format!("#{t}"))
-            .collect::<Vec<_>>()
-            .join(", "),
+        allowed: format_tags(&allowed),
     }
     .into())
 }
+
+pub fn enforce_required_tags(
+    source: &Project,
+    required_tags: &FxHashMap<ProjectType, Vec<Id>>,
+) -> miette::Result<()> {
+    let Some(tags) = required_tags.get(&source.type_of) else {
+        return Ok(());
+    };
+
+    if tags.is_empty() || source.config.tags.iter().any(|tag| tags.contains(tag)) {
+        return Ok(());
+    }
+
+    Err(ProjectConstraintsError::MissingRequiredTag {
+        source_id: source.id.clone(),
+        source_type: source.type_of,
+        allowed: format_tags(tags),
+    }
+    .into())
+}
+
+pub fn enforce_restricted_tags(
+    source: &Project,
+    dependency: &Project,
+    dependency_scope: &DependencyScope,
+    restricted_tags: &FxHashMap<Id, Vec<Id>>,
+) -> miette::Result<()> {
+    if matches!(
+        dependency_scope,
+        DependencyScope::Build | DependencyScope::Root
+    ) {
+        return Ok(());
+    }
+
+    for dep_tag in &dependency.config.tags {
+        let Some(allowed_tags) = restricted_tags.get(dep_tag) else {
+            continue;
+        };
+
+        // Dependent has the restricted tag or one of the allowed tags
+        if source.config.tags.contains(dep_tag)
+            || source
+                .config
+                .tags
+                .iter()
+                .any(|tag| allowed_tags.contains(tag))
+        {
+            continue;
+        }
+
+        let mut allowed = allowed_tags.to_owned();
+        allowed.push(dep_tag.to_owned());
+
+        return Err(ProjectConstraintsError::RestrictedTag {
+            source_id: source.id.clone(),
+            dep_id: dependency.id.clone(),
+            dep_tag: dep_tag.to_owned(),
+            allowed: format_tags(&allowed),
+        }
+        .into());
+    }
+
+    Ok(())
+}
@@ -1,7 +1,11 @@
 use moon_common::Id;
-use moon_config::{DependencyScope, StackType};
+use moon_config::{DependencyConfig, DependencyScope, ForbiddenDependencyConfig, StackType};
 use moon_project::{Project, ProjectConfig, ProjectType};
-use moon_project_constraints::{enforce_project_type_relationships, enforce_tag_relationships};
+use moon_project_constraints::{
+    DependencyConstraints, enforce_project_type_relationships, enforce_required_tags,
+    enforce_restricted_tags, enforce_tag_relationships,
+};
+use rustc_hash::{FxHashMap, FxHashSet};
 
 fn create_project(id: &str, type_of: ProjectType) -> Project {
     Project {
@@ -22,6 +26,25 @@ fn create_project_with_tags(id: &str, tags: Vec<Id>) -> Project {
     }
 }
 
+fn create_project_with_deps(id: &str, stack: StackType, deps: &[&str]) -> Project {
+    Project {
+        id: Id::raw(id),
+        config: ProjectConfig {
+            stack,
+            ..ProjectConfig::default()
+        },
+        dependencies: deps
+            .iter()
+            .map(|dep| DependencyConfig::new(Id::raw(dep)))
+            .collect(),
+        ..Project::default()
+    }
+}
+
+fn create_ids(ids: &[&str]) -> FxHashSet<Id> {
+    ids.iter().map(Id::raw).collect()
+}
+
 mod by_type {
     use super::*;
 
@@ -654,3 +677,229 @@ mod by_tag {
         .unwrap();
     }
 }
+
+mod by_required_tag {
+    use super::*;
+
+    fn create_required_tags() -> FxHashMap<ProjectType, Vec<Id>> {
+        FxHashMap::from_iter([(
+            ProjectType::Library,
+            vec![Id::raw("domain"), Id::raw("shared")],
+        )])
+    }
+
+    #[test]
+    fn skips_when_type_not_configured() {
+        enforce_required_tags(
+            &create_project("foo", ProjectType::Application),
+            &create_required_tags(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn allows_when_has_tag() {
+        let mut project = create_project_with_tags("foo", vec![Id::raw("shared")]);
+        project.type_of = ProjectType::Library;
+
+        enforce_required_tags(&project, &create_required_tags()).unwrap();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Project foo of type library is missing a required tag. Must have one of the following tags: #domain, #shared."
+    )]
+    fn fails_when_missing_tag() {
+        let mut project = create_project_with_tags("foo", vec![Id::raw("other")]);
+        project.type_of = ProjectType::Library;
+
+        enforce_required_tags(&project, &create_required_tags()).unwrap();
+    }
+}
+
+mod by_restricted_tag {
+    use super::*;
+
+    fn create_restricted_tags() -> FxHashMap<Id, Vec<Id>> {
+        FxHashMap::from_iter([(Id::raw("internal"), vec![Id::raw("admin")])])
+    }
+
+    #[test]
+    fn allows_when_dep_not_restricted() {
+        enforce_restricted_tags(
+            &create_project_with_tags("foo", vec![]),
+            &create_project_with_tags("bar", vec![Id::raw("public")]),
+            &DependencyScope::Production,
+            &create_restricted_tags(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn allows_when_source_has_allowed_tag() {
+        enforce_restricted_tags(
+            &create_project_with_tags("foo", vec![Id::raw("admin")]),
+            &create_project_with_tags("bar", vec![Id::raw("internal")]),
+            &DependencyScope::Production,
+            &create_restricted_tags(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn allows_when_source_has_same_tag() {
+        enforce_restricted_tags(
+            &create_project_with_tags("foo", vec![Id::raw("internal")]),
+            &create_project_with_tags("bar", vec![Id::raw("internal")]),
+            &DependencyScope::Production,
+            &create_restricted_tags(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn skips_build_scope() {
+        enforce_restricted_tags(
+            &create_project_with_tags("foo", vec![]),
+            &create_project_with_tags("bar", vec![Id::raw("internal")]),
+            &DependencyScope::Build,
+            &create_restricted_tags(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Project foo cannot depend on project bar with tag #internal. Only projects with one of the following tags may depend on it: #admin, #internal."
+    )]
+    fn fails_when_source_missing_tag() {
+        enforce_restricted_tags(
+            &create_project_with_tags("foo", vec![Id::raw("public")]),
+            &create_project_with_tags("bar", vec![Id::raw("internal")]),
+            &DependencyScope::Production,
+            &create_restricted_tags(),
+        )
+        .unwrap();
+    }
+}
+
+mod by_dependency_graph {
+    use super::*;
+
+    fn create_chain() -> Vec<Project> {
+        vec![
+            create_project_with_deps("a", StackType::Frontend, &["b"]),
+            create_project_with_deps("b", StackType::Frontend, &["c"]),
+            create_project_with_deps("c", StackType::Frontend, &["d"]),
+            create_project_with_deps("d", StackType::Frontend, &[]),
+        ]
+    }
+
+    fn create_rule(transitive: bool) -> ForbiddenDependencyConfig {
+        ForbiddenDependencyConfig {
+            from: "project=a".into(),
+            to: "project=d".into(),
+            reason: Some("Use the API instead.".into()),
+            transitive,
+        }
+    }
+
+    #[test]
+    fn allows_max_depth() {
+        let projects = create_chain();
+
+        DependencyConstraints::new(&projects)
+            .enforce_max_depth(3)
+            .unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Project a has a dependency chain with a depth of 3")]
+    fn fails_max_depth() {
+        let projects = create_chain();
+
+        DependencyConstraints::new(&projects)
+            .enforce_max_depth(2)
+            .unwrap();
+    }
+
+    #[test]
+    fn max_depth_ignores_cycles() {
+        let projects = vec![
+            create_project_with_deps("a", StackType::Frontend, &["b"]),
+            create_project_with_deps("b", StackType::Frontend, &["a"]),
+        ];
+
+        DependencyConstraints::new(&projects)
+            .enforce_max_depth(1)
+            .unwrap();
+    }
+
+    #[test]
+    fn allows_transitive_deps_when_forbidding_direct() {
+        let projects = create_chain();
+
+        DependencyConstraints::new(&projects)
+            .enforce_forbidden_dependency(
+                &create_rule(false),
+                &create_ids(&["a"]),
+                &create_ids(&["d"]),
+            )
+            .unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Project a cannot depend on project b")]
+    fn fails_forbidden_direct_dep() {
+        let projects = create_chain();
+
+        DependencyConstraints::new(&projects)
+            .enforce_forbidden_dependency(
+                &create_rule(false),
+                &create_ids(&["a"]),
+                &create_ids(&["b", "c"]),
+            )
+            .unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Project a cannot depend on project d")]
+    fn fails_forbidden_transitive_dep() {
+        let projects = create_chain();
+
+        DependencyConstraints::new(&projects)
+            .enforce_forbidden_dependency(
+                &create_rule(true),
+                &create_ids(&["a"]),
+                &create_ids(&["d"]),
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn allows_cycles_in_same_stack() {
+        let projects = vec![
+            create_project_with_deps("a", StackType::Frontend, &["b"]),
+            create_project_with_deps("b", StackType::Frontend, &["c"]),
+            create_project_with_deps("c", StackType::Unknown, &["a"]),
+        ];
+
+        DependencyConstraints::new(&projects)
+            .enforce_cross_stack_cycles()
+            .unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Detected a dependency cycle between projects of different stacks.")]
+    fn fails_cycles_across_stacks() {
+        let projects = vec![
+            create_project_with_deps("a", StackType::Frontend, &["b"]),
+            create_project_with_deps("b", StackType::Backend, &["c"]),
+            create_project_with_deps("c", StackType::Backend, &["a"]),
+        ];
+
+        DependencyConstraints::new(&projects)
+            .enforce_cross_stack_cycles()
+            .unwrap();
+    }
+}
@@ -288,3 +288,9 @@ impl GraphConversions<Project, DependencyScope, Id> for ProjectGraph {}
 impl GraphToDot<Project, DependencyScope, Id> for ProjectGraph {}
 
 impl GraphToJson<Project, DependencyScope, Id> for ProjectGraph {}
+
+impl GraphToGraphml<Project, DependencyScope, Id> for ProjectGraph {}
+
+impl GraphToMermaid<Project, DependencyScope, Id> for ProjectGraph {}
+
+impl GraphToSvg<Project, DependencyScope, Id> for ProjectGraph {}
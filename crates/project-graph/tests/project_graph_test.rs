@@ -1,7 +1,7 @@
 use moon_common::{Id, path::WorkspaceRelativePathBuf};
 use moon_config::{
-    DependencyConfig, DependencyScope, DependencySource, TaskDependencyConfig, WorkspaceProjects,
-    WorkspaceProjectsConfig,
+    DependencyConfig, DependencyScope, DependencySource, ForbiddenDependencyConfig, ProjectType,
+    TaskDependencyConfig, WorkspaceConfig, WorkspaceProjects, WorkspaceProjectsConfig,
 };
 use moon_project::{FileGroup, Project};
 use moon_project_graph::*;
@@ -1472,6 +1472,120 @@ mod project_graph {
         }
     }
 
+    mod dependency_constraints {
+        use super::*;
+
+        async fn build_dependency_constraints_graph(
+            func: impl FnOnce(&Sandbox),
+            config_func: impl FnMut(&mut WorkspaceConfig),
+        ) -> WorkspaceGraph {
+            let sandbox = create_sandbox("tag-constraints");
+
+            func(&sandbox);
+
+            create_workspace_mocker(sandbox.path())
+                .update_workspace_config(config_func)
+                .mock_workspace_graph()
+                .await
+        }
+
+        #[tokio::test]
+        async fn allows_deps_not_matching_forbidden_query() {
+            build_dependency_constraints_graph(
+                |sandbox| {
+                    append_file(sandbox.path().join("a/moon.yml"), "dependsOn: [b]");
+                    append_file(sandbox.path().join("c/moon.yml"), "tags: [internal]");
+                },
+                |config| {
+                    config
+                        .constraints
+                        .forbidden_dependencies
+                        .push(ForbiddenDependencyConfig {
+                            from: "project=a".into(),
+                            to: "tag=internal".into(),
+                            ..Default::default()
+                        });
+                },
+            )
+            .await;
+        }
+
+        #[tokio::test]
+        #[should_panic(expected = "Dependency path: a → b → c")]
+        async fn errors_for_forbidden_transitive_query() {
+            build_dependency_constraints_graph(
+                |sandbox| {
+                    append_file(sandbox.path().join("a/moon.yml"), "dependsOn: [b]");
+                    append_file(sandbox.path().join("b/moon.yml"), "dependsOn: [c]");
+                    append_file(sandbox.path().join("c/moon.yml"), "tags: [internal]");
+                },
+                |config| {
+                    config
+                        .constraints
+                        .forbidden_dependencies
+                        .push(ForbiddenDependencyConfig {
+                            from: "project=a".into(),
+                            to: "tag=internal".into(),
+                            transitive: true,
+                            ..Default::default()
+                        });
+                },
+            )
+            .await;
+        }
+
+        #[tokio::test]
+        #[should_panic(expected = "exceeds the maximum depth of 1")]
+        async fn errors_for_max_depth() {
+            build_dependency_constraints_graph(
+                |sandbox| {
+                    append_file(sandbox.path().join("a/moon.yml"), "dependsOn: [b]");
+                    append_file(sandbox.path().join("b/moon.yml"), "dependsOn: [c]");
+                },
+                |config| {
+                    config.constraints.max_dependency_depth = Some(1);
+                },
+            )
+            .await;
+        }
+
+        #[tokio::test]
+        #[should_panic(expected = "Project a of type library is missing a required tag")]
+        async fn errors_for_missing_required_tag() {
+            build_dependency_constraints_graph(
+                |sandbox| {
+                    append_file(sandbox.path().join("b/moon.yml"), "tags: [domain]");
+                    append_file(sandbox.path().join("c/moon.yml"), "tags: [domain]");
+                },
+                |config| {
+                    config
+                        .constraints
+                        .required_tags
+                        .insert(ProjectType::Library, vec![Id::raw("domain")]);
+                },
+            )
+            .await;
+        }
+
+        #[tokio::test]
+        #[should_panic(expected = "Project a cannot depend on project b with tag #internal")]
+        async fn errors_for_restricted_tag() {
+            build_dependency_constraints_graph(
+                |sandbox| {
+                    append_file(sandbox.path().join("a/moon.yml"), "dependsOn: [b]");
+                    append_file(sandbox.path().join("b/moon.yml"), "tags: [internal]");
+                },
+                |config| {
+                    config
+                        .constraints
+                        .restricted_tags
+                        .insert(Id::raw("internal"), vec![Id::raw("admin")]);
+                },
+            )
+            .await;
+        }
+    }
+
     mod query {
         use super::*;
 
@@ -1569,6 +1683,124 @@ mod project_graph {
             assert_eq!(get_ids_from_projects(projects), vec!["a"]);
         }
 
+        #[tokio::test]
+        async fn by_task_option() {
+            let graph = build_graph_from_fixture("query-mql").await;
+
+            let projects = graph
+                .query_projects(build_query("taskOption.cache=false").unwrap())
+                .unwrap();
+
+            assert_eq!(get_ids_from_projects(projects), vec!["a", "c"]);
+
+            let projects = graph
+                .query_projects(build_query("taskOption.persistent=true").unwrap())
+                .unwrap();
+
+            assert_eq!(get_ids_from_projects(projects), vec!["a"]);
+        }
+
+        #[tokio::test]
+        async fn by_depends_on() {
+            let graph = build_graph_from_fixture("query-mql").await;
+
+            let projects = graph
+                .query_projects(build_query("dependsOn=b").unwrap())
+                .unwrap();
+
+            assert_eq!(get_ids_from_projects(projects), vec!["a"]);
+        }
+
+        #[tokio::test]
+        async fn by_dependent_of() {
+            let graph = build_graph_from_fixture("query-mql").await;
+
+            let projects = graph
+                .query_projects(build_query("dependentOf=a").unwrap())
+                .unwrap();
+
+            assert_eq!(get_ids_from_projects(projects), vec!["b"]);
+        }
+
+        #[tokio::test]
+        async fn by_has_output() {
+            let graph = build_graph_from_fixture("query-mql").await;
+
+            let projects = graph
+                .query_projects(build_query("hasOutput=dist").unwrap())
+                .unwrap();
+
+            assert_eq!(get_ids_from_projects(projects), vec!["a", "d"]);
+
+            let projects = graph
+                .query_projects(build_query("hasOutput~dist/**").unwrap())
+                .unwrap();
+
+            assert_eq!(get_ids_from_projects(projects), vec!["a", "d"]);
+
+            let projects = graph
+                .query_projects(build_query("hasOutput~a/*").unwrap())
+                .unwrap();
+
+            assert_eq!(get_ids_from_projects(projects), vec!["a"]);
+        }
+
+        #[tokio::test]
+        async fn by_input() {
+            let graph = build_graph_from_fixture("query-mql").await;
+
+            let projects = graph
+                .query_projects(build_query("input~**/*.graphql").unwrap())
+                .unwrap();
+
+            assert_eq!(get_ids_from_projects(projects), vec!["a"]);
+        }
+
+        #[tokio::test]
+        async fn by_owner() {
+            let graph = build_graph_from_fixture("query-mql").await;
+
+            let projects = graph
+                .query_projects(build_query("owner=@team").unwrap())
+                .unwrap();
+
+            assert_eq!(get_ids_from_projects(projects), vec!["b"]);
+        }
+
+        #[tokio::test]
+        async fn tasks_by_task_option_and_input() {
+            let graph = build_graph_from_fixture("query-mql").await;
+
+            let tasks = graph
+                .query_tasks(build_query("taskOption.cache=true && input~**/*.graphql").unwrap())
+                .unwrap();
+
+            assert_eq!(
+                tasks
+                    .iter()
+                    .map(|task| task.target.to_string())
+                    .collect::<Vec<_>>(),
+                vec!["a:build"]
+            );
+        }
+
+        #[tokio::test]
+        async fn tasks_by_project_fields() {
+            let graph = build_graph_from_fixture("query-mql").await;
+
+            let tasks = graph
+                .query_tasks(build_query("dependsOn=b || owner=@team").unwrap())
+                .unwrap();
+
+            assert_eq!(
+                tasks
+                    .iter()
+                    .map(|task| task.target.to_string())
+                    .collect::<Vec<_>>(),
+                vec!["a:build", "a:dev", "a:global", "b:global", "b:lint"]
+            );
+        }
+
         #[tokio::test]
         async fn with_and_conditions() {
             let graph = build_graph_from_fixture("query").await;
@@ -13,7 +13,12 @@ pub type FieldValues<'l> = Vec<FieldValue<'l>>;
 
 #[derive(Debug, PartialEq)]
 pub enum Field<'l> {
+    DependentOf(FieldValues<'l>),
+    DependsOn(FieldValues<'l>),
+    HasOutput(FieldValues<'l>),
+    Input(FieldValues<'l>),
     Language(Vec<LanguageType>),
+    Owner(FieldValues<'l>),
     Project(FieldValues<'l>),
     ProjectAlias(FieldValues<'l>),
     ProjectName(FieldValues<'l>),
@@ -22,6 +27,8 @@ pub enum Field<'l> {
     ProjectType(Vec<ProjectType>),
     Tag(FieldValues<'l>),
     Task(FieldValues<'l>),
+    /// Name of the option (without the `taskOption.` prefix), and its values.
+    TaskOption(FieldValue<'l>, FieldValues<'l>),
     TaskPlatform(FieldValues<'l>),
     TaskToolchain(FieldValues<'l>),
     TaskType(Vec<TaskType>),
@@ -117,9 +124,14 @@ fn build_criteria(ast: Vec<AstNode<'_>>) -> miette::Result<Criteria<'_>> {
         match node {
             AstNode::Comparison { field, op, value } => {
                 let field = match field.as_ref() {
+                    "dependentOf" => Field::DependentOf(value),
+                    "dependsOn" => Field::DependsOn(value),
+                    "hasOutput" => Field::HasOutput(value),
+                    "input" => Field::Input(value),
                     "language" => {
                         Field::Language(build_criteria_enum::<LanguageType>(&field, &op, value)?)
                     }
+                    "owner" => Field::Owner(value),
                     "project" => Field::Project(value),
                     "projectAlias" => Field::ProjectAlias(value),
                     "projectName" => Field::ProjectName(value),
@@ -145,9 +157,12 @@ fn build_criteria(ast: Vec<AstNode<'_>>) -> miette::Result<Criteria<'_>> {
                     "taskType" => {
                         Field::TaskType(build_criteria_enum::<TaskType>(&field, &op, value)?)
                     }
-                    _ => {
-                        return Err(QueryError::UnknownField(field.to_string()).into());
-                    }
+                    other => match other.strip_prefix("taskOption.") {
+                        Some(option) => Field::TaskOption(Cow::Owned(option.to_owned()), value),
+                        None => {
+                            return Err(QueryError::UnknownField(field.to_string()).into());
+                        }
+                    },
                 };
 
                 conditions.push(Condition::Field { field, op });
@@ -254,6 +254,74 @@ mod mql_build {
         // }
     }
 
+    mod dependencies {
+        use super::*;
+
+        #[test]
+        fn depends_on_eq() {
+            assert_eq!(
+                build_query("dependsOn=app").unwrap(),
+                Criteria {
+                    op: LogicalOperator::And,
+                    conditions: vec![Condition::Field {
+                        field: Field::DependsOn(value_list(["app"])),
+                        op: ComparisonOperator::Equal,
+                    }],
+                    input: Some("dependsOn=app".into())
+                }
+            );
+        }
+
+        #[test]
+        fn dependent_of_like() {
+            assert_eq!(
+                build_query("dependentOf~app-*").unwrap(),
+                Criteria {
+                    op: LogicalOperator::And,
+                    conditions: vec![Condition::Field {
+                        field: Field::DependentOf(value_list(["app-*"])),
+                        op: ComparisonOperator::Like,
+                    }],
+                    input: Some("dependentOf~app-*".into())
+                }
+            );
+        }
+    }
+
+    mod paths {
+        use super::*;
+
+        #[test]
+        fn has_output_like() {
+            assert_eq!(
+                build_query("hasOutput~dist/**").unwrap(),
+                Criteria {
+                    op: LogicalOperator::And,
+                    conditions: vec![Condition::Field {
+                        field: Field::HasOutput(value_list(["dist/**"])),
+                        op: ComparisonOperator::Like,
+                    }],
+                    input: Some("hasOutput~dist/**".into())
+                }
+            );
+        }
+
+        #[test]
+        fn input_like() {
+            assert_eq!(
+                build_query("input~**/*.graphql").unwrap(),
+                Criteria {
+                    op: LogicalOperator::And,
+                    conditions: vec![Condition::Field {
+                        field: Field::Input(value_list(["**/*.graphql"])),
+                        op: ComparisonOperator::Like,
+                    }],
+                    input: Some("input~**/*.graphql".into())
+                }
+            );
+        }
+    }
+
     mod language {
         use super::*;
 
@@ -304,6 +372,25 @@ mod mql_build {
         }
     }
 
+    mod owner {
+        use super::*;
+
+        #[test]
+        fn owner_eq() {
+            assert_eq!(
+                build_query("owner=@team").unwrap(),
+                Criteria {
+                    op: LogicalOperator::And,
+                    conditions: vec![Condition::Field {
+                        field: Field::Owner(value_list(["@team"])),
+                        op: ComparisonOperator::Equal,
+                    }],
+                    input: Some("owner=@team".into())
+                }
+            );
+        }
+    }
+
     mod project {
         use super::*;
 
@@ -616,6 +703,55 @@ mod mql_build {
         }
     }
 
+    mod task_option {
+        use super::*;
+
+        #[test]
+        fn option_eq() {
+            assert_eq!(
+                build_query("taskOption.cache=false").unwrap(),
+                Criteria {
+                    op: LogicalOperator::And,
+                    conditions: vec![Condition::Field {
+                        field: Field::TaskOption("cache".into(), value_list(["false"])),
+                        op: ComparisonOperator::Equal,
+                    }],
+                    input: Some("taskOption.cache=false".into())
+                }
+            );
+        }
+
+        #[test]
+        fn option_neq_list() {
+            assert_eq!(
+                build_query("taskOption.outputStyle!=[buffer,stream]").unwrap(),
+                Criteria {
+                    op: LogicalOperator::And,
+                    conditions: vec![Condition::Field {
+                        field: Field::TaskOption(
+                            "outputStyle".into(),
+                            value_list(["buffer", "stream"])
+                        ),
+                        op: ComparisonOperator::NotEqual,
+                    }],
+                    input: Some("taskOption.outputStyle!=[buffer,stream]".into())
+                }
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "Unknown query field taskOption.")]
+        fn errors_without_option() {
+            build_query("taskOption=true").unwrap();
+        }
+
+        #[test]
+        #[should_panic(expected = "Unknown query field task.cache.")]
+        fn errors_for_other_nested_fields() {
+            build_query("task.cache=true").unwrap();
+        }
+    }
+
     mod task_toolchain {
         use super::*;
 
@@ -341,6 +341,24 @@ mod mql_parse {
         );
     }
 
+    #[test]
+    fn nested_keys() {
+        assert_eq!(
+            parse_query("key.nested=value").unwrap(),
+            vec![AstNode::Comparison {
+                field: "key.nested".into(),
+                op: ComparisonOperator::Equal,
+                value: vec!["value".into()],
+            }],
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_trailing_key_dot() {
+        parse_query("key.=value").unwrap();
+    }
+
     #[test]
     fn id_patterns() {
         assert!(parse_query("key=id").is_ok());
@@ -0,0 +1,325 @@
+// Note: Don't use `starbase_utils::fs` as it spams the logs far too much!
+
+use crate::remote_server_error::RemoteServerError;
+use rustc_hash::FxHashMap;
+use starbase_utils::fs::FsError;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+use tracing::{debug, trace, warn};
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum StorageKind {
+    /// Action cache (AC), keyed by action digest.
+    ActionCache,
+    /// Content addressable storage (CAS), keyed by blob digest.
+    Cas,
+}
+
+impl StorageKind {
+    pub fn get_dir_name(&self) -> &'static str {
+        match self {
+            Self::ActionCache => "ac",
+            Self::Cas => "cas",
+        }
+    }
+}
+
+type StorageKey = (StorageKind, String);
+
+struct StorageEntry {
+    size: u64,
+    tick: u64,
+}
+
+/// Tracks the size and access order of every entry, in memory.
+#[derive(Default)]
+struct StorageIndex {
+    entries: FxHashMap<StorageKey, StorageEntry>,
+    order: BTreeMap<u64, StorageKey>,
+    tick: u64,
+    total_size: u64,
+}
+
+impl StorageIndex {
+    fn touch(&mut self, key: StorageKey, size: u64) {
+        self.remove(&key);
+        self.tick += 1;
+        self.total_size += size;
+        self.order.insert(self.tick, key.clone());
+        self.entries.insert(
+            key,
+            StorageEntry {
+                size,
+                tick: self.tick,
+            },
+        );
+    }
+
+    fn remove(&mut self, key: &StorageKey) -> Option<StorageEntry> {
+        let entry = self.entries.remove(key)?;
+
+        self.order.remove(&entry.tick);
+        self.total_size -= entry.size;
+
+        Some(entry)
+    }
+}
+
+/// A directory backed storage for the action cache and CAS,
+/// that evicts the least recently used entries once the
+/// maximum size has been exceeded.
+pub struct DiskStorage {
+    pub max_size: u64,
+    pub root: PathBuf,
+
+    index: Mutex<StorageIndex>,
+}
+
+impl DiskStorage {
+    pub fn new(root: &Path, max_size: u64) -> miette::Result<Self> {
+        debug!(
+            root = ?root,
+            max_size,
+            "Creating disk storage for remote cache"
+        );
+
+        let storage = Self {
+            index: Mutex::new(StorageIndex::default()),
+            max_size,
+            root: root.to_path_buf(),
+        };
+
+        storage.load_index()?;
+        storage.evict();
+
+        Ok(storage)
+    }
+
+    pub fn get_entry_count(&self) -> usize {
+        self.index.lock().unwrap().entries.len()
+    }
+
+    pub fn get_path(&self, kind: StorageKind, hash: &str) -> PathBuf {
+        self.root
+            .join(kind.get_dir_name())
+            .join(&hash[0..2])
+            .join(hash)
+    }
+
+    pub fn get_total_size(&self) -> u64 {
+        self.index.lock().unwrap().total_size
+    }
+
+    pub fn contains(&self, kind: StorageKind, hash: &str) -> bool {
+        is_valid_hash(hash)
+            && self
+                .index
+                .lock()
+                .unwrap()
+                .entries
+                .contains_key(&(kind, hash.to_owned()))
+    }
+
+    pub fn read(&self, kind: StorageKind, hash: &str) -> miette::Result<Option<Vec<u8>>> {
+        if !self.contains(kind, hash) {
+            return Ok(None);
+        }
+
+        let path = self.get_path(kind, hash);
+
+        let bytes = match fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                self.index.lock().unwrap().remove(&(kind, hash.to_owned()));
+
+                return Ok(None);
+            }
+            Err(error) => {
+                return Err(FsError::Read {
+                    path,
+                    error: Box::new(error),
+                }
+                .into());
+            }
+        };
+
+        trace!(kind = kind.get_dir_name(), hash, "Read entry from storage");
+
+        // Persist the access time, so that the order is retained across restarts
+        if let Ok(file) = fs::File::options().write(true).open(&path) {
+            let _ = file.set_modified(SystemTime::now());
+        }
+
+        self.index
+            .lock()
+            .unwrap()
+            .touch((kind, hash.to_owned()), bytes.len() as u64);
+
+        Ok(Some(bytes))
+    }
+
+    pub fn write(&self, kind: StorageKind, hash: &str, bytes: &[u8]) -> miette::Result<()> {
+        if !is_valid_hash(hash) {
+            return Err(RemoteServerError::InvalidHash(hash.to_owned()).into());
+        }
+
+        let size = bytes.len() as u64;
+
+        if self.max_size > 0 && size > self.max_size {
+            return Err(RemoteServerError::BlobTooLarge {
+                hash: hash.to_owned(),
+                size,
+                max_size: self.max_size,
+            }
+            .into());
+        }
+
+        let path = self.get_path(kind, hash);
+        let parent = path.parent().unwrap();
+
+        fs::create_dir_all(parent).map_err(|error| FsError::Create {
+            path: parent.to_path_buf(),
+            error: Box::new(error),
+        })?;
+
+        // Write to a temporary file and then rename it, so that
+        // concurrent readers never see a partially written file
+        let temp_path = parent.join(format!("{hash}.{}.tmp", uuid::Uuid::new_v4()));
+
+        fs::write(&temp_path, bytes).map_err(|error| FsError::Write {
+            path: temp_path.clone(),
+            error: Box::new(error),
+        })?;
+
+        fs::rename(&temp_path, &path).map_err(|error| FsError::Rename {
+            from: temp_path,
+            to: path.clone(),
+            error: Box::new(error),
+        })?;
+
+        trace!(
+            kind = kind.get_dir_name(),
+            hash, size, "Wrote entry to storage"
+        );
+
+        self.index
+            .lock()
+            .unwrap()
+            .touch((kind, hash.to_owned()), size);
+
+        self.evict();
+
+        Ok(())
+    }
+
+    /// Remove the least recently used entries until the
+    /// total size is below the maximum size.
+    pub fn evict(&self) {
+        if self.max_size == 0 {
+            return;
+        }
+
+        let mut index = self.index.lock().unwrap();
+
+        while index.total_size > self.max_size {
+            let Some((_, key)) = index.order.pop_first() else {
+                break;
+            };
+
+            let Some(entry) = index.entries.remove(&key) else {
+                continue;
+            };
+
+            index.total_size -= entry.size;
+
+            let path = self.get_path(key.0, &key.1);
+
+            trace!(
+                kind = key.0.get_dir_name(),
+                hash = &key.1,
+                size = entry.size,
+                "Evicting least recently used entry from storage"
+            );
+
+            if let Err(error) = fs::remove_file(&path) {
+                warn!(path = ?path, "Failed to evict entry from storage: {error}");
+            }
+        }
+    }
+
+    fn load_index(&self) -> miette::Result<()> {
+        let mut found = vec![];
+
+        for kind in [StorageKind::ActionCache, StorageKind::Cas] {
+            let dir = self.root.join(kind.get_dir_name());
+
+            if !dir.exists() {
+                continue;
+            }
+
+            for shard in read_dir(&dir)? {
+                if !shard.is_dir() {
+                    continue;
+                }
+
+                for file in read_dir(&shard)? {
+                    let Some(hash) = file.file_name().and_then(|name| name.to_str()) else {
+                        continue;
+                    };
+
+                    // Remove temporary files from interrupted writes
+                    if !is_valid_hash(hash) {
+                        let _ = fs::remove_file(&file);
+
+                        continue;
+                    }
+
+                    if let Ok(metadata) = fs::metadata(&file) {
+                        found.push((
+                            metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                            (kind, hash.to_owned()),
+                            metadata.len(),
+                        ));
+                    }
+                }
+            }
+        }
+
+        // Oldest modified files are the least recently used
+        found.sort_by(|a, d| a.0.cmp(&d.0));
+
+        let mut index = self.index.lock().unwrap();
+
+        for (_, key, size) in found {
+            index.touch(key, size);
+        }
+
+        debug!(
+            entries = index.entries.len(),
+            total_size = index.total_size,
+            "Loaded existing entries into storage index"
+        );
+
+        Ok(())
+    }
+}
+
+fn read_dir(dir: &Path) -> miette::Result<Vec<PathBuf>> {
+    let entries = fs::read_dir(dir).map_err(|error| FsError::Read {
+        path: dir.to_path_buf(),
+        error: Box::new(error),
+    })?;
+
+    Ok(entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .collect())
+}
+
+/// Hashes are used as file names, so they must be strictly validated
+/// to avoid path traversal.
+pub fn is_valid_hash(hash: &str) -> bool {
+    hash.len() >= 2 && hash.len() <= 128 && hash.chars().all(|ch| ch.is_ascii_hexdigit())
+}
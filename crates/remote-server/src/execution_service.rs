@@ -0,0 +1,378 @@
+use crate::disk_storage::StorageKind;
+use crate::grpc_services::RemoteCacheService;
+use bazel_remote_apis::build::bazel::remote::execution::v2::{
+    Action, ActionResult, Command, Digest, Directory, ExecuteOperationMetadata, ExecuteRequest,
+    ExecuteResponse, ExecutedActionMetadata, OutputFile, WaitExecutionRequest,
+    execution_server::Execution, execution_stage,
+};
+use bazel_remote_apis::google::longrunning::{Operation, operation};
+use bazel_remote_apis::google::protobuf::Any;
+use moon_remote::{create_digest, create_timestamp};
+use prost::Message;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::process::Stdio;
+use std::time::{Duration, SystemTime};
+use tonic::codegen::tokio_stream::Stream;
+use tonic::{Request, Response, Status};
+use tracing::{debug, warn};
+
+const METADATA_TYPE: &str =
+    "type.googleapis.com/build.bazel.remote.execution.v2.ExecuteOperationMetadata";
+const RESPONSE_TYPE: &str = "type.googleapis.com/build.bazel.remote.execution.v2.ExecuteResponse";
+
+type OperationStream = Pin<Box<dyn Stream<Item = Result<Operation, Status>> + Send>>;
+
+/// A stand-in for a build farm, that implements the REAPI Execution service
+/// by running actions as local child processes, within a temporary directory
+/// that has been populated from the input root.
+#[derive(Clone)]
+pub struct LocalExecutionService {
+    pub cache: RemoteCacheService,
+}
+
+impl LocalExecutionService {
+    fn read_message<T: Message + Default>(&self, digest: Option<&Digest>) -> Result<T, Status> {
+        let Some(digest) = digest else {
+            return Err(Status::invalid_argument("Missing digest"));
+        };
+
+        let Some(bytes) = self
+            .cache
+            .storage
+            .read(StorageKind::Cas, &digest.hash)
+            .map_err(|error| Status::internal(error.to_string()))?
+        else {
+            return Err(Status::failed_precondition(format!(
+                "Blob {} is missing from the CAS",
+                digest.hash
+            )));
+        };
+
+        T::decode(bytes.as_slice()).map_err(|error| Status::invalid_argument(error.to_string()))
+    }
+
+    fn write_blob(&self, bytes: &[u8]) -> Result<Digest, Status> {
+        let digest = create_digest(bytes);
+
+        self.cache
+            .storage
+            .write(StorageKind::Cas, &digest.hash, bytes)
+            .map_err(|error| Status::internal(error.to_string()))?;
+
+        Ok(digest)
+    }
+
+    fn materialize_directory(&self, digest: Option<&Digest>, dir: &Path) -> Result<(), Status> {
+        let directory: Directory = self.read_message(digest)?;
+
+        std::fs::create_dir_all(dir).map_err(|error| Status::internal(error.to_string()))?;
+
+        for file in &directory.files {
+            let bytes = match &file.digest {
+                Some(digest) if digest.size_bytes > 0 => self
+                    .cache
+                    .storage
+                    .read(StorageKind::Cas, &digest.hash)
+                    .map_err(|error| Status::internal(error.to_string()))?
+                    .ok_or_else(|| {
+                        Status::failed_precondition(format!(
+                            "Input file {} is missing from the CAS",
+                            file.name
+                        ))
+                    })?,
+                _ => vec![],
+            };
+
+            let path = dir.join(&file.name);
+
+            std::fs::write(&path, bytes).map_err(|error| Status::internal(error.to_string()))?;
+
+            #[cfg(unix)]
+            if file.is_executable {
+                use std::os::unix::fs::PermissionsExt;
+
+                let _ = std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755));
+            }
+        }
+
+        for child in &directory.directories {
+            self.materialize_directory(child.digest.as_ref(), &dir.join(&child.name))?;
+        }
+
+        Ok(())
+    }
+
+    fn collect_outputs(
+        &self,
+        command: &Command,
+        working_dir: &Path,
+        result: &mut ActionResult,
+    ) -> Result<(), Status> {
+        for output_path in &command.output_paths {
+            let path = working_dir.join(output_path);
+
+            if path.is_file() {
+                result
+                    .output_files
+                    .push(self.collect_output_file(&path, output_path.to_owned())?);
+            } else if path.is_dir() {
+                // Outputs are flattened into files, as moon does not support trees
+                for entry in walk_files(&path) {
+                    let Ok(suffix) = entry.strip_prefix(&path) else {
+                        continue;
+                    };
+
+                    let name = Path::new(output_path)
+                        .join(suffix)
+                        .to_string_lossy()
+                        .replace('\\', "/");
+
+                    result
+                        .output_files
+                        .push(self.collect_output_file(&entry, name)?);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn collect_output_file(&self, path: &Path, name: String) -> Result<OutputFile, Status> {
+        let bytes = std::fs::read(path).map_err(|error| Status::internal(error.to_string()))?;
+
+        #[cfg(unix)]
+        let is_executable = {
+            use std::os::unix::fs::PermissionsExt;
+
+            std::fs::metadata(path).is_ok_and(|metadata| metadata.permissions().mode() & 0o111 != 0)
+        };
+
+        #[cfg(not(unix))]
+        let is_executable = false;
+
+        Ok(OutputFile {
+            path: name,
+            digest: Some(self.write_blob(&bytes)?),
+            is_executable,
+            ..Default::default()
+        })
+    }
+
+    async fn run_action(
+        &self,
+        action: &Action,
+        command: &Command,
+        exec_dir: &Path,
+    ) -> Result<ActionResult, Status> {
+        self.materialize_directory(action.input_root_digest.as_ref(), exec_dir)?;
+
+        let Some((bin, args)) = command.arguments.split_first() else {
+            return Err(Status::invalid_argument("Command has no arguments"));
+        };
+
+        let working_dir = exec_dir.join(&command.working_directory);
+
+        std::fs::create_dir_all(&working_dir)
+            .map_err(|error| Status::internal(error.to_string()))?;
+
+        let mut child = tokio::process::Command::new(bin);
+        child
+            .args(args)
+            .current_dir(&working_dir)
+            .stdin(Stdio::null())
+            .kill_on_drop(true);
+
+        for var in &command.environment_variables {
+            child.env(&var.name, &var.value);
+        }
+
+        let started_at = SystemTime::now();
+
+        let output = match &action.timeout {
+            Some(timeout) if timeout.seconds > 0 => {
+                tokio::time::timeout(Duration::from_secs(timeout.seconds as u64), child.output())
+                    .await
+                    .map_err(|_| Status::deadline_exceeded("Action timed out"))?
+            }
+            _ => child.output().await,
+        }
+        .map_err(|error| Status::invalid_argument(format!("Failed to run {bin}: {error}")))?;
+
+        let mut result = ActionResult {
+            exit_code: output.status.code().unwrap_or(-1),
+            stderr_digest: Some(self.write_blob(&output.stderr)?),
+            stdout_digest: Some(self.write_blob(&output.stdout)?),
+            stderr_raw: output.stderr,
+            stdout_raw: output.stdout,
+            execution_metadata: Some(ExecutedActionMetadata {
+                worker: "moon-local-executor".into(),
+                execution_start_timestamp: create_timestamp(started_at),
+                execution_completed_timestamp: create_timestamp(SystemTime::now()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        if result.exit_code == 0 {
+            self.collect_outputs(command, &working_dir, &mut result)?;
+        }
+
+        Ok(result)
+    }
+
+    async fn execute_action(&self, request: ExecuteRequest) -> Result<ExecuteResponse, Status> {
+        let Some(action_digest) = request.action_digest else {
+            return Err(Status::invalid_argument("Missing action digest"));
+        };
+
+        if !request.skip_cache_lookup {
+            if let Some(result) = self.cache.get_action_result(&action_digest.hash)? {
+                debug!(hash = &action_digest.hash, "Action has been cached");
+
+                return Ok(ExecuteResponse {
+                    result: Some(result),
+                    cached_result: true,
+                    ..Default::default()
+                });
+            }
+        }
+
+        let action: Action = self.read_message(Some(&action_digest))?;
+        let command: Command = self.read_message(action.command_digest.as_ref())?;
+        let exec_dir = self
+            .cache
+            .storage
+            .root
+            .join("exec")
+            .join(uuid::Uuid::new_v4().to_string());
+
+        debug!(
+            hash = &action_digest.hash,
+            args = ?command.arguments,
+            exec_dir = ?exec_dir,
+            "Executing action"
+        );
+
+        let result = self.run_action(&action, &command, &exec_dir).await;
+
+        if let Err(error) = std::fs::remove_dir_all(&exec_dir) {
+            warn!(exec_dir = ?exec_dir, "Failed to remove execution directory: {error}");
+        }
+
+        let result = result?;
+
+        // Only successful results should be cached
+        if result.exit_code == 0 && !action.do_not_cache {
+            self.cache
+                .update_action_result(&action_digest.hash, &result)?;
+        }
+
+        Ok(ExecuteResponse {
+            result: Some(result),
+            ..Default::default()
+        })
+    }
+}
+
+#[tonic::async_trait]
+impl Execution for LocalExecutionService {
+    type ExecuteStream = OperationStream;
+    type WaitExecutionStream = OperationStream;
+
+    async fn execute(
+        &self,
+        request: Request<ExecuteRequest>,
+    ) -> Result<Response<OperationStream>, Status> {
+        let request = request.into_inner();
+        let name = format!("operations/{}", uuid::Uuid::new_v4());
+
+        let executing = Operation {
+            name: name.clone(),
+            metadata: Some(create_metadata(&request, execution_stage::Value::Executing)),
+            done: false,
+            result: None,
+        };
+
+        let metadata = create_metadata(&request, execution_stage::Value::Completed);
+
+        // Execute in the background, so that the executing stage is sent
+        // to the client immediately, instead of after the action completes
+        let service = self.clone();
+        let handle = tokio::spawn(async move { service.execute_action(request).await });
+
+        let stream = async_stream::stream! {
+            yield Ok(executing);
+
+            let result = match handle.await {
+                Ok(Ok(response)) => operation::Result::Response(Any {
+                    type_url: RESPONSE_TYPE.into(),
+                    value: response.encode_to_vec().into(),
+                }),
+                Ok(Err(status)) => operation::Result::Error(create_status(&status)),
+                Err(error) => {
+                    operation::Result::Error(create_status(&Status::internal(error.to_string())))
+                }
+            };
+
+            yield Ok(Operation {
+                name,
+                metadata: Some(metadata),
+                done: true,
+                result: Some(result),
+            });
+        };
+
+        Ok(Response::new(Box::pin(stream)))
+    }
+
+    async fn wait_execution(
+        &self,
+        _request: Request<WaitExecutionRequest>,
+    ) -> Result<Response<OperationStream>, Status> {
+        Err(Status::unimplemented(
+            "Executions complete within a single stream",
+        ))
+    }
+}
+
+fn create_status(status: &Status) -> bazel_remote_apis::google::rpc::Status {
+    bazel_remote_apis::google::rpc::Status {
+        code: status.code() as i32,
+        message: status.message().to_owned(),
+        details: vec![],
+    }
+}
+
+fn create_metadata(request: &ExecuteRequest, stage: execution_stage::Value) -> Any {
+    Any {
+        type_url: METADATA_TYPE.into(),
+        value: ExecuteOperationMetadata {
+            stage: stage as i32,
+            action_digest: request.action_digest.clone(),
+            digest_function: request.digest_function,
+            ..Default::default()
+        }
+        .encode_to_vec()
+        .into(),
+    }
+}
+
+fn walk_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = vec![];
+
+    if let Ok(entries) = std::fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+
+            if path.is_dir() {
+                files.extend(walk_files(&path));
+            } else if path.is_file() {
+                files.push(path);
+            }
+        }
+    }
+
+    files.sort();
+    files
+}
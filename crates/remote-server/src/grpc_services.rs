@@ -0,0 +1,458 @@
+use crate::disk_storage::{DiskStorage, StorageKind};
+use bazel_remote_apis::build::bazel::remote::execution::v2::{
+    ActionCacheUpdateCapabilities, ActionResult, BatchReadBlobsRequest, BatchReadBlobsResponse,
+    BatchUpdateBlobsRequest, BatchUpdateBlobsResponse, CacheCapabilities, Digest,
+    ExecutionCapabilities, FindMissingBlobsRequest, FindMissingBlobsResponse,
+    GetActionResultRequest, GetCapabilitiesRequest, GetTreeRequest, GetTreeResponse,
+    ServerCapabilities, UpdateActionResultRequest, action_cache_server::ActionCache,
+    batch_read_blobs_response, batch_update_blobs_response, capabilities_server::Capabilities,
+    compressor, content_addressable_storage_server::ContentAddressableStorage, digest_function,
+};
+use bazel_remote_apis::build::bazel::semver::SemVer;
+use bazel_remote_apis::google::bytestream::{
+    QueryWriteStatusRequest, QueryWriteStatusResponse, ReadRequest, ReadResponse, WriteRequest,
+    WriteResponse, byte_stream_server::ByteStream,
+};
+use bazel_remote_apis::google::rpc::Status as RpcStatus;
+use moon_remote::create_digest;
+use prost::Message;
+use std::pin::Pin;
+use std::sync::Arc;
+use tonic::codegen::tokio_stream::{self, Stream, StreamExt};
+use tonic::{Code, Request, Response, Status, Streaming};
+use tracing::{trace, warn};
+
+pub const MAX_BATCH_SIZE: i64 = 4 * 1024 * 1024;
+
+const READ_CHUNK_SIZE: usize = 64 * 1024;
+
+type ServiceResult<T> = Result<Response<T>, Status>;
+
+/// Implements the REAPI action cache, CAS, bytestream, and capabilities
+/// services, backed by the disk storage.
+#[derive(Clone)]
+pub struct RemoteCacheService {
+    pub storage: Arc<DiskStorage>,
+
+    /// Advertise the execution service in the capabilities.
+    pub execute: bool,
+}
+
+impl RemoteCacheService {
+    fn read_blob(&self, digest: &Digest) -> Result<Option<Vec<u8>>, Status> {
+        self.storage
+            .read(StorageKind::Cas, &digest.hash)
+            .map_err(map_storage_error)
+    }
+
+    fn write_blob(&self, digest: &Digest, bytes: Vec<u8>, compressor: i32) -> Result<(), Status> {
+        let bytes = decompress(bytes, compressor)?;
+        let actual = create_digest(&bytes);
+
+        if &actual != digest {
+            return Err(Status::invalid_argument(format!(
+                "Mismatched blob digests, received {}:{}, but expected {}:{}",
+                actual.hash, actual.size_bytes, digest.hash, digest.size_bytes
+            )));
+        }
+
+        self.storage
+            .write(StorageKind::Cas, &digest.hash, &bytes)
+            .map_err(map_storage_error)
+    }
+
+    /// An action result is only valid if all of its outputs still exist,
+    /// as they may have been evicted since the result was cached.
+    fn has_all_outputs(&self, result: &ActionResult) -> bool {
+        let mut digests = vec![];
+
+        for file in &result.output_files {
+            digests.extend(file.digest.as_ref());
+        }
+
+        for dir in &result.output_directories {
+            digests.extend(dir.tree_digest.as_ref());
+        }
+
+        digests.extend(result.stdout_digest.as_ref());
+        digests.extend(result.stderr_digest.as_ref());
+
+        digests
+            .into_iter()
+            .filter(|digest| digest.size_bytes > 0)
+            .all(|digest| self.storage.contains(StorageKind::Cas, &digest.hash))
+    }
+
+    pub fn get_action_result(&self, hash: &str) -> Result<Option<ActionResult>, Status> {
+        let Some(bytes) = self
+            .storage
+            .read(StorageKind::ActionCache, hash)
+            .map_err(map_storage_error)?
+        else {
+            return Ok(None);
+        };
+
+        let result = ActionResult::decode(bytes.as_slice())
+            .map_err(|error| Status::data_loss(error.to_string()))?;
+
+        if !self.has_all_outputs(&result) {
+            trace!(hash, "Action result is missing outputs, treating as a miss");
+
+            return Ok(None);
+        }
+
+        Ok(Some(result))
+    }
+
+    pub fn update_action_result(&self, hash: &str, result: &ActionResult) -> Result<(), Status> {
+        self.storage
+            .write(StorageKind::ActionCache, hash, &result.encode_to_vec())
+            .map_err(map_storage_error)
+    }
+}
+
+#[tonic::async_trait]
+impl Capabilities for RemoteCacheService {
+    async fn get_capabilities(
+        &self,
+        _request: Request<GetCapabilitiesRequest>,
+    ) -> ServiceResult<ServerCapabilities> {
+        let compressors = vec![
+            compressor::Value::Identity as i32,
+            compressor::Value::Zstd as i32,
+        ];
+
+        Ok(Response::new(ServerCapabilities {
+            cache_capabilities: Some(CacheCapabilities {
+                digest_functions: vec![digest_function::Value::Sha256 as i32],
+                action_cache_update_capabilities: Some(ActionCacheUpdateCapabilities {
+                    update_enabled: true,
+                }),
+                max_batch_total_size_bytes: MAX_BATCH_SIZE,
+                supported_compressors: compressors.clone(),
+                supported_batch_update_compressors: compressors,
+                ..Default::default()
+            }),
+            execution_capabilities: self.execute.then(|| ExecutionCapabilities {
+                digest_function: digest_function::Value::Sha256 as i32,
+                digest_functions: vec![digest_function::Value::Sha256 as i32],
+                exec_enabled: true,
+                ..Default::default()
+            }),
+            low_api_version: Some(SemVer {
+                major: 2,
+                ..Default::default()
+            }),
+            high_api_version: Some(SemVer {
+                major: 2,
+                minor: 3,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+    }
+}
+
+#[tonic::async_trait]
+impl ActionCache for RemoteCacheService {
+    async fn get_action_result(
+        &self,
+        request: Request<GetActionResultRequest>,
+    ) -> ServiceResult<ActionResult> {
+        let digest = require_digest(request.into_inner().action_digest)?;
+
+        match RemoteCacheService::get_action_result(self, &digest.hash)? {
+            Some(result) => Ok(Response::new(result)),
+            None => Err(Status::not_found(format!(
+                "Action result {} not found",
+                digest.hash
+            ))),
+        }
+    }
+
+    async fn update_action_result(
+        &self,
+        request: Request<UpdateActionResultRequest>,
+    ) -> ServiceResult<ActionResult> {
+        let request = request.into_inner();
+        let digest = require_digest(request.action_digest)?;
+        let result = request.action_result.unwrap_or_default();
+
+        RemoteCacheService::update_action_result(self, &digest.hash, &result)?;
+
+        Ok(Response::new(result))
+    }
+}
+
+#[tonic::async_trait]
+impl ContentAddressableStorage for RemoteCacheService {
+    async fn find_missing_blobs(
+        &self,
+        request: Request<FindMissingBlobsRequest>,
+    ) -> ServiceResult<FindMissingBlobsResponse> {
+        let missing_blob_digests = request
+            .into_inner()
+            .blob_digests
+            .into_iter()
+            .filter(|digest| !self.storage.contains(StorageKind::Cas, &digest.hash))
+            .collect();
+
+        Ok(Response::new(FindMissingBlobsResponse {
+            missing_blob_digests,
+        }))
+    }
+
+    async fn batch_update_blobs(
+        &self,
+        request: Request<BatchUpdateBlobsRequest>,
+    ) -> ServiceResult<BatchUpdateBlobsResponse> {
+        let mut responses = vec![];
+
+        for upload in request.into_inner().requests {
+            let status = match &upload.digest {
+                Some(digest) => match self.write_blob(digest, upload.data, upload.compressor) {
+                    Ok(_) => ok_status(),
+                    Err(status) => {
+                        warn!(hash = &digest.hash, "Failed to store blob: {status}");
+
+                        to_rpc_status(status)
+                    }
+                },
+                None => to_rpc_status(Status::invalid_argument("Missing blob digest")),
+            };
+
+            responses.push(batch_update_blobs_response::Response {
+                digest: upload.digest,
+                status: Some(status),
+            });
+        }
+
+        Ok(Response::new(BatchUpdateBlobsResponse { responses }))
+    }
+
+    async fn batch_read_blobs(
+        &self,
+        request: Request<BatchReadBlobsRequest>,
+    ) -> ServiceResult<BatchReadBlobsResponse> {
+        let mut responses = vec![];
+
+        for digest in request.into_inner().digests {
+            let (data, status) = match self.read_blob(&digest) {
+                Ok(Some(data)) => (data, ok_status()),
+                Ok(None) => (
+                    vec![],
+                    to_rpc_status(Status::not_found(format!("Blob {} not found", digest.hash))),
+                ),
+                Err(status) => (vec![], to_rpc_status(status)),
+            };
+
+            responses.push(batch_read_blobs_response::Response {
+                digest: Some(digest),
+                data,
+                compressor: compressor::Value::Identity as i32,
+                status: Some(status),
+            });
+        }
+
+        Ok(Response::new(BatchReadBlobsResponse { responses }))
+    }
+
+    type GetTreeStream = Pin<Box<dyn Stream<Item = Result<GetTreeResponse, Status>> + Send>>;
+
+    async fn get_tree(
+        &self,
+        _request: Request<GetTreeRequest>,
+    ) -> ServiceResult<Self::GetTreeStream> {
+        Err(Status::unimplemented("Directory trees are not supported"))
+    }
+}
+
+#[tonic::async_trait]
+impl ByteStream for RemoteCacheService {
+    type ReadStream = Pin<Box<dyn Stream<Item = Result<ReadResponse, Status>> + Send>>;
+
+    async fn read(&self, request: Request<ReadRequest>) -> ServiceResult<Self::ReadStream> {
+        let request = request.into_inner();
+        let resource = parse_resource_name(&request.resource_name)?;
+
+        let Some(bytes) = self.read_blob(&resource.digest)? else {
+            return Err(Status::not_found(format!(
+                "Blob {} not found",
+                resource.digest.hash
+            )));
+        };
+
+        let mut bytes = compress(bytes, resource.compressor)?;
+        let offset = (request.read_offset.max(0) as usize).min(bytes.len());
+
+        bytes.drain(0..offset);
+
+        if request.read_limit > 0 {
+            bytes.truncate(request.read_limit as usize);
+        }
+
+        let chunks = bytes
+            .chunks(READ_CHUNK_SIZE)
+            .map(|chunk| {
+                Ok(ReadResponse {
+                    data: chunk.to_vec(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        Ok(Response::new(Box::pin(tokio_stream::iter(chunks))))
+    }
+
+    async fn write(
+        &self,
+        request: Request<Streaming<WriteRequest>>,
+    ) -> ServiceResult<WriteResponse> {
+        let mut stream = request.into_inner();
+        let mut resource = None;
+        let mut bytes = vec![];
+
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+
+            // Only the first request is required to include the resource name
+            if resource.is_none() {
+                resource = Some(parse_resource_name(&chunk.resource_name)?);
+            }
+
+            if chunk.write_offset as usize != bytes.len() {
+                return Err(Status::invalid_argument(format!(
+                    "Invalid write offset {}, expected {}",
+                    chunk.write_offset,
+                    bytes.len()
+                )));
+            }
+
+            bytes.extend(chunk.data);
+
+            if chunk.finish_write {
+                break;
+            }
+        }
+
+        let Some(resource) = resource else {
+            return Err(Status::invalid_argument("Missing resource name"));
+        };
+
+        self.write_blob(&resource.digest, bytes, resource.compressor)?;
+
+        Ok(Response::new(WriteResponse {
+            committed_size: resource.digest.size_bytes,
+        }))
+    }
+
+    async fn query_write_status(
+        &self,
+        request: Request<QueryWriteStatusRequest>,
+    ) -> ServiceResult<QueryWriteStatusResponse> {
+        let resource = parse_resource_name(&request.into_inner().resource_name)?;
+        let complete = self
+            .storage
+            .contains(StorageKind::Cas, &resource.digest.hash);
+
+        Ok(Response::new(QueryWriteStatusResponse {
+            committed_size: if complete {
+                resource.digest.size_bytes
+            } else {
+                0
+            },
+            complete,
+        }))
+    }
+}
+
+struct ResourceName {
+    compressor: i32,
+    digest: Digest,
+}
+
+// Supports the following formats:
+//  {instance}/blobs/{hash}/{size}
+//  {instance}/compressed-blobs/{compressor}/{hash}/{size}
+//  {instance}/uploads/{uuid}/blobs/{hash}/{size}
+//  {instance}/uploads/{uuid}/compressed-blobs/{compressor}/{hash}/{size}
+fn parse_resource_name(name: &str) -> Result<ResourceName, Status> {
+    let parts = name.split('/').collect::<Vec<_>>();
+    let invalid = || Status::invalid_argument(format!("Invalid resource name {name}"));
+
+    let (compressor, rest) = if let Some(index) = parts.iter().position(|part| *part == "blobs") {
+        (compressor::Value::Identity as i32, &parts[index + 1..])
+    } else if let Some(index) = parts.iter().position(|part| *part == "compressed-blobs") {
+        let compressor = match parts.get(index + 1) {
+            Some(&"zstd") => compressor::Value::Zstd as i32,
+            Some(&"identity") => compressor::Value::Identity as i32,
+            _ => {
+                return Err(Status::unimplemented(format!(
+                    "Unsupported compressor in {name}"
+                )));
+            }
+        };
+
+        (compressor, &parts[(index + 2).min(parts.len())..])
+    } else {
+        return Err(invalid());
+    };
+
+    let (Some(hash), Some(size)) = (rest.first(), rest.get(1)) else {
+        return Err(invalid());
+    };
+
+    Ok(ResourceName {
+        compressor,
+        digest: Digest {
+            hash: (*hash).to_owned(),
+            size_bytes: size.parse().map_err(|_| invalid())?,
+        },
+    })
+}
+
+fn compress(bytes: Vec<u8>, compressor: i32) -> Result<Vec<u8>, Status> {
+    if compressor == compressor::Value::Zstd as i32 {
+        return zstd::encode_all(bytes.as_slice(), 1)
+            .map_err(|error| Status::internal(error.to_string()));
+    }
+
+    Ok(bytes)
+}
+
+fn decompress(bytes: Vec<u8>, compressor: i32) -> Result<Vec<u8>, Status> {
+    if compressor == compressor::Value::Zstd as i32 {
+        return zstd::decode_all(bytes.as_slice())
+            .map_err(|error| Status::invalid_argument(error.to_string()));
+    }
+
+    if compressor != compressor::Value::Identity as i32 {
+        return Err(Status::unimplemented(format!(
+            "Unsupported compressor {compressor}"
+        )));
+    }
+
+    Ok(bytes)
+}
+
+fn require_digest(digest: Option<Digest>) -> Result<Digest, Status> {
+    digest.ok_or_else(|| Status::invalid_argument("Missing action digest"))
+}
+
+fn map_storage_error(error: miette::Report) -> Status {
+    Status::new(Code::Internal, error.to_string())
+}
+
+fn ok_status() -> RpcStatus {
+    RpcStatus {
+        code: Code::Ok as i32,
+        ..Default::default()
+    }
+}
+
+fn to_rpc_status(status: Status) -> RpcStatus {
+    RpcStatus {
+        code: status.code() as i32,
+        message: status.message().to_owned(),
+        details: vec![],
+    }
+}
@@ -0,0 +1,143 @@
+use crate::disk_storage::StorageKind;
+use crate::grpc_services::RemoteCacheService;
+use axum::Router;
+use axum::body::Bytes;
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use bazel_remote_apis::build::bazel::remote::execution::v2::ActionResult;
+use moon_remote::create_digest;
+use prost::Message;
+use rustc_hash::FxHashMap;
+use tracing::warn;
+
+type PathParams = Path<FxHashMap<String, String>>;
+
+const JSON_TYPE: &str = "application/json";
+const OCTET_TYPE: &str = "application/octet-stream";
+
+// Implements the same HTTP API as `bazel-remote`, where the instance
+// name is optional and is ignored (all instances share the same storage):
+//  GET/PUT /{instance}/ac/{hash}
+//  GET/PUT /{instance}/cas/{hash}
+pub fn create_http_router(service: RemoteCacheService) -> Router {
+    Router::new()
+        .route("/status", get(get_status))
+        .route("/ac/{hash}", get(get_action_result).put(put_action_result))
+        .route("/cas/{hash}", get(get_blob).put(put_blob))
+        .route(
+            "/{instance}/ac/{hash}",
+            get(get_action_result).put(put_action_result),
+        )
+        .route("/{instance}/cas/{hash}", get(get_blob).put(put_blob))
+        .with_state(service)
+}
+
+fn is_json(headers: &HeaderMap, name: header::HeaderName) -> bool {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.contains(JSON_TYPE))
+}
+
+fn error_response(status: StatusCode, message: impl AsRef<str>) -> Response {
+    (status, message.as_ref().to_owned()).into_response()
+}
+
+async fn get_status(State(service): State<RemoteCacheService>) -> Response {
+    let body = serde_json::json!({
+        "entries": service.storage.get_entry_count(),
+        "maxSize": service.storage.max_size,
+        "totalSize": service.storage.get_total_size(),
+    });
+
+    ([(header::CONTENT_TYPE, JSON_TYPE)], body.to_string()).into_response()
+}
+
+async fn get_action_result(
+    State(service): State<RemoteCacheService>,
+    Path(params): PathParams,
+    headers: HeaderMap,
+) -> Response {
+    let hash = &params["hash"];
+
+    let result = match service.get_action_result(hash) {
+        Ok(Some(result)) => result,
+        Ok(None) => return error_response(StatusCode::NOT_FOUND, "Not found"),
+        Err(status) => return error_response(StatusCode::INTERNAL_SERVER_ERROR, status.message()),
+    };
+
+    if is_json(&headers, header::ACCEPT) {
+        match serde_json::to_vec(&result) {
+            Ok(body) => ([(header::CONTENT_TYPE, JSON_TYPE)], body).into_response(),
+            Err(error) => error_response(StatusCode::INTERNAL_SERVER_ERROR, error.to_string()),
+        }
+    } else {
+        ([(header::CONTENT_TYPE, OCTET_TYPE)], result.encode_to_vec()).into_response()
+    }
+}
+
+async fn put_action_result(
+    State(service): State<RemoteCacheService>,
+    Path(params): PathParams,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let hash = &params["hash"];
+
+    let result = if is_json(&headers, header::CONTENT_TYPE) {
+        serde_json::from_slice::<ActionResult>(&body).map_err(|error| error.to_string())
+    } else {
+        ActionResult::decode(body).map_err(|error| error.to_string())
+    };
+
+    let result = match result {
+        Ok(result) => result,
+        Err(error) => return error_response(StatusCode::BAD_REQUEST, error),
+    };
+
+    match service.update_action_result(hash, &result) {
+        Ok(_) => StatusCode::OK.into_response(),
+        Err(status) => {
+            warn!(hash, "Failed to store action result: {}", status.message());
+
+            error_response(StatusCode::INTERNAL_SERVER_ERROR, status.message())
+        }
+    }
+}
+
+async fn get_blob(State(service): State<RemoteCacheService>, Path(params): PathParams) -> Response {
+    let hash = &params["hash"];
+
+    match service.storage.read(StorageKind::Cas, hash) {
+        Ok(Some(bytes)) => ([(header::CONTENT_TYPE, OCTET_TYPE)], bytes).into_response(),
+        Ok(None) => error_response(StatusCode::NOT_FOUND, "Not found"),
+        Err(error) => error_response(StatusCode::INTERNAL_SERVER_ERROR, error.to_string()),
+    }
+}
+
+async fn put_blob(
+    State(service): State<RemoteCacheService>,
+    Path(params): PathParams,
+    body: Bytes,
+) -> Response {
+    let hash = &params["hash"];
+    let digest = create_digest(&body);
+
+    if &digest.hash != hash {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            format!("Mismatched blob digests, received {}", digest.hash),
+        );
+    }
+
+    match service.storage.write(StorageKind::Cas, hash, &body) {
+        Ok(_) => StatusCode::OK.into_response(),
+        Err(error) => {
+            warn!(hash, "Failed to store blob: {error}");
+
+            error_response(StatusCode::INTERNAL_SERVER_ERROR, error.to_string())
+        }
+    }
+}
@@ -0,0 +1,13 @@
+mod disk_storage;
+mod execution_service;
+mod grpc_services;
+mod http_server;
+mod remote_server;
+mod remote_server_error;
+
+pub use disk_storage::*;
+pub use execution_service::*;
+pub use grpc_services::*;
+pub use http_server::*;
+pub use remote_server::*;
+pub use remote_server_error::*;
@@ -0,0 +1,96 @@
+use crate::disk_storage::DiskStorage;
+use crate::execution_service::LocalExecutionService;
+use crate::grpc_services::RemoteCacheService;
+use crate::http_server::create_http_router;
+use crate::remote_server_error::RemoteServerError;
+use bazel_remote_apis::build::bazel::remote::execution::v2::{
+    action_cache_server::ActionCacheServer, capabilities_server::CapabilitiesServer,
+    content_addressable_storage_server::ContentAddressableStorageServer,
+    execution_server::ExecutionServer,
+};
+use bazel_remote_apis::google::bytestream::byte_stream_server::ByteStreamServer;
+use std::future::Future;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tonic::service::Routes;
+use tonic::transport::Server;
+use tonic::transport::server::TcpIncoming;
+use tracing::{debug, instrument};
+
+const MAX_MESSAGE_SIZE: usize = 256 * 1024 * 1024;
+
+/// A remote cache server that supports both the Bazel Remote Execution API
+/// (gRPC) and the `bazel-remote` HTTP API, on the same port.
+pub struct RemoteServer {
+    pub storage: Arc<DiskStorage>,
+
+    /// Run actions locally through the Execution service.
+    pub execute: bool,
+}
+
+impl RemoteServer {
+    pub fn new(storage: DiskStorage) -> Self {
+        Self {
+            storage: Arc::new(storage),
+            execute: false,
+        }
+    }
+
+    #[instrument(skip_all)]
+    pub async fn serve(
+        &self,
+        listener: TcpListener,
+        shutdown: impl Future<Output = ()>,
+    ) -> miette::Result<()> {
+        debug!(
+            address = ?listener.local_addr().ok(),
+            root = ?self.storage.root,
+            execute = self.execute,
+            "Starting remote cache server"
+        );
+
+        let service = RemoteCacheService {
+            storage: Arc::clone(&self.storage),
+            execute: self.execute,
+        };
+
+        let mut routes = Routes::from(create_http_router(service.clone()))
+            .add_service(CapabilitiesServer::new(service.clone()))
+            .add_service(
+                ActionCacheServer::new(service.clone())
+                    .max_decoding_message_size(MAX_MESSAGE_SIZE)
+                    .max_encoding_message_size(MAX_MESSAGE_SIZE),
+            )
+            .add_service(
+                ContentAddressableStorageServer::new(service.clone())
+                    .max_decoding_message_size(MAX_MESSAGE_SIZE)
+                    .max_encoding_message_size(MAX_MESSAGE_SIZE),
+            )
+            .add_service(
+                ByteStreamServer::new(service.clone())
+                    .max_decoding_message_size(MAX_MESSAGE_SIZE)
+                    .max_encoding_message_size(MAX_MESSAGE_SIZE),
+            );
+
+        if self.execute {
+            routes = routes.add_service(
+                ExecutionServer::new(LocalExecutionService { cache: service })
+                    .max_decoding_message_size(MAX_MESSAGE_SIZE)
+                    .max_encoding_message_size(MAX_MESSAGE_SIZE),
+            );
+        }
+
+        Server::builder()
+            .accept_http1(true)
+            .add_routes(routes)
+            .serve_with_incoming_shutdown(TcpIncoming::from(listener), shutdown)
+            .await
+            .map_err(|error| RemoteServerError::ServeFailed {
+                error: Box::new(error),
+            })?;
+
+        debug!("Stopped remote cache server");
+
+        Ok(())
+    }
+}
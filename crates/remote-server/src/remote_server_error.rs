@@ -0,0 +1,35 @@
+use miette::Diagnostic;
+use moon_common::{Style, Stylize};
+use thiserror::Error;
+
+#[derive(Error, Debug, Diagnostic)]
+pub enum RemoteServerError {
+    #[diagnostic(code(remote_server::blob_too_large))]
+    #[error(
+        "Unable to store blob {}, its size of {size} bytes is larger than the maximum cache size of {max_size} bytes.",
+        .hash.style(Style::Hash),
+    )]
+    BlobTooLarge {
+        hash: String,
+        size: u64,
+        max_size: u64,
+    },
+
+    #[diagnostic(code(remote_server::invalid_hash))]
+    #[error("Invalid digest hash {}, must be a hexadecimal string.", .0.style(Style::Hash))]
+    InvalidHash(String),
+
+    #[diagnostic(code(remote_server::invalid_size))]
+    #[error(
+        "Invalid size {}, must be a number with an optional unit (B, KB, MB, GB, TB).",
+        .0.style(Style::Symbol),
+    )]
+    InvalidSize(String),
+
+    #[diagnostic(code(remote_server::serve_failed))]
+    #[error("Failed to serve the remote cache.")]
+    ServeFailed {
+        #[source]
+        error: Box<tonic::transport::Error>,
+    },
+}
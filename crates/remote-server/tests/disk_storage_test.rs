@@ -0,0 +1,80 @@
+use moon_remote_server::*;
+use starbase_sandbox::create_empty_sandbox;
+
+const HASH_A: &str = "aaaa";
+const HASH_B: &str = "bbbb";
+const HASH_C: &str = "cccc";
+
+mod disk_storage {
+    use super::*;
+
+    #[test]
+    fn writes_and_reads_entries() {
+        let sandbox = create_empty_sandbox();
+        let storage = DiskStorage::new(sandbox.path(), 0).unwrap();
+
+        storage.write(StorageKind::Cas, HASH_A, b"abc").unwrap();
+
+        assert!(sandbox.path().join("cas/aa/aaaa").exists());
+        assert!(storage.contains(StorageKind::Cas, HASH_A));
+        assert!(!storage.contains(StorageKind::ActionCache, HASH_A));
+        assert_eq!(
+            storage.read(StorageKind::Cas, HASH_A).unwrap(),
+            Some(b"abc".to_vec())
+        );
+        assert_eq!(storage.read(StorageKind::Cas, HASH_B).unwrap(), None);
+        assert_eq!(storage.get_total_size(), 3);
+    }
+
+    #[test]
+    fn rejects_invalid_hashes() {
+        let sandbox = create_empty_sandbox();
+        let storage = DiskStorage::new(sandbox.path(), 0).unwrap();
+
+        assert!(storage.write(StorageKind::Cas, "../aa", b"abc").is_err());
+        assert!(storage.write(StorageKind::Cas, "a", b"abc").is_err());
+        assert!(storage.write(StorageKind::Cas, "xyz", b"abc").is_err());
+    }
+
+    #[test]
+    fn rejects_blobs_larger_than_max() {
+        let sandbox = create_empty_sandbox();
+        let storage = DiskStorage::new(sandbox.path(), 2).unwrap();
+
+        assert!(storage.write(StorageKind::Cas, HASH_A, b"abc").is_err());
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let sandbox = create_empty_sandbox();
+        let storage = DiskStorage::new(sandbox.path(), 6).unwrap();
+
+        storage.write(StorageKind::Cas, HASH_A, b"aaa").unwrap();
+        storage.write(StorageKind::Cas, HASH_B, b"bbb").unwrap();
+
+        // Access A so that B becomes the least recently used
+        storage.read(StorageKind::Cas, HASH_A).unwrap();
+        storage.write(StorageKind::Cas, HASH_C, b"ccc").unwrap();
+
+        assert!(storage.contains(StorageKind::Cas, HASH_A));
+        assert!(!storage.contains(StorageKind::Cas, HASH_B));
+        assert!(storage.contains(StorageKind::Cas, HASH_C));
+        assert!(!sandbox.path().join("cas/bb/bbbb").exists());
+        assert_eq!(storage.get_total_size(), 6);
+    }
+
+    #[test]
+    fn loads_existing_entries() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file("ac/aa/aaaa", "aaa");
+        sandbox.create_file("cas/bb/bbbb", "bbb");
+        sandbox.create_file("cas/bb/bbbb.123.tmp", "bbb");
+
+        let storage = DiskStorage::new(sandbox.path(), 0).unwrap();
+
+        assert_eq!(storage.get_entry_count(), 2);
+        assert!(storage.contains(StorageKind::ActionCache, HASH_A));
+        assert!(storage.contains(StorageKind::Cas, HASH_B));
+        assert!(!sandbox.path().join("cas/bb/bbbb.123.tmp").exists());
+    }
+}
@@ -0,0 +1,200 @@
+use bazel_remote_apis::build::bazel::remote::execution::v2::{Action, Command};
+use moon_common::path::WorkspaceRelativePathBuf;
+use moon_config::{RemoteApi, RemoteConfig};
+use moon_remote::*;
+use moon_remote_server::*;
+use prost::Message;
+use starbase_sandbox::{Sandbox, create_empty_sandbox};
+use std::net::SocketAddr;
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+
+async fn start_server(sandbox: &Sandbox) -> (SocketAddr, oneshot::Sender<()>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let mut server = RemoteServer::new(DiskStorage::new(&sandbox.path().join("cache"), 0).unwrap());
+    let (sender, receiver) = oneshot::channel::<()>();
+
+    server.execute = true;
+
+    tokio::spawn(async move {
+        server
+            .serve(listener, async move {
+                let _ = receiver.await;
+            })
+            .await
+            .unwrap();
+    });
+
+    (address, sender)
+}
+
+async fn connect_client(sandbox: &Sandbox, address: SocketAddr) -> GrpcRemoteClient {
+    let mut client = GrpcRemoteClient::default();
+
+    let config = RemoteConfig {
+        api: RemoteApi::Grpc,
+        host: format!("grpc://{address}"),
+        ..Default::default()
+    };
+
+    assert!(
+        client
+            .connect_to_host(&config, &sandbox.path().join("workspace"))
+            .await
+            .unwrap()
+    );
+
+    client
+}
+
+async fn upload_action(
+    client: &GrpcRemoteClient,
+    sandbox: &Sandbox,
+    arguments: Vec<String>,
+    output_paths: Vec<String>,
+) -> Digest {
+    let (input_root_digest, mut blobs) = create_input_root(
+        &[
+            WorkspaceRelativePathBuf::from("project/input.txt"),
+            WorkspaceRelativePathBuf::from("project/missing.txt"),
+        ],
+        &sandbox.path().join("workspace"),
+    )
+    .unwrap();
+
+    let command_blob = Blob::from(
+        Command {
+            arguments,
+            output_paths,
+            working_directory: "project".into(),
+            ..Default::default()
+        }
+        .encode_to_vec(),
+    );
+
+    let action_blob = Blob::from(
+        Action {
+            command_digest: Some(command_blob.digest.clone()),
+            input_root_digest: Some(input_root_digest),
+            ..Default::default()
+        }
+        .encode_to_vec(),
+    );
+
+    let action_digest = action_blob.digest.clone();
+
+    blobs.push(command_blob);
+    blobs.push(action_blob);
+
+    client
+        .batch_update_blobs(&action_digest, blobs)
+        .await
+        .unwrap();
+
+    action_digest
+}
+
+mod execution_service {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn advertises_execution_capabilities() {
+        let sandbox = create_empty_sandbox();
+        let (address, shutdown) = start_server(&sandbox).await;
+        let client = connect_client(&sandbox, address).await;
+
+        let capabilities = client.load_capabilities().await.unwrap();
+
+        assert!(capabilities.execution_capabilities.unwrap().exec_enabled);
+
+        shutdown.send(()).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn executes_action_with_inputs_and_outputs() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file("workspace/project/input.txt", "hello");
+
+        let (address, shutdown) = start_server(&sandbox).await;
+        let client = connect_client(&sandbox, address).await;
+
+        let action_digest = upload_action(
+            &client,
+            &sandbox,
+            vec![
+                "/bin/sh".into(),
+                "-c".into(),
+                "mkdir -p out && cat input.txt > out/copy.txt && echo done".into(),
+            ],
+            vec!["out".into()],
+        )
+        .await;
+
+        let result = client.execute_action(&action_digest, false).await.unwrap();
+
+        assert_eq!(result.exit_code, 0);
+        assert_eq!(result.stdout_raw, b"done\n".to_vec());
+        assert_eq!(result.output_files.len(), 1);
+        assert_eq!(result.output_files[0].path, "out/copy.txt");
+        assert_eq!(result.output_files[0].digest, Some(create_digest(b"hello")));
+
+        // Successful results are cached
+        assert_eq!(
+            client.get_action_result(&action_digest).await.unwrap(),
+            Some(result)
+        );
+
+        shutdown.send(()).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn does_not_cache_failed_actions() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file("workspace/project/input.txt", "hello");
+
+        let (address, shutdown) = start_server(&sandbox).await;
+        let client = connect_client(&sandbox, address).await;
+
+        let action_digest = upload_action(
+            &client,
+            &sandbox,
+            vec![
+                "/bin/sh".into(),
+                "-c".into(),
+                "echo oops >&2; exit 3".into(),
+            ],
+            vec![],
+        )
+        .await;
+
+        let result = client.execute_action(&action_digest, false).await.unwrap();
+
+        assert_eq!(result.exit_code, 3);
+        assert_eq!(result.stderr_raw, b"oops\n".to_vec());
+        assert_eq!(
+            client.get_action_result(&action_digest).await.unwrap(),
+            None
+        );
+
+        shutdown.send(()).unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn errors_when_action_is_missing() {
+        let sandbox = create_empty_sandbox();
+        let (address, shutdown) = start_server(&sandbox).await;
+        let client = connect_client(&sandbox, address).await;
+
+        assert!(
+            client
+                .execute_action(&create_digest(b"unknown"), false)
+                .await
+                .is_err()
+        );
+
+        shutdown.send(()).unwrap();
+    }
+}
@@ -0,0 +1,184 @@
+use bazel_remote_apis::build::bazel::remote::execution::v2::{ActionResult, OutputFile};
+use moon_config::{RemoteApi, RemoteConfig};
+use moon_remote::*;
+use moon_remote_server::*;
+use starbase_sandbox::{Sandbox, create_empty_sandbox};
+use std::net::SocketAddr;
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+
+async fn start_server(sandbox: &Sandbox) -> (SocketAddr, oneshot::Sender<()>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let server = RemoteServer::new(DiskStorage::new(&sandbox.path().join("cache"), 0).unwrap());
+    let (sender, receiver) = oneshot::channel::<()>();
+
+    tokio::spawn(async move {
+        server
+            .serve(listener, async move {
+                let _ = receiver.await;
+            })
+            .await
+            .unwrap();
+    });
+
+    (address, sender)
+}
+
+async fn connect_client(sandbox: &Sandbox, api: RemoteApi, host: String) -> Box<dyn RemoteClient> {
+    let mut client: Box<dyn RemoteClient> = match api {
+        RemoteApi::Grpc => Box::new(GrpcRemoteClient::default()),
+        RemoteApi::Http => Box::new(HttpRemoteClient::default()),
+    };
+
+    let config = RemoteConfig {
+        api,
+        host,
+        ..Default::default()
+    };
+
+    assert!(
+        client
+            .connect_to_host(&config, sandbox.path())
+            .await
+            .unwrap()
+    );
+
+    client
+}
+
+async fn round_trip(client: Box<dyn RemoteClient>) {
+    let action_digest = create_digest(b"action");
+    let bytes = b"hello world".to_vec();
+    let digest = create_digest(&bytes);
+
+    // Empty cache
+    assert_eq!(
+        client.get_action_result(&action_digest).await.unwrap(),
+        None
+    );
+
+    // Upload outputs
+    let uploaded = client
+        .batch_update_blobs(
+            &action_digest,
+            vec![Blob::new(digest.clone(), bytes.clone())],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(uploaded, vec![Some(digest.clone())]);
+
+    // Upload result
+    let result = ActionResult {
+        exit_code: 0,
+        output_files: vec![OutputFile {
+            path: "out.txt".into(),
+            digest: Some(digest.clone()),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    client
+        .update_action_result(&action_digest, result.clone())
+        .await
+        .unwrap();
+
+    // Download everything
+    assert_eq!(
+        client.get_action_result(&action_digest).await.unwrap(),
+        Some(result)
+    );
+
+    let blobs = client
+        .batch_read_blobs(&action_digest, vec![digest.clone()])
+        .await
+        .unwrap();
+
+    assert_eq!(blobs[0].as_ref().unwrap().bytes, bytes);
+}
+
+mod remote_server {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn round_trips_with_grpc_client() {
+        let sandbox = create_empty_sandbox();
+        let (address, shutdown) = start_server(&sandbox).await;
+
+        round_trip(connect_client(&sandbox, RemoteApi::Grpc, format!("grpc://{address}")).await)
+            .await;
+
+        shutdown.send(()).unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn round_trips_with_http_client() {
+        let sandbox = create_empty_sandbox();
+        let (address, shutdown) = start_server(&sandbox).await;
+
+        round_trip(connect_client(&sandbox, RemoteApi::Http, format!("http://{address}")).await)
+            .await;
+
+        shutdown.send(()).unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn shares_storage_between_protocols() {
+        let sandbox = create_empty_sandbox();
+        let (address, shutdown) = start_server(&sandbox).await;
+        let action_digest = create_digest(b"action");
+        let bytes = b"shared".to_vec();
+        let digest = create_digest(&bytes);
+
+        let grpc = connect_client(&sandbox, RemoteApi::Grpc, format!("grpc://{address}")).await;
+
+        grpc.batch_update_blobs(
+            &action_digest,
+            vec![Blob::new(digest.clone(), bytes.clone())],
+        )
+        .await
+        .unwrap();
+
+        let http = connect_client(&sandbox, RemoteApi::Http, format!("http://{address}")).await;
+        let blobs = http
+            .batch_read_blobs(&action_digest, vec![digest])
+            .await
+            .unwrap();
+
+        assert_eq!(blobs[0].as_ref().unwrap().bytes, bytes);
+
+        shutdown.send(()).unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn misses_when_outputs_were_evicted() {
+        let sandbox = create_empty_sandbox();
+        let (address, shutdown) = start_server(&sandbox).await;
+        let client = connect_client(&sandbox, RemoteApi::Grpc, format!("grpc://{address}")).await;
+        let action_digest = create_digest(b"action");
+
+        client
+            .update_action_result(
+                &action_digest,
+                ActionResult {
+                    output_files: vec![OutputFile {
+                        path: "out.txt".into(),
+                        digest: Some(create_digest(b"missing")),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            client.get_action_result(&action_digest).await.unwrap(),
+            None
+        );
+
+        shutdown.send(()).unwrap();
+    }
+}
@@ -1,18 +1,25 @@
 use crate::blob::*;
-use crate::fs_digest::{OutputDigests, create_timestamp_from_naive};
+use crate::fs_digest::{OutputDigests, create_input_root, create_timestamp_from_naive};
 use bazel_remote_apis::build::bazel::remote::execution::v2::{
-    Action, ActionResult, Command, Digest, ExecutedActionMetadata, command, platform,
+    Action, ActionResult, Command, Digest, ExecutedActionMetadata, Platform, command, platform,
 };
+use bazel_remote_apis::google::protobuf::Duration;
 use moon_action::Operation;
+use moon_common::path::{PathExt, WorkspaceRelativePath, WorkspaceRelativePathBuf};
+use moon_feature_flags::glob_walk_with_options;
 use moon_task::Task;
+use prost::Message;
+use starbase_utils::glob::GlobWalkOptions;
 use std::collections::BTreeMap;
 use std::path::Path;
+use std::sync::Arc;
 
 pub struct ActionState<'task> {
     task: &'task Task,
 
     // RE API
     pub action: Option<Action>,
+    pub action_digest: Option<Digest>,
     pub action_result: Option<ActionResult>,
     pub command: Option<Command>,
     pub digest: Digest,
@@ -22,6 +29,11 @@ pub struct ActionState<'task> {
 
     // Bytes of our hashed manifest
     pub bytes: Vec<u8>,
+
+    // Input files (from the hasher) and the blobs
+    // required for remote execution
+    pub inputs: Vec<WorkspaceRelativePathBuf>,
+    pub input_blobs: Vec<Blob>,
 }
 
 impl ActionState<'_> {
@@ -29,25 +41,69 @@ impl ActionState<'_> {
         ActionState {
             task,
             action: None,
+            action_digest: None,
             action_result: None,
             command: None,
             digest,
             blobs: vec![],
             bytes: vec![],
+            inputs: vec![],
+            input_blobs: vec![],
         }
     }
 
-    pub fn create_action_from_task(&mut self) {
-        let mut action = Action {
-            command_digest: Some(self.digest.clone()),
-            do_not_cache: !self.task.options.cache,
-            input_root_digest: None, // TODO?
-            ..Default::default()
-        };
+    /// Include the outputs of dependencies in the input root, as they are
+    /// not available on the remote service, but are required by the task.
+    pub fn include_dependency_outputs(
+        &mut self,
+        workspace_root: &Path,
+        dependencies: &[Arc<Task>],
+    ) -> miette::Result<()> {
+        for dep in dependencies {
+            let mut dir_globs = vec![];
+
+            for file in dep.get_output_files(workspace_root, true)? {
+                if file.to_path(workspace_root).is_dir() {
+                    dir_globs.push(file.join("**/*"));
+                } else {
+                    self.inputs.push(file);
+                }
+            }
+
+            if !dir_globs.is_empty() {
+                for file in glob_walk_with_options(
+                    workspace_root,
+                    &dir_globs,
+                    GlobWalkOptions::default().cache().files(),
+                )? {
+                    // Glob results are absolute paths!
+                    if let Ok(file) = file.relative_to(workspace_root) {
+                        self.inputs.push(file);
+                    }
+                }
+            }
+        }
+
+        self.inputs.sort();
+        self.inputs.dedup();
+
+        Ok(())
+    }
 
+    /// Create an action from the task, with the arguments and environment variables
+    /// of the command that would have been executed locally (including the shell).
+    pub fn create_action_from_task(
+        &mut self,
+        workspace_root: &Path,
+        working_dir: &WorkspaceRelativePath,
+        arguments: Vec<String>,
+        env: BTreeMap<String, String>,
+    ) -> miette::Result<()> {
         // https://github.com/bazelbuild/remote-apis/blob/main/build/bazel/remote/execution/v2/platform.md
+        let mut platform = None;
+
         if let Some(os_list) = &self.task.options.os {
-            let platform = action.platform.get_or_insert_default();
+            let platform = platform.get_or_insert_with(Platform::default);
 
             for os in os_list {
                 platform.properties.push(platform::Property {
@@ -57,24 +113,56 @@ impl ActionState<'_> {
             }
         }
 
-        // Since we don't support (or plan to) remote execution,
-        // then we can ignore all the working directory logic
         let mut command = Command {
-            arguments: vec![self.task.command.clone()],
-            output_paths: vec![], // TODO?
+            arguments,
+            working_directory: working_dir.to_string(),
             ..Default::default()
         };
 
-        command.arguments.extend(self.task.args.clone());
-
-        for (name, value) in BTreeMap::from_iter(self.task.env.clone()) {
+        for (name, value) in env {
             command
                 .environment_variables
                 .push(command::EnvironmentVariable { name, value });
         }
 
+        // Output paths are relative to the working directory, and only literal
+        // files/directories are supported, so tasks with output globs are not
+        // executed remotely
+        let mut output_paths = self
+            .task
+            .output_files
+            .iter()
+            .map(|output| working_dir.relative(output).to_string())
+            .collect::<Vec<_>>();
+
+        output_paths.sort();
+        command.output_paths = output_paths;
+
+        let command_blob = Blob::from(command.encode_to_vec());
+        let (input_root_digest, input_blobs) = create_input_root(&self.inputs, workspace_root)?;
+
+        let action = Action {
+            command_digest: Some(command_blob.digest.clone()),
+            input_root_digest: Some(input_root_digest),
+            do_not_cache: !self.task.options.cache,
+            timeout: self.task.options.timeout.map(|seconds| Duration {
+                seconds: seconds as i64,
+                nanos: 0,
+            }),
+            platform,
+            ..Default::default()
+        };
+
+        let action_blob = Blob::from(action.encode_to_vec());
+
+        self.action_digest = Some(action_blob.digest.clone());
+        self.input_blobs = input_blobs;
+        self.input_blobs.push(command_blob);
+        self.input_blobs.push(action_blob);
         self.action = Some(action);
         self.command = Some(command);
+
+        Ok(())
     }
 
     pub fn create_action_result_from_operation(
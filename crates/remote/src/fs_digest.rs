@@ -2,15 +2,19 @@
 
 use crate::blob::Blob;
 use bazel_remote_apis::build::bazel::remote::execution::v2::{
-    Digest, NodeProperties, OutputDirectory, OutputFile, OutputSymlink,
+    Digest, Directory, DirectoryNode, FileNode, NodeProperties, OutputDirectory, OutputFile,
+    OutputSymlink,
 };
 use bazel_remote_apis::google::protobuf::Timestamp;
 use chrono::NaiveDateTime;
 use moon_common::path::{PathExt, WorkspaceRelativePathBuf};
 use moon_feature_flags::glob_walk_with_options;
+use prost::Message;
+use rustc_hash::FxHashSet;
 use sha2::{Digest as Sha256Digest, Sha256};
 use starbase_utils::fs::FsError;
 use starbase_utils::glob::GlobWalkOptions;
+use std::collections::BTreeMap;
 use std::{
     fs::{self, Metadata},
     path::{Path, PathBuf},
@@ -110,7 +114,14 @@ impl OutputDigests {
         };
 
         if abs_path.is_symlink() {
-            let link = fs::read_link(&abs_path).map_err(map_read_error)?;
+            let mut link = fs::read_link(&abs_path).map_err(map_read_error)?;
+
+            // Relative targets are relative to the symlink's parent directory
+            if link.is_relative() {
+                if let Some(parent) = abs_path.parent() {
+                    link = parent.join(link);
+                }
+            }
             let metadata = fs::metadata(&abs_path).map_err(map_read_error)?;
             let props = compute_node_properties(&metadata);
 
@@ -147,6 +158,101 @@ impl OutputDigests {
     }
 }
 
+#[derive(Default)]
+struct InputTree {
+    dirs: BTreeMap<String, InputTree>,
+    files: BTreeMap<String, FileNode>,
+}
+
+impl InputTree {
+    fn into_directory(self, blobs: &mut Vec<Blob>) -> Digest {
+        let directory = Directory {
+            // Nodes must be sorted by name, which the maps guarantee
+            directories: self
+                .dirs
+                .into_iter()
+                .map(|(name, tree)| DirectoryNode {
+                    digest: Some(tree.into_directory(blobs)),
+                    name,
+                })
+                .collect(),
+            files: self.files.into_values().collect(),
+            ..Default::default()
+        };
+
+        let blob = Blob::from(directory.encode_to_vec());
+        let digest = blob.digest.clone();
+
+        blobs.push(blob);
+        digest
+    }
+}
+
+/// Create a Merkle tree of directories from the provided files, that can be
+/// used as the input root for remote execution. Returns the digest of the root
+/// directory, and the blobs for all directories and files within the tree.
+pub fn create_input_root(
+    files: &[WorkspaceRelativePathBuf],
+    workspace_root: &Path,
+) -> miette::Result<(Digest, Vec<Blob>)> {
+    let mut root = InputTree::default();
+    let mut blobs = vec![];
+
+    for file in files {
+        let abs_path = file.to_path(workspace_root);
+
+        // Inputs may reference files that do not exist
+        if !abs_path.is_file() {
+            continue;
+        }
+
+        let map_read_error = |error| FsError::Read {
+            path: abs_path.clone(),
+            error: Box::new(error),
+        };
+
+        let bytes = fs::read(&abs_path).map_err(map_read_error)?;
+        let metadata = fs::metadata(&abs_path).map_err(map_read_error)?;
+        let blob = Blob::from(bytes);
+
+        let mut tree = &mut root;
+
+        if let Some(parent) = file.parent() {
+            for component in parent.components() {
+                tree = tree.dirs.entry(component.as_str().to_owned()).or_default();
+            }
+        }
+
+        if let Some(name) = file.file_name() {
+            // Don't include node properties (mtime) as they would
+            // cause the input root digest to change on every checkout
+            tree.files.insert(
+                name.to_owned(),
+                FileNode {
+                    name: name.to_owned(),
+                    digest: Some(blob.digest.clone()),
+                    is_executable: is_file_executable(
+                        &abs_path,
+                        &compute_node_properties(&metadata),
+                    ),
+                    node_properties: None,
+                },
+            );
+
+            blobs.push(blob);
+        }
+    }
+
+    let digest = root.into_directory(&mut blobs);
+
+    // Multiple files may have the same contents
+    let mut seen = FxHashSet::default();
+
+    blobs.retain(|blob| seen.insert(blob.digest.hash.clone()));
+
+    Ok((digest, blobs))
+}
+
 fn apply_node_properties(path: &Path, props: &NodeProperties) -> miette::Result<()> {
     if let Some(mtime) = &props.mtime {
         let modified = Duration::new(mtime.seconds as u64, mtime.nanos as u32);
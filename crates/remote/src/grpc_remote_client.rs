@@ -4,19 +4,23 @@ use crate::grpc_tls::*;
 use crate::remote_client::RemoteClient;
 use crate::remote_error::RemoteError;
 use bazel_remote_apis::build::bazel::remote::execution::v2::{
-    ActionResult, BatchReadBlobsRequest, BatchUpdateBlobsRequest, Digest, FindMissingBlobsRequest,
-    GetActionResultRequest, GetCapabilitiesRequest, ServerCapabilities, UpdateActionResultRequest,
+    ActionResult, BatchReadBlobsRequest, BatchUpdateBlobsRequest, Digest, ExecuteOperationMetadata,
+    ExecuteRequest, ExecuteResponse, FindMissingBlobsRequest, GetActionResultRequest,
+    GetCapabilitiesRequest, ServerCapabilities, UpdateActionResultRequest,
     action_cache_client::ActionCacheClient, batch_update_blobs_request,
     capabilities_client::CapabilitiesClient,
     content_addressable_storage_client::ContentAddressableStorageClient, digest_function,
+    execution_client::ExecutionClient, execution_stage,
 };
 use bazel_remote_apis::google::bytestream::{
     ReadRequest, WriteRequest, byte_stream_client::ByteStreamClient,
 };
+use bazel_remote_apis::google::longrunning::operation;
 use http::header::HeaderMap;
 use moon_common::color;
 use moon_config::RemoteConfig;
 use moon_env_var::GlobalEnvBag;
+use prost::Message;
 use std::path::Path;
 use std::sync::{Arc, OnceLock};
 use std::time::Duration;
@@ -43,6 +47,7 @@ pub struct GrpcRemoteClient {
     bs_client: OnceLock<ByteStreamClient<LayeredService>>,
     cap_client: OnceLock<CapabilitiesClient<LayeredService>>,
     cas_client: OnceLock<ContentAddressableStorageClient<LayeredService>>,
+    ex_client: OnceLock<ExecutionClient<LayeredService>>,
 }
 
 impl GrpcRemoteClient {
@@ -63,7 +68,9 @@ impl GrpcRemoteClient {
 
         let _ = self
             .cas_client
-            .set(ContentAddressableStorageClient::new(service));
+            .set(ContentAddressableStorageClient::new(service.clone()));
+
+        let _ = self.ex_client.set(ExecutionClient::new(service));
     }
 
     fn get_ac_client(&self) -> ActionCacheClient<LayeredService> {
@@ -82,6 +89,10 @@ impl GrpcRemoteClient {
         self.cas_client.get().unwrap().clone()
     }
 
+    fn get_ex_client(&self) -> ExecutionClient<LayeredService> {
+        self.ex_client.get().unwrap().clone()
+    }
+
     fn get_uuid(&self) -> &uuid::Uuid {
         self.uuid.get_or_init(uuid::Uuid::new_v4)
     }
@@ -630,4 +641,100 @@ impl RemoteClient for GrpcRemoteClient {
 
         Ok(blob.digest)
     }
+
+    // https://github.com/bazelbuild/remote-apis/blob/main/build/bazel/remote/execution/v2/remote_execution.proto#L122
+    async fn execute_action(
+        &self,
+        action_digest: &Digest,
+        skip_cache_lookup: bool,
+    ) -> miette::Result<ActionResult> {
+        trace!(hash = &action_digest.hash, "Executing action remotely");
+
+        let mut stream = self
+            .get_ex_client()
+            .execute(ExecuteRequest {
+                instance_name: self.config.cache.instance_name.clone(),
+                skip_cache_lookup,
+                action_digest: Some(action_digest.to_owned()),
+                digest_function: digest_function::Value::Sha256 as i32,
+                ..Default::default()
+            })
+            .await
+            .map_err(|error| self.map_status_error("execute_action", error))?
+            .into_inner();
+
+        // The stream emits an operation every time the execution stage changes,
+        // with the last operation being marked as done and containing the result
+        while let Some(operation) = stream
+            .message()
+            .await
+            .map_err(|error| self.map_status_error("execute_action", error))?
+        {
+            if let Some(metadata) = operation
+                .metadata
+                .as_ref()
+                .and_then(|any| ExecuteOperationMetadata::decode(any.value.clone()).ok())
+            {
+                trace!(
+                    hash = &action_digest.hash,
+                    stage = execution_stage::Value::try_from(metadata.stage)
+                        .map(|stage| stage.as_str_name())
+                        .unwrap_or("UNKNOWN"),
+                    "Remote execution stage changed"
+                );
+            }
+
+            if !operation.done {
+                continue;
+            }
+
+            let response = match operation.result {
+                Some(operation::Result::Response(any)) => ExecuteResponse::decode(any.value)
+                    .map_err(|error| RemoteError::ExecuteInvalidResponse {
+                        error: Box::new(error),
+                    })?,
+                Some(operation::Result::Error(status)) => {
+                    return Err(self
+                        .map_status_error(
+                            "execute_action",
+                            tonic::Status::new(Code::from(status.code), status.message),
+                        )
+                        .into());
+                }
+                None => break,
+            };
+
+            if let Some(status) = response.status {
+                if status.code != Code::Ok as i32 {
+                    return Err(self
+                        .map_status_error(
+                            "execute_action",
+                            tonic::Status::new(Code::from(status.code), status.message),
+                        )
+                        .into());
+                }
+            }
+
+            if let Some(result) = response.result {
+                trace!(
+                    hash = &action_digest.hash,
+                    files = result.output_files.len(),
+                    links = result.output_symlinks.len(),
+                    dirs = result.output_directories.len(),
+                    exit_code = result.exit_code,
+                    cached = response.cached_result,
+                    "Executed action remotely"
+                );
+
+                return Ok(result);
+            }
+
+            break;
+        }
+
+        Err(RemoteError::ExecuteMissingResult {
+            hash: action_digest.hash.clone(),
+        }
+        .into())
+    }
 }
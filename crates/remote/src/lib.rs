@@ -11,8 +11,12 @@ mod remote_error;
 mod remote_service;
 
 pub use action_state::*;
-pub use bazel_remote_apis::build::bazel::remote::execution::v2::Digest;
+pub use bazel_remote_apis::build::bazel::remote::execution::v2::{Digest, OutputSymlink};
+pub use blob::*;
 pub use fs_digest::*;
+pub use grpc_remote_client::*;
+pub use http_remote_client::*;
+pub use remote_client::*;
 pub use remote_error::*;
 pub use remote_service::*;
 
@@ -1,4 +1,5 @@
 use crate::blob::Blob;
+use crate::remote_error::RemoteError;
 use bazel_remote_apis::build::bazel::remote::execution::v2::{
     ActionResult, Digest, ServerCapabilities,
 };
@@ -105,4 +106,12 @@ pub trait RemoteClient: Send + Sync {
 
         Ok(result.remove(0).unwrap())
     }
+
+    async fn execute_action(
+        &self,
+        _action_digest: &Digest,
+        _skip_cache_lookup: bool,
+    ) -> miette::Result<ActionResult> {
+        Err(RemoteError::ExecuteNotSupported.into())
+    }
 }
@@ -51,6 +51,23 @@ pub enum RemoteError {
         error: Box<std::io::Error>,
     },
 
+    #[diagnostic(code(remote::execute::invalid_response))]
+    #[error("Failed to decode the remote execution response.")]
+    ExecuteInvalidResponse {
+        #[source]
+        error: Box<prost::DecodeError>,
+    },
+
+    #[diagnostic(code(remote::execute::missing_result))]
+    #[error("Remote execution of action {hash} completed without an action result.")]
+    ExecuteMissingResult { hash: String },
+
+    #[diagnostic(code(remote::execute::no_support))]
+    #[error(
+        "Remote execution is not supported by the HTTP based remote service, use gRPC instead."
+    )]
+    ExecuteNotSupported,
+
     #[diagnostic(code(remote::http::call_failed))]
     #[error("Failed to make HTTP call.")]
     HttpCallFailed {
@@ -8,6 +8,7 @@ use bazel_remote_apis::build::bazel::remote::execution::v2::{
     ActionResult, Digest, ServerCapabilities, digest_function,
 };
 use miette::IntoDiagnostic;
+use moon_common::path::WorkspaceRelativePathBuf;
 use moon_common::{color, is_ci};
 use moon_config::{RemoteApi, RemoteCompression, RemoteConfig};
 use rustc_hash::FxHashMap;
@@ -26,6 +27,7 @@ pub struct RemoteService {
     pub workspace_root: PathBuf,
 
     cache_enabled: bool,
+    execution_enabled: bool,
     capabilities: ServerCapabilities,
     client: Arc<Box<dyn RemoteClient>>,
     upload_requests: Arc<RwLock<Vec<JoinHandle<()>>>>,
@@ -40,6 +42,12 @@ impl RemoteService {
         INSTANCE.get().is_some_and(|remote| remote.cache_enabled)
     }
 
+    pub fn is_execution_enabled() -> bool {
+        INSTANCE
+            .get()
+            .is_some_and(|remote| remote.cache_enabled && remote.execution_enabled)
+    }
+
     #[instrument]
     pub async fn connect(config: &RemoteConfig, workspace_root: &Path) -> miette::Result<()> {
         if is_ci() && config.is_localhost() {
@@ -64,6 +72,7 @@ impl RemoteService {
 
         let mut instance = Self {
             cache_enabled: client.connect_to_host(config, workspace_root).await?,
+            execution_enabled: false,
             capabilities: ServerCapabilities::default(),
             client: Arc::new(client),
             config: config.to_owned(),
@@ -150,11 +159,54 @@ impl RemoteService {
 
         self.cache_enabled = enabled;
 
+        if enabled && self.config.execute {
+            self.validate_execution_capabilities();
+        }
+
         // TODO check low_api_version/high_api_version
 
         Ok(())
     }
 
+    fn validate_execution_capabilities(&mut self) {
+        let host = &self.config.host;
+
+        if self.config.api != RemoteApi::Grpc {
+            warn!(
+                host,
+                "Remote execution is only supported by the gRPC API, but it has been enabled through the {} setting; tasks will run locally",
+                color::property("unstable_remote.execute"),
+            );
+
+            return;
+        }
+
+        let Some(cap) = &self.capabilities.execution_capabilities else {
+            warn!(
+                host,
+                "Remote service does not support execution, tasks will run locally"
+            );
+
+            return;
+        };
+
+        let sha256_fn = digest_function::Value::Sha256 as i32;
+
+        if !cap.exec_enabled {
+            warn!(
+                host,
+                "Remote service has disabled execution, tasks will run locally"
+            );
+        } else if cap.digest_function != sha256_fn && !cap.digest_functions.contains(&sha256_fn) {
+            warn!(
+                host,
+                "Remote service does not support SHA256 digests for execution, which is required by moon; tasks will run locally"
+            );
+        } else {
+            self.execution_enabled = true;
+        }
+    }
+
     pub fn get_max_batch_size(&self) -> i64 {
         let max = self
             .capabilities
@@ -362,6 +414,57 @@ impl RemoteService {
         Ok(true)
     }
 
+    #[instrument(skip(self, state))]
+    pub async fn execute_action(&self, state: &mut ActionState<'_>) -> miette::Result<bool> {
+        if !self.cache_enabled || !self.execution_enabled {
+            return Ok(false);
+        }
+
+        let Some(action_digest) = state.action_digest.clone() else {
+            return Ok(false);
+        };
+
+        // Upload the action, command, and input root, so that the
+        // remote workers have everything they need to execute
+        let blobs = state.input_blobs.drain(0..).collect::<Vec<_>>();
+
+        if !batch_upload_blobs(
+            self.client.clone(),
+            action_digest.clone(),
+            blobs,
+            self.get_max_batch_size() as usize,
+        )
+        .await?
+        {
+            return Ok(false);
+        }
+
+        let mut result = self.client.execute_action(&action_digest, false).await?;
+
+        // Output paths are relative to the working directory of the command,
+        // but we restore them relative to the workspace root
+        if let Some(command) = &state.command {
+            let working_dir = WorkspaceRelativePathBuf::from(&command.working_directory);
+
+            for file in &mut result.output_files {
+                file.path = working_dir.join_normalized(&file.path).to_string();
+            }
+
+            for link in &mut result.output_symlinks {
+                link.path = working_dir.join_normalized(&link.path).to_string();
+            }
+
+            for dir in &mut result.output_directories {
+                dir.path = working_dir.join_normalized(&dir.path).to_string();
+            }
+        }
+
+        state.set_action_result(result);
+
+        // Download the outputs and logs into the workspace
+        self.restore_action_result(state).await
+    }
+
     #[instrument(skip(self))]
     pub async fn wait_for_requests(&self) {
         let mut requests = self.upload_requests.write().await;
@@ -360,6 +360,15 @@ impl<'proj> TasksBuilder<'proj> {
                 task.env = self.merge_map(task.env, env.to_owned(), task.options.merge_env, index);
             }
 
+            if let Some(secrets) = &config.secrets {
+                task.secrets = self.merge_map(
+                    task.secrets,
+                    secrets.to_owned(),
+                    task.options.merge_env,
+                    index,
+                );
+            }
+
             // Inherit global inputs as normal inputs, but do not consider them a configured input
             if !config.global_inputs.is_empty() {
                 global_inputs.extend(config.global_inputs.to_owned());
@@ -663,6 +672,10 @@ impl<'proj> TasksBuilder<'proj> {
                 options.priority = *priority;
             }
 
+            if let Some(resources) = &config.resources {
+                options.resources = Some(resources.to_owned());
+            }
+
             if let Some(retry_count) = &config.retry_count {
                 options.retry_count = *retry_count;
             }
@@ -679,10 +692,18 @@ impl<'proj> TasksBuilder<'proj> {
                 options.run_from_workspace_root = *run_from_workspace_root;
             }
 
+            if let Some(sandbox) = &config.sandbox {
+                options.sandbox = Some(sandbox.to_owned());
+            }
+
             if let Some(shell) = &config.shell {
                 options.shell = Some(*shell);
             }
 
+            if let Some(test_report) = &config.test_report {
+                options.test_report = Some(test_report.to_owned());
+            }
+
             if let Some(timeout) = &config.timeout {
                 options.timeout = Some(*timeout);
             }
@@ -201,8 +201,12 @@ impl<'graph> TaskExpander<'graph> {
             }
         }
 
+        // Secrets are resolved when the task is ran, so keep their references
+        let secrets = task.get_secret_env_names();
+
         task.env = EnvSubstitutor::new()
             .with_local_vars(&env)
+            .with_secret_vars(&secrets)
             .substitute_all(&env);
 
         Ok(())
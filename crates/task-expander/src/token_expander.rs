@@ -663,8 +663,11 @@ impl<'graph> TokenExpander<'graph> {
         task: &Task,
         value: T,
     ) -> miette::Result<String> {
+        let secrets = task.get_secret_env_names();
+
         Ok(EnvSubstitutor::new()
             .with_local_vars(&task.env)
+            .with_secret_vars(&secrets)
             .substitute(self.replace_variables(task, value.as_ref())?))
     }
 
@@ -673,7 +676,10 @@ impl<'graph> TokenExpander<'graph> {
         task: &mut Task,
         value: T,
     ) -> miette::Result<String> {
-        let mut substitutor = EnvSubstitutor::new().with_local_vars(&task.env);
+        let secrets = task.get_secret_env_names();
+        let mut substitutor = EnvSubstitutor::new()
+            .with_local_vars(&task.env)
+            .with_secret_vars(&secrets);
 
         let result = substitutor.substitute(self.replace_variables(task, value.as_ref())?);
 
@@ -201,3 +201,9 @@ impl GraphConversions<Task, DependencyType, Target> for TaskGraph {}
 impl GraphToDot<Task, DependencyType, Target> for TaskGraph {}
 
 impl GraphToJson<Task, DependencyType, Target> for TaskGraph {}
+
+impl GraphToGraphml<Task, DependencyType, Target> for TaskGraph {}
+
+impl GraphToMermaid<Task, DependencyType, Target> for TaskGraph {}
+
+impl GraphToSvg<Task, DependencyType, Target> for TaskGraph {}
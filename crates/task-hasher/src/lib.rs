@@ -1,5 +1,7 @@
 mod task_hash;
+mod task_hash_diff;
 mod task_hasher;
 
 pub use task_hash::*;
+pub use task_hash_diff::*;
 pub use task_hasher::*;
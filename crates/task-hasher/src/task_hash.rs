@@ -40,6 +40,10 @@ hash_content!(
         #[serde(skip_serializing_if = "Option::is_none")]
         pub script: Option<&'task str>,
 
+        // Task `secrets` names, but never their values
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        pub secrets: Vec<&'task str>,
+
         // Task `target`
         pub target: &'task Target,
 
@@ -68,6 +72,11 @@ impl<'task> TaskHash<'task> {
             outputs: task.outputs.iter().collect(),
             project_deps: project.get_dependency_ids(),
             script: task.script.as_deref(),
+            secrets: {
+                let mut names = task.secrets.keys().map(|k| k.as_str()).collect::<Vec<_>>();
+                names.sort();
+                names
+            },
             target: &task.target,
             toolchains: task.toolchains.iter().collect(),
             // 1 - Original implementation
@@ -0,0 +1,246 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TaskHashChangeKind {
+    Args,
+    Command,
+    Dep,
+    Env,
+    Input,
+    InputEnv,
+    Output,
+    ProjectDep,
+    Setting,
+    Toolchain,
+}
+
+/// A single difference between 2 hash manifests of the same task.
+/// A value of `None` means it did not exist in that manifest.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TaskHashChange {
+    pub kind: TaskHashChangeKind,
+    pub key: String,
+    pub previous: Option<String>,
+    pub current: Option<String>,
+}
+
+/// Diff the previous and current hash manifests of a task. The first item in
+/// a manifest is the [`TaskHash`](crate::TaskHash), while the remaining items
+/// are contents hashed by toolchains and plugins (`hash_task_contents`).
+pub fn diff_task_hash_manifests(previous: &Value, current: &Value) -> Vec<TaskHashChange> {
+    let empty = vec![];
+    let previous = previous.as_array().unwrap_or(&empty);
+    let current = current.as_array().unwrap_or(&empty);
+    let mut changes = vec![];
+
+    diff_task_hash(previous.first(), current.first(), &mut changes);
+
+    for index in 1..previous.len().max(current.len()) {
+        let mut previous_fields = BTreeMap::default();
+        let mut current_fields = BTreeMap::default();
+        let prefix = format!("[{index}]");
+
+        if let Some(value) = previous.get(index) {
+            flatten_value(value, &prefix, &mut previous_fields);
+        }
+
+        if let Some(value) = current.get(index) {
+            flatten_value(value, &prefix, &mut current_fields);
+        }
+
+        diff_fields(
+            TaskHashChangeKind::Toolchain,
+            &previous_fields,
+            &current_fields,
+            &mut changes,
+        );
+    }
+
+    changes
+}
+
+fn diff_task_hash(
+    previous: Option<&Value>,
+    current: Option<&Value>,
+    changes: &mut Vec<TaskHashChange>,
+) {
+    let empty = Map::new();
+    let previous = previous.and_then(Value::as_object).unwrap_or(&empty);
+    let current = current.and_then(Value::as_object).unwrap_or(&empty);
+
+    for field in previous
+        .keys()
+        .chain(current.keys())
+        .collect::<BTreeSet<_>>()
+    {
+        let previous_value = previous.get(field);
+        let current_value = current.get(field);
+
+        if previous_value == current_value {
+            continue;
+        }
+
+        match field.as_str() {
+            "deps" => diff_map(
+                TaskHashChangeKind::Dep,
+                previous_value,
+                current_value,
+                changes,
+            ),
+            "env" => diff_map(
+                TaskHashChangeKind::Env,
+                previous_value,
+                current_value,
+                changes,
+            ),
+            "inputs" => diff_map(
+                TaskHashChangeKind::Input,
+                previous_value,
+                current_value,
+                changes,
+            ),
+            "inputEnv" => diff_map(
+                TaskHashChangeKind::InputEnv,
+                previous_value,
+                current_value,
+                changes,
+            ),
+            "outputs" => diff_list(
+                TaskHashChangeKind::Output,
+                previous_value,
+                current_value,
+                changes,
+            ),
+            "projectDeps" => diff_list(
+                TaskHashChangeKind::ProjectDep,
+                previous_value,
+                current_value,
+                changes,
+            ),
+            "toolchains" => diff_list(
+                TaskHashChangeKind::Toolchain,
+                previous_value,
+                current_value,
+                changes,
+            ),
+            _ => changes.push(TaskHashChange {
+                kind: match field.as_str() {
+                    "args" => TaskHashChangeKind::Args,
+                    "command" | "script" => TaskHashChangeKind::Command,
+                    _ => TaskHashChangeKind::Setting,
+                },
+                key: field.to_owned(),
+                previous: previous_value.map(format_value),
+                current: current_value.map(format_value),
+            }),
+        };
+    }
+}
+
+// Maps are keyed by a path, variable, or target, and compared by their value
+fn diff_map(
+    kind: TaskHashChangeKind,
+    previous: Option<&Value>,
+    current: Option<&Value>,
+    changes: &mut Vec<TaskHashChange>,
+) {
+    let to_fields = |value: Option<&Value>| {
+        value
+            .and_then(Value::as_object)
+            .map(|map| {
+                map.iter()
+                    .map(|(key, value)| (key.to_owned(), format_value(value)))
+                    .collect::<BTreeMap<_, _>>()
+            })
+            .unwrap_or_default()
+    };
+
+    diff_fields(kind, &to_fields(previous), &to_fields(current), changes);
+}
+
+// Lists are compared by their items, as the order is deterministic
+fn diff_list(
+    kind: TaskHashChangeKind,
+    previous: Option<&Value>,
+    current: Option<&Value>,
+    changes: &mut Vec<TaskHashChange>,
+) {
+    let to_items = |value: Option<&Value>| {
+        value
+            .and_then(Value::as_array)
+            .map(|list| list.iter().map(format_value).collect::<BTreeSet<_>>())
+            .unwrap_or_default()
+    };
+
+    let previous = to_items(previous);
+    let current = to_items(current);
+
+    for item in previous.union(&current) {
+        let in_previous = previous.contains(item);
+        let in_current = current.contains(item);
+
+        if in_previous != in_current {
+            changes.push(TaskHashChange {
+                kind,
+                key: item.to_owned(),
+                previous: in_previous.then(|| item.to_owned()),
+                current: in_current.then(|| item.to_owned()),
+            });
+        }
+    }
+}
+
+fn diff_fields(
+    kind: TaskHashChangeKind,
+    previous: &BTreeMap<String, String>,
+    current: &BTreeMap<String, String>,
+    changes: &mut Vec<TaskHashChange>,
+) {
+    for key in previous
+        .keys()
+        .chain(current.keys())
+        .collect::<BTreeSet<_>>()
+    {
+        let previous_value = previous.get(key);
+        let current_value = current.get(key);
+
+        if previous_value != current_value {
+            changes.push(TaskHashChange {
+                kind,
+                key: key.to_owned(),
+                previous: previous_value.cloned(),
+                current: current_value.cloned(),
+            });
+        }
+    }
+}
+
+// Flatten nested contents into paths, for example, `[1].nodeVersion`
+fn flatten_value(value: &Value, path: &str, fields: &mut BTreeMap<String, String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, inner) in map {
+                flatten_value(inner, &format!("{path}.{key}"), fields);
+            }
+        }
+        Value::Array(list) => {
+            for (index, inner) in list.iter().enumerate() {
+                flatten_value(inner, &format!("{path}[{index}]"), fields);
+            }
+        }
+        _ => {
+            fields.insert(path.to_owned(), format_value(value));
+        }
+    };
+}
+
+fn format_value(value: &Value) -> String {
+    match value {
+        Value::String(string) => string.to_owned(),
+        Value::Array(list) => list.iter().map(format_value).collect::<Vec<_>>().join(" "),
+        _ => value.to_string(),
+    }
+}
@@ -82,8 +82,14 @@ impl<'task> TaskHasher<'task> {
 
         if !self.task.input_env.is_empty() {
             let bag = GlobalEnvBag::instance();
+            let secrets = self.task.get_secret_env_names();
 
             for input in &self.task.input_env {
+                // Secret values must never be hashed
+                if secrets.contains(input) {
+                    continue;
+                }
+
                 self.content
                     .input_env
                     .insert(input, bag.get(input).unwrap_or_default());
@@ -0,0 +1,167 @@
+use moon_task_hasher::*;
+use serde_json::json;
+
+fn change(
+    kind: TaskHashChangeKind,
+    key: &str,
+    previous: Option<&str>,
+    current: Option<&str>,
+) -> TaskHashChange {
+    TaskHashChange {
+        kind,
+        key: key.into(),
+        previous: previous.map(String::from),
+        current: current.map(String::from),
+    }
+}
+
+mod diff_task_hash_manifests {
+    use super::*;
+
+    #[test]
+    fn returns_nothing_if_equal() {
+        let manifest = json!([{
+            "command": "vite",
+            "args": ["build"],
+            "inputs": { "src/index.ts": "abc" },
+        }]);
+
+        assert!(diff_task_hash_manifests(&manifest, &manifest).is_empty());
+    }
+
+    #[test]
+    fn diffs_inputs() {
+        let changes = diff_task_hash_manifests(
+            &json!([{
+                "inputs": { "src/a.ts": "aaa", "src/b.ts": "bbb", "src/c.ts": "ccc" },
+            }]),
+            &json!([{
+                "inputs": { "src/a.ts": "aaa", "src/b.ts": "xyz", "src/d.ts": "ddd" },
+            }]),
+        );
+
+        assert_eq!(
+            changes,
+            [
+                change(
+                    TaskHashChangeKind::Input,
+                    "src/b.ts",
+                    Some("bbb"),
+                    Some("xyz")
+                ),
+                change(TaskHashChangeKind::Input, "src/c.ts", Some("ccc"), None),
+                change(TaskHashChangeKind::Input, "src/d.ts", None, Some("ddd")),
+            ]
+        );
+    }
+
+    #[test]
+    fn diffs_env_and_deps() {
+        let changes = diff_task_hash_manifests(
+            &json!([{
+                "deps": { "lib:build": "aaa" },
+                "env": { "NODE_ENV": "development" },
+                "inputEnv": { "API_URL": "" },
+            }]),
+            &json!([{
+                "deps": { "lib:build": "bbb" },
+                "env": { "NODE_ENV": "production" },
+                "inputEnv": { "API_URL": "https://moonrepo.dev" },
+            }]),
+        );
+
+        assert_eq!(
+            changes,
+            [
+                change(
+                    TaskHashChangeKind::Dep,
+                    "lib:build",
+                    Some("aaa"),
+                    Some("bbb")
+                ),
+                change(
+                    TaskHashChangeKind::Env,
+                    "NODE_ENV",
+                    Some("development"),
+                    Some("production")
+                ),
+                change(
+                    TaskHashChangeKind::InputEnv,
+                    "API_URL",
+                    Some(""),
+                    Some("https://moonrepo.dev")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn diffs_args_and_command() {
+        let changes = diff_task_hash_manifests(
+            &json!([{ "command": "vite", "args": ["build"] }]),
+            &json!([{ "command": "vite", "args": ["build", "--minify"], "cacheKey": "v2" }]),
+        );
+
+        assert_eq!(
+            changes,
+            [
+                change(
+                    TaskHashChangeKind::Args,
+                    "args",
+                    Some("build"),
+                    Some("build --minify")
+                ),
+                change(TaskHashChangeKind::Setting, "cacheKey", None, Some("v2")),
+            ]
+        );
+    }
+
+    #[test]
+    fn diffs_lists_by_item() {
+        let changes = diff_task_hash_manifests(
+            &json!([{ "outputs": ["dist"], "projectDeps": ["a", "b"] }]),
+            &json!([{ "outputs": ["dist"], "projectDeps": ["b", "c"] }]),
+        );
+
+        assert_eq!(
+            changes,
+            [
+                change(TaskHashChangeKind::ProjectDep, "a", Some("a"), None),
+                change(TaskHashChangeKind::ProjectDep, "c", None, Some("c")),
+            ]
+        );
+    }
+
+    #[test]
+    fn diffs_toolchain_contents() {
+        let changes = diff_task_hash_manifests(
+            &json!([
+                { "command": "node" },
+                { "nodeVersion": "20.0.0", "dependencies": { "react": ["19.0.0"] } },
+            ]),
+            &json!([
+                { "command": "node" },
+                { "nodeVersion": "22.0.0", "dependencies": { "react": ["19.0.0"] } },
+                { "lockfile": "abc" },
+            ]),
+        );
+
+        assert_eq!(
+            changes,
+            [
+                change(
+                    TaskHashChangeKind::Toolchain,
+                    "[1].nodeVersion",
+                    Some("20.0.0"),
+                    Some("22.0.0")
+                ),
+                change(
+                    TaskHashChangeKind::Toolchain,
+                    "[2].lockfile",
+                    None,
+                    Some("abc")
+                ),
+            ]
+        );
+    }
+}
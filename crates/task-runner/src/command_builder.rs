@@ -1,8 +1,10 @@
+use crate::task_secrets::resolve_task_secrets;
 use moon_action::ActionNode;
 use moon_action_context::ActionContext;
 use moon_app_context::AppContext;
 use moon_common::consts::PROTO_CLI_VERSION;
 use moon_config::TaskOptionAffectedFiles;
+use moon_env_var::EnvSubstitutor;
 use moon_platform::PlatformManager;
 use moon_process::{Command, Shell, ShellType};
 use moon_project::Project;
@@ -86,9 +88,14 @@ impl<'task> CommandBuilder<'task> {
             .cwd(self.working_dir)
             .set_error_on_nonzero(false);
 
+        if let Some(group) = &context.process_group {
+            self.command.set_process_group(group);
+        }
+
         // Order is important!
         self.inject_args(context);
         self.inject_env();
+        self.inject_secrets().await?;
         self.inject_shell();
         self.inherit_affected(context)?;
         self.inherit_config();
@@ -174,6 +181,43 @@ impl<'task> CommandBuilder<'task> {
         }
     }
 
+    #[instrument(skip_all)]
+    async fn inject_secrets(&mut self) -> miette::Result<()> {
+        if self.task.secrets.is_empty() {
+            return Ok(());
+        }
+
+        let secrets = resolve_task_secrets(self.app, self.project, self.task).await?;
+
+        trace!(
+            task_target = self.task.target.as_str(),
+            secrets = ?secrets.keys().collect::<Vec<_>>(),
+            "Injecting secrets as environment variables"
+        );
+
+        // Secret references in env vars are not substituted when
+        // the task is expanded, so substitute them now instead
+        for (key, value) in &self.task.env {
+            if !value.contains('$') {
+                continue;
+            }
+
+            let mut substitutor = EnvSubstitutor::default().with_local_vars(&secrets);
+            let value = substitutor.substitute(value);
+
+            if !substitutor.replaced.is_empty() {
+                self.command.env(key, value);
+            }
+        }
+
+        for (key, value) in secrets {
+            self.command.env(&key, &value);
+            self.command.secret(value);
+        }
+
+        Ok(())
+    }
+
     #[instrument(skip_all)]
     fn inject_shell(&mut self) {
         if self.task.options.shell == Some(true) {
@@ -1,8 +1,10 @@
-use moon_action::{ActionNode, ActionStatus, Operation, OperationList};
+use crate::task_runner_error::TaskRunnerError;
+use crate::task_sandbox::TaskSandbox;
+use moon_action::{ActionNode, ActionStatus, Operation, OperationList, OperationMetaSandbox};
 use moon_action_context::{ActionContext, TargetState};
 use moon_app_context::AppContext;
 use moon_common::{is_ci, is_test_env};
-use moon_config::TaskOutputStyle;
+use moon_config::{TaskOptionSandbox, TaskOutputStyle};
 use moon_console::TaskReportItem;
 use moon_process::{Command, CommandLine, args::join_args};
 use moon_project::Project;
@@ -12,7 +14,7 @@ use std::time::Duration;
 use tokio::task::{self, JoinHandle};
 use tokio::time::{sleep, timeout};
 use tokio_util::sync::CancellationToken;
-use tracing::{debug, instrument};
+use tracing::{debug, instrument, warn};
 
 fn is_ci_env() -> bool {
     is_ci() && !is_test_env()
@@ -42,6 +44,7 @@ pub struct CommandExecutor<'task> {
     // States
     interactive: bool,
     persistent: bool,
+    sandbox: Option<TaskOptionSandbox>,
     stream: bool,
 }
 
@@ -61,6 +64,7 @@ impl<'task> CommandExecutor<'task> {
             attempt_total: task.options.retry_count + 1,
             interactive: node.is_interactive() || task.is_interactive(),
             persistent: node.is_persistent() || task.is_persistent(),
+            sandbox: None,
             stream: false,
             handle: None,
             app,
@@ -88,6 +92,14 @@ impl<'task> CommandExecutor<'task> {
         let command_line = self.get_command_line(context);
 
         let execution_error: Option<miette::Report> = loop {
+            // Sandboxed tasks run exclusively, so that file access from other tasks
+            // isn't traced. Persistent tasks never exit, so they're only tracked.
+            let _lock = if self.persistent {
+                TaskSandbox::lock_persistent()
+            } else {
+                TaskSandbox::lock(self.is_sandboxed()).await
+            };
+
             let mut attempt = Operation::task_execution(&command_line);
             report_item.attempt_current = self.attempt_index;
 
@@ -118,6 +130,9 @@ impl<'task> CommandExecutor<'task> {
                 }
             }
 
+            // Trace file access while the command is running
+            let sandbox = self.start_sandbox()?;
+
             let timeout_token = CancellationToken::new();
             let timeout_handle =
                 self.monitor_timeout(self.task.options.timeout, timeout_token.clone());
@@ -144,11 +159,17 @@ impl<'task> CommandExecutor<'task> {
                 handle.abort();
             }
 
+            let sandbox_result = match sandbox {
+                Some(sandbox) => Some(sandbox.stop(self.task)?),
+                None => None,
+            };
+
             // Handle the execution result
             match attempt_result {
                 // Zero and non-zero exit codes
                 Ok(maybe_output) => {
                     let mut is_success = false;
+                    let mut sandbox_error = None;
 
                     if let Some(output) = maybe_output {
                         is_success = output.status.success();
@@ -161,6 +182,10 @@ impl<'task> CommandExecutor<'task> {
                         );
 
                         attempt.finish_from_output(output);
+
+                        if let Some(result) = sandbox_result {
+                            sandbox_error = self.check_sandbox(&mut attempt, result, is_success);
+                        }
                     } else {
                         debug!(
                             task_target = self.task.target.as_str(),
@@ -180,8 +205,33 @@ impl<'task> CommandExecutor<'task> {
 
                     self.attempts.push(attempt);
 
+                    // Persistent task was restarted from the dashboard, so run it again
+                    if self.persistent
+                        && self
+                            .app
+                            .console
+                            .get_dashboard()
+                            .is_some_and(|dashboard| dashboard.is_restarting(&self.task.target))
+                    {
+                        debug!(
+                            task_target = self.task.target.as_str(),
+                            "Task was restarted, running again",
+                        );
+
+                        continue;
+                    }
+
+                    // Successful execution, but accessed undeclared files
+                    if let Some(error) = sandbox_error {
+                        debug!(
+                            task_target = self.task.target.as_str(),
+                            "Task accessed undeclared files, failing as the sandbox is strict",
+                        );
+
+                        break Some(error);
+                    }
                     // Successful execution, so break the loop
-                    if is_success {
+                    else if is_success {
                         debug!(
                             task_target = self.task.target.as_str(),
                             "Task was successful, proceeding to next step",
@@ -244,6 +294,83 @@ impl<'task> CommandExecutor<'task> {
         })
     }
 
+    fn is_sandboxed(&self) -> bool {
+        self.sandbox.is_some() && cfg!(target_os = "linux")
+    }
+
+    fn start_sandbox(&self) -> miette::Result<Option<TaskSandbox>> {
+        if !self.is_sandboxed() {
+            if self.sandbox.is_some() {
+                debug!(
+                    task_target = self.task.target.as_str(),
+                    "Task sandboxing is only supported on Linux, skipping"
+                );
+            }
+
+            return Ok(None);
+        }
+
+        if TaskSandbox::is_persistent_running() {
+            warn!(
+                task_target = self.task.target.as_str(),
+                "Persistent tasks are running, so their file access may be reported for this task"
+            );
+        }
+
+        TaskSandbox::new(
+            self.task,
+            &self.app.workspace_root,
+            &self.project.root,
+            vec![self.app.cache_engine.cache_dir.clone()],
+        )
+        .map(Some)
+    }
+
+    fn check_sandbox(
+        &self,
+        attempt: &mut Operation,
+        result: OperationMetaSandbox,
+        is_success: bool,
+    ) -> Option<miette::Report> {
+        let mut error = None;
+
+        if result.has_violations() {
+            let mut files = vec![];
+
+            for file in &result.undeclared_inputs {
+                files.push(format!("  - {file} (read)"));
+            }
+
+            for file in &result.undeclared_outputs {
+                files.push(format!("  - {file} (written)"));
+            }
+
+            if is_success && self.sandbox.as_ref().is_some_and(|mode| mode.is_error()) {
+                attempt.status = ActionStatus::Failed;
+
+                error = Some(
+                    TaskRunnerError::SandboxViolation {
+                        target: self.task.target.clone(),
+                        files,
+                    }
+                    .into(),
+                );
+            } else {
+                warn!(
+                    task_target = self.task.target.as_str(),
+                    "Task accessed files that were not declared as inputs or outputs:\n{}",
+                    files.join("\n"),
+                );
+            }
+        }
+
+        if let Some(output) = attempt.get_exec_output_mut() {
+            output.sandbox = Some(result);
+        }
+
+        error
+    }
+
     fn monitor_running_status(&mut self) {
         if self.persistent {
             return;
@@ -316,17 +443,57 @@ impl<'task> CommandExecutor<'task> {
             report_item.output_prefix = None;
         }
 
+        // The dashboard owns the terminal, so output is always
+        // streamed into the task's pane instead of the console
+        if let Some(dashboard) = self.app.console.get_dashboard() {
+            self.interactive = false;
+            self.stream = true;
+            self.command
+                .with_pane(dashboard.get_pane(&self.task.target));
+
+            if self.persistent {
+                if let Some(restart) = dashboard.get_restart_signal(&self.task.target) {
+                    self.command.with_interrupt(restart);
+                }
+            }
+
+            report_item.output_prefix = None;
+        }
+
         if let Some(prefix) = &report_item.output_prefix {
             self.command.set_prefix(prefix);
         }
 
+        // Persistent processes never exit, so their file access can't be reported.
+        // The `--sandbox` flag applies to all tasks, regardless of their option.
+        if self.persistent {
+            if self
+                .task
+                .options
+                .sandbox
+                .as_ref()
+                .is_some_and(|mode| mode.is_enabled())
+            {
+                warn!(
+                    task_target = self.task.target.as_str(),
+                    "Persistent tasks can't be sandboxed, skipping"
+                );
+            }
+        } else {
+            self.sandbox = match &self.task.options.sandbox {
+                Some(mode) if mode.is_enabled() => Some(mode.to_owned()),
+                _ if context.sandbox => Some(TaskOptionSandbox::Warn),
+                _ => None,
+            };
+        }
+
         report_item.attempt_current = self.attempt_index;
         report_item.attempt_total = self.attempt_total;
         report_item.output_streamed = self.stream;
     }
 
     fn get_command_line(&self, context: &ActionContext) -> String {
-        if self.task.script.is_some() {
+        let line = if self.task.script.is_some() {
             self.task.get_command_line()
         } else {
             let mut args = vec![&self.task.command];
@@ -337,7 +504,10 @@ impl<'task> CommandExecutor<'task> {
             }
 
             join_args(args)
-        }
+        };
+
+        // Secrets may have been passed through the command line
+        self.command.redact(line)
     }
 
     // We don't use `Command::print_command` because we need to explicitly
@@ -347,6 +517,12 @@ impl<'task> CommandExecutor<'task> {
             return Ok(());
         }
 
+        if let Some(dashboard) = self.app.console.get_dashboard() {
+            dashboard.write_line(&self.task.target, format!("$ {command_line}"), false);
+
+            return Ok(());
+        }
+
         let workspace_root = &self.app.workspace_root;
         let working_dir = if self.task.options.run_from_workspace_root {
             &self.app.workspace_root
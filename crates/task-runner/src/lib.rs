@@ -6,6 +6,9 @@ pub mod output_hydrater;
 mod run_state;
 mod task_runner;
 mod task_runner_error;
+pub mod task_sandbox;
+pub mod task_secrets;
+pub mod test_report;
 
 pub use run_state::*;
 pub use task_runner::*;
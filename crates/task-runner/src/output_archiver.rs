@@ -1,17 +1,17 @@
 use crate::task_runner_error::TaskRunnerError;
 use moon_app_context::AppContext;
+use moon_cache::{OutputManifest, OutputManifestFile, OutputManifestSymlink};
 use moon_common::color;
 use moon_project::Project;
-use moon_remote::{ActionState, RemoteService};
+use moon_remote::{ActionState, OutputDigests, RemoteService, create_digest};
 use moon_task::Task;
-use starbase_archive::Archiver;
-use starbase_archive::tar::TarPacker;
-use std::path::{Path, PathBuf};
+use starbase_utils::fs;
+use std::path::PathBuf;
 use tracing::{debug, instrument, warn};
 
-/// Cache outputs to the `.moon/cache/outputs` folder and to the cloud,
-/// so that subsequent builds are faster, and any local outputs
-/// can be hydrated easily.
+/// Cache outputs to the `.moon/cache/blobs` folder (with a manifest in
+/// `.moon/cache/outputs`) and to the cloud, so that subsequent builds
+/// are faster, and any local outputs can be hydrated easily.
 pub struct OutputArchiver<'task> {
     pub app: &'task AppContext,
     pub project: &'task Project,
@@ -26,7 +26,7 @@ impl OutputArchiver<'_> {
         remote_state: Option<&mut ActionState<'_>>,
     ) -> miette::Result<Option<PathBuf>> {
         let mut archived = false;
-        let archive_file = self.app.cache_engine.hash.get_archive_path(hash);
+        let manifest_file = self.app.cache_engine.hash.get_outputs_manifest_path(hash);
 
         // Check that outputs actually exist
         if self.task.is_build_type() && !self.has_outputs_been_created(false)? {
@@ -36,8 +36,8 @@ impl OutputArchiver<'_> {
             .into());
         }
 
-        // If so, create and pack the archive!
-        if archive_file.exists() {
+        // If so, store the blobs and create the manifest!
+        if manifest_file.exists() {
             archived = true;
         } else if self.app.cache_engine.is_writable() {
             debug!(
@@ -45,8 +45,17 @@ impl OutputArchiver<'_> {
                 hash, "Archiving task outputs from project"
             );
 
-            self.create_local_archive(hash, &archive_file)?;
-            archived = true;
+            if let Err(error) = self.create_local_manifest(hash) {
+                warn!(
+                    task_target = self.task.target.as_str(),
+                    hash,
+                    manifest_file = ?manifest_file,
+                    "Failed to store outputs in the local cache: {}",
+                    color::muted_light(error.to_string()),
+                );
+            } else {
+                archived = true;
+            }
         } else {
             debug!(
                 task_target = self.task.target.as_str(),
@@ -59,7 +68,7 @@ impl OutputArchiver<'_> {
             archived = self.upload_to_remote_service(state).await?;
         }
 
-        Ok(if archived { Some(archive_file) } else { None })
+        Ok(if archived { Some(manifest_file) } else { None })
     }
 
     #[instrument(skip(self))]
@@ -99,46 +108,71 @@ impl OutputArchiver<'_> {
     }
 
     #[instrument(skip(self))]
-    fn create_local_archive(&self, hash: &str, archive_file: &Path) -> miette::Result<()> {
+    fn create_local_manifest(&self, hash: &str) -> miette::Result<()> {
+        let hash_engine = &self.app.cache_engine.hash;
+        let workspace_root = &self.app.workspace_root;
+        let mut manifest = OutputManifest::default();
+        let mut outputs = OutputDigests::default();
+
         debug!(
             task_target = self.task.target.as_str(),
-            hash,
-            archive_file = ?archive_file, "Creating archive file"
+            hash, "Creating outputs manifest"
         );
 
-        // Create the archiver instance based on task outputs
-        let mut archive = Archiver::new(&self.app.workspace_root, archive_file);
+        for path in self.task.get_output_files(workspace_root, true)? {
+            outputs.insert_relative_path(path, workspace_root)?;
+        }
+
+        // Blobs are keyed by digest, so identical files
+        // across hashes are only stored once
+        for blob in outputs.blobs {
+            hash_engine.save_blob(&blob.digest.hash, &blob.bytes)?;
+        }
+
+        for file in outputs.files {
+            let digest = file.digest.unwrap_or_default();
 
-        for output_file in &self.task.output_files {
-            archive.add_source_file(output_file.as_str(), None);
+            manifest.files.push(OutputManifestFile {
+                executable: file.is_executable,
+                hash: digest.hash,
+                path: file.path.into(),
+                size: digest.size_bytes as u64,
+            });
         }
 
-        for output_glob in &self.task.output_globs {
-            archive.add_source_glob(output_glob.as_str());
+        for link in outputs.symlinks {
+            manifest.symlinks.push(OutputManifestSymlink {
+                path: link.path.into(),
+                target: link.target.into(),
+            });
         }
 
-        // Also include stdout/stderr logs in the tarball
+        // Also include stdout/stderr logs as blobs
         let state_dir = self
             .app
             .cache_engine
             .state
             .get_target_dir(&self.task.target);
 
-        archive.add_source_file(state_dir.join("stdout.log"), None);
+        for (log_name, log_hash) in [
+            ("stderr.log", &mut manifest.stderr_hash),
+            ("stdout.log", &mut manifest.stdout_hash),
+        ] {
+            let log_path = state_dir.join(log_name);
 
-        archive.add_source_file(state_dir.join("stderr.log"), None);
+            if log_path.exists() {
+                let bytes = fs::read_file_bytes(&log_path)?;
+                let digest = create_digest(&bytes);
 
-        // Pack the archive
-        if let Err(error) = archive.pack(TarPacker::new_gz) {
-            warn!(
-                task_target = self.task.target.as_str(),
-                hash,
-                archive_file = ?archive_file,
-                "Failed to package outputs into archive: {}",
-                color::muted_light(error.to_string()),
-            );
+                hash_engine.save_blob(&digest.hash, &bytes)?;
+
+                *log_hash = Some(digest.hash);
+            }
         }
 
+        // Save the manifest last, as it marks the outputs as cached
+        hash_engine.save_outputs_manifest(hash, &manifest)?;
+
         Ok(())
     }
 
@@ -1,9 +1,8 @@
 use moon_app_context::AppContext;
+use moon_cache::{OutputManifest, OutputManifestFile};
 use moon_common::color;
-use moon_remote::{ActionState, RemoteService};
+use moon_remote::{ActionState, OutputSymlink, RemoteService, create_digest, link_output_file};
 use moon_task::Task;
-use starbase_archive::Archiver;
-use starbase_archive::tar::TarUnpacker;
 use starbase_utils::fs;
 use std::path::Path;
 use tracing::{debug, instrument, warn};
@@ -44,7 +43,6 @@ impl OutputHydrater<'_> {
 
             // Otherwise write to local cache
             _ => {
-                let archive_file = self.app.cache_engine.hash.get_archive_path(hash);
                 let mut hydrated = false;
 
                 if self.app.cache_engine.is_readable() {
@@ -53,10 +51,11 @@ impl OutputHydrater<'_> {
                         hash, "Hydrating cached outputs into project"
                     );
 
-                    // Otherwise hydrate the cached archive into the task's outputs
-                    if archive_file.exists() {
-                        self.unpack_local_archive(hash, &archive_file)?;
-                        hydrated = true
+                    // Otherwise hydrate the cached blobs into the task's outputs
+                    if let Some(manifest) =
+                        self.app.cache_engine.hash.load_outputs_manifest(hash)?
+                    {
+                        hydrated = self.hydrate_local_outputs(hash, &manifest)?;
                     }
                 } else {
                     debug!(
@@ -70,32 +69,35 @@ impl OutputHydrater<'_> {
         }
     }
 
-    #[instrument(skip(self))]
-    fn unpack_local_archive(&self, hash: &str, archive_file: &Path) -> miette::Result<bool> {
+    #[instrument(skip(self, manifest))]
+    fn hydrate_local_outputs(&self, hash: &str, manifest: &OutputManifest) -> miette::Result<bool> {
+        let hash_engine = &self.app.cache_engine.hash;
+
+        // Ensure all blobs exist before touching the project,
+        // otherwise we may hydrate a partial set of outputs
+        for blob_hash in manifest.get_blob_hashes() {
+            if !hash_engine.get_blob_path(blob_hash).exists() {
+                warn!(
+                    task_target = self.task.target.as_str(),
+                    hash,
+                    blob = blob_hash,
+                    "Failed to hydrate outputs, a blob is missing from the local cache",
+                );
+
+                return Ok(false);
+            }
+        }
+
         debug!(
             task_target = self.task.target.as_str(),
-            hash,
-            archive_file = ?archive_file, "Unpacking archive into project"
+            hash, "Copying changed outputs from local cache into project"
         );
 
-        // Create the archiver instance based on task outputs
-        let mut archive = Archiver::new(&self.app.workspace_root, archive_file);
-
-        for output_file in &self.task.output_files {
-            archive.add_source_file(output_file.as_str(), None);
-        }
-
-        for output_glob in &self.task.output_globs {
-            archive.add_source_glob(output_glob.as_str());
-        }
-
-        // Unpack the archive
-        if let Err(error) = archive.unpack(TarUnpacker::new_gz) {
+        if let Err(error) = self.write_outputs(manifest) {
             warn!(
                 task_target = self.task.target.as_str(),
                 hash,
-                archive_file = ?archive_file,
-                "Failed to hydrate outputs from archive: {}",
+                "Failed to hydrate outputs from local cache: {}",
                 color::muted_light(error.to_string()),
             );
 
@@ -103,11 +105,90 @@ impl OutputHydrater<'_> {
             for output in &self.task.output_files {
                 fs::remove_file(output.to_logical_path(&self.app.workspace_root))?;
             }
+
+            return Ok(false);
         }
 
         Ok(true)
     }
 
+    fn write_outputs(&self, manifest: &OutputManifest) -> miette::Result<()> {
+        let hash_engine = &self.app.cache_engine.hash;
+        let workspace_root = &self.app.workspace_root;
+        let mut copied = 0;
+
+        // Only copy files that have changed, so that unchanged
+        // outputs (and their timestamps) are left untouched
+        for file in &manifest.files {
+            let output_path = file.path.to_logical_path(workspace_root);
+
+            if is_output_unchanged(&output_path, file) {
+                continue;
+            }
+
+            // Avoid writing through an existing symlink
+            if output_path.is_symlink() {
+                remove_symlink(&output_path)?;
+            }
+
+            fs::copy_file(hash_engine.get_blob_path(&file.hash), &output_path)?;
+
+            if file.executable {
+                fs::update_perms(&output_path, Some(0o755))?;
+            }
+
+            copied += 1;
+        }
+
+        // Create symlinks after files have been copied,
+        // as the link target may reference one of these outputs
+        for link in &manifest.symlinks {
+            let link_path = link.path.to_logical_path(workspace_root);
+
+            if link_path.is_symlink() {
+                remove_symlink(&link_path)?;
+            }
+
+            link_output_file(
+                link.target.to_logical_path(workspace_root),
+                link_path,
+                &OutputSymlink {
+                    path: link.path.to_string(),
+                    target: link.target.to_string(),
+                    node_properties: None,
+                },
+            )?;
+        }
+
+        // Restore the logs so that they can be replayed
+        let state_dir = self
+            .app
+            .cache_engine
+            .state
+            .get_target_dir(&self.task.target);
+
+        for (log_name, log_hash) in [
+            ("stderr.log", &manifest.stderr_hash),
+            ("stdout.log", &manifest.stdout_hash),
+        ] {
+            let log_path = state_dir.join(log_name);
+
+            match log_hash {
+                Some(log_hash) => fs::copy_file(hash_engine.get_blob_path(log_hash), log_path)?,
+                None => fs::remove_file(log_path)?,
+            };
+        }
+
+        debug!(
+            task_target = self.task.target.as_str(),
+            copied,
+            unchanged = manifest.files.len() - copied,
+            "Hydrated outputs from local cache"
+        );
+
+        Ok(())
+    }
+
     #[instrument(skip(self, state))]
     async fn download_from_remote_service(
         &self,
@@ -133,3 +214,25 @@ impl OutputHydrater<'_> {
         Ok(false)
     }
 }
+
+fn is_output_unchanged(path: &Path, file: &OutputManifestFile) -> bool {
+    if path.is_symlink() {
+        return false;
+    }
+
+    match std::fs::metadata(path) {
+        Ok(meta) if meta.is_file() && meta.len() == file.size => {
+            std::fs::read(path).is_ok_and(|bytes| create_digest(&bytes).hash == file.hash)
+        }
+        _ => false,
+    }
+}
+
+fn remove_symlink(path: &Path) -> miette::Result<()> {
+    std::fs::remove_file(path).map_err(|error| fs::FsError::Remove {
+        path: path.to_path_buf(),
+        error: Box::new(error),
+    })?;
+
+    Ok(())
+}
@@ -5,6 +5,17 @@ cache_item!(
         pub exit_code: i32,
         pub hash: String,
         pub last_run_time: u128,
+        pub last_success_hash: String,
+        pub sandbox_violation: bool,
         pub target: String,
     }
 );
+
+impl TaskRunCacheState {
+    /// Whether the last run succeeded, and its outputs can be reused.
+    /// The process may have exited successfully while the task itself
+    /// failed, because it accessed files outside of the sandbox.
+    pub fn is_successful(&self) -> bool {
+        self.exit_code == 0 && !self.sandbox_violation
+    }
+}
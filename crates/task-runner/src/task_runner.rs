@@ -4,14 +4,18 @@ use crate::output_archiver::OutputArchiver;
 use crate::output_hydrater::{HydrateFrom, OutputHydrater};
 use crate::run_state::*;
 use crate::task_runner_error::TaskRunnerError;
-use moon_action::{ActionNode, ActionStatus, Operation, OperationList, OperationMeta};
+use crate::test_report::load_test_report;
+use moon_action::{ActionNode, ActionStatus, Operation, OperationList, OperationMeta, TestReport};
 use moon_action_context::{ActionContext, TargetState};
 use moon_app_context::AppContext;
 use moon_cache::CacheItem;
+use moon_common::color;
+use moon_common::path::WorkspaceRelativePathBuf;
 use moon_console::TaskReportItem;
+use moon_env_var::GlobalEnvBag;
 use moon_pdk_api::HashTaskContentsInput;
 use moon_platform::PlatformManager;
-use moon_process::ProcessError;
+use moon_process::{Command, CommandLine, ProcessError, redact_secrets};
 use moon_project::Project;
 use moon_remote::{ActionState, Digest, RemoteService};
 use moon_task::Task;
@@ -19,14 +23,17 @@ use moon_task_hasher::TaskHasher;
 use moon_time::{is_stale, now_millis};
 use starbase_utils::fs;
 use std::collections::BTreeMap;
+use std::env;
+use std::sync::Arc;
 use std::time::SystemTime;
-use tracing::{debug, instrument, trace};
+use tracing::{debug, instrument, trace, warn};
 
 #[derive(Debug)]
 pub struct TaskRunResult {
     pub hash: Option<String>,
     pub error: Option<miette::Report>,
     pub operations: OperationList,
+    pub test_report: Option<TestReport>,
 }
 
 pub struct TaskRunner<'task> {
@@ -38,12 +45,21 @@ pub struct TaskRunner<'task> {
     archiver: OutputArchiver<'task>,
     hydrater: OutputHydrater<'task>,
 
+    // Tasks that this task depends on, whose outputs are required for remote execution
+    dependencies: Vec<Arc<Task>>,
+
     // Public for testing
     pub cache: CacheItem<TaskRunCacheState>,
     pub operations: OperationList,
     pub remote_state: Option<ActionState<'task>>,
     pub report_item: TaskReportItem,
     pub target_state: Option<TargetState>,
+
+    // When the command started executing, so that stale test reports are ignored
+    executed_at: Option<SystemTime>,
+
+    // Resolved secret values, so that they can be redacted from reports
+    secrets: Vec<String>,
 }
 
 impl<'task> TaskRunner<'task> {
@@ -69,6 +85,8 @@ impl<'task> TaskRunner<'task> {
         Ok(Self {
             cache,
             archiver: OutputArchiver { app, project, task },
+            dependencies: vec![],
+            executed_at: None,
             hydrater: OutputHydrater { app, task },
             platform_manager: PlatformManager::read(),
             project,
@@ -77,6 +95,7 @@ impl<'task> TaskRunner<'task> {
                 output_style: task.options.output_style,
                 ..Default::default()
             },
+            secrets: vec![],
             target_state: None,
             task,
             app,
@@ -84,6 +103,10 @@ impl<'task> TaskRunner<'task> {
         })
     }
 
+    pub fn set_dependencies(&mut self, dependencies: Vec<Arc<Task>>) {
+        self.dependencies = dependencies;
+    }
+
     pub fn set_platform_manager(&mut self, manager: &'task PlatformManager) {
         self.platform_manager = manager;
     }
@@ -144,9 +167,29 @@ impl<'task> TaskRunner<'task> {
 
         let result = self.internal_run(context, node).await;
 
+        // Track the last successful hash, so that cache misses can be explained
+        if let Ok(Some(hash)) = &result {
+            if self.cache.data.is_successful() {
+                self.cache.data.last_success_hash = hash.to_owned();
+            }
+        }
+
         self.cache.data.last_run_time = now_millis();
         self.cache.save()?;
 
+        // Parse the test report (if configured), regardless of the result,
+        // as failing tests are the most important to report
+        let test_report = self.load_test_report().unwrap_or_else(|error| {
+            warn!(
+                task_target = self.task.target.as_str(),
+                "Failed to load test report: {error}"
+            );
+
+            None
+        });
+
+        self.report_item.test_report = test_report.clone();
+
         match result {
             Ok(maybe_hash) => {
                 context.set_target_state(
@@ -167,6 +210,7 @@ impl<'task> TaskRunner<'task> {
                     error: None,
                     hash: maybe_hash,
                     operations: self.operations.take(),
+                    test_report,
                 })
             }
             Err(error) => {
@@ -188,6 +232,7 @@ impl<'task> TaskRunner<'task> {
                     error: Some(error),
                     hash: None,
                     operations: self.operations.take(),
+                    test_report,
                 })
             }
         }
@@ -242,7 +287,7 @@ impl<'task> TaskRunner<'task> {
 
         // If hash is the same as the previous build, we can simply abort!
         // However, ensure the outputs also exist, otherwise we should hydrate
-        if self.cache.data.exit_code == 0
+        if self.cache.data.is_successful()
             && self.cache.data.hash == hash
             && self.archiver.has_outputs_been_created(true)?
         {
@@ -271,7 +316,7 @@ impl<'task> TaskRunner<'task> {
         self.cache.data.hash = hash.to_owned();
 
         // If the previous run was a failure, avoid hydrating
-        if self.cache.data.exit_code > 0 {
+        if self.cache.data.exit_code > 0 || self.cache.data.sandbox_violation {
             debug!(
                 task_target = self.task.target.as_str(),
                 hash, "Previous run failed, avoiding hydration"
@@ -286,18 +331,19 @@ impl<'task> TaskRunner<'task> {
         }
 
         // Check to see if a build with the provided hash has been cached locally.
-        // We only check for the archive, as the manifest is purely for local debugging!
-        let archive_file = cache_engine.hash.get_archive_path(hash);
+        // We only check for the outputs manifest, as the hash manifest is purely
+        // for local debugging! Blobs are verified when hydrating.
+        let manifest_file = cache_engine.hash.get_outputs_manifest_path(hash);
 
-        if archive_file.exists() {
-            // Also check if the archive itself is stale
+        if manifest_file.exists() {
+            // Also check if the manifest itself is stale
             if let Some(duration) = cache_lifetime {
-                if fs::is_stale(&archive_file, false, duration, SystemTime::now())?.is_some() {
+                if fs::is_stale(&manifest_file, false, duration, SystemTime::now())?.is_some() {
                     debug!(
                         task_target = self.task.target.as_str(),
                         hash,
-                        archive_file = ?archive_file,
-                        "Cache skip in local cache, a lifetime has been configured and the archive is stale, continuing run"
+                        manifest_file = ?manifest_file,
+                        "Cache skip in local cache, a lifetime has been configured and the outputs manifest is stale, continuing run"
                     );
 
                     return Ok(None);
@@ -307,8 +353,8 @@ impl<'task> TaskRunner<'task> {
             debug!(
                 task_target = self.task.target.as_str(),
                 hash,
-                archive_file = ?archive_file,
-                "Cache hit in local cache, will reuse existing archive"
+                manifest_file = ?manifest_file,
+                "Cache hit in local cache, will reuse existing outputs"
             );
 
             return Ok(Some(HydrateFrom::LocalCache));
@@ -423,7 +469,10 @@ impl<'task> TaskRunner<'task> {
 
         task_hasher.hash_inputs().await?;
 
-        hasher.hash_content(task_hasher.hash())?;
+        let task_hash = task_hasher.hash();
+        let inputs = task_hash.inputs.keys().cloned().collect::<Vec<_>>();
+
+        hasher.hash_content(task_hash)?;
 
         // Hash toolchain fields
         self.platform_manager
@@ -476,6 +525,7 @@ impl<'task> TaskRunner<'task> {
                 self.task,
             );
             state.bytes = bytes;
+            state.inputs = inputs;
 
             self.remote_state = Some(state);
         }
@@ -502,6 +552,14 @@ impl<'task> TaskRunner<'task> {
             return Ok(());
         }
 
+        self.executed_at = Some(SystemTime::now());
+
+        // If the remote service supports execution, attempt to run the
+        // task there, otherwise fallback to executing it locally
+        if self.should_execute_remotely(node) && self.execute_remotely(context, node).await? {
+            return self.check_last_execution();
+        }
+
         debug!(
             task_target = self.task.target.as_str(),
             "Building and executing the task command"
@@ -513,6 +571,8 @@ impl<'task> TaskRunner<'task> {
 
         let command = builder.build(context).await?;
 
+        self.secrets = command.secrets.clone();
+
         // Execute the command and gather all attempts made
         let executor = CommandExecutor::new(self.app, self.project, self.task, node, command);
 
@@ -567,7 +627,45 @@ impl<'task> TaskRunner<'task> {
             return Err(result_error);
         }
 
-        // If our last task execution was a failure, return a hard error
+        self.check_last_execution()
+    }
+
+    #[instrument(skip(self))]
+    pub fn load_test_report(&self) -> miette::Result<Option<TestReport>> {
+        let (Some(config), Some(executed_at)) = (&self.task.options.test_report, self.executed_at)
+        else {
+            return Ok(None);
+        };
+
+        debug!(
+            task_target = self.task.target.as_str(),
+            path = config.path.as_str(),
+            "Loading test report for task"
+        );
+
+        let mut report = load_test_report(
+            config,
+            &self.app.workspace_root,
+            &self.project.root,
+            Some(executed_at),
+        )?;
+
+        // Test output may include secrets that were injected into the command
+        if let Some(report) = &mut report {
+            if !self.secrets.is_empty() {
+                for test in &mut report.tests {
+                    if let Some(message) = &test.message {
+                        test.message = Some(redact_secrets(message, &self.secrets));
+                    }
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    // If our last task execution was a failure, return a hard error
+    fn check_last_execution(&self) -> miette::Result<()> {
         if let Some(last_attempt) = self.operations.get_last_execution() {
             if last_attempt.has_failed() {
                 return Err(TaskRunnerError::RunFailed {
@@ -584,6 +682,203 @@ impl<'task> TaskRunner<'task> {
         Ok(())
     }
 
+    fn should_execute_remotely(&self, node: &ActionNode) -> bool {
+        if !RemoteService::is_execution_enabled()
+            || self.remote_state.is_none()
+            || node.is_interactive()
+            || node.is_persistent()
+            || self.task.is_interactive()
+            || self.task.is_persistent()
+            // Secrets are resolved locally and must never leave the machine
+            || !self.task.secrets.is_empty()
+        {
+            return false;
+        }
+
+        // Output paths must be declared up front in the action,
+        // so globs can't be downloaded from the remote service
+        if !self.task.output_globs.is_empty() {
+            debug!(
+                task_target = self.task.target.as_str(),
+                "Task has output globs, which are not supported by remote execution, executing locally instead"
+            );
+
+            return false;
+        }
+
+        true
+    }
+
+    /// The remote service doesn't inherit our process environment, so pass the
+    /// variables that moon has set, and `PATH` so that binaries can be resolved.
+    fn create_remote_env(&self, command: &Command) -> BTreeMap<String, String> {
+        let mut vars = BTreeMap::default();
+
+        if let Some(path) = env::var_os("PATH") {
+            vars.insert("PATH".into(), path.to_string_lossy().to_string());
+        }
+
+        let bag = GlobalEnvBag::instance();
+
+        bag.list_added(|key, value| {
+            vars.insert(
+                key.to_string_lossy().to_string(),
+                value.to_string_lossy().to_string(),
+            );
+        });
+
+        bag.list_removed(|key| {
+            vars.remove(key.to_string_lossy().as_ref());
+        });
+
+        for (key, value) in &command.env {
+            let key = key.to_string_lossy().to_string();
+
+            match value {
+                Some(value) => {
+                    vars.insert(key, value.to_string_lossy().to_string());
+                }
+                None => {
+                    vars.remove(&key);
+                }
+            };
+        }
+
+        vars
+    }
+
+    #[instrument(skip_all)]
+    async fn execute_remotely(
+        &mut self,
+        context: &ActionContext,
+        node: &ActionNode,
+    ) -> miette::Result<bool> {
+        if self.remote_state.is_none() {
+            return Ok(false);
+        }
+
+        // Build the command the same way as local execution, so that the
+        // remote action inherits the same arguments, environment, and shell
+        let mut builder = CommandBuilder::new(self.app, self.project, self.task, node);
+        builder.set_platform_manager(self.platform_manager);
+
+        let command = builder.build(context).await?;
+        let command_line = CommandLine::new(&command);
+
+        // Input can't be written to stdin of a remote process
+        if !command_line.input.is_empty() {
+            debug!(
+                task_target = self.task.target.as_str(),
+                "Task command requires stdin, which is not supported by remote execution, executing locally instead"
+            );
+
+            return Ok(false);
+        }
+
+        let arguments = command_line
+            .command
+            .iter()
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+        let env = self.create_remote_env(&command);
+
+        let (Some(state), Some(remote)) = (&mut self.remote_state, RemoteService::session()) else {
+            return Ok(false);
+        };
+
+        debug!(
+            task_target = self.task.target.as_str(),
+            "Executing the task on the remote service"
+        );
+
+        let working_dir = if self.task.options.run_from_workspace_root {
+            WorkspaceRelativePathBuf::default()
+        } else {
+            self.project.source.clone()
+        };
+
+        state.include_dependency_outputs(&self.app.workspace_root, &self.dependencies)?;
+        state.create_action_from_task(&self.app.workspace_root, &working_dir, arguments, env)?;
+
+        let mut attempt = Operation::task_execution(self.task.get_command_line());
+
+        match remote.execute_action(state).await {
+            Ok(true) => {}
+            Ok(false) => {
+                debug!(
+                    task_target = self.task.target.as_str(),
+                    "Remote service did not execute the task, executing locally instead"
+                );
+
+                return Ok(false);
+            }
+            Err(error) => {
+                warn!(
+                    task_target = self.task.target.as_str(),
+                    "Failed to execute the task on the remote service, executing locally instead: {}",
+                    color::muted_light(error.to_string()),
+                );
+
+                return Ok(false);
+            }
+        };
+
+        let exit_code = state
+            .action_result
+            .as_ref()
+            .map(|result| result.exit_code)
+            .unwrap_or(-1);
+
+        if let (Some(output), Some(result)) =
+            (attempt.get_exec_output_mut(), state.action_result.as_ref())
+        {
+            output.exit_code = Some(exit_code);
+
+            if !result.stderr_raw.is_empty() {
+                output.set_stderr(String::from_utf8_lossy(&result.stderr_raw).into());
+            }
+
+            if !result.stdout_raw.is_empty() {
+                output.set_stdout(String::from_utf8_lossy(&result.stdout_raw).into());
+            }
+        }
+
+        attempt.finish(if exit_code == 0 {
+            ActionStatus::Passed
+        } else {
+            ActionStatus::Failed
+        });
+
+        // The output was captured remotely, so it must be printed
+        self.report_item.output_streamed = false;
+
+        self.app
+            .console
+            .on_task_started(&self.task.target, &attempt, &self.report_item)?;
+
+        self.app
+            .console
+            .on_task_finished(&self.task.target, &attempt, &self.report_item, None)?;
+
+        // Persist the state locally, while the remote action result
+        // will be re-created and uploaded by the archiver
+        self.persist_state(&attempt)?;
+
+        if let Some(state) = &mut self.remote_state {
+            state.create_action_result_from_operation(&attempt)?;
+        }
+
+        self.target_state = Some(if exit_code == 0 {
+            TargetState::from_hash(self.report_item.hash.as_deref())
+        } else {
+            TargetState::Failed
+        });
+
+        self.operations.push(attempt);
+
+        Ok(true)
+    }
+
     #[instrument(skip(self))]
     pub fn skip(&mut self) -> miette::Result<()> {
         debug!(task_target = self.task.target.as_str(), "Skipping task");
@@ -802,6 +1097,12 @@ impl<'task> TaskRunner<'task> {
 
         if let Some(output) = operation.get_exec_output() {
             self.cache.data.exit_code = output.get_exit_code();
+            self.cache.data.sandbox_violation = output
+                .sandbox
+                .as_ref()
+                .is_some_and(|sandbox| sandbox.has_violations())
+                && self.cache.data.exit_code == 0
+                && operation.has_failed();
 
             fs::write_file(
                 err_path,
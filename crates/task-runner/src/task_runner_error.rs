@@ -31,4 +31,46 @@ pub enum TaskRunnerError {
         .target.style(Style::Label)
     )]
     MissingOutputs { target: Target },
+
+    #[diagnostic(
+        code(task_runner::secrets::missing),
+        help = "Mark the secret as optional if it's not always available."
+    )]
+    #[error(
+        "Unable to resolve secret {} for task {}.",
+        .name.style(Style::Symbol),
+        .target.style(Style::Label),
+    )]
+    MissingSecret { name: String, target: Target },
+
+    #[diagnostic(code(task_runner::secrets::unsupported_provider))]
+    #[error(
+        "Extension {} cannot be used as a secrets provider for task {}, as it does not implement the {} function.",
+        .id.style(Style::Id),
+        .target.style(Style::Label),
+        "resolve_secret".style(Style::Symbol),
+    )]
+    UnsupportedSecretProvider { id: String, target: Target },
+
+    #[diagnostic(code(task_runner::sandbox::failed))]
+    #[error(
+        "Failed to trace file access for task {}.",
+        .target.style(Style::Label),
+    )]
+    SandboxFailed {
+        target: Target,
+        #[source]
+        error: Box<notify::Error>,
+    },
+
+    #[diagnostic(code(task_runner::sandbox::violation))]
+    #[error(
+        "Task {} accessed files that were not declared as inputs or outputs:\n{}\n\nDeclare them in the task's {} or {}, or disable the task's {} option.",
+        .target.style(Style::Label),
+        .files.join("\n"),
+        "inputs".style(Style::Property),
+        "outputs".style(Style::Property),
+        "sandbox".style(Style::Property),
+    )]
+    SandboxViolation { target: Target, files: Vec<String> },
 }
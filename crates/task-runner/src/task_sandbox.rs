@@ -0,0 +1,360 @@
+use crate::task_runner_error::TaskRunnerError;
+use moon_action::OperationMetaSandbox;
+use moon_common::path::{PathExt, WorkspaceRelativePath};
+use moon_task::Task;
+use notify::event::{AccessKind, AccessMode, CreateKind, ModifyKind, RemoveKind};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use rustc_hash::FxHashMap;
+use starbase_utils::glob::GlobSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Arc, LazyLock};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use tracing::{debug, trace};
+
+// Directories that are never traced, as they are either managed by
+// moon itself, or are too large and noisy to be useful
+const IGNORED_DIR_NAMES: [&str; 6] = [".git", ".hg", ".jj", ".sl", ".svn", "node_modules"];
+
+// How long to wait for the kernel to deliver remaining events,
+// after the process has exited
+const SETTLE_DURATION: Duration = Duration::from_millis(50);
+const MAX_SETTLE_DURATION: Duration = Duration::from_secs(1);
+
+// Sandboxed tasks hold this lock exclusively while running, while all other
+// tasks hold it shared, as inotify can't attribute file access to a process.
+// This serializes the pipeline while a sandboxed task is running!
+static SANDBOX_LOCK: LazyLock<RwLock<()>> = LazyLock::new(|| RwLock::new(()));
+
+// Persistent tasks never exit, so they can't hold the lock, and
+// are counted instead, so that sandboxed tasks can warn about them
+static PERSISTENT_RUNNING: AtomicUsize = AtomicUsize::new(0);
+
+/// Guard that keeps sandboxed tasks from running in parallel with other tasks.
+pub struct TaskSandboxLock {
+    _exclusive: Option<RwLockWriteGuard<'static, ()>>,
+    _shared: Option<RwLockReadGuard<'static, ()>>,
+    persistent: bool,
+}
+
+impl Drop for TaskSandboxLock {
+    fn drop(&mut self) {
+        if self.persistent {
+            PERSISTENT_RUNNING.fetch_sub(1, Ordering::AcqRel);
+        }
+    }
+}
+
+#[derive(Default)]
+struct FileAccess {
+    read: bool,
+    written: bool,
+}
+
+struct FileAccessTracer {
+    accessed: FxHashMap<PathBuf, FileAccess>,
+    ignored_dirs: Vec<PathBuf>,
+    watcher: RecommendedWatcher,
+}
+
+impl FileAccessTracer {
+    fn is_ignored(&self, path: &Path) -> bool {
+        self.ignored_dirs.iter().any(|dir| path.starts_with(dir))
+            || path.components().any(|comp| {
+                IGNORED_DIR_NAMES
+                    .iter()
+                    .any(|name| comp.as_os_str() == *name)
+            })
+    }
+
+    // Watch each directory individually (instead of recursively),
+    // so that we can skip ignored directories entirely
+    fn watch_dir(&mut self, dir: &Path, record_files: bool) -> Result<(), notify::Error> {
+        if self.is_ignored(dir) {
+            return Ok(());
+        }
+
+        if let Err(error) = self.watcher.watch(dir, RecursiveMode::NonRecursive) {
+            if matches!(error.kind, notify::ErrorKind::MaxFilesWatch) {
+                return Err(error);
+            }
+
+            trace!(dir = ?dir, "Failed to trace directory: {error}");
+
+            return Ok(());
+        }
+
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return Ok(());
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+
+            if path.is_dir() {
+                self.watch_dir(&path, record_files)?;
+            } else if record_files {
+                // Files may have been created before we started watching
+                // the new directory, so consider them written
+                self.accessed.entry(path).or_default().written = true;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: Event) {
+        for path in event.paths {
+            if self.is_ignored(&path) {
+                continue;
+            }
+
+            match event.kind {
+                EventKind::Access(AccessKind::Open(_)) => {
+                    if !path.is_dir() {
+                        self.accessed.entry(path).or_default().read = true;
+                    }
+                }
+                EventKind::Create(CreateKind::Folder) => {
+                    let _ = self.watch_dir(&path, true);
+                }
+                EventKind::Create(_)
+                | EventKind::Access(AccessKind::Close(AccessMode::Write))
+                | EventKind::Modify(ModifyKind::Data(_) | ModifyKind::Name(_))
+                | EventKind::Remove(RemoveKind::File) => {
+                    self.accessed.entry(path).or_default().written = true;
+                }
+                _ => {}
+            };
+        }
+    }
+
+    fn run(
+        mut self,
+        receiver: Receiver<notify::Result<Event>>,
+        stopped: Arc<AtomicBool>,
+    ) -> FxHashMap<PathBuf, FileAccess> {
+        let mut stopped_at: Option<Instant> = None;
+
+        loop {
+            if stopped_at.is_none() && stopped.load(Ordering::Acquire) {
+                stopped_at = Some(Instant::now());
+            }
+
+            match receiver.recv_timeout(SETTLE_DURATION) {
+                Ok(Ok(event)) => self.handle_event(event),
+                Ok(Err(error)) => {
+                    trace!("Failed to receive file access event: {error}");
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    if stopped_at.is_some() {
+                        break;
+                    }
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            };
+
+            // Other processes may continuously modify the workspace,
+            // so don't wait forever for events to settle
+            if stopped_at.is_some_and(|at| at.elapsed() >= MAX_SETTLE_DURATION) {
+                break;
+            }
+        }
+
+        self.accessed
+    }
+}
+
+/// Traces file access within the project and declared inputs while a task is
+/// running (using inotify), and reports files that were read but not declared
+/// as inputs, or were written but not declared as outputs.
+///
+/// Since inotify does not report the process that accessed a file, file access
+/// from other processes will also be traced. To avoid this for tasks in the
+/// same pipeline, sandboxed tasks run exclusively (see [`TaskSandbox::lock`]),
+/// with the exception of persistent tasks, which are never sandboxed.
+pub struct TaskSandbox {
+    handle: JoinHandle<FxHashMap<PathBuf, FileAccess>>,
+    stopped: Arc<AtomicBool>,
+    workspace_root: PathBuf,
+}
+
+impl TaskSandbox {
+    /// Acquire the lock required to run a task. Sandboxed tasks wait for all
+    /// other running tasks to finish, and block new tasks from starting.
+    pub async fn lock(sandboxed: bool) -> TaskSandboxLock {
+        if sandboxed {
+            TaskSandboxLock {
+                _exclusive: Some(SANDBOX_LOCK.write().await),
+                _shared: None,
+                persistent: false,
+            }
+        } else {
+            TaskSandboxLock {
+                _exclusive: None,
+                _shared: Some(SANDBOX_LOCK.read().await),
+                persistent: false,
+            }
+        }
+    }
+
+    /// Track a running persistent task. Persistent tasks are never sandboxed,
+    /// and don't block sandboxed tasks, as they never exit.
+    pub fn lock_persistent() -> TaskSandboxLock {
+        PERSISTENT_RUNNING.fetch_add(1, Ordering::AcqRel);
+
+        TaskSandboxLock {
+            _exclusive: None,
+            _shared: None,
+            persistent: true,
+        }
+    }
+
+    /// Return true if any persistent tasks are running, whose
+    /// file access would be traced by sandboxed tasks.
+    pub fn is_persistent_running() -> bool {
+        PERSISTENT_RUNNING.load(Ordering::Acquire) > 0
+    }
+
+    pub fn new(
+        task: &Task,
+        workspace_root: &Path,
+        project_root: &Path,
+        ignored_dirs: Vec<PathBuf>,
+    ) -> miette::Result<Self> {
+        debug!(
+            task_target = task.target.as_str(),
+            "Sandboxing task, tracing file access within the project and declared inputs"
+        );
+
+        let map_error = |error: notify::Error| TaskRunnerError::SandboxFailed {
+            target: task.target.clone(),
+            error: Box::new(error),
+        };
+
+        let (sender, receiver) = mpsc::channel();
+
+        let mut tracer = FileAccessTracer {
+            accessed: FxHashMap::default(),
+            ignored_dirs,
+            watcher: notify::recommended_watcher(sender).map_err(map_error)?,
+        };
+
+        for path in get_watch_paths(task, workspace_root, project_root) {
+            if path.is_dir() {
+                tracer.watch_dir(&path, false).map_err(map_error)?;
+            } else if path.exists() && !tracer.is_ignored(&path) {
+                tracer
+                    .watcher
+                    .watch(&path, RecursiveMode::NonRecursive)
+                    .map_err(map_error)?;
+            }
+        }
+
+        let stopped = Arc::new(AtomicBool::new(false));
+        let stopped_clone = Arc::clone(&stopped);
+
+        Ok(Self {
+            handle: thread::spawn(move || tracer.run(receiver, stopped_clone)),
+            stopped,
+            workspace_root: workspace_root.to_path_buf(),
+        })
+    }
+
+    /// Stop tracing and compare the accessed files against the
+    /// task's declared inputs and outputs.
+    pub fn stop(self, task: &Task) -> miette::Result<OperationMetaSandbox> {
+        self.stopped.store(true, Ordering::Release);
+
+        let accessed = self.handle.join().unwrap_or_default();
+        let input_globs = GlobSet::new(&task.input_globs)?;
+        let output_globs = GlobSet::new(&task.output_globs)?;
+        let mut result = OperationMetaSandbox::default();
+
+        for (path, access) in accessed {
+            if path.is_dir() {
+                continue;
+            }
+
+            let Ok(file) = path.relative_to(&self.workspace_root) else {
+                continue;
+            };
+
+            let is_output = task
+                .output_files
+                .iter()
+                .any(|output| file.starts_with(output))
+                || output_globs.matches(file.as_str());
+
+            if is_output {
+                continue;
+            }
+
+            if access.written {
+                result.undeclared_outputs.push(file);
+            } else if access.read
+                && !is_declared_input(task, &file)
+                && !input_globs.matches(file.as_str())
+            {
+                result.undeclared_inputs.push(file);
+            }
+        }
+
+        result.undeclared_inputs.sort();
+        result.undeclared_outputs.sort();
+
+        debug!(
+            task_target = task.target.as_str(),
+            undeclared_inputs = result.undeclared_inputs.len(),
+            undeclared_outputs = result.undeclared_outputs.len(),
+            "Stopped sandboxing task"
+        );
+
+        Ok(result)
+    }
+}
+
+fn is_declared_input(task: &Task, file: &WorkspaceRelativePath) -> bool {
+    task.input_files.iter().any(|input| file.starts_with(input))
+}
+
+// Only trace the project and the task's declared inputs, instead of walking
+// the entire workspace. Paths nested within another traced path are removed.
+fn get_watch_paths(task: &Task, workspace_root: &Path, project_root: &Path) -> Vec<PathBuf> {
+    let mut paths = vec![project_root.to_path_buf()];
+
+    for file in &task.input_files {
+        paths.push(file.to_logical_path(workspace_root));
+    }
+
+    for glob in &task.input_globs {
+        if glob.as_str().starts_with('!') {
+            continue;
+        }
+
+        // Trace the static base of the glob, before any wildcards
+        let base = glob
+            .as_str()
+            .split('/')
+            .take_while(|part| !part.contains(['*', '?', '[', '{']))
+            .collect::<Vec<_>>()
+            .join("/");
+
+        paths.push(workspace_root.join(base));
+    }
+
+    paths.sort();
+
+    let mut watch_paths: Vec<PathBuf> = vec![];
+
+    for path in paths {
+        if !watch_paths.iter().any(|parent| path.starts_with(parent)) {
+            watch_paths.push(path);
+        }
+    }
+
+    watch_paths
+}
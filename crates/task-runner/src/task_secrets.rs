@@ -0,0 +1,103 @@
+use crate::task_runner_error::TaskRunnerError;
+use moon_app_context::AppContext;
+use moon_env_var::GlobalEnvBag;
+use moon_project::Project;
+use moon_task::Task;
+use rustc_hash::FxHashMap;
+use starbase_utils::fs;
+use tracing::{debug, instrument, trace};
+
+/// Resolve the values of all secrets configured for the task, from either
+/// the host environment, a file, or an extension that acts as a provider.
+#[instrument(skip_all)]
+pub async fn resolve_task_secrets(
+    app: &AppContext,
+    project: &Project,
+    task: &Task,
+) -> miette::Result<FxHashMap<String, String>> {
+    let mut secrets = FxHashMap::default();
+
+    for (name, config) in &task.secrets {
+        let value = if let Some(env) = &config.env {
+            trace!(
+                task_target = task.target.as_str(),
+                secret = name,
+                env,
+                "Resolving secret from environment variable"
+            );
+
+            GlobalEnvBag::instance().get(env)
+        } else if let Some(file) = &config.file {
+            let path = match file.as_str().strip_prefix('/') {
+                Some(ws_path) => app.workspace_root.join(ws_path),
+                None => project.root.join(file.as_str()),
+            };
+
+            trace!(
+                task_target = task.target.as_str(),
+                secret = name,
+                file = ?path,
+                "Resolving secret from file"
+            );
+
+            if path.exists() {
+                Some(
+                    fs::read_file(&path)?
+                        .trim_end_matches(['\r', '\n'])
+                        .to_owned(),
+                )
+            } else {
+                None
+            }
+        } else if let Some(id) = &config.provider {
+            trace!(
+                task_target = task.target.as_str(),
+                secret = name,
+                provider = id.as_str(),
+                "Resolving secret from extension provider"
+            );
+
+            let extension = app.extension_registry.load(id).await?;
+
+            if !extension.supports_secrets().await {
+                return Err(TaskRunnerError::UnsupportedSecretProvider {
+                    id: id.to_string(),
+                    target: task.target.clone(),
+                }
+                .into());
+            }
+
+            extension
+                .resolve_secret(
+                    config.key.as_deref().unwrap_or(name.as_str()),
+                    task.target.as_str(),
+                    app.extension_registry.create_context(),
+                )
+                .await?
+        } else {
+            None
+        };
+
+        match value {
+            Some(value) if !value.is_empty() => {
+                secrets.insert(name.to_owned(), value);
+            }
+            _ if config.optional => {
+                debug!(
+                    task_target = task.target.as_str(),
+                    secret = name,
+                    "Unable to resolve optional secret, skipping"
+                );
+            }
+            _ => {
+                return Err(TaskRunnerError::MissingSecret {
+                    name: name.to_owned(),
+                    target: task.target.clone(),
+                }
+                .into());
+            }
+        };
+    }
+
+    Ok(secrets)
+}
@@ -0,0 +1,300 @@
+use moon_action::{TestCase, TestCaseStatus, TestReport};
+use moon_config::{TaskOptionTestReportConfig, TaskTestReportFormat};
+use roxmltree::{Document, Node};
+use starbase_utils::{fs, glob};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tracing::{debug, warn};
+
+fn to_duration(secs: f64) -> Option<Duration> {
+    (secs.is_finite() && secs >= 0.0).then(|| Duration::from_secs_f64(secs))
+}
+
+fn parse_seconds(value: &str) -> Option<Duration> {
+    value
+        .trim()
+        .replace(',', "")
+        .parse::<f64>()
+        .ok()
+        .and_then(to_duration)
+}
+
+fn get_junit_message(node: Node) -> Option<String> {
+    node.attribute("message")
+        .or_else(|| node.text())
+        .map(|message| message.trim())
+        .filter(|message| !message.is_empty())
+        .map(|message| message.to_owned())
+}
+
+/// Parse a JUnit XML report. Supports both `<testsuites>` and `<testsuite>`
+/// roots, and nested suites.
+pub fn parse_junit(content: &str) -> Result<TestReport, roxmltree::Error> {
+    let document = Document::parse(content)?;
+    let mut report = TestReport::default();
+
+    for node in document
+        .descendants()
+        .filter(|node| node.has_tag_name("testcase"))
+    {
+        let suite = node.attribute("classname").or_else(|| {
+            node.ancestors()
+                .find(|parent| parent.has_tag_name("testsuite"))
+                .and_then(|parent| parent.attribute("name"))
+        });
+
+        let mut test = TestCase {
+            duration: node.attribute("time").and_then(parse_seconds),
+            name: node.attribute("name").unwrap_or_default().to_owned(),
+            suite: suite
+                .filter(|suite| !suite.is_empty())
+                .map(|suite| suite.to_owned()),
+            ..Default::default()
+        };
+
+        for child in node.children().filter(|child| child.is_element()) {
+            match child.tag_name().name() {
+                "failure" | "error" => {
+                    test.status = TestCaseStatus::Failed;
+                    test.message = get_junit_message(child);
+                }
+                "skipped" if test.status != TestCaseStatus::Failed => {
+                    test.status = TestCaseStatus::Skipped;
+                    test.message = get_junit_message(child);
+                }
+                _ => {}
+            };
+        }
+
+        report.add_test(test);
+    }
+
+    Ok(report)
+}
+
+fn parse_tap_test_point(line: &str) -> Option<TestCase> {
+    let (passed, rest) = if let Some(rest) = line.strip_prefix("not ok") {
+        (false, rest)
+    } else if let Some(rest) = line.strip_prefix("ok") {
+        (true, rest)
+    } else {
+        return None;
+    };
+
+    if !rest.is_empty() && !rest.starts_with(' ') {
+        return None;
+    }
+
+    // Strip the test number and separator
+    let rest = rest
+        .trim_start()
+        .trim_start_matches(|ch: char| ch.is_ascii_digit())
+        .trim_start();
+    let rest = rest.strip_prefix('-').unwrap_or(rest).trim_start();
+
+    let mut test = TestCase {
+        status: if passed {
+            TestCaseStatus::Passed
+        } else {
+            TestCaseStatus::Failed
+        },
+        ..Default::default()
+    };
+
+    // Directives (SKIP, TODO) come after a hash
+    match rest.split_once('#') {
+        Some((name, directive)) => {
+            let directive = directive.trim();
+            let upper = directive.to_uppercase();
+
+            test.name = name.trim().to_owned();
+
+            if upper.starts_with("SKIP") || upper.starts_with("TODO") {
+                let reason = directive[4..].trim();
+
+                test.status = TestCaseStatus::Skipped;
+                test.message = (!reason.is_empty()).then(|| reason.to_owned());
+            }
+        }
+        None => {
+            test.name = rest.trim().to_owned();
+        }
+    };
+
+    Some(test)
+}
+
+fn get_indent(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+fn unquote(value: &str) -> String {
+    let value = value.trim();
+
+    for quote in ['\'', '"'] {
+        if value.len() >= 2 && value.starts_with(quote) && value.ends_with(quote) {
+            return value[1..value.len() - 1].to_owned();
+        }
+    }
+
+    value.to_owned()
+}
+
+/// Parse the YAML diagnostic block of a test point, and extract
+/// the message and duration (`duration_ms`) if available.
+fn parse_tap_diagnostics(lines: &[&str], test: &mut TestCase) {
+    let mut index = 0;
+
+    while index < lines.len() {
+        let line = lines[index];
+        let indent = get_indent(line);
+        index += 1;
+
+        let Some((key, value)) = line.trim().split_once(':') else {
+            continue;
+        };
+
+        let value = value.trim();
+
+        match key {
+            "duration_ms" => {
+                test.duration = value
+                    .parse::<f64>()
+                    .ok()
+                    .and_then(|ms| to_duration(ms / 1000.0));
+            }
+            "message" | "error" if test.message.is_none() => {
+                // Block scalars span multiple indented lines
+                if value.starts_with('|') || value.starts_with('>') {
+                    let mut message = vec![];
+
+                    while index < lines.len() && get_indent(lines[index]) > indent {
+                        message.push(lines[index].trim());
+                        index += 1;
+                    }
+
+                    test.message = Some(message.join("\n"));
+                } else if !value.is_empty() {
+                    test.message = Some(unquote(value));
+                }
+            }
+            _ => {}
+        };
+    }
+}
+
+/// Parse a TAP (Test Anything Protocol) report. Only top-level test points
+/// are recorded, as indented subtests are summarized by their parent.
+pub fn parse_tap(content: &str) -> TestReport {
+    let lines = content.lines().collect::<Vec<_>>();
+    let mut report = TestReport::default();
+    let mut index = 0;
+
+    while index < lines.len() {
+        let Some(mut test) = parse_tap_test_point(lines[index]) else {
+            index += 1;
+            continue;
+        };
+
+        index += 1;
+
+        // A YAML diagnostic block may directly follow the test point
+        if index < lines.len() && lines[index].trim() == "---" && get_indent(lines[index]) > 0 {
+            let start = index + 1;
+            let mut end = start;
+
+            while end < lines.len() && lines[end].trim() != "..." {
+                end += 1;
+            }
+
+            parse_tap_diagnostics(&lines[start..end], &mut test);
+
+            index = end + 1;
+        }
+
+        report.add_test(test);
+    }
+
+    report
+}
+
+/// Parse the content of a report based on its format.
+pub fn parse_test_report(
+    content: &str,
+    format: TaskTestReportFormat,
+) -> Result<TestReport, roxmltree::Error> {
+    match format {
+        TaskTestReportFormat::Junit => parse_junit(content),
+        TaskTestReportFormat::Tap => Ok(parse_tap(content)),
+    }
+}
+
+/// Locate the report files for the configured path, relative from the project
+/// root, or the workspace root when prefixed with `/`.
+pub fn locate_test_report_files(
+    config: &TaskOptionTestReportConfig,
+    workspace_root: &Path,
+    project_root: &Path,
+) -> miette::Result<Vec<PathBuf>> {
+    let path = config.path.as_str();
+
+    let (base, pattern) = match path.strip_prefix('/') {
+        Some(pattern) => (workspace_root, pattern),
+        None => (project_root, path),
+    };
+
+    if glob::is_glob(pattern) {
+        return Ok(glob::walk_files(base, [pattern])?);
+    }
+
+    let file = base.join(pattern);
+
+    Ok(if file.is_file() { vec![file] } else { vec![] })
+}
+
+/// Load and parse all report files for the configured path, and merge them into
+/// a single report. Files that were modified before the provided time are ignored,
+/// so that stale reports from a previous run are not attached. Reports that
+/// fail to parse are logged, but do not fail the task.
+pub fn load_test_report(
+    config: &TaskOptionTestReportConfig,
+    workspace_root: &Path,
+    project_root: &Path,
+    since: Option<SystemTime>,
+) -> miette::Result<Option<TestReport>> {
+    let mut files = locate_test_report_files(config, workspace_root, project_root)?;
+
+    if let Some(since) = since {
+        files.retain(|file| {
+            std::fs::metadata(file)
+                .and_then(|meta| meta.modified())
+                .is_ok_and(|modified| modified >= since)
+        });
+    }
+
+    if files.is_empty() {
+        debug!(
+            path = config.path.as_str(),
+            "No test report files found, or they are stale"
+        );
+
+        return Ok(None);
+    }
+
+    files.sort();
+
+    let mut report = TestReport::default();
+
+    for file in files {
+        debug!(file = ?file, format = ?config.format, "Parsing test report");
+
+        match parse_test_report(&fs::read_file(&file)?, config.format) {
+            Ok(result) => report.extend(result),
+            Err(error) => {
+                warn!(file = ?file, "Failed to parse test report: {error}");
+            }
+        };
+    }
+
+    Ok(Some(report))
+}
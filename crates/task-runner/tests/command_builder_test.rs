@@ -223,6 +223,116 @@ mod command_builder {
         }
     }
 
+    mod secrets {
+        use super::*;
+        use moon_config::{FilePath, TaskSecretConfig};
+        use moon_env_var::GlobalEnvBag;
+
+        #[tokio::test]
+        async fn injects_secret_from_env() {
+            GlobalEnvBag::instance().set("MOON_TEST_BUILDER_SECRET", "abc123");
+
+            let container = TaskRunnerContainer::new("builder", "base").await;
+            let command = container
+                .create_command_with_config(ActionContext::default(), |task, _| {
+                    task.secrets.insert(
+                        "TOKEN".into(),
+                        TaskSecretConfig::from_env("MOON_TEST_BUILDER_SECRET"),
+                    );
+                })
+                .await;
+
+            GlobalEnvBag::instance().remove("MOON_TEST_BUILDER_SECRET");
+
+            assert_eq!(get_env(&command, "TOKEN").unwrap(), "abc123");
+            assert_eq!(command.secrets, vec!["abc123"]);
+        }
+
+        #[tokio::test]
+        async fn injects_secret_from_file() {
+            let container = TaskRunnerContainer::new("builder", "base").await;
+            container.sandbox.create_file("project/.token", "xyz789\n");
+
+            let command = container
+                .create_command_with_config(ActionContext::default(), |task, _| {
+                    task.secrets.insert(
+                        "TOKEN".into(),
+                        TaskSecretConfig::from_file(FilePath(".token".into())),
+                    );
+                })
+                .await;
+
+            assert_eq!(get_env(&command, "TOKEN").unwrap(), "xyz789");
+            assert_eq!(command.secrets, vec!["xyz789"]);
+        }
+
+        #[tokio::test]
+        async fn substitutes_secrets_in_task_env() {
+            let container = TaskRunnerContainer::new("builder", "base").await;
+            container.sandbox.create_file(".token", "xyz789");
+
+            let command = container
+                .create_command_with_config(ActionContext::default(), |task, _| {
+                    task.env.insert("AUTH".into(), "Bearer $TOKEN".into());
+                    task.secrets.insert(
+                        "TOKEN".into(),
+                        TaskSecretConfig::from_file(FilePath("/.token".into())),
+                    );
+                })
+                .await;
+
+            assert_eq!(get_env(&command, "AUTH").unwrap(), "Bearer xyz789");
+            assert_eq!(get_env(&command, "KEY").unwrap(), "value");
+        }
+
+        #[tokio::test]
+        async fn redacts_secrets_from_command_line() {
+            let container = TaskRunnerContainer::new("builder", "base").await;
+            container.sandbox.create_file(".token", "xyz789");
+
+            let mut command = container
+                .create_command_with_config(ActionContext::default(), |task, _| {
+                    task.secrets.insert(
+                        "TOKEN".into(),
+                        TaskSecretConfig::from_file(FilePath("/.token".into())),
+                    );
+                })
+                .await;
+
+            command.arg("--token=xyz789");
+
+            assert!(
+                moon_process::CommandLine::new(&command)
+                    .get_line(false, false)
+                    .ends_with("--token=*****")
+            );
+        }
+    }
+
+    mod process_group {
+        use super::*;
+
+        #[tokio::test]
+        async fn not_set_by_default() {
+            let container = TaskRunnerContainer::new("builder", "base").await;
+            let command = container.create_command(ActionContext::default()).await;
+
+            assert!(command.process_group.is_none());
+        }
+
+        #[tokio::test]
+        async fn inherits_from_context() {
+            let container = TaskRunnerContainer::new("builder", "base").await;
+
+            let mut context = ActionContext::default();
+            context.process_group = Some("persistent".into());
+
+            let command = container.create_command(context).await;
+
+            assert_eq!(command.process_group.as_deref(), Some("persistent"));
+        }
+    }
+
     mod shell {
         use super::*;
 
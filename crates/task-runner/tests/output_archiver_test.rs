@@ -2,7 +2,6 @@ mod utils;
 
 use moon_cache::CacheMode;
 use moon_env_var::GlobalEnvBag;
-use starbase_archive::Archiver;
 use std::fs;
 use utils::*;
 
@@ -33,7 +32,7 @@ mod output_archiver {
                 container
                     .sandbox
                     .path()
-                    .join(".moon/cache/outputs/hash123.tar.gz")
+                    .join(".moon/cache/outputs/hash123.json")
                     .exists()
             );
         }
@@ -44,7 +43,7 @@ mod output_archiver {
             container.sandbox.create_file("project/file.txt", "");
             container
                 .sandbox
-                .create_file(".moon/cache/outputs/hash123.tar.gz", "");
+                .create_file(".moon/cache/outputs/hash123.json", "");
 
             let archiver = container.create_archiver();
 
@@ -97,7 +96,7 @@ mod output_archiver {
             let file = archiver.archive("hash123", None).await.unwrap().unwrap();
             let dir = container.sandbox.path().join("out");
 
-            Archiver::new(&dir, &file).unpack_from_ext().unwrap();
+            container.unpack_archive(&file, &dir);
 
             assert!(dir.join("project/file.txt").exists());
         }
@@ -114,7 +113,7 @@ mod output_archiver {
             let file = archiver.archive("hash123", None).await.unwrap().unwrap();
             let dir = container.sandbox.path().join("out");
 
-            Archiver::new(&dir, &file).unpack_from_ext().unwrap();
+            container.unpack_archive(&file, &dir);
 
             assert!(dir.join("project/one.txt").exists());
             assert!(dir.join("project/two.txt").exists());
@@ -137,7 +136,7 @@ mod output_archiver {
             let file = archiver.archive("hash123", None).await.unwrap().unwrap();
             let dir = container.sandbox.path().join("out");
 
-            Archiver::new(&dir, &file).unpack_from_ext().unwrap();
+            container.unpack_archive(&file, &dir);
 
             let err = dir.join(".moon/cache/states/project/file-outputs/stderr.log");
             let out = dir.join(".moon/cache/states/project/file-outputs/stdout.log");
@@ -148,6 +147,22 @@ mod output_archiver {
             assert_eq!(fs::read_to_string(out).unwrap(), "out");
         }
 
+        #[tokio::test]
+        async fn stores_identical_files_as_one_blob() {
+            let container = TaskRunnerContainer::new("archive", "output-many-files").await;
+            container.sandbox.create_file("project/a.txt", "same");
+            container.sandbox.create_file("project/b.txt", "same");
+            container.sandbox.create_file("project/c.txt", "other");
+
+            let archiver = container.create_archiver();
+
+            archiver.archive("hash123", None).await.unwrap().unwrap();
+
+            let blobs_dir = &container.app_context.cache_engine.hash.blobs_dir;
+
+            assert_eq!(fs::read_dir(blobs_dir).unwrap().count(), 2);
+        }
+
         #[tokio::test]
         async fn can_ignore_output_files_with_negation() {
             let container = TaskRunnerContainer::new("archive", "file-outputs-negated").await;
@@ -160,7 +175,7 @@ mod output_archiver {
             let file = archiver.archive("hash123", None).await.unwrap().unwrap();
             let dir = container.sandbox.path().join("out");
 
-            Archiver::new(&dir, &file).unpack_from_ext().unwrap();
+            container.unpack_archive(&file, &dir);
 
             assert!(dir.join("project/a.txt").exists());
             assert!(!dir.join("project/b.txt").exists());
@@ -179,7 +194,7 @@ mod output_archiver {
             let file = archiver.archive("hash123", None).await.unwrap().unwrap();
             let dir = container.sandbox.path().join("out");
 
-            Archiver::new(&dir, &file).unpack_from_ext().unwrap();
+            container.unpack_archive(&file, &dir);
 
             assert!(dir.join("project/a.txt").exists());
             assert!(!dir.join("project/b.txt").exists());
@@ -196,7 +211,7 @@ mod output_archiver {
             let file = archiver.archive("hash123", None).await.unwrap().unwrap();
             let dir = container.sandbox.path().join("out");
 
-            Archiver::new(&dir, &file).unpack_from_ext().unwrap();
+            container.unpack_archive(&file, &dir);
 
             assert!(dir.join("project/file.txt").exists());
         }
@@ -213,7 +228,7 @@ mod output_archiver {
             let file = archiver.archive("hash123", None).await.unwrap().unwrap();
             let dir = container.sandbox.path().join("out");
 
-            Archiver::new(&dir, &file).unpack_from_ext().unwrap();
+            container.unpack_archive(&file, &dir);
 
             assert!(dir.join("project/a.txt").exists());
             assert!(dir.join("project/b.txt").exists());
@@ -230,7 +245,7 @@ mod output_archiver {
             let file = archiver.archive("hash123", None).await.unwrap().unwrap();
             let dir = container.sandbox.path().join("out");
 
-            Archiver::new(&dir, &file).unpack_from_ext().unwrap();
+            container.unpack_archive(&file, &dir);
 
             assert!(dir.join("project/dir/file.txt").exists());
         }
@@ -247,7 +262,7 @@ mod output_archiver {
             let file = archiver.archive("hash123", None).await.unwrap().unwrap();
             let dir = container.sandbox.path().join("out");
 
-            Archiver::new(&dir, &file).unpack_from_ext().unwrap();
+            container.unpack_archive(&file, &dir);
 
             assert!(dir.join("project/a/file.txt").exists());
             assert!(dir.join("project/b/file.txt").exists());
@@ -265,7 +280,7 @@ mod output_archiver {
             let file = archiver.archive("hash123", None).await.unwrap().unwrap();
             let dir = container.sandbox.path().join("out");
 
-            Archiver::new(&dir, &file).unpack_from_ext().unwrap();
+            container.unpack_archive(&file, &dir);
 
             assert!(dir.join("project/file.txt").exists());
             assert!(dir.join("project/dir/file.txt").exists());
@@ -283,7 +298,7 @@ mod output_archiver {
             let file = archiver.archive("hash123", None).await.unwrap().unwrap();
             let dir = container.sandbox.path().join("out");
 
-            Archiver::new(&dir, &file).unpack_from_ext().unwrap();
+            container.unpack_archive(&file, &dir);
 
             assert!(dir.join("root.txt").exists());
             assert!(dir.join("shared/a.txt").exists());
@@ -302,7 +317,7 @@ mod output_archiver {
             let file = archiver.archive("hash123", None).await.unwrap().unwrap();
             let dir = container.sandbox.path().join("out");
 
-            Archiver::new(&dir, &file).unpack_from_ext().unwrap();
+            container.unpack_archive(&file, &dir);
 
             assert!(dir.join("root.txt").exists());
             assert!(dir.join("project/file.txt").exists());
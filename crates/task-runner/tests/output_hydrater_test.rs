@@ -3,6 +3,8 @@ mod utils;
 use moon_cache::CacheMode;
 use moon_env_var::GlobalEnvBag;
 use moon_task_runner::output_hydrater::HydrateFrom;
+use std::fs;
+use std::time::{Duration, SystemTime};
 use utils::*;
 
 mod output_hydrater {
@@ -37,7 +39,7 @@ mod output_hydrater {
             let container = TaskRunnerContainer::new("archive", "file-outputs").await;
             container
                 .sandbox
-                .create_file(".moon/cache/outputs/hash123.tar.gz", "");
+                .create_file(".moon/cache/outputs/hash123.json", "{}");
 
             let hydrater = container.create_hydrator();
 
@@ -61,7 +63,7 @@ mod output_hydrater {
             let container = TaskRunnerContainer::new("archive", "file-outputs").await;
             container
                 .sandbox
-                .create_file(".moon/cache/outputs/hash123.tar.gz", "");
+                .create_file(".moon/cache/outputs/hash123.json", "{}");
 
             let hydrater = container.create_hydrator();
 
@@ -125,5 +127,66 @@ mod output_hydrater {
                     .exists()
             );
         }
+
+        #[tokio::test]
+        async fn doesnt_unpack_if_a_blob_is_missing() {
+            let container = TaskRunnerContainer::new("archive", "file-outputs").await;
+            container.pack_archive();
+
+            let hash_engine = &container.app_context.cache_engine.hash;
+
+            for entry in fs::read_dir(&hash_engine.blobs_dir).unwrap() {
+                fs::remove_file(entry.unwrap().path()).unwrap();
+            }
+
+            let hydrater = container.create_hydrator();
+
+            assert!(
+                !hydrater
+                    .hydrate(HydrateFrom::LocalCache, "hash123", None)
+                    .await
+                    .unwrap()
+            );
+            assert!(!container.sandbox.path().join("project/file.txt").exists());
+        }
+
+        #[tokio::test]
+        async fn only_copies_changed_files() {
+            let container = TaskRunnerContainer::new("archive", "file-outputs").await;
+            container.pack_archive();
+            container.sandbox.create_file("project/file.txt", "content");
+
+            let output = container.sandbox.path().join("project/file.txt");
+            let modified = SystemTime::UNIX_EPOCH + Duration::from_secs(1000);
+
+            fs::File::options()
+                .write(true)
+                .open(&output)
+                .unwrap()
+                .set_modified(modified)
+                .unwrap();
+
+            let hydrater = container.create_hydrator();
+
+            assert!(
+                hydrater
+                    .hydrate(HydrateFrom::LocalCache, "hash123", None)
+                    .await
+                    .unwrap()
+            );
+
+            // Unchanged, so not touched
+            assert_eq!(fs::metadata(&output).unwrap().modified().unwrap(), modified);
+
+            // Changed, so overwritten
+            container.sandbox.create_file("project/file.txt", "changed");
+
+            hydrater
+                .hydrate(HydrateFrom::LocalCache, "hash123", None)
+                .await
+                .unwrap();
+
+            assert_eq!(fs::read_to_string(&output).unwrap(), "content");
+        }
     }
 }
@@ -137,6 +137,20 @@ mod task_runner {
                 assert!(result.hash.is_some());
             }
 
+            #[tokio::test]
+            async fn tracks_last_success_hash() {
+                let container = TaskRunnerContainer::new_os("runner", "create-file").await;
+                container.sandbox.enable_git();
+
+                let mut runner = container.create_runner();
+                let node = container.create_action_node();
+                let context = ActionContext::default();
+
+                let result = runner.run_with_panic(&context, &node).await.unwrap();
+
+                assert_eq!(runner.cache.data.last_success_hash, result.hash.unwrap());
+            }
+
             #[tokio::test]
             async fn generates_a_hash_for_noop() {
                 let container = TaskRunnerContainer::new_os("runner", "noop").await;
@@ -455,7 +469,7 @@ mod task_runner {
 
                 container
                     .sandbox
-                    .create_file(".moon/cache/outputs/hash123.tar.gz", "");
+                    .create_file(".moon/cache/outputs/hash123.json", "{}");
 
                 assert_eq!(
                     runner.is_cached("hash123").await.unwrap(),
@@ -478,7 +492,7 @@ mod task_runner {
 
                 container
                     .sandbox
-                    .create_file(".moon/cache/outputs/hash123.tar.gz", "");
+                    .create_file(".moon/cache/outputs/hash123.json", "{}");
 
                 container
                     .app_context
@@ -497,7 +511,7 @@ mod task_runner {
 
                 container
                     .sandbox
-                    .create_file(".moon/cache/outputs/hash123.tar.gz", "");
+                    .create_file(".moon/cache/outputs/hash123.json", "{}");
 
                 container
                     .app_context
@@ -0,0 +1,177 @@
+#![cfg(target_os = "linux")]
+
+mod utils;
+
+use moon_common::path::WorkspaceRelativePathBuf;
+use moon_task_runner::task_sandbox::TaskSandbox;
+use std::fs;
+use std::time::Duration;
+use tokio::time::timeout;
+use utils::*;
+
+mod task_sandbox {
+    use super::*;
+
+    fn create_task_sandbox(container: &TaskRunnerContainer) -> TaskSandbox {
+        TaskSandbox::new(
+            &container.task,
+            container.sandbox.path(),
+            &container.project.root,
+            vec![container.sandbox.path().join(".moon/cache")],
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn doesnt_report_declared_inputs() {
+        let container = TaskRunnerContainer::new("sandbox", "sandboxed").await;
+        let sandbox = create_task_sandbox(&container);
+
+        fs::read_to_string(container.sandbox.path().join("project/src/index.js")).unwrap();
+        fs::read_to_string(container.sandbox.path().join("project/config.json")).unwrap();
+
+        let result = sandbox.stop(&container.task).unwrap();
+
+        assert!(!result.has_violations());
+    }
+
+    #[tokio::test]
+    async fn reports_undeclared_inputs() {
+        let container = TaskRunnerContainer::new("sandbox", "sandboxed").await;
+        let sandbox = create_task_sandbox(&container);
+
+        fs::read_to_string(container.sandbox.path().join("project/other.txt")).unwrap();
+
+        let result = sandbox.stop(&container.task).unwrap();
+
+        assert_eq!(
+            result.undeclared_inputs,
+            vec![WorkspaceRelativePathBuf::from("project/other.txt")]
+        );
+        assert!(result.undeclared_outputs.is_empty());
+    }
+
+    #[tokio::test]
+    async fn doesnt_report_declared_outputs() {
+        let container = TaskRunnerContainer::new("sandbox", "sandboxed").await;
+        let sandbox = create_task_sandbox(&container);
+
+        container
+            .sandbox
+            .create_file("project/out/nested/file.txt", "");
+        container.sandbox.create_file("project/build.log", "");
+
+        let result = sandbox.stop(&container.task).unwrap();
+
+        assert!(!result.has_violations());
+    }
+
+    #[tokio::test]
+    async fn reports_undeclared_outputs() {
+        let container = TaskRunnerContainer::new("sandbox", "sandboxed").await;
+        let sandbox = create_task_sandbox(&container);
+
+        container.sandbox.create_file("project/stray.txt", "");
+        container
+            .sandbox
+            .create_file("project/other.txt", "changed");
+
+        let result = sandbox.stop(&container.task).unwrap();
+
+        assert!(result.undeclared_inputs.is_empty());
+        assert_eq!(
+            result.undeclared_outputs,
+            vec![
+                WorkspaceRelativePathBuf::from("project/other.txt"),
+                WorkspaceRelativePathBuf::from("project/stray.txt")
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn reports_files_created_in_new_dirs() {
+        let container = TaskRunnerContainer::new("sandbox", "sandboxed").await;
+        let sandbox = create_task_sandbox(&container);
+
+        container
+            .sandbox
+            .create_file("project/dist/a/b/file.txt", "");
+
+        let result = sandbox.stop(&container.task).unwrap();
+
+        assert_eq!(
+            result.undeclared_outputs,
+            vec![WorkspaceRelativePathBuf::from("project/dist/a/b/file.txt")]
+        );
+    }
+
+    #[tokio::test]
+    async fn ignores_cache_and_vcs_dirs() {
+        let container = TaskRunnerContainer::new("sandbox", "sandboxed").await;
+        container.sandbox.create_file(".git/HEAD", "");
+        container
+            .sandbox
+            .create_file("node_modules/pkg/index.js", "");
+
+        let sandbox = create_task_sandbox(&container);
+
+        container
+            .sandbox
+            .create_file(".moon/cache/states/file.json", "");
+        container.sandbox.create_file(".git/HEAD", "changed");
+        fs::read_to_string(container.sandbox.path().join("node_modules/pkg/index.js")).unwrap();
+
+        let result = sandbox.stop(&container.task).unwrap();
+
+        assert!(!result.has_violations());
+    }
+
+    #[tokio::test]
+    async fn doesnt_trace_outside_of_project() {
+        let container = TaskRunnerContainer::new("sandbox", "sandboxed").await;
+        container.sandbox.create_file("other/file.txt", "");
+
+        let sandbox = create_task_sandbox(&container);
+
+        container.sandbox.create_file("other/file.txt", "changed");
+        container.sandbox.create_file("root.txt", "");
+
+        let result = sandbox.stop(&container.task).unwrap();
+
+        assert!(!result.has_violations());
+    }
+
+    #[tokio::test]
+    async fn sandboxed_lock_waits_for_other_tasks() {
+        let shared = TaskSandbox::lock(false).await;
+
+        assert!(
+            timeout(Duration::from_millis(100), TaskSandbox::lock(true))
+                .await
+                .is_err()
+        );
+
+        drop(shared);
+
+        let exclusive = TaskSandbox::lock(true).await;
+
+        assert!(
+            timeout(Duration::from_millis(100), TaskSandbox::lock(false))
+                .await
+                .is_err()
+        );
+
+        drop(exclusive);
+    }
+
+    #[test]
+    fn tracks_running_persistent_tasks() {
+        let persistent = TaskSandbox::lock_persistent();
+
+        assert!(TaskSandbox::is_persistent_running());
+
+        drop(persistent);
+
+        assert!(!TaskSandbox::is_persistent_running());
+    }
+}
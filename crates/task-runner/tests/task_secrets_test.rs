@@ -0,0 +1,101 @@
+mod utils;
+
+use moon_config::{FilePath, TaskSecretConfig};
+use moon_env_var::GlobalEnvBag;
+use moon_task_runner::task_secrets::resolve_task_secrets;
+use utils::*;
+
+mod task_secrets {
+    use super::*;
+
+    #[tokio::test]
+    async fn returns_empty_if_no_secrets() {
+        let container = TaskRunnerContainer::new("builder", "base").await;
+
+        let secrets =
+            resolve_task_secrets(&container.app_context, &container.project, &container.task)
+                .await
+                .unwrap();
+
+        assert!(secrets.is_empty());
+    }
+
+    #[tokio::test]
+    async fn resolves_from_env_and_files() {
+        GlobalEnvBag::instance().set("MOON_TEST_RESOLVE_SECRET", "abc123");
+
+        let container = TaskRunnerContainer::new("builder", "base").await;
+        container.sandbox.create_file("project/.key", "xyz789\r\n");
+
+        let mut task = container.task.as_ref().to_owned();
+        task.secrets.insert(
+            "FROM_ENV".into(),
+            TaskSecretConfig::from_env("MOON_TEST_RESOLVE_SECRET"),
+        );
+        task.secrets.insert(
+            "FROM_FILE".into(),
+            TaskSecretConfig::from_file(FilePath(".key".into())),
+        );
+
+        let secrets = resolve_task_secrets(&container.app_context, &container.project, &task)
+            .await
+            .unwrap();
+
+        GlobalEnvBag::instance().remove("MOON_TEST_RESOLVE_SECRET");
+
+        assert_eq!(secrets.get("FROM_ENV").unwrap(), "abc123");
+        assert_eq!(secrets.get("FROM_FILE").unwrap(), "xyz789");
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Unable to resolve secret")]
+    async fn errors_if_missing() {
+        let container = TaskRunnerContainer::new("builder", "base").await;
+
+        let mut task = container.task.as_ref().to_owned();
+        task.secrets.insert(
+            "TOKEN".into(),
+            TaskSecretConfig::from_file(FilePath(".missing".into())),
+        );
+
+        resolve_task_secrets(&container.app_context, &container.project, &task)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn skips_if_missing_and_optional() {
+        let container = TaskRunnerContainer::new("builder", "base").await;
+
+        let mut task = container.task.as_ref().to_owned();
+        task.secrets.insert(
+            "TOKEN".into(),
+            TaskSecretConfig {
+                optional: true,
+                ..TaskSecretConfig::from_env("MOON_TEST_UNKNOWN_SECRET")
+            },
+        );
+
+        let secrets = resolve_task_secrets(&container.app_context, &container.project, &task)
+            .await
+            .unwrap();
+
+        assert!(secrets.is_empty());
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "unknown_provider")]
+    async fn errors_for_unknown_provider() {
+        let container = TaskRunnerContainer::new("builder", "base").await;
+
+        let mut task = container.task.as_ref().to_owned();
+        task.secrets.insert(
+            "TOKEN".into(),
+            TaskSecretConfig::from_provider(moon_common::Id::raw("unknown_provider")),
+        );
+
+        resolve_task_secrets(&container.app_context, &container.project, &task)
+            .await
+            .unwrap();
+    }
+}
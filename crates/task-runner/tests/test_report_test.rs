@@ -0,0 +1,229 @@
+use moon_action::TestCaseStatus;
+use moon_config::{GlobPath, TaskOptionTestReportConfig, TaskTestReportFormat};
+use moon_task_runner::test_report::*;
+use starbase_sandbox::create_empty_sandbox;
+use std::time::{Duration, SystemTime};
+
+const JUNIT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites>
+  <testsuite name="math" tests="4">
+    <testcase classname="math.add" name="adds numbers" time="0.012" />
+    <testcase name="subtracts numbers" time="1.5">
+      <failure message="expected 1 to equal 2">stack trace</failure>
+    </testcase>
+    <testcase name="divides by zero">
+      <error>panic!</error>
+    </testcase>
+    <testcase name="multiplies numbers">
+      <skipped message="not implemented" />
+    </testcase>
+  </testsuite>
+</testsuites>
+"#;
+
+const TAP: &str = r#"TAP version 13
+1..5
+ok 1 - adds numbers
+not ok 2 - subtracts numbers
+  ---
+  message: 'expected 1 to equal 2'
+  duration_ms: 250
+  ...
+ok 3 - multiplies numbers # SKIP not implemented
+not ok 4 divides numbers # TODO
+    ok 1 - subtest is ignored
+ok 5
+"#;
+
+fn create_config(format: TaskTestReportFormat, path: &str) -> TaskOptionTestReportConfig {
+    TaskOptionTestReportConfig {
+        format,
+        path: GlobPath(path.into()),
+    }
+}
+
+mod junit {
+    use super::*;
+
+    #[test]
+    fn parses_test_cases() {
+        let report = parse_junit(JUNIT).unwrap();
+
+        assert_eq!(report.total(), 4);
+        assert_eq!(report.passed, 1);
+        assert_eq!(report.failed, 2);
+        assert_eq!(report.skipped, 1);
+    }
+
+    #[test]
+    fn inherits_suite_name() {
+        let report = parse_junit(JUNIT).unwrap();
+
+        assert_eq!(report.tests[0].suite.as_deref(), Some("math.add"));
+        assert_eq!(report.tests[1].suite.as_deref(), Some("math"));
+        assert_eq!(report.tests[1].get_label(), "math › subtracts numbers");
+    }
+
+    #[test]
+    fn extracts_durations_and_messages() {
+        let report = parse_junit(JUNIT).unwrap();
+
+        assert_eq!(report.tests[1].duration, Some(Duration::from_millis(1500)));
+        assert_eq!(
+            report.tests[1].message.as_deref(),
+            Some("expected 1 to equal 2")
+        );
+        assert_eq!(report.tests[2].message.as_deref(), Some("panic!"));
+        assert_eq!(report.tests[3].status, TestCaseStatus::Skipped);
+        assert_eq!(report.tests[3].message.as_deref(), Some("not implemented"));
+    }
+
+    #[test]
+    fn supports_single_suite_root() {
+        let report = parse_junit(
+            r#"<testsuite name="root"><testcase name="a" /><testcase name="b" /></testsuite>"#,
+        )
+        .unwrap();
+
+        assert_eq!(report.passed, 2);
+        assert_eq!(report.tests[0].suite.as_deref(), Some("root"));
+    }
+
+    #[test]
+    fn errors_for_invalid_xml() {
+        assert!(parse_junit("<testsuite>").is_err());
+    }
+}
+
+mod tap {
+    use super::*;
+
+    #[test]
+    fn parses_test_points() {
+        let report = parse_tap(TAP);
+
+        assert_eq!(report.total(), 5);
+        assert_eq!(report.passed, 2);
+        assert_eq!(report.failed, 1);
+        assert_eq!(report.skipped, 2);
+    }
+
+    #[test]
+    fn extracts_names_and_directives() {
+        let report = parse_tap(TAP);
+
+        assert_eq!(report.tests[0].name, "adds numbers");
+        assert_eq!(report.tests[2].name, "multiplies numbers");
+        assert_eq!(report.tests[2].message.as_deref(), Some("not implemented"));
+        assert_eq!(report.tests[3].name, "divides numbers");
+        assert_eq!(report.tests[3].message, None);
+        assert_eq!(report.tests[4].name, "");
+    }
+
+    #[test]
+    fn extracts_yaml_diagnostics() {
+        let report = parse_tap(TAP);
+
+        assert_eq!(
+            report.tests[1].message.as_deref(),
+            Some("expected 1 to equal 2")
+        );
+        assert_eq!(report.tests[1].duration, Some(Duration::from_millis(250)));
+    }
+
+    #[test]
+    fn supports_block_messages() {
+        let report = parse_tap("not ok 1 - fails\n  ---\n  message: |\n    line 1\n    line 2\n  ...\n");
+
+        assert_eq!(report.tests[0].message.as_deref(), Some("line 1\nline 2"));
+    }
+}
+
+mod load {
+    use super::*;
+
+    #[test]
+    fn returns_none_if_no_files() {
+        let sandbox = create_empty_sandbox();
+
+        let report = load_test_report(
+            &create_config(TaskTestReportFormat::Junit, "junit.xml"),
+            sandbox.path(),
+            &sandbox.path().join("project"),
+            None,
+        )
+        .unwrap();
+
+        assert!(report.is_none());
+    }
+
+    #[test]
+    fn loads_from_project_root() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file("project/junit.xml", JUNIT);
+
+        let report = load_test_report(
+            &create_config(TaskTestReportFormat::Junit, "junit.xml"),
+            sandbox.path(),
+            &sandbox.path().join("project"),
+            None,
+        )
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(report.total(), 4);
+    }
+
+    #[test]
+    fn loads_from_workspace_root() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file("reports/results.tap", TAP);
+
+        let report = load_test_report(
+            &create_config(TaskTestReportFormat::Tap, "/reports/results.tap"),
+            sandbox.path(),
+            &sandbox.path().join("project"),
+            None,
+        )
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(report.total(), 5);
+    }
+
+    #[test]
+    fn merges_multiple_files_from_glob() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file("project/reports/a.xml", JUNIT);
+        sandbox.create_file("project/reports/b.xml", JUNIT);
+        sandbox.create_file("project/reports/invalid.xml", "<testsuite>");
+
+        let report = load_test_report(
+            &create_config(TaskTestReportFormat::Junit, "reports/*.xml"),
+            sandbox.path(),
+            &sandbox.path().join("project"),
+            None,
+        )
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(report.total(), 8);
+        assert_eq!(report.failed, 4);
+    }
+
+    #[test]
+    fn ignores_stale_files() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file("project/junit.xml", JUNIT);
+
+        let report = load_test_report(
+            &create_config(TaskTestReportFormat::Junit, "junit.xml"),
+            sandbox.path(),
+            &sandbox.path().join("project"),
+            Some(SystemTime::now() + Duration::from_secs(60)),
+        )
+        .unwrap();
+
+        assert!(report.is_none());
+    }
+}
@@ -3,9 +3,11 @@
 use moon_action::{ActionNode, RunTaskNode};
 use moon_action_context::ActionContext;
 use moon_app_context::AppContext;
+use moon_cache::{OutputManifest, OutputManifestFile};
 use moon_platform::{PlatformManager, Runtime};
 use moon_process::Command;
 use moon_project::Project;
+use moon_remote::create_digest;
 use moon_task::Task;
 use moon_task_runner::TaskRunner;
 use moon_task_runner::command_builder::CommandBuilder;
@@ -13,10 +15,10 @@ use moon_task_runner::command_executor::CommandExecutor;
 use moon_task_runner::output_archiver::OutputArchiver;
 use moon_task_runner::output_hydrater::OutputHydrater;
 use moon_test_utils2::{WorkspaceGraph, WorkspaceMocker};
-use starbase_archive::Archiver;
 use starbase_sandbox::{Sandbox, create_sandbox};
+use starbase_utils::json;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 pub fn create_node(task: &Task) -> ActionNode {
@@ -133,37 +135,59 @@ impl TaskRunnerContainer {
     }
 
     pub fn pack_archive(&self) -> PathBuf {
-        let sandbox = &self.sandbox;
-        let file = sandbox.path().join(".moon/cache/outputs/hash123.tar.gz");
+        let hash_engine = &self.app_context.cache_engine.hash;
+        let mut manifest = OutputManifest::default();
 
-        let out = format!(
-            ".moon/cache/states/{}/{}/stdout.log",
-            self.project_id, self.task_id,
-        );
+        let save_blob = |content: &str| {
+            let digest = create_digest(content.as_bytes());
 
-        let err = format!(
-            ".moon/cache/states/{}/{}/stderr.log",
-            self.project_id, self.task_id,
-        );
+            hash_engine
+                .save_blob(&digest.hash, content.as_bytes())
+                .unwrap();
 
-        let txt = format!("{}/file.txt", self.project_id);
+            digest
+        };
 
-        sandbox.create_file(&out, "stdout");
-        sandbox.create_file(&err, "stderr");
-        sandbox.create_file(&txt, "content");
+        manifest.stdout_hash = Some(save_blob("stdout").hash);
+        manifest.stderr_hash = Some(save_blob("stderr").hash);
 
-        let mut archiver = Archiver::new(sandbox.path(), &file);
-        archiver.add_source_file(&out, None);
-        archiver.add_source_file(&err, None);
-        archiver.add_source_file(&txt, None);
-        archiver.pack_from_ext().unwrap();
+        let digest = save_blob("content");
 
-        // Remove sources so we can test unpacking
-        fs::remove_file(sandbox.path().join(out)).unwrap();
-        fs::remove_file(sandbox.path().join(err)).unwrap();
-        fs::remove_file(sandbox.path().join(txt)).unwrap();
+        manifest.files.push(OutputManifestFile {
+            executable: false,
+            hash: digest.hash,
+            path: format!("{}/file.txt", self.project_id).into(),
+            size: digest.size_bytes as u64,
+        });
 
-        file
+        hash_engine
+            .save_outputs_manifest("hash123", &manifest)
+            .unwrap();
+
+        hash_engine.get_outputs_manifest_path("hash123")
+    }
+
+    pub fn unpack_archive(&self, file: &Path, dir: &Path) {
+        let hash_engine = &self.app_context.cache_engine.hash;
+        let manifest: OutputManifest = json::read_file(file).unwrap();
+        let state_dir = format!(".moon/cache/states/{}/{}", self.project_id, self.task_id);
+
+        let copy_blob = |hash: &str, path: PathBuf| {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::copy(hash_engine.get_blob_path(hash), path).unwrap();
+        };
+
+        for file in &manifest.files {
+            copy_blob(&file.hash, file.path.to_logical_path(dir));
+        }
+
+        if let Some(hash) = &manifest.stderr_hash {
+            copy_blob(hash, dir.join(&state_dir).join("stderr.log"));
+        }
+
+        if let Some(hash) = &manifest.stdout_hash {
+            copy_blob(hash, dir.join(&state_dir).join("stdout.log"));
+        }
     }
 
     async fn internal_create_command(
@@ -2,8 +2,8 @@ mod task;
 mod task_options;
 
 pub use moon_config::{
-    TaskConfig, TaskOptionAffectedFiles, TaskOptionEnvFile, TaskOptionRunInCI, TaskOptionsConfig,
-    TaskType,
+    TaskConfig, TaskOptionAffectedFiles, TaskOptionEnvFile, TaskOptionRunInCI, TaskOptionSandbox,
+    TaskOptionTestReportConfig, TaskOptionsConfig, TaskTestReportFormat, TaskType,
 };
 pub use moon_target::*;
 pub use task::*;
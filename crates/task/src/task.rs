@@ -4,7 +4,8 @@ use moon_common::{
     path::{PathExt, ProjectRelativePathBuf, WorkspaceRelativePathBuf},
 };
 use moon_config::{
-    InputPath, OutputPath, PlatformType, TaskDependencyConfig, TaskPreset, TaskType,
+    InputPath, OutputPath, PlatformType, TaskDependencyConfig, TaskPreset, TaskSecretConfig,
+    TaskType,
 };
 use moon_feature_flags::glob_walk_with_options;
 use moon_target::Target;
@@ -77,6 +78,9 @@ cacheable!(
         #[serde(skip_serializing_if = "Option::is_none")]
         pub script: Option<String>,
 
+        #[serde(skip_serializing_if = "FxHashMap::is_empty")]
+        pub secrets: FxHashMap<String, TaskSecretConfig>,
+
         pub state: TaskState,
 
         pub target: Target,
@@ -141,6 +145,22 @@ impl Task {
             .unwrap_or_else(|| format!("{} {}", self.command, self.args.join(" ")))
     }
 
+    /// Return the names of all secrets, and the host environment variables
+    /// they are sourced from. These should never be substituted or hashed.
+    pub fn get_secret_env_names(&self) -> FxHashSet<String> {
+        let mut names = FxHashSet::default();
+
+        for (name, secret) in &self.secrets {
+            names.insert(name.to_owned());
+
+            if let Some(env) = &secret.env {
+                names.insert(env.to_owned());
+            }
+        }
+
+        names
+    }
+
     /// Return a list of all workspace-relative input files.
     pub fn get_input_files(
         &self,
@@ -285,6 +305,7 @@ impl Default for Task {
             platform: PlatformType::default(),
             preset: None,
             script: None,
+            secrets: FxHashMap::default(),
             state: TaskState::default(),
             target: Target::default(),
             toolchains: vec![Id::raw("system")],
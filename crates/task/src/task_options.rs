@@ -1,8 +1,10 @@
-use moon_common::cacheable;
+use moon_common::{Id, cacheable};
 use moon_config::{
     InputPath, TaskMergeStrategy, TaskOperatingSystem, TaskOptionAffectedFiles, TaskOptionRunInCI,
-    TaskOutputStyle, TaskPriority, TaskUnixShell, TaskWindowsShell,
+    TaskOptionSandbox, TaskOptionTestReportConfig, TaskOutputStyle, TaskPriority, TaskUnixShell,
+    TaskWindowsShell,
 };
+use std::collections::BTreeMap;
 
 cacheable!(
     #[derive(Clone, Debug, Eq, PartialEq)]
@@ -55,6 +57,9 @@ cacheable!(
 
         pub priority: TaskPriority,
 
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub resources: Option<BTreeMap<Id, u32>>,
+
         pub retry_count: u8,
 
         pub run_deps_in_parallel: bool,
@@ -64,9 +69,15 @@ cacheable!(
 
         pub run_from_workspace_root: bool,
 
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub sandbox: Option<TaskOptionSandbox>,
+
         #[serde(skip_serializing_if = "Option::is_none")]
         pub shell: Option<bool>,
 
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub test_report: Option<TaskOptionTestReportConfig>,
+
         #[serde(skip_serializing_if = "Option::is_none")]
         pub timeout: Option<u64>,
 
@@ -101,11 +112,14 @@ impl Default for TaskOptions {
             output_style: None,
             persistent: false,
             priority: TaskPriority::Normal,
+            resources: None,
             retry_count: 0,
             run_deps_in_parallel: true,
             run_in_ci: TaskOptionRunInCI::Affected,
             run_from_workspace_root: false,
+            sandbox: None,
             shell: None,
+            test_report: None,
             timeout: None,
             unix_shell: None,
             windows_shell: None,
@@ -5,6 +5,7 @@ use moon_common::{Id, path::WorkspaceRelativePathBuf};
 use moon_config::*;
 use moon_console::{Console, MoonReporter};
 use moon_env::MoonEnvironment;
+use moon_extension_plugin::ExtensionRegistry;
 use moon_platform::PlatformManager;
 use moon_plugin::PluginHostData;
 use moon_project_builder::*;
@@ -322,6 +323,7 @@ impl WorkspaceMocker {
             cli_version: Version::parse(env!("CARGO_PKG_VERSION")).unwrap(),
             cache_engine: Arc::new(self.mock_cache_engine()),
             console: Arc::new(self.mock_console()),
+            extension_registry: Arc::new(self.mock_extension_registry()),
             toolchain_registry: Arc::new(self.mock_toolchain_registry()),
             vcs: Arc::new(self.mock_vcs_adapter()),
             toolchain_config: Arc::new(self.toolchain_config.clone()),
@@ -341,6 +343,16 @@ impl WorkspaceMocker {
         console
     }
 
+    pub fn mock_extension_registry(&self) -> ExtensionRegistry {
+        let mut registry = ExtensionRegistry::new(PluginHostData {
+            moon_env: Arc::new(self.moon_env.clone()),
+            proto_env: Arc::new(self.proto_env.clone()),
+            workspace_graph: Arc::new(OnceLock::new()),
+        });
+        registry.inherit_configs(&self.workspace_config.extensions);
+        registry
+    }
+
     pub async fn mock_platform_manager(&self) -> PlatformManager {
         generate_platform_manager(
             &self.workspace_root,
@@ -2,9 +2,10 @@ use crate::toolchain_plugin::ToolchainPlugin;
 use crate::toolchain_registry::{CallResult, ToolchainRegistry};
 use moon_common::Id;
 use moon_pdk_api::{
-    ConfigSchema, DefineDockerMetadataInput, DefineDockerMetadataOutput, ExtendProjectInput,
-    ExtendProjectOutput, HashTaskContentsInput, ScaffoldDockerInput, ScaffoldDockerOutput,
-    SyncOutput, SyncProjectInput, SyncWorkspaceInput, TeardownToolchainInput,
+    CheckProjectConstraintsInput, CheckProjectConstraintsOutput, ConfigSchema,
+    DefineDockerMetadataInput, DefineDockerMetadataOutput, ExtendProjectInput, ExtendProjectOutput,
+    HashTaskContentsInput, ScaffoldDockerInput, ScaffoldDockerOutput, SyncOutput, SyncProjectInput,
+    SyncWorkspaceInput, TeardownToolchainInput,
 };
 use rustc_hash::FxHashMap;
 use starbase_utils::json::JsonValue;
@@ -50,6 +51,23 @@ impl ToolchainRegistry {
         Ok(detected)
     }
 
+    pub async fn check_project_constraints_many<InFn>(
+        &self,
+        ids: Vec<&Id>,
+        input_factory: InFn,
+    ) -> miette::Result<Vec<CallResult<CheckProjectConstraintsOutput>>>
+    where
+        InFn: Fn(&ToolchainRegistry, &ToolchainPlugin) -> CheckProjectConstraintsInput,
+    {
+        self.call_func_all(
+            "check_project_constraints",
+            ids,
+            input_factory,
+            |toolchain, input| async move { toolchain.check_project_constraints(input).await },
+        )
+        .await
+    }
+
     pub async fn define_toolchain_config_all(
         &self,
     ) -> miette::Result<FxHashMap<String, ConfigSchema>> {
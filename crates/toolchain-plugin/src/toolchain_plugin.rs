@@ -108,6 +108,19 @@ impl ToolchainPlugin {
         Ok(output)
     }
 
+    #[instrument(skip(self))]
+    pub async fn check_project_constraints(
+        &self,
+        input: CheckProjectConstraintsInput,
+    ) -> miette::Result<CheckProjectConstraintsOutput> {
+        let output: CheckProjectConstraintsOutput = self
+            .plugin
+            .cache_func_with("check_project_constraints", input)
+            .await?;
+
+        Ok(output)
+    }
+
     #[instrument(skip(self))]
     pub async fn define_docker_metadata(
         &self,
@@ -1,11 +1,14 @@
 use moon_common::{color, consts, is_docker, path};
-use moon_config::{VcsConfig, VcsHookFormat};
-use moon_vcs::BoxedVcs;
+use moon_config::{VcsConfig, VcsHookFormat, VcsManager};
+use moon_vcs::{BoxedVcs, HgFlavor};
 use rustc_hash::FxHashMap;
 use starbase_utils::fs;
 use std::path::{Path, PathBuf};
 use tracing::{debug, instrument, warn};
 
+const HG_CONFIG_START: &str = "# moon:hooks:start";
+const HG_CONFIG_END: &str = "# moon:hooks:end";
+
 pub enum ShellType {
     Bash,
     Pwsh,
@@ -61,6 +64,10 @@ impl<'app> HooksGenerator<'app> {
             }
         }
 
+        if let Some(config_path) = self.get_hg_config_path(&hooks_dir) {
+            self.sync_to_hg_config(&config_path, vec![])?;
+        }
+
         debug!(dir = ?self.output_dir, "Removing local hooks");
 
         fs::remove_dir_all(&self.output_dir)?;
@@ -76,12 +83,12 @@ impl<'app> HooksGenerator<'app> {
         if !self.vcs.is_enabled() {
             if is_docker() {
                 warn!(
-                    "In a Docker container/image and .git does not exist, not generating {} hooks",
+                    "In a Docker container/image and the repository does not exist, not generating {} hooks",
                     self.config.manager
                 );
             } else {
                 debug!(
-                    "Not generating {} hooks as .git does not exist",
+                    "Not generating {} hooks as the repository does not exist",
                     self.config.manager
                 );
             }
@@ -141,10 +148,13 @@ impl<'app> HooksGenerator<'app> {
     async fn sync_to_vcs(&self, hooks: FxHashMap<&'app String, PathBuf>) -> miette::Result<()> {
         let hooks_dir = self.vcs.get_hooks_dir().await?;
         let repo_root = self.vcs.get_repository_root().await?;
+        let mut external_paths = vec![];
 
         for (hook_name, internal_path) in hooks {
             let external_path = hooks_dir.join(hook_name);
 
+            external_paths.push((hook_name.as_str(), external_path.clone()));
+
             let external_command = match internal_path.strip_prefix(&repo_root) {
                 Ok(rel) => PathBuf::from(".").join(rel),
                 _ => internal_path.clone(),
@@ -192,6 +202,93 @@ impl<'app> HooksGenerator<'app> {
             }
         }
 
+        if let Some(config_path) = self.get_hg_config_path(&hooks_dir) {
+            self.sync_to_hg_config(&config_path, external_paths)?;
+        }
+
+        Ok(())
+    }
+
+    fn get_hg_config_path(&self, hooks_dir: &Path) -> Option<PathBuf> {
+        let flavor = match self.config.manager {
+            VcsManager::Mercurial => HgFlavor::Mercurial,
+            VcsManager::Sapling => HgFlavor::Sapling,
+            _ => return None,
+        };
+
+        hooks_dir
+            .parent()
+            .map(|dir| dir.join(flavor.get_config_file_name()))
+    }
+
+    // Mercurial and Sapling don't execute files within a hooks directory,
+    // so each hook must be registered in the repository config file.
+    // https://www.mercurial-scm.org/doc/hgrc.5.html#hooks
+    fn sync_to_hg_config(
+        &self,
+        config_path: &Path,
+        mut hooks: Vec<(&str, PathBuf)>,
+    ) -> miette::Result<()> {
+        let existing = if config_path.exists() {
+            fs::read_file(config_path)?
+        } else {
+            String::new()
+        };
+
+        // Remove the previously managed section
+        let mut lines = vec![];
+        let mut in_section = false;
+
+        for line in existing.lines() {
+            if line == HG_CONFIG_START {
+                in_section = true;
+            } else if line == HG_CONFIG_END {
+                in_section = false;
+            } else if !in_section {
+                lines.push(line.to_owned());
+            }
+        }
+
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+
+        if !hooks.is_empty() {
+            hooks.sort_by(|a, d| a.0.cmp(d.0));
+
+            if !lines.is_empty() {
+                lines.push(String::new());
+            }
+
+            lines.push(HG_CONFIG_START.into());
+            lines.push("[hooks]".into());
+
+            for (hook_name, hook_path) in hooks {
+                lines.push(format!(
+                    "{hook_name}.moon = {}",
+                    path::to_virtual_string(hook_path)?
+                ));
+            }
+
+            lines.push(HG_CONFIG_END.into());
+        }
+
+        debug!(
+            config_file = ?config_path,
+            "Syncing {} hooks to repository config",
+            self.config.manager,
+        );
+
+        if lines.is_empty() {
+            if config_path.exists() {
+                fs::write_file(config_path, "")?;
+            }
+        } else {
+            lines.push(String::new());
+
+            fs::write_file(config_path, lines.join("\n"))?;
+        }
+
         Ok(())
     }
 
@@ -1,5 +1,5 @@
-use moon_config::VcsConfig;
-use moon_vcs::{BoxedVcs, Git};
+use moon_config::{VcsConfig, VcsManager};
+use moon_vcs::{BoxedVcs, Git, Hg, HgFlavor};
 use moon_vcs_hooks::HooksGenerator;
 use rustc_hash::FxHashMap;
 use starbase_sandbox::{assert_snapshot, create_empty_sandbox};
@@ -99,6 +99,101 @@ async fn cleans_up_hooks() {
     assert!(!local_hooks.exists());
 }
 
+mod mercurial {
+    use super::*;
+
+    fn load_hg(root: &Path) -> BoxedVcs {
+        Box::new(Hg::load(root, "default", &[], HgFlavor::Mercurial).unwrap())
+    }
+
+    fn create_hg_config() -> VcsConfig {
+        VcsConfig {
+            hooks: FxHashMap::from_iter([
+                ("precommit".into(), vec!["moon run :lint".into()]),
+                ("preoutgoing".into(), vec!["moon check --all".into()]),
+            ]),
+            manager: VcsManager::Mercurial,
+            ..VcsConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn registers_hooks_in_config() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file(".hg/hgrc", "[paths]\ndefault = https://example.com/repo\n");
+
+        HooksGenerator::new(
+            &load_hg(sandbox.path()),
+            &create_hg_config(),
+            sandbox.path(),
+        )
+        .generate()
+        .await
+        .unwrap();
+
+        assert!(sandbox.path().join(".hg/hooks/precommit").exists());
+        assert!(sandbox.path().join(".hg/hooks/preoutgoing").exists());
+
+        let config = fs::read_to_string(sandbox.path().join(".hg/hgrc")).unwrap();
+
+        assert!(config.starts_with("[paths]\ndefault = https://example.com/repo\n"));
+        assert!(config.contains("[hooks]\n"));
+        assert!(config.contains("precommit.moon = "));
+        assert!(config.contains("preoutgoing.moon = "));
+    }
+
+    #[tokio::test]
+    async fn doesnt_duplicate_hooks_in_config() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file(".hg/hgrc", "");
+
+        for _ in 0..2 {
+            HooksGenerator::new(
+                &load_hg(sandbox.path()),
+                &create_hg_config(),
+                sandbox.path(),
+            )
+            .generate()
+            .await
+            .unwrap();
+        }
+
+        let config = fs::read_to_string(sandbox.path().join(".hg/hgrc")).unwrap();
+
+        assert_eq!(config.matches("precommit.moon = ").count(), 1);
+    }
+
+    #[tokio::test]
+    async fn removes_hooks_from_config() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file(".hg/hgrc", "[ui]\nusername = moon\n");
+
+        HooksGenerator::new(
+            &load_hg(sandbox.path()),
+            &create_hg_config(),
+            sandbox.path(),
+        )
+        .generate()
+        .await
+        .unwrap();
+
+        HooksGenerator::new(
+            &load_hg(sandbox.path()),
+            &create_hg_config(),
+            sandbox.path(),
+        )
+        .cleanup()
+        .await
+        .unwrap();
+
+        assert!(!sandbox.path().join(".hg/hooks/precommit").exists());
+        assert_eq!(
+            fs::read_to_string(sandbox.path().join(".hg/hgrc")).unwrap(),
+            "[ui]\nusername = moon\n"
+        );
+    }
+}
+
 #[cfg(unix)]
 mod unix {
     use super::*;
@@ -554,6 +554,10 @@ impl Git {
 
 #[async_trait]
 impl Vcs for Git {
+    fn clear_cache(&self) {
+        self.process.clear();
+    }
+
     async fn get_local_branch(&self) -> miette::Result<Arc<String>> {
         if self.is_version_supported(">=2.22.0").await? {
             return self.process.run(["branch", "--show-current"], true).await;
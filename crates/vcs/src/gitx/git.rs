@@ -141,6 +141,10 @@ impl Gitx {
 
 #[async_trait]
 impl Vcs for Gitx {
+    fn clear_cache(&self) {
+        self.get_process().clear();
+    }
+
     async fn get_local_branch(&self) -> miette::Result<Arc<String>> {
         if self.is_version_supported(">=2.22.0").await? {
             return self
@@ -0,0 +1,539 @@
+use crate::process_cache::ProcessCache;
+use crate::touched_files::TouchedFiles;
+use crate::vcs::Vcs;
+use crate::vcs_utils::*;
+use async_trait::async_trait;
+use miette::{Diagnostic, IntoDiagnostic};
+use moon_common::path::{RelativePath, RelativePathBuf, WorkspaceRelativePathBuf};
+use moon_common::{Style, Stylize};
+use moon_process::Command;
+use semver::Version;
+use serde::Deserialize;
+use starbase_utils::fs;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::ffi::OsStr;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::{debug, instrument};
+
+// Requirements that are added to a repository when cloned with partial history
+const SHALLOW_REQUIREMENTS: [&str; 3] =
+    ["exp-remotefilelog-repo-req-1", "remotefilelog", "shallow"];
+
+#[derive(Error, Debug, Diagnostic)]
+pub enum HgError {
+    #[diagnostic(code(hg::invalid_version))]
+    #[error("Invalid or unsupported {flavor} version.")]
+    InvalidVersion {
+        flavor: HgFlavor,
+        #[source]
+        error: Box<semver::Error>,
+    },
+
+    #[diagnostic(code(hg::ignore::load_invalid))]
+    #[error("Failed to load and parse {}.", .file.style(Style::File))]
+    IgnoreLoadFailed {
+        file: String,
+        #[source]
+        error: Box<ignore::Error>,
+    },
+
+    #[diagnostic(code(hg::repository::extract_slug))]
+    #[error("Failed to extract a repository slug from {flavor} paths.")]
+    ExtractRepoSlugFailed { flavor: HgFlavor },
+}
+
+/// Mercurial and Sapling share the same CLI and data model,
+/// and only differ in binary and directory names.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HgFlavor {
+    Mercurial,
+    Sapling,
+}
+
+impl HgFlavor {
+    pub fn get_bin_name(&self) -> &'static str {
+        match self {
+            Self::Mercurial => "hg",
+            Self::Sapling => "sl",
+        }
+    }
+
+    pub fn get_config_file_name(&self) -> &'static str {
+        match self {
+            Self::Mercurial => "hgrc",
+            Self::Sapling => "config",
+        }
+    }
+
+    pub fn get_dir_name(&self) -> &'static str {
+        match self {
+            Self::Mercurial => ".hg",
+            Self::Sapling => ".sl",
+        }
+    }
+}
+
+impl fmt::Display for HgFlavor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Mercurial => "mercurial",
+                Self::Sapling => "sapling",
+            }
+        )
+    }
+}
+
+#[derive(Deserialize)]
+struct HgFileEntry {
+    path: String,
+}
+
+#[derive(Deserialize)]
+struct HgStatusEntry {
+    path: String,
+    status: String,
+}
+
+#[derive(Debug)]
+pub struct Hg {
+    /// Ignore rules derived from a root `.hgignore` or `.gitignore` file.
+    ignore: Option<HgIgnore>,
+
+    /// Default bookmark or branch name.
+    pub default_branch: Arc<String>,
+
+    /// Mercurial or Sapling.
+    pub flavor: HgFlavor,
+
+    /// Root of the `.hg` or `.sl` directory.
+    pub hg_root: PathBuf,
+
+    /// Run and cache `hg` or `sl` commands.
+    pub process: ProcessCache,
+
+    /// List of remotes to use as merge candidates.
+    pub remote_candidates: Vec<String>,
+
+    /// Root of the repository that contains `.hg` or `.sl`.
+    pub repository_root: PathBuf,
+
+    /// Path between the repository and workspace root.
+    pub root_prefix: Option<RelativePathBuf>,
+}
+
+impl Hg {
+    pub fn load<R: AsRef<Path>, B: AsRef<str>>(
+        workspace_root: R,
+        default_branch: B,
+        remote_candidates: &[String],
+        flavor: HgFlavor,
+    ) -> miette::Result<Hg> {
+        debug!("Using {} as a version control system", flavor);
+
+        let workspace_root = workspace_root.as_ref();
+        let (repository_root, hg_root) =
+            find_repository_root(workspace_root, flavor.get_dir_name());
+
+        // Sapling uses .gitignore, while Mercurial uses .hgignore
+        let ignore = match flavor {
+            HgFlavor::Mercurial => {
+                load_hgignore(&repository_root).map_err(|error| HgError::IgnoreLoadFailed {
+                    file: ".hgignore".into(),
+                    error: Box::new(error),
+                })?
+            }
+            HgFlavor::Sapling => load_gitignore(&repository_root)
+                .map_err(|error| HgError::IgnoreLoadFailed {
+                    file: ".gitignore".into(),
+                    error: Box::new(error),
+                })?
+                .map(HgIgnore::from),
+        };
+
+        Ok(Hg {
+            default_branch: Arc::new(default_branch.as_ref().to_owned()),
+            flavor,
+            hg_root,
+            ignore,
+            process: ProcessCache::new(flavor.get_bin_name(), workspace_root),
+            remote_candidates: remote_candidates.to_owned(),
+            root_prefix: create_root_prefix(workspace_root, &repository_root),
+            repository_root,
+        })
+    }
+
+    // Always run from the repository root, so that paths in the
+    // output are consistently relative from the repository root
+    fn create_command<I, A>(&self, args: I) -> Command
+    where
+        I: IntoIterator<Item = A>,
+        A: AsRef<OsStr>,
+    {
+        self.process
+            .create_command_in_cwd(args, &self.repository_root)
+    }
+
+    async fn run_json<T: for<'de> Deserialize<'de>>(
+        &self,
+        mut command: Command,
+    ) -> miette::Result<Vec<T>> {
+        // Commands like `files` fail when there are no matches
+        command.set_error_on_nonzero(false);
+
+        let output = self.process.run_command(command, true).await?;
+
+        if output.is_empty() || !output.starts_with('[') {
+            return Ok(vec![]);
+        }
+
+        serde_json::from_str(&output).into_diagnostic()
+    }
+
+    // Limit paths to the workspace when not in the repository root
+    fn create_path_pattern(&self, dir: &str) -> Option<String> {
+        let path = match &self.root_prefix {
+            Some(prefix) => prefix.join(dir),
+            None => RelativePathBuf::from(dir),
+        };
+
+        if path.as_str().is_empty() || path.as_str() == "." {
+            None
+        } else {
+            Some(format!("path:{path}"))
+        }
+    }
+
+    async fn get_merge_base(&self, base: &str, head: &str) -> Option<Arc<String>> {
+        let mut candidates = vec![base.to_owned()];
+
+        for remote in &self.remote_candidates {
+            candidates.push(format!("{remote}/{base}"));
+        }
+
+        for candidate in candidates {
+            if let Ok(node) = self
+                .process
+                .run_command(
+                    self.create_command([
+                        "log",
+                        "--rev",
+                        &format!("ancestor({candidate}, {head})"),
+                        "--template",
+                        "{node}",
+                    ]),
+                    true,
+                )
+                .await
+            {
+                if !node.is_empty() {
+                    return Some(node);
+                }
+            }
+        }
+
+        None
+    }
+
+    #[instrument(skip(self))]
+    async fn exec_status(&self, args: &[&str]) -> miette::Result<TouchedFiles> {
+        let mut command = self.create_command(["status", "--template", "json"]);
+        command.args(args);
+
+        if let Some(pattern) = self.create_path_pattern("") {
+            command.arg(pattern);
+        }
+
+        let mut touched_files = TouchedFiles::default();
+
+        for entry in self.run_json::<HgStatusEntry>(command).await? {
+            let Some(file) = to_workspace_relative_path(self.root_prefix.as_ref(), &entry.path)
+            else {
+                continue;
+            };
+
+            // There's no staging area, so all tracked changes
+            // will be included in the next commit
+            match entry.status.as_str() {
+                "A" => {
+                    touched_files.added.insert(file.clone());
+                    touched_files.staged.insert(file);
+                }
+                "M" => {
+                    touched_files.modified.insert(file.clone());
+                    touched_files.staged.insert(file);
+                }
+                "R" => {
+                    touched_files.deleted.insert(file.clone());
+                    touched_files.staged.insert(file);
+                }
+                // Missing, deleted without `hg remove`
+                "!" => {
+                    touched_files.deleted.insert(file.clone());
+                    touched_files.unstaged.insert(file);
+                }
+                "?" => {
+                    touched_files.untracked.insert(file);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(touched_files)
+    }
+
+    async fn run_template(&self, revision: &str, template: &str) -> miette::Result<Arc<String>> {
+        self.process
+            .run_command(
+                self.create_command(["log", "--rev", revision, "--template", template]),
+                true,
+            )
+            .await
+    }
+}
+
+// Translate Git's `HEAD` (the default head of affected checks) into the
+// working directory's parent revision, for example, `HEAD~2` into `.~2`.
+fn translate_revision(revision: &str) -> Cow<'_, str> {
+    match parse_head_revision(revision) {
+        Some(0) => Cow::Borrowed("."),
+        Some(depth) => Cow::Owned(format!(".~{depth}")),
+        None => Cow::Borrowed(revision),
+    }
+}
+
+#[async_trait]
+impl Vcs for Hg {
+    fn clear_cache(&self) {
+        self.process.clear();
+    }
+
+    async fn get_local_branch(&self) -> miette::Result<Arc<String>> {
+        let bookmark = self.run_template(".", "{activebookmark}").await?;
+
+        // Sapling doesn't support named branches
+        if bookmark.is_empty() && self.flavor == HgFlavor::Mercurial {
+            return self
+                .process
+                .run_command(self.create_command(["branch"]), true)
+                .await;
+        }
+
+        Ok(bookmark)
+    }
+
+    async fn get_local_branch_revision(&self) -> miette::Result<Arc<String>> {
+        self.run_template(".", "{node}").await
+    }
+
+    async fn get_default_branch(&self) -> miette::Result<Arc<String>> {
+        Ok(self.default_branch.clone())
+    }
+
+    async fn get_default_branch_revision(&self) -> miette::Result<Arc<String>> {
+        self.run_template(&self.default_branch, "{node}").await
+    }
+
+    #[instrument(skip_all)]
+    async fn get_file_hashes(
+        &self,
+        files: &[WorkspaceRelativePathBuf],
+        allow_ignored: bool,
+    ) -> miette::Result<BTreeMap<WorkspaceRelativePathBuf, String>> {
+        hash_files(&self.process.workspace_root, files, |file| {
+            allow_ignored || !self.is_ignored(file)
+        })
+    }
+
+    #[instrument(skip(self))]
+    async fn get_file_tree(
+        &self,
+        dir: &RelativePath,
+    ) -> miette::Result<Vec<WorkspaceRelativePathBuf>> {
+        let pattern = self.create_path_pattern(dir.as_str());
+
+        // Tracked files
+        let mut files_command = self.create_command(["files", "--template", "json"]);
+
+        // Untracked files
+        let mut status_command = self.create_command(["status", "--unknown", "--template", "json"]);
+
+        if let Some(pattern) = pattern {
+            files_command.arg(&pattern);
+            status_command.arg(&pattern);
+        }
+
+        let mut paths = vec![];
+
+        for path in self
+            .run_json::<HgFileEntry>(files_command)
+            .await?
+            .into_iter()
+            .map(|entry| entry.path)
+            .chain(
+                self.run_json::<HgStatusEntry>(status_command)
+                    .await?
+                    .into_iter()
+                    .map(|entry| entry.path),
+            )
+        {
+            if let Some(file) = to_workspace_relative_path(self.root_prefix.as_ref(), &path) {
+                paths.push(file);
+            }
+        }
+
+        paths.sort();
+        paths.dedup();
+
+        Ok(paths)
+    }
+
+    async fn get_hooks_dir(&self) -> miette::Result<PathBuf> {
+        // Hooks are not files, but are configured in the repository
+        // config file, which references scripts in this directory
+        Ok(self.hg_root.join("hooks"))
+    }
+
+    async fn get_repository_root(&self) -> miette::Result<PathBuf> {
+        Ok(self.repository_root.clone())
+    }
+
+    async fn get_repository_slug(&self) -> miette::Result<Arc<String>> {
+        use git_url_parse::GitUrl;
+
+        let mut candidates = vec!["default".to_owned()];
+        candidates.extend(self.remote_candidates.clone());
+
+        for candidate in candidates {
+            if let Ok(output) = self
+                .process
+                .run_with_formatter(["paths", &candidate], true, |out| {
+                    if let Ok(url) = GitUrl::parse(&out) {
+                        url.fullname
+                    } else {
+                        out
+                    }
+                })
+                .await
+            {
+                if !output.is_empty() {
+                    return Ok(output);
+                }
+            }
+        }
+
+        Err(HgError::ExtractRepoSlugFailed {
+            flavor: self.flavor,
+        }
+        .into())
+    }
+
+    async fn get_touched_files(&self) -> miette::Result<TouchedFiles> {
+        self.exec_status(&[]).await
+    }
+
+    async fn get_touched_files_against_previous_revision(
+        &self,
+        revision: &str,
+    ) -> miette::Result<TouchedFiles> {
+        let revision = if self.is_default_branch(revision) {
+            Cow::Borrowed(".")
+        } else {
+            translate_revision(revision)
+        };
+
+        // Compares against the first parent, or all files
+        // are added when there is no parent
+        self.exec_status(&["--change", &revision]).await
+    }
+
+    async fn get_touched_files_between_revisions(
+        &self,
+        base_revision: &str,
+        revision: &str,
+    ) -> miette::Result<TouchedFiles> {
+        let base_revision = translate_revision(base_revision);
+        let revision = translate_revision(revision);
+        let base = self.get_merge_base(&base_revision, &revision).await;
+
+        self.exec_status(&[
+            "--rev",
+            base.as_ref()
+                .map(|base| base.as_str())
+                .unwrap_or(&base_revision),
+            "--rev",
+            &revision,
+        ])
+        .await
+    }
+
+    async fn get_version(&self) -> miette::Result<Version> {
+        let version = self
+            .process
+            .run_with_formatter(["--version"], true, extract_version)
+            .await?;
+
+        Ok(
+            Version::parse(&version).map_err(|error| HgError::InvalidVersion {
+                flavor: self.flavor,
+                error: Box::new(error),
+            })?,
+        )
+    }
+
+    fn is_default_branch(&self, branch: &str) -> bool {
+        let default_branch = &self.default_branch;
+
+        if default_branch.as_str() == branch {
+            return true;
+        }
+
+        if default_branch.contains('/') {
+            return default_branch.ends_with(&format!("/{branch}"));
+        }
+
+        false
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.hg_root.exists()
+    }
+
+    fn is_ignored(&self, file: &Path) -> bool {
+        if let Some(ignore) = &self.ignore {
+            ignore.is_ignored(&self.repository_root, file)
+        } else {
+            false
+        }
+    }
+
+    async fn is_shallow_checkout(&self) -> miette::Result<bool> {
+        // Shallow clones (via the remotefilelog extension) are
+        // tracked as a requirement of the repository or its store
+        for requires_path in [
+            self.hg_root.join("requires"),
+            self.hg_root.join("store").join("requires"),
+        ] {
+            if !requires_path.exists() {
+                continue;
+            }
+
+            let requires = fs::read_file(&requires_path)?;
+
+            if requires
+                .lines()
+                .any(|line| SHALLOW_REQUIREMENTS.contains(&line.trim()))
+            {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+}
@@ -0,0 +1,534 @@
+use crate::process_cache::ProcessCache;
+use crate::touched_files::TouchedFiles;
+use crate::vcs::Vcs;
+use crate::vcs_utils::*;
+use async_trait::async_trait;
+use ignore::gitignore::Gitignore;
+use miette::Diagnostic;
+use moon_common::path::{RelativePath, RelativePathBuf, WorkspaceRelativePathBuf};
+use moon_common::{Style, Stylize};
+use moon_process::Command;
+use semver::Version;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::{debug, instrument};
+
+#[derive(Error, Debug, Diagnostic)]
+pub enum JujutsuError {
+    #[diagnostic(code(jj::invalid_version))]
+    #[error("Invalid or unsupported jj version.")]
+    InvalidVersion {
+        #[source]
+        error: Box<semver::Error>,
+    },
+
+    #[diagnostic(code(jj::ignore::load_invalid))]
+    #[error("Failed to load and parse {}.", ".gitignore".style(Style::File))]
+    GitignoreLoadFailed {
+        #[source]
+        error: Box<ignore::Error>,
+    },
+
+    #[diagnostic(code(jj::repository::extract_slug))]
+    #[error("Failed to extract a repository slug from jj git remotes.")]
+    ExtractRepoSlugFailed,
+}
+
+#[derive(Debug)]
+pub struct Jujutsu {
+    /// Ignore rules derived from a root `.gitignore` file.
+    ignore: Option<Gitignore>,
+
+    /// Default bookmark name.
+    pub default_branch: Arc<String>,
+
+    /// Root of the `.git` directory, when colocated with git.
+    pub git_root: Option<PathBuf>,
+
+    /// Root of the `.jj` directory.
+    pub jj_root: PathBuf,
+
+    /// Run and cache `jj` commands.
+    pub process: ProcessCache,
+
+    /// List of remotes to use as merge candidates.
+    pub remote_candidates: Vec<String>,
+
+    /// Root of the repository that contains `.jj`.
+    pub repository_root: PathBuf,
+
+    /// Path between the repository and workspace root.
+    pub root_prefix: Option<RelativePathBuf>,
+}
+
+impl Jujutsu {
+    pub fn load<R: AsRef<Path>, B: AsRef<str>>(
+        workspace_root: R,
+        default_branch: B,
+        remote_candidates: &[String],
+    ) -> miette::Result<Jujutsu> {
+        debug!("Using jujutsu as a version control system");
+
+        let workspace_root = workspace_root.as_ref();
+        let (repository_root, jj_root) = find_repository_root(workspace_root, ".jj");
+        let git_root = repository_root.join(".git");
+
+        let ignore = load_gitignore(&repository_root).map_err(|error| {
+            JujutsuError::GitignoreLoadFailed {
+                error: Box::new(error),
+            }
+        })?;
+
+        Ok(Jujutsu {
+            default_branch: Arc::new(default_branch.as_ref().to_owned()),
+            git_root: if git_root.is_dir() {
+                debug!(git = ?git_root, "Colocated with a git repository");
+
+                Some(git_root)
+            } else {
+                None
+            },
+            ignore,
+            jj_root,
+            process: ProcessCache::new("jj", workspace_root),
+            remote_candidates: remote_candidates.to_owned(),
+            root_prefix: create_root_prefix(workspace_root, &repository_root),
+            repository_root,
+        })
+    }
+
+    // Always run from the repository root, so that paths in the
+    // output are consistently relative from the repository root
+    fn create_command<I, A>(&self, args: I) -> Command
+    where
+        I: IntoIterator<Item = A>,
+        A: AsRef<OsStr>,
+    {
+        let mut command = self
+            .process
+            .create_command_in_cwd(["--no-pager", "--color", "never"], &self.repository_root);
+        command.args(args);
+        command
+    }
+
+    async fn run<I, A>(&self, args: I) -> miette::Result<Arc<String>>
+    where
+        I: IntoIterator<Item = A>,
+        A: AsRef<OsStr>,
+    {
+        self.process
+            .run_command(self.create_command(args), true)
+            .await
+    }
+
+    async fn resolve_revision(&self, revision: &str) -> Option<Arc<String>> {
+        let mut candidates = vec![revision.to_owned()];
+
+        for remote in &self.remote_candidates {
+            candidates.push(format!("{revision}@{remote}"));
+        }
+
+        for candidate in candidates {
+            if let Ok(commit) = self
+                .run(["log", "--no-graph", "-r", &candidate, "-T", "commit_id"])
+                .await
+            {
+                if !commit.is_empty() {
+                    return Some(commit);
+                }
+            }
+        }
+
+        None
+    }
+
+    #[instrument(skip(self))]
+    async fn exec_diff(&self, args: &[&str]) -> miette::Result<TouchedFiles> {
+        let mut command = self.create_command(["diff", "--summary"]);
+        command.args(args);
+
+        if let Some(prefix) = &self.root_prefix {
+            command.arg(prefix.as_str());
+        }
+
+        let output = self.process.run_command(command, false).await?;
+
+        Ok(parse_diff_summary(&output, self.root_prefix.as_ref()))
+    }
+}
+
+// https://jj-vcs.github.io/jj/latest/cli-reference/#jj-diff
+//  M file
+//  R dir/{old => new}
+fn parse_diff_summary(output: &str, root_prefix: Option<&RelativePathBuf>) -> TouchedFiles {
+    let mut touched_files = TouchedFiles::default();
+
+    for line in output.lines() {
+        let Some((status, path)) = line.split_once(' ') else {
+            continue;
+        };
+
+        let paths = expand_renamed_path(path);
+
+        for path in paths {
+            let Some(file) = to_workspace_relative_path(root_prefix, &path) else {
+                continue;
+            };
+
+            // Changes are always part of the working copy commit,
+            // so there's no concept of unstaged or untracked files
+            match status {
+                "A" | "C" => {
+                    touched_files.added.insert(file.clone());
+                    touched_files.staged.insert(file);
+                }
+                "D" => {
+                    touched_files.deleted.insert(file.clone());
+                    touched_files.staged.insert(file);
+                }
+                "M" | "R" => {
+                    touched_files.modified.insert(file.clone());
+                    touched_files.staged.insert(file);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    touched_files
+}
+
+// Renames and copies are formatted as `prefix/{old => new}/suffix`
+fn expand_renamed_path(path: &str) -> Vec<String> {
+    let (Some(start), Some(end)) = (path.find('{'), path.rfind('}')) else {
+        return vec![path.to_owned()];
+    };
+
+    let Some((from, to)) = path[start + 1..end].split_once(" => ") else {
+        return vec![path.to_owned()];
+    };
+
+    let prefix = &path[0..start];
+    let suffix = &path[end + 1..];
+
+    [from, to]
+        .into_iter()
+        .map(|part| format!("{prefix}{part}{suffix}").replace("//", "/"))
+        .map(|part| part.trim_start_matches('/').to_owned())
+        .collect()
+}
+
+// Translate Git's `HEAD` (the default head of affected checks) into the
+// working copy revision, for example, `HEAD~2` into `@--`.
+fn translate_revision(revision: &str) -> Cow<'_, str> {
+    match parse_head_revision(revision) {
+        Some(0) => Cow::Borrowed("@"),
+        Some(depth) => Cow::Owned(format!("@{}", "-".repeat(depth))),
+        None => Cow::Borrowed(revision),
+    }
+}
+
+#[async_trait]
+impl Vcs for Jujutsu {
+    fn clear_cache(&self) {
+        self.process.clear();
+    }
+
+    async fn get_local_branch(&self) -> miette::Result<Arc<String>> {
+        // The working copy is rarely bookmarked,
+        // so find the closest bookmarked ancestor
+        let output = self
+            .run([
+                "log",
+                "--no-graph",
+                "-r",
+                "heads(::@ & bookmarks())",
+                "-T",
+                r#"local_bookmarks.map(|b| b.name()).join("\n") ++ "\n""#,
+            ])
+            .await?;
+
+        Ok(Arc::new(
+            output
+                .lines()
+                .map(|line| line.trim())
+                .find(|line| !line.is_empty())
+                .unwrap_or_default()
+                .to_owned(),
+        ))
+    }
+
+    async fn get_local_branch_revision(&self) -> miette::Result<Arc<String>> {
+        self.run(["log", "--no-graph", "-r", "@", "-T", "commit_id"])
+            .await
+    }
+
+    async fn get_default_branch(&self) -> miette::Result<Arc<String>> {
+        Ok(self.default_branch.clone())
+    }
+
+    async fn get_default_branch_revision(&self) -> miette::Result<Arc<String>> {
+        match self.resolve_revision(&self.default_branch).await {
+            Some(commit) => Ok(commit),
+            None => {
+                self.run([
+                    "log",
+                    "--no-graph",
+                    "-r",
+                    self.default_branch.as_str(),
+                    "-T",
+                    "commit_id",
+                ])
+                .await
+            }
+        }
+    }
+
+    #[instrument(skip_all)]
+    async fn get_file_hashes(
+        &self,
+        files: &[WorkspaceRelativePathBuf],
+        allow_ignored: bool,
+    ) -> miette::Result<BTreeMap<WorkspaceRelativePathBuf, String>> {
+        hash_files(&self.process.workspace_root, files, |file| {
+            allow_ignored || !self.is_ignored(file)
+        })
+    }
+
+    #[instrument(skip(self))]
+    async fn get_file_tree(
+        &self,
+        dir: &RelativePath,
+    ) -> miette::Result<Vec<WorkspaceRelativePathBuf>> {
+        let path = match &self.root_prefix {
+            Some(prefix) => prefix.join(dir),
+            None => dir.to_owned(),
+        };
+
+        // All files are tracked automatically, unless ignored
+        let mut command = self.create_command(["file", "list"]);
+
+        if !path.as_str().is_empty() && path.as_str() != "." {
+            command.arg(path.as_str());
+        }
+
+        let output = self.process.run_command(command, false).await?;
+
+        let mut paths = output
+            .lines()
+            .filter_map(|line| {
+                to_workspace_relative_path(self.root_prefix.as_ref(), &line.replace('\\', "/"))
+            })
+            .collect::<Vec<_>>();
+
+        paths.sort();
+
+        Ok(paths)
+    }
+
+    async fn get_hooks_dir(&self) -> miette::Result<PathBuf> {
+        // jj doesn't support hooks, but git commands
+        // will still run them in colocated repositories
+        Ok(match &self.git_root {
+            Some(git_root) => git_root.join("hooks"),
+            None => self.jj_root.join("hooks"),
+        })
+    }
+
+    async fn get_repository_root(&self) -> miette::Result<PathBuf> {
+        Ok(self.repository_root.clone())
+    }
+
+    async fn get_repository_slug(&self) -> miette::Result<Arc<String>> {
+        use git_url_parse::GitUrl;
+
+        let output = self.run(["git", "remote", "list"]).await?;
+
+        // origin https://github.com/moonrepo/moon.git
+        for candidate in &self.remote_candidates {
+            for line in output.lines() {
+                if let Some((name, url)) = line.split_once(' ') {
+                    if name == candidate {
+                        return Ok(Arc::new(match GitUrl::parse(url.trim()) {
+                            Ok(url) => url.fullname,
+                            Err(_) => url.trim().to_owned(),
+                        }));
+                    }
+                }
+            }
+        }
+
+        Err(JujutsuError::ExtractRepoSlugFailed.into())
+    }
+
+    async fn get_touched_files(&self) -> miette::Result<TouchedFiles> {
+        self.exec_diff(&["-r", "@"]).await
+    }
+
+    async fn get_touched_files_against_previous_revision(
+        &self,
+        revision: &str,
+    ) -> miette::Result<TouchedFiles> {
+        let revision = if self.is_default_branch(revision) {
+            Cow::Borrowed("@")
+        } else {
+            translate_revision(revision)
+        };
+
+        // Compares against the parent revisions
+        self.exec_diff(&["-r", &revision]).await
+    }
+
+    async fn get_touched_files_between_revisions(
+        &self,
+        base_revision: &str,
+        revision: &str,
+    ) -> miette::Result<TouchedFiles> {
+        let base_revision = translate_revision(base_revision);
+        let revision = translate_revision(revision);
+        let base = self
+            .resolve_revision(&base_revision)
+            .await
+            .map(|commit| commit.to_string())
+            .unwrap_or_else(|| base_revision.to_string());
+
+        // Compare against the merge base, to support forks
+        self.exec_diff(&[
+            "--from",
+            &format!("heads(::{base} & ::{revision})"),
+            "--to",
+            &revision,
+        ])
+        .await
+    }
+
+    async fn get_version(&self) -> miette::Result<Version> {
+        let version = self
+            .process
+            .run_with_formatter(["--version"], true, extract_version)
+            .await?;
+
+        Ok(
+            Version::parse(&version).map_err(|error| JujutsuError::InvalidVersion {
+                error: Box::new(error),
+            })?,
+        )
+    }
+
+    fn is_default_branch(&self, branch: &str) -> bool {
+        let default_branch = &self.default_branch;
+
+        if default_branch.as_str() == branch {
+            return true;
+        }
+
+        // main@origin
+        if let Some((name, _)) = default_branch.split_once('@') {
+            return name == branch;
+        }
+
+        false
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.jj_root.exists()
+    }
+
+    fn is_ignored(&self, file: &Path) -> bool {
+        if let Some(ignore) = &self.ignore {
+            ignore.matched(file, false).is_ignore()
+        } else {
+            false
+        }
+    }
+
+    async fn is_shallow_checkout(&self) -> miette::Result<bool> {
+        Ok(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn translates_head_revisions() {
+        assert_eq!(translate_revision("HEAD"), "@");
+        assert_eq!(translate_revision("HEAD~1"), "@-");
+        assert_eq!(translate_revision("HEAD~3"), "@---");
+        assert_eq!(translate_revision("HEAD^"), "@-");
+        assert_eq!(translate_revision("main"), "main");
+        assert_eq!(translate_revision("main@origin"), "main@origin");
+    }
+
+    #[test]
+    fn expands_renames() {
+        assert_eq!(expand_renamed_path("a/b.txt"), vec!["a/b.txt"]);
+        assert_eq!(
+            expand_renamed_path("{old.txt => new.txt}"),
+            vec!["old.txt", "new.txt"]
+        );
+        assert_eq!(
+            expand_renamed_path("dir/{a => b}/file.txt"),
+            vec!["dir/a/file.txt", "dir/b/file.txt"]
+        );
+        assert_eq!(
+            expand_renamed_path("dir/{ => sub}/file.txt"),
+            vec!["dir/file.txt", "dir/sub/file.txt"]
+        );
+    }
+
+    #[test]
+    fn parses_diff_summary() {
+        let files = parse_diff_summary(
+            "A added.txt\nD deleted.txt\nM modified.txt\nR {old.txt => new.txt}\n",
+            None,
+        );
+
+        assert!(
+            files
+                .added
+                .contains(&WorkspaceRelativePathBuf::from("added.txt"))
+        );
+        assert!(
+            files
+                .deleted
+                .contains(&WorkspaceRelativePathBuf::from("deleted.txt"))
+        );
+        assert!(
+            files
+                .modified
+                .contains(&WorkspaceRelativePathBuf::from("modified.txt"))
+        );
+        assert!(
+            files
+                .modified
+                .contains(&WorkspaceRelativePathBuf::from("old.txt"))
+        );
+        assert!(
+            files
+                .modified
+                .contains(&WorkspaceRelativePathBuf::from("new.txt"))
+        );
+        assert_eq!(files.staged.len(), 5);
+    }
+
+    #[test]
+    fn filters_files_outside_of_workspace() {
+        let files = parse_diff_summary(
+            "M sub/inside.txt\nM outside.txt\n",
+            Some(&RelativePathBuf::from("sub")),
+        );
+
+        assert_eq!(files.all().len(), 1);
+        assert!(
+            files
+                .modified
+                .contains(&WorkspaceRelativePathBuf::from("inside.txt"))
+        );
+    }
+}
@@ -1,14 +1,19 @@
 mod git;
 mod git_submodule;
 pub mod gitx;
+mod hg;
+mod jujutsu;
 
 mod git_worktree;
 mod process_cache;
 mod touched_files;
 mod vcs;
+mod vcs_utils;
 
 pub use git::*;
 pub use git_worktree::*;
+pub use hg::*;
+pub use jujutsu::*;
 pub use touched_files::*;
 pub use vcs::*;
 
@@ -33,6 +33,10 @@ impl ProcessCache {
         }
     }
 
+    pub fn clear(&self) {
+        self.cache.clear();
+    }
+
     pub fn create_command<I, A>(&self, args: I) -> Command
     where
         I: IntoIterator<Item = A>,
@@ -10,6 +10,10 @@ use std::sync::Arc;
 
 #[async_trait]
 pub trait Vcs: Debug {
+    /// Clear any cached command output, so that subsequent calls reflect the
+    /// current state of the repository. Required for long-lived processes.
+    fn clear_cache(&self) {}
+
     /// Get the local checkout branch name.
     async fn get_local_branch(&self) -> miette::Result<Arc<String>>;
 
@@ -0,0 +1,354 @@
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use miette::IntoDiagnostic;
+use moon_common::path::{RelativePathBuf, WorkspaceRelativePathBuf};
+use regex::Regex;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use tracing::{debug, warn};
+
+pub static VERSION_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(\d+)\.(\d+)(?:\.(\d+))?").unwrap());
+
+/// Extract the first major, minor, and patch version found in the output
+/// of a `--version` command.
+pub fn extract_version(output: String) -> String {
+    match VERSION_PATTERN.captures(&output) {
+        Some(caps) => format!(
+            "{}.{}.{}",
+            &caps[1],
+            &caps[2],
+            caps.get(3).map(|cap| cap.as_str()).unwrap_or("0")
+        ),
+        None => output,
+    }
+}
+
+/// Traverse upwards from the workspace root until a directory is found
+/// that contains the provided VCS directory name (`.hg`, `.jj`, etc).
+/// Returns the repository root and the VCS directory.
+pub fn find_repository_root(workspace_root: &Path, dir_name: &str) -> (PathBuf, PathBuf) {
+    let mut current_dir = workspace_root;
+
+    loop {
+        let check_dir = current_dir.join(dir_name);
+
+        if check_dir.is_dir() {
+            debug!(dir = ?check_dir, "Found a {} directory (repository root)", dir_name);
+
+            return (current_dir.to_path_buf(), check_dir);
+        }
+
+        match current_dir.parent() {
+            Some(parent) => current_dir = parent,
+            None => {
+                debug!(
+                    "Unable to find {}, falling back to workspace root",
+                    dir_name
+                );
+
+                return (workspace_root.to_path_buf(), workspace_root.join(dir_name));
+            }
+        };
+    }
+}
+
+/// Return the path between the repository and workspace root, if they differ.
+pub fn create_root_prefix(
+    workspace_root: &Path,
+    repository_root: &Path,
+) -> Option<RelativePathBuf> {
+    if repository_root == workspace_root {
+        return None;
+    }
+
+    workspace_root
+        .strip_prefix(repository_root)
+        .ok()
+        .and_then(|prefix| RelativePathBuf::from_path(prefix).ok())
+}
+
+/// Convert a repository relative path to a workspace relative path.
+/// If the path is outside of the workspace, returns `None`.
+pub fn to_workspace_relative_path(
+    root_prefix: Option<&RelativePathBuf>,
+    value: &str,
+) -> Option<WorkspaceRelativePathBuf> {
+    let file = WorkspaceRelativePathBuf::from(value);
+
+    match root_prefix {
+        Some(prefix) => file.strip_prefix(prefix).ok().map(|file| file.to_owned()),
+        None => Some(file),
+    }
+}
+
+/// Load ignore rules from a root `.gitignore` file.
+pub fn load_gitignore(repository_root: &Path) -> Result<Option<Gitignore>, ignore::Error> {
+    let ignore_path = repository_root.join(".gitignore");
+
+    if !ignore_path.exists() {
+        return Ok(None);
+    }
+
+    debug!(
+        ignore_file = ?ignore_path,
+        "Loading ignore rules from .gitignore",
+    );
+
+    let mut builder = GitignoreBuilder::new(repository_root);
+
+    if let Some(error) = builder.add(ignore_path) {
+        return Err(error);
+    }
+
+    builder.build().map(Some)
+}
+
+// Syntaxes that can be used as a pattern prefix, or with `syntax:`
+const HGIGNORE_SYNTAXES: [&str; 14] = [
+    "filepath",
+    "glob",
+    "include",
+    "listfile",
+    "listfile0",
+    "path",
+    "re",
+    "regexp",
+    "relglob",
+    "relpath",
+    "relre",
+    "rootfilesin",
+    "rootglob",
+    "subinclude",
+];
+
+/// Ignore rules loaded from a `.hgignore` file, which may contain
+/// both glob and regular expression patterns.
+#[derive(Debug)]
+pub struct HgIgnore {
+    pub globs: Gitignore,
+    pub regexes: Vec<Regex>,
+}
+
+impl HgIgnore {
+    pub fn is_ignored(&self, repository_root: &Path, file: &Path) -> bool {
+        if self.globs.matched(file, false).is_ignore() {
+            return true;
+        }
+
+        if self.regexes.is_empty() {
+            return false;
+        }
+
+        // Regular expressions are not rooted, and are matched against the path
+        // relative from the repository root. When a directory is matched,
+        // all files within it are also ignored.
+        let path = file
+            .strip_prefix(repository_root)
+            .unwrap_or(file)
+            .to_string_lossy()
+            .replace('\\', "/");
+        let mut current = path.as_str();
+
+        loop {
+            if self.regexes.iter().any(|regex| regex.is_match(current)) {
+                return true;
+            }
+
+            match current.rsplit_once('/') {
+                Some((parent, _)) => current = parent,
+                None => return false,
+            };
+        }
+    }
+}
+
+impl From<Gitignore> for HgIgnore {
+    fn from(globs: Gitignore) -> Self {
+        Self {
+            globs,
+            regexes: vec![],
+        }
+    }
+}
+
+/// Load ignore rules from a root `.hgignore` file. Only the glob and regexp
+/// syntaxes are supported, and other patterns are skipped with a warning.
+pub fn load_hgignore(repository_root: &Path) -> Result<Option<HgIgnore>, ignore::Error> {
+    let ignore_path = repository_root.join(".hgignore");
+
+    if !ignore_path.exists() {
+        return Ok(None);
+    }
+
+    debug!(
+        ignore_file = ?ignore_path,
+        "Loading ignore rules from .hgignore",
+    );
+
+    let contents = std::fs::read_to_string(&ignore_path)?;
+    let mut builder = GitignoreBuilder::new(repository_root);
+    let mut regexes = vec![];
+
+    // Mercurial defaults to the regexp syntax
+    let mut default_syntax = "regexp".to_owned();
+
+    for line in contents.lines() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(syntax) = line.strip_prefix("syntax:") {
+            default_syntax = syntax.trim().to_owned();
+            continue;
+        }
+
+        let (syntax, pattern) = match line.split_once(':') {
+            Some((prefix, pattern)) if HGIGNORE_SYNTAXES.contains(&prefix) => (prefix, pattern),
+            _ => (default_syntax.as_str(), line),
+        };
+
+        match syntax {
+            "glob" | "relglob" => {
+                builder.add_line(Some(ignore_path.clone()), pattern)?;
+            }
+            "re" | "regexp" | "relre" => match Regex::new(pattern) {
+                Ok(regex) => regexes.push(regex),
+                Err(error) => {
+                    warn!(
+                        ignore_file = ?ignore_path,
+                        "Failed to parse .hgignore pattern {pattern}, skipping: {error}",
+                    );
+                }
+            },
+            _ => {
+                warn!(
+                    ignore_file = ?ignore_path,
+                    "The {syntax} syntax in .hgignore is not supported, skipping pattern {pattern}",
+                );
+            }
+        };
+    }
+
+    Ok(Some(HgIgnore {
+        globs: builder.build()?,
+        regexes,
+    }))
+}
+
+/// Parse a Git-style `HEAD` revision, with optional `~N` and `^` suffixes, and
+/// return the number of generations it's behind `HEAD`. This is the default head
+/// of affected checks, so non-Git VCS' must translate it into their own syntax.
+pub fn parse_head_revision(revision: &str) -> Option<usize> {
+    let mut suffix = revision.strip_prefix("HEAD")?;
+    let mut depth = 0;
+
+    while !suffix.is_empty() {
+        if let Some(rest) = suffix.strip_prefix('^') {
+            depth += 1;
+            suffix = rest;
+        } else if let Some(rest) = suffix.strip_prefix('~') {
+            let digits = rest
+                .find(|ch: char| !ch.is_ascii_digit())
+                .unwrap_or(rest.len());
+
+            depth += if digits == 0 {
+                1
+            } else {
+                rest[..digits].parse::<usize>().ok()?
+            };
+            suffix = &rest[digits..];
+        } else {
+            return None;
+        }
+    }
+
+    Some(depth)
+}
+
+/// Hash the contents of each file with SHA256, for VCS' that don't provide
+/// a mechanism for hashing arbitrary files. Files that don't exist, or
+/// that have been filtered out, are not hashed.
+pub fn hash_files(
+    workspace_root: &Path,
+    files: &[WorkspaceRelativePathBuf],
+    filter: impl Fn(&Path) -> bool,
+) -> miette::Result<BTreeMap<WorkspaceRelativePathBuf, String>> {
+    let mut map = BTreeMap::new();
+
+    for file in files {
+        let abs_file = workspace_root.join(file.as_str());
+
+        if !abs_file.is_file() || !filter(&abs_file) {
+            continue;
+        }
+
+        let mut hasher = Sha256::new();
+        let mut handle = File::open(&abs_file).into_diagnostic()?;
+
+        io::copy(&mut handle, &mut hasher).into_diagnostic()?;
+
+        map.insert(file.to_owned(), format!("{:x}", hasher.finalize()));
+    }
+
+    Ok(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_versions() {
+        assert_eq!(
+            extract_version("Mercurial Distributed SCM (version 6.7.2)".into()),
+            "6.7.2"
+        );
+        assert_eq!(
+            extract_version("Mercurial Distributed SCM (version 6.7)".into()),
+            "6.7.0"
+        );
+        assert_eq!(
+            extract_version("Sapling 0.2.20240116-133042+8acecb66".into()),
+            "0.2.20240116"
+        );
+        assert_eq!(extract_version("jj 0.25.0-1a2b3c".into()), "0.25.0");
+    }
+
+    #[test]
+    fn parses_head_revisions() {
+        assert_eq!(parse_head_revision("HEAD"), Some(0));
+        assert_eq!(parse_head_revision("HEAD~"), Some(1));
+        assert_eq!(parse_head_revision("HEAD~1"), Some(1));
+        assert_eq!(parse_head_revision("HEAD~12"), Some(12));
+        assert_eq!(parse_head_revision("HEAD^"), Some(1));
+        assert_eq!(parse_head_revision("HEAD^^"), Some(2));
+        assert_eq!(parse_head_revision("HEAD~2^"), Some(3));
+
+        assert_eq!(parse_head_revision("master"), None);
+        assert_eq!(parse_head_revision("HEADS"), None);
+        assert_eq!(parse_head_revision("HEAD@{1}"), None);
+        assert_eq!(parse_head_revision("."), None);
+    }
+
+    #[test]
+    fn converts_to_workspace_relative() {
+        assert_eq!(
+            to_workspace_relative_path(None, "a/b.txt"),
+            Some(WorkspaceRelativePathBuf::from("a/b.txt"))
+        );
+        assert_eq!(
+            to_workspace_relative_path(Some(&RelativePathBuf::from("sub")), "sub/a/b.txt"),
+            Some(WorkspaceRelativePathBuf::from("a/b.txt"))
+        );
+        assert_eq!(
+            to_workspace_relative_path(Some(&RelativePathBuf::from("sub")), "other/b.txt"),
+            None
+        );
+    }
+}
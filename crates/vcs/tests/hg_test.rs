@@ -0,0 +1,403 @@
+use moon_common::path::{RelativePath, RelativePathBuf, WorkspaceRelativePathBuf};
+use moon_vcs::{Hg, HgFlavor, Vcs};
+use rustc_hash::FxHashSet;
+use starbase_sandbox::{Sandbox, create_empty_sandbox, create_sandbox};
+use std::fs;
+use std::process::Command;
+
+fn is_installed(bin: &str) -> bool {
+    Command::new(bin)
+        .arg("--version")
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+fn run_hg(sandbox: &Sandbox, args: &[&str]) {
+    let output = Command::new("hg")
+        .args(args)
+        .current_dir(sandbox.path())
+        .env("HGPLAIN", "1")
+        .env("HGUSER", "moon <moon@moonrepo.dev>")
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+// Tests that execute `hg` are ignored by default, and must be explicitly ran
+// with `--ignored`, so that they can't silently pass when it's not installed
+fn create_hg_sandbox(fixture: &str) -> (Sandbox, Hg) {
+    assert!(
+        is_installed("hg"),
+        "The `hg` binary is required to run this test!"
+    );
+
+    let sandbox = create_sandbox(fixture);
+
+    run_hg(&sandbox, &["init"]);
+    run_hg(&sandbox, &["add"]);
+    run_hg(&sandbox, &["commit", "--message", "Initial"]);
+    run_hg(&sandbox, &["bookmark", "default"]);
+
+    let hg = Hg::load(sandbox.path(), "default", &[], HgFlavor::Mercurial).unwrap();
+
+    (sandbox, hg)
+}
+
+fn create_touched_set<I: IntoIterator<Item = V>, V: AsRef<str>>(
+    files: I,
+) -> FxHashSet<WorkspaceRelativePathBuf> {
+    FxHashSet::from_iter(
+        files
+            .into_iter()
+            .map(|v| WorkspaceRelativePathBuf::from(v.as_ref())),
+    )
+}
+
+mod root_detection {
+    use super::*;
+
+    #[tokio::test]
+    async fn same_dir() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file(".hg/requires", "");
+
+        let hg = Hg::load(sandbox.path(), "default", &[], HgFlavor::Mercurial).unwrap();
+
+        assert_eq!(hg.hg_root, sandbox.path().join(".hg"));
+        assert_eq!(hg.repository_root, sandbox.path());
+        assert_eq!(hg.root_prefix, None);
+        assert!(hg.is_enabled());
+    }
+
+    #[tokio::test]
+    async fn different_dirs() {
+        let sandbox = create_sandbox("nested");
+        sandbox.create_file(".hg/requires", "");
+
+        let hg = Hg::load(
+            sandbox.path().join("frontend"),
+            "default",
+            &[],
+            HgFlavor::Mercurial,
+        )
+        .unwrap();
+
+        assert_eq!(hg.hg_root, sandbox.path().join(".hg"));
+        assert_eq!(hg.repository_root, sandbox.path());
+        assert_eq!(hg.root_prefix, Some(RelativePathBuf::from("frontend")));
+        assert_eq!(
+            hg.get_repository_root().await.unwrap(),
+            sandbox.path().to_path_buf()
+        );
+    }
+
+    #[tokio::test]
+    async fn not_enabled_if_no_dir() {
+        let sandbox = create_empty_sandbox();
+
+        let hg = Hg::load(sandbox.path(), "default", &[], HgFlavor::Mercurial).unwrap();
+
+        assert!(!hg.is_enabled());
+    }
+
+    #[tokio::test]
+    async fn uses_sapling_dir() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file(".sl/requires", "");
+
+        let sl = Hg::load(sandbox.path(), "main", &[], HgFlavor::Sapling).unwrap();
+
+        assert_eq!(sl.hg_root, sandbox.path().join(".sl"));
+        assert_eq!(sl.process.bin, "sl");
+        assert!(sl.is_enabled());
+        assert_eq!(
+            sl.get_hooks_dir().await.unwrap(),
+            sandbox.path().join(".sl/hooks")
+        );
+    }
+}
+
+mod shallow_checkout {
+    use super::*;
+
+    #[tokio::test]
+    async fn not_shallow_by_default() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file(".hg/requires", "dotencode\nfncache\nstore\n");
+
+        let hg = Hg::load(sandbox.path(), "default", &[], HgFlavor::Mercurial).unwrap();
+
+        assert!(!hg.is_shallow_checkout().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn shallow_with_remotefilelog_requirement() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file(
+            ".hg/requires",
+            "dotencode\nexp-remotefilelog-repo-req-1\nstore\n",
+        );
+
+        let hg = Hg::load(sandbox.path(), "default", &[], HgFlavor::Mercurial).unwrap();
+
+        assert!(hg.is_shallow_checkout().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn shallow_with_store_requirement() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file(".sl/requires", "store\n");
+        sandbox.create_file(".sl/store/requires", "remotefilelog\n");
+
+        let sl = Hg::load(sandbox.path(), "main", &[], HgFlavor::Sapling).unwrap();
+
+        assert!(sl.is_shallow_checkout().await.unwrap());
+    }
+}
+
+mod ignore {
+    use super::*;
+
+    #[tokio::test]
+    async fn supports_glob_syntax_in_hgignore() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file(".hg/requires", "");
+        sandbox.create_file(
+            ".hgignore",
+            "syntax: glob\n*.log\nglob:dist\nsyntax: regexp\n^target$\n",
+        );
+
+        let hg = Hg::load(sandbox.path(), "default", &[], HgFlavor::Mercurial).unwrap();
+
+        assert!(hg.is_ignored(&sandbox.path().join("debug.log")));
+        assert!(hg.is_ignored(&sandbox.path().join("dist")));
+        assert!(!hg.is_ignored(&sandbox.path().join("file.txt")));
+    }
+
+    #[tokio::test]
+    async fn supports_regexp_syntax_in_hgignore() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file(".hg/requires", "");
+        sandbox.create_file(
+            ".hgignore",
+            "\\.pyc$\nsyntax: glob\n*.log\nre:^build/\nsyntax: regexp\n^target$\n",
+        );
+
+        let hg = Hg::load(sandbox.path(), "default", &[], HgFlavor::Mercurial).unwrap();
+
+        assert!(hg.is_ignored(&sandbox.path().join("debug.log")));
+        assert!(hg.is_ignored(&sandbox.path().join("app/main.pyc")));
+        assert!(hg.is_ignored(&sandbox.path().join("build/out.js")));
+        assert!(hg.is_ignored(&sandbox.path().join("target")));
+        assert!(hg.is_ignored(&sandbox.path().join("target/debug/app")));
+        assert!(!hg.is_ignored(&sandbox.path().join("app/main.py")));
+        assert!(!hg.is_ignored(&sandbox.path().join("app/build/out.js")));
+        assert!(!hg.is_ignored(&sandbox.path().join("targets")));
+    }
+
+    #[tokio::test]
+    async fn skips_invalid_and_unsupported_patterns_in_hgignore() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file(".hg/requires", "");
+        sandbox.create_file(".hgignore", "(unclosed\npath:vendor\n\\.tmp$\n");
+
+        let hg = Hg::load(sandbox.path(), "default", &[], HgFlavor::Mercurial).unwrap();
+
+        assert!(hg.is_ignored(&sandbox.path().join("file.tmp")));
+        assert!(!hg.is_ignored(&sandbox.path().join("vendor/file.txt")));
+    }
+
+    #[tokio::test]
+    async fn uses_gitignore_for_sapling() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file(".sl/requires", "");
+        sandbox.create_file(".gitignore", "*.log\n");
+
+        let sl = Hg::load(sandbox.path(), "main", &[], HgFlavor::Sapling).unwrap();
+
+        assert!(sl.is_ignored(&sandbox.path().join("debug.log")));
+        assert!(!sl.is_ignored(&sandbox.path().join("file.txt")));
+    }
+}
+
+mod file_hashing {
+    use super::*;
+
+    #[tokio::test]
+    async fn hashes_file_contents() {
+        let sandbox = create_sandbox("vcs");
+        sandbox.create_file(".hg/requires", "");
+        sandbox.create_file("foo/file1.txt", "content");
+        sandbox.create_file("foo/file2.txt", "content");
+        sandbox.create_file("foo/file3.txt", "other");
+
+        let hg = Hg::load(sandbox.path(), "default", &[], HgFlavor::Mercurial).unwrap();
+
+        let hashes = hg
+            .get_file_hashes(
+                &[
+                    "foo/file1.txt".into(),
+                    "foo/file2.txt".into(),
+                    "foo/file3.txt".into(),
+                    "foo/missing.txt".into(),
+                    "foo".into(),
+                ],
+                false,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(hashes.len(), 3);
+        assert_eq!(
+            hashes[&WorkspaceRelativePathBuf::from("foo/file1.txt")],
+            hashes[&WorkspaceRelativePathBuf::from("foo/file2.txt")]
+        );
+        assert_ne!(
+            hashes[&WorkspaceRelativePathBuf::from("foo/file1.txt")],
+            hashes[&WorkspaceRelativePathBuf::from("foo/file3.txt")]
+        );
+    }
+
+    #[tokio::test]
+    async fn ignores_files_when_hashing() {
+        let sandbox = create_sandbox("vcs");
+        sandbox.create_file(".hg/requires", "");
+        sandbox.create_file(".hgignore", "syntax: glob\nfoo/*.txt\n");
+
+        let hg = Hg::load(sandbox.path(), "default", &[], HgFlavor::Mercurial).unwrap();
+        let files = vec![
+            WorkspaceRelativePathBuf::from("foo/file1.txt"),
+            WorkspaceRelativePathBuf::from("baz/file5.txt"),
+        ];
+
+        assert_eq!(
+            hg.get_file_hashes(&files, false)
+                .await
+                .unwrap()
+                .into_keys()
+                .collect::<Vec<_>>(),
+            vec![WorkspaceRelativePathBuf::from("baz/file5.txt")]
+        );
+        assert_eq!(hg.get_file_hashes(&files, true).await.unwrap().len(), 2);
+    }
+}
+
+mod cli {
+    use super::*;
+
+    #[tokio::test]
+    #[ignore = "requires hg"]
+    async fn local_branch_and_revision() {
+        let (_sandbox, hg) = create_hg_sandbox("vcs");
+
+        assert_eq!(hg.get_local_branch().await.unwrap().as_str(), "default");
+        assert_eq!(hg.get_local_branch_revision().await.unwrap().len(), 40);
+        assert_eq!(
+            hg.get_local_branch_revision().await.unwrap(),
+            hg.get_default_branch_revision().await.unwrap()
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "requires hg"]
+    async fn file_tree() {
+        let (sandbox, hg) = create_hg_sandbox("vcs");
+
+        sandbox.create_file("baz/untracked.txt", "");
+
+        assert_eq!(
+            hg.get_file_tree(RelativePath::new("baz")).await.unwrap(),
+            vec![
+                WorkspaceRelativePathBuf::from("baz/dir/file6.txt"),
+                WorkspaceRelativePathBuf::from("baz/file5.txt"),
+                WorkspaceRelativePathBuf::from("baz/untracked.txt"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "requires hg"]
+    async fn touched_files() {
+        let (sandbox, hg) = create_hg_sandbox("vcs");
+
+        sandbox.create_file("added.txt", "");
+        sandbox.create_file("untracked.txt", "");
+        sandbox.create_file("foo/file1.txt", "changed");
+        fs::remove_file(sandbox.path().join("foo/file2.txt")).unwrap();
+        run_hg(&sandbox, &["add", "added.txt"]);
+
+        let touched = hg.get_touched_files().await.unwrap();
+
+        assert_eq!(touched.added, create_touched_set(["added.txt"]));
+        assert_eq!(touched.modified, create_touched_set(["foo/file1.txt"]));
+        assert_eq!(touched.deleted, create_touched_set(["foo/file2.txt"]));
+        assert_eq!(touched.untracked, create_touched_set(["untracked.txt"]));
+        assert_eq!(
+            touched.staged,
+            create_touched_set(["added.txt", "foo/file1.txt"])
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "requires hg"]
+    async fn touched_files_between_revisions() {
+        let (sandbox, hg) = create_hg_sandbox("vcs");
+
+        run_hg(&sandbox, &["bookmark", "feature"]);
+        sandbox.create_file("foo/file1.txt", "changed");
+        sandbox.create_file("added.txt", "");
+        run_hg(&sandbox, &["add", "added.txt"]);
+        run_hg(&sandbox, &["commit", "--message", "Changes"]);
+
+        let touched = hg
+            .get_touched_files_between_revisions("default", "feature")
+            .await
+            .unwrap();
+
+        assert_eq!(touched.added, create_touched_set(["added.txt"]));
+        assert_eq!(touched.modified, create_touched_set(["foo/file1.txt"]));
+
+        let touched = hg
+            .get_touched_files_against_previous_revision("feature")
+            .await
+            .unwrap();
+
+        assert_eq!(touched.all().len(), 2);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires hg"]
+    async fn touched_files_against_head_by_default() {
+        let (sandbox, hg) = create_hg_sandbox("vcs");
+
+        run_hg(&sandbox, &["bookmark", "feature"]);
+        sandbox.create_file("foo/file1.txt", "changed");
+        run_hg(&sandbox, &["commit", "--message", "Changes"]);
+
+        let touched = hg
+            .get_touched_files_between_revisions("default", "HEAD")
+            .await
+            .unwrap();
+
+        assert_eq!(touched.modified, create_touched_set(["foo/file1.txt"]));
+
+        let touched = hg
+            .get_touched_files_between_revisions("default", "HEAD~1")
+            .await
+            .unwrap();
+
+        assert!(touched.all().is_empty());
+
+        let touched = hg
+            .get_touched_files_against_previous_revision("HEAD")
+            .await
+            .unwrap();
+
+        assert_eq!(touched.modified, create_touched_set(["foo/file1.txt"]));
+    }
+}
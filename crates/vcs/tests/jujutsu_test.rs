@@ -0,0 +1,241 @@
+use moon_common::path::{RelativePath, RelativePathBuf, WorkspaceRelativePathBuf};
+use moon_vcs::{Jujutsu, Vcs};
+use rustc_hash::FxHashSet;
+use starbase_sandbox::{Sandbox, create_empty_sandbox, create_sandbox};
+use std::process::Command;
+
+fn run_jj(sandbox: &Sandbox, args: &[&str]) {
+    let output = Command::new("jj")
+        .args(args)
+        .current_dir(sandbox.path())
+        .env("JJ_USER", "moon")
+        .env("JJ_EMAIL", "moon@moonrepo.dev")
+        .output()
+        .expect("The `jj` binary is required to run this test!");
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+// Tests that execute `jj` are ignored by default, and must be explicitly ran
+// with `--ignored`, so that they can't silently pass when it's not installed
+fn create_jj_sandbox(fixture: &str) -> (Sandbox, Jujutsu) {
+    let sandbox = create_sandbox(fixture);
+
+    run_jj(&sandbox, &["git", "init"]);
+    run_jj(&sandbox, &["commit", "--message", "Initial"]);
+    run_jj(
+        &sandbox,
+        &["bookmark", "create", "main", "--revision", "@-"],
+    );
+
+    let jj = Jujutsu::load(sandbox.path(), "main", &[]).unwrap();
+
+    (sandbox, jj)
+}
+
+fn create_touched_set<I: IntoIterator<Item = V>, V: AsRef<str>>(
+    files: I,
+) -> FxHashSet<WorkspaceRelativePathBuf> {
+    FxHashSet::from_iter(
+        files
+            .into_iter()
+            .map(|v| WorkspaceRelativePathBuf::from(v.as_ref())),
+    )
+}
+
+mod root_detection {
+    use super::*;
+
+    #[tokio::test]
+    async fn same_dir() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file(".jj/repo/store/type", "");
+
+        let jj = Jujutsu::load(sandbox.path(), "main", &[]).unwrap();
+
+        assert_eq!(jj.jj_root, sandbox.path().join(".jj"));
+        assert_eq!(jj.repository_root, sandbox.path());
+        assert_eq!(jj.root_prefix, None);
+        assert_eq!(jj.git_root, None);
+        assert!(jj.is_enabled());
+        assert_eq!(
+            jj.get_hooks_dir().await.unwrap(),
+            sandbox.path().join(".jj/hooks")
+        );
+    }
+
+    #[tokio::test]
+    async fn different_dirs() {
+        let sandbox = create_sandbox("nested");
+        sandbox.create_file(".jj/repo/store/type", "");
+
+        let jj = Jujutsu::load(sandbox.path().join("frontend"), "main", &[]).unwrap();
+
+        assert_eq!(jj.jj_root, sandbox.path().join(".jj"));
+        assert_eq!(jj.repository_root, sandbox.path());
+        assert_eq!(jj.root_prefix, Some(RelativePathBuf::from("frontend")));
+    }
+
+    #[tokio::test]
+    async fn detects_colocated_git() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file(".jj/repo/store/type", "");
+        sandbox.create_file(".git/HEAD", "");
+
+        let jj = Jujutsu::load(sandbox.path(), "main", &[]).unwrap();
+
+        assert_eq!(jj.git_root, Some(sandbox.path().join(".git")));
+        assert_eq!(
+            jj.get_hooks_dir().await.unwrap(),
+            sandbox.path().join(".git/hooks")
+        );
+    }
+
+    #[tokio::test]
+    async fn not_enabled_if_no_dir() {
+        let sandbox = create_empty_sandbox();
+
+        let jj = Jujutsu::load(sandbox.path(), "main", &[]).unwrap();
+
+        assert!(!jj.is_enabled());
+    }
+}
+
+mod branches {
+    use super::*;
+
+    #[test]
+    fn matches_default_branch() {
+        let sandbox = create_empty_sandbox();
+
+        let jj = Jujutsu::load(sandbox.path(), "main", &[]).unwrap();
+
+        assert!(jj.is_default_branch("main"));
+        assert!(!jj.is_default_branch("feature"));
+    }
+
+    #[test]
+    fn matches_default_remote_branch() {
+        let sandbox = create_empty_sandbox();
+
+        let jj = Jujutsu::load(sandbox.path(), "main@origin", &[]).unwrap();
+
+        assert!(jj.is_default_branch("main"));
+        assert!(jj.is_default_branch("main@origin"));
+        assert!(!jj.is_default_branch("origin"));
+    }
+}
+
+mod file_hashing {
+    use super::*;
+
+    #[tokio::test]
+    async fn ignores_files_from_gitignore() {
+        let sandbox = create_sandbox("vcs");
+        sandbox.create_file(".jj/repo/store/type", "");
+        sandbox.create_file(".gitignore", "foo/*.txt");
+
+        let jj = Jujutsu::load(sandbox.path(), "main", &[]).unwrap();
+        let files = vec![
+            WorkspaceRelativePathBuf::from("foo/file1.txt"),
+            WorkspaceRelativePathBuf::from("baz/file5.txt"),
+        ];
+
+        assert!(jj.is_ignored(&sandbox.path().join("foo/file1.txt")));
+        assert_eq!(
+            jj.get_file_hashes(&files, false)
+                .await
+                .unwrap()
+                .into_keys()
+                .collect::<Vec<_>>(),
+            vec![WorkspaceRelativePathBuf::from("baz/file5.txt")]
+        );
+    }
+}
+
+mod cli {
+    use super::*;
+
+    #[tokio::test]
+    #[ignore = "requires jj"]
+    async fn file_tree() {
+        let (_sandbox, jj) = create_jj_sandbox("vcs");
+
+        assert_eq!(
+            jj.get_file_tree(RelativePath::new("baz")).await.unwrap(),
+            vec![
+                WorkspaceRelativePathBuf::from("baz/dir/file6.txt"),
+                WorkspaceRelativePathBuf::from("baz/file5.txt"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "requires jj"]
+    async fn touched_files() {
+        let (sandbox, jj) = create_jj_sandbox("vcs");
+
+        sandbox.create_file("added.txt", "");
+        sandbox.create_file("foo/file1.txt", "changed");
+        std::fs::remove_file(sandbox.path().join("foo/file2.txt")).unwrap();
+
+        let touched = jj.get_touched_files().await.unwrap();
+
+        assert_eq!(touched.added, create_touched_set(["added.txt"]));
+        assert_eq!(touched.modified, create_touched_set(["foo/file1.txt"]));
+        assert_eq!(touched.deleted, create_touched_set(["foo/file2.txt"]));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires jj"]
+    async fn touched_files_between_revisions() {
+        let (sandbox, jj) = create_jj_sandbox("vcs");
+
+        sandbox.create_file("foo/file1.txt", "changed");
+        run_jj(&sandbox, &["commit", "--message", "Changes"]);
+        run_jj(
+            &sandbox,
+            &["bookmark", "create", "feature", "--revision", "@-"],
+        );
+
+        let touched = jj
+            .get_touched_files_between_revisions("main", "feature")
+            .await
+            .unwrap();
+
+        assert_eq!(touched.modified, create_touched_set(["foo/file1.txt"]));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires jj"]
+    async fn touched_files_against_head_by_default() {
+        let (sandbox, jj) = create_jj_sandbox("vcs");
+
+        sandbox.create_file("foo/file1.txt", "changed");
+
+        let touched = jj
+            .get_touched_files_between_revisions("main", "HEAD")
+            .await
+            .unwrap();
+
+        assert_eq!(touched.modified, create_touched_set(["foo/file1.txt"]));
+
+        let touched = jj
+            .get_touched_files_between_revisions("main", "HEAD~1")
+            .await
+            .unwrap();
+
+        assert!(touched.all().is_empty());
+
+        let touched = jj
+            .get_touched_files_against_previous_revision("HEAD")
+            .await
+            .unwrap();
+
+        assert_eq!(touched.modified, create_touched_set(["foo/file1.txt"]));
+    }
+}
@@ -1,5 +1,6 @@
-use crate::WorkspaceGraph;
+use crate::{GraphConnections, WorkspaceGraph};
 use moon_common::{Id, color};
+use moon_config::OwnersPaths;
 use moon_project_graph::Project;
 use moon_query::*;
 use std::{fmt::Debug, sync::Arc};
@@ -77,69 +78,7 @@ impl WorkspaceGraph {
         for condition in &query.conditions {
             let matches = match condition {
                 Condition::Field { field, .. } => {
-                    let result = match field {
-                        Field::Language(langs) => condition.matches_enum(langs, &project.language),
-                        Field::Project(ids) => {
-                            if condition.matches(ids, &project.id)? {
-                                Ok(true)
-                            } else if let Some(alias) = &project.alias {
-                                condition.matches(ids, alias)
-                            } else {
-                                Ok(false)
-                            }
-                        }
-                        Field::ProjectAlias(aliases) => {
-                            if let Some(alias) = &project.alias {
-                                condition.matches(aliases, alias)
-                            } else {
-                                Ok(false)
-                            }
-                        }
-                        Field::ProjectName(ids) => condition.matches(ids, &project.id),
-                        Field::ProjectSource(sources) => {
-                            condition.matches(sources, project.source.as_str())
-                        }
-                        Field::ProjectStack(types) => condition.matches_enum(types, &project.stack),
-                        Field::ProjectType(types) => {
-                            condition.matches_enum(types, &project.type_of)
-                        }
-                        Field::Tag(tags) => condition.matches_list(
-                            tags,
-                            &project
-                                .config
-                                .tags
-                                .iter()
-                                .map(|t| t.as_str())
-                                .collect::<Vec<_>>(),
-                        ),
-                        Field::Task(ids) => Ok(project.task_targets.iter().any(|target| {
-                            condition.matches(ids, &target.task_id).unwrap_or_default()
-                        })),
-                        Field::TaskPlatform(ids) | Field::TaskToolchain(ids) => Ok(self
-                            .tasks
-                            .get_all_for_project(&project.id, false)?
-                            .iter()
-                            .any(|task| {
-                                let toolchains = task
-                                    .toolchains
-                                    .iter()
-                                    .map(|t| t.as_str())
-                                    .collect::<Vec<_>>();
-
-                                condition.matches_list(ids, &toolchains).unwrap_or_default()
-                            })),
-                        Field::TaskType(types) => Ok(self
-                            .tasks
-                            .get_all_for_project(&project.id, false)?
-                            .iter()
-                            .any(|task| {
-                                condition
-                                    .matches_enum(types, &task.type_of)
-                                    .unwrap_or_default()
-                            })),
-                    };
-
-                    result?
+                    self.does_project_match_field(project, condition, field)?
                 }
                 Condition::Criteria { criteria } => {
                     self.does_project_match_criteria(project, criteria)?
@@ -166,4 +105,128 @@ impl WorkspaceGraph {
 
         Ok(true)
     }
+
+    pub(crate) fn does_project_match_field(
+        &self,
+        project: &Project,
+        condition: &Condition,
+        field: &Field,
+    ) -> miette::Result<bool> {
+        match field {
+            // The provided project is a dependent of this project
+            Field::DependentOf(ids) => {
+                let dependents = self.projects.dependents_of(project);
+
+                condition.matches_list(ids, &self.get_project_locators(dependents.iter()))
+            }
+            // This project depends on the provided project
+            Field::DependsOn(ids) => condition.matches_list(
+                ids,
+                &self.get_project_locators(project.get_dependency_ids().into_iter()),
+            ),
+            Field::HasOutput(_) | Field::Input(_) | Field::TaskOption(_, _) => {
+                for task in self.tasks.get_all_for_project(&project.id, false)? {
+                    if self.does_task_match_field(&task, condition, field)? {
+                        return Ok(true);
+                    }
+                }
+
+                Ok(false)
+            }
+            Field::Language(langs) => condition.matches_enum(langs, &project.language),
+            Field::Owner(owners) => condition.matches_list(owners, &get_project_owners(project)),
+            Field::Project(ids) => {
+                if condition.matches(ids, &project.id)? {
+                    Ok(true)
+                } else if let Some(alias) = &project.alias {
+                    condition.matches(ids, alias)
+                } else {
+                    Ok(false)
+                }
+            }
+            Field::ProjectAlias(aliases) => {
+                if let Some(alias) = &project.alias {
+                    condition.matches(aliases, alias)
+                } else {
+                    Ok(false)
+                }
+            }
+            Field::ProjectName(ids) => condition.matches(ids, &project.id),
+            Field::ProjectSource(sources) => condition.matches(sources, project.source.as_str()),
+            Field::ProjectStack(types) => condition.matches_enum(types, &project.stack),
+            Field::ProjectType(types) => condition.matches_enum(types, &project.type_of),
+            Field::Tag(tags) => condition.matches_list(
+                tags,
+                &project
+                    .config
+                    .tags
+                    .iter()
+                    .map(|t| t.as_str())
+                    .collect::<Vec<_>>(),
+            ),
+            Field::Task(ids) => Ok(project
+                .task_targets
+                .iter()
+                .any(|target| condition.matches(ids, &target.task_id).unwrap_or_default())),
+            Field::TaskPlatform(ids) | Field::TaskToolchain(ids) => Ok(self
+                .tasks
+                .get_all_for_project(&project.id, false)?
+                .iter()
+                .any(|task| {
+                    let toolchains = task
+                        .toolchains
+                        .iter()
+                        .map(|t| t.as_str())
+                        .collect::<Vec<_>>();
+
+                    condition.matches_list(ids, &toolchains).unwrap_or_default()
+                })),
+            Field::TaskType(types) => Ok(self
+                .tasks
+                .get_all_for_project(&project.id, false)?
+                .iter()
+                .any(|task| {
+                    condition
+                        .matches_enum(types, &task.type_of)
+                        .unwrap_or_default()
+                })),
+        }
+    }
+
+    // Include aliases so that projects can be referenced either way.
+    fn get_project_locators<'id>(&'id self, ids: impl Iterator<Item = &'id Id>) -> Vec<&'id str> {
+        let mut locators = vec![];
+
+        for id in ids {
+            locators.push(id.as_str());
+
+            if let Some(alias) = self
+                .projects
+                .get_unexpanded(id)
+                .ok()
+                .and_then(|project| project.alias.as_ref())
+            {
+                locators.push(alias.as_str());
+            }
+        }
+
+        locators
+    }
+}
+
+fn get_project_owners(project: &Project) -> Vec<&str> {
+    let config = &project.config.owners;
+    let mut owners = vec![];
+
+    if let Some(owner) = &config.default_owner {
+        owners.push(owner.as_str());
+    }
+
+    if let OwnersPaths::Map(map) = &config.paths {
+        for list in map.values() {
+            owners.extend(list.iter().map(|owner| owner.as_str()));
+        }
+    }
+
+    owners
 }
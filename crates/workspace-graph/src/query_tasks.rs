@@ -1,7 +1,10 @@
 use crate::WorkspaceGraph;
+use miette::IntoDiagnostic;
 use moon_common::color;
+use moon_common::path::{WorkspaceRelativePath, WorkspaceRelativePathBuf};
 use moon_query::*;
 use moon_task_graph::{Target, Task};
+use serde_json::Value as JsonValue;
 use std::{fmt::Debug, sync::Arc};
 use tracing::{debug, instrument};
 
@@ -43,7 +46,7 @@ impl WorkspaceGraph {
         // Don't use `get_all` as it recursively calls `query`,
         // which runs into a deadlock! This should be faster also...
         for task in self.tasks.get_all_unexpanded() {
-            if does_task_match_criteria(task, query)? {
+            if self.does_task_match_criteria(task, query)? {
                 targets.push(task.target.clone());
             }
         }
@@ -67,59 +70,170 @@ impl WorkspaceGraph {
 
         Ok(targets)
     }
-}
 
-fn does_task_match_criteria(task: &Task, query: &Criteria) -> miette::Result<bool> {
-    let match_all = matches!(query.op, LogicalOperator::And);
-    let mut matched_any = false;
-
-    for condition in &query.conditions {
-        let matches = match condition {
-            Condition::Field { field, .. } => {
-                let result = match field {
-                    Field::Project(ids) => {
-                        if let Some(project_id) = task.target.get_project_id() {
-                            condition.matches(ids, project_id)
-                        } else {
-                            Ok(false)
-                        }
-                    }
-                    Field::Task(ids) => condition.matches(ids, &task.id),
-                    Field::TaskPlatform(ids) | Field::TaskToolchain(ids) => {
-                        let toolchains = task
-                            .toolchains
-                            .iter()
-                            .map(|t| t.as_str())
-                            .collect::<Vec<_>>();
-
-                        condition.matches_list(ids, &toolchains)
-                    }
-                    Field::TaskType(types) => condition.matches_enum(types, &task.type_of),
-                    _ => Ok(false),
-                };
-
-                result?
+    fn does_task_match_criteria(&self, task: &Task, query: &Criteria) -> miette::Result<bool> {
+        let match_all = matches!(query.op, LogicalOperator::And);
+        let mut matched_any = false;
+
+        for condition in &query.conditions {
+            let matches = match condition {
+                Condition::Field { field, .. } => {
+                    self.does_task_match_field(task, condition, field)?
+                }
+                Condition::Criteria { criteria } => {
+                    self.does_task_match_criteria(task, criteria)?
+                }
+            };
+
+            if matches {
+                matched_any = true;
+
+                if match_all {
+                    continue;
+                } else {
+                    break;
+                }
+            } else if match_all {
+                return Ok(false);
+            }
+        }
+
+        // No matches using the OR condition
+        if !matched_any {
+            return Ok(false);
+        }
+
+        Ok(true)
+    }
+
+    pub(crate) fn does_task_match_field(
+        &self,
+        task: &Task,
+        condition: &Condition,
+        field: &Field,
+    ) -> miette::Result<bool> {
+        match field {
+            // Project based fields are inherited from the owning project
+            Field::DependentOf(_) | Field::DependsOn(_) | Field::Owner(_) => {
+                match task.target.get_project_id() {
+                    Some(project_id) => self.does_project_match_field(
+                        self.projects.get_unexpanded(project_id)?,
+                        condition,
+                        field,
+                    ),
+                    None => Ok(false),
+                }
+            }
+            // Paths are only resolved once the task has been expanded
+            Field::HasOutput(paths) => {
+                let task = self.tasks.get(&task.target)?;
+
+                self.does_task_match_paths(
+                    &task,
+                    condition,
+                    paths,
+                    task.output_files.iter().chain(task.output_globs.iter()),
+                )
+            }
+            Field::Input(paths) => {
+                let task = self.tasks.get(&task.target)?;
+
+                self.does_task_match_paths(
+                    &task,
+                    condition,
+                    paths,
+                    task.input_files.iter().chain(task.input_globs.iter()),
+                )
+            }
+            Field::Project(ids) => {
+                if let Some(project_id) = task.target.get_project_id() {
+                    condition.matches(ids, project_id)
+                } else {
+                    Ok(false)
+                }
+            }
+            Field::Task(ids) => condition.matches(ids, &task.id),
+            Field::TaskOption(option, values) => {
+                let option_values = get_task_option_values(task, option)?;
+
+                condition.matches_list(
+                    values,
+                    &option_values
+                        .iter()
+                        .map(|value| value.as_str())
+                        .collect::<Vec<_>>(),
+                )
+            }
+            Field::TaskPlatform(ids) | Field::TaskToolchain(ids) => {
+                let toolchains = task
+                    .toolchains
+                    .iter()
+                    .map(|t| t.as_str())
+                    .collect::<Vec<_>>();
+
+                condition.matches_list(ids, &toolchains)
             }
-            Condition::Criteria { criteria } => does_task_match_criteria(task, criteria)?,
+            Field::TaskType(types) => condition.matches_enum(types, &task.type_of),
+            _ => Ok(false),
+        }
+    }
+
+    // Paths are matched relative from the workspace root,
+    // and relative from the project root.
+    fn does_task_match_paths<'task>(
+        &self,
+        task: &Task,
+        condition: &Condition,
+        values: &FieldValues,
+        paths: impl Iterator<Item = &'task WorkspaceRelativePathBuf>,
+    ) -> miette::Result<bool> {
+        let source = match task.target.get_project_id() {
+            Some(project_id) => Some(&self.projects.get_unexpanded(project_id)?.source),
+            None => None,
         };
 
-        if matches {
-            matched_any = true;
+        for path in paths {
+            if condition.matches(values, path.as_str())? {
+                return Ok(true);
+            }
 
-            if match_all {
-                continue;
-            } else {
-                break;
+            if let Some(project_path) = source.and_then(|source| strip_source(path, source)) {
+                if condition.matches(values, project_path.as_str())? {
+                    return Ok(true);
+                }
             }
-        } else if match_all {
-            return Ok(false);
         }
+
+        Ok(false)
     }
+}
 
-    // No matches using the OR condition
-    if !matched_any {
-        return Ok(false);
+fn strip_source<'path>(
+    path: &'path WorkspaceRelativePath,
+    source: &WorkspaceRelativePath,
+) -> Option<&'path WorkspaceRelativePath> {
+    if source.as_str().is_empty() || source.as_str() == "." {
+        return None;
     }
 
-    Ok(true)
+    path.strip_prefix(source).ok()
+}
+
+fn get_task_option_values(task: &Task, option: &str) -> miette::Result<Vec<String>> {
+    let options = serde_json::to_value(&task.options).into_diagnostic()?;
+
+    Ok(match options.get(option) {
+        Some(JsonValue::Array(items)) => items.iter().filter_map(stringify_value).collect(),
+        Some(value) => stringify_value(value).into_iter().collect(),
+        None => vec![],
+    })
+}
+
+fn stringify_value(value: &JsonValue) -> Option<String> {
+    match value {
+        JsonValue::Bool(inner) => Some(inner.to_string()),
+        JsonValue::Number(inner) => Some(inner.to_string()),
+        JsonValue::String(inner) => Some(inner.to_owned()),
+        _ => None,
+    }
 }
@@ -15,10 +15,16 @@ use moon_config::{
     ToolchainConfig, WorkspaceConfig, WorkspaceProjects,
 };
 use moon_feature_flags::glob_walk_with_options;
+use moon_pdk_api::CheckProjectConstraintsInput;
 use moon_project::Project;
 use moon_project_builder::{ProjectBuilder, ProjectBuilderContext};
-use moon_project_constraints::{enforce_project_type_relationships, enforce_tag_relationships};
+use moon_project_constraints::{
+    DependencyConstraints, ProjectConstraintsError, enforce_project_type_relationships,
+    enforce_required_tags, enforce_restricted_tags, enforce_tag_relationships,
+    format_dependency_path,
+};
 use moon_project_graph::{ProjectGraph, ProjectGraphError, ProjectMetadata};
+use moon_query::build_query;
 use moon_task::{Target, Task};
 use moon_task_builder::TaskDepsBuilder;
 use moon_task_graph::{GraphExpanderContext, NodeState, TaskGraph, TaskGraphError, TaskMetadata};
@@ -263,7 +269,11 @@ impl<'app> WorkspaceBuilder<'app> {
             Arc::clone(&project_graph),
         ));
 
-        Ok(WorkspaceGraph::new(project_graph, task_graph))
+        let workspace_graph = WorkspaceGraph::new(project_graph, task_graph);
+
+        Self::enforce_graph_constraints(&context, &workspace_graph).await?;
+
+        Ok(workspace_graph)
     }
 
     /// Load a single project by ID or alias into the graph.
@@ -578,13 +588,17 @@ impl<'app> WorkspaceBuilder<'app> {
         debug!("Enforcing project constraints");
 
         let context = self.context();
-        let type_relationships = context
-            .workspace_config
-            .constraints
-            .enforce_project_type_relationships;
-        let tag_relationships = &context.workspace_config.constraints.tag_relationships;
-
-        if !type_relationships && tag_relationships.is_empty() {
+        let constraints = &context.workspace_config.constraints;
+        let type_relationships = constraints.enforce_project_type_relationships;
+        let tag_relationships = &constraints.tag_relationships;
+        let restricted_tags = &constraints.restricted_tags;
+        let required_tags = &constraints.required_tags;
+
+        if !type_relationships
+            && tag_relationships.is_empty()
+            && restricted_tags.is_empty()
+            && required_tags.is_empty()
+        {
             return Ok(());
         }
 
@@ -595,6 +609,8 @@ impl<'app> WorkspaceBuilder<'app> {
                 continue;
             };
 
+            enforce_required_tags(project, required_tags)?;
+
             let deps: Vec<_> = self
                 .project_graph
                 .neighbors_directed(project_index, Direction::Outgoing)
@@ -625,6 +641,109 @@ impl<'app> WorkspaceBuilder<'app> {
                 for (source_tag, required_tags) in tag_relationships {
                     enforce_tag_relationships(project, source_tag, dep, required_tags)?;
                 }
+
+                enforce_restricted_tags(project, dep, dep_scope, restricted_tags)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Enforce project constraints that require the entire graph, like dependency
+    /// chains and queries, and constraints provided by toolchain plugins.
+    #[instrument(skip_all)]
+    async fn enforce_graph_constraints(
+        context: &WorkspaceBuilderContext<'app>,
+        workspace_graph: &WorkspaceGraph,
+    ) -> miette::Result<()> {
+        let constraints = &context.workspace_config.constraints;
+
+        if !constraints.ban_cross_stack_cycles
+            && !constraints.enforce_plugin_constraints
+            && constraints.forbidden_dependencies.is_empty()
+            && constraints.max_dependency_depth.is_none()
+        {
+            return Ok(());
+        }
+
+        debug!("Enforcing project dependency constraints");
+
+        let projects = workspace_graph.projects.get_all_unexpanded();
+        let dependency_constraints = DependencyConstraints::new(projects.iter().copied());
+
+        if constraints.ban_cross_stack_cycles {
+            dependency_constraints.enforce_cross_stack_cycles()?;
+        }
+
+        if let Some(max_depth) = constraints.max_dependency_depth {
+            dependency_constraints.enforce_max_depth(max_depth)?;
+        }
+
+        for rule in &constraints.forbidden_dependencies {
+            let query_ids = |input: &str| -> miette::Result<FxHashSet<Id>> {
+                Ok(workspace_graph
+                    .query_projects(build_query(input)?)?
+                    .into_iter()
+                    .map(|project| project.id.clone())
+                    .collect())
+            };
+
+            dependency_constraints.enforce_forbidden_dependency(
+                rule,
+                &query_ids(&rule.from)?,
+                &query_ids(&rule.to)?,
+            )?;
+        }
+
+        if !constraints.enforce_plugin_constraints {
+            return Ok(());
+        }
+
+        for project in projects {
+            let mut dependency_fragments = vec![];
+
+            for dep_config in &project.dependencies {
+                let dep_project = workspace_graph.projects.get_unexpanded(&dep_config.id)?;
+
+                dependency_fragments.push({
+                    let mut fragment = dep_project.to_fragment();
+                    fragment.dependency_scope = Some(dep_config.scope);
+                    fragment
+                });
+            }
+
+            for result in context
+                .toolchain_registry
+                .check_project_constraints_many(
+                    project.get_enabled_toolchains(),
+                    |registry, toolchain| CheckProjectConstraintsInput {
+                        context: registry.create_context(),
+                        project_dependencies: dependency_fragments.clone(),
+                        project: project.to_fragment(),
+                        toolchain_config: registry.create_merged_config(
+                            &toolchain.id,
+                            context.toolchain_config,
+                            &project.config,
+                        ),
+                    },
+                )
+                .await?
+            {
+                if let Some(violation) = result.output.violations.into_iter().next() {
+                    let mut path = vec![&project.id];
+
+                    if let Some(dep_id) = &violation.dependency_id {
+                        path.push(dep_id);
+                    }
+
+                    return Err(ProjectConstraintsError::PluginViolation {
+                        toolchain: Id::raw(result.id.as_str()),
+                        source_id: project.id.clone(),
+                        message: violation.message,
+                        path: format_dependency_path(&path),
+                    }
+                    .into());
+                }
             }
         }
 
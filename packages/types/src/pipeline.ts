@@ -89,6 +89,23 @@ export interface Operation {
 
 // ACTIONS
 
+export type TestCaseStatus = 'failed' | 'passed' | 'skipped';
+
+export interface TestCase {
+	duration?: Duration;
+	message?: string;
+	name: string;
+	status: TestCaseStatus;
+	suite?: string;
+}
+
+export interface TestReport {
+	failed: number;
+	passed: number;
+	skipped: number;
+	tests: TestCase[];
+}
+
 export interface Action {
 	allowFailure: boolean;
 	/** @deprecated */
@@ -102,8 +119,10 @@ export interface Action {
 	node: ActionNode;
 	nodeIndex: number;
 	operations: Operation[];
+	quarantined: boolean;
 	startedAt: string | null;
 	status: ActionStatus;
+	testReport?: TestReport;
 }
 
 export interface TargetState {
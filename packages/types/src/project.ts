@@ -13,7 +13,9 @@ import type {
 	PlatformType,
 	TaskDependencyConfig,
 	TaskMergeStrategy,
+	TaskOptionTestReportConfig,
 	TaskOutputStyle,
+	TaskSecretConfig,
 	TaskType,
 	TaskUnixShell,
 	TaskWindowsShell,
@@ -42,11 +44,14 @@ export interface TaskOptions {
 	outputStyle: TaskOutputStyle | null;
 	mutex: string | null;
 	persistent: boolean;
+	resources: Record<string, number> | null;
 	retryCount: number;
 	runDepsInParallel: boolean;
 	runInCI: boolean;
 	runFromWorkspaceRoot: boolean;
+	sandbox: boolean | 'warn' | 'error' | null;
 	shell: boolean;
+	testReport: TaskOptionTestReportConfig | null;
 	unixShell: TaskUnixShell | null;
 	windowsShell: TaskWindowsShell | null;
 }
@@ -74,6 +79,7 @@ export interface Task {
 	outputGlobs: string[];
 	platform: PlatformType;
 	script: string | null;
+	secrets?: Record<string, TaskSecretConfig>;
 	state: TaskState;
 	target: string;
 	type: TaskType;
@@ -34,6 +34,28 @@ export type TaskOutputStyle = 'buffer' | 'buffer-only-failure' | 'hash' | 'none'
 /** The priority levels a task can be bucketed into. */
 export type TaskPriority = 'critical' | 'high' | 'normal' | 'low';
 
+/** The format of a test report generated by a task. */
+export type TaskTestReportFormat = 'junit' | 'tap';
+
+/**
+ * Configures a test report that is generated by the task, and parsed
+ * after the task has ran to record the results of each test.
+ */
+export interface TaskOptionTestReportConfig {
+	/**
+	 * The format of the report.
+	 *
+	 * @default 'junit'
+	 * @type {'junit' | 'tap'}
+	 */
+	format: TaskTestReportFormat;
+	/**
+	 * Path (or glob) to the report file(s), relative from the project root,
+	 * or the workspace root when prefixed with `/`.
+	 */
+	path: string;
+}
+
 /** A list of available shells on Unix. */
 export type TaskUnixShell =
 	| 'bash'
@@ -159,6 +181,12 @@ export interface TaskOptionsConfig {
 	 * @default 'normal'
 	 */
 	priority: TaskPriority | null;
+	/**
+	 * Resources required to run the task, used by the pipeline to weight
+	 * concurrency. Supports `cpu` (number of cores), `memory` (in megabytes),
+	 * and custom pools defined in `pipeline.resources`.
+	 */
+	resources: Record<string, number> | null;
 	/**
 	 * The number of times a failing task will be retried to succeed.
 	 *
@@ -174,11 +202,22 @@ export interface TaskOptionsConfig {
 	runFromWorkspaceRoot: boolean | null;
 	/** Whether to run the task in CI or not, when executing `moon ci` or `moon run`. */
 	runInCI: boolean | 'always' | 'affected' | null;
+	/**
+	 * Traces file access while the task is running (Linux only), and
+	 * reports files that were read but not declared as `inputs`, or
+	 * written but not declared as `outputs`.
+	 */
+	sandbox: boolean | 'warn' | 'error' | null;
 	/**
 	 * Runs the task within a shell. When not defined, runs the task
 	 * directly while relying on `PATH` resolution.
 	 */
 	shell: boolean | null;
+	/**
+	 * A test report (JUnit or TAP) generated by the task, that will be
+	 * parsed after the task has ran and attached to the run report.
+	 */
+	testReport: TaskOptionTestReportConfig | null;
 	/** The maximum time in seconds that a task can run before being cancelled. */
 	timeout: number | null;
 	/**
@@ -201,6 +240,30 @@ export type PlatformType = 'bun' | 'deno' | 'node' | 'python' | 'rust' | 'system
 /** Preset options to inherit. */
 export type TaskPreset = 'server' | 'watcher';
 
+/**
+ * Configures where the value of a secret is sourced from. Secrets are
+ * injected as environment variables when the task is ran, but are never
+ * hashed, and are redacted from logs, captured output, and reports.
+ */
+export interface TaskSecretConfig {
+	/** Name of an environment variable on the host machine to read the value from. */
+	env: string | null;
+	/**
+	 * Path to a file to read the value from, relative from the project root,
+	 * or the workspace root when prefixed with `/`.
+	 */
+	file: string | null;
+	/**
+	 * The key to request from the provider. When not provided,
+	 * will use the name of the secret.
+	 */
+	key: string | null;
+	/** Don't fail the task when the value could not be resolved. */
+	optional: boolean;
+	/** ID of an extension that will resolve the value. */
+	provider: string | null;
+}
+
 /** The type of task. */
 export type TaskType = 'build' | 'run' | 'test';
 
@@ -270,6 +333,12 @@ export interface TaskConfig {
 	 * arguments, merging, or inheritance.
 	 */
 	script: string | null;
+	/**
+	 * A mapping of secrets that will be set as environment variables when
+	 * the task is ran. Values are resolved at runtime, and are excluded
+	 * from the task hash and redacted from all output.
+	 */
+	secrets: Record<string, TaskSecretConfig> | null;
 	/**
 	 * The toolchain(s) in which the task will be ran in. The toolchain determines
 	 * available binaries, lookup paths, and more. When not provided, will
@@ -335,6 +404,24 @@ export interface PartialTaskDependencyConfig {
 
 export type PartialTaskDependency = string | PartialTaskDependencyConfig;
 
+/**
+ * Configures a test report that is generated by the task, and parsed
+ * after the task has ran to record the results of each test.
+ */
+export interface PartialTaskOptionTestReportConfig {
+	/**
+	 * The format of the report.
+	 *
+	 * @default 'junit'
+	 */
+	format?: TaskTestReportFormat | null;
+	/**
+	 * Path (or glob) to the report file(s), relative from the project root,
+	 * or the workspace root when prefixed with `/`.
+	 */
+	path?: string | null;
+}
+
 /** Options to control task inheritance and execution. */
 export interface PartialTaskOptionsConfig {
 	/** The pattern in which affected files will be passed to the task. */
@@ -445,6 +532,12 @@ export interface PartialTaskOptionsConfig {
 	 * @default 'normal'
 	 */
 	priority?: TaskPriority | null;
+	/**
+	 * Resources required to run the task, used by the pipeline to weight
+	 * concurrency. Supports `cpu` (number of cores), `memory` (in megabytes),
+	 * and custom pools defined in `pipeline.resources`.
+	 */
+	resources?: Record<string, number> | null;
 	/**
 	 * The number of times a failing task will be retried to succeed.
 	 *
@@ -460,11 +553,22 @@ export interface PartialTaskOptionsConfig {
 	runFromWorkspaceRoot?: boolean | null;
 	/** Whether to run the task in CI or not, when executing `moon ci` or `moon run`. */
 	runInCI?: boolean | 'always' | 'affected' | null;
+	/**
+	 * Traces file access while the task is running (Linux only), and
+	 * reports files that were read but not declared as `inputs`, or
+	 * written but not declared as `outputs`.
+	 */
+	sandbox?: boolean | 'warn' | 'error' | null;
 	/**
 	 * Runs the task within a shell. When not defined, runs the task
 	 * directly while relying on `PATH` resolution.
 	 */
 	shell?: boolean | null;
+	/**
+	 * A test report (JUnit or TAP) generated by the task, that will be
+	 * parsed after the task has ran and attached to the run report.
+	 */
+	testReport?: PartialTaskOptionTestReportConfig | null;
 	/** The maximum time in seconds that a task can run before being cancelled. */
 	timeout?: number | null;
 	/**
@@ -481,6 +585,30 @@ export interface PartialTaskOptionsConfig {
 	windowsShell?: TaskWindowsShell | null;
 }
 
+/**
+ * Configures where the value of a secret is sourced from. Secrets are
+ * injected as environment variables when the task is ran, but are never
+ * hashed, and are redacted from logs, captured output, and reports.
+ */
+export interface PartialTaskSecretConfig {
+	/** Name of an environment variable on the host machine to read the value from. */
+	env?: string | null;
+	/**
+	 * Path to a file to read the value from, relative from the project root,
+	 * or the workspace root when prefixed with `/`.
+	 */
+	file?: string | null;
+	/**
+	 * The key to request from the provider. When not provided,
+	 * will use the name of the secret.
+	 */
+	key?: string | null;
+	/** Don't fail the task when the value could not be resolved. */
+	optional?: boolean | null;
+	/** ID of an extension that will resolve the value. */
+	provider?: string | null;
+}
+
 /** Configures a task to be ran within the action pipeline. */
 export interface PartialTaskConfig {
 	/**
@@ -546,6 +674,12 @@ export interface PartialTaskConfig {
 	 * arguments, merging, or inheritance.
 	 */
 	script?: string | null;
+	/**
+	 * A mapping of secrets that will be set as environment variables when
+	 * the task is ran. Values are resolved at runtime, and are excluded
+	 * from the task hash and redacted from all output.
+	 */
+	secrets?: Record<string, PartialTaskSecretConfig> | null;
 	/**
 	 * The toolchain(s) in which the task will be ran in. The toolchain determines
 	 * available binaries, lookup paths, and more. When not provided, will
@@ -3,6 +3,7 @@
 /* eslint-disable */
 
 import type { ExtendsFrom } from './common';
+import type { ProjectType } from './project-config';
 import type { PluginLocator } from './toolchain-config';
 
 /** How to order ownership rules within the generated file. */
@@ -34,8 +35,39 @@ export interface CodeownersConfig {
 	syncOnRun: boolean;
 }
 
+/**
+ * Forbids dependencies between projects, using query language (MQL)
+ * statements to match the dependent and dependency projects.
+ */
+export interface ForbiddenDependencyConfig {
+	/**
+	 * A query that matches the projects that are not allowed
+	 * to depend on the `to` projects.
+	 */
+	from: string;
+	/** A custom reason that is displayed when the rule is violated. */
+	reason: string | null;
+	/** A query that matches the projects that can not be depended on. */
+	to: string;
+	/**
+	 * Also match dependencies of dependencies, instead of only
+	 * direct dependencies.
+	 */
+	transitive: boolean;
+}
+
 /** Configures boundaries and constraints between projects. */
 export interface ConstraintsConfig {
+	/**
+	 * Disallows dependency cycles between projects that belong
+	 * to different stacks, based on each project's `stack` setting.
+	 */
+	banCrossStackCycles: boolean;
+	/**
+	 * Calls the `check_project_constraints` function of each toolchain
+	 * plugin that a project belongs to, to enforce custom constraints.
+	 */
+	enforcePluginConstraints: boolean;
 	/**
 	 * Enforces relationships between projects based on each project's
 	 * `type` setting.
@@ -43,6 +75,24 @@ export interface ConstraintsConfig {
 	 * @default true
 	 */
 	enforceProjectTypeRelationships?: boolean;
+	/** A list of dependency rules that are forbidden between projects. */
+	forbiddenDependencies: ForbiddenDependencyConfig[];
+	/**
+	 * The maximum depth of a project's dependency chain. A direct
+	 * dependency has a depth of 1.
+	 */
+	maxDependencyDepth: number | null;
+	/**
+	 * Requires projects of a specific `type` to have at least one of
+	 * the configured tags.
+	 */
+	requiredTags: Record<ProjectType, string[]>;
+	/**
+	 * Restricts which projects can depend on a project with a specific
+	 * tag. Requires a mapping of tags, to tags that a dependent project
+	 * must have one of.
+	 */
+	restrictedTags: Record<string, string[]>;
 	/**
 	 * Enforces relationships between projects based on each project's
 	 * `tags` setting. Requires a mapping of tags, to acceptable tags.
@@ -234,6 +284,11 @@ export interface PipelineConfig {
 	 * @default '7 days'
 	 */
 	cacheLifetime?: string;
+	/**
+	 * The maximum size of the cache (like `20GB`). When exceeded, the
+	 * least recently used task hashes and outputs will be evicted.
+	 */
+	cacheMaxSize: string | null;
 	/**
 	 * Automatically inherit color settings for all tasks being ran.
 	 *
@@ -255,6 +310,26 @@ export interface PipelineConfig {
 	killProcessThreshold?: number;
 	/** Logs the task's command and arguments when running the task. */
 	logRunningCommand: boolean;
+	/**
+	 * Report failures of known flaky tasks as warnings, instead of
+	 * failing the pipeline. A task is flaky when it has failed and then
+	 * passed on retry, or passed with an unchanged hash.
+	 */
+	quarantineFlaky: boolean;
+	/**
+	 * The flakiness score (percentage of recent runs that were flaky)
+	 * in which a task is considered flaky and will be quarantined.
+	 *
+	 * @default 10
+	 */
+	quarantineFlakyThreshold: number;
+	/**
+	 * The capacity of resources available to tasks, as required by their
+	 * `options.resources`. The `cpu` resource defaults to the concurrency
+	 * of the pipeline, while `memory` (in megabytes) and custom pools are
+	 * unbounded unless defined.
+	 */
+	resources: Record<string, number>;
 	/**
 	 * When creating `SyncProject` actions, recursively create a `SyncProject`
 	 * action for each project dependency, and link them as a relationship.
@@ -401,6 +476,13 @@ export interface RemoteConfig {
 	auth: RemoteAuthConfig | null;
 	/** Configures the action cache (AC) and content addressable cache (CAS). */
 	cache: RemoteCacheConfig;
+	/**
+	 * Execute tasks on the remote host through the Execution service,
+	 * instead of only caching their outputs. Requires the gRPC API.
+	 *
+	 * @envvar MOON_REMOTE_EXECUTE
+	 */
+	execute: boolean;
 	/**
 	 * The remote host to connect and send requests to.
 	 * Supports gRPC protocols.
@@ -421,7 +503,7 @@ export interface RemoteConfig {
 export type VcsHookFormat = 'bash' | 'native';
 
 /** The VCS being utilized by the repository. */
-export type VcsManager = 'git';
+export type VcsManager = 'git' | 'jujutsu' | 'mercurial' | 'sapling';
 
 /**
  * The upstream version control provider, where the repository
@@ -450,7 +532,7 @@ export interface VcsConfig {
 	 * The VCS client being utilized by the repository.
 	 *
 	 * @default 'git'
-	 * @type {'git'}
+	 * @type {'git' | 'jujutsu' | 'mercurial' | 'sapling'}
 	 */
 	manager: VcsManager;
 	/**
@@ -542,8 +624,39 @@ export interface PartialCodeownersConfig {
 	syncOnRun?: boolean | null;
 }
 
+/**
+ * Forbids dependencies between projects, using query language (MQL)
+ * statements to match the dependent and dependency projects.
+ */
+export interface PartialForbiddenDependencyConfig {
+	/**
+	 * A query that matches the projects that are not allowed
+	 * to depend on the `to` projects.
+	 */
+	from?: string | null;
+	/** A custom reason that is displayed when the rule is violated. */
+	reason?: string | null;
+	/** A query that matches the projects that can not be depended on. */
+	to?: string | null;
+	/**
+	 * Also match dependencies of dependencies, instead of only
+	 * direct dependencies.
+	 */
+	transitive?: boolean | null;
+}
+
 /** Configures boundaries and constraints between projects. */
 export interface PartialConstraintsConfig {
+	/**
+	 * Disallows dependency cycles between projects that belong
+	 * to different stacks, based on each project's `stack` setting.
+	 */
+	banCrossStackCycles?: boolean | null;
+	/**
+	 * Calls the `check_project_constraints` function of each toolchain
+	 * plugin that a project belongs to, to enforce custom constraints.
+	 */
+	enforcePluginConstraints?: boolean | null;
 	/**
 	 * Enforces relationships between projects based on each project's
 	 * `type` setting.
@@ -551,6 +664,24 @@ export interface PartialConstraintsConfig {
 	 * @default true
 	 */
 	enforceProjectTypeRelationships?: boolean | null;
+	/** A list of dependency rules that are forbidden between projects. */
+	forbiddenDependencies?: PartialForbiddenDependencyConfig[] | null;
+	/**
+	 * The maximum depth of a project's dependency chain. A direct
+	 * dependency has a depth of 1.
+	 */
+	maxDependencyDepth?: number | null;
+	/**
+	 * Requires projects of a specific `type` to have at least one of
+	 * the configured tags.
+	 */
+	requiredTags?: Record<ProjectType, string[]> | null;
+	/**
+	 * Restricts which projects can depend on a project with a specific
+	 * tag. Requires a mapping of tags, to tags that a dependent project
+	 * must have one of.
+	 */
+	restrictedTags?: Record<string, string[]> | null;
 	/**
 	 * Enforces relationships between projects based on each project's
 	 * `tags` setting. Requires a mapping of tags, to acceptable tags.
@@ -734,6 +865,11 @@ export interface PartialPipelineConfig {
 	 * @default '7 days'
 	 */
 	cacheLifetime?: string | null;
+	/**
+	 * The maximum size of the cache (like `20GB`). When exceeded, the
+	 * least recently used task hashes and outputs will be evicted.
+	 */
+	cacheMaxSize?: string | null;
 	/**
 	 * Automatically inherit color settings for all tasks being ran.
 	 *
@@ -755,6 +891,26 @@ export interface PartialPipelineConfig {
 	killProcessThreshold?: number | null;
 	/** Logs the task's command and arguments when running the task. */
 	logRunningCommand?: boolean | null;
+	/**
+	 * Report failures of known flaky tasks as warnings, instead of
+	 * failing the pipeline. A task is flaky when it has failed and then
+	 * passed on retry, or passed with an unchanged hash.
+	 */
+	quarantineFlaky?: boolean | null;
+	/**
+	 * The flakiness score (percentage of recent runs that were flaky)
+	 * in which a task is considered flaky and will be quarantined.
+	 *
+	 * @default 10
+	 */
+	quarantineFlakyThreshold?: number | null;
+	/**
+	 * The capacity of resources available to tasks, as required by their
+	 * `options.resources`. The `cpu` resource defaults to the concurrency
+	 * of the pipeline, while `memory` (in megabytes) and custom pools are
+	 * unbounded unless defined.
+	 */
+	resources?: Record<string, number> | null;
 	/**
 	 * When creating `SyncProject` actions, recursively create a `SyncProject`
 	 * action for each project dependency, and link them as a relationship.
@@ -896,6 +1052,13 @@ export interface PartialRemoteConfig {
 	auth?: PartialRemoteAuthConfig | null;
 	/** Configures the action cache (AC) and content addressable cache (CAS). */
 	cache?: PartialRemoteCacheConfig | null;
+	/**
+	 * Execute tasks on the remote host through the Execution service,
+	 * instead of only caching their outputs. Requires the gRPC API.
+	 *
+	 * @envvar MOON_REMOTE_EXECUTE
+	 */
+	execute?: boolean | null;
 	/**
 	 * The remote host to connect and send requests to.
 	 * Supports gRPC protocols.
@@ -105,6 +105,18 @@ const sidebars: SidebarsConfig = {
 				'commands/overview',
 				'commands/action-graph',
 				'commands/bin',
+				{
+					type: 'category',
+					label: 'cache',
+					items: ['commands/cache/serve'],
+					link: {
+						type: 'generated-index',
+						title: 'cache',
+						description: 'Operations for managing the cache.',
+						slug: '/commands/cache',
+						keywords: ['cli', 'commands', 'cache'],
+					},
+				},
 				'commands/check',
 				'commands/ci',
 				'commands/clean',
@@ -128,11 +140,28 @@ const sidebars: SidebarsConfig = {
 				},
 				'commands/ext',
 				'commands/generate',
+				{
+					type: 'category',
+					label: 'graph',
+					items: ['commands/graph/diff'],
+					link: {
+						type: 'generated-index',
+						title: 'graph',
+						description: 'Operations for inspecting and comparing graphs.',
+						slug: '/commands/graph',
+						keywords: ['cli', 'commands', 'graph'],
+					},
+				},
 				'commands/init',
+				'commands/lsp',
 				{
 					type: 'category',
 					label: 'migrate',
-					items: ['commands/migrate/from-package-json', 'commands/migrate/from-turborepo'],
+					items: [
+						'commands/migrate/from-nx',
+						'commands/migrate/from-package-json',
+						'commands/migrate/from-turborepo',
+					],
 					link: {
 						type: 'generated-index',
 						title: 'migrate',
@@ -141,6 +170,7 @@ const sidebars: SidebarsConfig = {
 						keywords: ['cli', 'commands', 'migrate'],
 					},
 				},
+				'commands/owners',
 				'commands/project',
 				'commands/project-graph',
 				{
@@ -186,6 +216,8 @@ const sidebars: SidebarsConfig = {
 				'commands/teardown',
 				'commands/templates',
 				'commands/upgrade',
+				'commands/watch',
+				'commands/why',
 			],
 			link: {
 				type: 'generated-index',